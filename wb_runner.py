@@ -101,7 +101,7 @@ class FileSelector(tk.Frame):
             elif "ExistingFile" in self.parameter_type:
                 ftypes = [('All files', '*.*')]
                 if 'RasterAndVector' in self.file_type:
-                    ftypes = [("Shapefiles", "*.shp"), ('Raster files', ('*.dep', '*.tif',
+                    ftypes = [("Shapefiles", "*.shp"), ("GeoJSON files", ("*.geojson", "*.json")), ("GeoPackage files", "*.gpkg"), ('Raster files', ('*.dep', '*.tif',
                                                 '*.tiff', '*.bil', '*.flt',
                                                 '*.sdat', '*.rdc',
                                                 '*.asc', '*grd'))]
@@ -113,7 +113,7 @@ class FileSelector(tk.Frame):
                 elif 'Lidar' in self.file_type:
                     ftypes = [("LiDAR files", ('*.las', '*.zlidar', '*.laz', '*.zip'))]
                 elif 'Vector' in self.file_type:
-                    ftypes = [("Shapefiles", "*.shp")]
+                    ftypes = [("Shapefiles", "*.shp"), ("GeoJSON files", ("*.geojson", "*.json")), ("GeoPackage files", "*.gpkg")]
                 elif 'Text' in self.file_type:
                     ftypes = [("Text files", "*.txt"), ("all files", "*.*")]
                 elif 'Csv' in self.file_type:
@@ -257,7 +257,7 @@ class FileOrFloat(tk.Frame):
             result = self.value.get()
             ftypes = [('All files', '*.*')]
             if 'RasterAndVector' in self.file_type:
-                    ftypes = [("Shapefiles", "*.shp"), ('Raster files', ('*.dep', '*.tif',
+                    ftypes = [("Shapefiles", "*.shp"), ("GeoJSON files", ("*.geojson", "*.json")), ("GeoPackage files", "*.gpkg"), ('Raster files', ('*.dep', '*.tif',
                                                 '*.tiff', '*.bil', '*.flt',
                                                 '*.sdat', '*.rdc',
                                                 '*.asc'))]
@@ -269,7 +269,7 @@ class FileOrFloat(tk.Frame):
             elif 'Lidar' in self.file_type:
                 ftypes = [("LiDAR files", ('*.las', '*.zlidar', '*.laz', '*.zip'))]
             elif 'Vector' in self.file_type:
-                ftypes = [("Shapefiles", "*.shp")]
+                ftypes = [("Shapefiles", "*.shp"), ("GeoJSON files", ("*.geojson", "*.json")), ("GeoPackage files", "*.gpkg")]
             elif 'Text' in self.file_type:
                 ftypes = [("Text files", "*.txt"), ("all files", "*.*")]
             elif 'Csv' in self.file_type:
@@ -412,7 +412,7 @@ class MultifileSelector(tk.Frame):
             init_dir = self.runner.working_dir
             ftypes = [('All files', '*.*')]
             if 'RasterAndVector' in self.file_type:
-                    ftypes = [("Shapefiles", "*.shp"), ('Raster files', ('*.dep', '*.tif',
+                    ftypes = [("Shapefiles", "*.shp"), ("GeoJSON files", ("*.geojson", "*.json")), ("GeoPackage files", "*.gpkg"), ('Raster files', ('*.dep', '*.tif',
                                                 '*.tiff', '*.bil', '*.flt',
                                                 '*.sdat', '*.rdc',
                                                 '*.asc'))]
@@ -424,7 +424,7 @@ class MultifileSelector(tk.Frame):
             elif 'Lidar' in self.file_type:
                 ftypes = [("LiDAR files", ('*.las', '*.zlidar', '*.laz', '*.zip'))]
             elif 'Vector' in self.file_type:
-                ftypes = [("Shapefiles", "*.shp")]
+                ftypes = [("Shapefiles", "*.shp"), ("GeoJSON files", ("*.geojson", "*.json")), ("GeoPackage files", "*.gpkg")]
             elif 'Text' in self.file_type:
                 ftypes = [("Text files", "*.txt"), ("all files", "*.*")]
             elif 'Csv' in self.file_type:
@@ -0,0 +1,390 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::{polygon_overlay, polygon_union, OverlayOperation};
+use crate::structures::{Point2D, Polygon};
+use std::f64::consts::PI;
+
+/// The shape of the ends of a buffered line, and of a buffered point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CapStyle {
+    /// A semi-circle around the end point; a circle around a point.
+    Round,
+    /// A straight end through the end point; a point has no buffer.
+    Flat,
+    /// A square end, extended beyond the end point by the buffer distance; a square
+    /// around a point.
+    Square,
+}
+
+/// The shape of the corners on the outside of the bends in a line or polygon boundary.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinStyle {
+    /// A circular arc around the vertex.
+    Round,
+    /// The offset edges are extended until they meet, up to the mitre limit.
+    Mitre,
+    /// A straight edge across the corner.
+    Bevel,
+}
+
+/// The parameters that control the shape of a buffer.
+#[derive(Clone, Copy, Debug)]
+pub struct BufferStyle {
+    pub cap_style: CapStyle,
+    pub join_style: JoinStyle,
+    /// The furthest that a mitred corner may extend from its vertex, as a multiple of the
+    /// buffer distance. Sharper corners are cut off at this distance.
+    pub mitre_limit: f64,
+    /// The number of segments used to approximate a quarter circle.
+    pub quadrant_segments: usize,
+    /// The snap tolerance used when the parts of the buffer are merged (see `polygon_overlay`).
+    pub snap_tolerance: f64,
+}
+
+impl Default for BufferStyle {
+    fn default() -> BufferStyle {
+        BufferStyle {
+            cap_style: CapStyle::Round,
+            join_style: JoinStyle::Round,
+            mitre_limit: 5.0,
+            quadrant_segments: 8,
+            snap_tolerance: 0.0,
+        }
+    }
+}
+
+/// Returns the polygons covering the areas within `distance` of a set of points. Points
+/// have no area, so a distance that is not positive returns no polygons.
+pub fn buffer_points(points: &[Point2D], distance: f64, style: &BufferStyle) -> Vec<Polygon> {
+    if distance.is_nan() || distance <= 0f64 {
+        return vec![];
+    }
+    let pieces: Vec<Polygon> = points
+        .iter()
+        .filter_map(|p| point_piece(*p, distance, style))
+        .collect();
+    polygon_union(&pieces, style.snap_tolerance)
+}
+
+/// Returns the polygons covering the areas within `distance` of a set of polylines. A
+/// polyline whose last vertex is the same as its first is treated as a closed ring, without
+/// end caps. Lines have no area, so a distance that is not positive returns no polygons.
+pub fn buffer_lines(lines: &[Vec<Point2D>], distance: f64, style: &BufferStyle) -> Vec<Polygon> {
+    if distance.is_nan() || distance <= 0f64 {
+        return vec![];
+    }
+    let mut pieces = vec![];
+    for line in lines {
+        let closed = line.len() > 3 && line.first() == line.last();
+        add_line_pieces(line, closed, distance, style, &mut pieces);
+    }
+    polygon_union(&pieces, style.snap_tolerance)
+}
+
+/// Returns the polygons covering the areas within `distance` of a set of polygons, holes
+/// included. A negative distance shrinks the polygons instead, removing the areas within
+/// `-distance` of their boundaries, and a distance of zero merges overlapping polygons.
+pub fn buffer_polygons(polygons: &[Polygon], distance: f64, style: &BufferStyle) -> Vec<Polygon> {
+    if distance == 0f64 || !distance.is_finite() {
+        return polygon_union(polygons, style.snap_tolerance);
+    }
+    // The band around the boundaries is added to, or removed from, the polygons.
+    let mut pieces = vec![];
+    for polygon in polygons {
+        for ring in polygon.rings() {
+            add_line_pieces(ring, true, distance.abs(), style, &mut pieces);
+        }
+    }
+    let operation = if distance > 0f64 {
+        OverlayOperation::Union
+    } else {
+        OverlayOperation::Difference
+    };
+    polygon_overlay(polygons, &pieces, operation, style.snap_tolerance)
+}
+
+fn scale(p: Point2D, s: f64) -> Point2D {
+    Point2D::new(p.x * s, p.y * s)
+}
+
+/// The unit normal to the left of a unit direction.
+fn left_normal(u: Point2D) -> Point2D {
+    Point2D::new(-u.y, u.x)
+}
+
+/// Returns the points of an arc around `centre`, excluding its two ends, beginning at the
+/// angle `start` and turning through `sweep` radians (counter-clockwise if positive).
+fn arc_interior(
+    centre: Point2D,
+    radius: f64,
+    start: f64,
+    sweep: f64,
+    style: &BufferStyle,
+) -> Vec<Point2D> {
+    let segments =
+        ((sweep.abs() / (PI / 2f64)) * style.quadrant_segments.max(1) as f64).ceil() as usize;
+    (1..segments.max(1))
+        .map(|i| {
+            let angle = start + sweep * i as f64 / segments as f64;
+            Point2D::new(
+                centre.x + radius * angle.cos(),
+                centre.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+fn point_piece(p: Point2D, distance: f64, style: &BufferStyle) -> Option<Polygon> {
+    if !p.x.is_finite() || !p.y.is_finite() {
+        return None;
+    }
+    match style.cap_style {
+        CapStyle::Round => {
+            let start = Point2D::new(p.x + distance, p.y);
+            let mut circle = vec![start];
+            circle.extend(arc_interior(p, distance, 0f64, 2f64 * PI, style));
+            Some(Polygon::new(circle, vec![]))
+        }
+        CapStyle::Square => Some(Polygon::new(
+            vec![
+                Point2D::new(p.x - distance, p.y - distance),
+                Point2D::new(p.x + distance, p.y - distance),
+                Point2D::new(p.x + distance, p.y + distance),
+                Point2D::new(p.x - distance, p.y + distance),
+            ],
+            vec![],
+        )),
+        CapStyle::Flat => None,
+    }
+}
+
+/// Adds the convex pieces whose union is the buffer of a line: a rectangle around each
+/// segment, a wedge on the outside of each bend, and the end caps.
+fn add_line_pieces(
+    line: &[Point2D],
+    closed: bool,
+    distance: f64,
+    style: &BufferStyle,
+    pieces: &mut Vec<Polygon>,
+) {
+    let mut points: Vec<Point2D> = Vec::with_capacity(line.len());
+    for p in line {
+        if p.x.is_finite() && p.y.is_finite() && points.last() != Some(p) {
+            points.push(*p);
+        }
+    }
+    if closed {
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+    }
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+    if n == 1 {
+        pieces.extend(point_piece(points[0], distance, style));
+        return;
+    }
+    let closed = closed && n > 2;
+    let num_segments = if closed { n } else { n - 1 };
+    let unit = |a: Point2D, b: Point2D| scale(b - a, 1f64 / a.distance(&b));
+    for i in 0..num_segments {
+        let (mut a, mut b) = (points[i], points[(i + 1) % n]);
+        let u = unit(a, b);
+        let offset = scale(left_normal(u), distance);
+        if !closed && style.cap_style == CapStyle::Square {
+            if i == 0 {
+                a = a - scale(u, distance);
+            }
+            if i == num_segments - 1 {
+                b = b + scale(u, distance);
+            }
+        }
+        pieces.push(Polygon::new(
+            vec![a + offset, a - offset, b - offset, b + offset],
+            vec![],
+        ));
+    }
+    let joins = if closed { 0..n } else { 1..n - 1 };
+    for i in joins {
+        let (prev, v, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        pieces.extend(join_piece(unit(prev, v), v, unit(v, next), distance, style));
+    }
+    if !closed && style.cap_style == CapStyle::Round {
+        for (end, inner) in [(points[0], points[1]), (points[n - 1], points[n - 2])] {
+            // a semi-circle turning clockwise from the left of the outward direction
+            let w = unit(inner, end);
+            let side = scale(left_normal(w), distance);
+            let start = side.y.atan2(side.x);
+            let mut cap = vec![end + side];
+            cap.extend(arc_interior(end, distance, start, -PI, style));
+            cap.push(end - side);
+            pieces.push(Polygon::new(cap, vec![]));
+        }
+    }
+}
+
+/// Returns the piece filling the gap on the outside of the bend at vertex `v`, between the
+/// segments with unit directions `u1` (arriving) and `u2` (leaving).
+fn join_piece(
+    u1: Point2D,
+    v: Point2D,
+    u2: Point2D,
+    distance: f64,
+    style: &BufferStyle,
+) -> Option<Polygon> {
+    let turn = u1.cross(u2);
+    if turn.abs() < 1e-12 && u1 * u2 > 0f64 {
+        // no bend
+        return None;
+    }
+    // the outside of a left turn is on the right, and vice versa
+    let side = if turn > 0f64 { -1f64 } else { 1f64 };
+    let n1 = scale(left_normal(u1), side);
+    let n2 = scale(left_normal(u2), side);
+    let (p1, p2) = (v + scale(n1, distance), v + scale(n2, distance));
+    // The bisector of the outside of the bend. (u1 - u2) is the more accurate direction for
+    // sharp bends, including a line that turns back on itself.
+    let (sum, diff) = (n1 + n2, u1 - u2);
+    let bisector = if sum * sum > diff * diff {
+        scale(sum, 1f64 / (sum * sum).sqrt())
+    } else {
+        scale(diff, 1f64 / (diff * diff).sqrt())
+    };
+    let cos_half = n1 * bisector;
+    let sin_half = u1 * bisector;
+    let ring = match style.join_style {
+        JoinStyle::Round => {
+            let sweep = 2f64 * n1.cross(bisector).atan2(cos_half);
+            let mut ring = vec![v, p1];
+            ring.extend(arc_interior(v, distance, n1.y.atan2(n1.x), sweep, style));
+            ring.push(p2);
+            ring
+        }
+        JoinStyle::Mitre if cos_half * style.mitre_limit >= 1f64 => {
+            vec![v, p1, v + scale(bisector, distance / cos_half), p2]
+        }
+        JoinStyle::Mitre if style.mitre_limit > cos_half => {
+            // cut the mitre off square to the bisector, at the mitre limit
+            let e = distance * (style.mitre_limit - cos_half) / sin_half;
+            vec![v, p1, p1 + scale(u1, e), p2 - scale(u2, e), p2]
+        }
+        _ => vec![v, p1, p2],
+    };
+    Some(Polygon::new(ring, vec![]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{buffer_lines, buffer_points, buffer_polygons, BufferStyle, CapStyle, JoinStyle};
+    use crate::structures::{Point2D, Polygon};
+    use std::f64::consts::PI;
+
+    fn total_area(polygons: &[Polygon]) -> f64 {
+        polygons.iter().map(|p| p.area()).sum()
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<Point2D> {
+        vec![
+            Point2D::new(x, y),
+            Point2D::new(x + size, y),
+            Point2D::new(x + size, y + size),
+            Point2D::new(x, y + size),
+            Point2D::new(x, y),
+        ]
+    }
+
+    fn style(cap_style: CapStyle, join_style: JoinStyle) -> BufferStyle {
+        BufferStyle {
+            cap_style,
+            join_style,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_buffer_points() {
+        let points = vec![Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)];
+        let circles = buffer_points(&points, 1.0, &BufferStyle::default());
+        assert_eq!(circles.len(), 2);
+        assert!((total_area(&circles) / (2.0 * PI) - 1.0).abs() < 0.01);
+
+        // overlapping buffers are merged
+        let merged = buffer_points(&points, 6.0, &style(CapStyle::Square, JoinStyle::Round));
+        assert_eq!(merged.len(), 1);
+        assert!((total_area(&merged) - 22.0 * 12.0).abs() < 1e-6);
+
+        assert!(buffer_points(&points, 1.0, &style(CapStyle::Flat, JoinStyle::Round)).is_empty());
+        assert!(buffer_points(&points, -1.0, &BufferStyle::default()).is_empty());
+    }
+
+    #[test]
+    fn test_buffer_line_caps() {
+        let line = vec![vec![Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)]];
+        let flat = buffer_lines(&line, 1.0, &style(CapStyle::Flat, JoinStyle::Round));
+        assert!((total_area(&flat) - 20.0).abs() < 1e-6);
+        let square = buffer_lines(&line, 1.0, &style(CapStyle::Square, JoinStyle::Round));
+        assert!((total_area(&square) - 24.0).abs() < 1e-6);
+        let round = buffer_lines(&line, 1.0, &BufferStyle::default());
+        assert!((total_area(&round) - (20.0 + PI)).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_buffer_line_joins() {
+        let line = vec![vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(10.0, 10.0),
+        ]];
+        let mitre = buffer_lines(&line, 1.0, &style(CapStyle::Flat, JoinStyle::Mitre));
+        assert_eq!(mitre.len(), 1);
+        assert!((total_area(&mitre) - 40.0).abs() < 1e-6);
+        let bevel = buffer_lines(&line, 1.0, &style(CapStyle::Flat, JoinStyle::Bevel));
+        assert!((total_area(&bevel) - 39.5).abs() < 1e-6);
+        let round = buffer_lines(&line, 1.0, &style(CapStyle::Flat, JoinStyle::Round));
+        assert!((total_area(&round) - (39.0 + PI / 4.0)).abs() < 0.01);
+
+        // the mitre extends sqrt(2) times the distance from the vertex, beyond a limit of 1
+        let limited = BufferStyle {
+            mitre_limit: 1.0,
+            ..style(CapStyle::Flat, JoinStyle::Mitre)
+        };
+        let cut = buffer_lines(&line, 1.0, &limited);
+        let corner = 2f64 - 2f64.sqrt();
+        assert!((total_area(&cut) - (40.0 - corner * corner / 2.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_buffer_polygon_with_hole() {
+        let polygon = vec![Polygon::new(square(0.0, 0.0, 10.0), vec![square(3.0, 3.0, 4.0)])];
+        let grown = buffer_polygons(&polygon, 1.0, &style(CapStyle::Round, JoinStyle::Mitre));
+        assert_eq!(grown.len(), 1);
+        assert_eq!(grown[0].holes.len(), 1);
+        assert!((total_area(&grown) - (144.0 - 4.0)).abs() < 1e-6);
+
+        // the hole is closed by a larger buffer
+        let filled = buffer_polygons(&polygon, 2.5, &style(CapStyle::Round, JoinStyle::Mitre));
+        assert!(filled[0].holes.is_empty());
+        assert!((total_area(&filled) - 225.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_negative_buffer() {
+        let polygon = vec![Polygon::new(square(0.0, 0.0, 10.0), vec![square(3.0, 3.0, 4.0)])];
+        let shrunk = buffer_polygons(&polygon, -1.0, &BufferStyle::default());
+        assert_eq!(shrunk.len(), 1);
+        assert_eq!(shrunk[0].holes.len(), 1);
+        // the outer corners stay square, and the corners of the hole are rounded
+        let expected = 64.0 - (16.0 + 4.0 * 4.0 + PI);
+        assert!((total_area(&shrunk) - expected).abs() < 0.05);
+
+        assert!(buffer_polygons(&polygon, -6.0, &BufferStyle::default()).is_empty());
+    }
+}
@@ -6,6 +6,7 @@ Last Modified: 25/09/2018
 License: MIT
 */
 // private sub-module defined in other files
+mod buffer;
 mod convex_hull;
 mod delaunay_triangulation;
 mod is_clockwise_order;
@@ -15,9 +16,14 @@ mod minimum_bounding_box;
 mod poly_area;
 mod poly_ops;
 mod poly_perimeter;
+mod polygon_overlay;
 mod smallest_enclosing_circle;
+mod spatial_relations;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::buffer::{
+    buffer_lines, buffer_points, buffer_polygons, BufferStyle, CapStyle, JoinStyle,
+};
 pub use self::convex_hull::convex_hull;
 pub use self::delaunay_triangulation::{triangulate, Triangulation};
 pub use self::delaunay_triangulation::EMPTY;
@@ -33,4 +39,9 @@ pub use self::poly_ops::{
     interior_point, point_in_poly, poly_in_poly, poly_is_convex, poly_overlaps_poly, winding_number,
 };
 pub use self::poly_perimeter::polygon_perimeter;
+pub use self::polygon_overlay::{
+    locate_on_lines, locate_on_rings, polygon_overlay, polygon_union, OverlayOperation,
+    RingPosition,
+};
 pub use self::smallest_enclosing_circle::smallest_enclosing_circle;
+pub use self::spatial_relations::{map_features, FeatureGeometry, FeatureIndex};
@@ -0,0 +1,1003 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use crate::rstar::primitives::{GeomWithData, Line, Rectangle};
+use crate::rstar::{Envelope, RTree, RTreeObject, AABB};
+use crate::structures::{Point2D, Polygon};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The boolean operations that can be performed by `polygon_overlay`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverlayOperation {
+    /// The areas that are within either set of polygons.
+    Union,
+    /// The areas that are within both sets of polygons.
+    Intersection,
+    /// The areas that are within the first set of polygons but not the second.
+    Difference,
+    /// The areas that are within one of the sets of polygons but not both.
+    SymmetricDifference,
+}
+
+impl OverlayOperation {
+    fn contains(&self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            OverlayOperation::Union => in_a || in_b,
+            OverlayOperation::Intersection => in_a && in_b,
+            OverlayOperation::Difference => in_a && !in_b,
+            OverlayOperation::SymmetricDifference => in_a != in_b,
+        }
+    }
+}
+
+/// Performs a boolean operation on two sets of polygons and returns the resulting polygons.
+///
+/// The polygons within each set may overlap one another; a point is within a set if it is
+/// within any of its polygons. The orientation of the input rings does not matter. The
+/// output exterior rings are clockwise and the holes are counter-clockwise, as they are
+/// stored in Shapefiles, and every ring is closed.
+///
+/// The overlay is robust to self-touching rings and to coincident and nearly coincident
+/// edges. All vertices and edge intersections are snap-rounded to a grid with a cell size
+/// of `snap_tolerance`, so that vertices within this distance of one another, or of an
+/// edge, are merged, and rings that collapse to a single cell are removed. If
+/// `snap_tolerance` is zero, a grid that is fine relative to the magnitude of the
+/// coordinates is used.
+pub fn polygon_overlay(
+    a: &[Polygon],
+    b: &[Polygon],
+    operation: OverlayOperation,
+    snap_tolerance: f64,
+) -> Vec<Polygon> {
+    let grid = match Grid::new(a.iter().chain(b.iter()), snap_tolerance) {
+        Some(grid) => grid,
+        None => return vec![],
+    };
+    let mut segments = vec![];
+    add_segments(a, 0, &grid, &mut segments);
+    add_segments(b, 1, &grid, &mut segments);
+    let segments = snap_round(segments);
+    let graph = Graph::new(&segments);
+    let rings = graph.trace_rings(|w| operation.contains(w[0] > 0, w[1] > 0));
+    assemble_polygons(rings, &grid)
+}
+
+/// Merges a set of possibly overlapping polygons into the polygons that cover their union,
+/// dissolving any shared boundaries. See `polygon_overlay` for the treatment of the rings
+/// and the meaning of `snap_tolerance`.
+pub fn polygon_union(polygons: &[Polygon], snap_tolerance: f64) -> Vec<Polygon> {
+    polygon_overlay(polygons, &[], OverlayOperation::Union, snap_tolerance)
+}
+
+/// The position of a point on the nearest edge of a set of rings, found by `locate_on_rings`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RingPosition {
+    /// The index of the ring.
+    pub ring: usize,
+    /// The index, within the ring, of the edge's first vertex.
+    pub vertex: usize,
+    /// The index, within the ring, of the edge's second vertex.
+    pub next_vertex: usize,
+    /// How far along the edge the point is, from 0 at its first vertex to 1 at its second.
+    pub t: f64,
+    /// The distance from the point to the edge.
+    pub distance: f64,
+}
+
+/// Finds the nearest position on the edges of a set of rings to each of a set of points, or
+/// `None` if the rings have no edges. The rings may be open or closed.
+///
+/// The vertices that are output by `polygon_overlay` lie on the edges of its input rings, to
+/// within the snap tolerance, and so this can be used to interpolate values that are held at
+/// the input vertices, e.g. z and measure values, at the output vertices.
+pub fn locate_on_rings(points: &[Point2D], rings: &[Vec<Point2D>]) -> Vec<Option<RingPosition>> {
+    locate_on_parts(points, rings, true)
+}
+
+/// Finds the nearest position on a set of polylines to each of a set of points, as
+/// `locate_on_rings` does for rings, except that the last vertex of a line is not joined to
+/// its first. The `ring` of each position is the index of the line.
+pub fn locate_on_lines(points: &[Point2D], lines: &[Vec<Point2D>]) -> Vec<Option<RingPosition>> {
+    locate_on_parts(points, lines, false)
+}
+
+fn locate_on_parts(
+    points: &[Point2D],
+    rings: &[Vec<Point2D>],
+    closed: bool,
+) -> Vec<Option<RingPosition>> {
+    let mut edges = vec![];
+    for (r, ring) in rings.iter().enumerate() {
+        let num_edges = if closed || ring.len() == 1 {
+            ring.len()
+        } else {
+            ring.len().saturating_sub(1)
+        };
+        for i in 0..num_edges {
+            let j = (i + 1) % ring.len();
+            if ring[i] != ring[j] || ring.len() == 1 {
+                let line = Line::new([ring[i].x, ring[i].y], [ring[j].x, ring[j].y]);
+                edges.push(GeomWithData::new(line, (r, i, j)));
+            }
+        }
+    }
+    let tree = RTree::bulk_load(edges);
+    points
+        .iter()
+        .map(|p| {
+            tree.nearest_neighbor(&[p.x, p.y]).map(|edge| {
+                let (ring, vertex, next_vertex) = edge.data;
+                let (a, b) = (rings[ring][vertex], rings[ring][next_vertex]);
+                let (d, v) = (b - a, *p - a);
+                let length2 = d.x * d.x + d.y * d.y;
+                let t = if length2 > 0f64 {
+                    ((v.x * d.x + v.y * d.y) / length2).clamp(0f64, 1f64)
+                } else {
+                    0f64
+                };
+                let nearest = Point2D::new(a.x + t * d.x, a.y + t * d.y);
+                RingPosition {
+                    ring,
+                    vertex,
+                    next_vertex,
+                    t,
+                    distance: p.distance(&nearest),
+                }
+            })
+        })
+        .collect()
+}
+
+type IPoint = [i64; 2];
+
+/// The snap-rounding grid, onto which all coordinates are converted to integers.
+struct Grid {
+    x0: f64,
+    y0: f64,
+    size: f64,
+}
+
+impl Grid {
+    fn new<'a>(polygons: impl Iterator<Item = &'a Polygon>, snap_tolerance: f64) -> Option<Grid> {
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for polygon in polygons {
+            for p in polygon.rings().flatten() {
+                if p.x.is_finite() && p.y.is_finite() {
+                    min_x = min_x.min(p.x);
+                    min_y = min_y.min(p.y);
+                    max_x = max_x.max(p.x);
+                    max_y = max_y.max(p.y);
+                }
+            }
+        }
+        if !min_x.is_finite() {
+            return None;
+        }
+        let magnitude = min_x
+            .abs()
+            .max(min_y.abs())
+            .max(max_x.abs())
+            .max(max_y.abs())
+            .max(max_x - min_x)
+            .max(max_y - min_y)
+            .max(1f64);
+        // The grid must keep the integer coordinates well within the precision of an f64.
+        // The default cell size is a power of two, and the grid is aligned on multiples of
+        // it, so that the vertices of an earlier overlay of data in the same area are on
+        // the grid and are not moved again.
+        let size = if snap_tolerance > 0f64 {
+            snap_tolerance
+        } else {
+            2f64.powi((magnitude * 1e-10).log2().floor() as i32)
+        }
+        .max(magnitude * 1e-15);
+        Some(Grid {
+            x0: (min_x / size).floor() * size,
+            y0: (min_y / size).floor() * size,
+            size,
+        })
+    }
+
+    fn snap(&self, p: &Point2D) -> IPoint {
+        [
+            ((p.x - self.x0) / self.size).round() as i64,
+            ((p.y - self.y0) / self.size).round() as i64,
+        ]
+    }
+
+    fn point(&self, p: IPoint) -> Point2D {
+        Point2D::new(
+            self.x0 + p[0] as f64 * self.size,
+            self.y0 + p[1] as f64 * self.size,
+        )
+    }
+}
+
+/// A directed segment of the boundary of one of the operands. The interior of the operand
+/// lies to the left of the segment.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    a: IPoint,
+    b: IPoint,
+    operand: usize,
+}
+
+/// Returns twice the signed area of the triangle (a, b, c), which is positive if c is to
+/// the left of the line from a to b.
+fn orient(a: IPoint, b: IPoint, c: IPoint) -> i128 {
+    (b[0] - a[0]) as i128 * (c[1] - a[1]) as i128 - (b[1] - a[1]) as i128 * (c[0] - a[0]) as i128
+}
+
+fn ring_area2(ring: &[IPoint]) -> i128 {
+    let mut area = 0i128;
+    for i in 0..ring.len() {
+        let (p, q) = (ring[i], ring[(i + 1) % ring.len()]);
+        area += p[0] as i128 * q[1] as i128 - q[0] as i128 * p[1] as i128;
+    }
+    area
+}
+
+fn add_segments(polygons: &[Polygon], operand: usize, grid: &Grid, segments: &mut Vec<Segment>) {
+    for polygon in polygons {
+        for (ring_num, ring) in polygon.rings().enumerate() {
+            let mut points: Vec<IPoint> = Vec::with_capacity(ring.len());
+            for p in ring {
+                if !p.x.is_finite() || !p.y.is_finite() {
+                    continue;
+                }
+                let q = grid.snap(p);
+                if points.last() != Some(&q) {
+                    points.push(q);
+                }
+            }
+            while points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            if points.len() < 3 {
+                continue;
+            }
+            // exteriors are counter-clockwise and holes are clockwise, so that the interior
+            // of the polygon is always to the left of its segments.
+            let area = ring_area2(&points);
+            if area == 0 {
+                continue;
+            }
+            if (ring_num == 0) != (area > 0) {
+                points.reverse();
+            }
+            for i in 0..points.len() {
+                segments.push(Segment {
+                    a: points[i],
+                    b: points[(i + 1) % points.len()],
+                    operand,
+                });
+            }
+        }
+    }
+}
+
+fn envelope(a: IPoint, b: IPoint) -> Rectangle<[f64; 2]> {
+    Rectangle::from_corners([a[0] as f64, a[1] as f64], [b[0] as f64, b[1] as f64])
+}
+
+/// Returns the grid point nearest to the intersection of two segments, if they properly
+/// cross one another. Segments that only touch are handled by the hot pixels at their
+/// endpoints.
+fn crossing(s: &Segment, t: &Segment) -> Option<IPoint> {
+    let o1 = orient(s.a, s.b, t.a).signum();
+    let o2 = orient(s.a, s.b, t.b).signum();
+    let o3 = orient(t.a, t.b, s.a).signum();
+    let o4 = orient(t.a, t.b, s.b).signum();
+    if o1 * o2 >= 0 || o3 * o4 >= 0 {
+        return None;
+    }
+    let (dx, dy) = ((s.b[0] - s.a[0]) as i128, (s.b[1] - s.a[1]) as i128);
+    let (ex, ey) = ((t.b[0] - t.a[0]) as i128, (t.b[1] - t.a[1]) as i128);
+    let (fx, fy) = ((t.a[0] - s.a[0]) as i128, (t.a[1] - s.a[1]) as i128);
+    let param = (fx * ey - fy * ex) as f64 / (dx * ey - dy * ex) as f64;
+    Some([
+        s.a[0] + (param * dx as f64).round() as i64,
+        s.a[1] + (param * dy as f64).round() as i64,
+    ])
+}
+
+/// Tests whether the segment from a to b passes through the (closed) pixel centred on p.
+fn passes_through_pixel(a: IPoint, b: IPoint, p: IPoint) -> bool {
+    // in doubled coordinates, so that the pixel corners are integers
+    let (a2, b2) = ([2 * a[0], 2 * a[1]], [2 * b[0], 2 * b[1]]);
+    let (px, py) = (2 * p[0], 2 * p[1]);
+    if a2[0].min(b2[0]) > px + 1
+        || a2[0].max(b2[0]) < px - 1
+        || a2[1].min(b2[1]) > py + 1
+        || a2[1].max(b2[1]) < py - 1
+    {
+        return false;
+    }
+    let sides = [
+        orient(a2, b2, [px - 1, py - 1]).signum(),
+        orient(a2, b2, [px + 1, py - 1]).signum(),
+        orient(a2, b2, [px + 1, py + 1]).signum(),
+        orient(a2, b2, [px - 1, py + 1]).signum(),
+    ];
+    !(sides.iter().all(|s| *s > 0) || sides.iter().all(|s| *s < 0))
+}
+
+/// Snap-rounds the segments: every endpoint and every intersection is rounded to the grid,
+/// becoming a 'hot pixel', and each segment is rerouted through the centres of the hot
+/// pixels that it passes through. The result is a set of segments that meet only at their
+/// endpoints, although some may coincide. A rerouted segment can pass through another hot
+/// pixel, so the process is repeated for the new segments until nothing changes.
+fn snap_round(mut segments: Vec<Segment>) -> Vec<Segment> {
+    segments.retain(|s| s.a != s.b);
+    // the segments that have not yet been checked against all of the hot pixels
+    let mut unchecked = vec![true; segments.len()];
+    let mut pixel_set: HashSet<IPoint> = HashSet::new();
+    let mut pixel_tree: RTree<[f64; 2]> = RTree::new();
+    for _ in 0..10 {
+        let tree = RTree::bulk_load(
+            segments
+                .iter()
+                .enumerate()
+                .map(|(i, s)| GeomWithData::new(envelope(s.a, s.b), i))
+                .collect(),
+        );
+        let mut new_pixels: Vec<IPoint> = vec![];
+        for (i, s) in segments.iter().enumerate() {
+            if !unchecked[i] {
+                continue;
+            }
+            new_pixels.push(s.a);
+            new_pixels.push(s.b);
+            for candidate in tree.locate_in_envelope_intersecting(&envelope(s.a, s.b).envelope()) {
+                let j = candidate.data;
+                // two checked segments were found not to cross before
+                if j > i || !unchecked[j] {
+                    if let Some(p) = crossing(s, &segments[j]) {
+                        new_pixels.push(p);
+                    }
+                }
+            }
+        }
+        new_pixels.retain(|p| pixel_set.insert(*p));
+        let new_pixel_tree =
+            RTree::bulk_load(new_pixels.iter().map(|p| [p[0] as f64, p[1] as f64]).collect());
+        for p in &new_pixels {
+            pixel_tree.insert([p[0] as f64, p[1] as f64]);
+        }
+
+        // A long diagonal segment has a large envelope, containing many pixels that are far
+        // from it, so it is searched in pieces of about the average spacing of the pixels.
+        let spacing = {
+            let env = pixel_tree.root().envelope();
+            let (lower, upper) = (env.lower(), env.upper());
+            let area = (upper[0] - lower[0]) * (upper[1] - lower[1]);
+            ((area / pixel_set.len().max(1) as f64).sqrt() as i64).max(1)
+        };
+
+        let mut changed = false;
+        let mut rounded = Vec::with_capacity(segments.len());
+        let mut rounded_unchecked = Vec::with_capacity(segments.len());
+        for (i, s) in segments.iter().enumerate() {
+            // a checked segment only needs to be checked against the new pixels
+            let pixels = if unchecked[i] {
+                &pixel_tree
+            } else if !new_pixels.is_empty() {
+                &new_pixel_tree
+            } else {
+                rounded.push(*s);
+                rounded_unchecked.push(false);
+                continue;
+            };
+            let mut stops: Vec<(i128, IPoint)> = vec![];
+            let (dx, dy) = ((s.b[0] - s.a[0]) as i128, (s.b[1] - s.a[1]) as i128);
+            let length = (s.b[0] - s.a[0]).abs().max((s.b[1] - s.a[1]).abs());
+            let num_pieces = (length / spacing).clamp(1, 64);
+            for k in 0..num_pieces {
+                let along = |k: i64| {
+                    [
+                        s.a[0] + (dx * k as i128 / num_pieces as i128) as i64,
+                        s.a[1] + (dy * k as i128 / num_pieces as i128) as i64,
+                    ]
+                };
+                let (p, q) = (along(k), along(k + 1));
+                let env = AABB::from_corners(
+                    [p[0].min(q[0]) as f64 - 1f64, p[1].min(q[1]) as f64 - 1f64],
+                    [p[0].max(q[0]) as f64 + 1f64, p[1].max(q[1]) as f64 + 1f64],
+                );
+                for pixel in pixels.locate_in_envelope(&env) {
+                    let pixel = [pixel[0] as i64, pixel[1] as i64];
+                    if pixel != s.a && pixel != s.b && passes_through_pixel(s.a, s.b, pixel) {
+                        let along =
+                            (pixel[0] - s.a[0]) as i128 * dx + (pixel[1] - s.a[1]) as i128 * dy;
+                        stops.push((along, pixel));
+                    }
+                }
+            }
+            if stops.is_empty() {
+                rounded.push(*s);
+                rounded_unchecked.push(false);
+                continue;
+            }
+            changed = true;
+            stops.sort();
+            stops.dedup();
+            let mut start = s.a;
+            for (_, p) in stops.into_iter().chain(std::iter::once((0, s.b))) {
+                if p != start {
+                    rounded.push(Segment {
+                        a: start,
+                        b: p,
+                        operand: s.operand,
+                    });
+                    rounded_unchecked.push(true);
+                    start = p;
+                }
+            }
+        }
+        segments = rounded;
+        unchecked = rounded_unchecked;
+        if !changed {
+            break;
+        }
+    }
+    segments
+}
+
+/// The planar graph of the snap-rounded segments, stored as half-edges. Half-edges 2e and
+/// 2e + 1 are the two directions of edge e, and the face of a half-edge lies to its left.
+struct Graph {
+    vertices: Vec<IPoint>,
+    /// The origin vertex of each half-edge.
+    origin: Vec<usize>,
+    /// The change in the winding number of each operand across each edge, from its right
+    /// side to its left side when it is traversed as half-edge 2e.
+    deltas: Vec<[i32; 2]>,
+    /// The half-edge that follows each half-edge around its face.
+    next: Vec<usize>,
+    /// The face of each half-edge.
+    face: Vec<usize>,
+    /// The winding number of each operand within each face.
+    windings: Vec<[i32; 2]>,
+}
+
+impl Graph {
+    fn new(segments: &[Segment]) -> Graph {
+        // merge coincident segments, summing their contributions to the winding numbers
+        let mut vertex_map: HashMap<IPoint, usize> = HashMap::new();
+        let mut vertices: Vec<IPoint> = vec![];
+        let mut edge_map: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut edges: Vec<(usize, usize)> = vec![];
+        let mut deltas: Vec<[i32; 2]> = vec![];
+        for s in segments {
+            let mut index = |p: IPoint| {
+                *vertex_map.entry(p).or_insert_with(|| {
+                    vertices.push(p);
+                    vertices.len() - 1
+                })
+            };
+            let (u, v) = (index(s.a), index(s.b));
+            let (key, sign) = if u < v { ((u, v), 1) } else { ((v, u), -1) };
+            let e = *edge_map.entry(key).or_insert_with(|| {
+                edges.push(key);
+                deltas.push([0, 0]);
+                edges.len() - 1
+            });
+            deltas[e][s.operand] += sign;
+        }
+        // edges that are cancelled out, e.g. the shared boundary of two adjacent polygons
+        // in a dissolve, do not separate areas with different winding numbers.
+        let keep: Vec<usize> = (0..edges.len())
+            .filter(|&e| deltas[e] != [0, 0])
+            .collect();
+        let mut origin = Vec::with_capacity(2 * keep.len());
+        for &e in &keep {
+            origin.push(edges[e].0);
+            origin.push(edges[e].1);
+        }
+        let deltas: Vec<[i32; 2]> = keep.iter().map(|&e| deltas[e]).collect();
+
+        // sort the half-edges leaving each vertex counter-clockwise
+        let mut outgoing: Vec<Vec<usize>> = vec![vec![]; vertices.len()];
+        for h in 0..origin.len() {
+            outgoing[origin[h]].push(h);
+        }
+        let direction = |h: usize| {
+            let (p, q) = (vertices[origin[h]], vertices[origin[h ^ 1]]);
+            [q[0] - p[0], q[1] - p[1]]
+        };
+        let mut position = vec![0usize; origin.len()];
+        for list in outgoing.iter_mut() {
+            list.sort_by(|&h1, &h2| compare_directions(direction(h1), direction(h2)));
+            for (i, &h) in list.iter().enumerate() {
+                position[h] = i;
+            }
+        }
+        // the next half-edge around a face is the one that is clockwise-adjacent to the
+        // twin at the end vertex
+        let next: Vec<usize> = (0..origin.len())
+            .map(|h| {
+                let twin = h ^ 1;
+                let list = &outgoing[origin[twin]];
+                list[(position[twin] + list.len() - 1) % list.len()]
+            })
+            .collect();
+
+        let mut graph = Graph {
+            vertices,
+            origin,
+            deltas,
+            next,
+            face: vec![],
+            windings: vec![],
+        };
+        graph.find_windings();
+        graph
+    }
+
+    fn delta(&self, h: usize) -> [i32; 2] {
+        let d = self.deltas[h / 2];
+        if h % 2 == 0 {
+            d
+        } else {
+            [-d[0], -d[1]]
+        }
+    }
+
+    /// Identifies the faces of the graph and the winding numbers of the operands within them.
+    fn find_windings(&mut self) {
+        let num_half_edges = self.origin.len();
+        let mut face = vec![usize::MAX; num_half_edges];
+        let mut face_start = vec![];
+        let mut face_area = vec![];
+        for h in 0..num_half_edges {
+            if face[h] != usize::MAX {
+                continue;
+            }
+            let f = face_start.len();
+            let mut area = 0i128;
+            let mut g = h;
+            loop {
+                face[g] = f;
+                let (p, q) = (self.vertices[self.origin[g]], self.vertices[self.origin[g ^ 1]]);
+                area += p[0] as i128 * q[1] as i128 - q[0] as i128 * p[1] as i128;
+                g = self.next[g];
+                if g == h {
+                    break;
+                }
+            }
+            face_start.push(h);
+            face_area.push(area);
+        }
+
+        // the connected components of the graph
+        let mut parent: Vec<usize> = (0..self.vertices.len()).collect();
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for e in 0..num_half_edges / 2 {
+            let (u, v) = (
+                find(&mut parent, self.origin[2 * e]),
+                find(&mut parent, self.origin[2 * e + 1]),
+            );
+            parent[u] = v;
+        }
+        let component: Vec<usize> = (0..self.vertices.len())
+            .map(|v| find(&mut parent, v))
+            .collect();
+
+        // Each component has a single clockwise face, its outer boundary, and its lowest-left
+        // vertex lies on this boundary. Its winding numbers are found by casting a ray from
+        // this vertex through the edges of the other components; those of the remaining
+        // faces of the component then follow from the changes across its edges.
+        let mut outer_face: HashMap<usize, usize> = HashMap::new();
+        for f in 0..face_start.len() {
+            let c = component[self.origin[face_start[f]]];
+            let outer = outer_face.entry(c).or_insert(f);
+            if face_area[f] < face_area[*outer] {
+                *outer = f;
+            }
+        }
+        let mut lowest_left: HashMap<usize, IPoint> = HashMap::new();
+        for (v, p) in self.vertices.iter().enumerate() {
+            let lowest = lowest_left.entry(component[v]).or_insert(*p);
+            if p < lowest {
+                *lowest = *p;
+            }
+        }
+        let edge_tree = RTree::bulk_load(
+            (0..num_half_edges / 2)
+                .map(|e| {
+                    let (p, q) = (
+                        self.vertices[self.origin[2 * e]],
+                        self.vertices[self.origin[2 * e + 1]],
+                    );
+                    GeomWithData::new(envelope(p, q), e)
+                })
+                .collect(),
+        );
+        let max_x = self.vertices.iter().map(|p| p[0]).max().unwrap_or(0) as f64;
+
+        let mut windings = vec![[0i32; 2]; face_start.len()];
+        let mut known = vec![false; face_start.len()];
+        let mut components: Vec<usize> = outer_face.keys().copied().collect();
+        components.sort();
+        for c in components {
+            let p = lowest_left[&c];
+            let ray = AABB::from_corners([p[0] as f64, p[1] as f64], [max_x, p[1] as f64]);
+            let mut w = [0i32; 2];
+            for candidate in edge_tree.locate_in_envelope_intersecting(&ray) {
+                let e = candidate.data;
+                if component[self.origin[2 * e]] == c {
+                    continue;
+                }
+                let (a, b) = (
+                    self.vertices[self.origin[2 * e]],
+                    self.vertices[self.origin[2 * e + 1]],
+                );
+                let d = self.deltas[e];
+                if a[1] <= p[1] && b[1] > p[1] && orient(a, b, p) > 0 {
+                    w[0] += d[0];
+                    w[1] += d[1];
+                } else if b[1] <= p[1] && a[1] > p[1] && orient(a, b, p) < 0 {
+                    w[0] -= d[0];
+                    w[1] -= d[1];
+                }
+            }
+            let outer = outer_face[&c];
+            windings[outer] = w;
+            known[outer] = true;
+            let mut queue = VecDeque::new();
+            queue.push_back(outer);
+            while let Some(f) = queue.pop_front() {
+                let mut h = face_start[f];
+                loop {
+                    let g = face[h ^ 1];
+                    if !known[g] {
+                        let d = self.delta(h);
+                        windings[g] = [windings[f][0] - d[0], windings[f][1] - d[1]];
+                        known[g] = true;
+                        queue.push_back(g);
+                    }
+                    h = self.next[h];
+                    if h == face_start[f] {
+                        break;
+                    }
+                }
+            }
+        }
+        self.face = face;
+        self.windings = windings;
+    }
+
+    /// Traces the rings that separate the faces selected by `inside` from the others. The
+    /// selected faces are to the left of the rings, i.e. exteriors are counter-clockwise.
+    fn trace_rings<F: Fn([i32; 2]) -> bool>(&self, inside: F) -> Vec<Vec<IPoint>> {
+        let num_half_edges = self.origin.len();
+        let boundary: Vec<bool> = (0..num_half_edges)
+            .map(|h| {
+                inside(self.windings[self.face[h]]) && !inside(self.windings[self.face[h ^ 1]])
+            })
+            .collect();
+        let mut used = vec![false; num_half_edges];
+        let mut rings = vec![];
+        for start in 0..num_half_edges {
+            if !boundary[start] || used[start] {
+                continue;
+            }
+            let mut ring = vec![];
+            let mut h = start;
+            loop {
+                used[h] = true;
+                ring.push(self.vertices[self.origin[h]]);
+                // turn as sharply as possible, so that rings that touch at a vertex are kept apart
+                let mut n = self.next[h];
+                while !boundary[n] {
+                    n = self.next[n ^ 1];
+                }
+                h = n;
+                if h == start || used[h] {
+                    break;
+                }
+            }
+            remove_collinear_vertices(&mut ring);
+            // rings that snapping has collapsed to a single grid cell are dropped
+            if ring.len() >= 3 && ring_area2(&ring).abs() > 2 {
+                rings.push(ring);
+            }
+        }
+        rings
+    }
+}
+
+/// Orders directions counter-clockwise, beginning from the positive x axis.
+fn compare_directions(d1: IPoint, d2: IPoint) -> std::cmp::Ordering {
+    let half = |d: IPoint| if d[1] > 0 || (d[1] == 0 && d[0] > 0) { 0 } else { 1 };
+    half(d1)
+        .cmp(&half(d2))
+        .then_with(|| 0.cmp(&orient([0, 0], d1, d2)))
+}
+
+fn remove_collinear_vertices(ring: &mut Vec<IPoint>) {
+    let mut i = 0;
+    while ring.len() >= 3 && i < ring.len() {
+        let n = ring.len();
+        let (p, q, r) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        let forward = (q[0] - p[0]) as i128 * (r[0] - q[0]) as i128
+            + (q[1] - p[1]) as i128 * (r[1] - q[1]) as i128;
+        if orient(p, q, r) == 0 && forward > 0 {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum Location {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+fn locate(p: IPoint, ring: &[IPoint]) -> Location {
+    let mut wn = 0;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        let o = orient(a, b, p);
+        if o == 0
+            && p[0] >= a[0].min(b[0])
+            && p[0] <= a[0].max(b[0])
+            && p[1] >= a[1].min(b[1])
+            && p[1] <= a[1].max(b[1])
+        {
+            return Location::Boundary;
+        }
+        if a[1] <= p[1] {
+            if b[1] > p[1] && o > 0 {
+                wn += 1;
+            }
+        } else if b[1] <= p[1] && o < 0 {
+            wn -= 1;
+        }
+    }
+    if wn != 0 {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Assigns each hole (clockwise ring) to the smallest exterior (counter-clockwise ring)
+/// that contains it and converts the polygons back to real-world coordinates.
+fn assemble_polygons(rings: Vec<Vec<IPoint>>, grid: &Grid) -> Vec<Polygon> {
+    let mut exteriors: Vec<(Vec<IPoint>, i128)> = vec![];
+    let mut holes: Vec<Vec<IPoint>> = vec![];
+    for ring in rings {
+        let area = ring_area2(&ring);
+        if area > 0 {
+            exteriors.push((ring, area));
+        } else if area < 0 {
+            holes.push(ring);
+        }
+    }
+    let bounds = |ring: &[IPoint]| {
+        let mut env = AABB::from_point([ring[0][0] as f64, ring[0][1] as f64]);
+        for p in ring {
+            env.merge(&AABB::from_point([p[0] as f64, p[1] as f64]));
+        }
+        env
+    };
+    let tree = RTree::bulk_load(
+        exteriors
+            .iter()
+            .enumerate()
+            .map(|(i, (ring, _))| GeomWithData::new(Rectangle::from_aabb(bounds(ring)), i))
+            .collect(),
+    );
+    let mut polygon_holes: Vec<Vec<Vec<IPoint>>> = vec![vec![]; exteriors.len()];
+    for hole in holes {
+        let env = bounds(&hole);
+        let mut container: Option<usize> = None;
+        for candidate in tree.locate_in_envelope_intersecting(&env) {
+            let i = candidate.data;
+            if container.is_some_and(|c| exteriors[c].1 <= exteriors[i].1) {
+                continue;
+            }
+            let location = hole
+                .iter()
+                .map(|p| locate(*p, &exteriors[i].0))
+                .find(|l| *l != Location::Boundary);
+            if location == Some(Location::Inside) {
+                container = Some(i);
+            }
+        }
+        if let Some(c) = container {
+            polygon_holes[c].push(hole);
+        }
+    }
+
+    let to_ring = |ring: &[IPoint], reverse: bool| {
+        let mut points: Vec<Point2D> = ring.iter().map(|p| grid.point(*p)).collect();
+        if reverse {
+            points.reverse();
+        }
+        points.push(points[0]);
+        points
+    };
+    exteriors
+        .iter()
+        .zip(polygon_holes)
+        .map(|((exterior, _), holes)| Polygon {
+            exterior: to_ring(exterior, true),
+            holes: holes.iter().map(|h| to_ring(h, true)).collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        locate_on_lines, locate_on_rings, polygon_overlay, polygon_union, OverlayOperation,
+    };
+    use crate::algorithms::is_clockwise_order;
+    use crate::structures::{Point2D, Polygon};
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<Point2D> {
+        vec![
+            Point2D::new(x, y),
+            Point2D::new(x + size, y),
+            Point2D::new(x + size, y + size),
+            Point2D::new(x, y + size),
+            Point2D::new(x, y),
+        ]
+    }
+
+    fn total_area(polygons: &[Polygon]) -> f64 {
+        polygons.iter().map(|p| p.area()).sum()
+    }
+
+    #[test]
+    fn test_overlapping_squares() {
+        let a = vec![Polygon::new(square(0.0, 0.0, 10.0), vec![])];
+        let b = vec![Polygon::new(square(5.0, 5.0, 10.0), vec![])];
+        let union = polygon_overlay(&a, &b, OverlayOperation::Union, 0.0);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].exterior.len(), 9);
+        assert!((total_area(&union) - 175.0).abs() < 1e-6);
+        assert!(is_clockwise_order(&union[0].exterior));
+
+        let intersection = polygon_overlay(&a, &b, OverlayOperation::Intersection, 0.0);
+        assert_eq!(intersection.len(), 1);
+        assert!((total_area(&intersection) - 25.0).abs() < 1e-6);
+
+        let difference = polygon_overlay(&a, &b, OverlayOperation::Difference, 0.0);
+        assert_eq!(difference.len(), 1);
+        assert!((total_area(&difference) - 75.0).abs() < 1e-6);
+
+        let sym_diff = polygon_overlay(&a, &b, OverlayOperation::SymmetricDifference, 0.0);
+        assert_eq!(sym_diff.len(), 2);
+        assert!((total_area(&sym_diff) - 150.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_union_dissolves_shared_edges() {
+        // the rings are in opposite orientations, and the second is not closed
+        let mut second = square(10.0, 0.0, 10.0);
+        second.pop();
+        second.reverse();
+        let polygons = vec![
+            Polygon::new(square(0.0, 0.0, 10.0), vec![]),
+            Polygon::new(second, vec![]),
+        ];
+        let union = polygon_union(&polygons, 0.0);
+        assert_eq!(union.len(), 1);
+        // the collinear vertices along the dissolved edge are removed
+        assert_eq!(union[0].exterior.len(), 5);
+        assert!((total_area(&union) - 200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_nearly_coincident_edges_are_snapped() {
+        let polygons = vec![
+            Polygon::new(square(500000.0, 4800000.0, 10.0), vec![]),
+            Polygon::new(square(500010.001, 4800000.0, 10.0), vec![]),
+        ];
+        assert_eq!(polygon_union(&polygons, 0.0).len(), 2);
+        let union = polygon_union(&polygons, 0.01);
+        assert_eq!(union.len(), 1);
+        assert!((total_area(&union) - 200.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_holes_and_islands() {
+        let a = vec![Polygon::new(square(0.0, 0.0, 10.0), vec![])];
+        let b = vec![Polygon::new(
+            square(2.0, 2.0, 6.0),
+            vec![square(4.0, 4.0, 2.0)],
+        )];
+        let difference = polygon_overlay(&a, &b, OverlayOperation::Difference, 0.0);
+        assert_eq!(difference.len(), 2);
+        assert!((total_area(&difference) - 68.0).abs() < 1e-6);
+        let frame = difference.iter().find(|p| p.area() > 10.0).unwrap();
+        assert_eq!(frame.holes.len(), 1);
+        assert!(!is_clockwise_order(&frame.holes[0]));
+
+        // filling the hole leaves a single square
+        let union = polygon_overlay(&b, &[Polygon::new(square(4.0, 4.0, 2.0), vec![])], OverlayOperation::Union, 0.0);
+        assert_eq!(union.len(), 1);
+        assert!(union[0].holes.is_empty());
+        assert!((total_area(&union) - 36.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_self_touching_ring() {
+        // a ring that touches itself at (5, 5), enclosing two triangles
+        let bow_tie = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(5.0, 5.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(10.0, 10.0),
+            Point2D::new(5.0, 5.0),
+            Point2D::new(0.0, 10.0),
+            Point2D::new(0.0, 0.0),
+        ];
+        let result = polygon_union(&[Polygon::new(bow_tie, vec![])], 0.0);
+        assert_eq!(result.len(), 2);
+        assert!((total_area(&result) - 50.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_locate_on_rings() {
+        let rings = vec![square(0.0, 0.0, 10.0), square(20.0, 0.0, 10.0)];
+        // the vertices of an intersection lie on the edges of the inputs
+        let a = vec![Polygon::new(rings[0].clone(), vec![])];
+        let b = vec![Polygon::new(square(5.0, -5.0, 10.0), vec![])];
+        let intersection = polygon_overlay(&a, &b, OverlayOperation::Intersection, 0.0);
+        let input_rings = vec![a[0].exterior.clone(), b[0].exterior.clone()];
+        for position in locate_on_rings(&intersection[0].exterior, &input_rings) {
+            assert!(position.unwrap().distance < 1e-9);
+        }
+
+        let positions =
+            locate_on_rings(&[Point2D::new(2.5, -1.0), Point2D::new(31.0, 4.0)], &rings);
+        let first = positions[0].unwrap();
+        assert_eq!((first.ring, first.vertex, first.next_vertex), (0, 0, 1));
+        assert!((first.t - 0.25).abs() < 1e-9 && (first.distance - 1.0).abs() < 1e-9);
+        let second = positions[1].unwrap();
+        assert_eq!((second.ring, second.vertex, second.next_vertex), (1, 1, 2));
+        assert!((second.t - 0.4).abs() < 1e-9 && (second.distance - 1.0).abs() < 1e-9);
+
+        assert_eq!(locate_on_rings(&[Point2D::new(0.0, 0.0)], &[]), vec![None]);
+    }
+
+    #[test]
+    fn test_locate_on_lines() {
+        // the last vertex of a line is not joined to its first
+        let lines = vec![vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(10.0, 10.0),
+        ]];
+        let p = Point2D::new(4.0, 5.0);
+        let position = locate_on_lines(&[p], &lines)[0].unwrap();
+        assert_eq!((position.ring, position.vertex, position.next_vertex), (0, 0, 1));
+        assert!((position.t - 0.4).abs() < 1e-9 && (position.distance - 5.0).abs() < 1e-9);
+        let position = locate_on_rings(&[p], &lines)[0].unwrap();
+        assert_eq!((position.vertex, position.next_vertex), (2, 0));
+
+        let position = locate_on_lines(&[Point2D::new(12.0, 10.0)], &lines)[0].unwrap();
+        assert_eq!((position.vertex, position.next_vertex), (1, 2));
+        assert!((position.t - 1.0).abs() < 1e-9 && (position.distance - 2.0).abs() < 1e-9);
+    }
+}
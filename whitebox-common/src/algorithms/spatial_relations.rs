@@ -0,0 +1,781 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use crate::rstar::primitives::{GeomWithData, Line};
+use crate::rstar::{RTree, AABB};
+use crate::structures::{BoundingBox, Point2D, Polygon, RectangleWithData};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+type IndexedSegment = GeomWithData<Line<[f64; 2]>, usize>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Location {
+    Interior,
+    Boundary,
+    Exterior,
+}
+
+/// The geometry of a vector feature, i.e. a set of points, lines, or polygons, indexed for
+/// testing its spatial relations with other features. The tests follow the usual
+/// definitions of the relations, in which the boundary of a line is its end points and that
+/// of a polygon is its rings. Vertices within a small distance of a line or ring, relative
+/// to the magnitude of the coordinates, are treated as lying on it, to allow for the
+/// rounding of shared vertices.
+pub struct FeatureGeometry {
+    /// 0 for points, 1 for lines, and 2 for polygons.
+    dimension: usize,
+    polygons: Vec<Polygon>,
+    /// The line segments of the lines and polygon rings, or the points, as zero-length
+    /// segments, along with the index of the part to which each belongs.
+    segments: Vec<(Point2D, Point2D, usize)>,
+    tree: RTree<IndexedSegment>,
+    /// The end points of the open lines, which form the boundary of a line feature.
+    line_ends: Vec<Point2D>,
+    /// A vertex of each part, used to test whether one feature lies within another.
+    part_vertices: Vec<Point2D>,
+    bounding_box: BoundingBox,
+    tolerance: f64,
+}
+
+impl FeatureGeometry {
+    /// Creates a feature made of a set of points.
+    pub fn points(points: Vec<Point2D>) -> FeatureGeometry {
+        let segments = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (*p, *p, i))
+            .collect();
+        let part_vertices = points.clone();
+        FeatureGeometry::new(0, vec![], segments, vec![], part_vertices)
+    }
+
+    /// Creates a feature made of a set of lines.
+    pub fn lines(lines: Vec<Vec<Point2D>>) -> FeatureGeometry {
+        let lines: Vec<Vec<Point2D>> = lines.into_iter().filter(|l| !l.is_empty()).collect();
+        let mut segments = vec![];
+        let mut line_ends = vec![];
+        for (part, line) in lines.iter().enumerate() {
+            if line.len() == 1 {
+                segments.push((line[0], line[0], part));
+            }
+            for i in 1..line.len() {
+                if line[i - 1] != line[i] {
+                    segments.push((line[i - 1], line[i], part));
+                }
+            }
+            if line[0] != line[line.len() - 1] {
+                line_ends.push(line[0]);
+                line_ends.push(line[line.len() - 1]);
+            }
+        }
+        let part_vertices = lines.iter().map(|l| l[0]).collect();
+        FeatureGeometry::new(1, vec![], segments, line_ends, part_vertices)
+    }
+
+    /// Creates a feature made of a set of polygons, which should not overlap one another.
+    pub fn polygons(polygons: Vec<Polygon>) -> FeatureGeometry {
+        let polygons: Vec<Polygon> = polygons
+            .into_iter()
+            .filter(|p| !p.exterior.is_empty())
+            .collect();
+        let mut segments = vec![];
+        for (part, polygon) in polygons.iter().enumerate() {
+            for ring in polygon.rings() {
+                let num_segments = segments.len();
+                for i in 0..ring.len() {
+                    let j = (i + 1) % ring.len();
+                    if ring[i] != ring[j] {
+                        segments.push((ring[i], ring[j], part));
+                    }
+                }
+                if segments.len() == num_segments && !ring.is_empty() {
+                    segments.push((ring[0], ring[0], part));
+                }
+            }
+        }
+        let part_vertices = polygons.iter().map(|p| p.exterior[0]).collect();
+        FeatureGeometry::new(2, polygons, segments, vec![], part_vertices)
+    }
+
+    fn new(
+        dimension: usize,
+        polygons: Vec<Polygon>,
+        segments: Vec<(Point2D, Point2D, usize)>,
+        line_ends: Vec<Point2D>,
+        part_vertices: Vec<Point2D>,
+    ) -> FeatureGeometry {
+        let mut bounding_box = BoundingBox::default();
+        for (p, q, _) in &segments {
+            bounding_box.expand_to(BoundingBox::from_two_points(*p, *q));
+        }
+        let magnitude = if segments.is_empty() {
+            1f64
+        } else {
+            bounding_box
+                .min_x
+                .abs()
+                .max(bounding_box.max_x.abs())
+                .max(bounding_box.min_y.abs())
+                .max(bounding_box.max_y.abs())
+                .max(1f64)
+        };
+        let tree = RTree::bulk_load(
+            segments
+                .iter()
+                .enumerate()
+                .map(|(i, (p, q, _))| GeomWithData::new(Line::new([p.x, p.y], [q.x, q.y]), i))
+                .collect(),
+        );
+        FeatureGeometry {
+            dimension,
+            polygons,
+            segments,
+            tree,
+            line_ends,
+            part_vertices,
+            bounding_box,
+            tolerance: 1e-10 * magnitude,
+        }
+    }
+
+    /// Returns true if the feature has no points.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns 0 for points, 1 for lines, and 2 for polygons.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn get_bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+
+    /// Returns true if the two features share at least one point.
+    pub fn intersects(&self, other: &FeatureGeometry) -> bool {
+        self.common_point(other).is_some()
+    }
+
+    /// Returns true if no point of this feature lies outside of the other feature, and the
+    /// interiors of the two features intersect. A line lying along the boundary of a polygon
+    /// is therefore not within it.
+    pub fn within(&self, other: &FeatureGeometry) -> bool {
+        if self.is_empty() || other.is_empty() || self.dimension() > other.dimension() {
+            return false;
+        }
+        let tolerance = self.tolerance.max(other.tolerance);
+        if !self
+            .bounding_box
+            .within(expanded(other.bounding_box, tolerance))
+        {
+            return false;
+        }
+        let test_points = self.test_points(other, tolerance);
+        if test_points
+            .iter()
+            .any(|p| other.locate(p, tolerance) == Location::Exterior)
+        {
+            return false;
+        }
+        if self.dimension() == 2 {
+            // the boundary of the other polygon, e.g. a hole, must not cross the interior
+            // of this one
+            if other.test_points(self, tolerance).iter().any(|p| {
+                self.locate(p, tolerance) == Location::Interior
+                    && other.locate(p, tolerance) == Location::Boundary
+            }) {
+                return false;
+            }
+        }
+        self.interiors_meet(other, &test_points, tolerance)
+    }
+
+    /// Returns true if the other feature is within this one.
+    pub fn contains(&self, other: &FeatureGeometry) -> bool {
+        other.within(self)
+    }
+
+    /// Returns true if the two features intersect, but only along their boundaries.
+    pub fn touches(&self, other: &FeatureGeometry) -> bool {
+        if !self.intersects(other) {
+            return false;
+        }
+        let tolerance = self.tolerance.max(other.tolerance);
+        !self.interiors_meet(other, &self.test_points(other, tolerance), tolerance)
+            && !self.interiors_meet(other, &other.test_points(self, tolerance), tolerance)
+    }
+
+    /// Returns the shortest distance between the two features, which is zero if they
+    /// intersect, or infinity if either is empty.
+    pub fn distance(&self, other: &FeatureGeometry) -> f64 {
+        match self.nearest_points(other) {
+            Some((p, q)) => p.distance(&q),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Returns the nearest pair of points on this feature and the other feature, or a
+    /// shared point if they intersect.
+    pub fn nearest_points(&self, other: &FeatureGeometry) -> Option<(Point2D, Point2D)> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        if let Some(p) = self.common_point(other) {
+            return Some((p, p));
+        }
+        // the features do not cross, so the shortest distance is from a vertex of one of them
+        let mut min_distance = f64::INFINITY;
+        let mut nearest = None;
+        for (p, q, _) in &self.segments {
+            for v in [p, q] {
+                if let Some((distance, w)) = other.nearest_point(v) {
+                    if distance < min_distance {
+                        min_distance = distance;
+                        nearest = Some((*v, w));
+                    }
+                }
+            }
+        }
+        for (p, q, _) in &other.segments {
+            for v in [p, q] {
+                if let Some((distance, w)) = self.nearest_point(v) {
+                    if distance < min_distance {
+                        min_distance = distance;
+                        nearest = Some((w, *v));
+                    }
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Returns true if the interiors of the two features intersect at, or next to, any of
+    /// the points. The interior of a polygon includes the area around each of its interior
+    /// points, which therefore meets the interior of any feature that it overlaps.
+    fn interiors_meet(&self, other: &FeatureGeometry, points: &[Point2D], tolerance: f64) -> bool {
+        points.iter().any(|p| {
+            let (a, b) = (self.locate(p, tolerance), other.locate(p, tolerance));
+            (a == Location::Interior && b == Location::Interior)
+                || (self.dimension == 2 && a == Location::Interior && b != Location::Exterior)
+                || (other.dimension == 2 && b == Location::Interior && a != Location::Exterior)
+        })
+    }
+
+    /// Returns the distance to, and the location of, the nearest point of the feature.
+    fn nearest_point(&self, p: &Point2D) -> Option<(f64, Point2D)> {
+        self.tree.nearest_neighbor(&[p.x, p.y]).map(|segment| {
+            let (a, b, _) = self.segments[segment.data];
+            let nearest = nearest_on_segment(p, &a, &b);
+            (p.distance(&nearest), nearest)
+        })
+    }
+
+    /// Returns the segments whose bounding boxes are within a distance of a segment.
+    fn segments_near<'a>(
+        &'a self,
+        p: &Point2D,
+        q: &Point2D,
+        distance: f64,
+    ) -> impl Iterator<Item = &'a (Point2D, Point2D, usize)> {
+        let envelope = AABB::from_corners(
+            [p.x.min(q.x) - distance, p.y.min(q.y) - distance],
+            [p.x.max(q.x) + distance, p.y.max(q.y) + distance],
+        );
+        self.tree
+            .locate_in_envelope_intersecting(&envelope)
+            .map(move |segment| &self.segments[segment.data])
+    }
+
+    /// Returns a point shared by the two features, if there is one.
+    fn common_point(&self, other: &FeatureGeometry) -> Option<Point2D> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        let tolerance = self.tolerance.max(other.tolerance);
+        if !expanded(self.bounding_box, tolerance).overlaps(other.bounding_box) {
+            return None;
+        }
+        for (p, q, _) in &self.segments {
+            for (r, s, _) in other.segments_near(p, q, tolerance) {
+                let (distance, nearest, _) = segment_nearest_points(p, q, r, s);
+                if distance <= tolerance {
+                    return Some(nearest);
+                }
+            }
+        }
+        // with no crossing boundaries, each part is either entirely inside or outside a polygon
+        if other.dimension() == 2 {
+            for p in &self.part_vertices {
+                if other.locate(p, tolerance) != Location::Exterior {
+                    return Some(*p);
+                }
+            }
+        }
+        if self.dimension() == 2 {
+            for p in &other.part_vertices {
+                if self.locate(p, tolerance) != Location::Exterior {
+                    return Some(*p);
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds whether a point lies in the interior, on the boundary, or outside of the feature.
+    fn locate(&self, p: &Point2D, tolerance: f64) -> Location {
+        let on_feature = match self.nearest_point(p) {
+            Some((distance, _)) => distance <= tolerance,
+            None => return Location::Exterior,
+        };
+        match self.dimension {
+            0 => {
+                if on_feature {
+                    Location::Interior
+                } else {
+                    Location::Exterior
+                }
+            }
+            1 => {
+                if !on_feature {
+                    Location::Exterior
+                } else if self.line_ends.iter().any(|e| e.distance(p) <= tolerance) {
+                    Location::Boundary
+                } else {
+                    Location::Interior
+                }
+            }
+            _ => {
+                if on_feature {
+                    return Location::Boundary;
+                }
+                // count the crossings of a ray extending to the right of the point, for
+                // each polygon
+                let envelope =
+                    AABB::from_corners([p.x, p.y], [self.bounding_box.max_x.max(p.x), p.y]);
+                let mut inside = vec![false; self.polygons.len()];
+                for segment in self.tree.locate_in_envelope_intersecting(&envelope) {
+                    let (a, b, part) = self.segments[segment.data];
+                    if (a.y > p.y) != (b.y > p.y) {
+                        let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                        if x > p.x {
+                            inside[part] = !inside[part];
+                        }
+                    }
+                }
+                if inside.iter().any(|i| *i) {
+                    Location::Interior
+                } else {
+                    Location::Exterior
+                }
+            }
+        }
+    }
+
+    /// Returns points that sample each part of this feature, relative to the other feature.
+    /// The lines and rings are split where they meet the other feature, and the points are
+    /// the vertices and split points, together with the middle of each piece, so that each
+    /// piece lies entirely inside, on, or outside of the other feature. Polygons also
+    /// contribute a point from their interior.
+    fn test_points(&self, other: &FeatureGeometry, tolerance: f64) -> Vec<Point2D> {
+        let mut points = vec![];
+        for (p, q, _) in &self.segments {
+            if p == q {
+                points.push(*p);
+                continue;
+            }
+            let length = p.distance(q);
+            let mut splits = vec![0f64, 1f64];
+            for (r, s, _) in other.segments_near(p, q, tolerance) {
+                for v in [r, s] {
+                    if nearest_on_segment(v, p, q).distance(v) <= tolerance {
+                        splits.push(segment_parameter(v, p, q));
+                    }
+                }
+                let (o1, o2) = (orient(r, s, p), orient(r, s, q));
+                let (o3, o4) = (orient(p, q, r), orient(p, q, s));
+                if o1 * o2 < 0f64 && o3 * o4 < 0f64 {
+                    splits.push(o1 / (o1 - o2));
+                }
+            }
+            splits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            splits.dedup_by(|b, a| (*b - *a) * length <= tolerance);
+            let point_at = |t: f64| Point2D::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y));
+            for i in 0..splits.len() {
+                points.push(point_at(splits[i]));
+                if i > 0 {
+                    points.push(point_at((splits[i - 1] + splits[i]) / 2f64));
+                }
+            }
+        }
+        points.extend(self.polygons.iter().filter_map(interior_point));
+        points
+    }
+}
+
+fn expanded(mut bounding_box: BoundingBox, distance: f64) -> BoundingBox {
+    bounding_box.expand_by(distance);
+    bounding_box
+}
+
+/// A set of features, e.g. the records of a vector file, indexed by their bounding boxes
+/// for finding those that lie near another feature. The features are identified by their
+/// positions in the set.
+pub struct FeatureIndex {
+    features: Vec<FeatureGeometry>,
+    tree: RTree<RectangleWithData<usize>>,
+}
+
+impl FeatureIndex {
+    pub fn new(features: Vec<FeatureGeometry>) -> FeatureIndex {
+        let mut boxes = vec![];
+        for (index, feature) in features.iter().enumerate() {
+            if !feature.is_empty() {
+                let bb = feature.get_bounding_box();
+                boxes.push(RectangleWithData::new(
+                    index,
+                    [bb.min_x, bb.min_y],
+                    [bb.max_x, bb.max_y],
+                ));
+            }
+        }
+        FeatureIndex {
+            features,
+            tree: RTree::bulk_load(boxes),
+        }
+    }
+
+    /// Returns the number of features in the set.
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> &FeatureGeometry {
+        &self.features[index]
+    }
+
+    /// Returns the features, in order, whose bounding boxes lie within `distance` of that of
+    /// the target. Only these features can lie within that distance of the target, and so they
+    /// are the candidates for any test of their relation to it.
+    pub fn candidates(&self, target: &FeatureGeometry, distance: f64) -> Vec<usize> {
+        if target.is_empty() {
+            return vec![];
+        }
+        let bb = target.get_bounding_box();
+        let envelope = AABB::from_corners(
+            [bb.min_x - distance, bb.min_y - distance],
+            [bb.max_x + distance, bb.max_y + distance],
+        );
+        let mut candidates: Vec<usize> = self
+            .tree
+            .locate_in_envelope_intersecting(&envelope)
+            .map(|rect| rect.data)
+            .collect();
+        candidates.sort_unstable();
+        candidates
+    }
+
+    /// Returns up to `k` of the features nearest to the target, in order of their distance
+    /// from it, along with the distance and the nearest points on the target and the feature.
+    /// Ties go to the first of the features. Features farther than `max_distance` from the
+    /// target, and the `exclude` feature, e.g. the target itself, are not included.
+    pub fn nearest(
+        &self,
+        target: &FeatureGeometry,
+        k: usize,
+        max_distance: f64,
+        exclude: Option<usize>,
+    ) -> Vec<(usize, f64, Point2D, Point2D)> {
+        let mut nearest: Vec<(usize, f64, Point2D, Point2D)> = vec![];
+        if target.is_empty() || k == 0 {
+            return nearest;
+        }
+        // the features in order of the distance between their bounding boxes and the centre of
+        // the target's, less the target's radius, which is never more than the distance to the
+        // target
+        let bb = target.get_bounding_box();
+        let centre = [(bb.min_x + bb.max_x) / 2f64, (bb.min_y + bb.max_y) / 2f64];
+        let radius = bb.get_width().hypot(bb.get_height()) / 2f64;
+        for (rect, distance2) in self.tree.nearest_neighbor_iter_with_distance_2(&centre) {
+            let search_distance = if nearest.len() == k {
+                nearest[k - 1].1
+            } else {
+                max_distance
+            };
+            if distance2.sqrt() - radius > search_distance {
+                break;
+            }
+            if exclude == Some(rect.data) {
+                continue;
+            }
+            if let Some((from, to)) = target.nearest_points(&self.features[rect.data]) {
+                let distance = from.distance(&to);
+                if distance <= search_distance {
+                    let position = nearest
+                        .iter()
+                        .position(|n| distance < n.1 || (distance == n.1 && rect.data < n.0))
+                        .unwrap_or(nearest.len());
+                    nearest.insert(position, (rect.data, distance, from, to));
+                    nearest.truncate(k);
+                }
+            }
+        }
+        nearest
+    }
+}
+
+/// Calls `f` for each of the features numbered 0 to `num_features` - 1, e.g. the records of a
+/// vector file, on `num_procs` threads, and returns the results in the order of the features.
+/// `progress` is called with the number of features that are done as each one is finished.
+pub fn map_features<T, F, P>(num_features: usize, num_procs: usize, f: F, mut progress: P) -> Vec<T>
+where
+    T: Send + 'static,
+    F: Fn(usize) -> T + Send + Sync + 'static,
+    P: FnMut(usize),
+{
+    let num_procs = num_procs.max(1);
+    let f = Arc::new(f);
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let f = f.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for index in (0..num_features).filter(|i| i % num_procs == tid) {
+                tx.send((index, f(index))).unwrap();
+            }
+        });
+    }
+    let mut results: Vec<Option<T>> = (0..num_features).map(|_| None).collect();
+    for n in 0..num_features {
+        let (index, result) = rx.recv().expect("Error receiving data from thread.");
+        results[index] = Some(result);
+        progress(n + 1);
+    }
+    results.into_iter().map(|r| r.unwrap()).collect()
+}
+
+/// Positive if r is to the left of the line from p to q.
+fn orient(p: &Point2D, q: &Point2D, r: &Point2D) -> f64 {
+    (*q - *p).cross(*r - *p)
+}
+
+/// The position of the projection of a point onto a segment, from 0 at p to 1 at q.
+fn segment_parameter(v: &Point2D, p: &Point2D, q: &Point2D) -> f64 {
+    let d = *q - *p;
+    let length2 = d * d;
+    if length2 > 0f64 {
+        ((*v - *p) * d / length2).clamp(0f64, 1f64)
+    } else {
+        0f64
+    }
+}
+
+fn nearest_on_segment(v: &Point2D, p: &Point2D, q: &Point2D) -> Point2D {
+    let t = segment_parameter(v, p, q);
+    Point2D::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y))
+}
+
+/// Returns the distance between two segments, with the nearest points on each of them.
+fn segment_nearest_points(
+    p: &Point2D,
+    q: &Point2D,
+    r: &Point2D,
+    s: &Point2D,
+) -> (f64, Point2D, Point2D) {
+    let (o1, o2) = (orient(r, s, p), orient(r, s, q));
+    let (o3, o4) = (orient(p, q, r), orient(p, q, s));
+    if o1 * o2 < 0f64 && o3 * o4 < 0f64 {
+        let t = o1 / (o1 - o2);
+        let crossing = Point2D::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y));
+        return (0f64, crossing, crossing);
+    }
+    let candidates = [
+        (*p, nearest_on_segment(p, r, s)),
+        (*q, nearest_on_segment(q, r, s)),
+        (nearest_on_segment(r, p, q), *r),
+        (nearest_on_segment(s, p, q), *s),
+    ];
+    let mut nearest = (f64::INFINITY, *p, *r);
+    for (a, b) in candidates {
+        let distance = a.distance(&b);
+        if distance < nearest.0 {
+            nearest = (distance, a, b);
+        }
+    }
+    nearest
+}
+
+/// Returns a point in the interior of a polygon, in the middle of the widest span of a
+/// horizontal line that passes between its vertices.
+fn interior_point(polygon: &Polygon) -> Option<Point2D> {
+    let mut ys: Vec<f64> = polygon.rings().flatten().map(|p| p.y).collect();
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ys.dedup();
+    if ys.len() < 2 {
+        return None;
+    }
+    let k = ys.len() / 2;
+    let y = (ys[k - 1] + ys[k]) / 2f64;
+    let mut xs = vec![];
+    for ring in polygon.rings() {
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            if (a.y > y) != (b.y > y) {
+                xs.push(a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x));
+            }
+        }
+    }
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    xs.chunks_exact(2)
+        .max_by(|a, b| (a[1] - a[0]).partial_cmp(&(b[1] - b[0])).unwrap())
+        .map(|span| Point2D::new((span[0] + span[1]) / 2f64, y))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{map_features, FeatureGeometry, FeatureIndex};
+    use crate::structures::{Point2D, Polygon};
+
+    fn square(x: f64, y: f64, size: f64) -> FeatureGeometry {
+        FeatureGeometry::polygons(vec![Polygon::new(
+            vec![
+                Point2D::new(x, y),
+                Point2D::new(x + size, y),
+                Point2D::new(x + size, y + size),
+                Point2D::new(x, y + size),
+                Point2D::new(x, y),
+            ],
+            vec![],
+        )])
+    }
+
+    fn line(points: &[(f64, f64)]) -> FeatureGeometry {
+        FeatureGeometry::lines(vec![points
+            .iter()
+            .map(|(x, y)| Point2D::new(*x, *y))
+            .collect()])
+    }
+
+    fn point(x: f64, y: f64) -> FeatureGeometry {
+        FeatureGeometry::points(vec![Point2D::new(x, y)])
+    }
+
+    #[test]
+    fn test_polygon_relations() {
+        let big = square(0.0, 0.0, 10.0);
+        let small = square(2.0, 2.0, 3.0);
+        let adjacent = square(10.0, 0.0, 5.0);
+        let overlapping = square(5.0, 5.0, 10.0);
+        assert!(small.within(&big) && big.contains(&small) && !big.within(&small));
+        assert!(big.within(&big) && !big.touches(&big));
+        assert!(adjacent.touches(&big) && !adjacent.within(&big));
+        assert!(overlapping.intersects(&big) && !overlapping.touches(&big));
+        assert!(!overlapping.within(&big) && !square(20.0, 0.0, 1.0).intersects(&big));
+
+        // a polygon with a hole does not contain a polygon in the hole
+        let ring = FeatureGeometry::polygons(vec![Polygon::new(
+            vec![
+                Point2D::new(0.0, 0.0),
+                Point2D::new(10.0, 0.0),
+                Point2D::new(10.0, 10.0),
+                Point2D::new(0.0, 10.0),
+            ],
+            vec![vec![
+                Point2D::new(2.0, 2.0),
+                Point2D::new(2.0, 8.0),
+                Point2D::new(8.0, 8.0),
+                Point2D::new(8.0, 2.0),
+            ]],
+        )]);
+        let in_hole = square(3.0, 3.0, 2.0);
+        assert!(!in_hole.intersects(&ring) && !ring.contains(&in_hole));
+        assert!(small.touches(&ring) && !small.within(&ring));
+        assert!(!big.within(&ring) && ring.within(&big));
+        assert!(square(2.0, 2.0, 6.0).touches(&ring));
+    }
+
+    #[test]
+    fn test_line_and_point_relations() {
+        let polygon = square(0.0, 0.0, 10.0);
+        assert!(line(&[(1.0, 1.0), (9.0, 9.0)]).within(&polygon));
+        assert!(!line(&[(0.0, 0.0), (10.0, 0.0)]).within(&polygon));
+        assert!(line(&[(0.0, 0.0), (10.0, 0.0)]).touches(&polygon));
+        assert!(line(&[(-5.0, 5.0), (5.0, 5.0)]).intersects(&polygon));
+        assert!(!line(&[(-5.0, 5.0), (5.0, 5.0)]).within(&polygon));
+
+        // crossing lines do not touch, but lines meeting at an end point do
+        let a = line(&[(0.0, 0.0), (10.0, 10.0)]);
+        assert!(!a.touches(&line(&[(0.0, 10.0), (10.0, 0.0)])));
+        assert!(a.touches(&line(&[(10.0, 10.0), (20.0, 0.0)])));
+        assert!(line(&[(2.0, 2.0), (5.0, 5.0)]).within(&a));
+
+        assert!(point(5.0, 5.0).within(&polygon) && polygon.contains(&point(5.0, 5.0)));
+        assert!(point(0.0, 5.0).touches(&polygon) && !point(0.0, 5.0).within(&polygon));
+        assert!(point(5.0, 5.0).within(&a) && point(0.0, 0.0).touches(&a));
+        assert!(point(1.0, 1.0).within(&point(1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_distance() {
+        let polygon = square(0.0, 0.0, 10.0);
+        assert_eq!(point(13.0, 14.0).distance(&polygon), 5.0);
+        assert_eq!(point(5.0, 5.0).distance(&polygon), 0.0);
+        assert_eq!(square(12.0, 0.0, 5.0).distance(&polygon), 2.0);
+        let (p, q) = line(&[(5.0, 12.0), (15.0, 22.0)])
+            .nearest_points(&polygon)
+            .unwrap();
+        assert_eq!((p, q), (Point2D::new(5.0, 12.0), Point2D::new(5.0, 10.0)));
+        assert_eq!(
+            line(&[(-5.0, 5.0), (5.0, 5.0)]).nearest_points(&polygon),
+            Some((Point2D::new(0.0, 5.0), Point2D::new(0.0, 5.0)))
+        );
+        assert!(FeatureGeometry::points(vec![])
+            .distance(&polygon)
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_feature_index() {
+        let index = FeatureIndex::new(vec![
+            square(0.0, 0.0, 10.0),
+            point(15.0, 5.0),
+            FeatureGeometry::points(vec![]),
+            point(-5.0, 5.0),
+            square(30.0, 0.0, 10.0),
+        ]);
+        assert_eq!(index.len(), 5);
+        assert_eq!(index.candidates(&point(5.0, 5.0), 0.0), vec![0]);
+        assert_eq!(index.candidates(&point(12.0, 5.0), 5.0), vec![0, 1]);
+        assert!(index.candidates(&FeatureGeometry::points(vec![]), 10.0).is_empty());
+
+        // the two squares are both 10 units from the target, and the first of them wins
+        let target = point(20.0, 5.0);
+        let nearest = index.nearest(&target, 2, f64::INFINITY, None);
+        assert_eq!(nearest.len(), 2);
+        assert_eq!((nearest[0].0, nearest[0].1), (1, 5.0));
+        assert_eq!((nearest[1].0, nearest[1].1), (0, 10.0));
+        assert_eq!(nearest[1].3, Point2D::new(10.0, 5.0));
+        assert_eq!(index.nearest(&target, 5, f64::INFINITY, None).len(), 4);
+        assert_eq!(index.nearest(&target, 5, 9.0, None).len(), 1);
+        let nearest = index.nearest(&point(15.0, 5.0), 1, f64::INFINITY, Some(1));
+        assert_eq!((nearest[0].0, nearest[0].1), (0, 5.0));
+    }
+
+    #[test]
+    fn test_map_features() {
+        let mut num_done = 0;
+        let results = map_features(100, 4, |i| i * 2, |n| num_done = n);
+        assert_eq!(results, (0..100).map(|i| i * 2).collect::<Vec<usize>>());
+        assert_eq!(num_done, 100);
+    }
+}
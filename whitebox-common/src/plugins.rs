@@ -1,13 +1,55 @@
+//! Plugins are dynamic libraries (cdylib crates) that are loaded into a process at runtime.
+//! A plugin exports a PluginDeclaration, using the export_plugin! macro, and its register
+//! function adds the plugin's tools and functions to a PluginRegistrar, e.g.:
+//!
+//! ```ignore
+//! unsafe extern "C" fn register(registrar: &mut dyn PluginRegistrar) {
+//!     registrar.register_tool(Box::new(MyTool::new()));
+//!     registrar.register_function("hypot", Box::new(Hypot));
+//! }
+//!
+//! export_plugin!(register);
+//! ```
+//!
+//! Rust does not have a stable ABI, so a plugin must be built with the same version of rustc
+//! and of whitebox_common as the program that loads it. Both versions are stored in the
+//! declaration and are checked before the register function is called.
+
+use libloading::Library;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::sync::Arc;
+
 pub static CORE_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub static RUSTC_VERSION: &str = env!("RUSTC_VERSION");
 
-pub trait Function {
+pub trait Function: Send + Sync {
     fn call(&self, args: &[f64]) -> Result<f64, InvocationError>;
 
     /// Help text that may be used to display information about this function.
     fn help(&self) -> Option<&str> { None }
 }
 
+/// A tool provided by a plugin. It has the same methods as the tools that are built into
+/// WhiteboxTools and is run in the same way.
+pub trait Tool: Send + Sync {
+    fn get_tool_name(&self) -> String;
+    fn get_tool_description(&self) -> String;
+    fn get_tool_parameters(&self) -> String;
+    fn get_example_usage(&self) -> String;
+    fn get_toolbox(&self) -> String;
+    fn get_source_file(&self) -> String;
+    fn run(
+        &self,
+        args: Vec<String>,
+        working_directory: &str,
+        verbose: bool,
+    ) -> Result<(), Error>;
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InvocationError {
     InvalidArgumentCount { expected: usize, found: usize },
@@ -32,6 +74,7 @@ pub struct PluginDeclaration {
 
 pub trait PluginRegistrar {
     fn register_function(&mut self, name: &str, function: Box<dyn Function>);
+    fn register_tool(&mut self, tool: Box<dyn Tool>);
 }
 
 #[macro_export]
@@ -47,3 +90,210 @@ macro_rules! export_plugin {
             };
     };
 }
+
+/// The tools and functions of the plugins that have been loaded. The plugin libraries stay
+/// loaded for as long as any of their tools or functions are in use.
+#[derive(Default)]
+pub struct PluginManager {
+    tools: HashMap<String, Arc<dyn Tool>>,
+    functions: HashMap<String, Arc<dyn Function>>,
+}
+
+impl PluginManager {
+    pub fn new() -> PluginManager {
+        PluginManager::default()
+    }
+
+    /// Loads each of the plugin libraries in a directory, i.e. the files with the platform's
+    /// dynamic library extension, returning an error for each library that could not be
+    /// loaded. Libraries that do not declare a plugin are skipped.
+    pub fn load_directory<P: AsRef<Path>>(&mut self, directory: P) -> Vec<Error> {
+        let mut errors = vec![];
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(_) => return errors,
+        };
+        let mut files = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension() == Some(OsStr::new(std::env::consts::DLL_EXTENSION)))
+            .collect::<Vec<_>>();
+        files.sort();
+        for file in files {
+            // Loading a library runs its initialization code; only the plugins directory,
+            // which is installed alongside the program, is searched.
+            match unsafe { self.load(&file) } {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => errors.push(e),
+            }
+        }
+        errors
+    }
+
+    /// Loads a plugin library and registers its tools and functions. An error of kind
+    /// `NotFound` is returned if the library does not declare a plugin.
+    ///
+    /// # Safety
+    ///
+    /// The library's initialization code and its register function are run, and the
+    /// library must have been built with the export_plugin! macro.
+    pub unsafe fn load<P: AsRef<Path>>(&mut self, library_path: P) -> Result<(), Error> {
+        let library_path = library_path.as_ref();
+        let library = Library::new(library_path).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error loading plugin library ({}).", e),
+            )
+        })?;
+        let declaration = match library.get::<*mut PluginDeclaration>(b"plugin_declaration\0") {
+            Ok(symbol) => **symbol,
+            Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("{} does not declare a plugin.", library_path.display()),
+                ))
+            }
+        };
+        if declaration.rustc_version != RUSTC_VERSION || declaration.core_version != CORE_VERSION
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "The plugin {} was built with rustc {} and whitebox_common {}, but rustc {} and whitebox_common {} are required.",
+                    library_path.display(),
+                    declaration.rustc_version,
+                    declaration.core_version,
+                    RUSTC_VERSION,
+                    CORE_VERSION
+                ),
+            ));
+        }
+
+        let mut registrar = Registrar {
+            library: Arc::new(library),
+            tools: vec![],
+            functions: vec![],
+        };
+        (declaration.register)(&mut registrar);
+        for tool in registrar.tools {
+            self.tools.insert(plugin_key(&tool.get_tool_name()), Arc::new(tool));
+        }
+        for (name, function) in registrar.functions {
+            self.functions.insert(name.to_lowercase(), Arc::new(function));
+        }
+        Ok(())
+    }
+
+    /// Returns the tool with a name, which is compared without regard to case or underscores.
+    pub fn get_tool(&self, tool_name: &str) -> Option<Arc<dyn Tool>> {
+        self.tools.get(&plugin_key(tool_name)).cloned()
+    }
+
+    /// Returns the names of the tools, sorted.
+    pub fn get_tool_names(&self) -> Vec<String> {
+        let mut names = self
+            .tools
+            .values()
+            .map(|t| t.get_tool_name())
+            .collect::<Vec<String>>();
+        names.sort();
+        names
+    }
+
+    /// Returns the function with a name, which is compared without regard to case.
+    pub fn get_function(&self, name: &str) -> Option<Arc<dyn Function>> {
+        self.functions.get(&name.to_lowercase()).cloned()
+    }
+
+    /// Returns the functions, keyed by their lowercase names.
+    pub fn get_functions(&self) -> &HashMap<String, Arc<dyn Function>> {
+        &self.functions
+    }
+}
+
+fn plugin_key(tool_name: &str) -> String {
+    tool_name.to_lowercase().replace('_', "")
+}
+
+struct Registrar {
+    library: Arc<Library>,
+    tools: Vec<ToolProxy>,
+    functions: Vec<(String, FunctionProxy)>,
+}
+
+impl PluginRegistrar for Registrar {
+    fn register_function(&mut self, name: &str, function: Box<dyn Function>) {
+        self.functions.push((
+            name.to_string(),
+            FunctionProxy {
+                function,
+                _library: Arc::clone(&self.library),
+            },
+        ));
+    }
+
+    fn register_tool(&mut self, tool: Box<dyn Tool>) {
+        self.tools.push(ToolProxy {
+            tool,
+            _library: Arc::clone(&self.library),
+        });
+    }
+}
+
+/// A tool that keeps its plugin library loaded. The tool is dropped before the library,
+/// since fields are dropped in the order in which they are declared.
+struct ToolProxy {
+    tool: Box<dyn Tool>,
+    _library: Arc<Library>,
+}
+
+impl Tool for ToolProxy {
+    fn get_tool_name(&self) -> String {
+        self.tool.get_tool_name()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.tool.get_tool_description()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        self.tool.get_tool_parameters()
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.tool.get_example_usage()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.tool.get_toolbox()
+    }
+
+    fn get_source_file(&self) -> String {
+        self.tool.get_source_file()
+    }
+
+    fn run(
+        &self,
+        args: Vec<String>,
+        working_directory: &str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        self.tool.run(args, working_directory, verbose)
+    }
+}
+
+/// A function that keeps its plugin library loaded.
+struct FunctionProxy {
+    function: Box<dyn Function>,
+    _library: Arc<Library>,
+}
+
+impl Function for FunctionProxy {
+    fn call(&self, args: &[f64]) -> Result<f64, InvocationError> {
+        self.function.call(args)
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.function.help()
+    }
+}
@@ -0,0 +1,202 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::ellipsoid::Ellipsoid;
+
+/// A geodetic datum, i.e. an ellipsoid plus the seven-parameter (Helmert) transformation
+/// that relates the datum to WGS84. The parameters follow the position vector convention
+/// used by the WKT `TOWGS84` clause: translations (metres), rotations (arc-seconds), and
+/// scale difference (parts per million).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Datum {
+    pub name: String,
+    pub ellipsoid: Ellipsoid,
+    pub to_wgs84: Option<[f64; 7]>,
+}
+
+impl Default for Datum {
+    fn default() -> Datum {
+        Datum::wgs84()
+    }
+}
+
+impl Datum {
+    pub fn wgs84() -> Datum {
+        Datum {
+            name: "D_WGS_1984".to_string(),
+            ellipsoid: Ellipsoid::WGS84,
+            to_wgs84: Some([0f64; 7]),
+        }
+    }
+
+    /// Creates a datum, looking up the shift to WGS84 in the table of well-known datums
+    /// when the `to_wgs84` parameters are not supplied (e.g. ESRI WKT does not contain them).
+    pub fn new(name: &str, ellipsoid: Ellipsoid, to_wgs84: Option<[f64; 7]>) -> Datum {
+        let to_wgs84 = match to_wgs84 {
+            Some(p) => Some(p),
+            None => known_datum_shift(name),
+        };
+        Datum {
+            name: name.to_string(),
+            ellipsoid,
+            to_wgs84,
+        }
+    }
+
+    /// Returns true if the relation between this datum and WGS84 is known.
+    pub fn has_known_shift(&self) -> bool {
+        self.to_wgs84.is_some()
+    }
+
+    /// Returns true if the two datums can be treated as equivalent, so that no datum shift
+    /// is required when transforming between them.
+    pub fn is_equivalent(&self, other: &Datum) -> bool {
+        if self.ellipsoid != other.ellipsoid {
+            return false;
+        }
+        if normalize_datum_name(&self.name) == normalize_datum_name(&other.name) {
+            return true;
+        }
+        self.to_wgs84.unwrap_or([0f64; 7]) == other.to_wgs84.unwrap_or([0f64; 7])
+    }
+
+    /// Transforms geocentric coordinates on this datum to geocentric WGS84 coordinates.
+    pub fn geocentric_to_wgs84(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        match self.to_wgs84 {
+            Some(p) => helmert(x, y, z, &p),
+            None => (x, y, z),
+        }
+    }
+
+    /// Transforms geocentric WGS84 coordinates to geocentric coordinates on this datum.
+    pub fn geocentric_from_wgs84(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        match self.to_wgs84 {
+            Some(p) => helmert_inverse(x, y, z, &p),
+            None => (x, y, z),
+        }
+    }
+}
+
+/// Applies a seven-parameter position vector transformation, using the small-angle
+/// approximation of the rotation matrix.
+fn helmert(x: f64, y: f64, z: f64, p: &[f64; 7]) -> (f64, f64, f64) {
+    let arcsec = std::f64::consts::PI / (180f64 * 3600f64);
+    let (rx, ry, rz) = (p[3] * arcsec, p[4] * arcsec, p[5] * arcsec);
+    let m = 1f64 + p[6] * 1e-6;
+    (
+        m * (x - rz * y + ry * z) + p[0],
+        m * (rz * x + y - rx * z) + p[1],
+        m * (-ry * x + rx * y + z) + p[2],
+    )
+}
+
+/// Reverses `helmert`. The rotation matrix is orthogonal to within the small-angle
+/// approximation, so its transpose is used as the inverse.
+fn helmert_inverse(x: f64, y: f64, z: f64, p: &[f64; 7]) -> (f64, f64, f64) {
+    let arcsec = std::f64::consts::PI / (180f64 * 3600f64);
+    let (rx, ry, rz) = (p[3] * arcsec, p[4] * arcsec, p[5] * arcsec);
+    let m = 1f64 + p[6] * 1e-6;
+    let (x, y, z) = ((x - p[0]) / m, (y - p[1]) / m, (z - p[2]) / m);
+    (
+        x + rz * y - ry * z,
+        -rz * x + y + rx * z,
+        ry * x - rx * y + z,
+    )
+}
+
+fn normalize_datum_name(name: &str) -> String {
+    let n = name.to_lowercase();
+    let n = n.strip_prefix("d_").unwrap_or(&n);
+    n.chars().filter(|c| c.is_ascii_alphanumeric()).collect()
+}
+
+/// Transformation parameters to WGS84 for commonly encountered datums. Datums that are
+/// realizations of ITRS (NAD83, ETRS89, GDA94, SIRGAS, etc.) are treated as coincident with
+/// WGS84, which is accurate to within about a metre.
+fn known_datum_shift(name: &str) -> Option<[f64; 7]> {
+    let n = normalize_datum_name(name);
+    let zero = [0f64; 7];
+    let p = match n.as_str() {
+        "wgs1984" | "wgs84" | "worldgeodeticsystem1984" => zero,
+        "northamerican1983" | "northamericandatum1983" | "nad83" | "nad1983" => zero,
+        "northamerican1983harn" | "northamerican1983csrs" | "nad19832011" | "nad1983nsrs2007"
+        | "nad1983cors96" | "nad19832011pa11" | "nad1983ma11" => zero,
+        "etrs1989" | "europeanterrestrialreferencesystem1989" | "grs1980" => zero,
+        "gda1994" | "geocentricdatumofaustralia1994" | "gda2020" => zero,
+        "nzgd2000" | "newzealandgeodeticdatum2000" => zero,
+        "sirgas2000" | "sirgas" | "rgf1993" | "reseaugeodesiquefrancais1993" => zero,
+        "sweref99" | "jgd2000" | "jgd2011" | "china2000" | "itrf2000" | "itrf2008"
+        | "itrf2014" => zero,
+        "northamerican1927" | "northamericandatum1927" | "nad27" | "nad1927" => {
+            [-8.0, 160.0, 176.0, 0.0, 0.0, 0.0, 0.0]
+        }
+        "wgs1972" | "wgs72" => [0.0, 0.0, 4.5, 0.0, 0.0, 0.554, 0.2263],
+        "osgb1936" => [446.448, -125.157, 542.06, 0.15, 0.247, 0.842, -20.489],
+        "european1950" | "ed50" => [-87.0, -98.0, -121.0, 0.0, 0.0, 0.0, 0.0],
+        "european1979" => [-86.0, -98.0, -119.0, 0.0, 0.0, 0.0, 0.0],
+        "tokyo" => [-146.414, 507.337, 680.507, 0.0, 0.0, 0.0, 0.0],
+        "pulkovo1942" => [23.92, -141.27, -80.9, 0.0, 0.35, 0.82, -0.12],
+        "deutscheshauptdreiecksnetz" => [598.1, 73.7, 418.2, 0.202, 0.045, -2.455, 6.7],
+        "australian1984" | "australiangeodeticdatum1984" => {
+            [-117.763, -51.51, 139.061, -0.292, -0.443, -0.277, -0.191]
+        }
+        "australian1966" | "australiangeodeticdatum1966" => {
+            [-117.808, -51.536, 137.784, 0.303, 0.446, 0.234, -0.29]
+        }
+        "newzealand1949" | "newzealandgeodeticdatum1949" => {
+            [59.47, -5.04, 187.44, 0.47, -0.1, 1.024, -4.5993]
+        }
+        "southamerican1969" => [-57.0, 1.0, -41.0, 0.0, 0.0, 0.0, 0.0],
+        "mgi" | "militargeographischeinstitut" => {
+            [577.326, 90.129, 463.919, 5.137, 1.474, 5.297, 2.4232]
+        }
+        "ntf" | "nouvelletriangulationfrancaise" => [-168.0, -60.0, 320.0, 0.0, 0.0, 0.0, 0.0],
+        "amersfoort" => [565.417, 50.3319, 465.552, -0.398957, 0.343988, -1.8774, 4.0725],
+        "belge1972" | "reseaunationalbelge1972" => {
+            [-106.869, 52.2978, -103.724, 0.3366, -0.457, 1.8422, -1.2747]
+        }
+        "tm65" | "ireland1965" => [482.5, -130.6, 564.6, -1.042, -0.214, -0.631, 8.15],
+        "ch1903" => [674.374, 15.056, 405.346, 0.0, 0.0, 0.0, 0.0],
+        _ => return None,
+    };
+    Some(p)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Datum;
+    use crate::spatial_ref_system::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn test_known_datum_lookup() {
+        let nad27 = Datum::new(
+            "D_North_American_1927",
+            Ellipsoid::from_inverse_flattening(6378206.4, 294.9786982),
+            None,
+        );
+        assert!(nad27.has_known_shift());
+        let unknown = Datum::new("D_Not_A_Datum", Ellipsoid::WGS84, None);
+        assert!(!unknown.has_known_shift());
+    }
+
+    #[test]
+    fn test_helmert_round_trip() {
+        let osgb = Datum::new(
+            "D_OSGB_1936",
+            Ellipsoid::from_inverse_flattening(6377563.396, 299.3249646),
+            None,
+        );
+        let (x, y, z) = (3909833.018, -147097.138, 5020322.066);
+        let (x2, y2, z2) = osgb.geocentric_to_wgs84(x, y, z);
+        assert!((x2 - x).abs() > 100.0);
+        let (x3, y3, z3) = osgb.geocentric_from_wgs84(x2, y2, z2);
+        assert!((x3 - x).abs() < 0.01);
+        assert!((y3 - y).abs() < 0.01);
+        assert!((z3 - z).abs() < 0.01);
+    }
+}
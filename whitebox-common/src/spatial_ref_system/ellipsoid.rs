@@ -0,0 +1,125 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use std::f64;
+
+/// An ellipsoid of revolution, defined by its semi-major axis (`a`, in metres) and
+/// its flattening (`f`). A sphere has a flattening of zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ellipsoid {
+    pub a: f64,
+    pub f: f64,
+}
+
+impl Default for Ellipsoid {
+    fn default() -> Ellipsoid {
+        Ellipsoid::WGS84
+    }
+}
+
+impl Ellipsoid {
+    pub const WGS84: Ellipsoid = Ellipsoid {
+        a: 6378137.0,
+        f: 1.0 / 298.257223563,
+    };
+
+    pub const GRS80: Ellipsoid = Ellipsoid {
+        a: 6378137.0,
+        f: 1.0 / 298.257222101,
+    };
+
+    /// Creates an ellipsoid from the semi-major axis and the inverse flattening. As in
+    /// ESRI and OGC WKT, an inverse flattening of zero denotes a sphere.
+    pub fn from_inverse_flattening(a: f64, inv_f: f64) -> Ellipsoid {
+        let f = if inv_f == 0f64 { 0f64 } else { 1f64 / inv_f };
+        Ellipsoid { a, f }
+    }
+
+    /// Semi-minor axis (metres).
+    pub fn b(&self) -> f64 {
+        self.a * (1f64 - self.f)
+    }
+
+    /// First eccentricity squared.
+    pub fn e2(&self) -> f64 {
+        self.f * (2f64 - self.f)
+    }
+
+    /// First eccentricity.
+    pub fn e(&self) -> f64 {
+        self.e2().sqrt()
+    }
+
+    pub fn is_sphere(&self) -> bool {
+        self.f == 0f64
+    }
+
+    /// Converts geodetic coordinates (latitude and longitude in radians, ellipsoidal height
+    /// in metres) to earth-centred, earth-fixed (geocentric) X, Y, Z coordinates.
+    pub fn geodetic_to_geocentric(&self, lat: f64, lon: f64, h: f64) -> (f64, f64, f64) {
+        let e2 = self.e2();
+        let sin_lat = lat.sin();
+        let nu = self.a / (1f64 - e2 * sin_lat * sin_lat).sqrt();
+        let x = (nu + h) * lat.cos() * lon.cos();
+        let y = (nu + h) * lat.cos() * lon.sin();
+        let z = ((1f64 - e2) * nu + h) * sin_lat;
+        (x, y, z)
+    }
+
+    /// Converts geocentric X, Y, Z coordinates to geodetic latitude and longitude (radians)
+    /// and ellipsoidal height (metres), using Bowring's method with iterative refinement.
+    pub fn geocentric_to_geodetic(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let e2 = self.e2();
+        let lon = y.atan2(x);
+        let p = (x * x + y * y).sqrt();
+        if p < 1e-9 {
+            // on the polar axis
+            let lat = if z >= 0f64 {
+                f64::consts::FRAC_PI_2
+            } else {
+                -f64::consts::FRAC_PI_2
+            };
+            return (lat, lon, z.abs() - self.b());
+        }
+        let mut lat = (z / (p * (1f64 - e2))).atan();
+        let mut h = 0f64;
+        for _ in 0..10 {
+            let sin_lat = lat.sin();
+            let nu = self.a / (1f64 - e2 * sin_lat * sin_lat).sqrt();
+            h = p / lat.cos() - nu;
+            let new_lat = (z / (p * (1f64 - e2 * nu / (nu + h)))).atan();
+            if (new_lat - lat).abs() < 1e-12 {
+                lat = new_lat;
+                break;
+            }
+            lat = new_lat;
+        }
+        (lat, lon, h)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Ellipsoid;
+
+    #[test]
+    fn test_geocentric_conversion() {
+        // Worked example from IOGP Guidance Note 7-2, section 4.1.1.
+        let lat = (53f64 + 48f64 / 60f64 + 33.82 / 3600f64).to_radians();
+        let lon = (2f64 + 7f64 / 60f64 + 46.38 / 3600f64).to_radians();
+        let (x, y, z) = Ellipsoid::WGS84.geodetic_to_geocentric(lat, lon, 73.0);
+        assert!((x - 3771793.968).abs() < 0.01);
+        assert!((y - 140253.342).abs() < 0.01);
+        assert!((z - 5124304.349).abs() < 0.01);
+
+        let (lat2, lon2, h2) = Ellipsoid::WGS84.geocentric_to_geodetic(x, y, z);
+        assert!((lat2 - lat).abs() < 1e-10);
+        assert!((lon2 - lon).abs() < 1e-10);
+        assert!((h2 - 73.0).abs() < 1e-4);
+    }
+}
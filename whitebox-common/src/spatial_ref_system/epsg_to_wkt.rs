@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 macro_rules! hashmap {
     ($( $key: expr => $val: expr ),*) => {{
          let mut map = ::std::collections::HashMap::new();
@@ -6,8 +8,9 @@ macro_rules! hashmap {
     }}
 }
 
-pub fn esri_wkt_from_epsg(code: u16) -> String {
-    let hm = hashmap![
+/// The ESRI well-known text of the coordinate systems, keyed by EPSG code.
+fn esri_wkt_table() -> HashMap<u16, &'static str> {
+    hashmap![
         3819=>"GEOGCS[\"GCS_HD1909\",DATUM[\"D_Hungarian_Datum_1909\",SPHEROID[\"Bessel_1841\",6377397.155,299.1528128]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433],AUTHORITY[\"EPSG\",3819]]",
         3821=>"GEOGCS[\"GCS_TWD_1967\",DATUM[\"D_TWD_1967\",SPHEROID[\"GRS_1967_Truncated\",6378160.0,298.25]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433],AUTHORITY[\"EPSG\",3821]]",
         3824=>"GEOGCS[\"GCS_TWD_1997\",DATUM[\"D_TWD_1997\",SPHEROID[\"GRS_1980\",6378137.0,298.257222101]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433],AUTHORITY[\"EPSG\",3824]]",
@@ -4966,10 +4969,30 @@ pub fn esri_wkt_from_epsg(code: u16) -> String {
         32760=>"PROJCS[\"WGS_1984_UTM_Zone_60S\",GEOGCS[\"GCS_WGS_1984\",DATUM[\"D_WGS_1984\",SPHEROID[\"WGS_1984\",6378137.0,298.257223563]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]],PROJECTION[\"Transverse_Mercator\"],PARAMETER[\"False_Easting\",500000.0],PARAMETER[\"False_Northing\",10000000.0],PARAMETER[\"Central_Meridian\",177.0],PARAMETER[\"Scale_Factor\",0.9996],PARAMETER[\"Latitude_Of_Origin\",0.0],UNIT[\"Meter\",1.0],AUTHORITY[\"EPSG\",32760]]",
         32761=>"PROJCS[\"UPS_South\",GEOGCS[\"GCS_WGS_1984\",DATUM[\"D_WGS_1984\",SPHEROID[\"WGS_1984\",6378137.0,298.257223563]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]],PROJECTION[\"Stereographic\"],PARAMETER[\"False_Easting\",2000000.0],PARAMETER[\"False_Northing\",2000000.0],PARAMETER[\"Central_Meridian\",0.0],PARAMETER[\"Scale_Factor\",0.994],PARAMETER[\"Latitude_Of_Origin\",-90.0],UNIT[\"Meter\",1.0],AUTHORITY[\"EPSG\",32761]]",
         32766=>"PROJCS[\"WGS_1984_TM_36_SE\",GEOGCS[\"GCS_WGS_1984\",DATUM[\"D_WGS_1984\",SPHEROID[\"WGS_1984\",6378137.0,298.257223563]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]],PROJECTION[\"Transverse_Mercator\"],PARAMETER[\"False_Easting\",500000.0],PARAMETER[\"False_Northing\",10000000.0],PARAMETER[\"Central_Meridian\",36.0],PARAMETER[\"Scale_Factor\",0.9996],PARAMETER[\"Latitude_Of_Origin\",0.0],UNIT[\"Meter\",1.0],AUTHORITY[\"EPSG\",32766]]"
-    ];
+    ]
+}
+
+pub fn esri_wkt_from_epsg(code: u16) -> String {
+    let hm = esri_wkt_table();
     let s = match hm.get(&code) {
         Some(key) => key.to_string(),
         None => String::from("Unknown EPSG Code"),
     };
     s
 }
+
+/// Returns the EPSG codes, in ascending order, of the coordinate systems with an ESRI name,
+/// e.g. 'WGS_1984_UTM_Zone_17N'. A few names are shared by several codes.
+pub fn epsg_codes_from_esri_name(name: &str) -> Vec<u16> {
+    let mut codes: Vec<u16> = esri_wkt_table()
+        .into_iter()
+        .filter(|(_, wkt)| {
+            wkt.split('"')
+                .nth(1)
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+        .map(|(code, _)| code)
+        .collect();
+    codes.sort_unstable();
+    codes
+}
@@ -1,3 +1,13 @@
+mod datum;
+mod ellipsoid;
 mod epsg_to_wkt;
+mod projections;
+mod spatial_reference;
+mod wkt;
 
-pub use self::epsg_to_wkt::esri_wkt_from_epsg;
+pub use self::datum::Datum;
+pub use self::ellipsoid::Ellipsoid;
+pub use self::epsg_to_wkt::{epsg_codes_from_esri_name, esri_wkt_from_epsg};
+pub use self::projections::Projection;
+pub use self::spatial_reference::{CoordinateTransformation, SpatialReference};
+pub use self::wkt::{WktNode, WktValue};
@@ -0,0 +1,711 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::ellipsoid::Ellipsoid;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+use std::io::{Error, ErrorKind};
+
+/// A map projection. Angular parameters are stored in radians and false eastings and
+/// northings in metres. The formulas follow IOGP Guidance Note 7-2 (Coordinate Conversions
+/// and Transformations including Formulas).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Projection {
+    /// Geographic (unprojected) longitude and latitude.
+    Geographic,
+    /// Transverse Mercator, including UTM and Gauss-Kruger.
+    TransverseMercator {
+        lon0: f64,
+        lat0: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    /// Lambert Conformal Conic. The one standard parallel form is represented by setting
+    /// both standard parallels to the latitude of origin.
+    LambertConformalConic {
+        lon0: f64,
+        lat0: f64,
+        lat1: f64,
+        lat2: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    AlbersEqualArea {
+        lon0: f64,
+        lat0: f64,
+        lat1: f64,
+        lat2: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    Mercator {
+        lon0: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    /// Popular Visualisation Pseudo-Mercator (Web Mercator), which applies the spherical
+    /// Mercator formulas to ellipsoidal coordinates.
+    WebMercator {
+        lon0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    PolarStereographic {
+        north: bool,
+        lon0: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+}
+
+impl Projection {
+    /// Creates the Transverse Mercator projection of a UTM zone.
+    pub fn utm(zone: u8, south: bool) -> Projection {
+        Projection::TransverseMercator {
+            lon0: (-183f64 + 6f64 * zone as f64).to_radians(),
+            lat0: 0f64,
+            k0: 0.9996,
+            false_easting: 500000f64,
+            false_northing: if south { 10000000f64 } else { 0f64 },
+        }
+    }
+
+    /// Creates a Mercator projection defined by its standard parallel (EPSG variant B).
+    pub fn mercator_from_standard_parallel(
+        ellipsoid: &Ellipsoid,
+        lat1: f64,
+        lon0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    ) -> Projection {
+        Projection::Mercator {
+            lon0,
+            k0: m(ellipsoid.e2(), lat1),
+            false_easting,
+            false_northing,
+        }
+    }
+
+    /// Creates a polar stereographic projection defined by its latitude of true scale
+    /// (EPSG variant B). The hemisphere is taken from the sign of `lat_ts`.
+    pub fn polar_stereographic_from_standard_parallel(
+        ellipsoid: &Ellipsoid,
+        lat_ts: f64,
+        lon0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    ) -> Projection {
+        let north = lat_ts >= 0f64;
+        let e = ellipsoid.e();
+        let k0 = if (lat_ts.abs() - FRAC_PI_2).abs() < 1e-10 {
+            1f64
+        } else {
+            let tf = polar_t(e, lat_ts, north);
+            m(ellipsoid.e2(), lat_ts) * polar_c(e) / (2f64 * tf)
+        };
+        Projection::PolarStereographic {
+            north,
+            lon0,
+            k0,
+            false_easting,
+            false_northing,
+        }
+    }
+
+    pub fn is_geographic(&self) -> bool {
+        *self == Projection::Geographic
+    }
+
+    /// Projects a longitude and latitude (radians) to easting and northing (metres).
+    pub fn forward(&self, ellipsoid: &Ellipsoid, lon: f64, lat: f64) -> Result<(f64, f64), Error> {
+        let a = ellipsoid.a;
+        let e = ellipsoid.e();
+        let e2 = ellipsoid.e2();
+        match *self {
+            Projection::Geographic => Ok((lon, lat)),
+
+            Projection::TransverseMercator {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let tm = TmCoefficients::new(ellipsoid);
+                let q = lat.tan().asinh() - e * (e * lat.sin()).atanh();
+                let beta = q.sinh().atan();
+                let dlon = normalize_lon(lon - lon0);
+                let eta0 = (beta.cos() * dlon.sin()).atanh();
+                let xi0 = (beta.sin() * eta0.cosh()).asin();
+                let mut xi = xi0;
+                let mut eta = eta0;
+                for (i, h) in tm.h.iter().enumerate() {
+                    let j = 2f64 * (i + 1) as f64;
+                    xi += h * (j * xi0).sin() * (j * eta0).cosh();
+                    eta += h * (j * xi0).cos() * (j * eta0).sinh();
+                }
+                let m0 = tm.meridian_arc(ellipsoid, lat0);
+                Ok((
+                    false_easting + k0 * tm.b * eta,
+                    false_northing + k0 * (tm.b * xi - m0),
+                ))
+            }
+
+            Projection::LambertConformalConic {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let (n, f) = lcc_constants(e, e2, lat1, lat2);
+                let r_f = a * f * k0 * t(e, lat0).powf(n);
+                if (lat.abs() - FRAC_PI_2).abs() < 1e-12 && lat * n < 0f64 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The point cannot be projected (pole opposite the cone apex).",
+                    ));
+                }
+                let r = a * f * k0 * t(e, lat).powf(n);
+                let theta = n * normalize_lon(lon - lon0);
+                Ok((
+                    false_easting + r * theta.sin(),
+                    false_northing + r_f - r * theta.cos(),
+                ))
+            }
+
+            Projection::AlbersEqualArea {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                false_easting,
+                false_northing,
+            } => {
+                let (n, c) = albers_constants(e, e2, lat1, lat2);
+                let rho0 = a * (c - n * q(e, e2, lat0)).sqrt() / n;
+                let rho = a * (c - n * q(e, e2, lat)).sqrt() / n;
+                let theta = n * normalize_lon(lon - lon0);
+                Ok((
+                    false_easting + rho * theta.sin(),
+                    false_northing + rho0 - rho * theta.cos(),
+                ))
+            }
+
+            Projection::Mercator {
+                lon0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                if (lat.abs() - FRAC_PI_2).abs() < 1e-10 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The poles cannot be projected using the Mercator projection.",
+                    ));
+                }
+                let es = e * lat.sin();
+                Ok((
+                    false_easting + a * k0 * normalize_lon(lon - lon0),
+                    false_northing
+                        + a * k0
+                            * ((FRAC_PI_4 + lat / 2f64).tan()
+                                * ((1f64 - es) / (1f64 + es)).powf(e / 2f64))
+                            .ln(),
+                ))
+            }
+
+            Projection::WebMercator {
+                lon0,
+                false_easting,
+                false_northing,
+            } => {
+                if (lat.abs() - FRAC_PI_2).abs() < 1e-10 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The poles cannot be projected using the Mercator projection.",
+                    ));
+                }
+                Ok((
+                    false_easting + a * normalize_lon(lon - lon0),
+                    false_northing + a * (FRAC_PI_4 + lat / 2f64).tan().ln(),
+                ))
+            }
+
+            Projection::PolarStereographic {
+                north,
+                lon0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                if (north && lat <= -FRAC_PI_2 + 1e-10) || (!north && lat >= FRAC_PI_2 - 1e-10) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The opposite pole cannot be projected using the polar stereographic projection.",
+                    ));
+                }
+                let rho = 2f64 * a * k0 * polar_t(e, lat, north) / polar_c(e);
+                let dlon = lon - lon0;
+                if north {
+                    Ok((
+                        false_easting + rho * dlon.sin(),
+                        false_northing - rho * dlon.cos(),
+                    ))
+                } else {
+                    Ok((
+                        false_easting + rho * dlon.sin(),
+                        false_northing + rho * dlon.cos(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Converts an easting and northing (metres) to longitude and latitude (radians).
+    pub fn inverse(&self, ellipsoid: &Ellipsoid, x: f64, y: f64) -> Result<(f64, f64), Error> {
+        let a = ellipsoid.a;
+        let e = ellipsoid.e();
+        let e2 = ellipsoid.e2();
+        match *self {
+            Projection::Geographic => Ok((x, y)),
+
+            Projection::TransverseMercator {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let tm = TmCoefficients::new(ellipsoid);
+                let m0 = tm.meridian_arc(ellipsoid, lat0);
+                let eta1 = (x - false_easting) / (tm.b * k0);
+                let xi1 = ((y - false_northing) + k0 * m0) / (tm.b * k0);
+                let mut xi0 = xi1;
+                let mut eta0 = eta1;
+                for (i, h) in tm.h_inv.iter().enumerate() {
+                    let j = 2f64 * (i + 1) as f64;
+                    xi0 -= h * (j * xi1).sin() * (j * eta1).cosh();
+                    eta0 -= h * (j * xi1).cos() * (j * eta1).sinh();
+                }
+                let beta = (xi0.sin() / eta0.cosh()).asin();
+                let q1 = beta.tan().asinh();
+                let mut q2 = q1;
+                for _ in 0..20 {
+                    let next = q1 + e * (e * q2.tanh()).atanh();
+                    if (next - q2).abs() < 1e-14 {
+                        q2 = next;
+                        break;
+                    }
+                    q2 = next;
+                }
+                let lat = q2.sinh().atan();
+                let lon = lon0 + (eta0.tanh() / beta.cos()).asin();
+                Ok((lon, lat))
+            }
+
+            Projection::LambertConformalConic {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let (n, f) = lcc_constants(e, e2, lat1, lat2);
+                let r_f = a * f * k0 * t(e, lat0).powf(n);
+                let dx = x - false_easting;
+                let dy = r_f - (y - false_northing);
+                let sign = n.signum();
+                let r = sign * (dx * dx + dy * dy).sqrt();
+                let theta = (sign * dx).atan2(sign * dy);
+                let t1 = (r / (a * k0 * f)).powf(1f64 / n);
+                let lat = lat_from_t(e, t1);
+                Ok((lon0 + theta / n, lat))
+            }
+
+            Projection::AlbersEqualArea {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                false_easting,
+                false_northing,
+            } => {
+                let (n, c) = albers_constants(e, e2, lat1, lat2);
+                let rho0 = a * (c - n * q(e, e2, lat0)).sqrt() / n;
+                let dx = x - false_easting;
+                let dy = rho0 - (y - false_northing);
+                let sign = n.signum();
+                let rho = (dx * dx + dy * dy).sqrt();
+                let theta = (sign * dx).atan2(sign * dy);
+                let q1 = (c - rho * rho * n * n / (a * a)) / n;
+                let lat = lat_from_q(e, e2, q1);
+                Ok((lon0 + theta / n, lat))
+            }
+
+            Projection::Mercator {
+                lon0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let t1 = ((false_northing - y) / (a * k0)).exp();
+                Ok((
+                    lon0 + (x - false_easting) / (a * k0),
+                    lat_from_t(e, t1),
+                ))
+            }
+
+            Projection::WebMercator {
+                lon0,
+                false_easting,
+                false_northing,
+            } => Ok((
+                lon0 + (x - false_easting) / a,
+                FRAC_PI_2 - 2f64 * ((false_northing - y) / a).exp().atan(),
+            )),
+
+            Projection::PolarStereographic {
+                north,
+                lon0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let dx = x - false_easting;
+                let dy = y - false_northing;
+                let rho = (dx * dx + dy * dy).sqrt();
+                let t1 = rho * polar_c(e) / (2f64 * a * k0);
+                let chi = if north {
+                    FRAC_PI_2 - 2f64 * t1.atan()
+                } else {
+                    2f64 * t1.atan() - FRAC_PI_2
+                };
+                let (e4, e6, e8) = (e2 * e2, e2 * e2 * e2, e2 * e2 * e2 * e2);
+                let lat = chi
+                    + (e2 / 2f64 + 5f64 * e4 / 24f64 + e6 / 12f64 + 13f64 * e8 / 360f64)
+                        * (2f64 * chi).sin()
+                    + (7f64 * e4 / 48f64 + 29f64 * e6 / 240f64 + 811f64 * e8 / 11520f64)
+                        * (4f64 * chi).sin()
+                    + (7f64 * e6 / 120f64 + 81f64 * e8 / 1120f64) * (6f64 * chi).sin()
+                    + (4279f64 * e8 / 161280f64) * (8f64 * chi).sin();
+                let lon = if north {
+                    lon0 + dx.atan2(-dy)
+                } else {
+                    lon0 + dx.atan2(dy)
+                };
+                Ok((lon, lat))
+            }
+        }
+    }
+}
+
+/// Coefficients of the Transverse Mercator series (JHS 154 / Kruger n-series).
+struct TmCoefficients {
+    b: f64,
+    h: [f64; 4],
+    h_inv: [f64; 4],
+}
+
+impl TmCoefficients {
+    fn new(ellipsoid: &Ellipsoid) -> TmCoefficients {
+        let f = ellipsoid.f;
+        let n = f / (2f64 - f);
+        let (n2, n3, n4) = (n * n, n * n * n, n * n * n * n);
+        TmCoefficients {
+            b: ellipsoid.a / (1f64 + n) * (1f64 + n2 / 4f64 + n4 / 64f64),
+            h: [
+                n / 2f64 - 2f64 / 3f64 * n2 + 5f64 / 16f64 * n3 + 41f64 / 180f64 * n4,
+                13f64 / 48f64 * n2 - 3f64 / 5f64 * n3 + 557f64 / 1440f64 * n4,
+                61f64 / 240f64 * n3 - 103f64 / 140f64 * n4,
+                49561f64 / 161280f64 * n4,
+            ],
+            h_inv: [
+                n / 2f64 - 2f64 / 3f64 * n2 + 37f64 / 96f64 * n3 - 1f64 / 360f64 * n4,
+                1f64 / 48f64 * n2 + 1f64 / 15f64 * n3 - 437f64 / 1440f64 * n4,
+                17f64 / 480f64 * n3 - 37f64 / 840f64 * n4,
+                4397f64 / 161280f64 * n4,
+            ],
+        }
+    }
+
+    /// Meridional arc distance from the equator to `lat`.
+    fn meridian_arc(&self, ellipsoid: &Ellipsoid, lat: f64) -> f64 {
+        if lat == 0f64 {
+            return 0f64;
+        }
+        let e = ellipsoid.e();
+        let q = lat.tan().asinh() - e * (e * lat.sin()).atanh();
+        let xi0 = q.sinh().atan().sin().asin();
+        let mut xi = xi0;
+        for (i, h) in self.h.iter().enumerate() {
+            xi += h * (2f64 * (i + 1) as f64 * xi0).sin();
+        }
+        self.b * xi
+    }
+}
+
+/// Wraps a longitude difference into the range -pi to pi. A small tolerance keeps values of
+/// exactly +/-180 degrees, which rarely convert to exactly +/-pi, on their own side.
+pub(crate) fn normalize_lon(lon: f64) -> f64 {
+    let pi = std::f64::consts::PI;
+    let mut lon = lon;
+    while lon > pi + 1e-9 {
+        lon -= 2f64 * pi;
+    }
+    while lon < -pi - 1e-9 {
+        lon += 2f64 * pi;
+    }
+    lon
+}
+
+fn m(e2: f64, lat: f64) -> f64 {
+    lat.cos() / (1f64 - e2 * lat.sin() * lat.sin()).sqrt()
+}
+
+fn t(e: f64, lat: f64) -> f64 {
+    let es = e * lat.sin();
+    (FRAC_PI_4 - lat / 2f64).tan() / ((1f64 - es) / (1f64 + es)).powf(e / 2f64)
+}
+
+/// Inverts `t` by fixed-point iteration.
+fn lat_from_t(e: f64, t: f64) -> f64 {
+    let mut lat = FRAC_PI_2 - 2f64 * t.atan();
+    for _ in 0..20 {
+        let es = e * lat.sin();
+        let next = FRAC_PI_2 - 2f64 * (t * ((1f64 - es) / (1f64 + es)).powf(e / 2f64)).atan();
+        if (next - lat).abs() < 1e-14 {
+            return next;
+        }
+        lat = next;
+    }
+    lat
+}
+
+fn lcc_constants(e: f64, e2: f64, lat1: f64, lat2: f64) -> (f64, f64) {
+    let m1 = m(e2, lat1);
+    let t1 = t(e, lat1);
+    let n = if (lat1 - lat2).abs() < 1e-12 {
+        lat1.sin()
+    } else {
+        (m1.ln() - m(e2, lat2).ln()) / (t1.ln() - t(e, lat2).ln())
+    };
+    (n, m1 / (n * t1.powf(n)))
+}
+
+fn q(e: f64, e2: f64, lat: f64) -> f64 {
+    let s = lat.sin();
+    if e == 0f64 {
+        return 2f64 * s;
+    }
+    (1f64 - e2)
+        * (s / (1f64 - e2 * s * s) - 1f64 / (2f64 * e) * ((1f64 - e * s) / (1f64 + e * s)).ln())
+}
+
+fn albers_constants(e: f64, e2: f64, lat1: f64, lat2: f64) -> (f64, f64) {
+    let m1 = m(e2, lat1);
+    let q1 = q(e, e2, lat1);
+    let n = if (lat1 - lat2).abs() < 1e-12 {
+        lat1.sin()
+    } else {
+        let m2 = m(e2, lat2);
+        (m1 * m1 - m2 * m2) / (q(e, e2, lat2) - q1)
+    };
+    (n, m1 * m1 + n * q1)
+}
+
+/// Inverts `q` by Newton-Raphson iteration.
+fn lat_from_q(e: f64, e2: f64, q1: f64) -> f64 {
+    let mut lat = (q1 / 2f64).clamp(-1f64, 1f64).asin();
+    if e == 0f64 {
+        return lat;
+    }
+    for _ in 0..20 {
+        let s = lat.sin();
+        let one_less = 1f64 - e2 * s * s;
+        let delta = one_less * one_less / (2f64 * lat.cos())
+            * (q1 / (1f64 - e2) - s / one_less
+                + 1f64 / (2f64 * e) * ((1f64 - e * s) / (1f64 + e * s)).ln());
+        lat += delta;
+        if delta.abs() < 1e-14 {
+            break;
+        }
+    }
+    lat
+}
+
+fn polar_c(e: f64) -> f64 {
+    ((1f64 + e).powf(1f64 + e) * (1f64 - e).powf(1f64 - e)).sqrt()
+}
+
+fn polar_t(e: f64, lat: f64, north: bool) -> f64 {
+    let es = e * lat.sin();
+    if north {
+        (FRAC_PI_4 - lat / 2f64).tan() / ((1f64 - es) / (1f64 + es)).powf(e / 2f64)
+    } else {
+        (FRAC_PI_4 + lat / 2f64).tan() / ((1f64 + es) / (1f64 - es)).powf(e / 2f64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Projection;
+    use crate::spatial_ref_system::ellipsoid::Ellipsoid;
+
+    fn dms(d: f64, m: f64, s: f64) -> f64 {
+        (d.abs() + m / 60f64 + s / 3600f64).copysign(d).to_radians()
+    }
+
+    fn check(proj: &Projection, ellipsoid: &Ellipsoid, lon: f64, lat: f64, x: f64, y: f64) {
+        let (x2, y2) = proj.forward(ellipsoid, lon, lat).unwrap();
+        assert!((x2 - x).abs() < 0.01, "easting {} != {}", x2, x);
+        assert!((y2 - y).abs() < 0.01, "northing {} != {}", y2, y);
+        let (lon2, lat2) = proj.inverse(ellipsoid, x2, y2).unwrap();
+        assert!((lon2 - lon).abs() < 1e-9);
+        assert!((lat2 - lat).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transverse_mercator() {
+        // British National Grid example from IOGP Guidance Note 7-2.
+        let airy = Ellipsoid::from_inverse_flattening(6377563.396, 299.3249646);
+        let proj = Projection::TransverseMercator {
+            lon0: dms(-2.0, 0.0, 0.0),
+            lat0: dms(49.0, 0.0, 0.0),
+            k0: 0.9996012717,
+            false_easting: 400000.0,
+            false_northing: -100000.0,
+        };
+        check(
+            &proj,
+            &airy,
+            dms(0.0, 30.0, 0.0),
+            dms(50.0, 30.0, 0.0),
+            577274.99,
+            69740.50,
+        );
+    }
+
+    #[test]
+    fn test_lambert_conformal_conic() {
+        // Texas South Central (NAD27) example from IOGP Guidance Note 7-2.
+        let clarke = Ellipsoid::from_inverse_flattening(6378206.4, 294.9786982);
+        let us_ft = 0.3048006096012192;
+        let proj = Projection::LambertConformalConic {
+            lon0: dms(-99.0, 0.0, 0.0),
+            lat0: dms(27.0, 50.0, 0.0),
+            lat1: dms(28.0, 23.0, 0.0),
+            lat2: dms(30.0, 17.0, 0.0),
+            k0: 1.0,
+            false_easting: 2000000.0 * us_ft,
+            false_northing: 0.0,
+        };
+        check(
+            &proj,
+            &clarke,
+            dms(-96.0, 0.0, 0.0),
+            dms(28.0, 30.0, 0.0),
+            2963503.91 * us_ft,
+            254759.80 * us_ft,
+        );
+    }
+
+    #[test]
+    fn test_albers_equal_area() {
+        // Ellipsoidal example from Snyder (1987), Map Projections: A Working Manual.
+        let clarke = Ellipsoid::from_inverse_flattening(6378206.4, 294.9786982);
+        let proj = Projection::AlbersEqualArea {
+            lon0: (-96f64).to_radians(),
+            lat0: 23f64.to_radians(),
+            lat1: 29.5f64.to_radians(),
+            lat2: 45.5f64.to_radians(),
+            false_easting: 0.0,
+            false_northing: 0.0,
+        };
+        let (x, y) = proj
+            .forward(&clarke, (-75f64).to_radians(), 35f64.to_radians())
+            .unwrap();
+        assert!((x - 1885472.7).abs() < 0.1);
+        assert!((y - 1535925.0).abs() < 0.1);
+        let (lon, lat) = proj.inverse(&clarke, x, y).unwrap();
+        assert!((lon - (-75f64).to_radians()).abs() < 1e-9);
+        assert!((lat - 35f64.to_radians()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_web_mercator() {
+        // Example from IOGP Guidance Note 7-2.
+        let proj = Projection::WebMercator {
+            lon0: 0.0,
+            false_easting: 0.0,
+            false_northing: 0.0,
+        };
+        check(
+            &proj,
+            &Ellipsoid::WGS84,
+            dms(-100.0, 20.0, 0.0),
+            dms(24.0, 22.0, 54.433),
+            -11169055.58,
+            2800000.00,
+        );
+    }
+
+    #[test]
+    fn test_polar_stereographic() {
+        // Variant A and B examples from IOGP Guidance Note 7-2.
+        let proj = Projection::PolarStereographic {
+            north: true,
+            lon0: 0.0,
+            k0: 0.994,
+            false_easting: 2000000.0,
+            false_northing: 2000000.0,
+        };
+        check(
+            &proj,
+            &Ellipsoid::WGS84,
+            dms(44.0, 0.0, 0.0),
+            dms(73.0, 0.0, 0.0),
+            3320416.75,
+            632668.43,
+        );
+
+        let proj = Projection::polar_stereographic_from_standard_parallel(
+            &Ellipsoid::WGS84,
+            dms(-71.0, 0.0, 0.0),
+            dms(70.0, 0.0, 0.0),
+            6000000.0,
+            6000000.0,
+        );
+        check(
+            &proj,
+            &Ellipsoid::WGS84,
+            dms(120.0, 0.0, 0.0),
+            dms(-75.0, 0.0, 0.0),
+            7255380.79,
+            7053389.56,
+        );
+    }
+
+    #[test]
+    fn test_utm_round_trip() {
+        let proj = Projection::utm(17, false);
+        let (lon, lat) = ((-80.5f64).to_radians(), 43.5f64.to_radians());
+        let (x, y) = proj.forward(&Ellipsoid::GRS80, lon, lat).unwrap();
+        assert!((x - 540_000.0).abs() < 5_000.0);
+        let (lon2, lat2) = proj.inverse(&Ellipsoid::GRS80, x, y).unwrap();
+        assert!((lon2 - lon).abs() < 1e-10);
+        assert!((lat2 - lat).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,387 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::datum::Datum;
+use super::ellipsoid::Ellipsoid;
+use super::epsg_to_wkt::{epsg_codes_from_esri_name, esri_wkt_from_epsg};
+use super::projections::{normalize_lon, Projection};
+use super::wkt::{WktNode, WktValue};
+use std::io::{Error, ErrorKind};
+
+/// A coordinate reference system, i.e. a datum, a prime meridian, and either geographic
+/// coordinates or a map projection with its linear unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpatialReference {
+    pub name: String,
+    pub datum: Datum,
+    /// Longitude of the prime meridian relative to Greenwich (radians).
+    pub prime_meridian: f64,
+    /// Size of the angular unit (radians).
+    pub angular_unit: f64,
+    pub projection: Projection,
+    /// Size of the linear unit of projected coordinates (metres).
+    pub linear_unit: f64,
+    pub epsg: Option<u16>,
+}
+
+impl SpatialReference {
+    /// Creates a spatial reference from an EPSG code.
+    pub fn from_epsg(code: u16) -> Result<SpatialReference, Error> {
+        let wkt = esri_wkt_from_epsg(code);
+        if wkt == "Unknown EPSG Code" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unrecognized EPSG code ({}).", code),
+            ));
+        }
+        let mut sr = SpatialReference::from_wkt(&wkt)?;
+        sr.epsg = Some(code);
+        Ok(sr)
+    }
+
+    /// Creates a spatial reference from an ESRI or OGC (WKT1) well-known text string.
+    pub fn from_wkt(wkt: &str) -> Result<SpatialReference, Error> {
+        let root = WktNode::parse(wkt)?;
+        let epsg = root
+            .child("AUTHORITY")
+            .filter(|a| a.name().is_some_and(|n| n.eq_ignore_ascii_case("EPSG")))
+            .and_then(|a| {
+                a.numbers().first().map(|n| *n as u16).or_else(|| {
+                    a.values.iter().skip(1).find_map(|v| match v {
+                        WktValue::Text(s) => s.parse::<u16>().ok(),
+                        _ => None,
+                    })
+                })
+            });
+        let name = root.name().unwrap_or("").to_string();
+        if root.keyword.eq_ignore_ascii_case("GEOGCS") {
+            let (datum, prime_meridian, angular_unit) = parse_geogcs(&root)?;
+            return Ok(SpatialReference {
+                name,
+                datum,
+                prime_meridian,
+                angular_unit,
+                projection: Projection::Geographic,
+                linear_unit: 1f64,
+                epsg,
+            });
+        }
+        if !root.keyword.eq_ignore_ascii_case("PROJCS") {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported coordinate system type ({}).", root.keyword),
+            ));
+        }
+        let geogcs = root.child("GEOGCS").ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, "The WKT does not contain a GEOGCS.")
+        })?;
+        let (datum, prime_meridian, angular_unit) = parse_geogcs(geogcs)?;
+        let linear_unit = root
+            .child("UNIT")
+            .and_then(|u| u.numbers().first().copied())
+            .unwrap_or(1f64);
+        let method = root
+            .child("PROJECTION")
+            .and_then(|p| p.name())
+            .unwrap_or("")
+            .to_lowercase();
+
+        // angular parameters are in the GEOGCS angular unit, linear ones in the PROJCS unit
+        let angle = |names: &[&str], default: f64| {
+            root.parameter(names).map_or(default, |v| v * angular_unit)
+        };
+        let lon0 = angle(
+            &["central_meridian", "longitude_of_center", "longitude_of_origin"],
+            0f64,
+        );
+        let lat0 = angle(&["latitude_of_origin", "latitude_of_center"], 0f64);
+        let sp1 = root
+            .parameter(&["standard_parallel_1"])
+            .map(|v| v * angular_unit);
+        let sp2 = root
+            .parameter(&["standard_parallel_2"])
+            .map(|v| v * angular_unit);
+        let k0 = root.parameter(&["scale_factor"]);
+        let false_easting = root.parameter(&["false_easting"]).unwrap_or(0f64) * linear_unit;
+        let false_northing = root.parameter(&["false_northing"]).unwrap_or(0f64) * linear_unit;
+        let ellipsoid = datum.ellipsoid;
+
+        let projection = match method.as_str() {
+            "transverse_mercator" | "gauss_kruger" => Projection::TransverseMercator {
+                lon0,
+                lat0,
+                k0: k0.unwrap_or(1f64),
+                false_easting,
+                false_northing,
+            },
+            "lambert_conformal_conic" | "lambert_conformal_conic_1sp"
+            | "lambert_conformal_conic_2sp" => {
+                let lat1 = sp1.unwrap_or(lat0);
+                Projection::LambertConformalConic {
+                    lon0,
+                    lat0,
+                    lat1,
+                    lat2: sp2.unwrap_or(lat1),
+                    k0: k0.unwrap_or(1f64),
+                    false_easting,
+                    false_northing,
+                }
+            }
+            "albers" | "albers_conic_equal_area" => {
+                let lat1 = sp1.unwrap_or(lat0);
+                Projection::AlbersEqualArea {
+                    lon0,
+                    lat0,
+                    lat1,
+                    lat2: sp2.unwrap_or(lat1),
+                    false_easting,
+                    false_northing,
+                }
+            }
+            "mercator" | "mercator_1sp" | "mercator_2sp" => match (sp1, k0) {
+                (Some(lat1), _) => Projection::mercator_from_standard_parallel(
+                    &ellipsoid,
+                    lat1,
+                    lon0,
+                    false_easting,
+                    false_northing,
+                ),
+                (None, k0) => Projection::Mercator {
+                    lon0,
+                    k0: k0.unwrap_or(1f64),
+                    false_easting,
+                    false_northing,
+                },
+            },
+            "mercator_auxiliary_sphere" | "popular_visualisation_pseudo_mercator" => {
+                Projection::WebMercator {
+                    lon0,
+                    false_easting,
+                    false_northing,
+                }
+            }
+            "stereographic_north_pole" | "stereographic_south_pole" | "polar_stereographic" => {
+                let north = match method.as_str() {
+                    "stereographic_north_pole" => true,
+                    "stereographic_south_pole" => false,
+                    _ => sp1.unwrap_or(lat0) >= 0f64,
+                };
+                match (sp1, k0) {
+                    (Some(lat_ts), _) => Projection::polar_stereographic_from_standard_parallel(
+                        &ellipsoid,
+                        lat_ts,
+                        lon0,
+                        false_easting,
+                        false_northing,
+                    ),
+                    (None, k0) => Projection::PolarStereographic {
+                        north,
+                        lon0,
+                        k0: k0.unwrap_or(1f64),
+                        false_easting,
+                        false_northing,
+                    },
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Unsupported projection ({}).",
+                        root.child("PROJECTION").and_then(|p| p.name()).unwrap_or("")
+                    ),
+                ));
+            }
+        };
+
+        Ok(SpatialReference {
+            name,
+            datum,
+            prime_meridian,
+            angular_unit,
+            projection,
+            linear_unit,
+            epsg,
+        })
+    }
+
+    pub fn is_geographic(&self) -> bool {
+        self.projection.is_geographic()
+    }
+
+    /// Returns the EPSG code of the system. The ESRI WKT of a .prj file does not give one, and
+    /// so it is looked up by the name of the system. A few names are shared by a deprecated code
+    /// and its replacement, which have the same definition, and the later code is returned.
+    pub fn find_epsg(&self) -> Option<u16> {
+        self.epsg.or_else(|| epsg_codes_from_esri_name(&self.name).last().copied())
+    }
+
+    /// Converts coordinates in this system to Greenwich longitude and latitude (radians).
+    pub fn native_to_geodetic(&self, x: f64, y: f64) -> Result<(f64, f64), Error> {
+        let (lon, lat) = if self.is_geographic() {
+            (x * self.angular_unit, y * self.angular_unit)
+        } else {
+            self.projection.inverse(
+                &self.datum.ellipsoid,
+                x * self.linear_unit,
+                y * self.linear_unit,
+            )?
+        };
+        Ok((lon + self.prime_meridian, lat))
+    }
+
+    /// Converts Greenwich longitude and latitude (radians) to coordinates in this system.
+    pub fn geodetic_to_native(&self, lon: f64, lat: f64) -> Result<(f64, f64), Error> {
+        let lon = lon - self.prime_meridian;
+        if self.is_geographic() {
+            let lon = normalize_lon(lon);
+            return Ok((lon / self.angular_unit, lat / self.angular_unit));
+        }
+        let (x, y) = self.projection.forward(&self.datum.ellipsoid, lon, lat)?;
+        Ok((x / self.linear_unit, y / self.linear_unit))
+    }
+}
+
+/// Reads the datum, prime meridian (radians), and angular unit (radians) of a GEOGCS node.
+fn parse_geogcs(geogcs: &WktNode) -> Result<(Datum, f64, f64), Error> {
+    let datum_node = geogcs.child("DATUM").ok_or_else(|| {
+        Error::new(ErrorKind::InvalidData, "The WKT does not contain a DATUM.")
+    })?;
+    let spheroid = datum_node.child("SPHEROID").ok_or_else(|| {
+        Error::new(ErrorKind::InvalidData, "The WKT does not contain a SPHEROID.")
+    })?;
+    let axes = spheroid.numbers();
+    if axes.len() < 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "The WKT SPHEROID is missing its axis or flattening.",
+        ));
+    }
+    let ellipsoid = Ellipsoid::from_inverse_flattening(axes[0], axes[1]);
+    let to_wgs84 = datum_node.child("TOWGS84").and_then(|t| {
+        let p = t.numbers();
+        match p.len() {
+            3 => Some([p[0], p[1], p[2], 0f64, 0f64, 0f64, 0f64]),
+            7 => Some([p[0], p[1], p[2], p[3], p[4], p[5], p[6]]),
+            _ => None,
+        }
+    });
+    let datum = Datum::new(datum_node.name().unwrap_or(""), ellipsoid, to_wgs84);
+    let angular_unit = geogcs
+        .child("UNIT")
+        .and_then(|u| u.numbers().first().copied())
+        .unwrap_or_else(|| 1f64.to_radians());
+    let prime_meridian = geogcs
+        .child("PRIMEM")
+        .and_then(|p| p.numbers().first().copied())
+        .unwrap_or(0f64)
+        * angular_unit;
+    Ok((datum, prime_meridian, angular_unit))
+}
+
+/// Transforms coordinates between two spatial references, applying a datum shift (via
+/// geocentric WGS84 coordinates) when the two datums differ.
+#[derive(Clone, Debug)]
+pub struct CoordinateTransformation {
+    pub source: SpatialReference,
+    pub target: SpatialReference,
+    datum_shift: bool,
+}
+
+impl CoordinateTransformation {
+    pub fn new(source: &SpatialReference, target: &SpatialReference) -> CoordinateTransformation {
+        CoordinateTransformation {
+            source: source.clone(),
+            target: target.clone(),
+            datum_shift: !source.datum.is_equivalent(&target.datum),
+        }
+    }
+
+    /// Returns true if a datum shift is required but the shift parameters of one of the
+    /// datums are unknown, in which case the datums are treated as coincident.
+    pub fn is_missing_datum_shift(&self) -> bool {
+        self.datum_shift
+            && (!self.source.datum.has_known_shift() || !self.target.datum.has_known_shift())
+    }
+
+    /// Transforms a horizontal position from the source to the target system.
+    pub fn transform(&self, x: f64, y: f64) -> Result<(f64, f64), Error> {
+        let (mut lon, mut lat) = self.source.native_to_geodetic(x, y)?;
+        if self.datum_shift {
+            let src: &Ellipsoid = &self.source.datum.ellipsoid;
+            let dst: &Ellipsoid = &self.target.datum.ellipsoid;
+            let (gx, gy, gz) = src.geodetic_to_geocentric(lat, lon, 0f64);
+            let (gx, gy, gz) = self.source.datum.geocentric_to_wgs84(gx, gy, gz);
+            let (gx, gy, gz) = self.target.datum.geocentric_from_wgs84(gx, gy, gz);
+            let (lat2, lon2, _) = dst.geocentric_to_geodetic(gx, gy, gz);
+            lon = lon2;
+            lat = lat2;
+        }
+        self.target.geodetic_to_native(lon, lat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{esri_wkt_from_epsg, CoordinateTransformation, SpatialReference};
+
+    #[test]
+    fn test_from_epsg() {
+        let sr = SpatialReference::from_epsg(26917).unwrap();
+        assert!(!sr.is_geographic());
+        assert_eq!(sr.epsg, Some(26917));
+        assert!(SpatialReference::from_epsg(4326).unwrap().is_geographic());
+        assert!(SpatialReference::from_epsg(1).is_err());
+    }
+
+    #[test]
+    fn test_find_epsg() {
+        // the ESRI WKT of a .prj file, without the AUTHORITY of the EPSG definition
+        let esri_wkt = |code: u16| {
+            let wkt = esri_wkt_from_epsg(code);
+            format!("{}]", &wkt[..wkt.rfind(",AUTHORITY[").unwrap()])
+        };
+        let sr = SpatialReference::from_wkt(&esri_wkt(32617)).unwrap();
+        assert_eq!(sr.epsg, None);
+        assert_eq!(sr.find_epsg(), Some(32617));
+        // EPSG 2960 replaces 2037, which has the same name
+        let sr = SpatialReference::from_wkt(&esri_wkt(2960)).unwrap();
+        assert_eq!(sr.find_epsg(), Some(2960));
+        assert_eq!(SpatialReference::from_epsg(4269).unwrap().find_epsg(), Some(4269));
+        let mut sr = SpatialReference::from_wkt(&esri_wkt(26917)).unwrap();
+        sr.name = "Custom_UTM".to_string();
+        assert_eq!(sr.find_epsg(), None);
+    }
+
+    #[test]
+    fn test_utm_to_geographic() {
+        // NAD83 UTM zone 17N to NAD83 geographic; no datum shift is involved.
+        let utm = SpatialReference::from_epsg(26917).unwrap();
+        let geo = SpatialReference::from_epsg(4269).unwrap();
+        let ct = CoordinateTransformation::new(&utm, &geo);
+        assert!(!ct.is_missing_datum_shift());
+        let (lon, lat) = ct.transform(500000.0, 0.0).unwrap();
+        assert!((lon + 81.0).abs() < 1e-9);
+        assert!(lat.abs() < 1e-9);
+
+        let back = CoordinateTransformation::new(&geo, &utm);
+        let (x, y) = ct.transform(563000.0, 4815000.0).unwrap();
+        let (x2, y2) = back.transform(x, y).unwrap();
+        assert!((x2 - 563000.0).abs() < 1e-4);
+        assert!((y2 - 4815000.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_web_mercator() {
+        let geo = SpatialReference::from_epsg(4326).unwrap();
+        let web = SpatialReference::from_epsg(3857).unwrap();
+        let ct = CoordinateTransformation::new(&geo, &web);
+        let (x, _) = ct.transform(180.0, 0.0).unwrap();
+        assert!((x - 20037508.34).abs() < 0.01);
+    }
+}
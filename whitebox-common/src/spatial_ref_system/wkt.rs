@@ -0,0 +1,199 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use std::io::{Error, ErrorKind};
+
+/// A value within a well-known text (WKT) node.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WktValue {
+    Text(String),
+    Number(f64),
+    Node(WktNode),
+}
+
+/// A WKT node, e.g. `SPHEROID["GRS_1980",6378137.0,298.257222101]`. Both square brackets
+/// and parentheses are accepted as delimiters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WktNode {
+    pub keyword: String,
+    pub values: Vec<WktValue>,
+}
+
+impl WktNode {
+    /// Parses a WKT string into a tree of nodes.
+    pub fn parse(wkt: &str) -> Result<WktNode, Error> {
+        let chars: Vec<char> = wkt.trim().chars().collect();
+        let mut pos = 0usize;
+        let node = parse_node(&chars, &mut pos)?;
+        skip_whitespace(&chars, &mut pos);
+        if pos < chars.len() {
+            return Err(malformed("unexpected trailing characters"));
+        }
+        Ok(node)
+    }
+
+    /// Returns the first child node with the keyword (case-insensitive).
+    pub fn child(&self, keyword: &str) -> Option<&WktNode> {
+        self.children().find(|n| n.keyword.eq_ignore_ascii_case(keyword))
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = &WktNode> {
+        self.values.iter().filter_map(|v| match v {
+            WktValue::Node(n) => Some(n),
+            _ => None,
+        })
+    }
+
+    /// The first text value, which for most nodes is the name.
+    pub fn name(&self) -> Option<&str> {
+        self.values.iter().find_map(|v| match v {
+            WktValue::Text(s) => Some(s.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The numeric values of the node, in order.
+    pub fn numbers(&self) -> Vec<f64> {
+        self.values
+            .iter()
+            .filter_map(|v| match v {
+                WktValue::Number(n) => Some(*n),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the value of the PARAMETER child node whose name matches any of `names`
+    /// (case-insensitive).
+    pub fn parameter(&self, names: &[&str]) -> Option<f64> {
+        self.children()
+            .filter(|n| n.keyword.eq_ignore_ascii_case("PARAMETER"))
+            .find(|n| match n.name() {
+                Some(s) => names.iter().any(|name| s.eq_ignore_ascii_case(name)),
+                None => false,
+            })
+            .and_then(|n| n.numbers().first().copied())
+    }
+}
+
+fn malformed(msg: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("Malformed WKT string: {}.", msg),
+    )
+}
+
+fn skip_whitespace(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos].is_whitespace() {
+        *pos += 1;
+    }
+}
+
+fn parse_node(chars: &[char], pos: &mut usize) -> Result<WktNode, Error> {
+    skip_whitespace(chars, pos);
+    let start = *pos;
+    while *pos < chars.len() && (chars[*pos].is_alphanumeric() || chars[*pos] == '_') {
+        *pos += 1;
+    }
+    if start == *pos {
+        return Err(malformed("expected a keyword"));
+    }
+    let keyword: String = chars[start..*pos].iter().collect();
+    skip_whitespace(chars, pos);
+    let mut values = vec![];
+    if *pos >= chars.len() || (chars[*pos] != '[' && chars[*pos] != '(') {
+        // a bare keyword, e.g. the axis direction NORTH
+        return Ok(WktNode { keyword, values });
+    }
+    let close = if chars[*pos] == '[' { ']' } else { ')' };
+    *pos += 1;
+    loop {
+        skip_whitespace(chars, pos);
+        if *pos >= chars.len() {
+            return Err(malformed("unexpected end of string"));
+        }
+        let c = chars[*pos];
+        if c == '"' {
+            *pos += 1;
+            let mut s = String::new();
+            loop {
+                if *pos >= chars.len() {
+                    return Err(malformed("unterminated quoted string"));
+                }
+                if chars[*pos] == '"' {
+                    // a doubled quote is an escaped quote
+                    if *pos + 1 < chars.len() && chars[*pos + 1] == '"' {
+                        s.push('"');
+                        *pos += 2;
+                        continue;
+                    }
+                    *pos += 1;
+                    break;
+                }
+                s.push(chars[*pos]);
+                *pos += 1;
+            }
+            values.push(WktValue::Text(s));
+        } else if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' {
+            let start = *pos;
+            *pos += 1;
+            while *pos < chars.len()
+                && (chars[*pos].is_ascii_digit()
+                    || chars[*pos] == '.'
+                    || chars[*pos] == 'e'
+                    || chars[*pos] == 'E'
+                    || ((chars[*pos] == '-' || chars[*pos] == '+')
+                        && (chars[*pos - 1] == 'e' || chars[*pos - 1] == 'E')))
+            {
+                *pos += 1;
+            }
+            let s: String = chars[start..*pos].iter().collect();
+            let n = s
+                .parse::<f64>()
+                .map_err(|_| malformed(&format!("invalid number '{}'", s)))?;
+            values.push(WktValue::Number(n));
+        } else {
+            values.push(WktValue::Node(parse_node(chars, pos)?));
+        }
+        skip_whitespace(chars, pos);
+        if *pos >= chars.len() {
+            return Err(malformed("unexpected end of string"));
+        }
+        if chars[*pos] == ',' {
+            *pos += 1;
+        } else if chars[*pos] == close {
+            *pos += 1;
+            break;
+        } else {
+            return Err(malformed(&format!("unexpected character '{}'", chars[*pos])));
+        }
+    }
+    Ok(WktNode { keyword, values })
+}
+
+#[cfg(test)]
+mod test {
+    use super::WktNode;
+
+    #[test]
+    fn test_parse_wkt() {
+        let wkt = "PROJCS[\"NAD_1983_UTM_Zone_17N\",GEOGCS[\"GCS_North_American_1983\",DATUM[\"D_North_American_1983\",SPHEROID[\"GRS_1980\",6378137.0,298.257222101]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]],PROJECTION[\"Transverse_Mercator\"],PARAMETER[\"False_Easting\",500000.0],PARAMETER[\"Central_Meridian\",-81.0],UNIT[\"Meter\",1.0],AUTHORITY[\"EPSG\",26917]]";
+        let node = WktNode::parse(wkt).unwrap();
+        assert_eq!(node.keyword, "PROJCS");
+        assert_eq!(node.name(), Some("NAD_1983_UTM_Zone_17N"));
+        let spheroid = node.child("GEOGCS").unwrap().child("DATUM").unwrap().child("SPHEROID").unwrap();
+        assert_eq!(spheroid.numbers(), vec![6378137.0, 298.257222101]);
+        assert_eq!(node.parameter(&["central_meridian"]), Some(-81.0));
+        assert!(node.parameter(&["scale_factor"]).is_none());
+
+        let node = WktNode::parse("GEOGCS(\"WGS 84\", DATUM(\"WGS_1984\", SPHEROID(\"WGS 84\", 6378137, 298.257223563)), AXIS[\"Lat\", NORTH])").unwrap();
+        assert_eq!(node.child("datum").unwrap().name(), Some("WGS_1984"));
+
+        assert!(WktNode::parse("GEOGCS[\"WGS 84\",DATUM[").is_err());
+    }
+}
@@ -8,6 +8,7 @@ mod n_maximizer;
 mod n_minimizer;
 mod point2d;
 mod point3d;
+mod polygon;
 mod polyline;
 mod polynomial_regression_2d;
 mod radial_basis_function;
@@ -24,6 +25,7 @@ pub use self::n_minimizer::NMinimizer;
 pub use self::point2d::Direction;
 pub use self::point2d::Point2D;
 pub use self::point3d::Point3D;
+pub use self::polygon::Polygon;
 pub use self::polyline::MultiPolyline;
 pub use self::polyline::Polyline;
 pub use self::polynomial_regression_2d::PolynomialRegression2D;
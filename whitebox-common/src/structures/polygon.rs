@@ -0,0 +1,70 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::{BoundingBox, Point2D};
+
+/// A polygon, made of an exterior ring and any holes within it. The rings may be closed
+/// (i.e. the last vertex repeats the first) or open, and may be in either orientation.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Polygon {
+    pub exterior: Vec<Point2D>,
+    pub holes: Vec<Vec<Point2D>>,
+}
+
+impl Polygon {
+    /// Creates a new Polygon
+    pub fn new(exterior: Vec<Point2D>, holes: Vec<Vec<Point2D>>) -> Polygon {
+        Polygon { exterior, holes }
+    }
+
+    /// Returns the exterior ring followed by the holes.
+    pub fn rings(&self) -> impl Iterator<Item = &Vec<Point2D>> {
+        std::iter::once(&self.exterior).chain(self.holes.iter())
+    }
+
+    /// Returns the area of the polygon, i.e. that of the exterior less that of the holes.
+    pub fn area(&self) -> f64 {
+        let ring_area = |ring: &Vec<Point2D>| {
+            // relative to the first vertex, to retain precision with large coordinates
+            let mut area = 0f64;
+            for i in 1..ring.len() {
+                let j = (i + 1) % ring.len();
+                let (p, q) = (ring[i] - ring[0], ring[j] - ring[0]);
+                area += p.x * q.y - q.x * p.y;
+            }
+            (area / 2f64).abs()
+        };
+        ring_area(&self.exterior) - self.holes.iter().map(ring_area).sum::<f64>()
+    }
+
+    pub fn get_bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_points(&self.exterior)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Polygon;
+    use crate::structures::Point2D;
+
+    #[test]
+    fn test_polygon_area() {
+        let square = |x: f64, y: f64, size: f64| {
+            vec![
+                Point2D::new(x, y),
+                Point2D::new(x + size, y),
+                Point2D::new(x + size, y + size),
+                Point2D::new(x, y + size),
+                Point2D::new(x, y),
+            ]
+        };
+        let polygon = Polygon::new(square(0.0, 0.0, 10.0), vec![square(2.0, 2.0, 3.0)]);
+        assert_eq!(polygon.area(), 91.0);
+        assert_eq!(polygon.rings().count(), 2);
+    }
+}
@@ -0,0 +1,95 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! A plugin with one tool and one function, for the plugin tests of whitebox_common.
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+use whitebox_common::plugins::{Function, InvocationError, PluginRegistrar, Tool};
+
+/// Writes the value of --value to the --output file in the working directory.
+struct WriteValue;
+
+impl Tool for WriteValue {
+    fn get_tool_name(&self) -> String {
+        "WriteValue".to_string()
+    }
+
+    fn get_tool_description(&self) -> String {
+        "Writes a value to a text file.".to_string()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        r#"{"parameters": [
+            {"name": "Value", "flags": ["--value"], "description": "Value to write.",
+             "parameter_type": "String", "default_value": null, "optional": false},
+            {"name": "Output File", "flags": ["-o", "--output"], "description": "Output file.",
+             "parameter_type": {"NewFile": "Text"}, "default_value": null, "optional": false}
+        ]}"#
+        .to_string()
+    }
+
+    fn get_example_usage(&self) -> String {
+        ">>.*whitebox_tools -r=WriteValue --value=1 -o=value.txt".to_string()
+    }
+
+    fn get_toolbox(&self) -> String {
+        "Test Tools".to_string()
+    }
+
+    fn get_source_file(&self) -> String {
+        file!().to_string()
+    }
+
+    fn run(&self, args: Vec<String>, working_directory: &str, _verbose: bool) -> Result<(), Error> {
+        let find = |flag: &str| {
+            args.iter()
+                .find_map(|a| a.strip_prefix(flag).and_then(|v| v.strip_prefix('=')))
+                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("{} is required.", flag)))
+        };
+        let value = find("--value")?;
+        let output = find("--output")?;
+        fs::write(format!("{}{}", working_directory, output), value)
+    }
+}
+
+struct Hypot;
+
+impl Function for Hypot {
+    fn call(&self, args: &[f64]) -> Result<f64, InvocationError> {
+        if args.len() != 2 {
+            return Err(InvocationError::InvalidArgumentCount {
+                expected: 2,
+                found: args.len(),
+            });
+        }
+        Ok(args[0].hypot(args[1]))
+    }
+
+    fn help(&self) -> Option<&str> {
+        Some("hypot(x, y): the length of the hypotenuse of a right triangle.")
+    }
+}
+
+#[allow(dead_code, improper_ctypes_definitions)]
+unsafe extern "C" fn register(registrar: &mut dyn PluginRegistrar) {
+    registrar.register_tool(Box::new(WriteValue));
+    registrar.register_function("Hypot", Box::new(Hypot));
+}
+
+#[cfg(not(any(feature = "other_core_version", feature = "no_declaration")))]
+whitebox_common::export_plugin!(register);
+
+#[cfg(feature = "other_core_version")]
+#[no_mangle]
+pub static plugin_declaration: whitebox_common::plugins::PluginDeclaration =
+    whitebox_common::plugins::PluginDeclaration {
+        rustc_version: whitebox_common::plugins::RUSTC_VERSION,
+        core_version: "0.0.1",
+        register,
+    };
@@ -0,0 +1,158 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the loading of plugin libraries by `PluginManager`. The plugin in tests/plugin is
+//! built with the same cargo and whitebox_common as the tests, once as a plugin, once declaring
+//! another version of whitebox_common, and once without a declaration.
+
+use std::env::consts::{DLL_EXTENSION, DLL_PREFIX};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use whitebox_common::plugins::{InvocationError, PluginManager, CORE_VERSION};
+
+// The variants of the plugin are built in the same target directory, and each is copied
+// before the next is built.
+static BUILD_LOCK: Mutex<()> = Mutex::new(());
+
+/// Builds the test plugin with a feature, returning the path of a copy of the library.
+fn build_plugin(feature: Option<&str>) -> PathBuf {
+    let _lock = BUILD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("plugin");
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/plugin/Cargo.toml");
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .arg("--target-dir")
+        .arg(&target_dir);
+    if let Some(feature) = feature {
+        command.arg("--features").arg(feature);
+    }
+    let output = command.output().expect("Error running cargo.");
+    assert!(
+        output.status.success(),
+        "Error building the test plugin: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let file_name = format!("{}whitebox_test_plugin.{}", DLL_PREFIX, DLL_EXTENSION);
+    let copy = target_dir.join(format!(
+        "{}_{}",
+        feature.unwrap_or("plugin"),
+        file_name
+    ));
+    fs::copy(target_dir.join("debug").join(&file_name), &copy)
+        .expect("Error copying the test plugin.");
+    copy
+}
+
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "whitebox_plugins_test_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir
+}
+
+#[test]
+fn plugin_tools_and_functions_are_registered() {
+    let library = build_plugin(None);
+    let mut plugins = PluginManager::new();
+    unsafe { plugins.load(&library) }.expect("Error loading the test plugin.");
+    assert_eq!(plugins.get_tool_names(), vec!["WriteValue".to_string()]);
+
+    // Names are compared without regard to case or underscores.
+    let tool = plugins.get_tool("write_value").expect("The tool was not registered.");
+    assert_eq!(tool.get_toolbox(), "Test Tools");
+    let hypot = plugins.get_function("HYPOT").expect("The function was not registered.");
+    assert_eq!(hypot.call(&[3.0, 4.0]), Ok(5.0));
+    assert_eq!(
+        hypot.call(&[3.0]),
+        Err(InvocationError::InvalidArgumentCount {
+            expected: 2,
+            found: 1
+        })
+    );
+    assert!(hypot.help().unwrap().starts_with("hypot(x, y)"));
+    assert_eq!(plugins.get_functions().len(), 1);
+    assert!(plugins.get_tool("Hypot").is_none());
+
+    // A tool keeps its library loaded after the manager is dropped.
+    drop(plugins);
+    let dir = test_dir("run");
+    let working_dir = format!("{}{}", dir.to_string_lossy(), std::path::MAIN_SEPARATOR);
+    let args = vec!["--value=42".to_string(), "--output=value.txt".to_string()];
+    tool.run(args, &working_dir, false)
+        .expect("Error running the plugin tool.");
+    assert_eq!(fs::read_to_string(dir.join("value.txt")).unwrap(), "42");
+    let err = tool
+        .run(vec!["--value=42".to_string()], &working_dir, false)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn mismatched_and_invalid_libraries_are_rejected() {
+    let mut plugins = PluginManager::new();
+    let library = build_plugin(Some("other_core_version"));
+    let err = unsafe { plugins.load(&library) }.err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let message = err.to_string();
+    assert!(message.contains("whitebox_common 0.0.1"), "{}", message);
+    assert!(message.contains(&format!("whitebox_common {} are required", CORE_VERSION)));
+    // The register function of a mismatched plugin is not called.
+    assert!(plugins.get_tool_names().is_empty());
+    assert!(plugins.get_functions().is_empty());
+
+    let library = build_plugin(Some("no_declaration"));
+    let err = unsafe { plugins.load(&library) }.err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    let dir = test_dir("invalid");
+    let library = dir.join(format!("invalid.{}", DLL_EXTENSION));
+    fs::write(&library, "not a library").unwrap();
+    let err = unsafe { plugins.load(&library) }.err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().starts_with("Error loading plugin library"));
+    assert!(plugins.get_tool_names().is_empty());
+}
+
+#[test]
+fn plugin_directory_is_searched() {
+    let dir = test_dir("directory");
+    let copy = |library: PathBuf, name: &str| {
+        fs::copy(library, dir.join(format!("{}.{}", name, DLL_EXTENSION))).unwrap();
+    };
+    copy(build_plugin(None), "a_plugin");
+    copy(build_plugin(Some("other_core_version")), "b_other_version");
+    copy(build_plugin(Some("no_declaration")), "c_not_a_plugin");
+    fs::write(dir.join(format!("d_invalid.{}", DLL_EXTENSION)), "not a library").unwrap();
+    // Files without the library extension are not loaded.
+    fs::write(dir.join("e_plugin.json"), "{}").unwrap();
+
+    // The libraries that could not be loaded are reported, in order, and the others are
+    // loaded; a library that does not declare a plugin is skipped.
+    let mut plugins = PluginManager::new();
+    let errors = plugins.load_directory(&dir);
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors[0].to_string().contains("b_other_version"), "{}", errors[0]);
+    assert!(errors[1].to_string().starts_with("Error loading plugin library"));
+    assert_eq!(plugins.get_tool_names(), vec!["WriteValue".to_string()]);
+
+    // A directory that does not exist has no plugins.
+    let mut plugins = PluginManager::new();
+    assert!(plugins.load_directory(dir.join("missing")).is_empty());
+    assert!(plugins.get_tool_names().is_empty());
+}
@@ -0,0 +1,245 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Support for Cloud Optimized Point Cloud (COPC) files. A COPC file is a LAZ 1.4 file
+//! in which the points are organized into a clustered octree. Each node of the octree
+//! is stored as an independently compressed chunk and the nodes are located using a
+//! hierarchy of pages, which allows the points within an area of interest to be read
+//! without decompressing the rest of the file.
+
+use super::vlr::Vlr;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Error, ErrorKind, Read, Seek, SeekFrom};
+use whitebox_common::structures::BoundingBox;
+
+/// The contents of the COPC info VLR, which describes the cube bounding the octree and
+/// the location of the root hierarchy page.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct CopcInfo {
+    pub center_x: f64,
+    pub center_y: f64,
+    pub center_z: f64,
+    pub halfsize: f64,
+    pub spacing: f64,
+    pub root_hier_offset: u64,
+    pub root_hier_size: u64,
+    pub gpstime_minimum: f64,
+    pub gpstime_maximum: f64,
+}
+
+impl CopcInfo {
+    pub const USER_ID: &'static str = "copc";
+    pub const RECORD_ID: u16 = 1;
+
+    /// Returns true if `vlr` is a COPC info VLR.
+    pub fn is_info_vlr(vlr: &Vlr) -> bool {
+        vlr.user_id.trim_end_matches('\0') == CopcInfo::USER_ID
+            && vlr.record_id == CopcInfo::RECORD_ID
+    }
+
+    /// Parses the COPC info from the binary data of the COPC info VLR.
+    pub fn from_vlr(vlr: &Vlr) -> Result<CopcInfo, Error> {
+        if !CopcInfo::is_info_vlr(vlr) || vlr.binary_data.len() < 72 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The VLR is not a valid COPC info VLR.",
+            ));
+        }
+        let mut cursor = Cursor::new(&vlr.binary_data);
+        Ok(CopcInfo {
+            center_x: cursor.read_f64::<LittleEndian>()?,
+            center_y: cursor.read_f64::<LittleEndian>()?,
+            center_z: cursor.read_f64::<LittleEndian>()?,
+            halfsize: cursor.read_f64::<LittleEndian>()?,
+            spacing: cursor.read_f64::<LittleEndian>()?,
+            root_hier_offset: cursor.read_u64::<LittleEndian>()?,
+            root_hier_size: cursor.read_u64::<LittleEndian>()?,
+            gpstime_minimum: cursor.read_f64::<LittleEndian>()?,
+            gpstime_maximum: cursor.read_f64::<LittleEndian>()?,
+        })
+    }
+}
+
+/// Identifies a node of the octree by its depth (`level`) and its position within the
+/// grid of nodes at that depth.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VoxelKey {
+    pub level: i32,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl VoxelKey {
+    /// Returns the horizontal extent of the node.
+    pub fn bounds(&self, info: &CopcInfo) -> BoundingBox {
+        let side = 2.0 * info.halfsize / 2f64.powi(self.level);
+        let min_x = info.center_x - info.halfsize + self.x as f64 * side;
+        let min_y = info.center_y - info.halfsize + self.y as f64 * side;
+        BoundingBox::new(min_x, min_x + side, min_y, min_y + side)
+    }
+}
+
+/// The location of the compressed points of a node, or of a child hierarchy page when
+/// `point_count` is -1, as given by an entry of a hierarchy page.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct CopcEntry {
+    pub offset: u64,
+    pub byte_size: i32,
+    pub point_count: i32,
+}
+
+/// Reads the hierarchy of a COPC file and returns the entries of the nodes that contain
+/// points, intersect `bounds`, and are no deeper than `max_depth`, in file order. Child
+/// pages are only read when their root node meets these criteria, since the nodes of a
+/// page are all contained within its root node.
+pub(crate) fn read_hierarchy<R: Read + Seek>(
+    source: &mut R,
+    info: &CopcInfo,
+    bounds: BoundingBox,
+    max_depth: Option<u32>,
+) -> Result<Vec<CopcEntry>, Error> {
+    let mut entries = vec![];
+    let mut pages = vec![(info.root_hier_offset, info.root_hier_size)];
+    while let Some((offset, size)) = pages.pop() {
+        source.seek(SeekFrom::Start(offset))?;
+        let mut buffer = vec![0u8; size as usize];
+        source.read_exact(&mut buffer)?;
+        let mut cursor = Cursor::new(buffer);
+        for _ in 0..size / 32 {
+            let key = VoxelKey {
+                level: cursor.read_i32::<LittleEndian>()?,
+                x: cursor.read_i32::<LittleEndian>()?,
+                y: cursor.read_i32::<LittleEndian>()?,
+                z: cursor.read_i32::<LittleEndian>()?,
+            };
+            let entry = CopcEntry {
+                offset: cursor.read_u64::<LittleEndian>()?,
+                byte_size: cursor.read_i32::<LittleEndian>()?,
+                point_count: cursor.read_i32::<LittleEndian>()?,
+            };
+            if let Some(depth) = max_depth {
+                if key.level as i64 > depth as i64 {
+                    continue;
+                }
+            }
+            if !key.bounds(info).overlaps(bounds) {
+                continue;
+            }
+            if entry.point_count == -1 {
+                pages.push((entry.offset, entry.byte_size as u64));
+            } else if entry.point_count > 0 {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.sort_by_key(|entry| entry.offset);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use byteorder::WriteBytesExt;
+
+    fn write_entry(
+        page: &mut Vec<u8>,
+        key: (i32, i32, i32, i32),
+        offset: u64,
+        byte_size: i32,
+        point_count: i32,
+    ) {
+        for v in [key.0, key.1, key.2, key.3] {
+            page.write_i32::<LittleEndian>(v).unwrap();
+        }
+        page.write_u64::<LittleEndian>(offset).unwrap();
+        page.write_i32::<LittleEndian>(byte_size).unwrap();
+        page.write_i32::<LittleEndian>(point_count).unwrap();
+    }
+
+    // A hierarchy of a 100 x 100 cube, whose upper-right quadrant has a child page, and
+    // whose lower-left quadrant has a child page that is not valid and must not be read.
+    fn test_hierarchy() -> (Vec<u8>, CopcInfo) {
+        let mut root = vec![];
+        write_entry(&mut root, (0, 0, 0, 0), 1000, 10, 5);
+        write_entry(&mut root, (1, 1, 0, 0), 1010, 10, 3);
+        write_entry(&mut root, (1, 0, 1, 0), 1020, 10, 0);
+        write_entry(&mut root, (1, 1, 1, 0), 160, 96, -1);
+        write_entry(&mut root, (1, 0, 0, 0), 999_999, 32, -1);
+        let mut child = vec![];
+        write_entry(&mut child, (1, 1, 1, 0), 1030, 10, 4);
+        write_entry(&mut child, (2, 2, 2, 0), 1050, 10, 2);
+        write_entry(&mut child, (2, 3, 3, 1), 1040, 10, 1);
+        let mut data = root;
+        data.extend(child);
+        let info = CopcInfo {
+            center_x: 50.0,
+            center_y: 50.0,
+            center_z: 50.0,
+            halfsize: 50.0,
+            root_hier_offset: 0,
+            root_hier_size: 160,
+            ..Default::default()
+        };
+        (data, info)
+    }
+
+    fn read_offsets(bounds: BoundingBox, max_depth: Option<u32>) -> Vec<u64> {
+        let (data, info) = test_hierarchy();
+        read_hierarchy(&mut Cursor::new(data), &info, bounds, max_depth)
+            .unwrap()
+            .iter()
+            .map(|entry| entry.offset)
+            .collect()
+    }
+
+    #[test]
+    fn test_voxel_key_bounds() {
+        let (_, info) = test_hierarchy();
+        let key = VoxelKey {
+            level: 2,
+            x: 3,
+            y: 1,
+            z: 0,
+        };
+        let bounds = key.bounds(&info);
+        assert_eq!((bounds.min_x, bounds.max_x), (75.0, 100.0));
+        assert_eq!((bounds.min_y, bounds.max_y), (25.0, 50.0));
+    }
+
+    #[test]
+    fn test_hierarchy_entries_within_bounds() {
+        // nodes without points are skipped and the entries are sorted by offset
+        assert_eq!(
+            read_offsets(BoundingBox::new(60.0, 100.0, 0.0, 100.0), None),
+            vec![1000, 1010, 1030, 1040, 1050]
+        );
+        assert_eq!(
+            read_offsets(BoundingBox::new(60.0, 70.0, 60.0, 70.0), None),
+            vec![1000, 1030, 1050]
+        );
+        assert_eq!(
+            read_offsets(BoundingBox::new(60.0, 100.0, 0.0, 100.0), Some(1)),
+            vec![1000, 1010, 1030]
+        );
+        assert_eq!(
+            read_offsets(BoundingBox::new(60.0, 100.0, 0.0, 100.0), Some(0)),
+            vec![1000]
+        );
+    }
+
+    #[test]
+    fn test_child_pages_are_only_read_within_bounds() {
+        // the child page of the lower-left quadrant lies beyond the end of the data
+        let (data, info) = test_hierarchy();
+        let bounds = BoundingBox::new(60.0, 100.0, 60.0, 100.0);
+        assert!(read_hierarchy(&mut Cursor::new(data.clone()), &info, bounds, None).is_ok());
+        let bounds = BoundingBox::new(0.0, 40.0, 0.0, 40.0);
+        assert!(read_hierarchy(&mut Cursor::new(data), &info, bounds, None).is_err());
+    }
+}
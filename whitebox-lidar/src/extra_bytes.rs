@@ -0,0 +1,500 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Support for LAS 1.4 Extra Bytes, i.e. additional per-point attributes that are stored
+//! after the standard fields of each point record. The attributes are described by the
+//! Extra Bytes VLR (user ID 'LASF_Spec', record ID 4), which holds one 192-byte descriptor
+//! for each attribute, in the order that the attributes appear in the point records.
+
+use super::vlr::Vlr;
+use byteorder::{ByteOrder, LittleEndian};
+
+/// The data type of an extra bytes attribute.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExtraByteDataType {
+    /// Bytes without a documented meaning. The value is the number of bytes.
+    Undocumented(u8),
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    /// The two- and three-element array types (codes 11-30), which were deprecated in
+    /// LAS 1.4 R14. Their bytes are preserved but they cannot be decoded.
+    Deprecated(u8),
+}
+
+impl ExtraByteDataType {
+    /// Returns the data type for a descriptor's data type code and options byte. The options
+    /// byte holds the number of bytes of undocumented extra bytes.
+    pub fn from_code(code: u8, options: u8) -> ExtraByteDataType {
+        match code {
+            0 => ExtraByteDataType::Undocumented(options),
+            1 => ExtraByteDataType::U8,
+            2 => ExtraByteDataType::I8,
+            3 => ExtraByteDataType::U16,
+            4 => ExtraByteDataType::I16,
+            5 => ExtraByteDataType::U32,
+            6 => ExtraByteDataType::I32,
+            7 => ExtraByteDataType::U64,
+            8 => ExtraByteDataType::I64,
+            9 => ExtraByteDataType::F32,
+            10 => ExtraByteDataType::F64,
+            _ => ExtraByteDataType::Deprecated(code),
+        }
+    }
+
+    /// Returns the data type code used in the descriptor.
+    pub fn code(&self) -> u8 {
+        match self {
+            ExtraByteDataType::Undocumented(_) => 0,
+            ExtraByteDataType::U8 => 1,
+            ExtraByteDataType::I8 => 2,
+            ExtraByteDataType::U16 => 3,
+            ExtraByteDataType::I16 => 4,
+            ExtraByteDataType::U32 => 5,
+            ExtraByteDataType::I32 => 6,
+            ExtraByteDataType::U64 => 7,
+            ExtraByteDataType::I64 => 8,
+            ExtraByteDataType::F32 => 9,
+            ExtraByteDataType::F64 => 10,
+            ExtraByteDataType::Deprecated(code) => *code,
+        }
+    }
+
+    /// Returns the number of bytes occupied by the attribute in each point record.
+    pub fn size(&self) -> usize {
+        match self {
+            ExtraByteDataType::Undocumented(n) => *n as usize,
+            ExtraByteDataType::U8 | ExtraByteDataType::I8 => 1,
+            ExtraByteDataType::U16 | ExtraByteDataType::I16 => 2,
+            ExtraByteDataType::U32 | ExtraByteDataType::I32 | ExtraByteDataType::F32 => 4,
+            ExtraByteDataType::U64 | ExtraByteDataType::I64 | ExtraByteDataType::F64 => 8,
+            ExtraByteDataType::Deprecated(code) => {
+                if *code > 30 {
+                    // reserved codes have no defined size
+                    return 0;
+                }
+                let base = ExtraByteDataType::from_code((*code - 1) % 10 + 1, 0);
+                let num_elements = if *code <= 20 { 2 } else { 3 };
+                base.size() * num_elements
+            }
+        }
+    }
+
+    fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            ExtraByteDataType::I8
+                | ExtraByteDataType::I16
+                | ExtraByteDataType::I32
+                | ExtraByteDataType::I64
+        )
+    }
+
+    fn is_float(&self) -> bool {
+        *self == ExtraByteDataType::F32 || *self == ExtraByteDataType::F64
+    }
+}
+
+/// Describes one extra bytes attribute, as stored in the Extra Bytes VLR. The `no_data`,
+/// `min`, `max`, `scale`, and `offset` values are only meaningful if the corresponding
+/// bits of `options` are set; the `set_*` methods set both the value and its bit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtraByteDescriptor {
+    pub data_type: ExtraByteDataType,
+    pub options: u8,
+    pub name: String,
+    pub no_data: f64,
+    pub min: f64,
+    pub max: f64,
+    pub scale: f64,
+    pub offset: f64,
+    pub description: String,
+}
+
+impl ExtraByteDescriptor {
+    pub const USER_ID: &'static str = "LASF_Spec";
+    pub const RECORD_ID: u16 = 4;
+    /// The size of a descriptor in the Extra Bytes VLR.
+    pub const LENGTH: usize = 192;
+
+    const NO_DATA_BIT: u8 = 0b0000_0001;
+    const MIN_BIT: u8 = 0b0000_0010;
+    const MAX_BIT: u8 = 0b0000_0100;
+    const SCALE_BIT: u8 = 0b0000_1000;
+    const OFFSET_BIT: u8 = 0b0001_0000;
+
+    /// Creates a descriptor for an attribute without a no-data value, range, scale, or offset.
+    /// Names and descriptions are limited to 32 characters.
+    pub fn new(name: &str, data_type: ExtraByteDataType, description: &str) -> ExtraByteDescriptor {
+        ExtraByteDescriptor {
+            data_type,
+            options: match data_type {
+                ExtraByteDataType::Undocumented(n) => n,
+                _ => 0u8,
+            },
+            name: name.chars().take(32).collect(),
+            no_data: 0f64,
+            min: 0f64,
+            max: 0f64,
+            scale: 1f64,
+            offset: 0f64,
+            description: description.chars().take(32).collect(),
+        }
+    }
+
+    /// Returns the number of bytes occupied by the attribute in each point record.
+    pub fn size(&self) -> usize {
+        self.data_type.size()
+    }
+
+    /// Returns the no-data value, if one is set.
+    pub fn get_no_data(&self) -> Option<f64> {
+        if self.is_documented() && self.options & ExtraByteDescriptor::NO_DATA_BIT != 0 {
+            Some(self.no_data)
+        } else {
+            None
+        }
+    }
+
+    pub fn set_no_data(&mut self, value: f64) {
+        self.no_data = value;
+        self.options |= ExtraByteDescriptor::NO_DATA_BIT;
+    }
+
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.min = min;
+        self.max = max;
+        self.options |= ExtraByteDescriptor::MIN_BIT | ExtraByteDescriptor::MAX_BIT;
+    }
+
+    /// Sets the scale and offset applied to the stored values, i.e. value = stored * scale + offset.
+    pub fn set_scale_and_offset(&mut self, scale: f64, offset: f64) {
+        self.scale = scale;
+        self.offset = offset;
+        self.options |= ExtraByteDescriptor::SCALE_BIT | ExtraByteDescriptor::OFFSET_BIT;
+    }
+
+    fn is_documented(&self) -> bool {
+        !matches!(
+            self.data_type,
+            ExtraByteDataType::Undocumented(_) | ExtraByteDataType::Deprecated(_)
+        )
+    }
+
+    fn get_scale(&self) -> f64 {
+        if self.is_documented() && self.options & ExtraByteDescriptor::SCALE_BIT != 0 {
+            self.scale
+        } else {
+            1f64
+        }
+    }
+
+    fn get_offset(&self) -> f64 {
+        if self.is_documented() && self.options & ExtraByteDescriptor::OFFSET_BIT != 0 {
+            self.offset
+        } else {
+            0f64
+        }
+    }
+
+    /// Decodes the value of the attribute from its bytes within a point record, applying the
+    /// scale and offset. Returns `None` for undocumented and deprecated data types.
+    pub fn decode(&self, bytes: &[u8]) -> Option<f64> {
+        if bytes.len() < self.size() {
+            return None;
+        }
+        let raw = match self.data_type {
+            ExtraByteDataType::U8 => bytes[0] as f64,
+            ExtraByteDataType::I8 => bytes[0] as i8 as f64,
+            ExtraByteDataType::U16 => LittleEndian::read_u16(bytes) as f64,
+            ExtraByteDataType::I16 => LittleEndian::read_i16(bytes) as f64,
+            ExtraByteDataType::U32 => LittleEndian::read_u32(bytes) as f64,
+            ExtraByteDataType::I32 => LittleEndian::read_i32(bytes) as f64,
+            ExtraByteDataType::U64 => LittleEndian::read_u64(bytes) as f64,
+            ExtraByteDataType::I64 => LittleEndian::read_i64(bytes) as f64,
+            ExtraByteDataType::F32 => LittleEndian::read_f32(bytes) as f64,
+            ExtraByteDataType::F64 => LittleEndian::read_f64(bytes),
+            _ => return None,
+        };
+        Some(raw * self.get_scale() + self.get_offset())
+    }
+
+    /// Encodes `value` into the bytes of the attribute within a point record, removing the
+    /// scale and offset. Values are rounded to the nearest integer, and saturated at the limits
+    /// of the data type, for integer data types. Undocumented and deprecated data types are
+    /// left unchanged.
+    pub fn encode(&self, value: f64, bytes: &mut [u8]) {
+        if bytes.len() < self.size() {
+            return;
+        }
+        let mut raw = (value - self.get_offset()) / self.get_scale();
+        if !self.data_type.is_float() {
+            raw = raw.round();
+        }
+        match self.data_type {
+            ExtraByteDataType::U8 => bytes[0] = raw as u8,
+            ExtraByteDataType::I8 => bytes[0] = raw as i8 as u8,
+            ExtraByteDataType::U16 => LittleEndian::write_u16(bytes, raw as u16),
+            ExtraByteDataType::I16 => LittleEndian::write_i16(bytes, raw as i16),
+            ExtraByteDataType::U32 => LittleEndian::write_u32(bytes, raw as u32),
+            ExtraByteDataType::I32 => LittleEndian::write_i32(bytes, raw as i32),
+            ExtraByteDataType::U64 => LittleEndian::write_u64(bytes, raw as u64),
+            ExtraByteDataType::I64 => LittleEndian::write_i64(bytes, raw as i64),
+            ExtraByteDataType::F32 => LittleEndian::write_f32(bytes, raw as f32),
+            ExtraByteDataType::F64 => LittleEndian::write_f64(bytes, raw),
+            _ => {}
+        }
+    }
+
+    // The no-data, min, and max values are stored using the type of the attribute, widened
+    // to 8 bytes, i.e. as a u64, i64, or f64.
+    fn read_any_type(&self, bytes: &[u8]) -> f64 {
+        if self.data_type.is_float() {
+            LittleEndian::read_f64(bytes)
+        } else if self.data_type.is_signed() {
+            LittleEndian::read_i64(bytes) as f64
+        } else {
+            LittleEndian::read_u64(bytes) as f64
+        }
+    }
+
+    fn write_any_type(&self, value: f64, bytes: &mut [u8]) {
+        if self.data_type.is_float() {
+            LittleEndian::write_f64(bytes, value);
+        } else if self.data_type.is_signed() {
+            LittleEndian::write_i64(bytes, value as i64);
+        } else {
+            LittleEndian::write_u64(bytes, value as u64);
+        }
+    }
+
+    /// Parses a descriptor from its 192 bytes within the Extra Bytes VLR.
+    pub fn from_bytes(bytes: &[u8]) -> ExtraByteDescriptor {
+        let options = bytes[3];
+        let mut descriptor = ExtraByteDescriptor {
+            data_type: ExtraByteDataType::from_code(bytes[2], options),
+            options,
+            name: fixed_length_str(&bytes[4..36]),
+            no_data: 0f64,
+            min: 0f64,
+            max: 0f64,
+            scale: 1f64,
+            offset: 0f64,
+            description: fixed_length_str(&bytes[160..192]),
+        };
+        // The remaining fields are zeroed, rather than defaulted, for undocumented bytes.
+        if descriptor.is_documented() {
+            descriptor.no_data = descriptor.read_any_type(&bytes[40..48]);
+            descriptor.min = descriptor.read_any_type(&bytes[64..72]);
+            descriptor.max = descriptor.read_any_type(&bytes[88..96]);
+            descriptor.scale = LittleEndian::read_f64(&bytes[112..120]);
+            descriptor.offset = LittleEndian::read_f64(&bytes[136..144]);
+        }
+        descriptor
+    }
+
+    /// Returns the 192 bytes of the descriptor within the Extra Bytes VLR.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; ExtraByteDescriptor::LENGTH];
+        bytes[2] = self.data_type.code();
+        bytes[3] = self.options;
+        copy_str(&self.name, &mut bytes[4..36]);
+        if self.is_documented() {
+            self.write_any_type(self.no_data, &mut bytes[40..48]);
+            self.write_any_type(self.min, &mut bytes[64..72]);
+            self.write_any_type(self.max, &mut bytes[88..96]);
+            LittleEndian::write_f64(&mut bytes[112..120], self.scale);
+            LittleEndian::write_f64(&mut bytes[136..144], self.offset);
+        }
+        copy_str(&self.description, &mut bytes[160..192]);
+        bytes
+    }
+
+    /// Returns true if `vlr` is an Extra Bytes VLR.
+    pub fn is_extra_bytes_vlr(vlr: &Vlr) -> bool {
+        vlr.user_id.trim_end_matches('\0').trim() == ExtraByteDescriptor::USER_ID
+            && vlr.record_id == ExtraByteDescriptor::RECORD_ID
+    }
+
+    /// Parses the descriptors of an Extra Bytes VLR.
+    pub fn from_vlr(vlr: &Vlr) -> Vec<ExtraByteDescriptor> {
+        vlr.binary_data
+            .chunks_exact(ExtraByteDescriptor::LENGTH)
+            .map(ExtraByteDescriptor::from_bytes)
+            .collect()
+    }
+
+    /// Creates an Extra Bytes VLR holding `descriptors`.
+    pub fn to_vlr(descriptors: &[ExtraByteDescriptor]) -> Vlr {
+        let mut binary_data = Vec::with_capacity(descriptors.len() * ExtraByteDescriptor::LENGTH);
+        for descriptor in descriptors {
+            binary_data.extend(descriptor.to_bytes());
+        }
+        Vlr {
+            reserved: 0u16,
+            user_id: ExtraByteDescriptor::USER_ID.to_string(),
+            record_id: ExtraByteDescriptor::RECORD_ID,
+            record_length_after_header: binary_data.len() as u16,
+            description: "Extra Bytes Record".to_string(),
+            binary_data,
+        }
+    }
+}
+
+fn fixed_length_str(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string()
+}
+
+fn copy_str(s: &str, bytes: &mut [u8]) {
+    let n = s.len().min(bytes.len());
+    bytes[..n].copy_from_slice(&s.as_bytes()[..n]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DATA_TYPES: [ExtraByteDataType; 10] = [
+        ExtraByteDataType::U8,
+        ExtraByteDataType::I8,
+        ExtraByteDataType::U16,
+        ExtraByteDataType::I16,
+        ExtraByteDataType::U32,
+        ExtraByteDataType::I32,
+        ExtraByteDataType::U64,
+        ExtraByteDataType::I64,
+        ExtraByteDataType::F32,
+        ExtraByteDataType::F64,
+    ];
+
+    #[test]
+    fn test_data_type_codes() {
+        for data_type in DATA_TYPES {
+            assert_eq!(ExtraByteDataType::from_code(data_type.code(), 0), data_type);
+        }
+        assert_eq!(
+            ExtraByteDataType::from_code(0, 5),
+            ExtraByteDataType::Undocumented(5)
+        );
+        assert_eq!(ExtraByteDataType::Undocumented(5).size(), 5);
+        // deprecated arrays: 2 x i16, 3 x f64, and a reserved code
+        assert_eq!(ExtraByteDataType::from_code(14, 0).size(), 4);
+        assert_eq!(ExtraByteDataType::from_code(30, 0).size(), 24);
+        assert_eq!(ExtraByteDataType::from_code(31, 0).size(), 0);
+    }
+
+    #[test]
+    fn test_descriptor_bytes_round_trip() {
+        for data_type in DATA_TYPES {
+            let mut descriptor = ExtraByteDescriptor::new("height", data_type, "Height above ground");
+            descriptor.set_no_data(-1.0);
+            descriptor.set_range(0.0, 120.0);
+            descriptor.set_scale_and_offset(0.01, 100.0);
+            let bytes = descriptor.to_bytes();
+            assert_eq!(bytes.len(), ExtraByteDescriptor::LENGTH);
+            assert_eq!(bytes[2], data_type.code());
+            assert_eq!(bytes[3], 0b0001_1111);
+            assert_eq!(&bytes[4..10], b"height");
+            assert_eq!(&bytes[160..179], b"Height above ground");
+            let parsed = ExtraByteDescriptor::from_bytes(&bytes);
+            if data_type.is_signed() || data_type.is_float() {
+                assert_eq!(parsed, descriptor, "{:?}", data_type);
+            } else {
+                // an unsigned no-data value saturates at zero
+                assert_eq!(parsed.get_no_data(), Some(0.0), "{:?}", data_type);
+                assert_eq!(parsed.max, 120.0, "{:?}", data_type);
+            }
+        }
+
+        // Undocumented bytes keep their length, and nothing else, in the options byte.
+        let descriptor = ExtraByteDescriptor::new("", ExtraByteDataType::Undocumented(3), "");
+        let parsed = ExtraByteDescriptor::from_bytes(&descriptor.to_bytes());
+        assert_eq!(parsed.data_type, ExtraByteDataType::Undocumented(3));
+        assert_eq!(parsed.size(), 3);
+        assert_eq!(parsed.get_no_data(), None);
+
+        // Names and descriptions are limited to 32 characters.
+        let long_name = "a".repeat(40);
+        let descriptor = ExtraByteDescriptor::new(&long_name, ExtraByteDataType::U8, &long_name);
+        let parsed = ExtraByteDescriptor::from_bytes(&descriptor.to_bytes());
+        assert_eq!(parsed.name, "a".repeat(32));
+        assert_eq!(parsed.description, "a".repeat(32));
+    }
+
+    #[test]
+    fn test_vlr_round_trip() {
+        let mut descriptors = vec![
+            ExtraByteDescriptor::new("a", ExtraByteDataType::I16, ""),
+            ExtraByteDescriptor::new("b", ExtraByteDataType::F64, "second"),
+            ExtraByteDescriptor::new("", ExtraByteDataType::Undocumented(2), ""),
+        ];
+        descriptors[0].set_scale_and_offset(0.1, -5.0);
+        let vlr = ExtraByteDescriptor::to_vlr(&descriptors);
+        assert!(ExtraByteDescriptor::is_extra_bytes_vlr(&vlr));
+        assert_eq!(
+            vlr.record_length_after_header as usize,
+            3 * ExtraByteDescriptor::LENGTH
+        );
+        assert_eq!(ExtraByteDescriptor::from_vlr(&vlr), descriptors);
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let values = [0.0, 1.0, 100.0, -100.0, 12_345.0, -12_345.0, 1e6, -1e6];
+        for data_type in DATA_TYPES {
+            let mut descriptor = ExtraByteDescriptor::new("v", data_type, "");
+            descriptor.set_scale_and_offset(0.5, 10.0);
+            let mut bytes = vec![0u8; descriptor.size()];
+            for value in values {
+                descriptor.encode(value, &mut bytes);
+                let raw = (value - 10.0) / 0.5;
+                let (min, max) = match data_type {
+                    ExtraByteDataType::U8 => (0.0, u8::MAX as f64),
+                    ExtraByteDataType::I8 => (i8::MIN as f64, i8::MAX as f64),
+                    ExtraByteDataType::U16 => (0.0, u16::MAX as f64),
+                    ExtraByteDataType::I16 => (i16::MIN as f64, i16::MAX as f64),
+                    ExtraByteDataType::U32 | ExtraByteDataType::U64 => (0.0, f64::MAX),
+                    _ => (f64::MIN, f64::MAX),
+                };
+                let expected = raw.max(min).min(max) * 0.5 + 10.0;
+                assert_eq!(descriptor.decode(&bytes), Some(expected), "{:?} {}", data_type, value);
+            }
+        }
+
+        // Integer values are rounded, and the scale and offset only apply if their bits are set.
+        let mut descriptor = ExtraByteDescriptor::new("v", ExtraByteDataType::I32, "");
+        let mut bytes = [0u8; 4];
+        descriptor.encode(2.6, &mut bytes);
+        assert_eq!(descriptor.decode(&bytes), Some(3.0));
+        descriptor.scale = 0.01;
+        assert_eq!(descriptor.decode(&bytes), Some(3.0));
+        descriptor.set_scale_and_offset(0.01, 0.0);
+        assert_eq!(descriptor.decode(&bytes), Some(0.03));
+
+        // Undocumented bytes cannot be decoded and are not changed by encoding.
+        let descriptor = ExtraByteDescriptor::new("", ExtraByteDataType::Undocumented(2), "");
+        let mut bytes = [7u8, 9u8];
+        descriptor.encode(1.0, &mut bytes);
+        assert_eq!(bytes, [7u8, 9u8]);
+        assert_eq!(descriptor.decode(&bytes), None);
+        assert_eq!(
+            ExtraByteDescriptor::new("", ExtraByteDataType::U16, "").decode(&[1u8]),
+            None
+        );
+    }
+}
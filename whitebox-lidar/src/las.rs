@@ -9,12 +9,14 @@ License: MIT
 #![allow(dead_code, unused_assignments)]
 extern crate brotli;
 extern crate las;
+use super::copc::{read_hierarchy, CopcInfo};
+use super::extra_bytes::{ExtraByteDataType, ExtraByteDescriptor};
 use super::header::LasHeader;
 use super::point_data::{ ColourData, PointData, WaveformPacket };
 use super::vlr::Vlr;
 use super::zlidar_compression::{ZlidarCompression};
 use whitebox_raster::geotiff::geokeys::GeoKeys;
-use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::spatial_ref_system::{esri_wkt_from_epsg, SpatialReference};
 use whitebox_common::structures::{ BoundingBox, Point3D };
 use whitebox_common::utils::{ ByteOrderReader, Endianness };
 use byteorder::{ LittleEndian, WriteBytesExt };
@@ -29,7 +31,7 @@ use std::fmt;
 use std::fs;
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::{BufWriter, Cursor, Error, ErrorKind, Read, Seek};
+use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, Read, Seek, SeekFrom};
 use std::mem;
 use std::ops::Index;
 use std::path::Path;
@@ -49,18 +51,25 @@ use las::Write as OtherWrite;
 use las::Writer as OtherWriter;
 use las::raw::point::ScanAngle;
 use las::raw::vlr::RecordLength;
+use laz::record::{LayeredPointRecordDecompressor, RecordDecompressor};
+
+/// The lengths of the point records of each point format, excluding any extra bytes.
+const LAS_RECORD_LENGTHS: [u16; 11] = [20, 28, 26, 34, 57, 63, 30, 36, 38, 59, 67];
 
 #[derive(Default, Clone)]
 pub struct LasFile {
-    file_name: String,
+    pub(crate) file_name: String,
     file_mode: String,
     pub header: LasHeader,
     pub vlr_data: Vec<Vlr>,
-    point_data: Vec<PointData>,
+    pub(crate) point_data: Vec<PointData>,
     // point_buffer_size: usize,
-    gps_data: Vec<f64>,
-    colour_data: Vec<ColourData>,
-    waveform_data: Vec<WaveformPacket>,
+    pub(crate) gps_data: Vec<f64>,
+    pub(crate) colour_data: Vec<ColourData>,
+    pub(crate) waveform_data: Vec<WaveformPacket>,
+    pub(crate) extra_byte_descriptors: Vec<ExtraByteDescriptor>,
+    pub(crate) extra_bytes_length: usize,
+    pub(crate) extra_bytes: Vec<u8>,
     pub geokeys: GeoKeys,
     pub wkt: String,
     // starting_point: usize,
@@ -133,10 +142,15 @@ impl LasFile {
 
         output.add_header(input.header.clone());
 
-        // Copy the VLRs
+        // Copy the VLRs. The output is not a COPC file, even if the input is.
         for i in 0..(input.header.number_of_vlrs as usize) {
+            if CopcInfo::is_info_vlr(&input.vlr_data[i]) {
+                continue;
+            }
             output.add_vlr(input.vlr_data[i].clone());
         }
+        output.extra_byte_descriptors = input.extra_byte_descriptors.clone();
+        output.extra_bytes_length = input.extra_bytes_length;
 
         output
     }
@@ -363,6 +377,21 @@ impl LasFile {
         if which_return <= 5 {
             self.header.number_of_points_by_return[which_return - 1] += 1;
         }
+
+        // The extra bytes of the point are zeroed; see set_extra_bytes() and set_extra_attribute().
+        if self.extra_bytes_length > 0 {
+            self.extra_bytes
+                .resize(self.point_data.len() * self.extra_bytes_length, 0u8);
+        }
+    }
+
+    /// Adds a point record along with its extra bytes, e.g. those of a point from the input
+    /// file that the output was initialized with.
+    pub fn add_point_record_with_extra_bytes(&mut self, point: LidarPointRecord, extra_bytes: &[u8]) {
+        self.add_point_record(point);
+        if self.extra_bytes_length > 0 && !self.point_data.is_empty() {
+            self.set_extra_bytes(self.point_data.len() - 1, extra_bytes);
+        }
     }
 
     pub fn get_record(&self, index: usize) -> LidarPointRecord {
@@ -492,6 +521,186 @@ impl LasFile {
         self.gps_data.len() > 0
     }
 
+    /// Returns true if the point records contain extra bytes, whether or not they are
+    /// described by an Extra Bytes VLR.
+    pub fn has_extra_bytes(&self) -> bool {
+        self.extra_bytes_length > 0
+    }
+
+    /// Returns the descriptors of the extra bytes attributes, in the order that the
+    /// attributes are stored within the point records.
+    pub fn get_extra_byte_descriptors(&self) -> &[ExtraByteDescriptor] {
+        &self.extra_byte_descriptors
+    }
+
+    /// Returns the extra bytes of a point record, which are empty if the file has none.
+    pub fn get_extra_bytes(&self, index: usize) -> &[u8] {
+        let start = index * self.extra_bytes_length;
+        &self.extra_bytes[start..start + self.extra_bytes_length]
+    }
+
+    /// Replaces the extra bytes of a point record. The bytes are truncated, or padded with
+    /// zeros, to the length of the extra bytes of the file.
+    pub fn set_extra_bytes(&mut self, index: usize, bytes: &[u8]) {
+        let start = index * self.extra_bytes_length;
+        let record = &mut self.extra_bytes[start..start + self.extra_bytes_length];
+        let n = bytes.len().min(record.len());
+        record[..n].copy_from_slice(&bytes[..n]);
+        for b in &mut record[n..] {
+            *b = 0u8;
+        }
+    }
+
+    /// Returns the position of the named attribute within the extra bytes, and its descriptor.
+    fn find_extra_attribute(&self, name: &str) -> Option<(usize, &ExtraByteDescriptor)> {
+        let mut offset = 0usize;
+        for descriptor in &self.extra_byte_descriptors {
+            if descriptor.name == name {
+                return Some((offset, descriptor));
+            }
+            offset += descriptor.size();
+        }
+        None
+    }
+
+    /// Returns the value of the named extra bytes attribute of a point, with the scale and
+    /// offset of the attribute applied, or `None` if there is no such attribute or its data
+    /// type cannot be decoded. Any no-data value is returned as is.
+    pub fn get_extra_attribute(&self, index: usize, name: &str) -> Option<f64> {
+        let (offset, descriptor) = self.find_extra_attribute(name)?;
+        let start = index * self.extra_bytes_length + offset;
+        descriptor.decode(&self.extra_bytes[start..start + descriptor.size()])
+    }
+
+    /// Sets the value of the named extra bytes attribute of a point.
+    pub fn set_extra_attribute(&mut self, index: usize, name: &str, value: f64) -> Result<(), Error> {
+        let (offset, descriptor) = match self.find_extra_attribute(name) {
+            Some((offset, descriptor)) => (offset, descriptor.clone()),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("The file does not have an extra bytes attribute named '{}'.", name),
+                ))
+            }
+        };
+        let start = index * self.extra_bytes_length + offset;
+        descriptor.encode(value, &mut self.extra_bytes[start..start + descriptor.size()]);
+        Ok(())
+    }
+
+    /// Adds an extra bytes attribute to the point records, after any existing extra bytes,
+    /// and updates the Extra Bytes VLR. The attribute of any existing points is set to the
+    /// no-data value of the descriptor, if it has one, or to zero otherwise. Tools can use
+    /// this to output computed values (e.g. normals or heights) without overwriting the
+    /// standard point fields.
+    pub fn add_extra_attribute(&mut self, descriptor: ExtraByteDescriptor) -> Result<(), Error> {
+        if descriptor.size() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An extra bytes attribute must occupy at least one byte.",
+            ));
+        }
+        if self.find_extra_attribute(&descriptor.name).is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The file already has an extra bytes attribute named '{}'.", descriptor.name),
+            ));
+        }
+
+        // Any undocumented bytes at the end of the records must be described so that the
+        // new attribute can be located.
+        let mut documented_length: usize = self.extra_byte_descriptors.iter().map(|d| d.size()).sum();
+        let mut i = 1;
+        while documented_length < self.extra_bytes_length {
+            let n = (self.extra_bytes_length - documented_length).min(255);
+            self.extra_byte_descriptors.push(ExtraByteDescriptor::new(
+                &format!("undocumented_{}", i),
+                ExtraByteDataType::Undocumented(n as u8),
+                "",
+            ));
+            documented_length += n;
+            i += 1;
+        }
+
+        let old_length = self.extra_bytes_length;
+        let new_length = old_length + descriptor.size();
+        if LAS_RECORD_LENGTHS[self.header.point_format as usize % 11] as usize + new_length > u16::MAX as usize {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The point records cannot hold any more extra bytes.",
+            ));
+        }
+        let mut new_bytes = vec![0u8; descriptor.size()];
+        if let Some(no_data) = descriptor.get_no_data() {
+            descriptor.encode(no_data, &mut new_bytes);
+        }
+        let num_points = self.point_data.len();
+        let mut extra_bytes = Vec::with_capacity(num_points * new_length);
+        for i in 0..num_points {
+            extra_bytes.extend_from_slice(&self.extra_bytes[i * old_length..(i + 1) * old_length]);
+            extra_bytes.extend_from_slice(&new_bytes);
+        }
+        self.extra_bytes = extra_bytes;
+        self.extra_bytes_length = new_length;
+        self.extra_byte_descriptors.push(descriptor);
+        self.sync_extra_bytes_vlr();
+        Ok(())
+    }
+
+    /// Determines the number of extra bytes at the end of each point record, from the record
+    /// length, and reads the descriptors of the Extra Bytes VLR. Descriptors that do not fit
+    /// within the extra bytes are ignored.
+    fn read_extra_bytes_layout(&mut self) {
+        let standard_length = LAS_RECORD_LENGTHS[self.header.point_format as usize % 11] as usize;
+        self.extra_bytes_length = (self.header.point_record_length as usize).saturating_sub(standard_length);
+        self.extra_byte_descriptors = vec![];
+        if let Some(vlr) = self.vlr_data.iter().find(|vlr| ExtraByteDescriptor::is_extra_bytes_vlr(vlr)) {
+            let mut length = 0usize;
+            for descriptor in ExtraByteDescriptor::from_vlr(vlr) {
+                length += descriptor.size();
+                if length > self.extra_bytes_length {
+                    break;
+                }
+                self.extra_byte_descriptors.push(descriptor);
+            }
+        }
+        self.extra_bytes = vec![];
+    }
+
+    /// Appends the extra bytes of the point record at the reader's position.
+    fn read_extra_bytes<R: Read + Seek>(&mut self, bor: &mut ByteOrderReader<R>) -> Result<(), Error> {
+        let start = self.extra_bytes.len();
+        self.extra_bytes.resize(start + self.extra_bytes_length, 0u8);
+        bor.read_exact(&mut self.extra_bytes[start..])
+    }
+
+    /// Makes the Extra Bytes VLR agree with the extra bytes descriptors, before the file is
+    /// written. zLidar files cannot hold extra bytes and so they are removed.
+    pub(crate) fn sync_extra_bytes_vlr(&mut self) {
+        if self.file_name.to_lowercase().ends_with(".zlidar") && self.extra_bytes_length > 0 {
+            println!("Warning: zLidar files do not support extra bytes. The extra bytes attributes will be lost.");
+            self.extra_byte_descriptors = vec![];
+            self.extra_bytes_length = 0;
+            self.extra_bytes = vec![];
+        }
+        let position = self.vlr_data.iter().position(ExtraByteDescriptor::is_extra_bytes_vlr);
+        match position {
+            Some(i) => {
+                if self.extra_byte_descriptors.is_empty() {
+                    self.vlr_data.remove(i);
+                } else if ExtraByteDescriptor::from_vlr(&self.vlr_data[i]) != self.extra_byte_descriptors {
+                    self.vlr_data[i] = ExtraByteDescriptor::to_vlr(&self.extra_byte_descriptors);
+                }
+            }
+            None => {
+                if !self.extra_byte_descriptors.is_empty() {
+                    self.vlr_data.push(ExtraByteDescriptor::to_vlr(&self.extra_byte_descriptors));
+                }
+            }
+        }
+        self.header.number_of_vlrs = self.vlr_data.len() as u32;
+    }
+
     pub fn get_short_filename(&self) -> String {
         let path = Path::new(&self.file_name);
         let file_name = path.file_stem().unwrap();
@@ -520,6 +729,63 @@ impl LasFile {
         self.geokeys.find_epsg_code()
     }
 
+    /// Replaces the coordinate reference system of the file with the one identified by an
+    /// EPSG code. Existing GeoTIFF key and WKT VLRs are removed and a new projection VLR
+    /// is added, using WKT if the global encoding says so and GeoTIFF keys otherwise.
+    pub fn set_crs_from_epsg(&mut self, epsg_code: u16) -> Result<(), Error> {
+        let srs = SpatialReference::from_epsg(epsg_code)?;
+        let wkt = esri_wkt_from_epsg(epsg_code);
+
+        self.vlr_data.retain(|vlr| {
+            vlr.record_id != 34_735
+                && vlr.record_id != 34_736
+                && vlr.record_id != 34_737
+                && vlr.record_id != 2112
+        });
+        self.header.number_of_vlrs = self.vlr_data.len() as u32;
+
+        let (record_id, description, binary_data) =
+            match self.header.global_encoding.coordinate_reference_system_method() {
+                CoordinateReferenceSystem::WellKnownText => {
+                    let mut data = wkt.as_bytes().to_vec();
+                    data.push(0u8);
+                    (2112u16, "OGC Coordinate System WKT", data)
+                }
+                CoordinateReferenceSystem::GeoTiff => {
+                    // GTModelType, GTRasterType (pixel is area), and the geographic or
+                    // projected coordinate system type
+                    let (model_type, cs_key) = if srs.is_geographic() {
+                        (2u16, 2048u16)
+                    } else {
+                        (1u16, 3072u16)
+                    };
+                    let keys: Vec<u16> = vec![
+                        1, 1, 0, 3, 1024, 0, 1, model_type, 1025, 0, 1, 1, cs_key, 0, 1, epsg_code,
+                    ];
+                    let data = keys.iter().flat_map(|k| k.to_le_bytes().to_vec()).collect();
+                    (34_735u16, "GeoTiff GeoKeyDirectoryTag", data)
+                }
+            };
+        let vlr = Vlr {
+            reserved: 0u16,
+            user_id: "LASF_Projection".to_string(),
+            record_id,
+            record_length_after_header: binary_data.len() as u16,
+            description: description.to_string(),
+            binary_data,
+        };
+
+        self.geokeys = GeoKeys::default();
+        if vlr.record_id == 34_735 {
+            self.geokeys
+                .add_key_directory(&vlr.binary_data, Endianness::LittleEndian);
+        }
+        self.wkt = wkt;
+        self.vlr_data.push(vlr);
+        self.header.number_of_vlrs += 1;
+        Ok(())
+    }
+
     pub fn read(&mut self) -> Result<(), Error> {
         if self.file_name.to_lowercase().ends_with(".zlidar") {
             return self.read_zlidar_data();
@@ -537,7 +803,11 @@ impl LasFile {
                 //     375 // the size of the header
                 // };
 
-                let file_size: usize = metadata.len() as usize;
+                let mut file_size: usize = metadata.len() as usize;
+                if self.file_mode == "rh" {
+                    // Only the header and VLRs are needed, i.e. the bytes before the point data.
+                    file_size = header_and_vlrs_size(&mut f, file_size)?;
+                }
 
                 let mut buffer = vec![0; file_size]; // Vec::with_capacity(file_size);
                 if file_size < 1024 * 1024 * 500 {
@@ -738,6 +1008,7 @@ impl LasFile {
             }
             self.vlr_data.push(vlr);
         }
+        self.read_extra_bytes_layout();
 
         if self.file_mode != "rh" {
             // file_mode = "rh" does not read points, only the header and VLR data.
@@ -825,7 +1096,7 @@ impl LasFile {
                     p.point_source_id = bor.read_u16()?;
                     self.point_data.push(p);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 1 {
@@ -848,7 +1119,7 @@ impl LasFile {
                     // read the GPS data
                     self.gps_data.push(bor.read_f64()?);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 2 {
@@ -875,7 +1146,7 @@ impl LasFile {
                     rgb.blue = bor.read_u16()?;
                     self.colour_data.push(rgb);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 3 {
@@ -906,7 +1177,7 @@ impl LasFile {
                     rgb.blue = bor.read_u16()?;
                     self.colour_data.push(rgb);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 4 {
@@ -941,7 +1212,7 @@ impl LasFile {
                     wfp.zt = bor.read_f32()?;
                     self.waveform_data.push(wfp);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 5 {
@@ -983,7 +1254,7 @@ impl LasFile {
                     wfp.zt = bor.read_f32()?;
                     self.waveform_data.push(wfp);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 6 {
@@ -1009,7 +1280,7 @@ impl LasFile {
                     // read the GPS data
                     self.gps_data.push(bor.read_f64()?);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 7 {
@@ -1042,7 +1313,7 @@ impl LasFile {
                     rgb.blue = bor.read_u16()?;
                     self.colour_data.push(rgb);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 8 {
@@ -1077,7 +1348,7 @@ impl LasFile {
                     rgb.nir = bor.read_u16()?;
                     self.colour_data.push(rgb);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 9 {
@@ -1116,7 +1387,7 @@ impl LasFile {
                     wfp.zt = bor.read_f32()?;
                     self.waveform_data.push(wfp);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             } else if self.header.point_format == 10 {
@@ -1163,7 +1434,7 @@ impl LasFile {
                     wfp.zt = bor.read_f32()?;
                     self.waveform_data.push(wfp);
                     if skip_bytes > 0 {
-                        bor.inc_pos(skip_bytes);
+                        self.read_extra_bytes(&mut bor)?;
                     }
                 }
             }
@@ -1214,7 +1485,7 @@ impl LasFile {
         self.header.point_format = header.point_format().to_u8().unwrap(); // raw.point_data_record_format;
         self.header.point_record_length = raw.point_data_record_length;
         self.header.number_of_points_old = raw.number_of_point_records;
-        self.header.number_of_points = raw.number_of_point_records as u64;
+        self.header.number_of_points = header.number_of_points();
         self.header.number_of_points_by_return_old = raw.number_of_points_by_return.clone();
 
         let transforms = header.transforms().clone();
@@ -1315,7 +1586,7 @@ impl LasFile {
             }
             self.vlr_data.push(vlr);
         }
-
+        self.read_extra_bytes_layout();
 
         if self.file_mode != "rh" {
             // Read the points into memory
@@ -1323,105 +1594,208 @@ impl LasFile {
             self.gps_data = Vec::with_capacity(self.header.number_of_points as usize);
             self.colour_data = Vec::with_capacity(self.header.number_of_points as usize);
             self.waveform_data = Vec::with_capacity(self.header.number_of_points as usize);
-            let mut rgb: ColourData;
-            let mut wfp: WaveformPacket;
-                    
             for wrapped_point in reader.points() {
                 let point = wrapped_point.unwrap();
-                let raw_point = point.into_raw(&transforms).unwrap();
-
-                let mut p: PointData = Default::default();
-                p.x = raw_point.x;
-                p.y = raw_point.y;
-                p.z = raw_point.z;
-
-                // if self.use_point_intensity {
-                    p.intensity = raw_point.intensity;
-                // }
-                let flags = raw_point.flags;
-                p.set_return_number(flags.return_number());
-                p.set_number_of_returns(flags.number_of_returns());
-                p.set_classification(u8::from(flags.to_classification().unwrap()));
-                p.set_scan_direction_flag(flags.scan_direction() == las::point::ScanDirection::LeftToRight);
-                p.set_synthetic(flags.is_synthetic());
-                p.set_keypoint(flags.is_key_point());
-                p.set_withheld(flags.is_withheld());
-                p.set_overlap(flags.is_overlap());
-                p.set_scanner_channel(flags.scanner_channel());
-                p.set_edge_of_flightline_flag(flags.is_edge_of_flight_line());
-
-
-                // match flags {
-                //     TwoByte(b1, b2) => {
-                //         p.point_bit_field = b1;
-                //         p.class_bit_field = b2;
-                //     },
-                //     ThreeByte(b1, b2, b3) => {
-                //         p.point_bit_field = b1;
-                //         p.class_bit_field = b2;
-                //         p.classification = b3;
-                //     },
-                // }
-                
-                // if self.use_point_userdata {
-                    p.user_data = raw_point.user_data;
-                // }
-                p.scan_angle = match raw_point.scan_angle {
-                    ScanAngle::Rank(value) => value as i16,
-                    ScanAngle::Scaled(value) => value,
-                };
-                p.point_source_id = raw_point.point_source_id;
-                self.point_data.push(p);
+                self.push_laz_point(point, &transforms)?;
+            }
+        }
 
-                if raw_point.gps_time.is_some() {
-                    self.gps_data.push(raw_point.gps_time.unwrap());
-                }
-                
+        drop(raw);
+        // drop(header);
+        drop(reader);
 
-                // read the RGB/NIR data
-                if raw_point.color.is_some() {
-                    let colour = raw_point.color.unwrap();
+        Ok(())
 
-                    rgb = Default::default();
-                    rgb.red = colour.red;
-                    rgb.green = colour.green;
-                    rgb.blue = colour.blue;
+    }
 
-                    if raw_point.nir.is_some() {
-                        rgb.nir = raw_point.nir.unwrap();
-                    }
+    /// Returns the COPC info of the file, or `None` if it is not a Cloud Optimized Point
+    /// Cloud (COPC) file.
+    pub fn copc_info(&self) -> Option<CopcInfo> {
+        self.vlr_data
+            .iter()
+            .find(|vlr| CopcInfo::is_info_vlr(vlr))
+            .and_then(|vlr| CopcInfo::from_vlr(vlr).ok())
+    }
 
-                    self.colour_data.push(rgb);
-                }
-                
-                // read the waveform data
-                if raw_point.waveform.is_some() {
-                    let waveform = raw_point.waveform.unwrap();
-                    wfp = Default::default();
-                    wfp.packet_descriptor_index = waveform.wave_packet_descriptor_index;
-                    wfp.offset_to_waveform_data = waveform.byte_offset_to_waveform_data;
-                    wfp.waveform_packet_size = waveform.waveform_packet_size_in_bytes;
-                    wfp.ret_point_waveform_loc = waveform.return_point_waveform_location;
-                    wfp.xt = waveform.x_t;
-                    wfp.yt = waveform.y_t;
-                    wfp.zt = waveform.z_t;
-                    self.waveform_data.push(wfp);
+    /// Reads the points of a COPC file that are located within `bounds`, decompressing only
+    /// the octree nodes that intersect it. Each level of the octree adds detail to the levels
+    /// above it, so a `max_depth` may be used to read a thinned subset of the points. The file
+    /// is usually opened in 'rh' mode, and any points that have already been read are replaced.
+    pub fn read_bounds(&mut self, bounds: BoundingBox, max_depth: Option<u32>) -> Result<(), Error> {
+        let info = match self.copc_info() {
+            Some(info) => info,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("{} is not a COPC file.", self.get_short_filename()),
+                ))
+            }
+        };
+        let laz_vlr = match self.vlr_data.iter().find(|vlr| {
+            vlr.user_id.trim_end_matches('\0') == laz::LazVlr::USER_ID
+                && vlr.record_id == laz::LazVlr::RECORD_ID
+        }) {
+            Some(vlr) => laz::LazVlr::from_buffer(&vlr.binary_data)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The COPC file does not contain a LASzip VLR.",
+                ))
+            }
+        };
+        let mut format = las::point::Format::new(self.header.point_format)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        format.extra_bytes = self.header.point_record_length.saturating_sub(format.len());
+
+        let mut f = BufReader::new(File::open(&self.file_name)?);
+        let entries = read_hierarchy(&mut f, &info, bounds, max_depth)?;
+
+        self.point_data = vec![];
+        self.gps_data = vec![];
+        self.colour_data = vec![];
+        self.waveform_data = vec![];
+        self.extra_bytes = vec![];
+        let mut buffer = vec![0u8; self.header.point_record_length as usize];
+        for entry in &entries {
+            // Each node is a single LAZ chunk that can be decompressed independently.
+            f.seek(SeekFrom::Start(entry.offset))?;
+            let mut compressed = vec![0u8; entry.byte_size as usize];
+            f.read_exact(&mut compressed)?;
+            let mut decompressor = LayeredPointRecordDecompressor::new(Cursor::new(compressed));
+            decompressor
+                .set_fields_from(laz_vlr.items())
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+            for _ in 0..entry.point_count {
+                decompressor.decompress_next(&mut buffer)?;
+                let raw_point = las::raw::Point::read_from(&buffer[..], &format)
+                    .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+                let x = raw_point.x as f64 * self.header.x_scale_factor + self.header.x_offset;
+                let y = raw_point.y as f64 * self.header.y_scale_factor + self.header.y_offset;
+                if x >= bounds.min_x && x <= bounds.max_x && y >= bounds.min_y && y <= bounds.max_y {
+                    self.push_raw_point(raw_point);
                 }
             }
         }
+        self.header.number_of_points = self.point_data.len() as u64;
 
-        drop(raw);
-        // drop(header);
-        drop(reader);
+        Ok(())
+    }
 
+    /// Adds a point of a LAZ file, decoded by the las crate, to the point data.
+    pub(crate) fn push_laz_point(
+        &mut self,
+        point: las::Point,
+        transforms: &las::Vector<las::Transform>,
+    ) -> Result<(), Error> {
+        // The las crate holds scan angles in degrees, and truncates them when it converts
+        // them back to increments of 0.006 degrees, so they are rounded here instead.
+        let scan_angle = ScanAngle::Scaled((point.scan_angle / 0.006).round() as i16);
+        let mut raw_point = point
+            .into_raw(transforms)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        raw_point.scan_angle = scan_angle;
+        self.push_raw_point(raw_point);
         Ok(())
+    }
+
+    /// Adds a point, decoded by the las crate, to the point data.
+    pub(crate) fn push_raw_point(&mut self, raw_point: las::raw::Point) {
+        let mut p: PointData = Default::default();
+        p.x = raw_point.x;
+        p.y = raw_point.y;
+        p.z = raw_point.z;
+        p.is_64bit = self.header.point_format >= 6;
 
+        // if self.use_point_intensity {
+            p.intensity = raw_point.intensity;
+        // }
+        let flags = raw_point.flags;
+        p.set_return_number(flags.return_number());
+        p.set_number_of_returns(flags.number_of_returns());
+        p.set_classification(u8::from(flags.to_classification().unwrap()));
+        p.set_scan_direction_flag(flags.scan_direction() == las::point::ScanDirection::LeftToRight);
+        p.set_synthetic(flags.is_synthetic());
+        p.set_keypoint(flags.is_key_point());
+        p.set_withheld(flags.is_withheld());
+        p.set_overlap(flags.is_overlap());
+        p.set_scanner_channel(flags.scanner_channel());
+        p.set_edge_of_flightline_flag(flags.is_edge_of_flight_line());
+
+
+        // match flags {
+        //     TwoByte(b1, b2) => {
+        //         p.point_bit_field = b1;
+        //         p.class_bit_field = b2;
+        //     },
+        //     ThreeByte(b1, b2, b3) => {
+        //         p.point_bit_field = b1;
+        //         p.class_bit_field = b2;
+        //         p.classification = b3;
+        //     },
+        // }
+        
+        // if self.use_point_userdata {
+            p.user_data = raw_point.user_data;
+        // }
+        // Points decoded by the las crate may hold the scan angle in either form, whatever
+        // the point format, whereas it is held as the scan angle rank (in degrees) for point
+        // formats 0-5 and in increments of 0.006 degrees for point formats 6-10.
+        p.scan_angle = match raw_point.scan_angle {
+            ScanAngle::Rank(value) if self.header.point_format >= 6 => {
+                (value as f32 / 0.006).round() as i16
+            }
+            ScanAngle::Scaled(value) if self.header.point_format < 6 => {
+                (value as f32 * 0.006).round() as i16
+            }
+            ScanAngle::Rank(value) => value as i16,
+            ScanAngle::Scaled(value) => value,
+        };
+        p.point_source_id = raw_point.point_source_id;
+        self.point_data.push(p);
+
+        if let Some(gps_time) = raw_point.gps_time {
+            self.gps_data.push(gps_time);
+        }
+        
+
+        // read the RGB/NIR data
+        if let Some(colour) = raw_point.color {
+            self.colour_data.push(ColourData {
+                red: colour.red,
+                green: colour.green,
+                blue: colour.blue,
+                nir: raw_point.nir.unwrap_or_default(),
+            });
+        }
+        
+        // read the waveform data
+        if let Some(waveform) = raw_point.waveform {
+            self.waveform_data.push(WaveformPacket {
+                packet_descriptor_index: waveform.wave_packet_descriptor_index,
+                offset_to_waveform_data: waveform.byte_offset_to_waveform_data,
+                waveform_packet_size: waveform.waveform_packet_size_in_bytes,
+                ret_point_waveform_loc: waveform.return_point_waveform_location,
+                xt: waveform.x_t,
+                yt: waveform.y_t,
+                zt: waveform.z_t,
+            });
+        }
+
+        if self.extra_bytes_length > 0 {
+            let mut extra_bytes = raw_point.extra_bytes;
+            extra_bytes.resize(self.extra_bytes_length, 0u8);
+            self.extra_bytes.extend(extra_bytes);
+        }
     }
 
     pub fn read_zlidar_data(&mut self) -> Result<(), Error> {
         let mut f = File::open(&self.file_name).expect("Error opening LAS file.");
         let metadata = fs::metadata(&self.file_name)?;
-        let file_size: usize = metadata.len() as usize;
+        let mut file_size: usize = metadata.len() as usize;
+        if self.file_mode == "rh" {
+            file_size = header_and_vlrs_size(&mut f, file_size)?;
+        }
 
         let mut buffer = vec![0; file_size];
         if file_size < 1024 * 1024 * 500 {
@@ -1646,1196 +2020,1230 @@ impl LasFile {
             minor_version = bor.read_u8().expect("Error while reading byte data.");
             // println!("num_fields: {} compression_method: {} major_version: {} minor_version: {}", num_fields, compression_method, major_version, minor_version);
             if major_version == 1 && minor_version == 1 {
-                let mut field_code: u8;
-                let mut offset: u64;
-                let mut num_bytes: u64;
-                let mut change_bytes: Vec<u8> = vec![];
-                let mut change_byte_read: bool;
-                let mut scanner_chan_read: bool;
-                let mut ret_num_read: bool;
-                let mut num_rets_read: bool;
-                let mut val_u8: u8;
-                let mut val_num: usize;
-                let mut scan_chan: usize;
-                let mut cntx: usize;
-
                 next_offset = self.header.offset_to_points as usize + 4;
                 while flag {
-                    bor.seek(next_offset);
-                    // println!("offset: {}", next_offset);
-                    block_bytes = 0;
-                    change_byte_read = false;
-                    scanner_chan_read = false;
-                    ret_num_read = false;
-                    num_rets_read = false;
-
-                    for _ in 0..num_fields {
-                        // Read the field header
-                        field_code = bor.read_u8().expect("Error while reading byte data.");
-                        offset = bor.read_u64().expect("Error while reading byte data.");
-                        num_bytes = bor.read_u64().expect("Error while reading byte data.");
-                        block_bytes += 17 + num_bytes;
-                        
-                        // println!("field_code: {} offset: {} num_bytes: {} block_bytes: {}", field_code, offset, num_bytes, block_bytes);
-                        
-                        // Decompress the bytes
-                        bor.seek(offset as usize);
-                        let mut compressed = vec![0u8; num_bytes as usize];
-                        bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                        let decompressed = if compression_method == 0 { 
-                            // DEFLATE
-                            decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
-                        } else if compression_method == 1 {
-                            // brotli
-                            brotli_decompress(&compressed)
-                        } else {
-                            panic!("Unrecognized compression method.")
-                        };
-
-                        match field_code {
-                            0 => { // Change byte
-                                // println!("field_code: {} offset: {} num_bytes: {} {:?}", field_code, offset, num_bytes, compressed);
-                                change_bytes = decompressed.clone();
-                                num_points_in_block = change_bytes.len();
-                                change_byte_read = true;
-                            }, 
-                            1 => { // Scanner channel
-                                if !change_byte_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
-                                }
-
-                                // Convert to values
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let mut scan_chan = val_u8 & 0b0000_0011u8;
-                                let mut prev_val = scan_chan;
-                                self.point_data[point_num].set_scanner_channel(scan_chan);
-                                let num_bits = 2;
-                                let mut num_bits_read = num_bits;
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    if (change_bytes[j] & 0b0000_0001u8) == 1u8 {
-                                        scan_chan = (val_u8 >> num_bits_read) & 0b0000_0011u8;
-                                        self.point_data[pt].set_scanner_channel(scan_chan);
-                                        num_bits_read += num_bits;
-                                        if num_bits_read == 8 {
-                                            val_num += 1;
-                                            val_u8 = decompressed[val_num];
-                                            num_bits_read = 0;
-                                        }
-                                        prev_val = scan_chan;
-                                    } else {
-                                        self.point_data[pt].set_scanner_channel(prev_val);
-                                    }
-                                }
-
-                                scanner_chan_read = true;
-                            },
-                            2 => { // Return number
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
-                                }
-
-                                // Convert to values
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let mut ret_num = val_u8 & 0b0000_1111u8;
-                                self.point_data[point_num].set_return_number(ret_num);
-                                let num_bits = 4;
-                                let mut num_bits_read = num_bits;
-                                let mut prev_vals = [ret_num, ret_num, ret_num, ret_num];
-                                let mut ret_num_diff: u8;
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    ret_num_diff = (change_bytes[j] & 0b0000_1100u8) >> 2;
-                                    if ret_num_diff == 0 {
-                                        // same as previous for scan chan
-                                        self.point_data[pt].set_return_number(prev_vals[scan_chan]);
-                                    } else if ret_num_diff == 1 {
-                                        // one more than previous for scan chan
-                                        self.point_data[pt].set_return_number(prev_vals[scan_chan] + 1);
-                                        prev_vals[scan_chan] += 1;
-                                    } else if ret_num_diff == 2 {
-                                        // one less than previous for scan chan
-                                        self.point_data[pt].set_return_number(prev_vals[scan_chan] - 1);
-                                        prev_vals[scan_chan] -= 1;
-                                    } else { // 3
-                                        // new value stored in data
-                                        ret_num = (val_u8 >> num_bits_read) & 0b0000_1111u8;
-                                        self.point_data[pt].set_return_number(ret_num);
-                                        num_bits_read += num_bits;
-                                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                            val_num += 1;
-                                            val_u8 = decompressed[val_num];
-                                            num_bits_read = 0;
-                                        }
-                                        prev_vals[scan_chan] = ret_num;
-                                    }
-                                }
+                    (num_points_in_block, block_bytes) = self.read_zlidar_block(
+                        &mut bor,
+                        next_offset,
+                        point_num,
+                        num_fields,
+                        compression_method,
+                    );
 
-                                ret_num_read = true;
-                            }, 
+                    // println!("block_bytes: {}", block_bytes);
+                    point_num += num_points_in_block;
+                    next_offset += block_bytes as usize;
+                    if next_offset >= file_size {
+                        flag = false;
+                    }
+                }
+            } else {
+                while flag {
+                    bor.seek(next_offset);
 
-                            3 => { // Number of returns
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
-                                }
+                    let mut field_type = vec![];
+                    let mut offset = vec![];
+                    let mut num_bytes = vec![];
 
-                                // Convert to values
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let mut num_rets = val_u8 & 0b0000_1111u8;
-                                // println!("{} {}", point_num, num_rets);
-                                self.point_data[point_num].set_number_of_returns(num_rets);
-                                let num_bits = 4;
-                                let mut num_bits_read = num_bits;
-                                let mut prev_vals = [num_rets, num_rets, num_rets, num_rets];
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    if ((change_bytes[j] & 0b0001_0000u8) >> 4) == 1 {
-                                        // new value stored in data
-                                        num_rets = (val_u8 >> num_bits_read) & 0b0000_1111u8;
-                                        self.point_data[pt].set_number_of_returns(num_rets);
-                                        num_bits_read += num_bits;
-                                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                            val_num += 1;
-                                            val_u8 = decompressed[val_num];
-                                            num_bits_read = 0;
-                                        }
-                                        prev_vals[scan_chan] = num_rets;
-                                    } else {
-                                        self.point_data[pt].set_number_of_returns(prev_vals[scan_chan]);
-                                    }
+                    // Start by reading the point data table
+                    num_fields = bor.read_u8().expect("Error while reading byte data.");
+                    block_bytes = 4u64 + 20u64 * num_fields as u64;
+                    compression_method = bor.read_u8().expect("Error while reading byte data.");
+                    if compression_method != 0 {
+                        return Err(Error::new(
+                            ErrorKind::Other,
+                            "Unsupported compression method.",
+                        ));
+                    }
+                    major_version = bor.read_u8().expect("Error while reading byte data.");
+                    minor_version = bor.read_u8().expect("Error while reading byte data.");
 
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    // // if pt >= 0 && pt < 100 {
-                                    //     println!("{}, {}/{}", pt, self.point_data[pt].return_number(), self.point_data[pt].number_of_returns());
-                                    // }
-                                }
+                    // Other acceptable versions include 0.0, 0.1, 1.0
+                    if !(major_version == 0 && minor_version <= 1)
+                        && !(major_version == 1 && minor_version == 0)
+                    {
+                        return Err(Error::new(
+                            ErrorKind::Other,
+                            format!(
+                                "Unsupported ZLidar version {}.{}.",
+                                major_version, minor_version
+                            ),
+                        ));
+                    }
 
-                                num_rets_read = true;
-                            }, 
+                    let mut return_field = -1isize;
+                    for i in 0..num_fields as usize {
+                        field_type.push(bor.read_u32().expect("Error while reading byte data."));
+                        if field_type[i] == 4 {
+                            return_field = i as isize;
+                        }
+                        offset.push(bor.read_u64().expect("Error while reading byte data."));
+                        num_bytes.push(bor.read_u64().expect("Error while reading byte data."));
+                        block_bytes += num_bytes[i];
+                        // Don't forget about word alignment bytes
+                        if block_bytes % 4 > 0 {
+                            block_bytes += 4 - (num_bytes[i] % 4);
+                        }
+                        // println!("field_type: {} offset: {} num_bytes: {}", field_type[i], offset[i], num_bytes[i]);
+                    }
 
-                            4 => { // X
-                                if !change_byte_read || !scanner_chan_read || !ret_num_read || !num_rets_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
-                                }
+                    // we need to read the point return data before the z-values.
+                    if return_field >= 0 {
+                        bor.seek(offset[return_field as usize] as usize);
+                        let mut compressed = vec![0u8; num_bytes[return_field as usize] as usize];
+                        bor.read_exact(&mut compressed)?;
+                        let decompressed = decompress_to_vec_zlib(&compressed)
+                            .expect("DEFLATE failed to decompress data.");
+                        num_points_in_block = decompressed.len();
+                        for j in 0..num_points_in_block {
+                            pt = point_num + j;
+                            self.point_data[pt].point_bit_field = decompressed[j];
+                        }
+                    } else {
+                        return Err(Error::new(
+                        ErrorKind::Other,
+                        "An error was encountered while attempting to read the point return data.",
+                    ));
+                    }
 
-                                offset = bor.read_u64().expect("Error while reading byte data.");
-                                num_bytes = bor.read_u64().expect("Error while reading byte data.");
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                block_bytes += 16 + num_bytes;
-                                
-                                // Decompress the bytes
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                let decompressed2 = if compression_method == 0 { 
-                                    // DEFLATE
-                                    decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
-                                } else if compression_method == 1 {
-                                    // brotli
-                                    brotli_decompress(&compressed)
-                                } else {
-                                    panic!("Unrecognized compression method.")
-                                };
+                    for i in 0..num_fields as usize {
+                        bor.seek(offset[i] as usize);
+                        let mut compressed = vec![0u8; num_bytes[i] as usize];
+                        bor.read_exact(&mut compressed)?;
+                        let decompressed = decompress_to_vec_zlib(&compressed)
+                            .expect("DEFLATE failed to decompress data.");
 
+                        match field_type[i] {
+                            0 => {
+                                // x
+                                num_points_in_block = decompressed.len() / 4;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed2),
+                                    Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
+                                let mut val: i32;
+                                let mut vali32: i32;
+                                let mut prev_val = 0i32;
+                                for j in 0..num_points_in_block {
+                                    vali32 = bor2.read_i32().expect("Error reading byte data.");
+                                    // val = (vali32 + prev_val) as f64 * self.header.x_scale_factor
+                                    //     + self.header.x_offset;
+                                    val = vali32 + prev_val;
 
-                                // Convert to values
-                                let mut delta_values = Vec::with_capacity(num_points_in_block);
-                                let mut val2 = Vec::with_capacity(num_points_in_block);
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let mut tag = val_u8 & 0b0000_1111u8;
-                                let mut val_i32 = if tag == 15u8 {
-                                    bor2.read_i32().expect("Error reading byte data.")
-                                } else {
-                                    panic!("Error reading coordinate data from zLidar file.");
-                                };
-                                val2.push(val_i32);
-                                delta_values.push(val_i32);
-                                let mut prev_vals = [val_i32, val_i32, val_i32, val_i32];
-                                self.point_data[point_num].x = val_i32; // as f64 * self.header.x_scale_factor + self.header.x_offset;
-                                let num_bits = 4;
-                                let mut num_bits_read = num_bits;
-                                let mut prev_index = [
-                                    [0; 16],
-                                    [0; 16],
-                                    [0; 16],
-                                    [0; 16],
-                                ];
-                                for _ in 1..num_points_in_block {
-                                    // pt = point_num + j;
-                                    
-                                    tag = (val_u8 >> num_bits_read) & 0b0000_1111u8;
-                                    num_bits_read += num_bits;
-                                    if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                        val_num += 1;
-                                        val_u8 = decompressed[val_num];
-                                        num_bits_read = 0;
-                                    }
-
-                                    if tag < 13 {
-                                        // the offset from prev_val is tag - 6
-                                        val_i32 = tag as i32 - 6;
-                                    } else if tag == 13 {
-                                        // the offset is one byte
-                                        val_i32 = bor2.read_i8().expect("Error reading byte data.") as i32;
-                                    } else if tag == 14 {
-                                        // the offset is two bytes
-                                        val_i32 = bor2.read_i16().expect("Error reading byte data.") as i32;
-                                    } else { // tag == 15
-                                        // the offset is four bytes
-                                        val_i32 = bor2.read_i32().expect("Error reading byte data.");
-                                    }
-                                    val2.push(val_i32);
-                                }
-
-                                for j in 1..num_points_in_block {
+                                    prev_val += vali32;
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    
-                                    cntx = self.get_context(pt);
-                                    let delta_j: i32 = val2[j] + delta_values[prev_index[scan_chan][cntx]];
-                                    delta_values.push(delta_j);
-
-                                    let val: i32 = prev_vals[scan_chan] + delta_j;
-
-                                    self.point_data[pt].x = val; // as f64 * self.header.x_scale_factor + self.header.x_offset;
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}/{}, {}, {}, {}", pt, self.point_data[pt].return_number(), self.point_data[pt].number_of_returns(), val2[j], delta_j, self.point_data[pt].x);
-                                    // }
-                                    prev_vals[scan_chan] = val;
-                                    prev_index[scan_chan][cntx] = j;
-                                }
-                            },
-
-                            5 => { // Y
-                                if !change_byte_read || !scanner_chan_read || !ret_num_read || !num_rets_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                                    self.point_data[pt].x = val;
                                 }
-
-                                offset = bor.read_u64().expect("Error while reading byte data.");
-                                num_bytes = bor.read_u64().expect("Error while reading byte data.");
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                block_bytes += 16 + num_bytes;
-                                
-                                // Decompress the bytes
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                let decompressed2 = if compression_method == 0 { 
-                                    // DEFLATE
-                                    decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
-                                } else if compression_method == 1 {
-                                    // brotli
-                                    brotli_decompress(&compressed)
-                                } else {
-                                    panic!("Unrecognized compression method.")
-                                };
-
+                            }
+                            1 => {
+                                // y
+                                num_points_in_block = decompressed.len() / 4;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed2),
+                                    Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
+                                let mut val: i32;
+                                let mut vali32: i32;
+                                let mut prev_val = 0i32;
+                                for j in 0..num_points_in_block {
+                                    vali32 = bor2.read_i32().expect("Error reading byte data.");
+                                    val = vali32 + prev_val; // as f64 * self.header.y_scale_factor
+                                        // + self.header.y_offset;
 
-                                // Convert to values
-                                let mut delta_values = Vec::with_capacity(num_points_in_block);
-                                let mut val2 = Vec::with_capacity(num_points_in_block);
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let mut tag = val_u8 & 0b0000_1111u8;
-                                let mut val_i32 = if tag == 15u8 {
-                                    bor2.read_i32().expect("Error reading byte data.")
-                                } else {
-                                    panic!("Error reading coordinate data from zLidar file.");
-                                };
-                                val2.push(val_i32);
-                                delta_values.push(val_i32);
-                                let mut prev_vals = [val_i32, val_i32, val_i32, val_i32];
-                                self.point_data[point_num].y = val_i32; // as f64 * self.header.y_scale_factor + self.header.y_offset;
-                                let num_bits = 4;
-                                let mut num_bits_read = num_bits;
-                                let mut prev_index = [
-                                    [0; 16],
-                                    [0; 16],
-                                    [0; 16],
-                                    [0; 16],
-                                ];
-                                for _ in 1..num_points_in_block {
                                     // pt = point_num + j;
-                                    
-                                    tag = (val_u8 >> num_bits_read) & 0b0000_1111u8;
-                                    num_bits_read += num_bits;
-                                    if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                        val_num += 1;
-                                        val_u8 = decompressed[val_num];
-                                        num_bits_read = 0;
-                                    }
-
-                                    if tag < 13 {
-                                        // the offset from prev_val is tag - 6
-                                        val_i32 = tag as i32 - 6;
-                                    } else if tag == 13 {
-                                        // the offset is one byte
-                                        val_i32 = bor2.read_i8().expect("Error reading byte data.") as i32;
-                                    } else if tag == 14 {
-                                        // the offset is two bytes
-                                        val_i32 = bor2.read_i16().expect("Error reading byte data.") as i32;
-                                    } else { // tag == 15
-                                        // the offset is four bytes
-                                        val_i32 = bor2.read_i32().expect("Error reading byte data.");
-                                    }
-                                    val2.push(val_i32);
-                                }
-
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    
-                                    cntx = self.get_context(pt);
-                                    let delta_j: i32 = val2[j] + delta_values[prev_index[scan_chan][cntx]];
-                                    delta_values.push(delta_j);
-
-                                    let val: i32 = prev_vals[scan_chan] + delta_j;
-
-                                    self.point_data[pt].y = val; // as f64 * self.header.y_scale_factor + self.header.y_offset;
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}, {}", pt, self.point_data[pt].x, self.point_data[pt].y);
+                                    // if pt >= 500_000 && pt < 500_100 {
+                                    //     println!("{} {} {} {}", vali32+prev_val, vali32, prev_val, val);
                                     // }
-                                    prev_vals[scan_chan] = val;
-                                    prev_index[scan_chan][cntx] = j;
-                                }
-                            },
 
-                            6 => { // Z
-                                if !change_byte_read || !scanner_chan_read || !ret_num_read || !num_rets_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                                    prev_val += vali32;
+                                    pt = point_num + j;
+                                    self.point_data[pt].y = val;
                                 }
-
-                                offset = bor.read_u64().expect("Error while reading byte data.");
-                                num_bytes = bor.read_u64().expect("Error while reading byte data.");
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                block_bytes += 16 + num_bytes;
-                                
-                                // Decompress the bytes
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                let decompressed2 = if compression_method == 0 { 
-                                    // DEFLATE
-                                    decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
-                                } else if compression_method == 1 {
-                                    // brotli
-                                    brotli_decompress(&compressed)
-                                } else {
-                                    panic!("Unrecognized compression method.")
-                                };
-
+                            }
+                            2 => {
+                                // z
+                                num_points_in_block = decompressed.len() / 4;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed2),
+                                    Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
+                                let mut val: i32;
+                                let mut vali32: i32;
+
+                                // let mut prev_val = 0i32;
+                                // for j in 0..num_points_in_block {
+                                //     vali32 = bor2.read_i32().expect("Error reading byte data.");
+                                //     val = (vali32 + prev_val) as f64 * self.header.z_scale_factor + self.header.z_offset;
+
+                                //     pt = point_num + j;
+                                //     // if pt >= 500_000 && pt < 500_100 {
+                                //     //     println!("{} {} {} {}", vali32+prev_val, vali32, prev_val, val);
+                                //     // }
+
+                                //     if self.point_data[pt].is_late_return() {
+                                //         prev_val += vali32;
+                                //     }
+                                //     self.point_data[pt].z = val;
+                                // }
 
-                                // Convert to values
-                                let mut tag: u8;
-                                let mut val_i32: i32;
-                                let num_bits = 4;
-                                val_num = 0usize;
-                                let mut val_u8 = decompressed[val_num];
-                                let mut num_bits_read = 0;
-                                let mut prev_late_vals = [0i32, 0i32, 0i32, 0i32];
-                                let mut prev_early_vals = [0i32, 0i32, 0i32, 0i32];
                                 let mut prev_val = 0i32;
+                                let mut prev_late_val = 0i32;
+                                let mut prev_early_val = 0i32;
                                 for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-
-                                    tag = (val_u8 >> num_bits_read) & 0b0000_1111u8;
-                                    num_bits_read += num_bits;
-                                    if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                        val_num += 1;
-                                        val_u8 = decompressed[val_num];
-                                        num_bits_read = 0;
-                                    }
-
-                                    val_i32 = if tag < 13 {
-                                        // the offset from prev_val is tag - 6
-                                        tag as i32 - 6
-                                    } else if tag == 13 {
-                                        // the offset is one byte
-                                        bor2.read_i8().expect("Error reading byte data.") as i32
-                                    } else if tag == 14 {
-                                        // the offset is two bytes
-                                        bor2.read_i16().expect("Error reading byte data.") as i32
-                                    } else { // tag == 15
-                                        // the offset is four bytes
-                                        bor2.read_i32().expect("Error reading byte data.")
-                                    };
-
                                     prev_val = if self.point_data[pt].is_late_return() {
-                                        prev_late_vals[scan_chan]
+                                        prev_late_val
                                     } else {
-                                        prev_early_vals[scan_chan]
+                                        prev_early_val
                                     };
 
-                                    val_i32 += prev_val;
-                                    self.point_data[pt].z = val_i32; // as f64 * self.header.z_scale_factor + self.header.z_offset;
+                                    vali32 = bor2.read_i32().expect("Error reading byte data.");
+                                    val = vali32 + prev_val; // as f64 * self.header.z_scale_factor
+                                        // + self.header.z_offset;
+                                    self.point_data[pt].z = val;
 
                                     if self.point_data[pt].is_late_return() {
-                                        prev_late_vals[scan_chan] = val_i32;
+                                        prev_late_val += vali32;
                                     } else {
-                                        prev_early_vals[scan_chan] = val_i32;
+                                        prev_early_val += vali32;
                                     }
-
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}, {}, {}", pt, self.point_data[pt].x, self.point_data[pt].y, self.point_data[pt].z);
-                                    // }
                                 }
-                            },
 
-                            7 => { // Intensity
-                                if !change_byte_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
-                                }
+                                // let mut prev_val = 0i32;
+                                // for j in 0..num_points_in_block {
+                                //     vali32 = bor2.read_i32().expect("Error reading byte data.");
+                                //     val = (vali32 + prev_val) as f64 * self.header.z_scale_factor + self.header.z_offset;
+
+                                //     pt = point_num + j;
+                                //     if pt >= 500_000 && pt < 500_100 {
+                                //         println!("{} {} {} {}", vali32+prev_val, vali32, prev_val, val);
+                                //     }
+
+                                //     prev_val += vali32;
+                                //     // pt = point_num + j;
+                                //     self.point_data[pt].z = val;
+                                // }
+
+                                // let mut prev_val: i32;
+                                // let mut prev_late_val = 0i32;
+                                // let mut prev_early_val = 0i32;
+                                // for j in 0..num_points_in_block {
+                                //     pt = point_num + j;
+                                //     prev_val = if self.point_data[pt].is_late_return() {
+                                //         prev_late_val
+                                //     } else {
+                                //         prev_early_val
+                                //     };
+                                //     vali32 = bor2.read_i32().expect("Error reading byte data.");
+                                //     val = (vali32 + prev_val) as f64 * self.header.z_scale_factor + self.header.z_offset;
+                                //     self.point_data[pt].z = val;
 
+                                //     if self.point_data[pt].is_late_return() {
+                                //         prev_late_val = ((val - self.header.z_offset) / self.header.z_scale_factor) as i32;
+                                //     } else {
+                                //         prev_early_val = ((val - self.header.z_offset) / self.header.z_scale_factor) as i32;
+                                //     }
+                                //     // if j == 1000 {
+                                //     //     println!("z: {}", val);
+                                //     // }
+                                // }
+                            }
+                            3 => {
+                                // intensity
+                                num_points_in_block = decompressed.len() / 2;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
                                     Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
-
-                                // Convert to values
-                                self.point_data[point_num].intensity = bor2.read_u16().expect("Error while reading byte data.") as u16;
-                                for j in 1..num_points_in_block {
+                                for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    if ((change_bytes[j] & 0b1000_0000u8) >> 7) == 1 { // 2 bytes
-                                        self.point_data[pt].intensity = bor2.read_u16().expect("Error while reading byte data.");
-                                    } else { // 1 byte
-                                        self.point_data[pt].intensity = bor2.read_u8().expect("Error while reading byte data.") as u16;
-                                    }
-
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     let t = ((change_bytes[j] & 0b1000_0000u8) >> 7) == 1;
-                                    //     println!("{}, {}, {}", pt, t, self.point_data[pt].intensity);
-                                    // }
-                                }
-                            }, 
-
-                            8 => { // Flags
-                                if !change_byte_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                                    self.point_data[pt].intensity =
+                                        bor2.read_u16().expect("Error reading byte data.");
                                 }
-
-                                // Convert to values
+                            }
+                            4 => {
+                                // point return data has already been read.
+                            }
+                            5 => {
+                                // point class data
+                                num_points_in_block = decompressed.len();
                                 for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    val_u8 = decompressed[j];
-                                    if val_u8 & 0b0000_0001u8 == 1 {
-                                        self.point_data[pt].set_synthetic(true);
-                                    } else {
-                                        self.point_data[pt].set_synthetic(false);
-                                    }
-
-                                    if ((val_u8 & 0b0000_0010u8) >> 1) == 1 {
-                                        self.point_data[pt].set_keypoint(true);
-                                    } else {
-                                        self.point_data[pt].set_keypoint(false);
-                                    }
-
-                                    if ((val_u8 & 0b0000_0100u8) >> 2) == 1 {
-                                        self.point_data[pt].set_withheld(true);
-                                    } else {
-                                        self.point_data[pt].set_withheld(false);
-                                    }
-
-                                    if ((val_u8 & 0b0000_1000u8) >> 3) == 1 {
-                                        self.point_data[pt].set_overlap(true);
-                                    } else {
-                                        self.point_data[pt].set_overlap(false);
-                                    }
-
-                                    if ((val_u8 & 0b0001_0000u8) >> 4) == 1 {
-                                        self.point_data[pt].set_scan_direction_flag(true);
-                                    } else {
-                                        self.point_data[pt].set_scan_direction_flag(false);
-                                    }
-
-                                    if ((val_u8 & 0b0010_0000u8) >> 5) == 1 {
-                                        self.point_data[pt].set_edge_of_flightline_flag(true);
-                                    } else {
-                                        self.point_data[pt].set_edge_of_flightline_flag(false);
-                                    }
-                                }
-                            }, 
-
-                            9 => { // Classification byte
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                                    self.point_data[pt].class_bit_field = decompressed[j];
                                 }
-
-                                // Convert to values
-                                self.point_data[point_num].set_classification(decompressed[0]);
-                                let mut prev_val = [decompressed[0], decompressed[0], decompressed[0], decompressed[0]];
-                                val_num = 0usize;
-                                for j in 1..num_points_in_block {
+                            }
+                            6 => {
+                                // scan angle
+                                num_points_in_block = decompressed.len() / 2;
+                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                                    Cursor::new(decompressed),
+                                    Endianness::LittleEndian,
+                                );
+                                let mut val: i16;
+                                let mut prev_val = 0i16;
+                                for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    if ((change_bytes[j] & 0b0010_0000u8) >> 5) == 1 {
-                                        val_num += 1;
-                                        self.point_data[pt].set_classification(decompressed[val_num]);
-                                        prev_val[scan_chan] = decompressed[val_num];
-                                    } else {
-                                        self.point_data[pt].set_classification(prev_val[scan_chan]);
-                                    }
+                                    val = bor2.read_i16().expect("Error reading byte data.");
+                                    self.point_data[pt].scan_angle = val + prev_val;
+                                    prev_val = val;
                                 }
-                            }, 
-
-                            10 => { // User data
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                            }
+                            7 => {
+                                // user data
+                                num_points_in_block = decompressed.len();
+                                for j in 0..num_points_in_block {
+                                    pt = point_num + j;
+                                    self.point_data[pt].user_data = decompressed[j];
                                 }
-
-                                offset = bor.read_u64().expect("Error while reading byte data.");
-                                num_bytes = bor.read_u64().expect("Error while reading byte data.");
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                block_bytes += 16 + num_bytes;
-                                
-                                // Decompress the bytes
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                let decompressed2 = if compression_method == 0 { 
-                                    // DEFLATE
-                                    decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
-                                } else if compression_method == 1 {
-                                    // brotli
-                                    brotli_decompress(&compressed)
-                                } else {
-                                    panic!("Unrecognized compression method.")
-                                };
-
+                            }
+                            8 => {
+                                // point source id
+                                num_points_in_block = decompressed.len() / 2;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed2),
+                                    Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
-
-                                // Convert to values
-                                let mut tag: u8;
-                                let val = bor2.read_u8().expect("Error while reading byte data.");
-                                self.point_data[point_num].user_data = val;
-                                let mut prev_val = [val, val, val, val];
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let num_bits = 1;
-                                let mut num_bits_read = num_bits;
-                                for j in 1..num_points_in_block {
+                                for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    
-                                    tag = (val_u8 >> num_bits_read) & 0b0000_0001u8;
-                                    num_bits_read += num_bits;
-                                    if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                        val_num += 1;
-                                        val_u8 = decompressed[val_num];
-                                        num_bits_read = 0;
-                                    }
-
-                                    if tag == 1 {
-                                        self.point_data[pt].user_data = bor2.read_u8().expect("Error while reading byte data.");
-                                        prev_val[scan_chan] = self.point_data[pt].user_data;
-                                    } else {
-                                        self.point_data[pt].user_data = prev_val[scan_chan];
-                                    }
-
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}", pt, self.point_data[pt].user_data);
-                                    // }
+                                    self.point_data[pt].point_source_id =
+                                        bor2.read_u16().expect("Error reading byte data.");
                                 }
-                            },
-
-                            11 => { // Scan angle
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                            }
+                            9 => {
+                                // GPS time
+                                if self.gps_data.len() == 0 {
+                                    self.gps_data =
+                                        vec![0f64; self.header.number_of_points as usize];
                                 }
-
+                                num_points_in_block = decompressed.len() / 8;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
                                     Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
-
-                                // Convert to values
-                                let mut val = bor2.read_i16().expect("Error while reading byte data.");
-                                self.point_data[point_num].scan_angle = val;
-                                let mut prev_val = [val, val, val, val];
-                                for j in 1..num_points_in_block {
+                                let mut val: f64;
+                                let mut prev_val = 0f64;
+                                for j in 0..num_points_in_block {
+                                    val = bor2.read_f64().expect("Error reading byte data.")
+                                        + prev_val;
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    if ((change_bytes[j] & 0b0100_0000u8) >> 6) == 1 {
-                                        val = bor2.read_i16().expect("Error while reading byte data.");
-                                        self.point_data[pt].scan_angle = val;
-                                        prev_val[scan_chan] = val;
-                                    } else {
-                                        self.point_data[pt].scan_angle = prev_val[scan_chan];
-                                    }
-
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}", pt, self.point_data[pt].scan_angle);
-                                    // }
+                                    self.gps_data[pt] = val;
+                                    prev_val = val;
                                 }
-                            }, 
-
-                            12 => { // PointSourceID
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                            }
+                            10 => {
+                                // red
+                                if self.colour_data.len() == 0 {
+                                    self.colour_data = vec![
+                                        Default::default();
+                                        self.header.number_of_points as usize
+                                    ];
                                 }
-
-                                offset = bor.read_u64().expect("Error while reading byte data.");
-                                num_bytes = bor.read_u64().expect("Error while reading byte data.");
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                block_bytes += 16 + num_bytes;
-                                
-                                // Decompress the bytes
-                                bor.seek(offset as usize);
-                                let mut compressed = vec![0u8; num_bytes as usize];
-                                bor.read_exact(&mut compressed).expect("Error while reading byte data.");
-                                let decompressed2 = if compression_method == 0 { 
-                                    // DEFLATE
-                                    decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
-                                } else if compression_method == 1 {
-                                    // brotli
-                                    brotli_decompress(&compressed)
-                                } else {
-                                    panic!("Unrecognized compression method.")
-                                };
-
+                                num_points_in_block = decompressed.len() / 2;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed2),
+                                    Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
-
-                                // Convert to values
-                                let mut tag: u8;
-                                let val = bor2.read_u16().expect("Error while reading byte data.");
-                                self.point_data[point_num].point_source_id = val;
-                                let mut prev_val = [val, val, val, val];
-                                val_num = 0usize;
-                                val_u8 = decompressed[val_num];
-                                let num_bits = 1;
-                                let mut num_bits_read = num_bits;
-                                for j in 1..num_points_in_block {
+                                for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    
-                                    tag = (val_u8 >> num_bits_read) & 0b0000_0001u8;
-                                    num_bits_read += num_bits;
-                                    if num_bits_read == 8 && val_num < decompressed.len()-1 {
-                                        val_num += 1;
-                                        val_u8 = decompressed[val_num];
-                                        num_bits_read = 0;
-                                    }
-
-                                    if tag == 1 {
-                                        self.point_data[pt].point_source_id = bor2.read_u16().expect("Error while reading byte data.");
-                                        prev_val[scan_chan] = self.point_data[pt].point_source_id;
-                                    } else {
-                                        self.point_data[pt].point_source_id = prev_val[scan_chan];
-                                    }
-
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}", pt, self.point_data[pt].point_source_id);
-                                    // }
+                                    self.colour_data[pt].red =
+                                        bor2.read_u16().expect("Error reading byte data.");
                                 }
-                            },
-
-                            13 => { // GPS time
-                                if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
-                                    panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                            }
+                            11 => {
+                                // green
+                                if self.colour_data.len() == 0 {
+                                    self.colour_data = vec![
+                                        Default::default();
+                                        self.header.number_of_points as usize
+                                    ];
                                 }
-
+                                num_points_in_block = decompressed.len() / 2;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
                                     Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
-
-                                // Convert to values
-                                let mut val = bor2.read_f64().expect("Error while reading byte data.");
-                                self.gps_data.push(val);
-                                let mut prev_val = [val, val, val, val];
-                                for j in 1..num_points_in_block {
+                                for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    scan_chan = self.point_data[pt].scanner_channel() as usize;
-                                    if ((change_bytes[j] & 0b0000_0010u8) >> 1) == 1 {
-                                        val = bor2.read_f64().expect("Error while reading byte data.");
-                                        self.gps_data.push(val + prev_val[scan_chan]);
-                                        prev_val[scan_chan] = val + prev_val[scan_chan];
-                                    } else {
-                                        self.gps_data.push(prev_val[scan_chan]);
-                                    }
-
-                                    // if pt >= 100_000 && pt < 100_100 {
-                                    //     println!("{}, {}", pt, self.gps_data[pt]);
-                                    // }
-                                }
-                            }, 
-
-                            14 => { // Red
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-
-                                // Convert to values
-                                let mut val: u16;
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    val = bor2.read_u16().expect("Error while reading byte data.");
-                                    self.colour_data[pt].red = val;
-                                }
-                            },
-                            
-                            15 => { // Green
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-
-                                // Convert to values
-                                let mut val: u16;
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    val = bor2.read_u16().expect("Error while reading byte data.");
-                                    self.colour_data[pt].green = val;
+                                    self.colour_data[pt].green =
+                                        bor2.read_u16().expect("Error reading byte data.");
                                 }
-                            }, 
-
-                            16 => { // Blue
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-
-                                // Convert to values
-                                let mut val: u16;
-                                for j in 1..num_points_in_block {
-                                    pt = point_num + j;
-                                    val = bor2.read_u16().expect("Error while reading byte data.");
-                                    self.colour_data[pt].blue = val;
+                            }
+                            12 => {
+                                // blue
+                                if self.colour_data.len() == 0 {
+                                    self.colour_data = vec![
+                                        Default::default();
+                                        self.header.number_of_points as usize
+                                    ];
                                 }
-                            }, 
-
-                            17 => { // NIR
+                                num_points_in_block = decompressed.len() / 2;
                                 let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
                                     Cursor::new(decompressed),
                                     Endianness::LittleEndian,
                                 );
-
-                                // Convert to values
-                                let mut val: u16;
-                                for j in 1..num_points_in_block {
+                                for j in 0..num_points_in_block {
                                     pt = point_num + j;
-                                    val = bor2.read_u16().expect("Error while reading byte data.");
-                                    self.colour_data[pt].nir = val;
+                                    self.colour_data[pt].blue =
+                                        bor2.read_u16().expect("Error reading byte data.");
                                 }
-                            }, 
+                            }
                             _ => {
-                                panic!("Unrecognized field code.");
+                                // Do nothing // return Err(Error::new(ErrorKind::Other, "Unrecognized point field"));
                             }
                         }
                     }
 
-                    // println!("block_bytes: {}", block_bytes);
                     point_num += num_points_in_block;
                     next_offset += block_bytes as usize;
                     if next_offset >= file_size {
                         flag = false;
                     }
                 }
-            } else {
-                while flag {
-                    bor.seek(next_offset);
+            }
 
-                    let mut field_type = vec![];
-                    let mut offset = vec![];
-                    let mut num_bytes = vec![];
+            // for i in 100_000..100_200 {
+            //     println!("{}, {}, {}, {}, {}, {}", i, self.point_data[i].x, self.point_data[i].y, self.point_data[i].z, self.point_data[i].return_number(), self.point_data[i].number_of_returns());
+            // }
+        }
 
-                    // Start by reading the point data table
-                    num_fields = bor.read_u8().expect("Error while reading byte data.");
-                    block_bytes = 4u64 + 20u64 * num_fields as u64;
-                    compression_method = bor.read_u8().expect("Error while reading byte data.");
-                    if compression_method != 0 {
-                        return Err(Error::new(
-                            ErrorKind::Other,
-                            "Unsupported compression method.",
-                        ));
-                    }
-                    major_version = bor.read_u8().expect("Error while reading byte data.");
-                    minor_version = bor.read_u8().expect("Error while reading byte data.");
+        Ok(())
+    }
 
-                    // Other acceptable versions include 0.0, 0.1, 1.0
-                    if !(major_version == 0 && minor_version <= 1)
-                        && !(major_version == 1 && minor_version == 0)
-                    {
-                        return Err(Error::new(
-                            ErrorKind::Other,
-                            format!(
-                                "Unsupported ZLidar version {}.{}.",
-                                major_version, minor_version
-                            ),
-                        ));
-                    }
+    /// Decodes the zLidar (v1.1) point block located at `block_offset`, storing its points
+    /// starting at index `point_num`, and returns the number of points in the block and the
+    /// size of its field headers and data in bytes. The point data are grown as needed, which
+    /// allows a block to be decoded on its own.
+    pub(crate) fn read_zlidar_block<R: Read + Seek>(
+        &mut self,
+        bor: &mut ByteOrderReader<R>,
+        block_offset: usize,
+        point_num: usize,
+        num_fields: u8,
+        compression_method: u8,
+    ) -> (usize, u64) {
+        let mut field_code: u8;
+        let mut offset: u64;
+        let mut num_bytes: u64;
+        let mut change_bytes: Vec<u8> = vec![];
+        let mut change_byte_read = false;
+        let mut scanner_chan_read = false;
+        let mut ret_num_read = false;
+        let mut num_rets_read = false;
+        let mut val_u8: u8;
+        let mut val_num: usize;
+        let mut scan_chan: usize;
+        let mut cntx: usize;
+        let mut pt: usize;
+        let mut block_bytes = 0u64;
+        let mut num_points_in_block = 0usize;
+
+        bor.seek(block_offset);
+        // println!("offset: {}", block_offset);
+
+        for _ in 0..num_fields {
+            // Read the field header
+            field_code = bor.read_u8().expect("Error while reading byte data.");
+            offset = bor.read_u64().expect("Error while reading byte data.");
+            num_bytes = bor.read_u64().expect("Error while reading byte data.");
+            block_bytes += 17 + num_bytes;
+            
+            // println!("field_code: {} offset: {} num_bytes: {} block_bytes: {}", field_code, offset, num_bytes, block_bytes);
+            
+            // Decompress the bytes
+            bor.seek(offset as usize);
+            let mut compressed = vec![0u8; num_bytes as usize];
+            bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+            let decompressed = if compression_method == 0 { 
+                // DEFLATE
+                decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
+            } else if compression_method == 1 {
+                // brotli
+                brotli_decompress(&compressed)
+            } else {
+                panic!("Unrecognized compression method.")
+            };
 
-                    let mut return_field = -1isize;
-                    for i in 0..num_fields as usize {
-                        field_type.push(bor.read_u32().expect("Error while reading byte data."));
-                        if field_type[i] == 4 {
-                            return_field = i as isize;
-                        }
-                        offset.push(bor.read_u64().expect("Error while reading byte data."));
-                        num_bytes.push(bor.read_u64().expect("Error while reading byte data."));
-                        block_bytes += num_bytes[i];
-                        // Don't forget about word alignment bytes
-                        if block_bytes % 4 > 0 {
-                            block_bytes += 4 - (num_bytes[i] % 4);
+            match field_code {
+                0 => { // Change byte
+                    // println!("field_code: {} offset: {} num_bytes: {} {:?}", field_code, offset, num_bytes, compressed);
+                    change_bytes = decompressed.clone();
+                    num_points_in_block = change_bytes.len();
+                    change_byte_read = true;
+                    if self.point_data.len() < point_num + num_points_in_block {
+                        self.point_data
+                            .resize(point_num + num_points_in_block, Default::default());
+                        if self.header.point_format == 2
+                            || self.header.point_format == 3
+                            || self.header.point_format == 5
+                            || self.header.point_format == 7
+                            || self.header.point_format == 8
+                        {
+                            self.colour_data
+                                .resize(point_num + num_points_in_block, Default::default());
                         }
-                        // println!("field_type: {} offset: {} num_bytes: {}", field_type[i], offset[i], num_bytes[i]);
+                    }
+                }, 
+                1 => { // Scanner channel
+                    if !change_byte_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
                     }
 
-                    // we need to read the point return data before the z-values.
-                    if return_field >= 0 {
-                        bor.seek(offset[return_field as usize] as usize);
-                        let mut compressed = vec![0u8; num_bytes[return_field as usize] as usize];
-                        bor.read_exact(&mut compressed)?;
-                        let decompressed = decompress_to_vec_zlib(&compressed)
-                            .expect("DEFLATE failed to decompress data.");
-                        num_points_in_block = decompressed.len();
-                        for j in 0..num_points_in_block {
-                            pt = point_num + j;
-                            self.point_data[pt].point_bit_field = decompressed[j];
+                    // Convert to values
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let mut scan_chan = val_u8 & 0b0000_0011u8;
+                    let mut prev_val = scan_chan;
+                    self.point_data[point_num].set_scanner_channel(scan_chan);
+                    let num_bits = 2;
+                    let mut num_bits_read = num_bits;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        if (change_bytes[j] & 0b0000_0001u8) == 1u8 {
+                            scan_chan = (val_u8 >> num_bits_read) & 0b0000_0011u8;
+                            self.point_data[pt].set_scanner_channel(scan_chan);
+                            num_bits_read += num_bits;
+                            if num_bits_read == 8 {
+                                val_num += 1;
+                                val_u8 = decompressed[val_num];
+                                num_bits_read = 0;
+                            }
+                            prev_val = scan_chan;
+                        } else {
+                            self.point_data[pt].set_scanner_channel(prev_val);
                         }
-                    } else {
-                        return Err(Error::new(
-                        ErrorKind::Other,
-                        "An error was encountered while attempting to read the point return data.",
-                    ));
                     }
 
-                    for i in 0..num_fields as usize {
-                        bor.seek(offset[i] as usize);
-                        let mut compressed = vec![0u8; num_bytes[i] as usize];
-                        bor.read_exact(&mut compressed)?;
-                        let decompressed = decompress_to_vec_zlib(&compressed)
-                            .expect("DEFLATE failed to decompress data.");
-
-                        match field_type[i] {
-                            0 => {
-                                // x
-                                num_points_in_block = decompressed.len() / 4;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                let mut val: i32;
-                                let mut vali32: i32;
-                                let mut prev_val = 0i32;
-                                for j in 0..num_points_in_block {
-                                    vali32 = bor2.read_i32().expect("Error reading byte data.");
-                                    // val = (vali32 + prev_val) as f64 * self.header.x_scale_factor
-                                    //     + self.header.x_offset;
-                                    val = vali32 + prev_val;
+                    scanner_chan_read = true;
+                },
+                2 => { // Return number
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
 
-                                    prev_val += vali32;
-                                    pt = point_num + j;
-                                    self.point_data[pt].x = val;
-                                }
+                    // Convert to values
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let mut ret_num = val_u8 & 0b0000_1111u8;
+                    self.point_data[point_num].set_return_number(ret_num);
+                    let num_bits = 4;
+                    let mut num_bits_read = num_bits;
+                    let mut prev_vals = [ret_num, ret_num, ret_num, ret_num];
+                    let mut ret_num_diff: u8;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        ret_num_diff = (change_bytes[j] & 0b0000_1100u8) >> 2;
+                        if ret_num_diff == 0 {
+                            // same as previous for scan chan
+                            self.point_data[pt].set_return_number(prev_vals[scan_chan]);
+                        } else if ret_num_diff == 1 {
+                            // one more than previous for scan chan
+                            self.point_data[pt].set_return_number(prev_vals[scan_chan] + 1);
+                            prev_vals[scan_chan] += 1;
+                        } else if ret_num_diff == 2 {
+                            // one less than previous for scan chan
+                            self.point_data[pt].set_return_number(prev_vals[scan_chan] - 1);
+                            prev_vals[scan_chan] -= 1;
+                        } else { // 3
+                            // new value stored in data
+                            ret_num = (val_u8 >> num_bits_read) & 0b0000_1111u8;
+                            self.point_data[pt].set_return_number(ret_num);
+                            num_bits_read += num_bits;
+                            if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                                val_num += 1;
+                                val_u8 = decompressed[val_num];
+                                num_bits_read = 0;
                             }
-                            1 => {
-                                // y
-                                num_points_in_block = decompressed.len() / 4;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                let mut val: i32;
-                                let mut vali32: i32;
-                                let mut prev_val = 0i32;
-                                for j in 0..num_points_in_block {
-                                    vali32 = bor2.read_i32().expect("Error reading byte data.");
-                                    val = vali32 + prev_val; // as f64 * self.header.y_scale_factor
-                                        // + self.header.y_offset;
+                            prev_vals[scan_chan] = ret_num;
+                        }
+                    }
 
-                                    // pt = point_num + j;
-                                    // if pt >= 500_000 && pt < 500_100 {
-                                    //     println!("{} {} {} {}", vali32+prev_val, vali32, prev_val, val);
-                                    // }
+                    ret_num_read = true;
+                }, 
 
-                                    prev_val += vali32;
-                                    pt = point_num + j;
-                                    self.point_data[pt].y = val;
-                                }
+                3 => { // Number of returns
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    // Convert to values
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let mut num_rets = val_u8 & 0b0000_1111u8;
+                    // println!("{} {}", point_num, num_rets);
+                    self.point_data[point_num].set_number_of_returns(num_rets);
+                    let num_bits = 4;
+                    let mut num_bits_read = num_bits;
+                    let mut prev_vals = [num_rets, num_rets, num_rets, num_rets];
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        if ((change_bytes[j] & 0b0001_0000u8) >> 4) == 1 {
+                            // new value stored in data
+                            num_rets = (val_u8 >> num_bits_read) & 0b0000_1111u8;
+                            self.point_data[pt].set_number_of_returns(num_rets);
+                            num_bits_read += num_bits;
+                            if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                                val_num += 1;
+                                val_u8 = decompressed[val_num];
+                                num_bits_read = 0;
                             }
-                            2 => {
-                                // z
-                                num_points_in_block = decompressed.len() / 4;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                let mut val: i32;
-                                let mut vali32: i32;
+                            prev_vals[scan_chan] = num_rets;
+                        } else {
+                            self.point_data[pt].set_number_of_returns(prev_vals[scan_chan]);
+                        }
 
-                                // let mut prev_val = 0i32;
-                                // for j in 0..num_points_in_block {
-                                //     vali32 = bor2.read_i32().expect("Error reading byte data.");
-                                //     val = (vali32 + prev_val) as f64 * self.header.z_scale_factor + self.header.z_offset;
+                        // if pt >= 100_000 && pt < 100_100 {
+                        // // if pt >= 0 && pt < 100 {
+                        //     println!("{}, {}/{}", pt, self.point_data[pt].return_number(), self.point_data[pt].number_of_returns());
+                        // }
+                    }
 
-                                //     pt = point_num + j;
-                                //     // if pt >= 500_000 && pt < 500_100 {
-                                //     //     println!("{} {} {} {}", vali32+prev_val, vali32, prev_val, val);
-                                //     // }
+                    num_rets_read = true;
+                }, 
 
-                                //     if self.point_data[pt].is_late_return() {
-                                //         prev_val += vali32;
-                                //     }
-                                //     self.point_data[pt].z = val;
-                                // }
+                4 => { // X
+                    if !change_byte_read || !scanner_chan_read || !ret_num_read || !num_rets_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
 
-                                let mut prev_val = 0i32;
-                                let mut prev_late_val = 0i32;
-                                let mut prev_early_val = 0i32;
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    prev_val = if self.point_data[pt].is_late_return() {
-                                        prev_late_val
-                                    } else {
-                                        prev_early_val
-                                    };
+                    offset = bor.read_u64().expect("Error while reading byte data.");
+                    num_bytes = bor.read_u64().expect("Error while reading byte data.");
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    block_bytes += 16 + num_bytes;
+                    
+                    // Decompress the bytes
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    let decompressed2 = if compression_method == 0 { 
+                        // DEFLATE
+                        decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
+                    } else if compression_method == 1 {
+                        // brotli
+                        brotli_decompress(&compressed)
+                    } else {
+                        panic!("Unrecognized compression method.")
+                    };
 
-                                    vali32 = bor2.read_i32().expect("Error reading byte data.");
-                                    val = vali32 + prev_val; // as f64 * self.header.z_scale_factor
-                                        // + self.header.z_offset;
-                                    self.point_data[pt].z = val;
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed2),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut delta_values = Vec::with_capacity(num_points_in_block);
+                    let mut val2 = Vec::with_capacity(num_points_in_block);
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let mut tag = val_u8 & 0b0000_1111u8;
+                    let mut val_i32 = if tag == 15u8 {
+                        bor2.read_i32().expect("Error reading byte data.")
+                    } else {
+                        panic!("Error reading coordinate data from zLidar file.");
+                    };
+                    val2.push(val_i32);
+                    delta_values.push(val_i32);
+                    let mut prev_vals = [val_i32, val_i32, val_i32, val_i32];
+                    self.point_data[point_num].x = val_i32; // as f64 * self.header.x_scale_factor + self.header.x_offset;
+                    let num_bits = 4;
+                    let mut num_bits_read = num_bits;
+                    let mut prev_index = [
+                        [0; 16],
+                        [0; 16],
+                        [0; 16],
+                        [0; 16],
+                    ];
+                    for _ in 1..num_points_in_block {
+                        // pt = point_num + j;
+                        
+                        tag = (val_u8 >> num_bits_read) & 0b0000_1111u8;
+                        num_bits_read += num_bits;
+                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                            val_num += 1;
+                            val_u8 = decompressed[val_num];
+                            num_bits_read = 0;
+                        }
 
-                                    if self.point_data[pt].is_late_return() {
-                                        prev_late_val += vali32;
-                                    } else {
-                                        prev_early_val += vali32;
-                                    }
-                                }
+                        if tag < 13 {
+                            // the offset from prev_val is tag - 6
+                            val_i32 = tag as i32 - 6;
+                        } else if tag == 13 {
+                            // the offset is one byte
+                            val_i32 = bor2.read_i8().expect("Error reading byte data.") as i32;
+                        } else if tag == 14 {
+                            // the offset is two bytes
+                            val_i32 = bor2.read_i16().expect("Error reading byte data.") as i32;
+                        } else { // tag == 15
+                            // the offset is four bytes
+                            val_i32 = bor2.read_i32().expect("Error reading byte data.");
+                        }
+                        val2.push(val_i32);
+                    }
 
-                                // let mut prev_val = 0i32;
-                                // for j in 0..num_points_in_block {
-                                //     vali32 = bor2.read_i32().expect("Error reading byte data.");
-                                //     val = (vali32 + prev_val) as f64 * self.header.z_scale_factor + self.header.z_offset;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        
+                        cntx = self.get_context(pt);
+                        let delta_j: i32 = val2[j] + delta_values[prev_index[scan_chan][cntx]];
+                        delta_values.push(delta_j);
+
+                        let val: i32 = prev_vals[scan_chan] + delta_j;
+
+                        self.point_data[pt].x = val; // as f64 * self.header.x_scale_factor + self.header.x_offset;
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}/{}, {}, {}, {}", pt, self.point_data[pt].return_number(), self.point_data[pt].number_of_returns(), val2[j], delta_j, self.point_data[pt].x);
+                        // }
+                        prev_vals[scan_chan] = val;
+                        prev_index[scan_chan][cntx] = j;
+                    }
+                },
 
-                                //     pt = point_num + j;
-                                //     if pt >= 500_000 && pt < 500_100 {
-                                //         println!("{} {} {} {}", vali32+prev_val, vali32, prev_val, val);
-                                //     }
+                5 => { // Y
+                    if !change_byte_read || !scanner_chan_read || !ret_num_read || !num_rets_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
 
-                                //     prev_val += vali32;
-                                //     // pt = point_num + j;
-                                //     self.point_data[pt].z = val;
-                                // }
+                    offset = bor.read_u64().expect("Error while reading byte data.");
+                    num_bytes = bor.read_u64().expect("Error while reading byte data.");
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    block_bytes += 16 + num_bytes;
+                    
+                    // Decompress the bytes
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    let decompressed2 = if compression_method == 0 { 
+                        // DEFLATE
+                        decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
+                    } else if compression_method == 1 {
+                        // brotli
+                        brotli_decompress(&compressed)
+                    } else {
+                        panic!("Unrecognized compression method.")
+                    };
 
-                                // let mut prev_val: i32;
-                                // let mut prev_late_val = 0i32;
-                                // let mut prev_early_val = 0i32;
-                                // for j in 0..num_points_in_block {
-                                //     pt = point_num + j;
-                                //     prev_val = if self.point_data[pt].is_late_return() {
-                                //         prev_late_val
-                                //     } else {
-                                //         prev_early_val
-                                //     };
-                                //     vali32 = bor2.read_i32().expect("Error reading byte data.");
-                                //     val = (vali32 + prev_val) as f64 * self.header.z_scale_factor + self.header.z_offset;
-                                //     self.point_data[pt].z = val;
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed2),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut delta_values = Vec::with_capacity(num_points_in_block);
+                    let mut val2 = Vec::with_capacity(num_points_in_block);
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let mut tag = val_u8 & 0b0000_1111u8;
+                    let mut val_i32 = if tag == 15u8 {
+                        bor2.read_i32().expect("Error reading byte data.")
+                    } else {
+                        panic!("Error reading coordinate data from zLidar file.");
+                    };
+                    val2.push(val_i32);
+                    delta_values.push(val_i32);
+                    let mut prev_vals = [val_i32, val_i32, val_i32, val_i32];
+                    self.point_data[point_num].y = val_i32; // as f64 * self.header.y_scale_factor + self.header.y_offset;
+                    let num_bits = 4;
+                    let mut num_bits_read = num_bits;
+                    let mut prev_index = [
+                        [0; 16],
+                        [0; 16],
+                        [0; 16],
+                        [0; 16],
+                    ];
+                    for _ in 1..num_points_in_block {
+                        // pt = point_num + j;
+                        
+                        tag = (val_u8 >> num_bits_read) & 0b0000_1111u8;
+                        num_bits_read += num_bits;
+                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                            val_num += 1;
+                            val_u8 = decompressed[val_num];
+                            num_bits_read = 0;
+                        }
 
-                                //     if self.point_data[pt].is_late_return() {
-                                //         prev_late_val = ((val - self.header.z_offset) / self.header.z_scale_factor) as i32;
-                                //     } else {
-                                //         prev_early_val = ((val - self.header.z_offset) / self.header.z_scale_factor) as i32;
-                                //     }
-                                //     // if j == 1000 {
-                                //     //     println!("z: {}", val);
-                                //     // }
-                                // }
-                            }
-                            3 => {
-                                // intensity
-                                num_points_in_block = decompressed.len() / 2;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.point_data[pt].intensity =
-                                        bor2.read_u16().expect("Error reading byte data.");
-                                }
-                            }
-                            4 => {
-                                // point return data has already been read.
-                            }
-                            5 => {
-                                // point class data
-                                num_points_in_block = decompressed.len();
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.point_data[pt].class_bit_field = decompressed[j];
-                                }
-                            }
-                            6 => {
-                                // scan angle
-                                num_points_in_block = decompressed.len() / 2;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                let mut val: i16;
-                                let mut prev_val = 0i16;
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    val = bor2.read_i16().expect("Error reading byte data.");
-                                    self.point_data[pt].scan_angle = val + prev_val;
-                                    prev_val = val;
-                                }
-                            }
-                            7 => {
-                                // user data
-                                num_points_in_block = decompressed.len();
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.point_data[pt].user_data = decompressed[j];
-                                }
-                            }
-                            8 => {
-                                // point source id
-                                num_points_in_block = decompressed.len() / 2;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.point_data[pt].point_source_id =
-                                        bor2.read_u16().expect("Error reading byte data.");
-                                }
-                            }
-                            9 => {
-                                // GPS time
-                                if self.gps_data.len() == 0 {
-                                    self.gps_data =
-                                        vec![0f64; self.header.number_of_points as usize];
-                                }
-                                num_points_in_block = decompressed.len() / 8;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                let mut val: f64;
-                                let mut prev_val = 0f64;
-                                for j in 0..num_points_in_block {
-                                    val = bor2.read_f64().expect("Error reading byte data.")
-                                        + prev_val;
-                                    pt = point_num + j;
-                                    self.gps_data[pt] = val;
-                                    prev_val = val;
-                                }
-                            }
-                            10 => {
-                                // red
-                                if self.colour_data.len() == 0 {
-                                    self.colour_data = vec![
-                                        Default::default();
-                                        self.header.number_of_points as usize
-                                    ];
-                                }
-                                num_points_in_block = decompressed.len() / 2;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.colour_data[pt].red =
-                                        bor2.read_u16().expect("Error reading byte data.");
-                                }
-                            }
-                            11 => {
-                                // green
-                                if self.colour_data.len() == 0 {
-                                    self.colour_data = vec![
-                                        Default::default();
-                                        self.header.number_of_points as usize
-                                    ];
-                                }
-                                num_points_in_block = decompressed.len() / 2;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.colour_data[pt].green =
-                                        bor2.read_u16().expect("Error reading byte data.");
-                                }
-                            }
-                            12 => {
-                                // blue
-                                if self.colour_data.len() == 0 {
-                                    self.colour_data = vec![
-                                        Default::default();
-                                        self.header.number_of_points as usize
-                                    ];
-                                }
-                                num_points_in_block = decompressed.len() / 2;
-                                let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
-                                    Cursor::new(decompressed),
-                                    Endianness::LittleEndian,
-                                );
-                                for j in 0..num_points_in_block {
-                                    pt = point_num + j;
-                                    self.colour_data[pt].blue =
-                                        bor2.read_u16().expect("Error reading byte data.");
-                                }
-                            }
-                            _ => {
-                                // Do nothing // return Err(Error::new(ErrorKind::Other, "Unrecognized point field"));
-                            }
+                        if tag < 13 {
+                            // the offset from prev_val is tag - 6
+                            val_i32 = tag as i32 - 6;
+                        } else if tag == 13 {
+                            // the offset is one byte
+                            val_i32 = bor2.read_i8().expect("Error reading byte data.") as i32;
+                        } else if tag == 14 {
+                            // the offset is two bytes
+                            val_i32 = bor2.read_i16().expect("Error reading byte data.") as i32;
+                        } else { // tag == 15
+                            // the offset is four bytes
+                            val_i32 = bor2.read_i32().expect("Error reading byte data.");
                         }
+                        val2.push(val_i32);
                     }
 
-                    point_num += num_points_in_block;
-                    next_offset += block_bytes as usize;
-                    if next_offset >= file_size {
-                        flag = false;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        
+                        cntx = self.get_context(pt);
+                        let delta_j: i32 = val2[j] + delta_values[prev_index[scan_chan][cntx]];
+                        delta_values.push(delta_j);
+
+                        let val: i32 = prev_vals[scan_chan] + delta_j;
+
+                        self.point_data[pt].y = val; // as f64 * self.header.y_scale_factor + self.header.y_offset;
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}, {}", pt, self.point_data[pt].x, self.point_data[pt].y);
+                        // }
+                        prev_vals[scan_chan] = val;
+                        prev_index[scan_chan][cntx] = j;
+                    }
+                },
+
+                6 => { // Z
+                    if !change_byte_read || !scanner_chan_read || !ret_num_read || !num_rets_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    offset = bor.read_u64().expect("Error while reading byte data.");
+                    num_bytes = bor.read_u64().expect("Error while reading byte data.");
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    block_bytes += 16 + num_bytes;
+                    
+                    // Decompress the bytes
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    let decompressed2 = if compression_method == 0 { 
+                        // DEFLATE
+                        decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
+                    } else if compression_method == 1 {
+                        // brotli
+                        brotli_decompress(&compressed)
+                    } else {
+                        panic!("Unrecognized compression method.")
+                    };
+
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed2),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut tag: u8;
+                    let mut val_i32: i32;
+                    let num_bits = 4;
+                    val_num = 0usize;
+                    let mut val_u8 = decompressed[val_num];
+                    let mut num_bits_read = 0;
+                    let mut prev_late_vals = [0i32, 0i32, 0i32, 0i32];
+                    let mut prev_early_vals = [0i32, 0i32, 0i32, 0i32];
+                    let mut prev_val = 0i32;
+                    for j in 0..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+
+                        tag = (val_u8 >> num_bits_read) & 0b0000_1111u8;
+                        num_bits_read += num_bits;
+                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                            val_num += 1;
+                            val_u8 = decompressed[val_num];
+                            num_bits_read = 0;
+                        }
+
+                        val_i32 = if tag < 13 {
+                            // the offset from prev_val is tag - 6
+                            tag as i32 - 6
+                        } else if tag == 13 {
+                            // the offset is one byte
+                            bor2.read_i8().expect("Error reading byte data.") as i32
+                        } else if tag == 14 {
+                            // the offset is two bytes
+                            bor2.read_i16().expect("Error reading byte data.") as i32
+                        } else { // tag == 15
+                            // the offset is four bytes
+                            bor2.read_i32().expect("Error reading byte data.")
+                        };
+
+                        prev_val = if self.point_data[pt].is_late_return() {
+                            prev_late_vals[scan_chan]
+                        } else {
+                            prev_early_vals[scan_chan]
+                        };
+
+                        val_i32 += prev_val;
+                        self.point_data[pt].z = val_i32; // as f64 * self.header.z_scale_factor + self.header.z_offset;
+
+                        if self.point_data[pt].is_late_return() {
+                            prev_late_vals[scan_chan] = val_i32;
+                        } else {
+                            prev_early_vals[scan_chan] = val_i32;
+                        }
+
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}, {}, {}", pt, self.point_data[pt].x, self.point_data[pt].y, self.point_data[pt].z);
+                        // }
                     }
+                },
+
+                7 => { // Intensity
+                    if !change_byte_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    self.point_data[point_num].intensity = bor2.read_u16().expect("Error while reading byte data.") as u16;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        if ((change_bytes[j] & 0b1000_0000u8) >> 7) == 1 { // 2 bytes
+                            self.point_data[pt].intensity = bor2.read_u16().expect("Error while reading byte data.");
+                        } else { // 1 byte
+                            self.point_data[pt].intensity = bor2.read_u8().expect("Error while reading byte data.") as u16;
+                        }
+
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     let t = ((change_bytes[j] & 0b1000_0000u8) >> 7) == 1;
+                        //     println!("{}, {}, {}", pt, t, self.point_data[pt].intensity);
+                        // }
+                    }
+                }, 
+
+                8 => { // Flags
+                    if !change_byte_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    // Convert to values
+                    for j in 0..num_points_in_block {
+                        pt = point_num + j;
+                        val_u8 = decompressed[j];
+                        if val_u8 & 0b0000_0001u8 == 1 {
+                            self.point_data[pt].set_synthetic(true);
+                        } else {
+                            self.point_data[pt].set_synthetic(false);
+                        }
+
+                        if ((val_u8 & 0b0000_0010u8) >> 1) == 1 {
+                            self.point_data[pt].set_keypoint(true);
+                        } else {
+                            self.point_data[pt].set_keypoint(false);
+                        }
+
+                        if ((val_u8 & 0b0000_0100u8) >> 2) == 1 {
+                            self.point_data[pt].set_withheld(true);
+                        } else {
+                            self.point_data[pt].set_withheld(false);
+                        }
+
+                        if ((val_u8 & 0b0000_1000u8) >> 3) == 1 {
+                            self.point_data[pt].set_overlap(true);
+                        } else {
+                            self.point_data[pt].set_overlap(false);
+                        }
+
+                        if ((val_u8 & 0b0001_0000u8) >> 4) == 1 {
+                            self.point_data[pt].set_scan_direction_flag(true);
+                        } else {
+                            self.point_data[pt].set_scan_direction_flag(false);
+                        }
+
+                        if ((val_u8 & 0b0010_0000u8) >> 5) == 1 {
+                            self.point_data[pt].set_edge_of_flightline_flag(true);
+                        } else {
+                            self.point_data[pt].set_edge_of_flightline_flag(false);
+                        }
+                    }
+                }, 
+
+                9 => { // Classification byte
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    // Convert to values
+                    self.point_data[point_num].set_classification(decompressed[0]);
+                    let mut prev_val = [decompressed[0], decompressed[0], decompressed[0], decompressed[0]];
+                    val_num = 0usize;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        if ((change_bytes[j] & 0b0010_0000u8) >> 5) == 1 {
+                            val_num += 1;
+                            self.point_data[pt].set_classification(decompressed[val_num]);
+                            prev_val[scan_chan] = decompressed[val_num];
+                        } else {
+                            self.point_data[pt].set_classification(prev_val[scan_chan]);
+                        }
+                    }
+                }, 
+
+                10 => { // User data
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    offset = bor.read_u64().expect("Error while reading byte data.");
+                    num_bytes = bor.read_u64().expect("Error while reading byte data.");
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    block_bytes += 16 + num_bytes;
+                    
+                    // Decompress the bytes
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    let decompressed2 = if compression_method == 0 { 
+                        // DEFLATE
+                        decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
+                    } else if compression_method == 1 {
+                        // brotli
+                        brotli_decompress(&compressed)
+                    } else {
+                        panic!("Unrecognized compression method.")
+                    };
+
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed2),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut tag: u8;
+                    let val = bor2.read_u8().expect("Error while reading byte data.");
+                    self.point_data[point_num].user_data = val;
+                    let mut prev_val = [val, val, val, val];
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let num_bits = 1;
+                    let mut num_bits_read = num_bits;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        
+                        tag = (val_u8 >> num_bits_read) & 0b0000_0001u8;
+                        num_bits_read += num_bits;
+                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                            val_num += 1;
+                            val_u8 = decompressed[val_num];
+                            num_bits_read = 0;
+                        }
+
+                        if tag == 1 {
+                            self.point_data[pt].user_data = bor2.read_u8().expect("Error while reading byte data.");
+                            prev_val[scan_chan] = self.point_data[pt].user_data;
+                        } else {
+                            self.point_data[pt].user_data = prev_val[scan_chan];
+                        }
+
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}", pt, self.point_data[pt].user_data);
+                        // }
+                    }
+                },
+
+                11 => { // Scan angle
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut val = bor2.read_i16().expect("Error while reading byte data.");
+                    self.point_data[point_num].scan_angle = val;
+                    let mut prev_val = [val, val, val, val];
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        if ((change_bytes[j] & 0b0100_0000u8) >> 6) == 1 {
+                            val = bor2.read_i16().expect("Error while reading byte data.");
+                            self.point_data[pt].scan_angle = val;
+                            prev_val[scan_chan] = val;
+                        } else {
+                            self.point_data[pt].scan_angle = prev_val[scan_chan];
+                        }
+
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}", pt, self.point_data[pt].scan_angle);
+                        // }
+                    }
+                }, 
+
+                12 => { // PointSourceID
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    offset = bor.read_u64().expect("Error while reading byte data.");
+                    num_bytes = bor.read_u64().expect("Error while reading byte data.");
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    block_bytes += 16 + num_bytes;
+                    
+                    // Decompress the bytes
+                    bor.seek(offset as usize);
+                    let mut compressed = vec![0u8; num_bytes as usize];
+                    bor.read_exact(&mut compressed).expect("Error while reading byte data.");
+                    let decompressed2 = if compression_method == 0 { 
+                        // DEFLATE
+                        decompress_to_vec_zlib(&compressed).expect("DEFLATE failed to decompress data.")
+                    } else if compression_method == 1 {
+                        // brotli
+                        brotli_decompress(&compressed)
+                    } else {
+                        panic!("Unrecognized compression method.")
+                    };
+
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed2),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut tag: u8;
+                    let val = bor2.read_u16().expect("Error while reading byte data.");
+                    self.point_data[point_num].point_source_id = val;
+                    let mut prev_val = [val, val, val, val];
+                    val_num = 0usize;
+                    val_u8 = decompressed[val_num];
+                    let num_bits = 1;
+                    let mut num_bits_read = num_bits;
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        
+                        tag = (val_u8 >> num_bits_read) & 0b0000_0001u8;
+                        num_bits_read += num_bits;
+                        if num_bits_read == 8 && val_num < decompressed.len()-1 {
+                            val_num += 1;
+                            val_u8 = decompressed[val_num];
+                            num_bits_read = 0;
+                        }
+
+                        if tag == 1 {
+                            self.point_data[pt].point_source_id = bor2.read_u16().expect("Error while reading byte data.");
+                            prev_val[scan_chan] = self.point_data[pt].point_source_id;
+                        } else {
+                            self.point_data[pt].point_source_id = prev_val[scan_chan];
+                        }
+
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}", pt, self.point_data[pt].point_source_id);
+                        // }
+                    }
+                },
+
+                13 => { // GPS time
+                    if !change_byte_read || !scanner_chan_read || num_points_in_block == 0 {
+                        panic!("Point block fields do not appear to be in the proper order. The file will not be read.");
+                    }
+
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut val = bor2.read_f64().expect("Error while reading byte data.");
+                    self.gps_data.push(val);
+                    let mut prev_val = [val, val, val, val];
+                    for j in 1..num_points_in_block {
+                        pt = point_num + j;
+                        scan_chan = self.point_data[pt].scanner_channel() as usize;
+                        if ((change_bytes[j] & 0b0000_0010u8) >> 1) == 1 {
+                            val = bor2.read_f64().expect("Error while reading byte data.");
+                            self.gps_data.push(val + prev_val[scan_chan]);
+                            prev_val[scan_chan] = val + prev_val[scan_chan];
+                        } else {
+                            self.gps_data.push(prev_val[scan_chan]);
+                        }
+
+                        // if pt >= 100_000 && pt < 100_100 {
+                        //     println!("{}, {}", pt, self.gps_data[pt]);
+                        // }
+                    }
+                }, 
+
+                14 => { // Red
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut val: u16;
+                    for j in 0..num_points_in_block {
+                        pt = point_num + j;
+                        val = bor2.read_u16().expect("Error while reading byte data.");
+                        self.colour_data[pt].red = val;
+                    }
+                },
+                
+                15 => { // Green
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut val: u16;
+                    for j in 0..num_points_in_block {
+                        pt = point_num + j;
+                        val = bor2.read_u16().expect("Error while reading byte data.");
+                        self.colour_data[pt].green = val;
+                    }
+                }, 
+
+                16 => { // Blue
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut val: u16;
+                    for j in 0..num_points_in_block {
+                        pt = point_num + j;
+                        val = bor2.read_u16().expect("Error while reading byte data.");
+                        self.colour_data[pt].blue = val;
+                    }
+                }, 
+
+                17 => { // NIR
+                    let mut bor2 = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+                        Cursor::new(decompressed),
+                        Endianness::LittleEndian,
+                    );
+
+                    // Convert to values
+                    let mut val: u16;
+                    for j in 0..num_points_in_block {
+                        pt = point_num + j;
+                        val = bor2.read_u16().expect("Error while reading byte data.");
+                        self.colour_data[pt].nir = val;
+                    }
+                }, 
+                _ => {
+                    panic!("Unrecognized field code.");
                 }
             }
-
-            // for i in 100_000..100_200 {
-            //     println!("{}, {}, {}, {}, {}, {}", i, self.point_data[i].x, self.point_data[i].y, self.point_data[i].z, self.point_data[i].return_number(), self.point_data[i].number_of_returns());
-            // }
         }
 
-        Ok(())
+        (num_points_in_block, block_bytes)
     }
 
     pub fn write(&mut self) -> Result<(), Error> {
@@ -2921,7 +3329,10 @@ impl LasFile {
         Ok(())
     }
 
-    fn write_data<W: Write>(&mut self, writer: &mut W) -> Result<(), Error> {
+    /// Writes the header, VLRs, and alignment bytes of a LAS or zLidar file, i.e. everything
+    /// that precedes the point data. The point counts and extents are taken from the header,
+    /// and so this may be called again, after the points are written, to update them.
+    pub(crate) fn write_header<W: Write>(&mut self, writer: &mut W) -> Result<(), Error> {
         /////////////////////////////////
         // Write the header to the file /
         /////////////////////////////////
@@ -2929,7 +3340,13 @@ impl LasFile {
         let mut u32_bytes: [u8; 4];
         let mut u64_bytes: [u8; 8];
 
-        self.header.file_signature = "LASF".to_string();
+        self.sync_extra_bytes_vlr();
+
+        self.header.file_signature = if self.file_name.to_lowercase().ends_with(".zlidar") {
+            "ZLDR".to_string()
+        } else {
+            "LASF".to_string()
+        };
         writer.write_all(self.header.file_signature.as_bytes())?;
 
         u16_bytes = unsafe { mem::transmute(self.header.file_source_id) };
@@ -3068,6 +3485,7 @@ impl LasFile {
             //if !self.use_point_intensity && !self.use_point_userdata {
             self.header.point_record_length = rec_lengths[self.header.point_format as usize][3];
         }
+        self.header.point_record_length += self.extra_bytes_length as u16;
 
         u16_bytes = unsafe { mem::transmute(self.header.point_record_length) };
         writer.write_all(&u16_bytes)?;
@@ -3161,13 +3579,23 @@ impl LasFile {
             }
         }
 
+        Ok(())
+    }
+
+    /// Writes the first `num_points` point records, in a LAS point format (0-3), to `writer`.
+    pub(crate) fn write_point_records<W: Write>(&self, writer: &mut W, num_points: usize) -> Result<(), Error> {
+        let mut u8_bytes: [u8; 1];
+        let mut u16_bytes: [u8; 2];
+        let mut u32_bytes: [u8; 4];
+        let mut u64_bytes: [u8; 8];
+
         ////////////////////////////////
         // Write the point to the file /
         ////////////////////////////////
         // let mut val: i32;
         match self.header.point_format {
             0 => {
-                for i in 0..self.header.number_of_points as usize {
+                for i in 0..num_points {
                     // val = ((self.point_data[i].x - self.header.x_offset)
                     //     / self.header.x_scale_factor) as i32;
                     u32_bytes = unsafe { mem::transmute(self.point_data[i].x) };
@@ -3216,10 +3644,12 @@ impl LasFile {
 
                     u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
                     writer.write_all(&u16_bytes)?;
+
+                    writer.write_all(self.get_extra_bytes(i))?;
                 }
             }
             1 => {
-                for i in 0..self.header.number_of_points as usize {
+                for i in 0..num_points {
                     // x
                     // val = ((self.point_data[i].x - self.header.x_offset)
                     //     / self.header.x_scale_factor) as i32;
@@ -3262,511 +3692,340 @@ impl LasFile {
                     writer.write_all(&u8_bytes)?;
 
                     if self.use_point_userdata {
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].user_data) };
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u64_bytes = unsafe { mem::transmute(self.gps_data[i]) };
-                    writer.write_all(&u64_bytes)?;
-                }
-            }
-            2 => {
-                for i in 0..self.header.number_of_points as usize {
-                    // val = ((self.point_data[i].x - self.header.x_offset)
-                    //     / self.header.x_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(self.point_data[i].x) };
-                    writer.write_all(&u32_bytes)?;
-
-                    // val = ((self.point_data[i].y - self.header.y_offset)
-                    //     / self.header.y_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(self.point_data[i].y) };
-                    writer.write_all(&u32_bytes)?;
-
-                    // val = ((self.point_data[i].z - self.header.z_offset)
-                    //     / self.header.z_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(self.point_data[i].z) };
-                    writer.write_all(&u32_bytes)?;
-
-                    if self.use_point_intensity {
-                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
-                        writer.write_all(&u16_bytes)?;
-                    }
-
-                    if !self.point_data[i].is_64bit {
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].point_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].class_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-                    } else {
-                        // there is a 64-bit point in the data that we are trying to save as 32-bit.
-                        let (point_bit_field, class_bit_field) =
-                            self.point_data[i].get_32bit_from_64bit();
-
-                        u8_bytes = unsafe { mem::transmute(point_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-
-                        u8_bytes = unsafe { mem::transmute(class_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u8_bytes = unsafe { mem::transmute(self.point_data[i].scan_angle as i8) };
-                    writer.write_all(&u8_bytes)?;
-
-                    if self.use_point_userdata {
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].user_data) };
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].red) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].green) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].blue) };
-                    writer.write_all(&u16_bytes)?;
-                }
-            }
-            3 => {
-                for i in 0..self.header.number_of_points as usize {
-                    // val = ((self.point_data[i].x - self.header.x_offset)
-                    //     / self.header.x_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(self.point_data[i].x) };
-                    writer.write_all(&u32_bytes)?;
-
-                    // val = ((self.point_data[i].y - self.header.y_offset)
-                    //     / self.header.y_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(self.point_data[i].y) };
-                    writer.write_all(&u32_bytes)?;
-
-                    // val = ((self.point_data[i].z - self.header.z_offset)
-                    //     / self.header.z_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(self.point_data[i].z) };
-                    writer.write_all(&u32_bytes)?;
-
-                    if self.use_point_intensity {
-                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
-                        writer.write_all(&u16_bytes)?;
-                    }
-
-                    if !self.point_data[i].is_64bit {
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].point_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].class_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-                    } else {
-                        // there is a 64-bit point in the data that we are trying to save as 32-bit.
-                        let (point_bit_field, class_bit_field) =
-                            self.point_data[i].get_32bit_from_64bit();
-
-                        u8_bytes = unsafe { mem::transmute(point_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-
-                        u8_bytes = unsafe { mem::transmute(class_bit_field) };
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u8_bytes = unsafe { mem::transmute(self.point_data[i].scan_angle as i8) };
-                    writer.write_all(&u8_bytes)?;
-
-                    if self.use_point_userdata {
-                        u8_bytes = unsafe { mem::transmute(self.point_data[i].user_data) };
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u64_bytes = unsafe { mem::transmute(self.gps_data[i]) };
-                    writer.write_all(&u64_bytes)?;
-
-                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].red) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].green) };
-                    writer.write_all(&u16_bytes)?;
-
-                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].blue) };
-                    writer.write_all(&u16_bytes)?;
-                }
-            }
-            _ => {
-                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
-            }
-        }
-
-        Ok(())
-    }
-
-    fn write_laz_data(&mut self) -> Result<(), Error> {
-
-        // let mut reader = Reader::from_path(&input_file).expect("Error reading LAS file.");
-        // let in_header = reader.header();
-        let mut builder = Builder::from((1, 4));
-        // let mut format = in_header.point_format().clone();
-
-        let mut format = las::point::Format::new(self.header.point_format).unwrap();
-        format.is_compressed = true;
-        builder.point_format = format;
-        builder.generating_software = "WhiteboxTools".to_string();
-        let transforms: las::Vector<las::Transform> = las::Vector{ 
-            x: las::Transform {scale: self.header.x_scale_factor, offset: self.header.x_offset }, 
-            y: las::Transform {scale: self.header.y_scale_factor, offset: self.header.y_offset }, 
-            z: las::Transform {scale: self.header.z_scale_factor, offset: self.header.z_offset }
-        };
-        builder.transforms = transforms.clone();
-        
-        for vlr in &self.vlr_data {
-            let mut vlr2 = las::Vlr::default();
-            vlr2.user_id = vlr.user_id.clone();
-            vlr2.record_id = vlr.record_id;
-            vlr2.description = vlr2.description.clone();
-            vlr2.data = vlr.binary_data.clone();
-        //     while vlr2.description.len() > 32 {
-        //         vlr2.description.pop();
-        //     }
-            builder.vlrs.push(vlr2.clone());
-        }
-
-        let out_header = builder.into_header().unwrap();
-        let f = File::create(&self.file_name).expect("Unable to create file");
-        let f = BufWriter::new(f);
-        let mut writer = OtherWriter::new(f, out_header).unwrap();
-        let mut point: las::point::Point;
-        let mut raw_point: las::raw::Point;
-        // let mut p: Point3D;
-        let mut pd: PointData;
-        for point_num in 0..self.header.number_of_points as usize {
-            pd = self[point_num];
-            raw_point = las::raw::Point::default();
-
-            // Coordinates and intensity information
-            raw_point.x = pd.x;
-            raw_point.y = pd.y;
-            raw_point.z = pd.z;
-            raw_point.intensity = pd.intensity;
-
-            // Flags
-            let flags = if self.header.point_format < 6 {
-                las::raw::point::Flags::TwoByte(pd.point_bit_field, pd.class_bit_field)
-            } else {
-                las::raw::point::Flags::ThreeByte(pd.point_bit_field, pd.class_bit_field, pd.classification)
-            };
-            raw_point.flags = flags;
-
-            raw_point.user_data = pd.user_data;
-
-            if self.header.point_format < 6 {
-                raw_point.scan_angle = las::raw::point::ScanAngle::Rank(pd.scan_angle as i8);
-            } else {
-                raw_point.scan_angle = las::raw::point::ScanAngle::Scaled(pd.scan_angle);
-            }
-            
-            raw_point.point_source_id = pd.point_source_id;
-
-            // GPS time information
-            if self.has_gps_time() {
-                raw_point.gps_time = Some(self.gps_data[point_num]);
-            } else {
-                raw_point.gps_time = None;
-            }
-
-            // Colour information
-            if self.has_rgb() {
-                let colour = las::Color { 
-                    red: self.colour_data[point_num].red,
-                    green: self.colour_data[point_num].green,
-                    blue: self.colour_data[point_num].blue
-                };
-                raw_point.color = Some(colour);
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].user_data) };
+                        writer.write_all(&u8_bytes)?;
+                    }
 
-                if self.header.point_format == 8 || self.header.point_format == 10 {
-                    raw_point.nir = Some(self.colour_data[point_num].nir);
-                } else {
-                    raw_point.nir = None;
+                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
+                    writer.write_all(&u16_bytes)?;
+
+                    u64_bytes = unsafe { mem::transmute(self.gps_data[i]) };
+                    writer.write_all(&u64_bytes)?;
+
+                    writer.write_all(self.get_extra_bytes(i))?;
                 }
-            } else {
-                raw_point.color = None;
             }
+            2 => {
+                for i in 0..num_points {
+                    // val = ((self.point_data[i].x - self.header.x_offset)
+                    //     / self.header.x_scale_factor) as i32;
+                    u32_bytes = unsafe { mem::transmute(self.point_data[i].x) };
+                    writer.write_all(&u32_bytes)?;
 
-            // Waveform information
-            if self.header.point_format == 4 || self.header.point_format == 5 ||
-            self.header.point_format == 9 || self.header.point_format == 10 {
-                let wf = las::raw::point::Waveform {
-                    wave_packet_descriptor_index: self.waveform_data[point_num].packet_descriptor_index,
-                    byte_offset_to_waveform_data: self.waveform_data[point_num].offset_to_waveform_data,
-                    waveform_packet_size_in_bytes: self.waveform_data[point_num].waveform_packet_size,
-                    return_point_waveform_location: self.waveform_data[point_num].ret_point_waveform_loc,
-                    x_t: self.waveform_data[point_num].xt,
-                    y_t: self.waveform_data[point_num].yt,
-                    z_t: self.waveform_data[point_num].zt,
-                };
-                raw_point.waveform = Some(wf);
-            } else {
-                raw_point.waveform = None;
-            }
+                    // val = ((self.point_data[i].y - self.header.y_offset)
+                    //     / self.header.y_scale_factor) as i32;
+                    u32_bytes = unsafe { mem::transmute(self.point_data[i].y) };
+                    writer.write_all(&u32_bytes)?;
 
-            point = las::point::Point::new(raw_point, &transforms);
-            writer.write(point.clone()).expect("Error writing point data");
-        }
+                    // val = ((self.point_data[i].z - self.header.z_offset)
+                    //     / self.header.z_scale_factor) as i32;
+                    u32_bytes = unsafe { mem::transmute(self.point_data[i].z) };
+                    writer.write_all(&u32_bytes)?;
 
-        writer.close().unwrap();
-        
-        Ok(())
-    }
+                    if self.use_point_intensity {
+                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
+                        writer.write_all(&u16_bytes)?;
+                    }
 
-    fn write_zlidar_data<W: Write>(&mut self, writer: &mut W) -> Result<(), Error> {
-        /////////////////////////////////
-        // Write the header to the file /
-        /////////////////////////////////
-        let mut u16_bytes: [u8; 2];
-        let mut u32_bytes: [u8; 4];
-        let mut u64_bytes: [u8; 8];
+                    if !self.point_data[i].is_64bit {
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].point_bit_field) };
+                        writer.write_all(&u8_bytes)?;
 
-        self.header.file_signature = "ZLDR".to_string();
-        writer.write_all(self.header.file_signature.as_bytes())?;
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].class_bit_field) };
+                        writer.write_all(&u8_bytes)?;
+                    } else {
+                        // there is a 64-bit point in the data that we are trying to save as 32-bit.
+                        let (point_bit_field, class_bit_field) =
+                            self.point_data[i].get_32bit_from_64bit();
 
-        u16_bytes = unsafe { mem::transmute(self.header.file_source_id) };
-        writer.write_all(&u16_bytes)?;
+                        u8_bytes = unsafe { mem::transmute(point_bit_field) };
+                        writer.write_all(&u8_bytes)?;
 
-        u16_bytes = unsafe { mem::transmute(self.header.global_encoding) };
-        writer.write_all(&u16_bytes)?;
+                        u8_bytes = unsafe { mem::transmute(class_bit_field) };
+                        writer.write_all(&u8_bytes)?;
+                    }
 
-        if self.header.project_id_used {
-            u32_bytes = unsafe { mem::transmute(self.header.project_id1) };
-            writer.write_all(&u32_bytes)?;
+                    u8_bytes = unsafe { mem::transmute(self.point_data[i].scan_angle as i8) };
+                    writer.write_all(&u8_bytes)?;
 
-            u16_bytes = unsafe { mem::transmute(self.header.project_id2) };
-            writer.write_all(&u16_bytes)?;
+                    if self.use_point_userdata {
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].user_data) };
+                        writer.write_all(&u8_bytes)?;
+                    }
 
-            u16_bytes = unsafe { mem::transmute(self.header.project_id3) };
-            writer.write_all(&u16_bytes)?;
+                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
+                    writer.write_all(&u16_bytes)?;
 
-            u64_bytes = unsafe { mem::transmute(self.header.project_id4) };
-            writer.write_all(&u64_bytes)?;
-        }
+                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].red) };
+                    writer.write_all(&u16_bytes)?;
 
-        self.header.version_major = 1u8;
-        let mut u8_bytes: [u8; 1] = unsafe { mem::transmute(self.header.version_major) };
-        writer.write_all(&u8_bytes)?;
+                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].green) };
+                    writer.write_all(&u16_bytes)?;
 
-        self.header.version_minor = 3u8;
-        u8_bytes = unsafe { mem::transmute(self.header.version_minor) };
-        writer.write_all(&u8_bytes)?;
+                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].blue) };
+                    writer.write_all(&u16_bytes)?;
 
-        if self.header.system_id.len() == 0 {
-            self.header.system_id = fixed_length_string("OTHER", 32);
-        } else if !self.header.system_id.len() != 32 {
-            self.header.system_id = fixed_length_string(&(self.header.system_id), 32);
-        }
-        writer.write_all(self.header.system_id.as_bytes())?; //string_bytes));
+                    writer.write_all(self.get_extra_bytes(i))?;
+                }
+            }
+            3 => {
+                for i in 0..num_points {
+                    // val = ((self.point_data[i].x - self.header.x_offset)
+                    //     / self.header.x_scale_factor) as i32;
+                    u32_bytes = unsafe { mem::transmute(self.point_data[i].x) };
+                    writer.write_all(&u32_bytes)?;
 
-        self.header.generating_software =
-            fixed_length_string("WhiteboxTools                   ", 32);
-        writer.write_all(self.header.generating_software.as_bytes())?;
+                    // val = ((self.point_data[i].y - self.header.y_offset)
+                    //     / self.header.y_scale_factor) as i32;
+                    u32_bytes = unsafe { mem::transmute(self.point_data[i].y) };
+                    writer.write_all(&u32_bytes)?;
 
-        // let now = time::now();
-        // self.header.file_creation_day = now.tm_yday as u16;
-        let now = Local::now();
-        self.header.file_creation_day = now.ordinal() as u16;
-        u16_bytes = unsafe { mem::transmute(self.header.file_creation_day) };
-        writer.write_all(&u16_bytes)?;
+                    // val = ((self.point_data[i].z - self.header.z_offset)
+                    //     / self.header.z_scale_factor) as i32;
+                    u32_bytes = unsafe { mem::transmute(self.point_data[i].z) };
+                    writer.write_all(&u32_bytes)?;
 
-        // self.header.file_creation_year = (now.tm_year + 1900) as u16;
-        self.header.file_creation_year = now.year() as u16;
-        u16_bytes = unsafe { mem::transmute(self.header.file_creation_year) };
-        writer.write_all(&u16_bytes)?;
+                    if self.use_point_intensity {
+                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
+                        writer.write_all(&u16_bytes)?;
+                    }
 
-        self.header.header_size = 235; // THIS NEEDS TO BE FIXED WHEN LAS 1.4 SUPPORT IS ADDED FOR WRITING
-        u16_bytes = unsafe { mem::transmute(self.header.header_size) };
-        writer.write_all(&u16_bytes)?;
+                    if !self.point_data[i].is_64bit {
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].point_bit_field) };
+                        writer.write_all(&u8_bytes)?;
 
-        // figure out the offset to points
-        let mut total_vlr_size = 54 * self.header.number_of_vlrs;
-        for i in 0..(self.header.number_of_vlrs as usize) {
-            total_vlr_size += self.vlr_data[i].record_length_after_header as u32;
-        }
-        let alignment_bytes = 4u32 - ((self.header.header_size as u32 + total_vlr_size) % 4u32);
-        self.header.offset_to_points =
-            self.header.header_size as u32 + total_vlr_size + alignment_bytes; // THIS NEEDS TO BE FIXED WHEN LAS 1.4 SUPPORT IS ADDED FOR WRITING
-        u32_bytes = unsafe { mem::transmute(self.header.offset_to_points) };
-        writer.write_all(&u32_bytes)?;
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].class_bit_field) };
+                        writer.write_all(&u8_bytes)?;
+                    } else {
+                        // there is a 64-bit point in the data that we are trying to save as 32-bit.
+                        let (point_bit_field, class_bit_field) =
+                            self.point_data[i].get_32bit_from_64bit();
 
-        u32_bytes = unsafe { mem::transmute(self.header.number_of_vlrs) };
-        writer.write_all(&u32_bytes)?;
+                        u8_bytes = unsafe { mem::transmute(point_bit_field) };
+                        writer.write_all(&u8_bytes)?;
 
-        ////////////////////////////////////////////////////////////////////////
-        // THIS NEEDS TO BE REMOVED WHEN LAS 1.4 SUPPORT IS ADDED FOR WRITING //
-        ////////////////////////////////////////////////////////////////////////
-        self.header.point_format = match self.header.point_format {
-            0u8 => 0u8,
-            1u8 => 1u8,
-            2u8 => 2u8,
-            3u8 => 3u8,
-            4u8 => {
-                println!(
-                    "Warning: Point Format 4 is not supported for output. Some data will be lost."
-                );
-                1u8
-            }
-            5u8 => {
-                println!(
-                    "Warning: Point Format 5 is not supported for output. Some data will be lost."
-                );
-                3u8
-            }
-            6u8 => 1u8,
-            7u8 => 3u8,
-            8u8 => {
-                println!(
-                    "Warning: Point Format 8 is not supported for output. Some data will be lost."
-                );
-                3u8
-            }
-            9u8 => {
-                println!(
-                    "Warning: Point Format 9 is not supported for output. Some data will be lost."
-                );
-                1u8
-            }
-            10u8 => {
-                println!(
-                    "Warning: Point Format 10 is not supported for output. Some data will be lost."
-                );
-                3u8
-            }
-            _ => {
-                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
-            }
-        };
+                        u8_bytes = unsafe { mem::transmute(class_bit_field) };
+                        writer.write_all(&u8_bytes)?;
+                    }
 
-        u8_bytes = unsafe { mem::transmute(self.header.point_format) };
-        writer.write_all(&u8_bytes)?;
+                    u8_bytes = unsafe { mem::transmute(self.point_data[i].scan_angle as i8) };
+                    writer.write_all(&u8_bytes)?;
 
-        // Intensity and userdata are both optional. Figure out if they need to be read.
-        // The only way to do this is to compare the point record length by point format
-        let rec_lengths = [
-            [20_u16, 18_u16, 19_u16, 17_u16],
-            [28_u16, 26_u16, 27_u16, 25_u16],
-            [26_u16, 24_u16, 25_u16, 23_u16],
-            [34_u16, 32_u16, 33_u16, 31_u16],
-        ];
+                    if self.use_point_userdata {
+                        u8_bytes = unsafe { mem::transmute(self.point_data[i].user_data) };
+                        writer.write_all(&u8_bytes)?;
+                    }
 
-        if self.use_point_intensity && self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][0];
-        } else if !self.use_point_intensity && self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][1];
-        } else if self.use_point_intensity && !self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][2];
-        } else {
-            //if !self.use_point_intensity && !self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][3];
-        }
+                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
+                    writer.write_all(&u16_bytes)?;
+
+                    u64_bytes = unsafe { mem::transmute(self.gps_data[i]) };
+                    writer.write_all(&u64_bytes)?;
+
+                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].red) };
+                    writer.write_all(&u16_bytes)?;
 
-        u16_bytes = unsafe { mem::transmute(self.header.point_record_length) };
-        writer.write_all(&u16_bytes)?;
+                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].green) };
+                    writer.write_all(&u16_bytes)?;
 
-        if self.header.number_of_points <= u32::max_value() as u64 {
-            self.header.number_of_points_old = self.header.number_of_points as u32;
-        // THIS NEEDS TO BE FIXED WHEN LAS 1.4 SUPPORT IS ADDED FOR WRITING
-        } else {
-            return Err(Error::new(ErrorKind::Other, "The number of points in this file requires a 64-bit format. Currently LAS 1.4 files cannot be written."));
-        }
-        u32_bytes = unsafe { mem::transmute(self.header.number_of_points_old) };
-        writer.write_all(&u32_bytes)?;
+                    u16_bytes = unsafe { mem::transmute(self.colour_data[i].blue) };
+                    writer.write_all(&u16_bytes)?;
 
-        for i in 0..5 {
-            // THIS NEEDS TO BE FIXED WHEN LAS 1.4 SUPPORT IS ADDED FOR WRITING
-            u32_bytes = unsafe { mem::transmute(self.header.number_of_points_by_return[i] as u32) };
-            writer.write_all(&u32_bytes)?;
+                    writer.write_all(self.get_extra_bytes(i))?;
+                }
+            }
+            _ => {
+                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
+            }
         }
 
-        u64_bytes = unsafe { mem::transmute(self.header.x_scale_factor) };
-        writer.write_all(&u64_bytes)?;
 
-        u64_bytes = unsafe { mem::transmute(self.header.y_scale_factor) };
-        writer.write_all(&u64_bytes)?;
+        Ok(())
+    }
 
-        u64_bytes = unsafe { mem::transmute(self.header.z_scale_factor) };
-        writer.write_all(&u64_bytes)?;
+    fn write_data<W: Write>(&mut self, writer: &mut W) -> Result<(), Error> {
+        self.write_header(writer)?;
+        self.write_point_records(writer, self.header.number_of_points as usize)
+    }
 
-        u64_bytes = unsafe { mem::transmute(self.header.x_offset) };
-        writer.write_all(&u64_bytes)?;
+    fn write_laz_data(&mut self) -> Result<(), Error> {
+        self.sync_extra_bytes_vlr();
+        let mut writer = self.create_laz_writer()?;
+        self.write_laz_points(&mut writer, self.header.number_of_points as usize);
 
-        u64_bytes = unsafe { mem::transmute(self.header.y_offset) };
-        writer.write_all(&u64_bytes)?;
+        writer.close().unwrap();
+        
+        Ok(())
+    }
 
-        u64_bytes = unsafe { mem::transmute(self.header.z_offset) };
-        writer.write_all(&u64_bytes)?;
+    /// Returns the scale factors and offsets of the coordinates, for use with the las crate.
+    pub(crate) fn laz_transforms(&self) -> las::Vector<las::Transform> {
+        las::Vector{ 
+            x: las::Transform {scale: self.header.x_scale_factor, offset: self.header.x_offset }, 
+            y: las::Transform {scale: self.header.y_scale_factor, offset: self.header.y_offset }, 
+            z: las::Transform {scale: self.header.z_scale_factor, offset: self.header.z_offset }
+        }
+    }
 
-        u64_bytes = unsafe { mem::transmute(self.header.max_x) };
-        writer.write_all(&u64_bytes)?;
+    /// Creates the file and returns a las crate writer for LAZ data, with the header and VLRs
+    /// of this file.
+    pub(crate) fn create_laz_writer(&self) -> Result<OtherWriter<BufWriter<File>>, Error> {
+        // let mut reader = Reader::from_path(&input_file).expect("Error reading LAS file.");
+        // let in_header = reader.header();
+        let mut builder = Builder::from((1, 4));
+        // let mut format = in_header.point_format().clone();
 
-        u64_bytes = unsafe { mem::transmute(self.header.min_x) };
-        writer.write_all(&u64_bytes)?;
+        let mut format = las::point::Format::new(self.header.point_format)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        format.is_compressed = true;
+        format.extra_bytes = self.extra_bytes_length as u16;
+        builder.point_format = format;
+        builder.generating_software = "WhiteboxTools".to_string();
+        builder.transforms = self.laz_transforms();
+        
+        for vlr in &self.vlr_data {
+            // The las crate writes its own LASzip VLR, which must describe the extra bytes.
+            if vlr.user_id.trim_end_matches('\0') == laz::LazVlr::USER_ID
+                && vlr.record_id == laz::LazVlr::RECORD_ID
+            {
+                continue;
+            }
+            let mut vlr2 = las::Vlr::default();
+            vlr2.user_id = vlr.user_id.clone();
+            vlr2.record_id = vlr.record_id;
+            vlr2.description = vlr2.description.clone();
+            vlr2.data = vlr.binary_data.clone();
+        //     while vlr2.description.len() > 32 {
+        //         vlr2.description.pop();
+        //     }
+            builder.vlrs.push(vlr2.clone());
+        }
 
-        u64_bytes = unsafe { mem::transmute(self.header.max_y) };
-        writer.write_all(&u64_bytes)?;
+        let out_header = builder
+            .into_header()
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        let f = File::create(&self.file_name)?;
+        let f = BufWriter::new(f);
+        OtherWriter::new(f, out_header).map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+    }
 
-        u64_bytes = unsafe { mem::transmute(self.header.min_y) };
-        writer.write_all(&u64_bytes)?;
+    /// Writes the first `num_points` points to a las crate writer, for LAZ data.
+    pub(crate) fn write_laz_points(&self, writer: &mut OtherWriter<BufWriter<File>>, num_points: usize) {
+        let transforms = self.laz_transforms();
+        let mut point: las::point::Point;
+        let mut raw_point: las::raw::Point;
+        // let mut p: Point3D;
+        let mut pd: PointData;
+        for point_num in 0..num_points {
+            pd = self[point_num];
+            raw_point = las::raw::Point::default();
 
-        u64_bytes = unsafe { mem::transmute(self.header.max_z) };
-        writer.write_all(&u64_bytes)?;
+            // Coordinates and intensity information
+            raw_point.x = pd.x;
+            raw_point.y = pd.y;
+            raw_point.z = pd.z;
+            raw_point.intensity = pd.intensity;
 
-        u64_bytes = unsafe { mem::transmute(self.header.min_z) };
-        writer.write_all(&u64_bytes)?;
+            // Flags
+            let flags = if self.header.point_format < 6 {
+                las::raw::point::Flags::TwoByte(pd.point_bit_field, pd.class_bit_field)
+            } else {
+                las::raw::point::Flags::ThreeByte(pd.point_bit_field, pd.class_bit_field, pd.classification)
+            };
+            raw_point.flags = flags;
 
-        u64_bytes = unsafe { mem::transmute(self.header.waveform_data_start) };
-        writer.write_all(&u64_bytes)?;
+            raw_point.user_data = pd.user_data;
 
-        ///////////////////////////////
-        // Write the VLRs to the file /
-        ///////////////////////////////
-        for i in 0..(self.header.number_of_vlrs as usize) {
-            let vlr = self.vlr_data[i].clone();
-            u16_bytes = unsafe { mem::transmute(vlr.reserved) };
-            writer.write_all(&u16_bytes)?;
+            if self.header.point_format < 6 {
+                raw_point.scan_angle = las::raw::point::ScanAngle::Rank(pd.scan_angle as i8);
+            } else {
+                raw_point.scan_angle = las::raw::point::ScanAngle::Scaled(pd.scan_angle);
+            }
+            
+            raw_point.point_source_id = pd.point_source_id;
 
-            let user_id: &str = &vlr.user_id;
-            //string_bytes = unsafe { mem::transmute(user_id) };
-            writer.write_all(fixed_length_string(user_id, 16).as_bytes())?; //string_bytes));
+            // GPS time information
+            if self.has_gps_time() {
+                raw_point.gps_time = Some(self.gps_data[point_num]);
+            } else {
+                raw_point.gps_time = None;
+            }
 
-            u16_bytes = unsafe { mem::transmute(vlr.record_id) };
-            writer.write_all(&u16_bytes)?;
+            // Colour information
+            if self.has_rgb() {
+                let colour = las::Color { 
+                    red: self.colour_data[point_num].red,
+                    green: self.colour_data[point_num].green,
+                    blue: self.colour_data[point_num].blue
+                };
+                raw_point.color = Some(colour);
 
-            u16_bytes = unsafe { mem::transmute(vlr.record_length_after_header) };
-            writer.write_all(&u16_bytes)?;
+                if self.header.point_format == 8 || self.header.point_format == 10 {
+                    raw_point.nir = Some(self.colour_data[point_num].nir);
+                } else {
+                    raw_point.nir = None;
+                }
+            } else {
+                raw_point.color = None;
+            }
 
-            let description: &str = &vlr.description;
-            //string_bytes = unsafe { mem::transmute(description) };
-            writer.write_all(fixed_length_string(description, 32).as_bytes())?;
+            // Waveform information
+            if self.header.point_format == 4 || self.header.point_format == 5 ||
+            self.header.point_format == 9 || self.header.point_format == 10 {
+                let wf = las::raw::point::Waveform {
+                    wave_packet_descriptor_index: self.waveform_data[point_num].packet_descriptor_index,
+                    byte_offset_to_waveform_data: self.waveform_data[point_num].offset_to_waveform_data,
+                    waveform_packet_size_in_bytes: self.waveform_data[point_num].waveform_packet_size,
+                    return_point_waveform_location: self.waveform_data[point_num].ret_point_waveform_loc,
+                    x_t: self.waveform_data[point_num].xt,
+                    y_t: self.waveform_data[point_num].yt,
+                    z_t: self.waveform_data[point_num].zt,
+                };
+                raw_point.waveform = Some(wf);
+            } else {
+                raw_point.waveform = None;
+            }
 
-            writer.write_all(&vlr.binary_data)?;
-        }
+            raw_point.extra_bytes = self.get_extra_bytes(point_num).to_vec();
 
-        ////////////////////
-        // Alignment bytes /
-        ////////////////////
-        if alignment_bytes > 0 {
-            // println!("alignment bytes: {}", alignment_bytes);
-            for _ in 0..alignment_bytes {
-                writer.write_all(&[0u8])?;
+            point = las::point::Point::new(raw_point, &transforms);
+            if self.header.point_format >= 6 {
+                // The las crate truncates the scan angle, in degrees, when it converts it to
+                // increments of 0.006 degrees, so the angle is offset by half an increment.
+                let scan_angle = pd.scan_angle as f32;
+                point.scan_angle = (scan_angle + 0.5f32.copysign(scan_angle)) * 0.006;
             }
+            writer.write(point.clone()).expect("Error writing point data");
         }
+    }
 
-        /////////////////////////////////////
-        // Write the point data to the file /
-        /////////////////////////////////////
+    fn zlidar_compression_codes(&self) -> (u8, u8) {
+        let compression_method = match self.compression {
+            ZlidarCompression::Deflate { level: _ } => {
+                0u8
+            },
+            ZlidarCompression::Brotli { level: _ } => {
+                1u8
+            }
+            _ => { 1u8 }
+        };
+
+        let compression_level = match self.compression {
+            ZlidarCompression::Deflate { level } => {
+                level
+            },
+            ZlidarCompression::Brotli { level } => {
+                level
+            }
+            _ => { 5u8 }
+        };
+
+        (compression_method, compression_level)
+    }
 
+    /// Writes the four-byte zLidar header that precedes the point blocks, which gives the
+    /// number of fields in each block, the compression method and level, and the version.
+    pub(crate) fn write_zlidar_point_header<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
         // Write the zlidar header
 
         // Number of fields in point blocks
@@ -3796,25 +4055,7 @@ impl LasFile {
         //     _ => println!("Compression method: not recognized"),
         // }
 
-        let compression_method = match self.compression {
-            ZlidarCompression::Deflate { level: _ } => {
-                0u8
-            },
-            ZlidarCompression::Brotli { level: _ } => {
-                1u8
-            }
-            _ => { 1u8 }
-        };
-
-        let compression_level = match self.compression {
-            ZlidarCompression::Deflate { level } => {
-                level
-            },
-            ZlidarCompression::Brotli { level } => {
-                level
-            }
-            _ => { 5u8 }
-        };
+        let (compression_method, compression_level) = self.zlidar_compression_codes();
 
         let compression_byte = ((compression_level & 0b0001_1111) << 3) | (compression_method & 0b000_0111);
         
@@ -3835,8 +4076,20 @@ impl LasFile {
             .write_u8(1u8)
             .expect("Error writing byte data to file."); // zlidar minor version number
 
-        // Now write the point data blocks
-        let mut current_offset = self.header.offset_to_points as u64 + 4; // the four bytes are for the zlidar header
+        Ok(())
+    }
+
+    /// Writes the points from `first_point` up to, but not including, `last_point` as zLidar
+    /// point blocks of up to 50,000 points, starting at byte `current_offset` of the file.
+    /// Returns the offset of the end of the last block.
+    pub(crate) fn write_zlidar_blocks<W: Write>(
+        &self,
+        writer: &mut W,
+        first_point: usize,
+        last_point: usize,
+        mut current_offset: u64,
+    ) -> u64 {
+        let (compression_method, compression_level) = self.zlidar_compression_codes();
         let mut data_length_in_bytes: u64;
         let mut val: i32;
         let mut val2: i32;
@@ -3849,12 +4102,12 @@ impl LasFile {
         let mut cntx: usize;
         let mut tag: u8;
         let block_size = 50_000usize;
-        let mut block_start = 0usize;
-        let mut block_end = block_size;
-        if block_end > self.header.number_of_points as usize {
-            block_end = self.header.number_of_points as usize;
+        let mut block_start = first_point;
+        let mut block_end = first_point + block_size;
+        if block_end > last_point {
+            block_end = last_point;
         }
-        while block_start < self.header.number_of_points as usize {
+        while block_start < last_point {
             let mut scanner_chan_index = [block_start, block_start, block_start, block_start];
 
             // Change byte
@@ -5266,11 +5519,27 @@ impl LasFile {
             // Update the block start and end values
             block_start = block_end;
             block_end += block_size;
-            if block_end > self.header.number_of_points as usize {
-                block_end = self.header.number_of_points as usize;
+            if block_end > last_point {
+                block_end = last_point;
             }
         }
 
+        current_offset
+    }
+
+    fn write_zlidar_data<W: Write>(&mut self, writer: &mut W) -> Result<(), Error> {
+        self.write_header(writer)?;
+
+        /////////////////////////////////////
+        // Write the point data to the file /
+        /////////////////////////////////////
+
+        self.write_zlidar_point_header(writer)?;
+
+        // Now write the point data blocks
+        let current_offset = self.header.offset_to_points as u64 + 4; // the four bytes are for the zlidar header
+        self.write_zlidar_blocks(writer, 0, self.header.number_of_points as usize, current_offset);
+
         // let mut table_size = 4u64 + 10 * 20;
         // if self.use_point_intensity {
         //     table_size += 20u64;
@@ -6594,6 +6863,25 @@ impl LasFile {
     }
 }
 
+/// Returns the number of bytes preceding the point data of a LAS or zLidar file, or the
+/// file size if the header does not give a plausible offset to the point data. The file
+/// position is restored to the start of the file.
+fn header_and_vlrs_size(f: &mut File, file_size: usize) -> Result<usize, Error> {
+    if file_size < 375 {
+        return Ok(file_size);
+    }
+    let mut header = [0u8; 100];
+    f.read_exact(&mut header)?;
+    f.seek(SeekFrom::Start(0))?;
+    let header_size = u16::from_le_bytes([header[94], header[95]]) as usize;
+    let offset_to_points =
+        u32::from_le_bytes([header[96], header[97], header[98], header[99]]) as usize;
+    if header_size < 227 || offset_to_points < header_size || offset_to_points > file_size {
+        return Ok(file_size);
+    }
+    Ok(offset_to_points.max(375))
+}
+
 fn brotli_compress(input: &[u8], level: u8) -> Vec<u8> {
     let mut writer = brotli::CompressorWriter::new(Vec::new(), 4096, level as u32, 22);
     writer.write_all(input).unwrap();
@@ -6790,6 +7078,24 @@ pub enum LidarPointRecord {
 }
 
 impl LidarPointRecord {
+    /// Replaces the point data of the record, leaving any GPS time, colour, and waveform
+    /// data unchanged.
+    pub fn set_point_data(&mut self, data: PointData) {
+        match self {
+            LidarPointRecord::PointRecord0 { point_data } => *point_data = data,
+            LidarPointRecord::PointRecord1 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord2 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord3 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord4 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord5 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord6 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord7 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord8 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord9 { point_data, .. } => *point_data = data,
+            LidarPointRecord::PointRecord10 { point_data, .. } => *point_data = data,
+        }
+    }
+
     pub fn get_point_data(&self) -> PointData {
         return match self {
             LidarPointRecord::PointRecord0 { point_data } => point_data.clone(),
@@ -6805,6 +7111,35 @@ impl LidarPointRecord {
             LidarPointRecord::PointRecord10 { point_data, .. } => point_data.clone(),
         };
     }
+
+    /// Returns the GPS time of the record, or `None` if its point format does not include one.
+    pub fn get_gps_time(&self) -> Option<f64> {
+        match self {
+            LidarPointRecord::PointRecord0 { .. } | LidarPointRecord::PointRecord2 { .. } => None,
+            LidarPointRecord::PointRecord1 { gps_data, .. }
+            | LidarPointRecord::PointRecord3 { gps_data, .. }
+            | LidarPointRecord::PointRecord4 { gps_data, .. }
+            | LidarPointRecord::PointRecord5 { gps_data, .. }
+            | LidarPointRecord::PointRecord6 { gps_data, .. }
+            | LidarPointRecord::PointRecord7 { gps_data, .. }
+            | LidarPointRecord::PointRecord8 { gps_data, .. }
+            | LidarPointRecord::PointRecord9 { gps_data, .. }
+            | LidarPointRecord::PointRecord10 { gps_data, .. } => Some(*gps_data),
+        }
+    }
+
+    /// Returns the colour of the record, or `None` if its point format does not include one.
+    pub fn get_rgb(&self) -> Option<ColourData> {
+        match self {
+            LidarPointRecord::PointRecord2 { colour_data, .. }
+            | LidarPointRecord::PointRecord3 { colour_data, .. }
+            | LidarPointRecord::PointRecord5 { colour_data, .. }
+            | LidarPointRecord::PointRecord7 { colour_data, .. }
+            | LidarPointRecord::PointRecord8 { colour_data, .. }
+            | LidarPointRecord::PointRecord10 { colour_data, .. } => Some(*colour_data),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Default, Copy, Clone, Debug)]
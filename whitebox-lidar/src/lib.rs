@@ -7,13 +7,19 @@ License: MIT
 */
 
 // private sub-module defined in other files
+mod copc;
+mod extra_bytes;
 mod header;
 mod las;
 mod point_data;
+mod streaming;
 mod vlr;
 mod zlidar_compression;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::copc::CopcInfo;
+pub use self::extra_bytes::ExtraByteDataType;
+pub use self::extra_bytes::ExtraByteDescriptor;
 pub use self::header::LasHeader;
 pub use self::las::CoordinateReferenceSystem;
 pub use self::las::GlobalEncodingField;
@@ -35,5 +41,8 @@ pub use self::point_data::convert_class_val_to_class_string;
 pub use self::point_data::ColourData;
 pub use self::point_data::PointData;
 pub use self::point_data::WaveformPacket;
+pub use self::streaming::LasPoints;
+pub use self::streaming::LasReader;
+pub use self::streaming::LasWriter;
 pub use self::vlr::Vlr;
 pub use self::zlidar_compression::ZlidarCompression;
@@ -0,0 +1,426 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Chunked reading and writing of LiDAR point records, for point clouds that are too
+//! large to be held in memory. `LasReader` decodes the points of a LAS, LAZ, or zLidar
+//! file a chunk at a time and `LasWriter` buffers output points and writes them a chunk
+//! at a time, so that the memory requirement does not depend on the number of points.
+
+use super::las::{LasFile, LidarPointRecord};
+use super::header::LasHeader;
+use super::point_data::PointData;
+use super::zlidar_compression::ZlidarCompression;
+use las::Read as LasRead;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Seek, SeekFrom, Write};
+use whitebox_common::structures::Point3D;
+use whitebox_common::utils::{ByteOrderReader, Endianness};
+
+/// The number of points held in memory by a `LasReader` or `LasWriter`. This matches the
+/// size of the point blocks of zLidar files.
+const CHUNK_SIZE: usize = 50_000;
+
+enum PointSource {
+    Las {
+        reader: BufReader<File>,
+        format: las::point::Format,
+        num_remaining: u64,
+    },
+    Laz {
+        reader: las::Reader<'static>,
+        transforms: las::Vector<las::Transform>,
+    },
+    Zlidar {
+        bor: ByteOrderReader<BufReader<File>>,
+        next_offset: usize,
+        num_fields: u8,
+        compression_method: u8,
+    },
+    // Zipped LAS files and zLidar files prior to version 1.1 are read in their entirety.
+    InMemory { is_read: bool },
+}
+
+/// Reads the point records of a LAS, LAZ, or zLidar file a chunk of 50,000 points at a
+/// time, rather than reading the entire file into memory as `LasFile::new` does.
+/// Zipped LAS files and zLidar files created by versions of WhiteboxTools prior to zLidar
+/// 1.1 cannot be read in chunks and are instead read into memory when the first point is
+/// requested.
+///
+/// ```ignore
+/// let mut reader = LasReader::new("input.las")?;
+/// let mut output = LasFile::initialize_using_file("output.las", reader.las_file());
+/// let mut writer = LasWriter::new(output)?;
+/// for point in reader.points() {
+///     writer.write_point(point?)?;
+/// }
+/// writer.finish()?;
+/// ```
+pub struct LasReader {
+    las: LasFile,
+    source: PointSource,
+    chunk_position: usize,
+}
+
+impl LasReader {
+    /// Opens a LiDAR file and reads its header and VLRs.
+    pub fn new(file_name: &str) -> Result<LasReader, Error> {
+        let mut las = LasFile::new(file_name, "rh")?;
+        let lc_file_name = file_name.to_lowercase();
+        let source = if lc_file_name.ends_with(".laz") {
+            let reader = las::Reader::from_path(file_name)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+            let transforms = *reader.header().transforms();
+            PointSource::Laz { reader, transforms }
+        } else if lc_file_name.ends_with(".zlidar") {
+            let f = BufReader::new(File::open(file_name)?);
+            let mut bor = ByteOrderReader::new(f, Endianness::LittleEndian);
+            let offset = las.header.offset_to_points as usize;
+            if las.header.number_of_points == 0 || offset + 4 > bor.len() {
+                PointSource::InMemory { is_read: true }
+            } else {
+                bor.seek(offset);
+                let num_fields = bor.read_u8()?;
+                let compression_byte = bor.read_u8()?;
+                let compression_method = compression_byte & 0b0000_0111;
+                let level = (compression_byte & 0b1111_1000) >> 3;
+                las.compression = match compression_method {
+                    0 => ZlidarCompression::Deflate { level },
+                    1 => ZlidarCompression::Brotli { level },
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::Other,
+                            "Unsupported compression method.",
+                        ))
+                    }
+                };
+                let major_version = bor.read_u8()?;
+                let minor_version = bor.read_u8()?;
+                if major_version == 1 && minor_version == 1 {
+                    PointSource::Zlidar {
+                        bor,
+                        next_offset: offset + 4,
+                        num_fields,
+                        compression_method,
+                    }
+                } else {
+                    PointSource::InMemory { is_read: false }
+                }
+            }
+        } else if lc_file_name.ends_with(".zip") {
+            PointSource::InMemory { is_read: false }
+        } else {
+            let mut format = las::point::Format::new(las.header.point_format)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+            if las.header.point_record_length < format.len() {
+                // The optional intensity and user data fields are omitted, which only
+                // the in-memory reader supports.
+                PointSource::InMemory { is_read: false }
+            } else {
+                format.extra_bytes = las.header.point_record_length - format.len();
+                let mut reader = BufReader::new(File::open(file_name)?);
+                reader.seek(SeekFrom::Start(las.header.offset_to_points as u64))?;
+                PointSource::Las {
+                    reader,
+                    format,
+                    num_remaining: las.header.number_of_points,
+                }
+            }
+        };
+
+        Ok(LasReader {
+            las,
+            source,
+            chunk_position: 0,
+        })
+    }
+
+    /// Returns the header of the file.
+    pub fn header(&self) -> &LasHeader {
+        &self.las.header
+    }
+
+    /// Returns a `LasFile` holding the header and VLRs of the file, along with the points
+    /// of the current chunk. This is suitable for use with `LasFile::initialize_using_file`.
+    pub fn las_file(&self) -> &LasFile {
+        &self.las
+    }
+
+    /// Returns true if the point format of the file includes GPS time.
+    pub fn has_gps_time(&self) -> bool {
+        self.las.header.point_format != 0 && self.las.header.point_format != 2
+    }
+
+    /// Returns true if the point format of the file includes RGB colour.
+    pub fn has_rgb(&self) -> bool {
+        matches!(self.las.header.point_format, 2 | 3 | 5 | 7 | 8 | 10)
+    }
+
+    /// Returns the coordinates of a point, with the scale factors and offsets of the
+    /// header applied.
+    pub fn get_transformed_coords(&self, point_data: &PointData) -> Point3D {
+        let header = &self.las.header;
+        Point3D::new(
+            point_data.x as f64 * header.x_scale_factor + header.x_offset,
+            point_data.y as f64 * header.y_scale_factor + header.y_offset,
+            point_data.z as f64 * header.z_scale_factor + header.z_offset,
+        )
+    }
+
+    /// Reads the next point record, or returns `None` once all of the points have been read.
+    pub fn read_point(&mut self) -> Result<Option<LidarPointRecord>, Error> {
+        while self.chunk_position >= self.las.point_data.len() {
+            if !self.read_chunk()? {
+                return Ok(None);
+            }
+        }
+        let record = self.las.get_record(self.chunk_position);
+        self.chunk_position += 1;
+        Ok(Some(record))
+    }
+
+    /// Returns the extra bytes of the point record most recently returned by `read_point`,
+    /// which are empty if the file has none. The attributes that they hold are described
+    /// by the descriptors of `las_file()`.
+    pub fn get_extra_bytes(&self) -> &[u8] {
+        if self.chunk_position == 0 {
+            return &[];
+        }
+        self.las.get_extra_bytes(self.chunk_position - 1)
+    }
+
+    /// Returns an iterator over the remaining point records of the file.
+    pub fn points(&mut self) -> LasPoints<'_> {
+        LasPoints { reader: self }
+    }
+
+    /// Replaces the points held in memory with the next chunk of the file, returning false
+    /// if there are no more points to read.
+    fn read_chunk(&mut self) -> Result<bool, Error> {
+        self.las.point_data.clear();
+        self.las.gps_data.clear();
+        self.las.colour_data.clear();
+        self.las.waveform_data.clear();
+        self.las.extra_bytes.clear();
+        self.chunk_position = 0;
+        match &mut self.source {
+            PointSource::Las {
+                reader,
+                format,
+                num_remaining,
+            } => {
+                let n = (*num_remaining).min(CHUNK_SIZE as u64);
+                for _ in 0..n {
+                    let raw_point = las::raw::Point::read_from(&mut *reader, format)
+                        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+                    self.las.push_raw_point(raw_point);
+                }
+                *num_remaining -= n;
+            }
+            PointSource::Laz { reader, transforms } => {
+                for _ in 0..CHUNK_SIZE {
+                    match reader.read() {
+                        Some(point) => {
+                            let point = point
+                                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+                            self.las.push_laz_point(point, transforms)?;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            PointSource::Zlidar {
+                bor,
+                next_offset,
+                num_fields,
+                compression_method,
+            } => {
+                if *next_offset < bor.len() {
+                    let (_, block_bytes) = self.las.read_zlidar_block(
+                        bor,
+                        *next_offset,
+                        0,
+                        *num_fields,
+                        *compression_method,
+                    );
+                    *next_offset += block_bytes as usize;
+                }
+            }
+            PointSource::InMemory { is_read } => {
+                if !*is_read {
+                    let file_name = self.las.file_name.clone();
+                    self.las = LasFile::new(&file_name, "r")?;
+                    *is_read = true;
+                }
+            }
+        }
+        Ok(!self.las.point_data.is_empty())
+    }
+}
+
+/// An iterator over the point records of a `LasReader`.
+pub struct LasPoints<'a> {
+    reader: &'a mut LasReader,
+}
+
+impl<'a> Iterator for LasPoints<'a> {
+    type Item = Result<LidarPointRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.read_point().transpose()
+    }
+}
+
+enum PointSink {
+    Las { writer: BufWriter<File> },
+    Laz { writer: las::Writer<BufWriter<File>> },
+    Zlidar {
+        writer: BufWriter<File>,
+        current_offset: u64,
+    },
+    // Zipped LAS files are written in their entirety when the writer is finished.
+    InMemory,
+}
+
+/// Writes the point records of a LAS, LAZ, or zLidar file a chunk of 50,000 points at a
+/// time, so that the output points need not be held in memory. The writer is created from
+/// a `LasFile` holding the header and VLRs of the output, usually made with
+/// `LasFile::initialize_using_file`. The header and VLRs are written when the writer is
+/// created, and the point counts and extents are updated when it is finished. Zipped LAS
+/// files cannot be written in chunks and are instead held in memory until the writer is
+/// finished.
+pub struct LasWriter {
+    las: LasFile,
+    sink: PointSink,
+}
+
+impl LasWriter {
+    /// Creates the output file of `output` and writes its header and VLRs. Any points that
+    /// have already been added to `output` are written before those of `write_point`.
+    pub fn new(mut output: LasFile) -> Result<LasWriter, Error> {
+        if output.header.x_scale_factor == f64::NEG_INFINITY
+            || output.header.y_scale_factor == f64::NEG_INFINITY
+            || output.header.z_scale_factor == f64::NEG_INFINITY
+            || output.header.x_offset == f64::NEG_INFINITY
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The scale factors and offsets of the header must be set before points are written.",
+            ));
+        }
+        let file_name = output.file_name.clone();
+        let lc_file_name = file_name.to_lowercase();
+        output.sync_extra_bytes_vlr();
+        let sink = if lc_file_name.ends_with(".laz") {
+            PointSink::Laz {
+                writer: output.create_laz_writer()?,
+            }
+        } else if lc_file_name.ends_with(".zlidar") {
+            if output.compression == ZlidarCompression::None {
+                output.compression = ZlidarCompression::Brotli { level: 5u8 };
+            }
+            let mut writer = BufWriter::new(File::create(&file_name)?);
+            output.write_header(&mut writer)?;
+            output.write_zlidar_point_header(&mut writer)?;
+            PointSink::Zlidar {
+                writer,
+                current_offset: output.header.offset_to_points as u64 + 4, // the four bytes are for the zlidar header
+            }
+        } else if lc_file_name.ends_with(".zip") {
+            PointSink::InMemory
+        } else {
+            let mut writer = BufWriter::new(File::create(&file_name)?);
+            output.write_header(&mut writer)?;
+            PointSink::Las { writer }
+        };
+
+        Ok(LasWriter { las: output, sink })
+    }
+
+    /// Returns the header of the output file, which holds the number of points written so far.
+    pub fn header(&self) -> &LasHeader {
+        &self.las.header
+    }
+
+    /// Adds a point record to the output, writing the buffered points once a chunk is full.
+    pub fn write_point(&mut self, point: LidarPointRecord) -> Result<(), Error> {
+        self.las.add_point_record(point);
+        if self.las.point_data.len() >= CHUNK_SIZE && !matches!(self.sink, PointSink::InMemory) {
+            self.write_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Adds a point record along with its extra bytes, e.g. those returned by
+    /// `LasReader::get_extra_bytes` for a point of the input file.
+    pub fn write_point_with_extra_bytes(
+        &mut self,
+        point: LidarPointRecord,
+        extra_bytes: &[u8],
+    ) -> Result<(), Error> {
+        self.las.add_point_record_with_extra_bytes(point, extra_bytes);
+        if self.las.point_data.len() >= CHUNK_SIZE && !matches!(self.sink, PointSink::InMemory) {
+            self.write_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Writes any buffered points and updates the header of the output file.
+    pub fn finish(mut self) -> Result<(), Error> {
+        if let PointSink::InMemory = self.sink {
+            return self.las.write();
+        }
+        self.write_chunk()?;
+        if self.las.header.number_of_points < 2 {
+            println!("WARNING: There are fewer than two points in the LAS file. This may cause some tools to fail when reading these data.");
+        }
+        match &mut self.sink {
+            PointSink::Las { writer } | PointSink::Zlidar { writer, .. } => {
+                writer.seek(SeekFrom::Start(0))?;
+                self.las.write_header(writer)?;
+                writer.flush()?;
+            }
+            PointSink::Laz { writer } => {
+                writer
+                    .close()
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            }
+            PointSink::InMemory => {}
+        }
+        Ok(())
+    }
+
+    fn write_chunk(&mut self) -> Result<(), Error> {
+        let num_points = self.las.point_data.len();
+        if num_points == 0 {
+            return Ok(());
+        }
+        match &mut self.sink {
+            PointSink::Las { writer } => {
+                self.las.write_point_records(writer, num_points)?;
+            }
+            PointSink::Laz { writer } => {
+                self.las.write_laz_points(writer, num_points);
+            }
+            PointSink::Zlidar {
+                writer,
+                current_offset,
+            } => {
+                *current_offset =
+                    self.las
+                        .write_zlidar_blocks(writer, 0, num_points, *current_offset);
+            }
+            PointSink::InMemory => return Ok(()),
+        }
+        self.las.point_data.clear();
+        self.las.gps_data.clear();
+        self.las.colour_data.clear();
+        self.las.waveform_data.clear();
+        self.las.extra_bytes.clear();
+        Ok(())
+    }
+}
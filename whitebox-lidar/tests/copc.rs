@@ -0,0 +1,356 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the reading of Cloud Optimized Point Cloud (COPC) files with a small fixture, a
+//! 40 x 40 grid of points stored in an octree of three levels. The nodes of the upper-right
+//! quadrant of the octree are located by a child hierarchy page. The points read by
+//! `LasFile::read_bounds` are compared with the points of the grid within the bounds.
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use whitebox_common::structures::BoundingBox;
+use whitebox_lidar::LasFile;
+
+const GRID_SIZE: usize = 40;
+const SPACING: f64 = 2.5;
+const SCALE: f64 = 0.01;
+
+// The cube bounding the octree.
+const CENTER: f64 = 50.0;
+const HALFSIZE: f64 = 50.0;
+
+const POINT_FORMAT: u8 = 6;
+const POINT_RECORD_LENGTH: u16 = 30;
+const HEADER_SIZE: u16 = 375;
+const VLR_HEADER_SIZE: usize = 54;
+const COPC_INFO_SIZE: usize = 160;
+
+type Key = (i32, i32, i32, i32);
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_lidar_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+// The coordinates of the point in column `i` and row `j` of the grid.
+fn grid_point(i: usize, j: usize) -> (f64, f64, f64) {
+    (
+        SPACING / 2.0 + SPACING * i as f64,
+        SPACING / 2.0 + SPACING * j as f64,
+        10.0 + (i % 5) as f64,
+    )
+}
+
+// The octree node of a point. A quarter of the points are in the root node, and the rest
+// are in the nodes of the first level, except in the upper-right quadrant, where most are
+// in the nodes of the second level.
+fn node_of(i: usize, j: usize) -> Key {
+    let (x, y, _) = grid_point(i, j);
+    if (i + j).is_multiple_of(4) {
+        (0, 0, 0, 0)
+    } else if x > CENTER && y > CENTER && (i + j) % 4 != 1 {
+        (2, (x / 25.0) as i32, (y / 25.0) as i32, 0)
+    } else {
+        (1, (x / 50.0) as i32, (y / 50.0) as i32, 0)
+    }
+}
+
+fn point_record(x: f64, y: f64, z: f64, intensity: u16) -> Vec<u8> {
+    let mut record = Vec::with_capacity(POINT_RECORD_LENGTH as usize);
+    record.write_i32::<LittleEndian>((x / SCALE).round() as i32).unwrap();
+    record.write_i32::<LittleEndian>((y / SCALE).round() as i32).unwrap();
+    record.write_i32::<LittleEndian>((z / SCALE).round() as i32).unwrap();
+    record.write_u16::<LittleEndian>(intensity).unwrap();
+    record.write_u8(0x11).unwrap(); // return 1 of 1
+    record.write_u8(0).unwrap(); // classification flags
+    record.write_u8(2).unwrap(); // classification
+    record.write_u8(0).unwrap(); // user data
+    record.write_i16::<LittleEndian>(0).unwrap(); // scan angle
+    record.write_u16::<LittleEndian>(1).unwrap(); // point source ID
+    record.write_f64::<LittleEndian>(intensity as f64).unwrap(); // GPS time
+    record
+}
+
+fn write_padded(out: &mut Vec<u8>, s: &str, len: usize) {
+    let mut bytes = s.as_bytes().to_vec();
+    bytes.resize(len, 0);
+    out.extend(bytes);
+}
+
+fn write_vlr_header(out: &mut Vec<u8>, user_id: &str, record_id: u16, length: usize) {
+    out.write_u16::<LittleEndian>(0).unwrap();
+    write_padded(out, user_id, 16);
+    out.write_u16::<LittleEndian>(record_id).unwrap();
+    out.write_u16::<LittleEndian>(length as u16).unwrap();
+    write_padded(out, "", 32);
+}
+
+fn write_header(out: &mut Vec<u8>, offset_to_points: u32, num_points: u64, evlr_offset: u64) {
+    let max = SPACING * GRID_SIZE as f64;
+    out.extend(b"LASF");
+    out.write_u16::<LittleEndian>(0).unwrap(); // file source ID
+    out.write_u16::<LittleEndian>(0x10).unwrap(); // global encoding, WKT
+    out.extend([0u8; 16]); // project ID
+    out.extend([1u8, 4u8]); // version
+    write_padded(out, "", 32);
+    write_padded(out, "WhiteboxTools", 32);
+    out.write_u16::<LittleEndian>(1).unwrap();
+    out.write_u16::<LittleEndian>(2026).unwrap();
+    out.write_u16::<LittleEndian>(HEADER_SIZE).unwrap();
+    out.write_u32::<LittleEndian>(offset_to_points).unwrap();
+    out.write_u32::<LittleEndian>(2).unwrap(); // number of VLRs
+    out.write_u8(POINT_FORMAT | 0x80).unwrap(); // compressed
+    out.write_u16::<LittleEndian>(POINT_RECORD_LENGTH).unwrap();
+    out.extend([0u8; 24]); // legacy point counts
+    for v in [SCALE, SCALE, SCALE, 0.0, 0.0, 0.0] {
+        out.write_f64::<LittleEndian>(v).unwrap();
+    }
+    for v in [max, 0.0, max, 0.0, 15.0, 10.0] {
+        out.write_f64::<LittleEndian>(v).unwrap();
+    }
+    out.write_u64::<LittleEndian>(0).unwrap(); // start of waveform data
+    out.write_u64::<LittleEndian>(evlr_offset).unwrap();
+    out.write_u32::<LittleEndian>(1).unwrap(); // number of EVLRs
+    out.write_u64::<LittleEndian>(num_points).unwrap();
+    out.write_u64::<LittleEndian>(num_points).unwrap();
+    out.extend([0u8; 112]); // points by return
+    assert_eq!(out.len(), HEADER_SIZE as usize);
+}
+
+fn write_page_entry(page: &mut Vec<u8>, key: Key, offset: u64, byte_size: i32, point_count: i32) {
+    for v in [key.0, key.1, key.2, key.3] {
+        page.write_i32::<LittleEndian>(v).unwrap();
+    }
+    page.write_u64::<LittleEndian>(offset).unwrap();
+    page.write_i32::<LittleEndian>(byte_size).unwrap();
+    page.write_i32::<LittleEndian>(point_count).unwrap();
+}
+
+// Writes the COPC fixture, with each node of the octree compressed as one LAZ chunk.
+fn write_fixture(file_name: &str) {
+    let mut nodes: BTreeMap<Key, Vec<Vec<u8>>> = BTreeMap::new();
+    for i in 0..GRID_SIZE {
+        for j in 0..GRID_SIZE {
+            let (x, y, z) = grid_point(i, j);
+            let record = point_record(x, y, z, (i * GRID_SIZE + j) as u16);
+            nodes.entry(node_of(i, j)).or_default().push(record);
+        }
+    }
+    let num_points = (GRID_SIZE * GRID_SIZE) as u64;
+
+    let items = laz::LazItemRecordBuilder::default_for_point_format_id(POINT_FORMAT, 0).unwrap();
+    let laz_vlr = laz::LazVlrBuilder::new(items)
+        .with_variable_chunk_size()
+        .build();
+    let mut laz_vlr_data = vec![];
+    laz_vlr.write_to(&mut laz_vlr_data).unwrap();
+    let offset_to_points =
+        HEADER_SIZE as usize + 2 * VLR_HEADER_SIZE + COPC_INFO_SIZE + laz_vlr_data.len();
+
+    // The points, with the COPC info and hierarchy written once their offsets are known.
+    let mut out = Cursor::new(vec![0u8; offset_to_points]);
+    out.seek(SeekFrom::End(0)).unwrap();
+    let mut compressor = laz::LasZipCompressor::new(out, laz_vlr.clone()).unwrap();
+    compressor.reserve_offset_to_chunk_table().unwrap();
+    let mut chunks = vec![];
+    let mut chunk_start = offset_to_points as u64 + 8;
+    for (n, (key, records)) in nodes.iter().enumerate() {
+        for record in records {
+            compressor.compress_one(record).unwrap();
+        }
+        let chunk_end = if n + 1 < nodes.len() {
+            compressor.finish_current_chunk().unwrap();
+            compressor.get_mut().stream_position().unwrap()
+        } else {
+            // the last chunk ends at the chunk table
+            compressor.done().unwrap();
+            let start = offset_to_points;
+            u64::from_le_bytes(compressor.get().get_ref()[start..start + 8].try_into().unwrap())
+        };
+        chunks.push((*key, chunk_start, chunk_end - chunk_start, records.len()));
+        chunk_start = chunk_end;
+    }
+    let mut bytes = compressor.into_inner().into_inner();
+
+    // The hierarchy EVLR, whose first page refers to a child page for the upper-right
+    // quadrant, and which includes a node without points.
+    let evlr_offset = bytes.len() as u64;
+    let mut root_page = vec![];
+    let mut child_page = vec![];
+    for &(key, offset, size, count) in &chunks {
+        let page = if key == (1, 1, 1, 0) || key.0 == 2 {
+            &mut child_page
+        } else {
+            &mut root_page
+        };
+        write_page_entry(page, key, offset, size as i32, count as i32);
+    }
+    write_page_entry(&mut root_page, (1, 0, 0, 1), 0, 0, 0);
+    let root_offset = evlr_offset + 60;
+    let child_offset = root_offset + root_page.len() as u64 + 32;
+    write_page_entry(
+        &mut root_page,
+        (1, 1, 1, 0),
+        child_offset,
+        child_page.len() as i32,
+        -1,
+    );
+    bytes.write_u16::<LittleEndian>(0).unwrap();
+    write_padded(&mut bytes, "copc", 16);
+    bytes.write_u16::<LittleEndian>(1000).unwrap();
+    bytes
+        .write_u64::<LittleEndian>((root_page.len() + child_page.len()) as u64)
+        .unwrap();
+    write_padded(&mut bytes, "EPT hierarchy", 32);
+    bytes.extend(&root_page);
+    bytes.extend(&child_page);
+
+    let mut head = vec![];
+    write_header(&mut head, offset_to_points as u32, num_points, evlr_offset);
+    write_vlr_header(&mut head, "copc", 1, COPC_INFO_SIZE);
+    for v in [CENTER, CENTER, CENTER, HALFSIZE, SPACING] {
+        head.write_f64::<LittleEndian>(v).unwrap();
+    }
+    head.write_u64::<LittleEndian>(root_offset).unwrap();
+    head.write_u64::<LittleEndian>(root_page.len() as u64).unwrap();
+    head.write_f64::<LittleEndian>(0.0).unwrap();
+    head.write_f64::<LittleEndian>(num_points as f64).unwrap();
+    head.extend([0u8; 88]);
+    write_vlr_header(&mut head, "laszip encoded", 22204, laz_vlr_data.len());
+    head.extend(&laz_vlr_data);
+    assert_eq!(head.len(), offset_to_points);
+    bytes[..offset_to_points].copy_from_slice(&head);
+
+    let mut f = fs::File::create(file_name).expect("Error creating the fixture.");
+    f.write_all(&bytes).expect("Error writing the fixture.");
+}
+
+// The coordinates of the points of the grid within the bounds and no deeper than
+// `max_depth`, in units of the scale factor.
+fn expected_points(bounds: BoundingBox, max_depth: Option<i32>) -> Vec<(i32, i32, i32)> {
+    let mut points = vec![];
+    for i in 0..GRID_SIZE {
+        for j in 0..GRID_SIZE {
+            let (x, y, z) = grid_point(i, j);
+            if x < bounds.min_x || x > bounds.max_x || y < bounds.min_y || y > bounds.max_y {
+                continue;
+            }
+            if let Some(depth) = max_depth {
+                if node_of(i, j).0 > depth {
+                    continue;
+                }
+            }
+            points.push((
+                (x / SCALE).round() as i32,
+                (y / SCALE).round() as i32,
+                (z / SCALE).round() as i32,
+            ));
+        }
+    }
+    points.sort();
+    points
+}
+
+fn read_points(lf: &LasFile) -> Vec<(i32, i32, i32)> {
+    let mut points: Vec<(i32, i32, i32)> = (0..lf.header.number_of_points as usize)
+        .map(|i| {
+            let p = lf.get_point_info(i);
+            (p.x, p.y, p.z)
+        })
+        .collect();
+    points.sort();
+    points
+}
+
+#[test]
+fn copc_fixture_is_a_valid_laz_file() {
+    let file_name = temp_file("fixture_full.copc.laz");
+    write_fixture(&file_name);
+    let lf = LasFile::new(&file_name, "r").expect("Error reading the COPC file.");
+    assert_eq!(lf.header.number_of_points, (GRID_SIZE * GRID_SIZE) as u64);
+    let everything = BoundingBox::new(0.0, 100.0, 0.0, 100.0);
+    assert_eq!(read_points(&lf), expected_points(everything, None));
+}
+
+#[test]
+fn copc_info_is_read() {
+    let file_name = temp_file("fixture_info.copc.laz");
+    write_fixture(&file_name);
+    let lf = LasFile::new(&file_name, "rh").expect("Error reading the COPC file.");
+    let info = lf.copc_info().expect("The COPC info VLR was not found.");
+    assert_eq!(
+        (info.center_x, info.center_y, info.center_z),
+        (CENTER, CENTER, CENTER)
+    );
+    assert_eq!(info.halfsize, HALFSIZE);
+    assert_eq!(info.spacing, SPACING);
+    // the root node, three nodes of the first level, an empty node and the child page
+    assert_eq!(info.root_hier_size, 6 * 32);
+}
+
+#[test]
+fn read_bounds_returns_the_points_within_the_bounds() {
+    let file_name = temp_file("fixture_bounds.copc.laz");
+    write_fixture(&file_name);
+    let queries = [
+        (BoundingBox::new(0.0, 100.0, 0.0, 100.0), None),
+        // crossing all four quadrants
+        (BoundingBox::new(30.0, 70.0, 20.0, 45.0), None),
+        (BoundingBox::new(30.0, 70.0, 20.0, 75.0), None),
+        // within the upper-right quadrant, whose nodes are in the child page
+        (BoundingBox::new(80.0, 90.0, 55.0, 95.0), None),
+        // thinned to the upper levels of the octree
+        (BoundingBox::new(0.0, 100.0, 0.0, 100.0), Some(0)),
+        (BoundingBox::new(0.0, 100.0, 0.0, 100.0), Some(1)),
+        (BoundingBox::new(60.0, 100.0, 60.0, 100.0), Some(1)),
+        // outside of the points
+        (BoundingBox::new(200.0, 300.0, 200.0, 300.0), None),
+    ];
+    let mut lf = LasFile::new(&file_name, "rh").expect("Error reading the COPC file.");
+    for (bounds, max_depth) in queries {
+        lf.read_bounds(bounds, max_depth)
+            .expect("Error reading the bounds.");
+        let expected = expected_points(bounds, max_depth.map(|d| d as i32));
+        assert_eq!(
+            lf.header.number_of_points as usize,
+            expected.len(),
+            "{:?} {:?}",
+            bounds,
+            max_depth
+        );
+        assert_eq!(read_points(&lf), expected, "{:?} {:?}", bounds, max_depth);
+    }
+    assert_eq!(
+        expected_points(BoundingBox::new(80.0, 90.0, 55.0, 95.0), None).len(),
+        4 * 16
+    );
+}
+
+#[test]
+fn read_bounds_rejects_files_that_are_not_copc() {
+    let source = temp_file("fixture_source.copc.laz");
+    write_fixture(&source);
+    let lf = LasFile::new(&source, "r").expect("Error reading the COPC file.");
+    // outputs created from a COPC file are not COPC files
+    let file_name = temp_file("not_copc.las");
+    let mut output = LasFile::initialize_using_file(&file_name, &lf);
+    for i in 0..lf.header.number_of_points as usize {
+        output.add_point_record(lf.get_record(i));
+    }
+    output.write().expect("Error writing the LAS file.");
+    let mut lf = LasFile::new(&file_name, "rh").unwrap();
+    assert!(lf.copc_info().is_none());
+    assert!(lf
+        .read_bounds(BoundingBox::new(0.0, 100.0, 0.0, 100.0), None)
+        .is_err());
+}
@@ -0,0 +1,254 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that the descriptors of the Extra Bytes VLR, and the extra bytes of each point,
+//! are preserved when LAS and LAZ files are written and read, by `LasFile` and by the
+//! streaming `LasReader` and `LasWriter`, and that they are dropped from zLidar files.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_lidar::{
+    ExtraByteDataType, ExtraByteDescriptor, LasFile, LasHeader, LasReader, LasWriter,
+    LidarPointRecord, PointData,
+};
+
+const NUM_POINTS: usize = 1_234;
+
+const POINT_FORMATS: [u8; 2] = [1, 6];
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_lidar_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+// Attributes of several data types, with and without a no-data value, scale, and offset.
+fn descriptors() -> Vec<ExtraByteDescriptor> {
+    let mut height = ExtraByteDescriptor::new("height", ExtraByteDataType::I16, "Height above ground");
+    height.set_scale_and_offset(0.01, 100.0);
+    height.set_range(-50.0, 300.0);
+    let mut count = ExtraByteDescriptor::new("count", ExtraByteDataType::U8, "");
+    count.set_no_data(255.0);
+    let normal = ExtraByteDescriptor::new("normal z", ExtraByteDataType::F64, "Normal vector z");
+    let id = ExtraByteDescriptor::new("id", ExtraByteDataType::U32, "");
+    let mut density = ExtraByteDescriptor::new("density", ExtraByteDataType::F32, "");
+    density.set_no_data(-9999.0);
+    vec![height, count, normal, id, density]
+}
+
+// The expected value of each attribute of a point.
+fn attribute_value(name: &str, i: usize) -> f64 {
+    match name {
+        "height" => (i % 3001) as f64 * 0.1 - 50.0,
+        "count" => (i % 256) as f64,
+        "normal z" => (i as f64 * 0.37).sin(),
+        "id" => i as f64 * 3_000_001.0,
+        _ => (i % 100) as f64 * 0.25,
+    }
+}
+
+fn test_point(point_format: u8, i: usize) -> LidarPointRecord {
+    let mut point_data = PointData {
+        x: i as i32 * 10,
+        y: (i % 97) as i32 * 10,
+        z: (i % 50) as i32,
+        intensity: i as u16,
+        is_64bit: point_format >= 6,
+        ..Default::default()
+    };
+    point_data.set_return_number(1);
+    point_data.set_number_of_returns(1);
+    let gps_data = i as f64;
+    match point_format {
+        1 => LidarPointRecord::PointRecord1 {
+            point_data,
+            gps_data,
+        },
+        _ => LidarPointRecord::PointRecord6 {
+            point_data,
+            gps_data,
+        },
+    }
+}
+
+// Writes a file of the test points, along with an Extra Bytes VLR and the values of its
+// attributes, with `LasFile::write`.
+fn write_input(file_name: &str, point_format: u8) {
+    let mut output = LasFile::new(file_name, "w").expect("Error creating the file.");
+    output.add_header(LasHeader {
+        point_format,
+        x_scale_factor: 0.01,
+        y_scale_factor: 0.01,
+        z_scale_factor: 0.01,
+        x_offset: 0.0,
+        y_offset: 0.0,
+        z_offset: 0.0,
+        project_id_used: true,
+        ..Default::default()
+    });
+    for i in 0..NUM_POINTS / 2 {
+        output.add_point_record(test_point(point_format, i));
+    }
+    // Attributes added after some of the points are set to their no-data value, or zero,
+    // for those points.
+    for descriptor in descriptors() {
+        output.add_extra_attribute(descriptor).expect("Error adding an attribute.");
+    }
+    for i in 0..NUM_POINTS / 2 {
+        let count = output.get_extra_attribute(i, "count");
+        assert_eq!(count, Some(255.0));
+        assert_eq!(output.get_extra_attribute(i, "density"), Some(-9999.0));
+        assert_eq!(output.get_extra_attribute(i, "id"), Some(0.0));
+    }
+    for i in NUM_POINTS / 2..NUM_POINTS {
+        output.add_point_record(test_point(point_format, i));
+    }
+    for i in 0..NUM_POINTS {
+        for descriptor in descriptors() {
+            output
+                .set_extra_attribute(i, &descriptor.name, attribute_value(&descriptor.name, i))
+                .expect("Error setting an attribute.");
+        }
+    }
+    output.write().expect("Error writing the file.");
+}
+
+// Checks the descriptors, and the attribute values of each point, of a file written with
+// the test points.
+fn check_attributes(las: &LasFile, name: &str, step: usize) {
+    assert!(las.has_extra_bytes(), "{}", name);
+    assert_eq!(las.get_extra_byte_descriptors(), &descriptors()[..], "{}", name);
+    let extra_bytes_vlrs = las
+        .vlr_data
+        .iter()
+        .filter(|vlr| ExtraByteDescriptor::is_extra_bytes_vlr(vlr))
+        .count();
+    assert_eq!(extra_bytes_vlrs, 1, "{}", name);
+    // 2 + 1 + 8 + 4 + 4 extra bytes
+    let standard_length = if las.header.point_format == 6 { 30 } else { 28 };
+    assert_eq!(las.header.point_record_length, standard_length + 19, "{}", name);
+    for i in 0..las.header.number_of_points as usize {
+        let n = i * step;
+        assert_eq!(las.get_record(i).get_point_data().x, n as i32 * 10, "{}", name);
+        for descriptor in descriptors() {
+            let value = las.get_extra_attribute(i, &descriptor.name).unwrap();
+            let expected = attribute_value(&descriptor.name, n);
+            let tolerance = match descriptor.data_type {
+                ExtraByteDataType::F32 => 1e-6,
+                ExtraByteDataType::F64 => 0.0,
+                _ => 1e-9,
+            };
+            assert!(
+                (value - expected).abs() <= tolerance,
+                "{} point {} {}: {} != {}",
+                name,
+                i,
+                descriptor.name,
+                value,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn extra_bytes_survive_las_file() {
+    for point_format in POINT_FORMATS {
+        for extension in ["las", "laz"] {
+            let name = format!("extra_bytes_{}.{}", point_format, extension);
+            let file_name = temp_file(&name);
+            write_input(&file_name, point_format);
+            let input = LasFile::new(&file_name, "r").expect("Error reading the file.");
+            assert_eq!(input.header.number_of_points as usize, NUM_POINTS, "{}", name);
+            check_attributes(&input, &name, 1);
+
+            // The attributes of a copy of the file are preserved when the points are added
+            // along with their extra bytes.
+            let copy_name = format!("extra_bytes_copy_{}.{}", point_format, extension);
+            let copy_file_name = temp_file(&copy_name);
+            let mut output = LasFile::initialize_using_file(&copy_file_name, &input);
+            for i in 0..NUM_POINTS {
+                output.add_point_record_with_extra_bytes(input.get_record(i), input.get_extra_bytes(i));
+            }
+            output.write().expect("Error writing the copy.");
+            let copy = LasFile::new(&copy_file_name, "r").expect("Error reading the copy.");
+            check_attributes(&copy, &copy_name, 1);
+            for i in 0..NUM_POINTS {
+                assert_eq!(copy.get_extra_bytes(i), input.get_extra_bytes(i), "{}", copy_name);
+            }
+        }
+    }
+}
+
+#[test]
+fn extra_bytes_survive_streaming() {
+    for point_format in POINT_FORMATS {
+        for extension in ["las", "laz"] {
+            let input_name = temp_file(&format!("extra_bytes_input_{}.{}", point_format, extension));
+            write_input(&input_name, point_format);
+            let input = LasFile::new(&input_name, "r").expect("Error reading the file.");
+
+            let mut reader = LasReader::new(&input_name).expect("Error opening the file.");
+            assert_eq!(reader.get_extra_bytes(), &[] as &[u8]);
+            assert_eq!(
+                reader.las_file().get_extra_byte_descriptors(),
+                &descriptors()[..]
+            );
+
+            // Every other point is copied by streaming, along with its extra bytes.
+            let name = format!("extra_bytes_streamed_{}.{}", point_format, extension);
+            let output_name = temp_file(&name);
+            let output = LasFile::initialize_using_file(&output_name, reader.las_file());
+            let mut writer = LasWriter::new(output).expect("Error creating the writer.");
+            let mut i = 0;
+            while let Some(point) = reader.read_point().expect("Error reading a point.") {
+                assert_eq!(reader.get_extra_bytes(), input.get_extra_bytes(i), "{} point {}", name, i);
+                if i % 2 == 0 {
+                    let extra_bytes = reader.get_extra_bytes().to_vec();
+                    writer
+                        .write_point_with_extra_bytes(point, &extra_bytes)
+                        .expect("Error writing a point.");
+                }
+                i += 1;
+            }
+            assert_eq!(i, NUM_POINTS, "{}", name);
+            writer.finish().expect("Error finishing the file.");
+
+            let output = LasFile::new(&output_name, "r").expect("Error reading the output.");
+            assert_eq!(output.header.number_of_points as usize, NUM_POINTS.div_ceil(2), "{}", name);
+            check_attributes(&output, &name, 2);
+        }
+    }
+}
+
+#[test]
+fn extra_bytes_are_dropped_from_zlidar() {
+    let input_name = temp_file("extra_bytes_zlidar_input.las");
+    write_input(&input_name, 1);
+    let input = LasFile::new(&input_name, "r").expect("Error reading the file.");
+    let output_name = temp_file("extra_bytes.zlidar");
+    let mut output = LasFile::initialize_using_file(&output_name, &input);
+    for i in 0..NUM_POINTS {
+        output.add_point_record_with_extra_bytes(input.get_record(i), input.get_extra_bytes(i));
+    }
+    output.write().expect("Error writing the file.");
+
+    let output = LasFile::new(&output_name, "r").expect("Error reading the output.");
+    assert!(!output.has_extra_bytes());
+    assert!(output.get_extra_byte_descriptors().is_empty());
+    assert!(!output
+        .vlr_data
+        .iter()
+        .any(ExtraByteDescriptor::is_extra_bytes_vlr));
+    assert_eq!(output.header.number_of_points as usize, NUM_POINTS);
+    for i in 0..NUM_POINTS {
+        assert_eq!(output.get_record(i).get_point_data().x, i as i32 * 10);
+        assert!(output.get_extra_bytes(i).is_empty());
+    }
+}
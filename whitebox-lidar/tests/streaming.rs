@@ -0,0 +1,197 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that `LasReader` and `LasWriter`, which hold a chunk of points in memory at a
+//! time, read and write the same points as `LasFile::new` and `LasFile::write`, for LAS,
+//! LAZ, and zLidar files with more points than fit in a single chunk.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_lidar::{
+    ColourData, LasFile, LasHeader, LasReader, LasWriter, LidarPointRecord, PointData,
+};
+
+// More points than fit in two chunks of 50,000 points.
+const NUM_POINTS: usize = 100_123;
+
+const POINT_FORMATS: [u8; 3] = [1, 3, 6];
+const EXTENSIONS: [&str; 3] = ["las", "laz", "zlidar"];
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_lidar_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+fn test_point(point_format: u8, i: usize) -> LidarPointRecord {
+    let mut point_data = PointData {
+        x: (i % 1000) as i32 * 10,
+        y: (i / 1000) as i32 * 10,
+        z: (i % 777) as i32,
+        intensity: (i % 65_536) as u16,
+        scan_angle: (i % 60) as i16 - 30,
+        user_data: (i % 256) as u8,
+        point_source_id: (i % 100) as u16,
+        is_64bit: point_format >= 6,
+        ..Default::default()
+    };
+    point_data.set_return_number(1 + (i % 2) as u8);
+    point_data.set_number_of_returns(2);
+    point_data.set_classification((i % 10) as u8);
+    point_data.set_synthetic(i % 7 == 3);
+    let gps_data = i as f64 * 0.001;
+    let colour_data = ColourData {
+        red: (i % 65_536) as u16,
+        green: ((i * 3) % 65_536) as u16,
+        blue: ((i * 7) % 65_536) as u16,
+        nir: 0,
+    };
+    match point_format {
+        1 => LidarPointRecord::PointRecord1 {
+            point_data,
+            gps_data,
+        },
+        3 => LidarPointRecord::PointRecord3 {
+            point_data,
+            gps_data,
+            colour_data,
+        },
+        _ => LidarPointRecord::PointRecord6 {
+            point_data,
+            gps_data,
+        },
+    }
+}
+
+// The values of the fields of a point record, which differ only if the points differ.
+fn fields(record: &LidarPointRecord) -> String {
+    let p = record.get_point_data();
+    format!(
+        "{} {} {} {} ret={}/{} class={} synth={} angle={} user={} src={} gps={:?} rgb={:?}",
+        p.x,
+        p.y,
+        p.z,
+        p.intensity,
+        p.return_number(),
+        p.number_of_returns(),
+        p.classification(),
+        p.synthetic(),
+        p.scan_angle,
+        p.user_data,
+        p.point_source_id,
+        record.get_gps_time(),
+        record
+            .get_rgb()
+            .map(|c| (c.red, c.green, c.blue))
+    )
+}
+
+// Writes a file of the test points with `LasFile::write`.
+fn write_input(file_name: &str, point_format: u8) {
+    let mut output = LasFile::new(file_name, "w").expect("Error creating the file.");
+    let header = LasHeader {
+        point_format,
+        x_scale_factor: 0.01,
+        y_scale_factor: 0.01,
+        z_scale_factor: 0.01,
+        x_offset: 0.0,
+        y_offset: 0.0,
+        z_offset: 0.0,
+        project_id_used: true,
+        ..Default::default()
+    };
+    output.add_header(header);
+    for i in 0..NUM_POINTS {
+        output.add_point_record(test_point(point_format, i));
+    }
+    output.write().expect("Error writing the file.");
+}
+
+#[test]
+fn las_reader_matches_las_file() {
+    for point_format in POINT_FORMATS {
+        for extension in EXTENSIONS {
+            let name = format!("streaming_input_{}.{}", point_format, extension);
+            let file_name = temp_file(&name);
+            write_input(&file_name, point_format);
+
+            let input = LasFile::new(&file_name, "r").expect("Error reading the file.");
+            assert_eq!(input.header.number_of_points as usize, NUM_POINTS, "{}", name);
+            let mut reader = LasReader::new(&file_name).expect("Error opening the file.");
+            assert_eq!(reader.header().number_of_points as usize, NUM_POINTS, "{}", name);
+            let mut n = 0;
+            for point in reader.points() {
+                let point = point.expect("Error reading a point.");
+                assert_eq!(fields(&point), fields(&input.get_record(n)), "{} point {}", name, n);
+                assert_eq!(
+                    fields(&point),
+                    fields(&test_point(point_format, n)),
+                    "{} point {}",
+                    name,
+                    n
+                );
+                n += 1;
+            }
+            assert_eq!(n, NUM_POINTS, "{}", name);
+            assert!(reader.read_point().unwrap().is_none());
+        }
+    }
+}
+
+#[test]
+fn las_writer_matches_las_file() {
+    for point_format in POINT_FORMATS {
+        for extension in EXTENSIONS {
+            let input_name = temp_file(&format!("streaming_copy_{}.{}", point_format, extension));
+            write_input(&input_name, point_format);
+            let input = LasFile::new(&input_name, "r").expect("Error reading the file.");
+
+            // The points are copied by streaming, with every other point skipped.
+            let name = format!("streaming_output_{}.{}", point_format, extension);
+            let output_name = temp_file(&name);
+            let mut reader = LasReader::new(&input_name).expect("Error opening the file.");
+            let output = LasFile::initialize_using_file(&output_name, reader.las_file());
+            let mut writer = LasWriter::new(output).expect("Error creating the writer.");
+            for (i, point) in reader.points().enumerate() {
+                if i % 2 == 0 {
+                    writer.write_point(point.unwrap()).unwrap();
+                }
+            }
+            writer.finish().expect("Error finishing the file.");
+
+            let output = LasFile::new(&output_name, "r").expect("Error reading the output.");
+            let expected = NUM_POINTS.div_ceil(2);
+            assert_eq!(output.header.number_of_points as usize, expected, "{}", name);
+            // LAS and zLidar outputs of point formats 6-10 are written as point formats 0-5
+            assert_eq!(output.header.point_format, input.header.point_format, "{}", name);
+            for i in 0..expected {
+                assert_eq!(
+                    fields(&output.get_record(i)),
+                    fields(&input.get_record(2 * i)),
+                    "{} point {}",
+                    name,
+                    i
+                );
+            }
+
+            // The header is updated with the extent of the points that were written.
+            let mut min_y = f64::INFINITY;
+            let mut max_y = f64::NEG_INFINITY;
+            for i in (0..NUM_POINTS).step_by(2) {
+                let p = input.get_transformed_coords(i);
+                min_y = min_y.min(p.y);
+                max_y = max_y.max(p.y);
+            }
+            assert_eq!(output.header.min_x, 0.0, "{}", name);
+            assert_eq!(output.header.max_x, 99.8, "{}", name);
+            assert!((output.header.min_y - min_y).abs() < 1e-9, "{}", name);
+            assert!((output.header.max_y - max_y).abs() < 1e-9, "{}", name);
+        }
+    }
+}
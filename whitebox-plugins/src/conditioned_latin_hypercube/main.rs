@@ -306,7 +306,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         output_file = format!("{}{}", wd, output_file);
     }
 
-    if !output_file.ends_with(".shp") {
+    if get_vector_format_from_file(&output_file) == VectorFormat::Unknown {
         output_file.push_str(".shp");
     }
 
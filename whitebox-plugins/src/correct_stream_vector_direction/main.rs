@@ -21,6 +21,7 @@ use whitebox_common::utils::{
     wrapped_print
 };
 use whitebox_vector::{
+    add_suffix_to_vector_file_name,
     // AttributeField, 
     // FieldData, 
     // FieldDataType, 
@@ -220,10 +221,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     }
 
     if output_file.is_empty() {
-        output_file = input_file
-            .clone()
-            .replace(".shp", "_corrected.shp")
-            .replace(".SHP", "_corrected.shp");
+        output_file = add_suffix_to_vector_file_name(&input_file, "_corrected");
     }
     if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
         output_file = format!("{}{}", working_directory, output_file);
@@ -378,7 +376,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     println!("num. reversed arcs: {num_reversed}");
 
     // create output file
-    let mut output = Shapefile::initialize_using_file(&output_file.replace(".shp", "_reversed_arcs.shp"), &input, ShapeType::PolyLine, true).expect("Error creating output file");
+    let mut output = Shapefile::initialize_using_file(&add_suffix_to_vector_file_name(&output_file, "_reversed_arcs"), &input, ShapeType::PolyLine, true).expect("Error creating output file");
 
     let mut sfg: ShapefileGeometry;
     for fid in 0..polylines.len() {
@@ -263,6 +263,9 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                     FieldData::Int(val) => {
                         _ = context.set_value(att_fields[i].name.clone().into(), (*val as i64).into());
                     }
+                    FieldData::Int64(val) => {
+                        _ = context.set_value(att_fields[i].name.clone().into(), (*val).into());
+                    }
                     FieldData::Real(val) => {
                         _ = context.set_value(att_fields[i].name.clone().into(), (*val).into());
                     },
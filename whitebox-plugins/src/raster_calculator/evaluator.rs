@@ -0,0 +1,344 @@
+/*
+Authors:  Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use fasteval::compiler::IC;
+use fasteval::{Instruction, InstructionI, Slab};
+use std::collections::{BTreeMap, HashMap};
+use std::f64;
+use std::sync::Arc;
+use whitebox_common::plugins::{Function, InvocationError};
+
+/// Evaluates a statement that has been parsed and compiled by fasteval, for one grid cell.
+///
+/// NoData is represented by NaN. It propagates through arithmetic, comparisons, and
+/// function calls, so that a cell is NoData whenever its value depends on a NoData value.
+/// The exceptions are `isnodata(x)`, `x == nodata` and `x != nodata`, which test for NoData,
+/// and `if(cond, a, b)`, which only evaluates the branch that is selected by `cond`.
+pub struct Evaluator<'a> {
+    slab: &'a Slab,
+    functions: &'a HashMap<String, Arc<dyn Function>>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(slab: &'a Slab, functions: &'a HashMap<String, Arc<dyn Function>>) -> Evaluator<'a> {
+        Evaluator { slab, functions }
+    }
+
+    pub fn eval(&self, instruction: &Instruction, vars: &BTreeMap<String, f64>) -> Result<f64, String> {
+        use fasteval::Instruction::*;
+        let value = match instruction {
+            IConst(c) => *c,
+            INeg(i) => -self.eval_i(*i, vars)?,
+            IInv(i) => 1.0 / self.eval_i(*i, vars)?,
+            INot(i) => {
+                let v = self.eval_i(*i, vars)?;
+                if v.is_nan() { f64::NAN } else { bool_to_f64(approx_eq(v, 0.0)) }
+            }
+
+            IAdd(l, r) => self.eval_i(*l, vars)? + self.eval_ic(r, vars)?,
+            IMul(l, r) => self.eval_i(*l, vars)? * self.eval_ic(r, vars)?,
+            IMod { dividend, divisor } => self.eval_ic(dividend, vars)? % self.eval_ic(divisor, vars)?,
+            IExp { base, power } => self.eval_ic(base, vars)?.powf(self.eval_ic(power, vars)?),
+
+            // NoData is equal to NoData, so that 'x == nodata' tests for NoData.
+            IEQ(l, r) => {
+                let (l, r) = (self.eval_ic(l, vars)?, self.eval_ic(r, vars)?);
+                if l.is_nan() || r.is_nan() {
+                    bool_to_f64(l.is_nan() && r.is_nan())
+                } else {
+                    bool_to_f64(approx_eq(l, r))
+                }
+            }
+            INE(l, r) => {
+                let (l, r) = (self.eval_ic(l, vars)?, self.eval_ic(r, vars)?);
+                if l.is_nan() || r.is_nan() {
+                    bool_to_f64(l.is_nan() != r.is_nan())
+                } else {
+                    bool_to_f64(!approx_eq(l, r))
+                }
+            }
+            ILT(l, r) => compare(self.eval_ic(l, vars)?, self.eval_ic(r, vars)?, |l, r| l < r),
+            ILTE(l, r) => compare(self.eval_ic(l, vars)?, self.eval_ic(r, vars)?, |l, r| l <= r),
+            IGTE(l, r) => compare(self.eval_ic(l, vars)?, self.eval_ic(r, vars)?, |l, r| l >= r),
+            IGT(l, r) => compare(self.eval_ic(l, vars)?, self.eval_ic(r, vars)?, |l, r| l > r),
+
+            // The logical operators short-circuit, as they do in fasteval.
+            IAND(l, r) => {
+                let left = self.eval_i(*l, vars)?;
+                if left.is_nan() || approx_eq(left, 0.0) {
+                    left
+                } else {
+                    self.eval_ic(r, vars)?
+                }
+            }
+            IOR(l, r) => {
+                let left = self.eval_i(*l, vars)?;
+                if left.is_nan() || !approx_eq(left, 0.0) {
+                    left
+                } else {
+                    self.eval_ic(r, vars)?
+                }
+            }
+
+            IVar(name) => match name.as_str() {
+                "nodata" | "null" => f64::NAN,
+                _ => match vars.get(name) {
+                    Some(v) => *v,
+                    None => return Err(format!("Unrecognized variable '{}'.", name)),
+                },
+            },
+            IFunc { name, args } => self.eval_function(name, args, vars)?,
+
+            IFuncInt(i) => self.eval_i(*i, vars)?.trunc(),
+            IFuncCeil(i) => self.eval_i(*i, vars)?.ceil(),
+            IFuncFloor(i) => self.eval_i(*i, vars)?.floor(),
+            IFuncAbs(i) => self.eval_i(*i, vars)?.abs(),
+            IFuncSign(i) => self.eval_i(*i, vars)?.signum(),
+            IFuncLog { base, of } => {
+                let (base, of) = (self.eval_ic(base, vars)?, self.eval_ic(of, vars)?);
+                if base == 2.0 {
+                    of.log2()
+                } else if base == 10.0 {
+                    of.log10()
+                } else {
+                    of.log(base)
+                }
+            }
+            IFuncRound { modulus, of } => {
+                let modulus = self.eval_ic(modulus, vars)?;
+                (self.eval_ic(of, vars)? / modulus).round() * modulus
+            }
+            IFuncMin(l, r) => {
+                let (l, r) = (self.eval_i(*l, vars)?, self.eval_ic(r, vars)?);
+                if l.is_nan() || r.is_nan() { f64::NAN } else { l.min(r) }
+            }
+            IFuncMax(l, r) => {
+                let (l, r) = (self.eval_i(*l, vars)?, self.eval_ic(r, vars)?);
+                if l.is_nan() || r.is_nan() { f64::NAN } else { l.max(r) }
+            }
+
+            IFuncSin(i) => self.eval_i(*i, vars)?.sin(),
+            IFuncCos(i) => self.eval_i(*i, vars)?.cos(),
+            IFuncTan(i) => self.eval_i(*i, vars)?.tan(),
+            IFuncASin(i) => self.eval_i(*i, vars)?.asin(),
+            IFuncACos(i) => self.eval_i(*i, vars)?.acos(),
+            IFuncATan(i) => self.eval_i(*i, vars)?.atan(),
+            IFuncSinH(i) => self.eval_i(*i, vars)?.sinh(),
+            IFuncCosH(i) => self.eval_i(*i, vars)?.cosh(),
+            IFuncTanH(i) => self.eval_i(*i, vars)?.tanh(),
+            IFuncASinH(i) => self.eval_i(*i, vars)?.asinh(),
+            IFuncACosH(i) => self.eval_i(*i, vars)?.acosh(),
+            IFuncATanH(i) => self.eval_i(*i, vars)?.atanh(),
+
+            IPrintFunc(_) => return Err("The print function is not supported.".to_string()),
+        };
+        Ok(value)
+    }
+
+    fn eval_function(&self, name: &str, args: &[IC], vars: &BTreeMap<String, f64>) -> Result<f64, String> {
+        match name {
+            "if" => {
+                if args.len() != 3 {
+                    return Err(format!("if() takes 3 arguments but {} were given.", args.len()));
+                }
+                let condition = self.eval_ic(&args[0], vars)?;
+                if condition.is_nan() {
+                    Ok(f64::NAN)
+                } else if !approx_eq(condition, 0.0) {
+                    self.eval_ic(&args[1], vars)
+                } else {
+                    self.eval_ic(&args[2], vars)
+                }
+            }
+            "isnodata" => {
+                if args.len() != 1 {
+                    return Err(format!("isnodata() takes 1 argument but {} were given.", args.len()));
+                }
+                Ok(bool_to_f64(self.eval_ic(&args[0], vars)?.is_nan()))
+            }
+            // The argument, if there is one, names the raster, but NoData is the same for all of them.
+            "nodata" | "null" => Ok(f64::NAN),
+            _ => {
+                let function = match self.functions.get(&name.to_lowercase()) {
+                    Some(f) => f,
+                    None => return Err(format!("Unrecognized function '{}'.", name)),
+                };
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    let value = self.eval_ic(arg, vars)?;
+                    if value.is_nan() {
+                        return Ok(f64::NAN);
+                    }
+                    values.push(value);
+                }
+                function.call(&values).map_err(|e| match e {
+                    InvocationError::InvalidArgumentCount { expected, found } => format!(
+                        "{}() takes {} arguments but {} were given.",
+                        name, expected, found
+                    ),
+                    InvocationError::Other { msg } => format!("Error in {}(): {}", name, msg),
+                })
+            }
+        }
+    }
+
+    fn eval_i(&self, i: InstructionI, vars: &BTreeMap<String, f64>) -> Result<f64, String> {
+        self.eval(self.slab.cs.get_instr(i), vars)
+    }
+
+    fn eval_ic(&self, ic: &IC, vars: &BTreeMap<String, f64>) -> Result<f64, String> {
+        match ic {
+            IC::I(i) => self.eval_i(*i, vars),
+            IC::C(c) => Ok(*c),
+        }
+    }
+}
+
+fn compare<F: Fn(f64, f64) -> bool>(l: f64, r: f64, f: F) -> f64 {
+    if l.is_nan() || r.is_nan() {
+        f64::NAN
+    } else {
+        bool_to_f64(f(l, r))
+    }
+}
+
+/// Equality within a few ULPs, as fasteval compares values.
+fn approx_eq(l: f64, r: f64) -> bool {
+    (l - r).abs() <= 8.0 * f64::EPSILON
+}
+
+fn bool_to_f64(b: bool) -> f64 {
+    if b { 1.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fasteval::{Compiler, Parser};
+
+    struct Hypot;
+
+    impl Function for Hypot {
+        fn call(&self, args: &[f64]) -> Result<f64, InvocationError> {
+            if args.len() != 2 {
+                return Err(InvocationError::InvalidArgumentCount { expected: 2, found: args.len() });
+            }
+            Ok(args[0].hypot(args[1]))
+        }
+    }
+
+    /// Evaluates a statement with the variables value0 and value1, and the hypot function.
+    fn eval(statement: &str, value0: f64, value1: f64) -> Result<f64, String> {
+        let mut slab = Slab::new();
+        let instruction = Parser::new()
+            .parse(statement, &mut slab.ps)
+            .map_err(|e| e.to_string())?
+            .from(&slab.ps)
+            .compile(&slab.ps, &mut slab.cs);
+        let mut functions: HashMap<String, Arc<dyn Function>> = HashMap::new();
+        functions.insert("hypot".to_string(), Arc::new(Hypot));
+        let mut vars = BTreeMap::new();
+        vars.insert("value0".to_string(), value0);
+        vars.insert("value1".to_string(), value1);
+        Evaluator::new(&slab, &functions).eval(&instruction, &vars)
+    }
+
+    #[test]
+    fn test_evaluation() {
+        assert_eq!(eval("value0 + 2 * value1 - 1", 3.0, 4.0), Ok(10.0));
+        assert_eq!(eval("(value0 > 2) && (value1 <= 4)", 3.0, 4.0), Ok(1.0));
+        assert_eq!(eval("value0 == 3 || value1 == 3", 3.0, 4.0), Ok(1.0));
+        assert_eq!(eval("!(value0 != 3)", 3.0, 4.0), Ok(1.0));
+        assert_eq!(eval("min(value0, value1) + max(value0, value1)", 3.0, 4.0), Ok(7.0));
+        assert_eq!(eval("hypot(value0, value1)", 3.0, 4.0), Ok(5.0));
+        assert_eq!(eval("HYPOT(value0, 4)", 3.0, 4.0), Ok(5.0));
+        assert_eq!(eval("if(value0 > value1, value0, value1)", 3.0, 4.0), Ok(4.0));
+        assert_eq!(eval("[value0 + 1] * 2", 3.0, 4.0), Ok(8.0));
+    }
+
+    #[test]
+    fn test_nodata_propagation() {
+        let nan = f64::NAN;
+        for statement in [
+            "value0 + 1",
+            "-value0",
+            "1 / value0",
+            "value0 % 2",
+            "value0 ^ 2",
+            "2 ^ value0",
+            "value0 * value1",
+            "value0 > 1",
+            "value0 <= value1",
+            "!value0",
+            "value0 && 1",
+            "value1 > 0 && value0",
+            "value0 || 0",
+            "abs(value0)",
+            "round(value0)",
+            "log(value0)",
+            "sin(value0)",
+            "min(value0, value1)",
+            "max(value1, value0)",
+            "hypot(value0, value1)",
+            "if(value0 > 1, 1, 0)",
+            "if(value1 > 1, value0, 0)",
+        ] {
+            let value = eval(statement, nan, 4.0).unwrap();
+            assert!(value.is_nan(), "{} = {}", statement, value);
+        }
+        // A branch that is not selected does not need to have data.
+        assert_eq!(eval("if(value1 > 1, value1, value0)", nan, 4.0), Ok(4.0));
+        assert_eq!(eval("value1 < 0 && value0", nan, 4.0), Ok(0.0));
+        assert_eq!(eval("value1 > 0 || value0", nan, 4.0), Ok(1.0));
+    }
+
+    #[test]
+    fn test_nodata_tests() {
+        let nan = f64::NAN;
+        for (statement, expected) in [
+            ("isnodata(value0)", 1.0),
+            ("isnodata(value1)", 0.0),
+            ("isnodata(value0 + value1)", 1.0),
+            ("value0 == nodata", 1.0),
+            ("value0 != nodata", 0.0),
+            ("value1 == nodata", 0.0),
+            ("value1 != null", 1.0),
+            ("nodata == value0", 1.0),
+            ("value0 == nodata()", 1.0),
+            ("value0 == nodata(value1)", 1.0),
+            ("value0 == value1", 0.0),
+            ("if(isnodata(value0), value1, value0)", 4.0),
+        ] {
+            assert_eq!(eval(statement, nan, 4.0), Ok(expected), "{}", statement);
+        }
+        // nodata() can be the result of a statement.
+        assert!(eval("nodata()", 1.0, 4.0).unwrap().is_nan());
+        assert!(eval("if(value0 > 2, nodata(), value0)", 3.0, 4.0).unwrap().is_nan());
+        assert!(eval("null", 1.0, 4.0).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            eval("value2 + 1", 1.0, 2.0),
+            Err("Unrecognized variable 'value2'.".to_string())
+        );
+        assert_eq!(
+            eval("foo(value0)", 1.0, 2.0),
+            Err("Unrecognized function 'foo'.".to_string())
+        );
+        assert_eq!(
+            eval("hypot(value0)", 1.0, 2.0),
+            Err("hypot() takes 2 arguments but 1 were given.".to_string())
+        );
+        assert_eq!(
+            eval("if(value0, 1)", 1.0, 2.0),
+            Err("if() takes 3 arguments but 2 were given.".to_string())
+        );
+        assert!(eval("print(value0)", 1.0, 2.0).is_err());
+    }
+}
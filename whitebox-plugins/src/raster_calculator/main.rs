@@ -1,10 +1,13 @@
 /* 
 Authors:  Dr. John Lindsay
 Created: 21/07/2021
-Last Modified: 21/07/2021
+Last Modified: 17/10/2026
 License: MIT
 */
 
+mod evaluator;
+
+use evaluator::Evaluator;
 use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::f64;
@@ -16,10 +19,12 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 use num_cpus;
+use whitebox_common::plugins::PluginManager;
 use whitebox_common::utils::get_formatted_elapsed_time;
 use whitebox_raster::*;
 // use v_eval::{Value, Eval};
 use fasteval;
+use fasteval::{Compiler, Parser, Slab};
 
 /// The RasterCalculator tool can be used to perform a complex mathematical operations on one or more input
 /// raster images on a cell-to-cell basis. The user specifies the name of the output raster (`--output`)
@@ -78,8 +83,14 @@ use fasteval;
 ///  * sinh(val)       * asinh(val)
 ///  * cosh(val)       * acosh(val)
 ///  * tanh(val)       * atanh(val)
+/// 
+///  * if(cond, a, b) -- a where cond is true (non-zero), otherwise b.
+///  * isnodata(val)  -- 1 where val is NoData, otherwise 0.
 /// ```
 ///
+/// Functions provided by plugin libraries (see `whitebox_common::plugins`) in the plugins folder may also be
+/// used, by the name with which they are registered, e.g. `hypot("dx.tif", "dy.tif")`.
+///
 /// Notice that the constants pi and e must be specified as functions, `pi()` and `e()`. A number of global variables 
 /// are also available to build conditional statements. These include the following:
 /// 
@@ -87,7 +98,7 @@ use fasteval;
 ///
 /// | Name | Description |
 /// | :-- | :-- |
-/// | `nodata` | An input raster's NoData value. |
+/// | `nodata` | The NoData value. |
 /// | `null` | Same as `nodata`. |
 /// | `minvalue` | An input raster's minimum value. |
 /// | `maxvalue` | An input raster's maximum value. |
@@ -106,9 +117,13 @@ use fasteval;
 /// | `cellsize` | The input raster's average grid resolution. |
 ///
 /// The special variable names are case-sensitive. If there are more than one raster inputs used in the statement,
-/// the functional forms of the `nodata`, `null`, `minvalue`, and `maxvalue` variables should be used, e.g. 
-/// `nodata("InputRaster")`, otherwise the value is assumed to specify the attribute of the first raster in the 
-/// statement. The following are examples of valid statements:
+/// the functional forms of the `minvalue` and `maxvalue` variables should be used, e.g. `minvalue("InputRaster")`,
+/// otherwise the value is assumed to specify the attribute of the first raster in the statement.
+///
+/// The values of neighbouring grid cells are specified with a row and column offset in square brackets after
+/// a raster, e.g. `"dem.tif"[-1,0]` is the cell in the row above and `"dem.tif"[0,1]` is the cell to the right.
+/// Cells beyond the edges of the raster are NoData. The brackets must directly follow the raster, without a
+/// space; other square brackets are treated as parentheses. The following are examples of valid statements:
 /// 
 /// ```
 ///  "raster" != 300.0
@@ -120,11 +135,17 @@ use fasteval;
 ///  tan("raster" * pi() / 180.0) > 1.0
 /// 
 ///  "raster" == nodata
+/// 
+///  ("dem"[-1,0] + "dem"[1,0] + "dem"[0,-1] + "dem"[0,1]) / 4.0
+/// 
+///  if(isnodata("raster1"), "raster2", "raster1")
 /// ```
 ///
-/// Any grid cell in the input rasters containing the NoData value will be assigned NoData in the output raster, 
-/// unless a NoData grid cell value allows the statement to evaluate to True (i.e. the mathematical expression 
-/// includes the `nodata` value).
+/// NoData propagates through the statement: the result of an operator or a function is NoData if any of
+/// the values that it uses are NoData, and grid cells where the statement evaluates to NoData are assigned
+/// NoData in the output raster. The exceptions are `isnodata(val)`, `val == nodata`, and `val != nodata`, which
+/// test for NoData, and `if(cond, a, b)`, which only evaluates the branch that is selected (and is NoData
+/// where `cond` is NoData).
 ///
 /// # See Also
 /// `ConditionalEvaluation`
@@ -300,6 +321,9 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     }
     statement = statement.replace("'", "");
 
+    let (focal_statement, focal_cells) = parse_focal_cells(&statement, num_inputs)?;
+    statement = focal_statement;
+
     for i in 0..num_inputs {
         if !input_files[i].contains(".") {
             input_files[i].push_str(".tif");
@@ -369,19 +393,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         ));
     }
 
-    let statement_contains_nodata = if statement.contains("nodata") {
-        true
-    } else {
-        false
-    };
-    
     for i in 0..num_inputs {
-        statement = statement.replace(&format!("nodata(value{})", i), &format!("{}", nodata[i]));
-        statement = statement.replace("nodata()", &format!("{}", nodata[0]));
-        statement = statement.replace("nodata", &format!("{}", nodata[0]));
-        statement = statement.replace(&format!("null(value{})", i), &format!("{}", nodata[i]));
-        statement = statement.replace("null()", &format!("{}", nodata[0]));
-        statement = statement.replace("null", &format!("{}", nodata[0]));
         statement = statement.replace(&format!("minvalue(value{})", i), &format!("{}", input_raster[i].configs.minimum));
         statement = statement.replace("minvalue()", &format!("{}", input_raster[0].configs.minimum));
         statement = statement.replace("minvalue", &format!("{}", input_raster[0].configs.minimum));
@@ -404,6 +416,29 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         .replace("Row", "row");
 
     
+    // Functions provided by plugin libraries, in the plugins folder with this program, may
+    // also be used in the statement.
+    let mut plugins = PluginManager::new();
+    if let Some(plugin_directory) = env::current_exe()?.parent() {
+        for e in plugins.load_directory(plugin_directory) {
+            if configurations.verbose_mode {
+                println!("Warning: {}", e);
+            }
+        }
+    }
+
+    // The statement is parsed and compiled once, and then evaluated for each grid cell.
+    let mut slab = Slab::new();
+    let instruction = match Parser::new().parse(&statement, &mut slab.ps) {
+        Ok(expression) => expression.from(&slab.ps).compile(&slab.ps, &mut slab.cs),
+        Err(e) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Error parsing the statement: {}", e),
+            ))
+        }
+    };
+
     let mut output = Raster::initialize_using_config(&output_file, &input_raster[0].configs.clone());
     let out_nodata = -32_768f64;
     output.configs.nodata = out_nodata;
@@ -415,16 +450,21 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let input_raster = Arc::new(input_raster);
     let nodata = Arc::new(nodata);
-    // calculate the number of inflowing cells
+    let focal_cells = Arc::new(focal_cells);
+    let slab = Arc::new(slab);
+    let instruction = Arc::new(instruction);
+    let functions = Arc::new(plugins.get_functions().clone());
     let (tx, rx) = mpsc::channel();
     for tid in 0..num_procs {
         let tx = tx.clone();
-        let statement = statement.clone();
         let input_raster = input_raster.clone();
         let nodata = nodata.clone();
+        let focal_cells = focal_cells.clone();
+        let slab = slab.clone();
+        let instruction = instruction.clone();
+        let functions = functions.clone();
         thread::spawn(move || {
-            let mut value: f64;
-            let mut is_nodata: bool;
+            let evaluator = Evaluator::new(&slab, &functions);
             let mut map : BTreeMap<String, f64> = BTreeMap::new();
             map.insert("rows".to_string(), rows as f64);
             map.insert("columns".to_string(), columns as f64);
@@ -443,29 +483,37 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 for col in 0..columns {
                     map.insert("column".to_string(), col as f64);
                     map.insert("columnx".to_string(), input_raster[0].get_x_from_column(col));
-                    is_nodata = false;
-                    for i in 0..num_inputs {
-                        value = input_raster[i].get_value(row, col);
-                        if value == nodata[i] { is_nodata = true; }
-                        map.insert(format!("value{}", i), value);
-                    }
-                    if !is_nodata || statement_contains_nodata {
-                        let ret = fasteval::ez_eval(&statement, &mut map);
-                        if ret.is_ok() {
-                            value = ret.unwrap();
-                            data[col as usize] = value;
+                    set_cell_values(&mut map, &input_raster, &nodata, &focal_cells, row, col);
+                    match evaluator.eval(&instruction, &map) {
+                        Ok(v) => {
+                            if !v.is_nan() {
+                                data[col as usize] = v;
+                            }
+                        }
+                        Err(e) => {
+                            let _ = tx.send(Err(e));
+                            return;
                         }
                     }
                 }
-                tx.send((row, data))
-                    .expect("Error sending data to thread.");
+                if tx.send(Ok((row, data))).is_err() {
+                    return;
+                }
             }
         });
     }
 
     let mut is_float_data = false;
     for r in 0..rows {
-        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        let (row, data) = match rx.recv().expect("Error receiving data from thread.") {
+            Ok(d) => d,
+            Err(e) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Error evaluating the statement: {}", e),
+                ))
+            }
+        };
         
         if !is_float_data {
             for i in 0..data.len() {
@@ -538,3 +586,168 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     Ok(())
 }
+
+/// A neighbouring cell of a raster: the index of the raster and the row and column offsets.
+type FocalCell = (usize, isize, isize);
+
+/// Sets the raster variables of a statement, i.e. value0, value1, etc. and the focal cell
+/// variables, to their values for a grid cell. NoData values are NaN within the statement,
+/// as are cells beyond the edges of the rasters.
+fn set_cell_values(
+    map: &mut BTreeMap<String, f64>,
+    input_raster: &[Raster],
+    nodata: &[f64],
+    focal_cells: &[FocalCell],
+    row: isize,
+    col: isize,
+) {
+    for i in 0..input_raster.len() {
+        let mut value = input_raster[i].get_value(row, col);
+        if value == nodata[i] { value = f64::NAN; }
+        map.insert(format!("value{}", i), value);
+    }
+    for (k, &(i, row_offset, col_offset)) in focal_cells.iter().enumerate() {
+        let mut value = input_raster[i].get_value(row + row_offset, col + col_offset);
+        if value == nodata[i] { value = f64::NAN; }
+        map.insert(format!("focal{}", k), value);
+    }
+}
+
+/// Replaces the references to neighbouring cells of a raster, i.e. a row and column offset in
+/// square brackets directly after the raster, e.g. "dem.tif"[-1,0] for the cell in the row
+/// above, with variables named focal0, focal1, etc. Returns the statement and the raster,
+/// row offset, and column offset of each variable. The rasters must already have been
+/// replaced by their variables (value0, value1, etc.); other square brackets are left for
+/// fasteval, which treats them as parentheses.
+fn parse_focal_cells(statement: &str, num_inputs: usize) -> Result<(String, Vec<FocalCell>), Error> {
+    let mut statement = statement.to_string();
+    let mut focal_cells = vec![];
+    let mut search_from = 0;
+    while let Some(open) = statement[search_from..].find('[').map(|i| i + search_from) {
+        let name_start = statement[..open]
+            .rfind(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let input = match statement[name_start..open].strip_prefix("value").map(|i| i.parse::<usize>()) {
+            Some(Ok(i)) if i < num_inputs => i,
+            _ => {
+                search_from = open + 1;
+                continue;
+            }
+        };
+        let close = match statement[open..].find(']') {
+            Some(c) => open + c,
+            None => {
+                return Err(Error::new(ErrorKind::InvalidInput, "Unmatched square brackets."));
+            }
+        };
+        let offsets = statement[open + 1..close]
+            .split(',')
+            .map(|v| v.trim().parse::<isize>())
+            .collect::<Vec<_>>();
+        let (row_offset, col_offset) = match offsets[..] {
+            [Ok(r), Ok(c)] => (r, c),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Error parsing the cell offsets [{}]; they must be two integers, e.g. [-1,0].", &statement[open + 1..close]),
+                ));
+            }
+        };
+        let name = format!("focal{}", focal_cells.len());
+        statement = format!("{}{}{}", &statement[..name_start], name, &statement[close + 1..]);
+        search_from = name_start + name.len();
+        focal_cells.push((input, row_offset, col_offset));
+    }
+    Ok((statement, focal_cells))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use fasteval::Instruction;
+
+    fn compile(statement: &str) -> (Slab, Instruction) {
+        let mut slab = Slab::new();
+        let instruction = Parser::new()
+            .parse(statement, &mut slab.ps)
+            .unwrap()
+            .from(&slab.ps)
+            .compile(&slab.ps, &mut slab.cs);
+        (slab, instruction)
+    }
+
+    #[test]
+    fn test_parse_focal_cells() {
+        let (statement, focal_cells) = parse_focal_cells("(value0[-1,0] + value1[ 0 , 2 ]) / value0", 2).unwrap();
+        assert_eq!(statement, "(focal0 + focal1) / value0");
+        assert_eq!(focal_cells, vec![(0, -1, 0), (1, 0, 2)]);
+
+        // Square brackets that do not directly follow a raster are parentheses.
+        for statement in ["[value0 + 1] * 2", "value0 [0,1]", "min[1, 2]", "rows[0,1]", "value2[0,1]"] {
+            let (parsed, focal_cells) = parse_focal_cells(statement, 2).unwrap();
+            assert_eq!(parsed, statement);
+            assert!(focal_cells.is_empty());
+        }
+        let (statement, focal_cells) = parse_focal_cells("[value0[0,1] - value0[0,-1]] / 2", 1).unwrap();
+        assert_eq!(statement, "[focal0 - focal1] / 2");
+        assert_eq!(focal_cells, vec![(0, 0, 1), (0, 0, -1)]);
+
+        for statement in ["value0[1]", "value0[1,2,3]", "value0[a,0]", "value0[0.5,0]", "value0[0,1"] {
+            let err = parse_focal_cells(statement, 1).err().unwrap();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", statement);
+        }
+    }
+
+    #[test]
+    fn test_focal_cells_are_evaluated() {
+        // A 3 x 4 raster with the values 10 * row + column, and a NoData cell at (1, 1).
+        let configs = RasterConfigs {
+            rows: 3,
+            columns: 4,
+            nodata: -9999.0,
+            north: 3.0,
+            south: 0.0,
+            east: 4.0,
+            west: 0.0,
+            resolution_x: 1.0,
+            resolution_y: 1.0,
+            ..Default::default()
+        };
+        let mut raster = Raster::initialize_using_config("focal.tif", &configs);
+        for row in 0..3 {
+            for col in 0..4 {
+                raster.set_value(row, col, (10 * row + col) as f64);
+            }
+        }
+        raster.set_value(1, 1, -9999.0);
+        let input_raster = vec![raster];
+        let nodata = vec![-9999.0];
+
+        let (statement, focal_cells) = parse_focal_cells("value0[1,0] - 2 * value0[0,-1]", 1).unwrap();
+        let (slab, instruction) = compile(&statement);
+        let functions = HashMap::new();
+        let evaluator = Evaluator::new(&slab, &functions);
+        let mut map = BTreeMap::new();
+        let mut eval = |row: isize, col: isize| {
+            set_cell_values(&mut map, &input_raster, &nodata, &focal_cells, row, col);
+            evaluator.eval(&instruction, &map).unwrap()
+        };
+        assert_eq!(eval(0, 2), 12.0 - 2.0 * 1.0);
+        assert_eq!(eval(1, 3), 23.0 - 2.0 * 12.0);
+        // The NoData cell, and cells beyond the edges of the raster, are NoData.
+        assert!(eval(0, 1).is_nan());
+        assert!(eval(1, 2).is_nan());
+        assert!(eval(2, 1).is_nan());
+        assert!(eval(1, 0).is_nan());
+
+        let (statement, focal_cells) = parse_focal_cells("if(isnodata(value0[0,1]), value0, value0[0,1])", 1).unwrap();
+        let (slab, instruction) = compile(&statement);
+        let evaluator = Evaluator::new(&slab, &functions);
+        set_cell_values(&mut map, &input_raster, &nodata, &focal_cells, 0, 3);
+        assert_eq!(evaluator.eval(&instruction, &map), Ok(3.0));
+        set_cell_values(&mut map, &input_raster, &nodata, &focal_cells, 1, 0);
+        assert_eq!(evaluator.eval(&instruction, &map), Ok(10.0));
+    }
+}
@@ -26,6 +26,7 @@ use whitebox_common::utils::{
     wrapped_print
 };
 use whitebox_vector::{
+    add_suffix_to_vector_file_name,
     AttributeField, 
     FieldData, 
     FieldDataType, 
@@ -247,9 +248,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 if s.to_lowercase().ends_with(".shp") {
                     inputs.push(s);
                     outputs.push(
-                        inputs[inputs.len() - 1]
-                            .replace(".shp", "_repaired.shp")
-                            .replace(".SHP", "_repaired.shp"),
+                        add_suffix_to_vector_file_name(&inputs[inputs.len() - 1], "_repaired"),
                     )
                 }
             }
@@ -265,10 +264,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         }
         inputs.push(input_file.clone());
         if output_file.is_empty() {
-            output_file = input_file
-                .clone()
-                .replace(".shp", "_corrected.shp")
-                .replace(".SHP", "_corrected.shp");
+            output_file = add_suffix_to_vector_file_name(&input_file, "_corrected");
         }
         if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
             output_file = format!("{}{}", working_directory, output_file);
@@ -933,7 +929,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
             println!("num. reversed arcs: {num_reversed}");
 
             // create output file
-            let mut output = Shapefile::initialize_using_file(&output_file.replace(".shp", "_reversed_arcs.shp"), &input, ShapeType::PolyLine, false).expect("Error creating output file");
+            let mut output = Shapefile::initialize_using_file(&add_suffix_to_vector_file_name(&output_file, "_reversed_arcs"), &input, ShapeType::PolyLine, false).expect("Error creating output file");
 
             // add the attributes
             output.attributes.add_field(
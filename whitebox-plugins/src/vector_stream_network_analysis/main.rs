@@ -342,18 +342,18 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     output.attributes.add_fields(&fields_vec);
 
-    let mut output_confluences = Shapefile::initialize_using_file(&output_file.replace(".shp", "_confluences.shp"), &input, ShapeType::Point, false)?;
+    let mut output_confluences = Shapefile::initialize_using_file(&add_suffix_to_vector_file_name(&output_file, "_confluences"), &input, ShapeType::Point, false)?;
     output_confluences
         .attributes
         .add_field(&AttributeField::new("FID", FieldDataType::Int, 6u8, 0u8));
 
 
-    let mut output_channel_heads = Shapefile::initialize_using_file(&output_file.replace(".shp", "_channelHeads.shp"), &input, ShapeType::Point, false)?;
+    let mut output_channel_heads = Shapefile::initialize_using_file(&add_suffix_to_vector_file_name(&output_file, "_channelHeads"), &input, ShapeType::Point, false)?;
     output_channel_heads
         .attributes
         .add_field(&AttributeField::new("FID", FieldDataType::Int, 6u8, 0u8));
 
-    let mut output_outlets = Shapefile::initialize_using_file(&output_file.replace(".shp", "_outlets.shp"), &input, ShapeType::Point, false)?;
+    let mut output_outlets = Shapefile::initialize_using_file(&add_suffix_to_vector_file_name(&output_file, "_outlets"), &input, ShapeType::Point, false)?;
     output_outlets
         .attributes
         .add_field(&AttributeField::new("FID", FieldDataType::Int, 6u8, 0u8));
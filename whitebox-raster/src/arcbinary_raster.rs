@@ -12,7 +12,7 @@ use std::mem;
 pub fn read_arcbinary(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read the header file
     // let header_file = file_name.replace(".flt", ".hdr");
@@ -140,7 +140,7 @@ pub fn read_arcbinary(
             };
             data.push(z);
 
-            if z != configs.nodata {
+            if !configs.is_nodata(z) {
                 if z < configs.minimum {
                     configs.minimum = z;
                 }
@@ -219,7 +219,7 @@ pub fn write_arcbinary<'a>(r: &'a mut Raster) -> Result<(), Error> {
 
     let num_cells: usize = r.configs.rows * r.configs.columns;
     for i in 0..num_cells {
-        u32_bytes = unsafe { mem::transmute(r.data[i] as f32) };
+        u32_bytes = unsafe { mem::transmute(r.data.get_value(i) as f32) };
         writer.write(&u32_bytes)?;
     }
 
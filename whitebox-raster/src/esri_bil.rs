@@ -9,7 +9,7 @@ use std::io::{BufReader, BufWriter, Error, SeekFrom};
 pub fn read_esri_bil(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read the header file
     let header_file = Path::new(&file_name)
@@ -135,7 +135,7 @@ pub fn read_esri_bil(
     }
 
     // read the data file
-    data.reserve(configs.rows * configs.columns);
+    *data = RasterData::with_capacity(configs.data_type, configs.rows * configs.columns);
 
     let data_file = Path::new(&file_name)
         .with_extension("bil")
@@ -165,7 +165,7 @@ pub fn read_esri_bil(
                     z = buffer[offset] as f64;
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -197,7 +197,7 @@ pub fn read_esri_bil(
                     };
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -229,7 +229,7 @@ pub fn read_esri_bil(
                     };
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -255,7 +255,7 @@ pub fn read_esri_bil(
                     z = (buffer[offset] as i8) as f64;
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -287,7 +287,7 @@ pub fn read_esri_bil(
                     };
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -319,7 +319,7 @@ pub fn read_esri_bil(
                     };
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -350,7 +350,7 @@ pub fn read_esri_bil(
                     };
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -380,7 +380,7 @@ pub fn read_esri_bil(
                     };
                     data.push(z);
 
-                    if z != configs.nodata {
+                    if !configs.is_nodata(z) {
                         if z < configs.minimum {
                             configs.minimum = z;
                         }
@@ -600,56 +600,56 @@ pub fn write_esri_bil<'a>(r: &'a mut Raster) -> Result<(), Error> {
         DataType::U8 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&([r.data[i] as u8]))
+                    .write(&([r.data.get_value(i) as u8]))
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::U16 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i] as u16).to_le_bytes())
+                    .write(&(r.data.get_value(i) as u16).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::U32 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i] as u32).to_le_bytes())
+                    .write(&(r.data.get_value(i) as u32).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::I8 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i] as i8).to_le_bytes())
+                    .write(&(r.data.get_value(i) as i8).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::I16 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i] as i16).to_le_bytes())
+                    .write(&(r.data.get_value(i) as i16).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::I32 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i] as i32).to_le_bytes())
+                    .write(&(r.data.get_value(i) as i32).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::F32 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i] as f32).to_le_bytes())
+                    .write(&(r.data.get_value(i) as f32).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
         DataType::F64 => {
             for i in 0..r.data.len() {
                 writer
-                    .write(&(r.data[i]).to_le_bytes())
+                    .write(&(r.data.get_value(i)).to_le_bytes())
                     .expect("Error writing bytes to BIL file.");
             }
         }
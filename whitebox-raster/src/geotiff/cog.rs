@@ -0,0 +1,388 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Cloud-Optimized GeoTIFF (COG) writing. A COG stores its image data in square tiles and
+//! includes a pyramid of internal overviews, each half the resolution of the previous level,
+//! so that web viewers and tile servers can read any part of the image at any scale with a
+//! few HTTP range requests. The file is laid out in the order that COG readers expect: the
+//! header, followed by the IFDs of the full-resolution image and each of its overviews, and
+//! finally the tile data, from the smallest overview to the full-resolution image. As GDAL
+//! does, this layout is declared in a block of structural metadata (the 'ghost area') that
+//! immediately follows the header.
+
+use super::*;
+use std::cmp::Ordering::Equal;
+use std::str::FromStr;
+
+/// The resampling method used to create the overviews of a Cloud-Optimized GeoTIFF. Each
+/// overview is derived from the 2 × 2 blocks of cells of the next-finer level.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OverviewResampling {
+    /// The upper-left cell of each block. Suitable for categorical data.
+    Nearest,
+    /// The mean of the valid (non-nodata) cells of each block. RGB colours are averaged
+    /// channel by channel.
+    Average,
+    /// The most frequently occurring valid value of each block, with ties resolved in
+    /// favour of the smallest value. Suitable for categorical data.
+    Mode,
+}
+
+impl FromStr for OverviewResampling {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<OverviewResampling, Error> {
+        match s.trim().to_lowercase().as_str() {
+            "nearest" | "nn" => Ok(OverviewResampling::Nearest),
+            "average" | "mean" => Ok(OverviewResampling::Average),
+            "mode" => Ok(OverviewResampling::Mode),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unrecognized overview resampling method '{}'; options include 'nearest', 'average', and 'mode'.",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+/// Options controlling how a raster is written as a Cloud-Optimized GeoTIFF.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CogOptions {
+    /// The width and length of the internal tiles, in cells. It must be a multiple of 16.
+    pub tile_size: usize,
+    /// The resampling method used to create the overviews.
+    pub resampling: OverviewResampling,
+}
+
+impl CogOptions {
+    /// Creates options using the default tile size (512 cells) and the given resampling method.
+    pub fn new(resampling: OverviewResampling) -> CogOptions {
+        CogOptions {
+            resampling,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for CogOptions {
+    fn default() -> CogOptions {
+        CogOptions {
+            tile_size: 512,
+            resampling: OverviewResampling::Nearest,
+        }
+    }
+}
+
+/// Writes a raster to a tiled GeoTIFF with internal overviews, laid out in COG order. Overviews
+/// are added until the coarsest level fits within a single tile. Tiles are compressed in the
+/// same way as other GeoTIFF output.
+pub(crate) fn write_cog(r: &Raster, options: &CogOptions) -> Result<(), Error> {
+    let tile_size = options.tile_size;
+    if tile_size == 0 || tile_size % 16 != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The tile size of a Cloud-Optimized GeoTIFF must be a multiple of 16.",
+        ));
+    }
+    let mut configs = r.configs.clone();
+    // At the moment, categorical and paletted output is not supported.
+    if configs.photometric_interp == PhotometricInterpretation::Categorical
+        || configs.photometric_interp == PhotometricInterpretation::Paletted
+    {
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+    }
+    let bytes_per_cell = configs.data_type.get_data_size();
+    if bytes_per_cell == 0 || configs.data_type == DataType::RGB48 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Cloud-Optimized GeoTIFF output is not supported for the {:?} data type.",
+                configs.data_type
+            ),
+        ));
+    }
+    let encoder = BlockEncoder::new(&configs, tile_size)?;
+    let num_bands = if configs.photometric_interp == PhotometricInterpretation::RGB {
+        1
+    } else {
+        configs.bands.max(1) as usize
+    };
+
+    // The dimensions (rows, columns) of the full-resolution image and each of its overviews.
+    let mut dims = vec![(configs.rows, configs.columns)];
+    while let Some(&(rows, columns)) = dims.last() {
+        if rows <= tile_size && columns <= tile_size {
+            break;
+        }
+        dims.push(((rows + 1) / 2, (columns + 1) / 2));
+    }
+    let num_levels = dims.len();
+
+    let mut overviews: Vec<Vec<f64>> = Vec::with_capacity(num_levels - 1);
+    for level in 1..num_levels {
+        let (rows, columns) = dims[level - 1];
+        let values = match overviews.last() {
+            Some(finer) => downsample(|i| finer[i], rows, columns, num_bands, &configs, options.resampling),
+            None => downsample(|i| r.data.get_value(i), rows, columns, num_bands, &configs, options.resampling),
+        };
+        overviews.push(values);
+    }
+    let get_value = |level: usize, i: usize| -> f64 {
+        if level == 0 {
+            r.data.get_value(i)
+        } else {
+            overviews[level - 1][i]
+        }
+    };
+
+    let tiles_per_band = |(rows, columns): (usize, usize)| -> usize {
+        ((rows + tile_size - 1) / tile_size) * ((columns + tile_size - 1) / tile_size)
+    };
+    let data_size: usize = dims.iter().map(|&d| tiles_per_band(d)).sum::<usize>()
+        * num_bands
+        * tile_size
+        * tile_size
+        * bytes_per_cell;
+    let is_big_tiff = 16usize + data_size >= 4_000_000_000;
+    let header_size = if !is_big_tiff { 8u64 } else { 16u64 };
+    let ghost_area = ghost_area();
+
+    // The sizes of the IFDs do not depend on the values of the tile offsets and byte counts,
+    // so the space that they occupy at the start of the file can be reserved up front.
+    let mut level_configs = Vec::with_capacity(num_levels);
+    let mut ifd_starts = Vec::with_capacity(num_levels);
+    let mut ifd_end = header_size + ghost_area.len() as u64;
+    ifd_end += ifd_end % 2;
+    for (level, &(rows, columns)) in dims.iter().enumerate() {
+        let mut c = configs.clone();
+        c.rows = rows;
+        c.columns = columns;
+        let placeholder = vec![0u64; tiles_per_band((rows, columns)) * num_bands];
+        let (ifd_entries, larger_values_data) = geotiff_ifd_entries(
+            &c,
+            &encoder,
+            is_big_tiff,
+            BlockLayout::Tiles(tile_size),
+            level > 0,
+            &placeholder,
+            &placeholder,
+        )?;
+        ifd_starts.push(ifd_end);
+        ifd_end += ifd_size(is_big_tiff, ifd_entries.len(), larger_values_data.len());
+        ifd_end += ifd_end % 2; // IFDs must begin on a word boundary.
+        level_configs.push(c);
+    }
+
+    let f = File::create(&r.file_name)?;
+    let mut writer = BufWriter::new(f);
+
+    //////////////////////
+    // Write the header //
+    //////////////////////
+    if configs.endian == Endianness::LittleEndian {
+        write_bytes(&mut writer, "II".as_bytes())?;
+    } else {
+        write_bytes(&mut writer, "MM".as_bytes())?;
+    }
+    if !is_big_tiff {
+        write_u16(&mut writer, configs.endian, 42u16)?;
+        write_u32(&mut writer, configs.endian, ifd_starts[0] as u32)?;
+    } else {
+        write_u16(&mut writer, configs.endian, 43u16)?;
+        write_u16(&mut writer, configs.endian, 8u16)?;
+        write_u16(&mut writer, configs.endian, 0u16)?;
+        write_u64(&mut writer, configs.endian, ifd_starts[0])?;
+    }
+    write_bytes(&mut writer, &ghost_area)?;
+    write_bytes(
+        &mut writer,
+        &vec![0u8; (ifd_end - header_size - ghost_area.len() as u64) as usize],
+    )?;
+
+    ////////////////////////////////////////////////////////////
+    // Write the tiles, from the coarsest level to the finest //
+    ////////////////////////////////////////////////////////////
+    let mut tile_offsets = vec![vec![]; num_levels];
+    let mut tile_byte_counts = vec![vec![]; num_levels];
+    let mut current_offset = ifd_end;
+    for level in (0..num_levels).rev() {
+        let (rows, columns) = dims[level];
+        let tiles_across = (columns + tile_size - 1) / tile_size;
+        let tiles_down = (rows + tile_size - 1) / tile_size;
+        for band in 0..num_bands {
+            let band_offset = band * rows * columns;
+            for tile_row in 0..tiles_down {
+                for tile_col in 0..tiles_across {
+                    let mut bytes = ByteOrderWriter::<Vec<u8>>::new(
+                        Vec::with_capacity(tile_size * tile_size * bytes_per_cell),
+                        configs.endian,
+                    );
+                    for row in tile_row * tile_size..(tile_row + 1) * tile_size {
+                        for col in tile_col * tile_size..(tile_col + 1) * tile_size {
+                            let z = if row < rows && col < columns {
+                                get_value(level, band_offset + row * columns + col)
+                            } else {
+                                configs.nodata
+                            };
+                            write_cell_value(&mut bytes, configs.data_type, z)?;
+                        }
+                    }
+                    let tile = encoder.encode(bytes.into_inner())?;
+                    write_bytes(&mut writer, &tile)?;
+                    tile_offsets[level].push(current_offset);
+                    tile_byte_counts[level].push(tile.len() as u64);
+                    current_offset += tile.len() as u64;
+                    if tile.len() % 2 != 0 {
+                        // Tiles must start on a word (i.e. an even offset).
+                        write_u8(&mut writer, 0u8)?;
+                        current_offset += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    //////////////////////////////////////////////
+    // Write the IFDs into their reserved space //
+    //////////////////////////////////////////////
+    for level in 0..num_levels {
+        let (ifd_entries, larger_values_data) = geotiff_ifd_entries(
+            &level_configs[level],
+            &encoder,
+            is_big_tiff,
+            BlockLayout::Tiles(tile_size),
+            level > 0,
+            &tile_offsets[level],
+            &tile_byte_counts[level],
+        )?;
+        let next_ifd = if level + 1 < num_levels {
+            ifd_starts[level + 1]
+        } else {
+            0u64
+        };
+        writer.seek(SeekFrom::Start(ifd_starts[level]))?;
+        write_ifd(
+            &mut writer,
+            configs.endian,
+            is_big_tiff,
+            ifd_starts[level],
+            ifd_entries,
+            &larger_values_data,
+            next_ifd,
+        )?;
+    }
+    writer.flush()
+}
+
+/// Returns GDAL's structural metadata, declaring that the IFDs precede the tile data and
+/// that the tiles of each band and level are stored in row-major order.
+fn ghost_area() -> Vec<u8> {
+    let metadata = "LAYOUT=IFDS_BEFORE_DATA\nBLOCK_ORDER=ROW_MAJOR\nKNOWN_INCOMPATIBLE_EDITION=NO\n";
+    format!(
+        "GDAL_STRUCTURAL_METADATA_SIZE={:06} bytes\n{}",
+        metadata.len(),
+        metadata
+    )
+    .into_bytes()
+}
+
+/// Creates the next-coarser level of the overview pyramid from a grid of `rows` by `columns`
+/// cells (band by band), whose values are returned by `get_value`.
+fn downsample<F: Fn(usize) -> f64>(
+    get_value: F,
+    rows: usize,
+    columns: usize,
+    num_bands: usize,
+    configs: &RasterConfigs,
+    resampling: OverviewResampling,
+) -> Vec<f64> {
+    let (out_rows, out_columns) = ((rows + 1) / 2, (columns + 1) / 2);
+    let nodata = configs.nodata;
+    let mut output = Vec::with_capacity(out_rows * out_columns * num_bands);
+    let mut block = Vec::with_capacity(4);
+    for band in 0..num_bands {
+        let band_offset = band * rows * columns;
+        for row in 0..out_rows {
+            for col in 0..out_columns {
+                if resampling == OverviewResampling::Nearest {
+                    output.push(get_value(band_offset + 2 * row * columns + 2 * col));
+                    continue;
+                }
+                block.clear();
+                for r in 2 * row..(2 * row + 2).min(rows) {
+                    for c in 2 * col..(2 * col + 2).min(columns) {
+                        let z = get_value(band_offset + r * columns + c);
+                        if !is_nodata_value(z, nodata) {
+                            block.push(z);
+                        }
+                    }
+                }
+                let z = if block.is_empty() {
+                    nodata
+                } else if resampling == OverviewResampling::Mode {
+                    block_mode(&mut block)
+                } else {
+                    block_mean(&block, configs.data_type)
+                };
+                output.push(z);
+            }
+        }
+    }
+    output
+}
+
+/// Returns the mean of a block of valid values, rounded for integer data types and
+/// calculated channel by channel for RGB colours.
+fn block_mean(block: &[f64], data_type: DataType) -> f64 {
+    let n = block.len() as f64;
+    match data_type {
+        DataType::RGB24 | DataType::RGBA32 => {
+            let num_channels = if data_type == DataType::RGBA32 { 4 } else { 3 };
+            let mut colour = 0u32;
+            for channel in 0..num_channels {
+                let shift = 8 * channel;
+                let sum: f64 = block
+                    .iter()
+                    .map(|&z| ((z as u32 >> shift) & 0xFF) as f64)
+                    .sum();
+                colour |= ((sum / n).round() as u32) << shift;
+            }
+            colour as f64
+        }
+        _ => {
+            let mean = block.iter().sum::<f64>() / n;
+            if data_type.is_float() {
+                mean
+            } else {
+                mean.round()
+            }
+        }
+    }
+}
+
+/// Returns the most frequently occurring value of a block, or the smallest of the most
+/// frequent values in the case of a tie.
+fn block_mode(block: &mut [f64]) -> f64 {
+    block.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+    let (mut mode, mut mode_count) = (block[0], 0);
+    let mut i = 0;
+    while i < block.len() {
+        let mut j = i + 1;
+        while j < block.len() && block[j] == block[i] {
+            j += 1;
+        }
+        if j - i > mode_count {
+            mode = block[i];
+            mode_count = j - i;
+        }
+        i = j;
+    }
+    mode
+}
@@ -0,0 +1,360 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Compression and prediction of the strips and tiles of GeoTIFF files. Predictors
+//! transform the samples of each row of a block before compression, which typically
+//! yields much smaller files for continuous data such as DEMs. The horizontal predictor
+//! (PREDICTOR=2) stores each sample as its difference from the previous sample in the
+//! row, and the floating-point predictor (PREDICTOR=3) applies the same differencing to
+//! the bytes of floating-point samples after grouping them by significance.
+
+use super::*;
+use std::io::Read;
+
+/// The compression method used to write the image data of GeoTIFF files.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TiffCompression {
+    None,
+    Deflate,
+    Lzw,
+    Zstd,
+}
+
+impl TiffCompression {
+    /// Returns the value of the TIFF Compression tag (259) for this method.
+    pub fn code(&self) -> u16 {
+        match self {
+            TiffCompression::None => COMPRESS_NONE,
+            TiffCompression::Deflate => COMPRESS_DEFLATE,
+            TiffCompression::Lzw => COMPRESS_LZW,
+            TiffCompression::Zstd => COMPRESS_ZSTD,
+        }
+    }
+}
+
+/// The predictor applied to the image data of GeoTIFF files before compression.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum TiffPredictor {
+    #[default]
+    None,
+    /// Horizontal differencing (PREDICTOR=2), suitable for integer data.
+    Horizontal,
+    /// Floating-point prediction (PREDICTOR=3), suitable for F32 and F64 data.
+    FloatingPoint,
+}
+
+impl TiffPredictor {
+    /// Returns the value of the TIFF Predictor tag (317) for this predictor.
+    pub fn code(&self) -> u16 {
+        match self {
+            TiffPredictor::None => 1,
+            TiffPredictor::Horizontal => 2,
+            TiffPredictor::FloatingPoint => 3,
+        }
+    }
+}
+
+/// Applies the predictor and compression method of a GeoTIFF output to each of its
+/// strips or tiles.
+pub(crate) struct BlockEncoder {
+    pub compression: TiffCompression,
+    pub predictor: TiffPredictor,
+    endian: Endianness,
+    block_width: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+}
+
+impl BlockEncoder {
+    /// Creates an encoder for blocks that are `block_width` pixels wide. The compression
+    /// method is taken from the configs or, if it is unspecified, is DEFLATE when raster
+    /// compression is enabled in the settings. Predictors are only applied to compressed
+    /// output.
+    pub(crate) fn new(configs: &RasterConfigs, block_width: usize) -> Result<BlockEncoder, Error> {
+        let compression = match configs.compression {
+            Some(compression) => compression,
+            None => {
+                if whitebox_common::configs::get_configs()?.compress_rasters {
+                    TiffCompression::Deflate
+                } else {
+                    TiffCompression::None
+                }
+            }
+        };
+        let predictor = if compression == TiffCompression::None {
+            TiffPredictor::None
+        } else {
+            configs.predictor
+        };
+        if predictor == TiffPredictor::FloatingPoint && !configs.data_type.is_float() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The floating-point predictor can only be used with F32 and F64 data.",
+            ));
+        }
+        let (samples_per_pixel, bytes_per_sample) = match configs.data_type {
+            DataType::RGB24 => (3, 1),
+            DataType::RGBA32 => (4, 1),
+            DataType::RGB48 => (3, 2),
+            _ => (1, configs.data_type.get_data_size()),
+        };
+        Ok(BlockEncoder {
+            compression,
+            predictor,
+            endian: configs.endian,
+            block_width,
+            samples_per_pixel,
+            bytes_per_sample,
+        })
+    }
+
+    /// Sets the number of bands that are interleaved in each pixel of the blocks, which is
+    /// the distance between the samples that the predictor differences.
+    pub(crate) fn with_interleaved_bands(mut self, num_bands: usize) -> BlockEncoder {
+        self.samples_per_pixel = num_bands;
+        self
+    }
+
+    /// Returns true if the blocks are compressed.
+    pub(crate) fn is_compressed(&self) -> bool {
+        self.compression != TiffCompression::None
+    }
+
+    /// Applies the predictor to, and compresses, the bytes of a block.
+    pub(crate) fn encode(&self, mut data: Vec<u8>) -> Result<Vec<u8>, Error> {
+        apply_predictor(
+            &mut data,
+            self.predictor,
+            self.block_width,
+            self.samples_per_pixel,
+            self.bytes_per_sample,
+            self.endian,
+        );
+        match self.compression {
+            TiffCompression::None => Ok(data),
+            TiffCompression::Deflate => Ok(compress_to_vec_zlib(&data, 6)),
+            TiffCompression::Lzw => {
+                weezl::encode::Encoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8)
+                    .encode(&data)
+                    .map_err(|e| {
+                        Error::other(format!("Error encountered during LZW compression: {}", e))
+                    })
+            }
+            TiffCompression::Zstd => Ok(ruzstd::encoding::compress_to_vec(
+                &data[..],
+                ruzstd::encoding::CompressionLevel::Fastest,
+            )),
+        }
+    }
+}
+
+/// Decompresses a ZSTD compressed block.
+pub(crate) fn decompress_zstd(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut source = compressed;
+    let mut decoder = ruzstd::decoding::StreamingDecoder::new(&mut source).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Error encountered while decoding the ZSTD compressed GeoTIFF file: {}",
+                e
+            ),
+        )
+    })?;
+    let mut buf = vec![];
+    decoder.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Decompresses an LZMA compressed block, which is stored in the .xz container format.
+pub(crate) fn decompress_lzma(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut buf = vec![];
+    lzma_rs::xz_decompress(&mut &compressed[..], &mut buf).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Error encountered while decoding the LZMA compressed GeoTIFF file: {:?}",
+                e
+            ),
+        )
+    })?;
+    Ok(buf)
+}
+
+/// Reads an unsigned integer sample of `bytes_per_sample` bytes at position `pos`.
+fn read_uint(row: &[u8], pos: usize, bytes_per_sample: usize, endian: Endianness) -> u64 {
+    let mut value = 0u64;
+    for k in 0..bytes_per_sample {
+        let byte = match endian {
+            Endianness::LittleEndian => row[pos + bytes_per_sample - 1 - k],
+            Endianness::BigEndian => row[pos + k],
+        };
+        value = (value << 8) | byte as u64;
+    }
+    value
+}
+
+/// Writes the low-order `bytes_per_sample` bytes of an unsigned integer sample at position `pos`.
+fn write_uint(row: &mut [u8], pos: usize, bytes_per_sample: usize, endian: Endianness, value: u64) {
+    for k in 0..bytes_per_sample {
+        let byte = (value >> (8 * k)) as u8;
+        match endian {
+            Endianness::LittleEndian => row[pos + k] = byte,
+            Endianness::BigEndian => row[pos + bytes_per_sample - 1 - k] = byte,
+        }
+    }
+}
+
+/// Applies a predictor to the rows of a block of uncompressed data. Each row contains
+/// `block_width` pixels of `samples_per_pixel` samples, which are stored in the byte order
+/// `endian`.
+pub(crate) fn apply_predictor(
+    data: &mut [u8],
+    predictor: TiffPredictor,
+    block_width: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+    endian: Endianness,
+) {
+    let num_samples = block_width * samples_per_pixel;
+    let row_length = num_samples * bytes_per_sample;
+    if row_length == 0 {
+        return;
+    }
+    match predictor {
+        TiffPredictor::None => {}
+        TiffPredictor::Horizontal => {
+            for row in data.chunks_exact_mut(row_length) {
+                for i in (samples_per_pixel..num_samples).rev() {
+                    let pos = i * bytes_per_sample;
+                    let value = read_uint(row, pos, bytes_per_sample, endian);
+                    let prev = read_uint(
+                        row,
+                        pos - samples_per_pixel * bytes_per_sample,
+                        bytes_per_sample,
+                        endian,
+                    );
+                    write_uint(row, pos, bytes_per_sample, endian, value.wrapping_sub(prev));
+                }
+            }
+        }
+        TiffPredictor::FloatingPoint => {
+            // The bytes of the samples are grouped by significance, with the most significant
+            // bytes of each sample first, and then differenced.
+            let mut tmp = vec![0u8; row_length];
+            for row in data.chunks_exact_mut(row_length) {
+                for count in 0..num_samples {
+                    for byte in 0..bytes_per_sample {
+                        tmp[byte * num_samples + count] = match endian {
+                            Endianness::LittleEndian => {
+                                row[count * bytes_per_sample + bytes_per_sample - 1 - byte]
+                            }
+                            Endianness::BigEndian => row[count * bytes_per_sample + byte],
+                        };
+                    }
+                }
+                for i in (samples_per_pixel..row_length).rev() {
+                    tmp[i] = tmp[i].wrapping_sub(tmp[i - samples_per_pixel]);
+                }
+                row.copy_from_slice(&tmp);
+            }
+        }
+    }
+}
+
+/// Reverses a predictor that was applied to the rows of a block of uncompressed data. This
+/// is the inverse of `apply_predictor`.
+pub(crate) fn undo_predictor(
+    data: &mut [u8],
+    predictor: TiffPredictor,
+    block_width: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+    endian: Endianness,
+) {
+    let num_samples = block_width * samples_per_pixel;
+    let row_length = num_samples * bytes_per_sample;
+    if row_length == 0 {
+        return;
+    }
+    match predictor {
+        TiffPredictor::None => {}
+        TiffPredictor::Horizontal => {
+            for row in data.chunks_exact_mut(row_length) {
+                for i in samples_per_pixel..num_samples {
+                    let pos = i * bytes_per_sample;
+                    let value = read_uint(row, pos, bytes_per_sample, endian);
+                    let prev = read_uint(
+                        row,
+                        pos - samples_per_pixel * bytes_per_sample,
+                        bytes_per_sample,
+                        endian,
+                    );
+                    write_uint(row, pos, bytes_per_sample, endian, value.wrapping_add(prev));
+                }
+            }
+        }
+        TiffPredictor::FloatingPoint => {
+            let mut tmp = vec![0u8; row_length];
+            for row in data.chunks_exact_mut(row_length) {
+                for i in samples_per_pixel..row_length {
+                    row[i] = row[i].wrapping_add(row[i - samples_per_pixel]);
+                }
+                tmp.copy_from_slice(row);
+                for count in 0..num_samples {
+                    for byte in 0..bytes_per_sample {
+                        let b = tmp[byte * num_samples + count];
+                        match endian {
+                            Endianness::LittleEndian => {
+                                row[count * bytes_per_sample + bytes_per_sample - 1 - byte] = b
+                            }
+                            Endianness::BigEndian => row[count * bytes_per_sample + byte] = b,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_predictors_are_reversed() {
+        let original: Vec<u8> = (0..3 * 5 * 2 * 4).map(|i| (i * 37 % 251) as u8).collect();
+        for endian in [Endianness::LittleEndian, Endianness::BigEndian] {
+            for predictor in [TiffPredictor::Horizontal, TiffPredictor::FloatingPoint] {
+                // three rows of five pixels, each with two interleaved four-byte samples
+                let mut data = original.clone();
+                apply_predictor(&mut data, predictor, 5, 2, 4, endian);
+                assert_ne!(data, original);
+                undo_predictor(&mut data, predictor, 5, 2, 4, endian);
+                assert_eq!(data, original, "{:?} {:?}", predictor, endian);
+            }
+        }
+    }
+
+    #[test]
+    fn test_floating_point_predictor_requires_float_data() {
+        let mut configs = RasterConfigs {
+            data_type: DataType::I32,
+            compression: Some(TiffCompression::Deflate),
+            predictor: TiffPredictor::FloatingPoint,
+            ..Default::default()
+        };
+        assert!(BlockEncoder::new(&configs, 10).is_err());
+        configs.data_type = DataType::F32;
+        assert!(BlockEncoder::new(&configs, 10).is_ok());
+        // without compression, no predictor is applied
+        configs.data_type = DataType::I32;
+        configs.compression = Some(TiffCompression::None);
+        let encoder = BlockEncoder::new(&configs, 10).unwrap();
+        assert_eq!(encoder.predictor, TiffPredictor::None);
+    }
+}
@@ -1,12 +1,19 @@
 #![allow(unused_assignments, dead_code)]
+pub mod cog;
+pub mod compression;
 pub mod geokeys;
 pub mod ifd;
+pub mod streaming;
 pub mod tiff_consts;
 
 // use flate2::read::GzDecoder;
 // use super::use_compression;
 use crate::geotiff::geokeys::*;
 use crate::geotiff::tiff_consts::*;
+pub use self::cog::{CogOptions, OverviewResampling};
+pub use self::compression::{TiffCompression, TiffPredictor};
+use self::compression::*;
+pub use self::streaming::{use_streaming, GeoTiffReader, GeoTiffWriter};
 use crate::*;
 use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
 use whitebox_common::structures::{Point2D, PolynomialRegression2D};
@@ -231,15 +238,99 @@ pub fn print_tags<'a>(file_name: &'a String) -> Result<(), Error> {
 pub fn read_geotiff<'a>(
     file_name: &'a String,
     configs: &'a mut RasterConfigs,
-    data: &'a mut Vec<f64>,
+    data: &'a mut RasterData,
 ) -> Result<(), Error> {
     let f = File::open(file_name.clone())?;
+    let br = BufReader::new(f);
+    let mut th = ByteOrderReader::<BufReader<File>>::new(br, configs.endian);
+    let layout = read_geotiff_layout(&mut th, configs)?;
+
+    ////////////////////
+    // Read the data! //
+    ////////////////////
+    // Multi-band data are stored band-sequentially, i.e. all of the cells of the
+    // first band, followed by those of the second band, and so on. The values are
+    // held in the native data type of the image.
+    let width = layout.width;
+    let band_size = layout.width * layout.height;
+    *data = RasterData::filled(configs.data_type, 0.0, band_size * layout.num_bands);
+    for plane in 0..layout.num_planes {
+        let (first_band, block_bands) = layout.block_bands(plane);
+        for block_row in 0..layout.blocks_down {
+            for block_col in 0..layout.blocks_across {
+                let block = read_block(&mut th, configs, &layout, plane, block_row, block_col)?;
+                let (ymin, ymax, xmin, xmax) = layout.block_extent(block_row, block_col);
+                let columns = xmax - xmin;
+                let block_size = (ymax - ymin) * columns;
+                for b in 0..block_bands {
+                    for y in ymin..ymax {
+                        for x in xmin..xmax {
+                            data.set_value(
+                                (first_band + b) * band_size + y * width + x,
+                                block.get_value(b * block_size + (y - ymin) * columns + x - xmin),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The arrangement of the image data within a GeoTIFF file, i.e. how the cells are
+/// divided into strips or tiles and how each of these blocks is encoded.
+pub(crate) struct TiffLayout {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) block_width: usize,
+    pub(crate) block_height: usize,
+    pub(crate) blocks_across: usize,
+    pub(crate) blocks_down: usize,
+    block_padding: bool,
+    block_offsets: Vec<u64>,
+    block_counts: Vec<u64>,
+    compression: u16,
+    predictor: TiffPredictor,
+    bits_per_sample: Vec<u16>,
+    sample_format: Vec<u16>,
+    mode: u16,
+    palette: Vec<u32>,
+    pub(crate) num_bands: usize,
+    pub(crate) num_planes: usize,
+}
+
+impl TiffLayout {
+    /// Returns the rows (ymin..ymax) and columns (xmin..xmax) of the image covered by
+    /// a block, excluding any padding.
+    pub(crate) fn block_extent(&self, block_row: usize, block_col: usize) -> (usize, usize, usize, usize) {
+        let xmin = block_col * self.block_width;
+        let ymin = block_row * self.block_height;
+        let xmax = min(xmin + self.block_width, self.width);
+        let ymax = min(ymin + self.block_height, self.height);
+        (ymin, ymax, xmin, xmax)
+    }
+
+    /// Returns the first band, and the number of bands, held in the blocks of a plane.
+    pub(crate) fn block_bands(&self, plane: usize) -> (usize, usize) {
+        if self.num_planes > 1 {
+            (plane, 1)
+        } else {
+            (0, self.num_bands)
+        }
+    }
+}
 
+/// Reads the TIFF header and image file directory, filling in the raster configs and
+/// returning the layout of the image data.
+pub(crate) fn read_geotiff_layout<R: Read + Seek>(
+    th: &mut ByteOrderReader<R>,
+    configs: &mut RasterConfigs,
+) -> Result<TiffLayout, Error> {
     //////////////////////////
     // Read the TIFF header //
     //////////////////////////
-    let br = BufReader::new(f);
-    let mut th = ByteOrderReader::<BufReader<File>>::new(br, configs.endian);
 
     let bo_indicator1 = th.read_u8()?;
     let bo_indicator2 = th.read_u8()?;
@@ -467,11 +558,18 @@ pub fn read_geotiff<'a>(
         && compression != COMPRESS_PACKBITS
         && compression != COMPRESS_LZW
         && compression != COMPRESS_DEFLATE
+        && compression != COMPRESS_ZSTD
+        && compression != COMPRESS_LZMA
     {
-        println!("Compression: {}", compression);
+        let method = match compression {
+            COMPRESS_JPEGOLD | COMPRESS_JPEG => "JPEG".to_string(),
+            COMPRESS_LERC => "LERC".to_string(),
+            COMPRESS_WEBP => "WEBP".to_string(),
+            _ => format!("code {}", compression),
+        };
         return Err(Error::new(
             ErrorKind::InvalidData,
-            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, and DEFLATE compression.",
+            format!("The WhiteboxTools GeoTIFF decoder does not support {} compression; supported methods include PACKBITS, LZW, DEFLATE, ZSTD, and LZMA.", method),
         ));
     }
 
@@ -485,10 +583,16 @@ pub fn read_geotiff<'a>(
         }
     };
 
-    // let num_samples = match ifd_map.get(&277) {
-    //     Some(ifd) => ifd.interpret_as_u16()[0],
-    //     _ => 0,
-    // };
+    let samples_per_pixel = match ifd_map.get(&TAG_SAMPLESPERPIXEL) {
+        Some(ifd) => ifd.interpret_as_u16()[0] as usize,
+        _ => 1,
+    };
+
+    // PlanarConfiguration: 1 = chunky (samples of a pixel are interleaved), 2 = planar (one band per block)
+    let planar_config = match ifd_map.get(&TAG_PLANARCONFIGURATION) {
+        Some(ifd) => ifd.interpret_as_u16()[0],
+        _ => 1,
+    };
 
     match ifd_map.get(&280) {
         Some(ifd) => {
@@ -637,9 +741,9 @@ pub fn read_geotiff<'a>(
         let mut row = 0.0;
         configs.west = configs.resolution_x * col + tx;
         configs.north = -configs.resolution_y * row + ty;
-        // lower-right corner coordinates
-        col = (configs.columns - 1) as f64;
-        row = (configs.rows - 1) as f64;
+        // lower-right corner coordinates, i.e. of the lower-right corner of the last cell
+        col = configs.columns as f64;
+        row = configs.rows as f64;
         configs.east = configs.resolution_x * col + tx;
         configs.south = -configs.resolution_y * row + ty;
     } else if configs.model_tiepoint.len() > 6 {
@@ -688,7 +792,7 @@ pub fn read_geotiff<'a>(
         let upper_left_y = minyp + val.1;
 
         // upper-right corner coordinates
-        col = (configs.columns - 1) as f64;
+        col = configs.columns as f64;
         row = 0.0;
         val = pr2d.get_value(col, row);
         let upper_right_x = minxp + val.0;
@@ -696,14 +800,14 @@ pub fn read_geotiff<'a>(
 
         // lower-left corner coordinates
         col = 0.0;
-        row = (configs.rows - 1) as f64;
+        row = configs.rows as f64;
         val = pr2d.get_value(col, row);
         let lower_left_x = minxp + val.0;
         let lower_left_y = minyp + val.1;
 
         // lower-right corner coordinates
-        col = (configs.columns - 1) as f64;
-        row = (configs.rows - 1) as f64;
+        col = configs.columns as f64;
+        row = configs.rows as f64;
         val = pr2d.get_value(col, row);
         let lower_right_x = minxp + val.0;
         let lower_right_y = minyp + val.1;
@@ -733,7 +837,7 @@ pub fn read_geotiff<'a>(
             + configs.model_transformation[7];
 
         // upper-right corner coordinates
-        col = (configs.columns - 1) as f64;
+        col = configs.columns as f64;
         row = 0.0;
         let upper_right_x = configs.model_transformation[0] * col
             + configs.model_transformation[1] * row
@@ -744,7 +848,7 @@ pub fn read_geotiff<'a>(
 
         // lower-left corner coordinates
         col = 0.0;
-        row = (configs.rows - 1) as f64;
+        row = configs.rows as f64;
         let lower_left_x = configs.model_transformation[0] * col
             + configs.model_transformation[1] * row
             + configs.model_transformation[3];
@@ -753,8 +857,8 @@ pub fn read_geotiff<'a>(
             + configs.model_transformation[7];
 
         // lower-right corner coordinates
-        col = (configs.columns - 1) as f64;
-        row = (configs.rows - 1) as f64;
+        col = configs.columns as f64;
+        row = configs.rows as f64;
         let lower_right_x = configs.model_transformation[0] * col
             + configs.model_transformation[1] * row
             + configs.model_transformation[3];
@@ -895,6 +999,33 @@ pub fn read_geotiff<'a>(
         ));
     }
 
+    // Greyscale images with more than one sample per pixel are multi-band
+    // images (e.g. multispectral satellite imagery). Each sample is read
+    // into its own band.
+    let num_bands = if (mode == IM_GRAY || mode == IM_GRAYINVERT) && samples_per_pixel > 1 {
+        samples_per_pixel
+    } else {
+        1
+    };
+    if num_bands > u8::MAX as usize {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("The GeoTIFF contains {} bands; a maximum of {} bands is supported.", num_bands, u8::MAX),
+        ));
+    }
+    configs.bands = num_bands as u8;
+    let num_planes = if planar_config == 2 && samples_per_pixel > 1 {
+        if num_bands == 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The GeoTIFF decoder does not support planar (PlanarConfiguration = 2) colour images.",
+            ));
+        }
+        samples_per_pixel
+    } else {
+        1
+    };
+
     let width = configs.columns;
     let height = configs.rows;
 
@@ -1103,277 +1234,438 @@ pub fn read_geotiff<'a>(
         };
     }
 
-    ////////////////////
-    // Read the data! //
-    ////////////////////
-    if data.len() > 0 {
-        data.clear();
-    }
-    data.reserve_exact(configs.rows * configs.columns);
-    unsafe {
-        // The memory will be initialized when we read
-        // the pixel values.
-        data.set_len(configs.rows * configs.columns);
-    }
-
-    for i in 0..blocks_across {
-        let mut blk_w = block_width;
-        if !block_padding && i == blocks_across - 1 && width % block_width != 0 {
-            blk_w = width % block_width;
-        }
-        for j in 0..blocks_down {
-            let mut blk_h = block_height;
-            if !block_padding && j == blocks_down - 1 && height % block_height != 0 {
-                blk_h = height % block_height;
-            }
-            let offset = block_offsets[j * blocks_across + i] as usize;
-            let n = block_counts[j * blocks_across + i] as usize;
-            let mut buf: Vec<u8> = vec![];
-            if n != 0 {
-                // it's not a sparse tile
-                match compression {
-                    COMPRESS_NONE => {
-                        // no compression
-                        // buf = vec![0u8; n];
-                        buf.reserve_exact(n);
-                        unsafe { buf.set_len(n); }
-                        th.seek(offset);
-                        th.read_exact(&mut buf)?;
-                    }
-                    COMPRESS_PACKBITS => {
-                        // buf = packbits_decoder(th.buffer[offset..(offset + n)].to_vec());
-                        let mut b = vec![0u8; n];
-                        th.seek(offset);
-                        th.read_exact(&mut b).expect("Error reading bytes from file.");
-                        buf = packbits_decoder(b);
-                    }
-                    COMPRESS_LZW => {
-                        let mut compressed = vec![0; n];
-                        th.seek(offset);
-                        th.read_exact(&mut compressed).expect("Error reading bytes from file.");
-                        let max_uncompressed_length = block_width * block_height * bits_per_sample.len() * bits_per_sample[0] as usize / 8;
-                        buf = Vec::with_capacity(max_uncompressed_length);
-                        let mut decoder = lzw::DecoderEarlyChange::new(lzw::MsbReader::new(), 8);
-                        let mut bytes_read = 0;
-                        while bytes_read < n && buf.len() < max_uncompressed_length {
-                            let (len, bytes) = decoder.decode_bytes(&compressed[bytes_read..]).expect("Error encountered while decoding the LZW compressed GeoTIFF file.");
-                            bytes_read += len;
-                            buf.extend_from_slice(bytes);
+    // The data type of the image.
+    match mode {
+        IM_GRAYINVERT | IM_GRAY => {
+            //ImageMode::GrayInvert | ImageMode::Gray => {
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            match sample_format[0] {
+                1 => {
+                    // unsigned integer
+                    match bits_per_sample[0] {
+                        8 => {
+                            configs.data_type = DataType::U8;
+                        }
+                        16 => {
+                            configs.data_type = DataType::U16;
+                        }
+                        32 => {
+                            configs.data_type = DataType::U32;
+                        }
+                        64 => {
+                            configs.data_type = DataType::U64;
+                        }
+                        _ => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidData,
+                                "The raster was not read correctly",
+                            ))
                         }
                     }
-                    COMPRESS_DEFLATE => {
-                        // let mut dec = GzDecoder::new(th.buffer[offset..(offset + n)].to_vec());
-                        // let compressed = &th.buffer[offset..(offset + n)];
-                        // let mut decoder = Decoder::new(&compressed[..]).unwrap();
-                        // decoder.read_to_end(&mut buf).unwrap();
-                        th.seek(offset);
-                        let mut compressed = vec![0u8; n];
-                        th.read_exact(&mut compressed).expect("Error reading bytes from file.");
-                        // let mut decoder = Decoder::new(&compressed[..])?;
-                        // decoder.read_to_end(&mut buf).unwrap();
-                        buf.extend(decompress_to_vec_zlib(&compressed).expect("Error encountered while decoding the DEFLATE compressed GeoTIFF file."));
+                }
+                2 => {
+                    // signed integer
+                    match bits_per_sample[0] {
+                        8 => {
+                            configs.data_type = DataType::I8;
+                        }
+                        16 => {
+                            configs.data_type = DataType::I16;
+                        }
+                        32 => {
+                            configs.data_type = DataType::I32;
+                        }
+                        64 => {
+                            configs.data_type = DataType::I64;
+                        }
+                        _ => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidData,
+                                "The raster was not read correctly",
+                            ))
+                        }
                     }
-                    _ => {
-                        return Err(Error::new(
-                            ErrorKind::InvalidData,
-                            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS and DEFLATE compression.",
-                        ))
+                }
+                3 => {
+                    // floating point
+                    match bits_per_sample[0] {
+                        32 => {
+                            configs.data_type = DataType::F32;
+                        }
+                        64 => {
+                            configs.data_type = DataType::F64;
+                        }
+                        _ => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidData,
+                                "The raster was not read correctly",
+                            ))
+                        }
                     }
                 }
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "The raster was not read correctly",
+                    ))
+                }
+            }
+        }
+        IM_PALETTED => {
+            //ImageMode::Paletted => {
+            configs.photometric_interp = PhotometricInterpretation::Categorical;
+            configs.data_type = DataType::U8;
+        }
+        IM_RGB => {
+            configs.photometric_interp = PhotometricInterpretation::RGB;
+            if bits_per_sample[0] == 8 {
+                configs.data_type = DataType::U8;
+            } else if bits_per_sample[0] == 16 {
+                configs.data_type = DataType::U16;
+            } else {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The raster was not read correctly",
+                ));
+            }
+        }
+        IM_NRGBA | IM_RGBA => {
+            // if bits_per_sample[0] == 8 {
+            //     configs.data_type = DataType::U32;
+            // } else if bits_per_sample[0] == 16 {
+            //     configs.data_type = DataType::U64;
+            // } else {
+            //     return Err(Error::new(
+            //         ErrorKind::InvalidData,
+            //         "The raster was not read correctly",
+            //     ));
+            // }
+            if bits_per_sample[0] == 8 && bits_per_sample.len() == 4 {
+                configs.data_type = DataType::RGBA32;
+            } else if bits_per_sample[0] == 8 && bits_per_sample.len() == 3 {
+                configs.data_type = DataType::RGB24;
+            } else if bits_per_sample[0] == 16 {
+                configs.data_type = DataType::U16;
+            } else {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The raster was not read correctly",
+                ));
             }
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The raster was not read correctly",
+            ))
+        }
+    }
+
+    let predictor = match ifd_map.get(&317) {
+        Some(ifd) => match ifd.interpret_as_u16()[0] {
+            2 => TiffPredictor::Horizontal,
+            3 => TiffPredictor::FloatingPoint,
+            _ => TiffPredictor::None,
+        },
+        _ => TiffPredictor::None,
+    };
+    if predictor != TiffPredictor::None && bits_per_sample.iter().any(|&b| b % 8 != 0) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "The GeoTIFF reader does not support predictors with samples that are not a whole number of bytes.",
+        ));
+    }
 
-            // println!("{:?}", &buf[0..8]);
-            let mut bor = ByteOrderReader::<Cursor<Vec<u8>>>::new(Cursor::new(buf), configs.endian);
+    Ok(TiffLayout {
+        width,
+        height,
+        block_width,
+        block_height,
+        blocks_across,
+        blocks_down,
+        block_padding,
+        block_offsets,
+        block_counts,
+        compression,
+        predictor,
+        bits_per_sample,
+        sample_format,
+        mode,
+        palette,
+        num_bands,
+        num_planes,
+    })
+}
 
-            let xmin = i * block_width;
-            let ymin = j * block_height;
-            let mut xmax = xmin + blk_w;
-            let mut ymax = ymin + blk_h;
+/// Reads and decodes a single strip or tile. The returned values cover the block's
+/// extent within the image (see `TiffLayout::block_extent`) and are stored
+/// band-sequentially for the bands held in the block's plane.
+pub(crate) fn read_block<R: Read + Seek>(
+    th: &mut ByteOrderReader<R>,
+    configs: &RasterConfigs,
+    layout: &TiffLayout,
+    plane: usize,
+    block_row: usize,
+    block_col: usize,
+) -> Result<RasterData, Error> {
+    let (block_width, block_height) = (layout.block_width, layout.block_height);
+    let width = layout.width;
+    let (compression, mode, num_bands) = (layout.compression, layout.mode, layout.num_bands);
+    let bits_per_sample = &layout.bits_per_sample;
+    let sample_format = &layout.sample_format;
+    let palette = &layout.palette;
+    let mut blk_w = block_width;
+    if !layout.block_padding && block_col == layout.blocks_across - 1 && width % block_width != 0 {
+        blk_w = width % block_width;
+    }
+    // For planar data, the block offsets of each band follow those of the previous band.
+    let block_index = (plane * layout.blocks_down + block_row) * layout.blocks_across + block_col;
+    let offset = layout.block_offsets[block_index] as usize;
+    let n = layout.block_counts[block_index] as usize;
+
+    let (ymin, ymax, xmin, xmax) = layout.block_extent(block_row, block_col);
+    let columns = xmax - xmin;
+    let block_size = (ymax - ymin) * columns;
+    let skip_bytes = if xmin + blk_w > width {
+        xmin + blk_w - width
+    } else {
+        0
+    };
+    // The bands held in this block.
+    let (first_band, block_bands) = layout.block_bands(plane);
+    let mut data = RasterData::filled(configs.data_type, 0.0, block_size * block_bands);
+
+    let mut buf: Vec<u8> = vec![];
+    if n != 0 {
+        // it's not a sparse tile
+        match compression {
+            COMPRESS_NONE => {
+                // no compression
+                // buf = vec![0u8; n];
+                buf.reserve_exact(n);
+                unsafe { buf.set_len(n); }
+                th.seek(offset);
+                th.read_exact(&mut buf)?;
+            }
+            COMPRESS_PACKBITS => {
+                // buf = packbits_decoder(th.buffer[offset..(offset + n)].to_vec());
+                let mut b = vec![0u8; n];
+                th.seek(offset);
+                th.read_exact(&mut b).expect("Error reading bytes from file.");
+                buf = packbits_decoder(b);
+            }
+            COMPRESS_LZW => {
+                let mut compressed = vec![0; n];
+                th.seek(offset);
+                th.read_exact(&mut compressed).expect("Error reading bytes from file.");
+                let max_uncompressed_length = block_width * block_height * bits_per_sample.len() * bits_per_sample[0] as usize / 8;
+                buf = Vec::with_capacity(max_uncompressed_length);
+                let mut decoder = lzw::DecoderEarlyChange::new(lzw::MsbReader::new(), 8);
+                let mut bytes_read = 0;
+                while bytes_read < n && buf.len() < max_uncompressed_length {
+                    let (len, bytes) = decoder.decode_bytes(&compressed[bytes_read..]).expect("Error encountered while decoding the LZW compressed GeoTIFF file.");
+                    bytes_read += len;
+                    buf.extend_from_slice(bytes);
+                }
+            }
+            COMPRESS_DEFLATE => {
+                // let mut dec = GzDecoder::new(th.buffer[offset..(offset + n)].to_vec());
+                // let compressed = &th.buffer[offset..(offset + n)];
+                // let mut decoder = Decoder::new(&compressed[..]).unwrap();
+                // decoder.read_to_end(&mut buf).unwrap();
+                th.seek(offset);
+                let mut compressed = vec![0u8; n];
+                th.read_exact(&mut compressed).expect("Error reading bytes from file.");
+                // let mut decoder = Decoder::new(&compressed[..])?;
+                // decoder.read_to_end(&mut buf).unwrap();
+                buf.extend(decompress_to_vec_zlib(&compressed).expect("Error encountered while decoding the DEFLATE compressed GeoTIFF file."));
+            }
+            COMPRESS_ZSTD => {
+                th.seek(offset);
+                let mut compressed = vec![0u8; n];
+                th.read_exact(&mut compressed)?;
+                buf = decompress_zstd(&compressed)?;
+            }
+            COMPRESS_LZMA => {
+                th.seek(offset);
+                let mut compressed = vec![0u8; n];
+                th.read_exact(&mut compressed)?;
+                buf = decompress_lzma(&compressed)?;
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, DEFLATE, ZSTD, and LZMA compression.",
+                ))
+            }
+        }
 
-            xmax = min(xmax, width);
-            ymax = min(ymax, height);
+        // Predictors are applied to the samples of each row of the block before compression.
+        let samples_per_pixel = if layout.num_planes > 1 {
+            1
+        } else {
+            bits_per_sample.len()
+        };
+        undo_predictor(
+            &mut buf,
+            layout.predictor,
+            blk_w,
+            samples_per_pixel,
+            bits_per_sample[0] as usize / 8,
+            configs.endian,
+        );
+    }
 
-            let skip_bytes = if xmin + blk_w > width {
-                xmin + blk_w - width
-            } else {
-                0
-            };
+    // println!("{:?}", &buf[0..8]);
+    let mut bor = ByteOrderReader::<Cursor<Vec<u8>>>::new(Cursor::new(buf), configs.endian);
 
-            let mut off = 0;
-            let mut i: usize;
-            let (mut red, mut green, mut blue): (u32, u32, u32);
-            if n != 0 {
-                match mode {
-                    IM_GRAYINVERT | IM_GRAY => {
-                        match sample_format[0] {
-                            1 => {
-                                // unsigned integer
-                                match bits_per_sample[0] {
-                                    8 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_u8()? as f64;
-                                                    off += 1;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes);
-                                            }
+    let mut off = 0;
+    let mut i: usize;
+    let (mut red, mut green, mut blue): (u32, u32, u32);
+    if n != 0 {
+        match mode {
+            IM_GRAYINVERT | IM_GRAY if num_bands > 1 => {
+                let bytes_per_sample = bits_per_sample[0] as usize / 8;
+                for y in ymin..ymax {
+                    for x in xmin..xmax {
+                        i = (y - ymin) * columns + x - xmin;
+                        for b in first_band..first_band + block_bands {
+                            data.set_value(
+                                (b - first_band) * block_size + i,
+                                read_sample(&mut bor, sample_format[0], bits_per_sample[0])?,
+                            );
+                        }
+                    }
+                    if skip_bytes > 0 {
+                        bor.inc_pos(skip_bytes * block_bands * bytes_per_sample);
+                    }
+                }
+            }
+            IM_GRAYINVERT | IM_GRAY => {
+                match sample_format[0] {
+                    1 => {
+                        // unsigned integer
+                        match bits_per_sample[0] {
+                            8 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_u8()? as f64);
+                                            off += 1;
                                         }
                                     }
-                                    16 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_u16()? as f64;
-                                                    off += 2;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 2);
-                                            }
-                                        }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes);
                                     }
-                                    32 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_u32()? as f64;
-                                                    off += 4;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 4);
-                                            }
+                                }
+                            }
+                            16 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_u16()? as f64);
+                                            off += 2;
                                         }
                                     }
-                                    64 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_u64()? as f64;
-                                                    off += 8;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 8);
-                                            }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 2);
+                                    }
+                                }
+                            }
+                            32 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_u32()? as f64);
+                                            off += 4;
                                         }
                                     }
-                                    _ => {
-                                        return Err(Error::new(
-                                            ErrorKind::InvalidData,
-                                            "The raster was not read correctly",
-                                        ))
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 4);
                                     }
                                 }
                             }
-                            2 => {
-                                // signed integer
-                                match bits_per_sample[0] {
-                                    8 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_i8()? as f64;
-                                                    off += 1;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes);
-                                            }
+                            64 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_u64()? as f64);
+                                            off += 8;
                                         }
                                     }
-                                    16 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_i16()? as f64;
-                                                    off += 2;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 2);
-                                            }
-                                        }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 8);
                                     }
-                                    32 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_i32()? as f64;
-                                                    off += 4;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 4);
-                                            }
+                                }
+                            }
+                            _ => {
+                                return Err(Error::new(
+                                    ErrorKind::InvalidData,
+                                    "The raster was not read correctly",
+                                ))
+                            }
+                        }
+                    }
+                    2 => {
+                        // signed integer
+                        match bits_per_sample[0] {
+                            8 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_i8()? as f64);
+                                            off += 1;
                                         }
                                     }
-                                    64 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_i64()? as f64;
-                                                    off += 8;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 8);
-                                            }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes);
+                                    }
+                                }
+                            }
+                            16 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_i16()? as f64);
+                                            off += 2;
                                         }
                                     }
-                                    _ => {
-                                        return Err(Error::new(
-                                            ErrorKind::InvalidData,
-                                            "The raster was not read correctly",
-                                        ))
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 2);
                                     }
                                 }
                             }
-                            3 => {
-                                // floating point
-                                match bits_per_sample[0] {
-                                    32 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                i = y * width + x;
-                                                data[i] = bor.read_f32()? as f64;
-                                                off += 4;
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 4);
-                                            }
+                            32 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_i32()? as f64);
+                                            off += 4;
                                         }
                                     }
-                                    64 => {
-                                        for y in ymin..ymax {
-                                            for x in xmin..xmax {
-                                                if off <= bor.len() {
-                                                    i = y * width + x;
-                                                    data[i] = bor.read_f64()?;
-                                                    off += 8;
-                                                }
-                                            }
-                                            if skip_bytes > 0 {
-                                                bor.inc_pos(skip_bytes * 8);
-                                            }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 4);
+                                    }
+                                }
+                            }
+                            64 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_i64()? as f64);
+                                            off += 8;
                                         }
                                     }
-                                    _ => {
-                                        return Err(Error::new(
-                                            ErrorKind::InvalidData,
-                                            "The raster was not read correctly",
-                                        ))
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 8);
                                     }
                                 }
                             }
@@ -1385,94 +1677,41 @@ pub fn read_geotiff<'a>(
                             }
                         }
                     }
-                    IM_PALETTED => {
-                        let mut value: usize;
-                        for y in ymin..ymax {
-                            for x in xmin..xmax {
-                                i = y * width + x;
-                                value = bor.read_u8()? as usize;
-                                data[i] = palette[value] as f64;
-                            }
-                        }
-                    }
-                    IM_RGB => {
-                        let mut value: u32;
-                        let mut a: u32;
-                        if bits_per_sample[0] == 8 {
-                            for y in ymin..ymax {
-                                for x in xmin..xmax {
-                                    red = bor.read_u8()? as u32; //uint32(g.buf[g.off]);
-                                    green = bor.read_u8()? as u32; //uint32(g.buf[g.off+1]);
-                                    blue = bor.read_u8()? as u32; //uint32(g.buf[g.off+2]);
-                                    a = 255u32;
-                                    value = (a << 24) | (blue << 16) | (green << 8) | red;
-                                    i = y * width + x;
-                                    data[i] = value as f64;
-                                }
-                            }
-                        } else if bits_per_sample[0] == 16 {
-                            // the spec doesn't talk about 16-bit RGB images so
-                            // I'm not sure why I bother with this. They specifically
-                            // say that RGB images are 8-bits per channel. Anyhow,
-                            // I rescale the 16-bits to an 8-bit channel for simplicity.
-                            let mut value: u32;
-                            let mut a: u32;
-                            for y in ymin..ymax {
-                                for x in xmin..xmax {
-                                    red = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    green = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    blue = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    a = 255u32;
-                                    value = (a << 24) | (blue << 16) | (green << 8) | red;
-                                    i = y * width + x;
-                                    data[i] = value as f64;
+                    3 => {
+                        // floating point
+                        match bits_per_sample[0] {
+                            32 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        i = (y - ymin) * columns + x - xmin;
+                                        data.set_value(i, bor.read_f32()? as f64);
+                                        off += 4;
+                                    }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 4);
+                                    }
                                 }
                             }
-                        } else {
-                            return Err(Error::new(
-                                ErrorKind::InvalidData,
-                                "The raster was not read correctly",
-                            ));
-                        }
-                    }
-                    IM_NRGBA | IM_RGBA => {
-                        let mut value: u32;
-                        let mut a: u32;
-                        if bits_per_sample[0] == 8 {
-                            for y in ymin..ymax {
-                                for x in xmin..xmax {
-                                    red = bor.read_u8()? as u32; //uint32(g.buf[g.off]);
-                                    green = bor.read_u8()? as u32; //uint32(g.buf[g.off+1]);
-                                    blue = bor.read_u8()? as u32; //uint32(g.buf[g.off+2]);
-                                    a = bor.read_u8()? as u32;
-                                    value = (a << 24) | (blue << 16) | (green << 8) | red;
-                                    i = y * width + x;
-                                    data[i] = value as f64;
+                            64 => {
+                                for y in ymin..ymax {
+                                    for x in xmin..xmax {
+                                        if off <= bor.len() {
+                                            i = (y - ymin) * columns + x - xmin;
+                                            data.set_value(i, bor.read_f64()?);
+                                            off += 8;
+                                        }
+                                    }
+                                    if skip_bytes > 0 {
+                                        bor.inc_pos(skip_bytes * 8);
+                                    }
                                 }
                             }
-                        } else if bits_per_sample[0] == 16 {
-                            // the spec doesn't talk about 16-bit RGB images so
-                            // I'm not sure why I bother with this. They specifically
-                            // say that RGB images are 8-bits per channel. Anyhow,
-                            // I rescale the 16-bits to an 8-bit channel for simplicity.
-                            let mut value: u32;
-                            let mut a: u32;
-                            for y in ymin..ymax {
-                                for x in xmin..xmax {
-                                    red = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    green = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    blue = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    a = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
-                                    value = (a << 24) | (blue << 16) | (green << 8) | red;
-                                    i = y * width + x;
-                                    data[i] = value as f64;
-                                }
+                            _ => {
+                                return Err(Error::new(
+                                    ErrorKind::InvalidData,
+                                    "The raster was not read correctly",
+                                ))
                             }
-                        } else {
-                            return Err(Error::new(
-                                ErrorKind::InvalidData,
-                                "The raster was not read correctly",
-                            ));
                         }
                     }
                     _ => {
@@ -1482,224 +1721,177 @@ pub fn read_geotiff<'a>(
                         ))
                     }
                 }
-            } else {
-                // GDAL supports sparse tiles. That is, if the block count is zero,
-                // instead of reading the block, simply assume it is filled with either
-                // nodata, if the value is defined, or zeros otherwise.
+            }
+            IM_PALETTED => {
+                let mut value: usize;
                 for y in ymin..ymax {
                     for x in xmin..xmax {
-                        i = y * width + x;
-                        data[i] = configs.nodata;
+                        i = (y - ymin) * columns + x - xmin;
+                        value = bor.read_u8()? as usize;
+                        data.set_value(i, palette[value] as f64);
                     }
                 }
             }
-
-            match mode {
-                IM_GRAYINVERT | IM_GRAY => {
-                    //ImageMode::GrayInvert | ImageMode::Gray => {
-                    configs.photometric_interp = PhotometricInterpretation::Continuous;
-                    match sample_format[0] {
-                        1 => {
-                            // unsigned integer
-                            match bits_per_sample[0] {
-                                8 => {
-                                    configs.data_type = DataType::U8;
-                                }
-                                16 => {
-                                    configs.data_type = DataType::U16;
-                                }
-                                32 => {
-                                    configs.data_type = DataType::U32;
-                                }
-                                64 => {
-                                    configs.data_type = DataType::U64;
-                                }
-                                _ => {
-                                    return Err(Error::new(
-                                        ErrorKind::InvalidData,
-                                        "The raster was not read correctly",
-                                    ))
-                                }
-                            }
-                        }
-                        2 => {
-                            // signed integer
-                            match bits_per_sample[0] {
-                                8 => {
-                                    configs.data_type = DataType::I8;
-                                }
-                                16 => {
-                                    configs.data_type = DataType::I16;
-                                }
-                                32 => {
-                                    configs.data_type = DataType::I32;
-                                }
-                                64 => {
-                                    configs.data_type = DataType::I64;
-                                }
-                                _ => {
-                                    return Err(Error::new(
-                                        ErrorKind::InvalidData,
-                                        "The raster was not read correctly",
-                                    ))
-                                }
-                            }
-                        }
-                        3 => {
-                            // floating point
-                            match bits_per_sample[0] {
-                                32 => {
-                                    configs.data_type = DataType::F32;
-                                }
-                                64 => {
-                                    configs.data_type = DataType::F64;
-                                }
-                                _ => {
-                                    return Err(Error::new(
-                                        ErrorKind::InvalidData,
-                                        "The raster was not read correctly",
-                                    ))
-                                }
-                            }
+            IM_RGB => {
+                let mut value: u32;
+                let mut a: u32;
+                if bits_per_sample[0] == 8 {
+                    for y in ymin..ymax {
+                        for x in xmin..xmax {
+                            red = bor.read_u8()? as u32; //uint32(g.buf[g.off]);
+                            green = bor.read_u8()? as u32; //uint32(g.buf[g.off+1]);
+                            blue = bor.read_u8()? as u32; //uint32(g.buf[g.off+2]);
+                            a = 255u32;
+                            value = (a << 24) | (blue << 16) | (green << 8) | red;
+                            i = (y - ymin) * columns + x - xmin;
+                            data.set_value(i, value as f64);
                         }
-                        _ => {
-                            return Err(Error::new(
-                                ErrorKind::InvalidData,
-                                "The raster was not read correctly",
-                            ))
+                    }
+                } else if bits_per_sample[0] == 16 {
+                    // the spec doesn't talk about 16-bit RGB images so
+                    // I'm not sure why I bother with this. They specifically
+                    // say that RGB images are 8-bits per channel. Anyhow,
+                    // I rescale the 16-bits to an 8-bit channel for simplicity.
+                    let mut value: u32;
+                    let mut a: u32;
+                    for y in ymin..ymax {
+                        for x in xmin..xmax {
+                            red = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            green = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            blue = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            a = 255u32;
+                            value = (a << 24) | (blue << 16) | (green << 8) | red;
+                            i = (y - ymin) * columns + x - xmin;
+                            data.set_value(i, value as f64);
                         }
                     }
+                } else {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "The raster was not read correctly",
+                    ));
                 }
-                IM_PALETTED => {
-                    //ImageMode::Paletted => {
-                    configs.photometric_interp = PhotometricInterpretation::Categorical;
-                    configs.data_type = DataType::U8;
-                }
-                IM_RGB => {
-                    configs.photometric_interp = PhotometricInterpretation::RGB;
-                    if bits_per_sample[0] == 8 {
-                        configs.data_type = DataType::U8;
-                    } else if bits_per_sample[0] == 16 {
-                        configs.data_type = DataType::U16;
-                    } else {
-                        return Err(Error::new(
-                            ErrorKind::InvalidData,
-                            "The raster was not read correctly",
-                        ));
+            }
+            IM_NRGBA | IM_RGBA => {
+                let mut value: u32;
+                let mut a: u32;
+                if bits_per_sample[0] == 8 {
+                    for y in ymin..ymax {
+                        for x in xmin..xmax {
+                            red = bor.read_u8()? as u32; //uint32(g.buf[g.off]);
+                            green = bor.read_u8()? as u32; //uint32(g.buf[g.off+1]);
+                            blue = bor.read_u8()? as u32; //uint32(g.buf[g.off+2]);
+                            a = bor.read_u8()? as u32;
+                            value = (a << 24) | (blue << 16) | (green << 8) | red;
+                            i = (y - ymin) * columns + x - xmin;
+                            data.set_value(i, value as f64);
+                        }
                     }
-                }
-                IM_NRGBA | IM_RGBA => {
-                    // if bits_per_sample[0] == 8 {
-                    //     configs.data_type = DataType::U32;
-                    // } else if bits_per_sample[0] == 16 {
-                    //     configs.data_type = DataType::U64;
-                    // } else {
-                    //     return Err(Error::new(
-                    //         ErrorKind::InvalidData,
-                    //         "The raster was not read correctly",
-                    //     ));
-                    // }
-                    if bits_per_sample[0] == 8 && bits_per_sample.len() == 4 {
-                        configs.data_type = DataType::RGBA32;
-                    } else if bits_per_sample[0] == 8 && bits_per_sample.len() == 3 {
-                        configs.data_type = DataType::RGB24;
-                    } else if bits_per_sample[0] == 16 {
-                        configs.data_type = DataType::U16;
-                    } else {
-                        return Err(Error::new(
-                            ErrorKind::InvalidData,
-                            "The raster was not read correctly",
-                        ));
+                } else if bits_per_sample[0] == 16 {
+                    // the spec doesn't talk about 16-bit RGB images so
+                    // I'm not sure why I bother with this. They specifically
+                    // say that RGB images are 8-bits per channel. Anyhow,
+                    // I rescale the 16-bits to an 8-bit channel for simplicity.
+                    let mut value: u32;
+                    let mut a: u32;
+                    for y in ymin..ymax {
+                        for x in xmin..xmax {
+                            red = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            green = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            blue = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            a = (bor.read_u16()? as f64 / 65535f64 * 255f64) as u32;
+                            value = (a << 24) | (blue << 16) | (green << 8) | red;
+                            i = (y - ymin) * columns + x - xmin;
+                            data.set_value(i, value as f64);
+                        }
                     }
-                }
-                _ => {
+                } else {
                     return Err(Error::new(
                         ErrorKind::InvalidData,
                         "The raster was not read correctly",
-                    ))
+                    ));
                 }
             }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The raster was not read correctly",
+                ))
+            }
         }
-    }
-
-    // Check to see if a predictor is used with LZW and DEFLATE
-    match ifd_map.get(&317) {
-        Some(ifd) => {
-            if ifd.interpret_as_u16()[0] == 2 {
-                // Horizontal predictor
-                // transform the data
-                let mut idx: usize;
-                for row in 0..configs.rows {
-                    for col in 1..configs.columns {
-                        idx = row * configs.columns + col;
-                        data[idx] += data[idx - 1];
-                    }
+    } else {
+        // GDAL supports sparse tiles. That is, if the block count is zero,
+        // instead of reading the block, simply assume it is filled with either
+        // nodata, if the value is defined, or zeros otherwise.
+        for y in ymin..ymax {
+            for x in xmin..xmax {
+                i = (y - ymin) * columns + x - xmin;
+                for b in first_band..first_band + block_bands {
+                    data.set_value((b - first_band) * block_size + i, configs.nodata);
                 }
             }
-            // if ifd.interpret_as_u16()[0] == 3 {
-            //     if configs.endian == Endianness::LittleEndian {
-            //         if configs.data_type == DataType::F32 {
-            //             let mut in_val: f32;
-            //             let mut out_val: f32;
-            //             let mut in_bytes: [u8; 4];
-            //             let mut out_bytes = [0u8; 4];
-            //             let mut idx: usize;
-            //             for row in 0..configs.rows {
-            //                 for col in 1..configs.columns {
-            //                     idx = row * configs.columns + col;
-            //                     in_val = data[idx] as f32;
-            //                     in_bytes = in_val.to_le_bytes();
-            //                     for b in 0..4 {
-            //                         out_bytes[4-b-1] = in_bytes[b];
-            //                     }
-            //                     out_val = f32::from_le_bytes(out_bytes);
-            //                     data[idx] = out_val as f64;
-            //                 }
-            //             }
-            //         } else { // F64
-
-            //         }
-            //     } else {
-            //         if configs.data_type == DataType::F32 {
-
-            //         } else { // F64
-
-            //         }
-            //     }
-
-            if ifd.interpret_as_u16()[0] == 3 {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "The GeoTIFF reader does not currently support floating-point predictors (PREDICTOR=3).",
-                ));
-            }
         }
-        _ => {} // do nothing,
     }
 
-    // match geokeys_map.get(&1024) {
-    //     Some(ifd) => geokeys.add_key_directory(&ifd.data),
-    //     _ => return Err(Error::new(ErrorKind::InvalidData, "The TIFF file does not contain geokeys")),
-    // };
-
-    let mut map_sorter = vec![];
-    for (key, _) in ifd_map.iter() {
-        map_sorter.push(key);
-    }
-    map_sorter.sort();
+    Ok(data)
+}
 
-    map_sorter.clear();
-    for (key, _) in geokeys_map.iter() {
-        map_sorter.push(key);
-    }
+/// Reads a single sample value of the specified SampleFormat and BitsPerSample.
+fn read_sample<R: Read + Seek>(
+    bor: &mut ByteOrderReader<R>,
+    sample_format: u16,
+    bits_per_sample: u16,
+) -> Result<f64, Error> {
+    let value = match (sample_format, bits_per_sample) {
+        // unsigned integer; the SampleFormat tag is optional and defaults to unsigned
+        (0, 8) | (1, 8) => bor.read_u8()? as f64,
+        (0, 16) | (1, 16) => bor.read_u16()? as f64,
+        (0, 32) | (1, 32) => bor.read_u32()? as f64,
+        (0, 64) | (1, 64) => bor.read_u64()? as f64,
+        // signed integer
+        (2, 8) => bor.read_i8()? as f64,
+        (2, 16) => bor.read_i16()? as f64,
+        (2, 32) => bor.read_i32()? as f64,
+        (2, 64) => bor.read_i64()? as f64,
+        // floating point
+        (3, 32) => bor.read_f32()? as f64,
+        (3, 64) => bor.read_f64()?,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The raster was not read correctly",
+            ))
+        }
+    };
+    Ok(value)
+}
 
-    Ok(())
+/// The arrangement of the bands of a multi-band GeoTIFF (the PlanarConfiguration tag).
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum TiffPlanarConfig {
+    /// The bands of each pixel are stored together (PlanarConfiguration = 1).
+    Chunky,
+    /// Each band is stored in a separate plane (PlanarConfiguration = 2).
+    #[default]
+    Planar,
 }
 
 pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
-    // We'll need to look at the configurations to see if compression should be used
-    let configs = whitebox_common::configs::get_configs()?;
-    let use_compression = configs.compress_rasters;
+    // Cloud-Optimized GeoTIFFs are written with internal tiles and overviews.
+    if let Some(options) = r.configs.cog {
+        return cog::write_cog(r, &options);
+    }
+
+    if r.configs.planar_config == TiffPlanarConfig::Chunky
+        && r.num_bands() > 1
+        && r.configs.photometric_interp != PhotometricInterpretation::RGB
+    {
+        return write_chunky_geotiff(r);
+    }
+
+    // The compression method and predictor are specified by the configs or the settings.
+    let encoder = BlockEncoder::new(&r.configs, r.configs.columns)?;
+    let use_compression = encoder.is_compressed();
 
     
     // get the ByteOrderWriter
@@ -1720,9 +1912,18 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         ));
     }
 
+    // Multi-band rasters are written with each band in a separate plane (PlanarConfiguration = 2),
+    // with one strip per row of each band.
+    let num_bands = if r.configs.photometric_interp == PhotometricInterpretation::RGB {
+        1
+    } else {
+        r.configs.bands.max(1) as usize
+    };
+    let num_strips = r.configs.rows * num_bands;
+
     // is it a BigTiff?
     let is_big_tiff = if 8usize
-        + (r.configs.rows * r.configs.columns) as usize * total_bytes_per_pixel
+        + num_strips * r.configs.columns * total_bytes_per_pixel
         >= 4_000_000_000
     {
         true
@@ -1732,19 +1933,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
 
     let header_size = if !is_big_tiff { 8u64 } else { 16u64 };
 
-    // get the offset to the first ifd
-    let mut ifd_start_needs_extra_byte = false;
-    let mut ifd_start = if !use_compression {
-        let mut val = header_size
-            + (r.configs.rows * r.configs.columns) as u64 * total_bytes_per_pixel as u64;
-        if val % 2 == 1 {
-            val += 1;
-            ifd_start_needs_extra_byte = true;
-        }
-        val
-    } else {
-        0u64
-    };
+    // The offset to the first IFD isn't known until the image data have been written
+    // and so it is initially left as zero.
+    let ifd_start = 0u64;
 
     //////////////////////
     // Write the header //
@@ -1786,8 +1977,6 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     let mut strip_byte_counts = vec![];
     let mut current_offset = header_size;
     if use_compression {
-        // DEFLATE is the only supported compression method at present
-
         // let mut current_offset = header_size;
         let mut row_length_in_bytes: u64;
         match r.configs.photometric_interp {
@@ -1796,23 +1985,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             | PhotometricInterpretation::Boolean => match r.configs.data_type {
                 DataType::F64 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 8);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_f64::<LittleEndian>(r.data[i] as f64)
+                                data.write_f64::<LittleEndian>(r.data.get_value(i) as f64)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_f64::<BigEndian>(r.data[i] as f64)
+                                data.write_f64::<BigEndian>(r.data.get_value(i) as f64)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -1828,23 +2017,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::F32 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 4);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_f32::<LittleEndian>(r.data[i] as f32)
+                                data.write_f32::<LittleEndian>(r.data.get_value(i) as f32)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_f32::<BigEndian>(r.data[i] as f32)
+                                data.write_f32::<BigEndian>(r.data.get_value(i) as f32)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -1860,23 +2049,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::U64 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 8);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u64::<LittleEndian>(r.data[i] as u64)
+                                data.write_u64::<LittleEndian>(r.data.get_value(i) as u64)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u64::<BigEndian>(r.data[i] as u64)
+                                data.write_u64::<BigEndian>(r.data.get_value(i) as u64)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -1892,23 +2081,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::U32 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 4);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u32::<LittleEndian>(r.data[i] as u32)
+                                data.write_u32::<LittleEndian>(r.data.get_value(i) as u32)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u32::<BigEndian>(r.data[i] as u32)
+                                data.write_u32::<BigEndian>(r.data.get_value(i) as u32)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -1924,23 +2113,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::U16 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 2);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u16::<LittleEndian>(r.data[i] as u16)
+                                data.write_u16::<LittleEndian>(r.data.get_value(i) as u16)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u16::<BigEndian>(r.data[i] as u16)
+                                data.write_u16::<BigEndian>(r.data.get_value(i) as u16)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -1956,23 +2145,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::U8 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u8(r.data[i] as u8)
+                                data.write_u8(r.data.get_value(i) as u8)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_u8(r.data[i] as u8)
+                                data.write_u8(r.data.get_value(i) as u8)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -1988,23 +2177,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::I64 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 8);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i64::<LittleEndian>(r.data[i] as i64)
+                                data.write_i64::<LittleEndian>(r.data.get_value(i) as i64)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i64::<BigEndian>(r.data[i] as i64)
+                                data.write_i64::<BigEndian>(r.data.get_value(i) as i64)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -2020,23 +2209,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::I32 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 4);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i32::<LittleEndian>(r.data[i] as i32)
+                                data.write_i32::<LittleEndian>(r.data.get_value(i) as i32)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i32::<BigEndian>(r.data[i] as i32)
+                                data.write_i32::<BigEndian>(r.data.get_value(i) as i32)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -2052,23 +2241,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::I16 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns * 2);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i16::<LittleEndian>(r.data[i] as i16)
+                                data.write_i16::<LittleEndian>(r.data.get_value(i) as i16)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i16::<BigEndian>(r.data[i] as i16)
+                                data.write_i16::<BigEndian>(r.data.get_value(i) as i16)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -2084,23 +2273,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 }
                 DataType::I8 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         let mut data = Vec::with_capacity(r.configs.columns);
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i8(r.data[i] as i8)
+                                data.write_i8(r.data.get_value(i) as i8)
                                     .expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_i8(r.data[i] as i8)
+                                data.write_i8(r.data.get_value(i) as i8)
                                     .expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
+                        let compressed = encoder.encode(data)?;
                         write_bytes(&mut writer, &compressed)
                             .expect("Error writing byte data to file.");
                         row_length_in_bytes = compressed.len() as u64;
@@ -2130,11 +2319,11 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         // let mut bytes: [u8; 3] = [0u8; 3];
                         let mut i: usize;
                         let mut val: u32;
-                        for row in 0..r.configs.rows {
+                        for row in 0..num_strips {
                             let mut data = Vec::with_capacity(r.configs.columns * 3);
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                val = r.data[i] as u32;
+                                val = r.data.get_value(i) as u32;
                                 data.write_u8((val & 0xFF) as u8)
                                     .expect("Error writing byte data."); // red
 
@@ -2145,7 +2334,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                                     .expect("Error writing byte data."); // blue
                             }
                             // compress the data vec
-                            let compressed = compress_to_vec_zlib(&data, 6);
+                            let compressed = encoder.encode(data)?;
                             write_bytes(&mut writer, &compressed)
                                 .expect("Error writing byte data to file.");
                             row_length_in_bytes = compressed.len() as u64;
@@ -2163,11 +2352,11 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         let mut i: usize;
                         // let mut bytes: [u8; 4] = [0u8; 4];
                         let mut val: u32;
-                        for row in 0..r.configs.rows {
+                        for row in 0..num_strips {
                             let mut data = Vec::with_capacity(r.configs.columns * 4);
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                val = r.data[i] as u32;
+                                val = r.data.get_value(i) as u32;
                                 data.write_u8((val & 0xFF) as u8)
                                     .expect("Error writing byte data."); // red
 
@@ -2182,7 +2371,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                             }
                             // for col in 0..r.configs.columns {
                             //     i = row * r.configs.columns + col;
-                            //     val = r.data[i] as u32;
+                            //     val = r.data.get_value(i) as u32;
                             //     bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                             //     bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                             //     bytes[0] = (val & 0xFF) as u8; // red
@@ -2191,7 +2380,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                             // }
 
                             // compress the data vec
-                            let compressed = compress_to_vec_zlib(&data, 6);
+                            let compressed = encoder.encode(data)?;
                             write_bytes(&mut writer, &compressed)
                                 .expect("Error writing byte data to file.");
                             row_length_in_bytes = compressed.len() as u64;
@@ -2236,91 +2425,91 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             | PhotometricInterpretation::Boolean => match r.configs.data_type {
                 DataType::F64 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_f64(&mut writer, r.configs.endian, r.data[i])?;
+                            write_f64(&mut writer, r.configs.endian, r.data.get_value(i))?;
                         }
                     }
                 }
                 DataType::F32 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_f32(&mut writer, r.configs.endian, r.data[i] as f32)?;
+                            write_f32(&mut writer, r.configs.endian, r.data.get_value(i) as f32)?;
                         }
                     }
                 }
                 DataType::U64 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_u64(&mut writer, r.configs.endian, r.data[i] as u64)?;
+                            write_u64(&mut writer, r.configs.endian, r.data.get_value(i) as u64)?;
                         }
                     }
                 }
                 DataType::U32 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_u32(&mut writer, r.configs.endian, r.data[i] as u32)?;
+                            write_u32(&mut writer, r.configs.endian, r.data.get_value(i) as u32)?;
                         }
                     }
                 }
                 DataType::U16 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_u16(&mut writer, r.configs.endian, r.data[i] as u16)?;
+                            write_u16(&mut writer, r.configs.endian, r.data.get_value(i) as u16)?;
                         }
                     }
                 }
                 DataType::U8 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_u8(&mut writer, r.data[i] as u8)?;
+                            write_u8(&mut writer, r.data.get_value(i) as u8)?;
                         }
                     }
                 }
                 DataType::I64 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_i64(&mut writer, r.configs.endian, r.data[i] as i64)?;
+                            write_i64(&mut writer, r.configs.endian, r.data.get_value(i) as i64)?;
                         }
                     }
                 }
                 DataType::I32 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_i32(&mut writer, r.configs.endian, r.data[i] as i32)?;
+                            write_i32(&mut writer, r.configs.endian, r.data.get_value(i) as i32)?;
                         }
                     }
                 }
                 DataType::I16 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_i16(&mut writer, r.configs.endian, r.data[i] as i16)?;
+                            write_i16(&mut writer, r.configs.endian, r.data.get_value(i) as i16)?;
                         }
                     }
                 }
                 DataType::I8 => {
                     let mut i: usize;
-                    for row in 0..r.configs.rows {
+                    for row in 0..num_strips {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_i8(&mut writer, r.data[i] as i8)?;
+                            write_i8(&mut writer, r.data.get_value(i) as i8)?;
                         }
                     }
                 }
@@ -2339,10 +2528,10 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     DataType::RGB24 => {
                         let mut bytes: [u8; 3] = [0u8; 3];
                         let mut i: usize;
-                        for row in 0..r.configs.rows {
+                        for row in 0..num_strips {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
+                                let val = r.data.get_value(i) as u32;
                                 bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                                 bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                                 bytes[0] = (val & 0xFF) as u8; // red
@@ -2353,10 +2542,10 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     DataType::RGBA32 | DataType::U32 => {
                         let mut i: usize;
                         let mut bytes: [u8; 4] = [0u8; 4];
-                        for row in 0..r.configs.rows {
+                        for row in 0..num_strips {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
+                                let val = r.data.get_value(i) as u32;
                                 bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                                 bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                                 bytes[0] = (val & 0xFF) as u8; // red
@@ -2391,28 +2580,190 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
-    if use_compression {
-        ifd_start = current_offset; // header_size + strip_byte_counts.iter().sum();
-        if ifd_start % 2 == 1 {
-            ifd_start += 1;
-            ifd_start_needs_extra_byte = true;
+    if !use_compression {
+        let row_length_in_bytes = (r.configs.columns * total_bytes_per_pixel) as u64;
+        for i in 0..num_strips as u64 {
+            strip_offsets.push(header_size + row_length_in_bytes * i);
+            strip_byte_counts.push(row_length_in_bytes);
         }
-        if !is_big_tiff {
-            let _ = writer.seek(SeekFrom::Start(4));
-            write_u32(&mut writer, r.configs.endian, ifd_start as u32)?;
-        } else {
-            let _ = writer.seek(SeekFrom::Start(8));
-            write_u64(&mut writer, r.configs.endian, ifd_start)?;
+        current_offset = header_size + row_length_in_bytes * num_strips as u64;
+    }
+
+    write_geotiff_ifd(
+        &mut writer,
+        &r.configs,
+        &encoder,
+        is_big_tiff,
+        BlockLayout::Strips,
+        current_offset,
+        strip_offsets,
+        strip_byte_counts,
+    )
+}
+
+/// Writes a multi-band raster with the bands of each pixel interleaved (PlanarConfiguration
+/// = 1), with one strip per row.
+fn write_chunky_geotiff(r: &mut Raster) -> Result<(), Error> {
+    if r.configs.data_type.get_data_size() == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Unknown data type: {:?}. Photomet interp: {:?}",
+                r.configs.data_type, r.configs.photometric_interp
+            ),
+        ));
+    }
+    if r.configs.photometric_interp == PhotometricInterpretation::Categorical
+        || r.configs.photometric_interp == PhotometricInterpretation::Paletted
+    {
+        r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    }
+    let num_bands = r.num_bands();
+    let (rows, columns) = (r.configs.rows, r.configs.columns);
+    let encoder = BlockEncoder::new(&r.configs, columns)?.with_interleaved_bands(num_bands);
+    let row_length = columns * num_bands * r.configs.data_type.get_data_size();
+    let is_big_tiff = 8usize + rows * row_length >= 4_000_000_000;
+
+    let f = File::create(r.file_name.clone())?;
+    let mut writer = BufWriter::new(f);
+    if r.configs.endian == Endianness::LittleEndian {
+        write_bytes(&mut writer, "II".as_bytes())?;
+    } else {
+        write_bytes(&mut writer, "MM".as_bytes())?;
+    }
+    // The offset to the first IFD is written once the image data are complete.
+    if !is_big_tiff {
+        write_u16(&mut writer, r.configs.endian, 42u16)?;
+        write_u32(&mut writer, r.configs.endian, 0u32)?;
+    } else {
+        write_u16(&mut writer, r.configs.endian, 43u16)?;
+        write_u16(&mut writer, r.configs.endian, 8u16)?;
+        write_u16(&mut writer, r.configs.endian, 0u16)?;
+        write_u64(&mut writer, r.configs.endian, 0u64)?;
+    }
+
+    let num_cells = rows * columns;
+    let mut strip_offsets = Vec::with_capacity(rows);
+    let mut strip_byte_counts = Vec::with_capacity(rows);
+    let mut current_offset = if is_big_tiff { 16u64 } else { 8u64 };
+    for row in 0..rows {
+        let mut bytes =
+            ByteOrderWriter::<Vec<u8>>::new(Vec::with_capacity(row_length), r.configs.endian);
+        for col in 0..columns {
+            for band in 0..num_bands {
+                let z = r.data.get_value(band * num_cells + row * columns + col);
+                write_cell_value(&mut bytes, r.configs.data_type, z)?;
+            }
+        }
+        let strip = encoder.encode(bytes.into_inner())?;
+        write_bytes(&mut writer, &strip)?;
+        strip_offsets.push(current_offset);
+        strip_byte_counts.push(strip.len() as u64);
+        current_offset += strip.len() as u64;
+        if encoder.is_compressed() && strip.len() % 2 != 0 {
+            // Compressed strips must start on a word (i.e. an even offset).
+            write_u8(&mut writer, 0u8)?;
+            current_offset += 1;
         }
-        let _ = writer.seek(SeekFrom::End(0));
     }
 
+    write_geotiff_ifd(
+        &mut writer,
+        &r.configs,
+        &encoder,
+        is_big_tiff,
+        BlockLayout::ChunkyStrips,
+        current_offset,
+        strip_offsets,
+        strip_byte_counts,
+    )?;
+    writer.flush()
+}
+
+/// Writes the image file directory (IFD) of a GeoTIFF file whose image data, laid out in
+/// strips as described by `layout`, have already been written. `data_end` is the file
+/// offset immediately following the last strip.
+fn write_geotiff_ifd(
+    writer: &mut BufWriter<File>,
+    configs: &RasterConfigs,
+    encoder: &BlockEncoder,
+    is_big_tiff: bool,
+    layout: BlockLayout,
+    data_end: u64,
+    strip_offsets: Vec<u64>,
+    strip_byte_counts: Vec<u64>,
+) -> Result<(), Error> {
+    let mut ifd_start = data_end;
+    let ifd_start_needs_extra_byte = ifd_start % 2 == 1;
+    if ifd_start_needs_extra_byte {
+        ifd_start += 1;
+    }
+    if !is_big_tiff {
+        let _ = writer.seek(SeekFrom::Start(4));
+        write_u32(writer, configs.endian, ifd_start as u32)?;
+    } else {
+        let _ = writer.seek(SeekFrom::Start(8));
+        write_u64(writer, configs.endian, ifd_start)?;
+    }
+    let _ = writer.seek(SeekFrom::End(0));
+
     // This is just because the IFD must start on a word (i.e. an even value). If the data are
     // single bytes, then this may not be the case.
     if ifd_start_needs_extra_byte {
-        write_u8(&mut writer, 0u8).expect("Error writing byte data.");
+        write_u8(writer, 0u8).expect("Error writing byte data.");
     }
 
+    let (ifd_entries, larger_values_data) = geotiff_ifd_entries(
+        configs,
+        encoder,
+        is_big_tiff,
+        layout,
+        false,
+        &strip_offsets,
+        &strip_byte_counts,
+    )?;
+    write_ifd(
+        writer,
+        configs.endian,
+        is_big_tiff,
+        ifd_start,
+        ifd_entries,
+        &larger_values_data,
+        0u64,
+    )
+}
+
+/// The organization of the image data referenced by an IFD.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BlockLayout {
+    /// A single strip per row of each band.
+    Strips,
+    /// A single strip per row, with the bands of each pixel interleaved.
+    ChunkyStrips,
+    /// Square tiles with the given width and length, band by band.
+    Tiles(usize),
+}
+
+/// Creates the entries of the IFD describing an image with the dimensions, data type and
+/// georeferencing of `configs`, returning the entries and the data of any values that are
+/// too large to be stored within the entries. `block_offsets` and `block_byte_counts` locate
+/// the strips or tiles of the image. Overviews (reduced-resolution images) are flagged with
+/// the NewSubfileType tag and, following GDAL's convention, carry no georeferencing tags.
+fn geotiff_ifd_entries(
+    configs: &RasterConfigs,
+    encoder: &BlockEncoder,
+    is_big_tiff: bool,
+    layout: BlockLayout,
+    is_overview: bool,
+    block_offsets: &[u64],
+    block_byte_counts: &[u64],
+) -> Result<(Vec<Entry>, Vec<u8>), Error> {
+    let num_bands = if configs.photometric_interp == PhotometricInterpretation::RGB {
+        1
+    } else {
+        configs.bands.max(1) as usize
+    };
+
     ////////////////////////////
     // Create the IFD entries //
     ////////////////////////////
@@ -2480,7 +2831,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
 
     let mut ifd_entries: Vec<Entry> = vec![];
     // let mut larger_values_data: Vec<u8> = vec![];
-    let mut larger_values_data = ByteOrderWriter::<Vec<u8>>::new(vec![], r.configs.endian);
+    let mut larger_values_data = ByteOrderWriter::<Vec<u8>>::new(vec![], configs.endian);
 
     /*
     Classic TIFF IFD entries
@@ -2500,12 +2851,17 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     and Count of the field.
     */
 
+    if is_overview {
+        // NewSubfileType tag (254)
+        ifd_entries.push(Entry::new(TAG_NEWSUBFILETYPE, DT_LONG, 1u64, 1u64));
+    }
+
     // ImageWidth tag (256)
     ifd_entries.push(Entry::new(
         TAG_IMAGEWIDTH,
         DT_LONG,
         1u64,
-        r.configs.columns as u64,
+        configs.columns as u64,
     ));
 
     // ImageLength tag (257)
@@ -2513,10 +2869,10 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         TAG_IMAGELENGTH,
         DT_LONG,
         1u64,
-        r.configs.rows as u64,
+        configs.rows as u64,
     ));
 
-    let bits_per_sample = match r.configs.data_type {
+    let bits_per_sample = match configs.data_type {
         DataType::I8 | DataType::U8 => 8u16,
         DataType::I16 | DataType::U16 => 16u16,
         DataType::I32 | DataType::U32 | DataType::F32 => 32u16,
@@ -2529,7 +2885,8 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     };
 
-    let samples_per_pixel = match r.configs.data_type {
+    let samples_per_pixel = match configs.data_type {
+        _ if num_bands > 1 => num_bands as u16,
         DataType::I8 | DataType::U8 => 1u16,
         DataType::I16 | DataType::U16 => 1u16,
         DataType::I32 | DataType::U32 | DataType::F32 => 1u16,
@@ -2543,7 +2900,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     };
 
     // BitsPerSample tag (258)
-    if r.configs.photometric_interp != PhotometricInterpretation::Boolean {
+    if configs.photometric_interp != PhotometricInterpretation::Boolean {
         if samples_per_pixel == 1 {
             ifd_entries.push(Entry::new(
                 TAG_BITSPERSAMPLE,
@@ -2551,6 +2908,14 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 samples_per_pixel as u64,
                 bits_per_sample as u64,
             ));
+        } else if samples_per_pixel == 2 {
+            // Two shorts fit within the entry itself.
+            ifd_entries.push(Entry::new(
+                TAG_BITSPERSAMPLE,
+                DT_SHORT,
+                samples_per_pixel as u64,
+                bits_per_sample as u64 | (bits_per_sample as u64) << 16,
+            ));
         } else {
             ifd_entries.push(Entry::new(
                 TAG_BITSPERSAMPLE,
@@ -2565,24 +2930,15 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     }
 
     // Compression tag (259)
-    if use_compression {
-        ifd_entries.push(Entry::new(
-            TAG_COMPRESSION,
-            DT_SHORT,
-            1u64,
-            COMPRESS_DEFLATE as u64,
-        ));
-    } else {
-        ifd_entries.push(Entry::new(
-            TAG_COMPRESSION,
-            DT_SHORT,
-            1u64,
-            COMPRESS_NONE as u64,
-        ));
-    }
+    ifd_entries.push(Entry::new(
+        TAG_COMPRESSION,
+        DT_SHORT,
+        1u64,
+        encoder.compression.code() as u64,
+    ));
 
     // PhotometricInterpretation tag (262)
-    let pi = match r.configs.photometric_interp {
+    let pi = match configs.photometric_interp {
         PhotometricInterpretation::Continuous => PI_BLACKISZERO,
         PhotometricInterpretation::Categorical | PhotometricInterpretation::Paletted => PI_PALETTED,
         PhotometricInterpretation::Boolean => PI_BLACKISZERO,
@@ -2601,70 +2957,38 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         pi as u64,
     ));
 
-    // StripOffsets tag (273)
-    if !is_big_tiff {
-        ifd_entries.push(Entry::new(
-            TAG_STRIPOFFSETS,
-            DT_LONG,
-            r.configs.rows as u64,
-            larger_values_data.len() as u64,
-        ));
-        if use_compression {
-            for val in strip_offsets {
-                larger_values_data
-                    .write_u32(val as u32)
-                    .expect("Error writing the TIFF strip offsets tag");
-            }
-        } else {
-            let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
-            for i in 0..r.configs.rows as u32 {
-                larger_values_data
-                    .write_u32(header_size as u32 + row_length_in_bytes * i)
-                    .expect("Error writing the TIFF strip offsets tag");
-            }
-        }
-    } else {
-        ifd_entries.push(Entry::new(
-            TAG_STRIPOFFSETS,
-            DT_TIFF_LONG8,
-            r.configs.rows as u64,
-            larger_values_data.len() as u64,
-        ));
-        if use_compression {
-            for val in strip_offsets {
-                larger_values_data
-                    .write_u64(val)
-                    .expect("Error writing the TIFF strip offsets tag");
-            }
-        } else {
-            let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
-            for i in 0..r.configs.rows as u64 {
-                larger_values_data
-                    .write_u64(header_size + row_length_in_bytes * i)
-                    .expect("Error writing the TIFF strip offsets");
-            }
-        }
-    }
+    // StripOffsets (273) or TileOffsets (324) tag
+    let (offsets_tag, byte_counts_tag) = match layout {
+        BlockLayout::Strips | BlockLayout::ChunkyStrips => (TAG_STRIPOFFSETS, TAG_STRIPBYTECOUNTS),
+        BlockLayout::Tiles(_) => (TAG_TILEOFFSETS, TAG_TILEBYTECOUNTS),
+    };
+    push_block_entry(
+        &mut ifd_entries,
+        &mut larger_values_data,
+        offsets_tag,
+        is_big_tiff,
+        block_offsets,
+    )?;
     // if !is_big_tiff {
     //     ifd_entries.push(Entry::new(
     //         TAG_STRIPOFFSETS,
     //         DT_LONG,
-    //         r.configs.rows as u64,
+    //         configs.rows as u64,
     //         larger_values_data.len() as u64,
     //     ));
-    //     let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
-    //     for i in 0..r.configs.rows as u32 {
+    //     let row_length_in_bytes: u32 = configs.columns as u32 * total_bytes_per_pixel as u32;
+    //     for i in 0..configs.rows as u32 {
     //         larger_values_data.write_u32(8u32 + row_length_in_bytes * i)?;
     //     }
     // } else {
     //     ifd_entries.push(Entry::new(
     //         TAG_STRIPOFFSETS,
     //         DT_TIFF_LONG8,
-    //         r.configs.rows as u64,
+    //         configs.rows as u64,
     //         larger_values_data.len() as u64,
     //     ));
-    //     let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
-    //     for i in 0..r.configs.rows as u64 {
+    //     let row_length_in_bytes: u64 = configs.columns as u64 * total_bytes_per_pixel as u64;
+    //     for i in 0..configs.rows as u64 {
     //         larger_values_data.write_u64(8u64 + row_length_in_bytes * i)?;
     //     }
     // }
@@ -2677,86 +3001,35 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         samples_per_pixel as u64,
     ));
 
-    // RowsPerStrip tag (278)
-    ifd_entries.push(Entry::new(TAG_ROWSPERSTRIP, DT_SHORT, 1u64, 1u64));
-
-    // StripByteCounts tag (279)
-    if !is_big_tiff {
-        ifd_entries.push(Entry::new(
-            TAG_STRIPBYTECOUNTS,
-            DT_LONG,
-            r.configs.rows as u64,
-            larger_values_data.len() as u64,
-        ));
-        let total_bytes_per_pixel = match r.configs.data_type {
-            DataType::I8 | DataType::U8 => 1u32,
-            DataType::I16 | DataType::U16 => 2u32,
-            DataType::I32 | DataType::U32 | DataType::F32 => 4u32,
-            DataType::I64 | DataType::U64 | DataType::F64 => 8u32,
-            DataType::RGB24 => 3u32,
-            DataType::RGBA32 => 4u32,
-            DataType::RGB48 => 6u32,
-            _ => {
-                return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
-            }
-        };
-        if use_compression {
-            for val in strip_byte_counts {
-                larger_values_data
-                    .write_u32(val as u32)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
-        } else {
-            let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
-            for _ in 0..r.configs.rows as u32 {
-                larger_values_data
-                    .write_u32(row_length_in_bytes)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
+    match layout {
+        BlockLayout::Strips | BlockLayout::ChunkyStrips => {
+            // RowsPerStrip tag (278)
+            ifd_entries.push(Entry::new(TAG_ROWSPERSTRIP, DT_SHORT, 1u64, 1u64));
         }
-    } else {
-        ifd_entries.push(Entry::new(
-            TAG_STRIPBYTECOUNTS,
-            DT_TIFF_LONG8,
-            r.configs.rows as u64,
-            larger_values_data.len() as u64,
-        ));
-        let total_bytes_per_pixel = match r.configs.data_type {
-            DataType::I8 | DataType::U8 => 1u64,
-            DataType::I16 | DataType::U16 => 2u64,
-            DataType::I32 | DataType::U32 | DataType::F32 => 4u64,
-            DataType::I64 | DataType::U64 | DataType::F64 => 8u64,
-            DataType::RGB24 => 3u64,
-            DataType::RGBA32 => 4u64,
-            DataType::RGB48 => 6u64,
-            _ => {
-                return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
-            }
-        };
-        if use_compression {
-            for val in strip_byte_counts {
-                larger_values_data
-                    .write_u64(val)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
-        } else {
-            let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
-            for _ in 0..r.configs.rows as u32 {
-                larger_values_data
-                    .write_u64(row_length_in_bytes)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
+        BlockLayout::Tiles(tile_size) => {
+            // TileWidth (322) and TileLength (323) tags
+            ifd_entries.push(Entry::new(TAG_TILEWIDTH, DT_SHORT, 1u64, tile_size as u64));
+            ifd_entries.push(Entry::new(TAG_TILELENGTH, DT_SHORT, 1u64, tile_size as u64));
         }
     }
+
+    // StripByteCounts (279) or TileByteCounts (325) tag
+    push_block_entry(
+        &mut ifd_entries,
+        &mut larger_values_data,
+        byte_counts_tag,
+        is_big_tiff,
+        block_byte_counts,
+    )?;
     /*
     if !is_big_tiff {
         ifd_entries.push(Entry::new(
             TAG_STRIPBYTECOUNTS,
             DT_LONG,
-            r.configs.rows as u64,
+            configs.rows as u64,
             larger_values_data.len() as u64,
         ));
-        let total_bytes_per_pixel = match r.configs.data_type {
+        let total_bytes_per_pixel = match configs.data_type {
             DataType::I8 | DataType::U8 => 1u32,
             DataType::I16 | DataType::U16 => 2u32,
             DataType::I32 | DataType::U32 | DataType::F32 => 4u32,
@@ -2768,18 +3041,18 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
             }
         };
-        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
-        for _ in 0..r.configs.rows as u32 {
+        let row_length_in_bytes: u32 = configs.columns as u32 * total_bytes_per_pixel;
+        for _ in 0..configs.rows as u32 {
             larger_values_data.write_u32(row_length_in_bytes)?;
         }
     } else {
         ifd_entries.push(Entry::new(
             TAG_STRIPBYTECOUNTS,
             DT_TIFF_LONG8,
-            r.configs.rows as u64,
+            configs.rows as u64,
             larger_values_data.len() as u64,
         ));
-        let total_bytes_per_pixel = match r.configs.data_type {
+        let total_bytes_per_pixel = match configs.data_type {
             DataType::I8 | DataType::U8 => 1u64,
             DataType::I16 | DataType::U16 => 2u64,
             DataType::I32 | DataType::U32 | DataType::F32 => 4u64,
@@ -2791,8 +3064,8 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
             }
         };
-        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
-        for _ in 0..r.configs.rows as u32 {
+        let row_length_in_bytes: u64 = configs.columns as u64 * total_bytes_per_pixel;
+        for _ in 0..configs.rows as u32 {
             larger_values_data.write_u64(row_length_in_bytes)?;
         }
     }
@@ -2819,9 +3092,25 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     larger_values_data.write_u32(72u32)?;
     larger_values_data.write_u32(1u32)?;
 
+    if num_bands > 1 {
+        // PlanarConfiguration tag (284)
+        let planar_config = if layout == BlockLayout::ChunkyStrips { 1u64 } else { 2u64 };
+        ifd_entries.push(Entry::new(TAG_PLANARCONFIGURATION, DT_SHORT, 1u64, planar_config));
+    }
+
     // ResolutionUnit tag (296)
     ifd_entries.push(Entry::new(TAG_RESOLUTIONUNIT, DT_SHORT, 1u64, 2u64));
 
+    if encoder.predictor != TiffPredictor::None {
+        // Predictor tag (317)
+        ifd_entries.push(Entry::new(
+            TAG_PREDICTOR,
+            DT_SHORT,
+            1u64,
+            encoder.predictor.code() as u64,
+        ));
+    }
+
     // Software tag (305)
     let software = "WhiteboxTools".to_owned();
     let mut soft_bytes = software.into_bytes();
@@ -2834,13 +3123,13 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     ));
     larger_values_data.write_bytes(&soft_bytes)?;
 
-    if samples_per_pixel == 4 {
+    if samples_per_pixel == 4 && num_bands == 1 {
         // ExtraSamples tag (338)
         ifd_entries.push(Entry::new(TAG_EXTRASAMPLES, DT_SHORT, 1u64, 2u64));
     }
 
     // SampleFormat tag (339)
-    let samples_format = match r.configs.data_type {
+    let samples_format = match configs.data_type {
         DataType::U8 | DataType::U16 | DataType::U32 | DataType::U64 => 1u16,
         DataType::I8 | DataType::I16 | DataType::I32 | DataType::I64 => 2u16,
         DataType::F32 | DataType::F64 => 3u16,
@@ -2856,6 +3145,13 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             samples_per_pixel as u64,
             samples_format as u64,
         ));
+    } else if samples_per_pixel == 2 {
+        ifd_entries.push(Entry::new(
+            TAG_SAMPLEFORMAT,
+            DT_SHORT,
+            samples_per_pixel as u64,
+            samples_format as u64 | (samples_format as u64) << 16,
+        ));
     } else {
         ifd_entries.push(Entry::new(
             TAG_SAMPLEFORMAT,
@@ -2868,10 +3164,70 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
+    // TAG_GDAL_NODATA tag (42113); NaN is written as GDAL writes it.
+    let nodata_str = if configs.nodata.is_nan() {
+        "nan".to_string()
+    } else {
+        format!("{}", configs.nodata)
+    };
+    let mut nodata_bytes = nodata_str.into_bytes();
+    if !is_big_tiff {
+        // we buffer this string with spaces to ensure that it is
+        // long enough to be printed to larger_values_data.
+        if nodata_bytes.len() < 4 {
+            for _ in 0..(4 - nodata_bytes.len()) {
+                nodata_bytes.push(32);
+            }
+        }
+        if nodata_bytes.len() % 2 == 0 {
+            nodata_bytes.push(32);
+        }
+        nodata_bytes.push(0);
+        ifd_entries.push(Entry::new(
+            TAG_GDAL_NODATA,
+            DT_ASCII,
+            nodata_bytes.len() as u64,
+            larger_values_data.len() as u64,
+        ));
+        larger_values_data.write_bytes(&nodata_bytes)?;
+    } else {
+        // we buffer this string with spaces to ensure that it is
+        // long enough to be printed to larger_values_data.
+        if nodata_bytes.len() < 8 {
+            for _ in 0..(8 - nodata_bytes.len()) {
+                nodata_bytes.push(32);
+            }
+        }
+        if nodata_bytes.len() % 2 == 0 {
+            nodata_bytes.push(32);
+        }
+        nodata_bytes.push(0);
+        ifd_entries.push(Entry::new(
+            TAG_GDAL_NODATA,
+            DT_ASCII,
+            nodata_bytes.len() as u64,
+            larger_values_data.len() as u64,
+        ));
+        larger_values_data.write_bytes(&nodata_bytes)?;
+    }
+
+    if !is_overview {
+        add_georeferencing_entries(configs, &mut ifd_entries, &mut larger_values_data)?;
+    }
+
+    Ok((ifd_entries, larger_values_data.into_inner()))
+}
+
+/// Adds the entries of an IFD holding the GeoTIFF georeferencing tags of an image.
+fn add_georeferencing_entries(
+    configs: &RasterConfigs,
+    ifd_entries: &mut Vec<Entry>,
+    larger_values_data: &mut ByteOrderWriter<Vec<u8>>,
+) -> Result<(), Error> {
     // ModelPixelScaleTag tag (33550)
-    if r.configs.model_pixel_scale[0] == 0f64
-        && r.configs.model_tiepoint.is_empty()
-        && r.configs.model_transformation[0] == 0f64
+    if configs.model_pixel_scale[0] == 0f64
+        && configs.model_tiepoint.is_empty()
+        && configs.model_transformation[0] == 0f64
     {
         ifd_entries.push(Entry::new(
             TAG_MODELPIXELSCALETAG,
@@ -2879,22 +3235,22 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             3u64,
             larger_values_data.len() as u64,
         ));
-        larger_values_data.write_f64(r.configs.resolution_x)?;
-        larger_values_data.write_f64(r.configs.resolution_y)?;
+        larger_values_data.write_f64(configs.resolution_x)?;
+        larger_values_data.write_f64(configs.resolution_y)?;
         larger_values_data.write_f64(0f64)?;
-    } else if r.configs.model_pixel_scale[0] != 0f64 {
+    } else if configs.model_pixel_scale[0] != 0f64 {
         ifd_entries.push(Entry::new(
             TAG_MODELPIXELSCALETAG,
             DT_DOUBLE,
             3u64,
             larger_values_data.len() as u64,
         ));
-        larger_values_data.write_f64(r.configs.model_pixel_scale[0])?;
-        larger_values_data.write_f64(r.configs.model_pixel_scale[1])?;
-        larger_values_data.write_f64(r.configs.model_pixel_scale[2])?;
+        larger_values_data.write_f64(configs.model_pixel_scale[0])?;
+        larger_values_data.write_f64(configs.model_pixel_scale[1])?;
+        larger_values_data.write_f64(configs.model_pixel_scale[2])?;
     }
 
-    if r.configs.model_tiepoint.is_empty() && r.configs.model_transformation[0] == 0f64 {
+    if configs.model_tiepoint.is_empty() && configs.model_transformation[0] == 0f64 {
         // ModelTiepointTag tag (33922)
         ifd_entries.push(Entry::new(
             TAG_MODELTIEPOINTTAG,
@@ -2905,23 +3261,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         larger_values_data.write_f64(0f64)?; // I
         larger_values_data.write_f64(0f64)?; // J
         larger_values_data.write_f64(0f64)?; // K
-        larger_values_data.write_f64(r.configs.west)?; // X
-        larger_values_data.write_f64(r.configs.north)?; // Y
+        larger_values_data.write_f64(configs.west)?; // X
+        larger_values_data.write_f64(configs.north)?; // Y
         larger_values_data.write_f64(0f64)?; // Z
-    } else if !r.configs.model_tiepoint.is_empty() {
+    } else if !configs.model_tiepoint.is_empty() {
         // ModelTiepointTag tag (33922)
         ifd_entries.push(Entry::new(
             TAG_MODELTIEPOINTTAG,
             DT_DOUBLE,
-            r.configs.model_tiepoint.len() as u64,
+            configs.model_tiepoint.len() as u64,
             larger_values_data.len() as u64,
         ));
-        for i in 0..r.configs.model_tiepoint.len() {
-            larger_values_data.write_f64(r.configs.model_tiepoint[i])?;
+        for i in 0..configs.model_tiepoint.len() {
+            larger_values_data.write_f64(configs.model_tiepoint[i])?;
         }
     }
 
-    if r.configs.model_transformation[0] != 0f64 {
+    if configs.model_transformation[0] != 0f64 {
         // ModelTransformationTag tag (33920)
         ifd_entries.push(Entry::new(
             TAG_MODELTRANSFORMATIONTAG,
@@ -2930,53 +3286,10 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             larger_values_data.len() as u64,
         ));
         for i in 0..16 {
-            larger_values_data.write_f64(r.configs.model_transformation[i])?;
+            larger_values_data.write_f64(configs.model_transformation[i])?;
         }
     }
 
-    // TAG_GDAL_NODATA tag (42113)
-    let nodata_str = format!("{}", r.configs.nodata);
-    let mut nodata_bytes = nodata_str.into_bytes();
-    if !is_big_tiff {
-        // we buffer this string with spaces to ensure that it is
-        // long enough to be printed to larger_values_data.
-        if nodata_bytes.len() < 4 {
-            for _ in 0..(4 - nodata_bytes.len()) {
-                nodata_bytes.push(32);
-            }
-        }
-        if nodata_bytes.len() % 2 == 0 {
-            nodata_bytes.push(32);
-        }
-        nodata_bytes.push(0);
-        ifd_entries.push(Entry::new(
-            TAG_GDAL_NODATA,
-            DT_ASCII,
-            nodata_bytes.len() as u64,
-            larger_values_data.len() as u64,
-        ));
-        larger_values_data.write_bytes(&nodata_bytes)?;
-    } else {
-        // we buffer this string with spaces to ensure that it is
-        // long enough to be printed to larger_values_data.
-        if nodata_bytes.len() < 8 {
-            for _ in 0..(8 - nodata_bytes.len()) {
-                nodata_bytes.push(32);
-            }
-        }
-        if nodata_bytes.len() % 2 == 0 {
-            nodata_bytes.push(32);
-        }
-        nodata_bytes.push(0);
-        ifd_entries.push(Entry::new(
-            TAG_GDAL_NODATA,
-            DT_ASCII,
-            nodata_bytes.len() as u64,
-            larger_values_data.len() as u64,
-        ));
-        larger_values_data.write_bytes(&nodata_bytes)?;
-    }
-
     let kw_map = get_keyword_map();
     let geographic_type_map = match kw_map.get(&2048u16) {
         Some(map) => map,
@@ -3001,7 +3314,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     let mut gk_entries: Vec<GeoKeyEntry> = vec![];
     let mut ascii_params = String::new(); //: Vec<u8> = vec![];
     let double_params: Vec<f64> = vec![];
-    if geographic_type_map.contains_key(&r.configs.epsg_code) {
+    if geographic_type_map.contains_key(&configs.epsg_code) {
         // tGTModelTypeGeoKey (1024)
         gk_entries.push(GeoKeyEntry {
             tag: TAG_GTMODELTYPEGEOKEY,
@@ -3011,7 +3324,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         });
 
         // GTRasterTypeGeoKey (1025)
-        if r.configs.pixel_is_area {
+        if configs.pixel_is_area {
             gk_entries.push(GeoKeyEntry {
                 tag: TAG_GTRASTERTYPEGEOKEY,
                 location: 0u16,
@@ -3030,7 +3343,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         // tGTCitationGeoKey (1026)
         let mut v = String::from(
             *geographic_type_map
-                .get(&r.configs.epsg_code)
+                .get(&configs.epsg_code)
                 .unwrap()
                 // .clone(),
         );
@@ -3049,12 +3362,12 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             tag: TAG_GEOGRAPHICTYPEGEOKEY,
             location: 0u16,
             count: 1u16,
-            value_offset: r.configs.epsg_code,
+            value_offset: configs.epsg_code,
         });
 
-        if r.configs.z_units.to_lowercase() != "not specified" {
+        if configs.z_units.to_lowercase() != "not specified" {
             // VerticalUnitsGeoKey (4099)
-            let units = r.configs.z_units.to_lowercase();
+            let units = configs.z_units.to_lowercase();
             if units.contains("met") {
                 gk_entries.push(GeoKeyEntry {
                     tag: TAG_VERTICALUNITSGEOKEY,
@@ -3071,7 +3384,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 });
             }
         }
-    } else if projected_cs_type_map.contains_key(&r.configs.epsg_code) {
+    } else if projected_cs_type_map.contains_key(&configs.epsg_code) {
         // tGTModelTypeGeoKey (1024)
         gk_entries.push(GeoKeyEntry {
             tag: TAG_GTMODELTYPEGEOKEY,
@@ -3081,7 +3394,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         });
 
         // GTRasterTypeGeoKey (1025)
-        if r.configs.pixel_is_area {
+        if configs.pixel_is_area {
             gk_entries.push(GeoKeyEntry {
                 tag: TAG_GTRASTERTYPEGEOKEY,
                 location: 0u16,
@@ -3102,13 +3415,13 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             tag: TAG_PROJECTEDCSTYPEGEOKEY,
             location: 0u16,
             count: 1u16,
-            value_offset: r.configs.epsg_code,
+            value_offset: configs.epsg_code,
         });
 
         // PCSCitationGeoKey (3073)
         let mut v = String::from(
             *projected_cs_type_map
-                .get(&r.configs.epsg_code)
+                .get(&configs.epsg_code)
                 .unwrap()
                 // .clone(),
         );
@@ -3122,9 +3435,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         });
         ascii_params.push_str(&v);
 
-        if r.configs.xy_units.to_lowercase() != "not specified" {
+        if configs.xy_units.to_lowercase() != "not specified" {
             // ProjLinearUnitsGeoKey (3076)
-            let units = r.configs.xy_units.to_lowercase();
+            let units = configs.xy_units.to_lowercase();
             if units.contains("met") {
                 gk_entries.push(GeoKeyEntry {
                     tag: TAG_PROJLINEARUNITSGEOKEY,
@@ -3142,9 +3455,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             }
         }
 
-        if r.configs.z_units.to_lowercase() != "not specified" {
+        if configs.z_units.to_lowercase() != "not specified" {
             // VerticalUnitsGeoKey (4099)
-            let units = r.configs.z_units.to_lowercase();
+            let units = configs.z_units.to_lowercase();
             if units.contains("met") {
                 gk_entries.push(GeoKeyEntry {
                     tag: TAG_VERTICALUNITSGEOKEY,
@@ -3173,7 +3486,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         });
 
         // GTRasterTypeGeoKey (1025)
-        if r.configs.pixel_is_area {
+        if configs.pixel_is_area {
             gk_entries.push(GeoKeyEntry {
                 tag: TAG_GTRASTERTYPEGEOKEY,
                 location: 0u16,
@@ -3190,7 +3503,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
-    if r.configs.geo_key_directory.is_empty() {
+    if configs.geo_key_directory.is_empty() {
         // create the GeoKeyDirectoryTag tag (34735)
         ifd_entries.push(Entry::new(
             TAG_GEOKEYDIRECTORYTAG,
@@ -3240,34 +3553,34 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             larger_values_data.write_bytes(&ascii_params_bytes)?;
         }
     } else {
-        // let num_keys = (r.configs.geo_key_directory.len() - 4) / 4;
+        // let num_keys = (configs.geo_key_directory.len() - 4) / 4;
         // output the GeoKeyDirectoryTag tag (34735)
         ifd_entries.push(Entry::new(
             TAG_GEOKEYDIRECTORYTAG,
             DT_SHORT,
-            r.configs.geo_key_directory.len() as u64,
+            configs.geo_key_directory.len() as u64,
             larger_values_data.len() as u64,
         ));
-        for val in &r.configs.geo_key_directory {
+        for val in &configs.geo_key_directory {
             larger_values_data.write_u16(*val)?;
         }
 
-        if r.configs.geo_double_params.len() > 0 {
+        if configs.geo_double_params.len() > 0 {
             // create the GeoDoubleParamsTag tag (34736)
             ifd_entries.push(Entry::new(
                 TAG_GEODOUBLEPARAMSTAG,
                 DT_DOUBLE,
-                r.configs.geo_double_params.len() as u64,
+                configs.geo_double_params.len() as u64,
                 larger_values_data.len() as u64,
             ));
-            for double_val in &r.configs.geo_double_params {
+            for double_val in &configs.geo_double_params {
                 larger_values_data.write_f64(*double_val)?;
             }
         }
 
-        if !r.configs.geo_ascii_params.is_empty() {
+        if !configs.geo_ascii_params.is_empty() {
             // create the GeoAsciiParamsTag tag (34737)
-            let mut ascii_params_bytes = r.configs.geo_ascii_params.clone().into_bytes();
+            let mut ascii_params_bytes = configs.geo_ascii_params.clone().into_bytes();
             ascii_params_bytes.push(0);
             ifd_entries.push(Entry::new(
                 TAG_GEOASCIIPARAMSTAG,
@@ -3283,13 +3596,39 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
+    Ok(())
+}
+
+/// Returns the size in bytes of an IFD with `num_entries` entries, followed by
+/// `larger_values_len` bytes of values that are too large to be stored within the entries.
+fn ifd_size(is_big_tiff: bool, num_entries: usize, larger_values_len: usize) -> u64 {
+    let ifd_length = if !is_big_tiff {
+        2u64 + num_entries as u64 * 12u64 + 4u64
+    } else {
+        8u64 + num_entries as u64 * 20u64 + 8u64
+    };
+    ifd_length + larger_values_len as u64
+}
+
+/// Writes an IFD, followed by the data of its larger values, at the current position of
+/// `writer`, which must be the file offset `ifd_start`. `next_ifd` is the offset of the
+/// following IFD, or zero if this is the last IFD in the file.
+fn write_ifd(
+    writer: &mut BufWriter<File>,
+    endian: Endianness,
+    is_big_tiff: bool,
+    ifd_start: u64,
+    mut ifd_entries: Vec<Entry>,
+    larger_values_data: &[u8],
+    next_ifd: u64,
+) -> Result<(), Error> {
     ///////////////////
     // Write the IFD //
     ///////////////////
 
     // Number of Directory Entries.
     if !is_big_tiff {
-        write_u16(&mut writer, r.configs.endian, ifd_entries.len() as u16)?;
+        write_u16(writer, endian, ifd_entries.len() as u16)?;
 
         // Sort the IFD entries
         ifd_entries.sort_by(|a, b| a.tag.cmp(&b.tag));
@@ -3298,34 +3637,33 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         let ifd_length = 2u64 + ifd_entries.len() as u64 * 12u64 + 4u64;
 
         for ifde in ifd_entries {
-            write_u16(&mut writer, r.configs.endian, ifde.tag)?; // Tag
-            write_u16(&mut writer, r.configs.endian, ifde.ifd_type)?; // Field type
-            write_u32(&mut writer, r.configs.endian, ifde.num_values as u32)?; // Num of values
+            write_u16(writer, endian, ifde.tag)?; // Tag
+            write_u16(writer, endian, ifde.ifd_type)?; // Field type
+            write_u32(writer, endian, ifde.num_values as u32)?; // Num of values
             if ifde.ifd_type == DT_SHORT && ifde.num_values == 1 {
                 // it's a value
-                write_u16(&mut writer, r.configs.endian, ifde.offset as u16)?; // Value
-                write_u16(&mut writer, r.configs.endian, 0u16)?; // Fill the remaining 2 right bytes of the u32
+                write_u16(writer, endian, ifde.offset as u16)?; // Value
+                write_u16(writer, endian, 0u16)?; // Fill the remaining 2 right bytes of the u32
             } else if ifde.ifd_type == DT_LONG && ifde.num_values == 1 {
                 // it's a value
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?;
+                write_u32(writer, endian, ifde.offset as u32)?;
             } else if ifde.ifd_type == DT_SHORT && ifde.num_values == 2 {
                 // I'm not really sure about this one. Two shorts will fit in the value_offset, but will they be interpreted correctly?
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?; // Value
+                write_u32(writer, endian, ifde.offset as u32)?; // Value
             } else {
                 // it's an offset
                 write_u32(
-                    &mut writer,
-                    r.configs.endian,
+                    writer,
+                    endian,
                     ifd_start as u32 + ifd_length as u32 + ifde.offset as u32,
                 )?;
             }
         }
 
-        // 4-byte offset of the next IFD; Note, only single image TIFFs are currently supported
-        // and therefore, this will always be set to '0'.
-        write_u32(&mut writer, r.configs.endian, 0u32)?;
+        // 4-byte offset of the next IFD, or '0' if this is the last IFD.
+        write_u32(writer, endian, next_ifd as u32)?;
     } else {
-        write_u64(&mut writer, r.configs.endian, ifd_entries.len() as u64)?;
+        write_u64(writer, endian, ifd_entries.len() as u64)?;
 
         // Sort the IFD entries
         ifd_entries.sort_by(|a, b| a.tag.cmp(&b.tag));
@@ -3334,47 +3672,121 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         let ifd_length = 8u64 + ifd_entries.len() as u64 * 20u64 + 8u64;
 
         for ifde in ifd_entries {
-            write_u16(&mut writer, r.configs.endian, ifde.tag)?; // Tag
-            write_u16(&mut writer, r.configs.endian, ifde.ifd_type)?; // Field type
-            write_u64(&mut writer, r.configs.endian, ifde.num_values)?; // Num of values
+            write_u16(writer, endian, ifde.tag)?; // Tag
+            write_u16(writer, endian, ifde.ifd_type)?; // Field type
+            write_u64(writer, endian, ifde.num_values)?; // Num of values
             if ifde.ifd_type == DT_SHORT && ifde.num_values == 1 {
                 // it's a value
-                write_u16(&mut writer, r.configs.endian, ifde.offset as u16)?; // Value
-                write_u16(&mut writer, r.configs.endian, 0u16)?; // Fill the remaining bytes of the u64
-                write_u32(&mut writer, r.configs.endian, 0u32)?; // Fill the remaining bytes of the u64
+                write_u16(writer, endian, ifde.offset as u16)?; // Value
+                write_u16(writer, endian, 0u16)?; // Fill the remaining bytes of the u64
+                write_u32(writer, endian, 0u32)?; // Fill the remaining bytes of the u64
             } else if ifde.ifd_type == DT_SHORT && ifde.num_values == 2 {
                 // I'm not really sure about this one. Two shorts will fit in the value_offset, but will they be interpreted correctly?
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?; // Value
-                write_u32(&mut writer, r.configs.endian, 0u32)?; // Fill the remaining bytes of the u64
+                write_u32(writer, endian, ifde.offset as u32)?; // Value
+                write_u32(writer, endian, 0u32)?; // Fill the remaining bytes of the u64
             } else if ifde.ifd_type == DT_LONG && ifde.num_values == 1 {
                 // it's a value
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?;
-                write_u32(&mut writer, r.configs.endian, 0u32)?; // Fill the remaining bytes of the u64
+                write_u32(writer, endian, ifde.offset as u32)?;
+                write_u32(writer, endian, 0u32)?; // Fill the remaining bytes of the u64
             } else if (ifde.ifd_type == DT_LONG && ifde.num_values == 2)
                 || (ifde.ifd_type == DT_TIFF_LONG8 && ifde.num_values == 1)
             {
                 // it's a value
-                write_u64(&mut writer, r.configs.endian, ifde.offset)?;
+                write_u64(writer, endian, ifde.offset)?;
             } else {
                 // it's an offset
                 write_u64(
-                    &mut writer,
-                    r.configs.endian,
+                    writer,
+                    endian,
                     ifd_start + ifd_length + ifde.offset,
                 )?;
             }
         }
 
-        // 4-byte offset of the next IFD; Note, only single image TIFFs are currently supported
-        // and therefore, this will always be set to '0'.
-        write_u64(&mut writer, r.configs.endian, 0u64)?;
+        // 8-byte offset of the next IFD, or '0' if this is the last IFD.
+        write_u64(writer, endian, next_ifd)?;
     }
 
     //////////////////////////////////
     // Write the larger_values_data //
     //////////////////////////////////
-    write_bytes(&mut writer, larger_values_data.get_inner())?;
+    write_bytes(writer, larger_values_data)?;
+
+    Ok(())
+}
+
+/// Appends the bytes of a grid cell value, stored in the given data type, to `bytes`.
+/// RGB24 and RGBA32 values are packed into the low-order bytes of `z`.
+fn write_cell_value(
+    bytes: &mut ByteOrderWriter<Vec<u8>>,
+    data_type: DataType,
+    z: f64,
+) -> Result<(), Error> {
+    match data_type {
+        DataType::F64 => bytes.write_f64(z)?,
+        DataType::F32 => bytes.write_f32(z as f32)?,
+        DataType::U64 => bytes.write_u64(z as u64)?,
+        DataType::U32 => bytes.write_u32(z as u32)?,
+        DataType::U16 => bytes.write_u16(z as u16)?,
+        DataType::U8 => bytes.write_u8(z as u8)?,
+        DataType::I64 => bytes.write_i64(z as i64)?,
+        DataType::I32 => bytes.write_i32(z as i32)?,
+        DataType::I16 => bytes.write_i16(z as i16)?,
+        DataType::I8 => bytes.write_i8(z as i8)?,
+        DataType::RGB24 => {
+            let val = z as u32;
+            bytes.write_bytes(&[
+                (val & 0xFF) as u8,
+                ((val >> 8) & 0xFF) as u8,
+                ((val >> 16) & 0xFF) as u8,
+            ])?
+        }
+        DataType::RGBA32 => {
+            let val = z as u32;
+            bytes.write_bytes(&[
+                (val & 0xFF) as u8,
+                ((val >> 8) & 0xFF) as u8,
+                ((val >> 16) & 0xFF) as u8,
+                ((val >> 24) & 0xFF) as u8,
+            ])?
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Writing {:?} data is not supported.", data_type),
+            ));
+        }
+    }
+    Ok(())
+}
 
+/// Adds an entry holding the offsets or byte counts of the strips or tiles of an image.
+/// A single value is stored within the entry itself.
+fn push_block_entry(
+    ifd_entries: &mut Vec<Entry>,
+    larger_values_data: &mut ByteOrderWriter<Vec<u8>>,
+    tag: u16,
+    is_big_tiff: bool,
+    values: &[u64],
+) -> Result<(), Error> {
+    let ifd_type = if !is_big_tiff { DT_LONG } else { DT_TIFF_LONG8 };
+    if values.len() == 1 {
+        ifd_entries.push(Entry::new(tag, ifd_type, 1u64, values[0]));
+        return Ok(());
+    }
+    ifd_entries.push(Entry::new(
+        tag,
+        ifd_type,
+        values.len() as u64,
+        larger_values_data.len() as u64,
+    ));
+    for &val in values {
+        if !is_big_tiff {
+            larger_values_data.write_u32(val as u32)?;
+        } else {
+            larger_values_data.write_u64(val)?;
+        }
+    }
     Ok(())
 }
 
@@ -3484,7 +3896,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_f64(r.data[i])?;
+                            bow.write_f64(r.data.get_value(i))?;
                         }
                     }
                 }
@@ -3495,12 +3907,12 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         if r.configs.endian == Endianness::LittleEndian {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_f32::<LittleEndian>(r.data[i] as f32).expect("Error writing byte data.");
+                                data.write_f32::<LittleEndian>(r.data.get_value(i) as f32).expect("Error writing byte data.");
                             }
                         } else {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                data.write_f32::<BigEndian>(r.data[i] as f32).expect("Error writing byte data.");
+                                data.write_f32::<BigEndian>(r.data.get_value(i) as f32).expect("Error writing byte data.");
                             }
                         }
                         // compress the data vec
@@ -3523,7 +3935,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u64(r.data[i] as u64)?;
+                            bow.write_u64(r.data.get_value(i) as u64)?;
                         }
                     }
                 }
@@ -3532,7 +3944,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u32(r.data[i] as u32)?;
+                            bow.write_u32(r.data.get_value(i) as u32)?;
                         }
                     }
                 }
@@ -3541,7 +3953,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u16(r.data[i] as u16)?;
+                            bow.write_u16(r.data.get_value(i) as u16)?;
                         }
                     }
                 }
@@ -3550,7 +3962,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u8(r.data[i] as u8)?;
+                            bow.write_u8(r.data.get_value(i) as u8)?;
                         }
                     }
                 }
@@ -3559,7 +3971,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i64(r.data[i] as i64)?;
+                            bow.write_i64(r.data.get_value(i) as i64)?;
                         }
                     }
                 }
@@ -3568,7 +3980,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i32(r.data[i] as i32)?;
+                            bow.write_i32(r.data.get_value(i) as i32)?;
                         }
                     }
                 }
@@ -3577,7 +3989,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i16(r.data[i] as i16)?;
+                            bow.write_i16(r.data.get_value(i) as i16)?;
                         }
                     }
                 }
@@ -3586,7 +3998,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i8(r.data[i] as i8)?;
+                            bow.write_i8(r.data.get_value(i) as i8)?;
                         }
                     }
                 }
@@ -3608,7 +4020,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         for row in 0..r.configs.rows {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
+                                let val = r.data.get_value(i) as u32;
                                 bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                                 bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                                 bytes[0] = (val & 0xFF) as u8; // red
@@ -3622,7 +4034,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         for row in 0..r.configs.rows {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
+                                let val = r.data.get_value(i) as u32;
                                 bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                                 bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                                 bytes[0] = (val & 0xFF) as u8; // red
@@ -3666,7 +4078,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_f64(r.data[i]).expect("Error writing byte data to file.");
+                            bow.write_f64(r.data.get_value(i)).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3675,7 +4087,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_f32(r.data[i] as f32).expect("Error writing byte data to file.");
+                            bow.write_f32(r.data.get_value(i) as f32).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3684,7 +4096,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u64(r.data[i] as u64).expect("Error writing byte data to file.");
+                            bow.write_u64(r.data.get_value(i) as u64).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3693,7 +4105,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u32(r.data[i] as u32).expect("Error writing byte data to file.");
+                            bow.write_u32(r.data.get_value(i) as u32).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3702,7 +4114,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u16(r.data[i] as u16).expect("Error writing byte data to file.");
+                            bow.write_u16(r.data.get_value(i) as u16).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3711,7 +4123,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_u8(r.data[i] as u8).expect("Error writing byte data to file.");
+                            bow.write_u8(r.data.get_value(i) as u8).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3720,7 +4132,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i64(r.data[i] as i64).expect("Error writing byte data to file.");
+                            bow.write_i64(r.data.get_value(i) as i64).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3729,7 +4141,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i32(r.data[i] as i32).expect("Error writing byte data to file.");
+                            bow.write_i32(r.data.get_value(i) as i32).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3738,7 +4150,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i16(r.data[i] as i16).expect("Error writing byte data to file.");
+                            bow.write_i16(r.data.get_value(i) as i16).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3747,7 +4159,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            bow.write_i8(r.data[i] as i8).expect("Error writing byte data to file.");
+                            bow.write_i8(r.data.get_value(i) as i8).expect("Error writing byte data to file.");
                         }
                     }
                 }
@@ -3769,7 +4181,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         for row in 0..r.configs.rows {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
+                                let val = r.data.get_value(i) as u32;
                                 bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                                 bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                                 bytes[0] = (val & 0xFF) as u8; // red
@@ -3783,7 +4195,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         for row in 0..r.configs.rows {
                             for col in 0..r.configs.columns {
                                 i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
+                                let val = r.data.get_value(i) as u32;
                                 bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
                                 bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
                                 bytes[0] = (val & 0xFF) as u8; // red
@@ -0,0 +1,517 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Block-oriented GeoTIFF reading and writing for rasters that are too large to be
+//! held in memory. `GeoTiffReader` decodes strips or tiles on demand and retains the
+//! most recently used blocks in a cache of bounded size, while `GeoTiffWriter` writes
+//! an output file one row at a time.
+
+use super::*;
+use std::sync::Arc;
+
+/// The default size of the block cache of a `GeoTiffReader`, in bytes.
+pub const DEFAULT_CACHE_SIZE: usize = 256 * 1024 * 1024;
+
+/// The number of grid cells above which tools that support streaming process a GeoTIFF
+/// in bands of rows, rather than reading the entire grid into memory.
+pub const STREAMING_THRESHOLD: usize = 250_000_000;
+
+/// The approximate number of grid cells in each band of rows processed by `stream_rows`.
+const BAND_SIZE: usize = 16_000_000;
+
+/// Returns true if a tool should stream its input through memory in bands of rows. This is
+/// the case when both the input and output are GeoTIFF files and the input grid contains
+/// more than `STREAMING_THRESHOLD` cells.
+pub fn use_streaming(input_file: &str, output_file: &str) -> bool {
+    let is_tiff = |f: &str| {
+        let f = f.to_lowercase();
+        f.ends_with(".tif") || f.ends_with(".tiff")
+    };
+    if !is_tiff(input_file) || !is_tiff(output_file) {
+        return false;
+    }
+    match GeoTiffReader::new(input_file) {
+        Ok(reader) => reader.configs.rows * reader.configs.columns > STREAMING_THRESHOLD,
+        Err(_) => false,
+    }
+}
+
+struct CachedBlock {
+    data: RasterData,
+    last_used: u64,
+}
+
+/// Reads the cells of a GeoTIFF file on demand, one strip or tile at a time, rather
+/// than reading the entire grid into memory as `Raster::new` does. Decoded blocks are
+/// held in a least-recently-used cache, so that the memory requirement is bounded by
+/// the cache size rather than by the size of the image.
+pub struct GeoTiffReader {
+    pub file_name: String,
+    pub configs: RasterConfigs,
+    reader: ByteOrderReader<BufReader<File>>,
+    layout: TiffLayout,
+    cache: HashMap<usize, CachedBlock>,
+    cache_size: usize,
+    cache_bytes: usize,
+    counter: u64,
+}
+
+impl GeoTiffReader {
+    /// Opens a GeoTIFF file and reads its header, but none of its image data.
+    pub fn new(file_name: &str) -> Result<GeoTiffReader, Error> {
+        let mut configs = RasterConfigs::default();
+        let f = File::open(file_name)?;
+        let mut reader = ByteOrderReader::<BufReader<File>>::new(BufReader::new(f), configs.endian);
+        let layout = read_geotiff_layout(&mut reader, &mut configs)?;
+        Ok(GeoTiffReader {
+            file_name: file_name.to_string(),
+            configs,
+            reader,
+            layout,
+            cache: HashMap::new(),
+            cache_size: DEFAULT_CACHE_SIZE,
+            cache_bytes: 0,
+            counter: 0,
+        })
+    }
+
+    /// Sets the maximum size of the block cache, in bytes. At least one block is always
+    /// retained, regardless of the cache size.
+    pub fn with_cache_size(mut self, cache_size: usize) -> GeoTiffReader {
+        self.cache_size = cache_size;
+        self
+    }
+
+    /// Returns the number of bands in the file.
+    pub fn num_bands(&self) -> usize {
+        self.layout.num_bands
+    }
+
+    /// Returns the height and width of the strips or tiles in which the image is stored.
+    /// Reading windows that are aligned with these blocks minimizes decoding.
+    pub fn block_size(&self) -> (usize, usize) {
+        (self.layout.block_height, self.layout.block_width)
+    }
+
+    /// Returns the value of a grid cell in the first band. Cells outside of the grid
+    /// return the nodata value.
+    pub fn get_value(&mut self, row: isize, column: isize) -> Result<f64, Error> {
+        self.get_band_value(0, row, column)
+    }
+
+    /// Returns the value of a grid cell in a band (zero-based). Cells outside of the
+    /// grid, or in a band that does not exist, return the nodata value.
+    pub fn get_band_value(&mut self, band: usize, row: isize, column: isize) -> Result<f64, Error> {
+        if band >= self.layout.num_bands
+            || row < 0
+            || column < 0
+            || row as usize >= self.layout.height
+            || column as usize >= self.layout.width
+        {
+            return Ok(self.configs.nodata);
+        }
+        let (row, column) = (row as usize, column as usize);
+        let plane = self.plane(band);
+        let block_row = row / self.layout.block_height;
+        let block_col = column / self.layout.block_width;
+        let (first_band, _) = self.layout.block_bands(plane);
+        let (ymin, ymax, xmin, xmax) = self.layout.block_extent(block_row, block_col);
+        let columns = xmax - xmin;
+        let idx = (band - first_band) * (ymax - ymin) * columns + (row - ymin) * columns + column - xmin;
+        let block = self.get_block(plane, block_row, block_col)?;
+        Ok(block.get_value(idx))
+    }
+
+    /// Returns the values of a row of the first band.
+    pub fn get_row_data(&mut self, row: isize) -> Result<Vec<f64>, Error> {
+        Ok(self.read_window(row, 0, 1, self.configs.columns)?.get_row_data(0))
+    }
+
+    /// Reads a window of the first band, with its top-left corner at `row` and `column`,
+    /// into an `Array2D`. The window may extend beyond the edges of the grid, in which
+    /// case those cells are assigned the nodata value; this is convenient for reading
+    /// the halo of rows and columns needed by a neighbourhood operation.
+    pub fn read_window(&mut self, row: isize, column: isize, rows: usize, columns: usize) -> Result<Array2D<f64>, Error> {
+        self.read_band_window(0, row, column, rows, columns)
+    }
+
+    /// Reads a window of a band (zero-based). See `read_window`.
+    pub fn read_band_window(
+        &mut self,
+        band: usize,
+        row: isize,
+        column: isize,
+        rows: usize,
+        columns: usize,
+    ) -> Result<Array2D<f64>, Error> {
+        let nodata = self.configs.nodata;
+        let mut window: Array2D<f64> = Array2D::new(rows as isize, columns as isize, nodata, nodata)?;
+        if band >= self.layout.num_bands {
+            return Ok(window);
+        }
+
+        // the part of the window that overlaps the grid
+        let r0 = row.max(0) as usize;
+        let c0 = column.max(0) as usize;
+        let r1 = (row + rows as isize).clamp(0, self.layout.height as isize) as usize;
+        let c1 = (column + columns as isize).clamp(0, self.layout.width as isize) as usize;
+        if r0 >= r1 || c0 >= c1 {
+            return Ok(window);
+        }
+
+        let plane = self.plane(band);
+        let (first_band, _) = self.layout.block_bands(plane);
+        let (block_height, block_width) = (self.layout.block_height, self.layout.block_width);
+        for block_row in r0 / block_height..=(r1 - 1) / block_height {
+            for block_col in c0 / block_width..=(c1 - 1) / block_width {
+                let (ymin, ymax, xmin, xmax) = self.layout.block_extent(block_row, block_col);
+                let block_columns = xmax - xmin;
+                let band_offset = (band - first_band) * (ymax - ymin) * block_columns;
+                let block = self.get_block(plane, block_row, block_col)?;
+                for y in ymin.max(r0)..ymax.min(r1) {
+                    for x in xmin.max(c0)..xmax.min(c1) {
+                        window.set_value(
+                            y as isize - row,
+                            x as isize - column,
+                            block.get_value(band_offset + (y - ymin) * block_columns + x - xmin),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(window)
+    }
+
+    /// Reads `rows` rows of the first band, starting at `row`, into an in-memory `Raster`
+    /// with the extent of those rows. Rows beyond the top and bottom edges of the grid are
+    /// filled with nodata. Note that the minimum and maximum values of the returned raster
+    /// are those of the rows that were read.
+    pub fn read_rows(&mut self, row: isize, rows: usize) -> Result<Raster, Error> {
+        let window = self.read_window(row, 0, rows, self.configs.columns)?;
+        let mut configs = self.configs.clone();
+        configs.rows = rows;
+        configs.bands = 1;
+        configs.north = self.configs.north - row as f64 * self.configs.resolution_y;
+        configs.south = configs.north - rows as f64 * self.configs.resolution_y;
+        let mut r = Raster::initialize_using_array2d(&self.file_name, &configs, window);
+        r.update_min_max();
+        Ok(r)
+    }
+
+    /// Applies a neighbourhood operation to the first band of the file in bands of rows,
+    /// writing the output with `writer`. For each band, `process` is given the band's rows,
+    /// plus `halo` rows above and below it, as an in-memory `Raster` and the range of rows
+    /// of that raster (`first_row..last_row`) for which it must return output rows, in order.
+    /// Cells beyond the edges of the grid have the nodata value, just as they would with
+    /// `Raster::get_value`, and so operations written for an in-memory `Raster` give the
+    /// same output when they are streamed.
+    pub fn stream_rows<F>(&mut self, writer: &mut GeoTiffWriter, halo: usize, mut process: F) -> Result<(), Error>
+    where
+        F: FnMut(Arc<Raster>, isize, isize) -> Result<Vec<Vec<f64>>, Error>,
+    {
+        let rows = self.configs.rows;
+        let band_rows = (BAND_SIZE / self.configs.columns.max(1)).max(1);
+        let mut row = 0;
+        while row < rows {
+            let n = band_rows.min(rows - row);
+            let band = self.read_rows(row as isize - halo as isize, n + 2 * halo)?;
+            let output_rows = process(Arc::new(band), halo as isize, (halo + n) as isize)?;
+            if output_rows.len() != n {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "The number of output rows does not match the number of rows processed.",
+                ));
+            }
+            for values in output_rows {
+                writer.write_row(&values)?;
+            }
+            row += n;
+        }
+        Ok(())
+    }
+
+    fn plane(&self, band: usize) -> usize {
+        if self.layout.num_planes > 1 {
+            band
+        } else {
+            0
+        }
+    }
+
+    // Returns a decoded block, reading it from the file if it is not already cached.
+    fn get_block(&mut self, plane: usize, block_row: usize, block_col: usize) -> Result<&RasterData, Error> {
+        let key = (plane * self.layout.blocks_down + block_row) * self.layout.blocks_across + block_col;
+        self.counter += 1;
+        if !self.cache.contains_key(&key) {
+            let data = read_block(&mut self.reader, &self.configs, &self.layout, plane, block_row, block_col)?;
+            let size = data.size_in_bytes();
+            self.evict(size);
+            self.cache_bytes += size;
+            self.cache.insert(key, CachedBlock { data, last_used: 0 });
+        }
+        let block = self.cache.get_mut(&key).unwrap();
+        block.last_used = self.counter;
+        Ok(&block.data)
+    }
+
+    // Removes the least recently used blocks until a block of `size` bytes will fit.
+    fn evict(&mut self, size: usize) {
+        while !self.cache.is_empty() && self.cache_bytes + size > self.cache_size {
+            let oldest = *self
+                .cache
+                .iter()
+                .min_by_key(|(_, block)| block.last_used)
+                .unwrap()
+                .0;
+            if let Some(block) = self.cache.remove(&oldest) {
+                self.cache_bytes -= block.data.size_in_bytes();
+            }
+        }
+    }
+}
+
+/// Writes a GeoTIFF file one row at a time, so that an output raster need not be held
+/// in memory. Rows must be written in order, from the top of the grid to the bottom,
+/// band by band for multi-band files. Each row is stored as a separate strip, which is
+/// compressed as specified by the `compression` and `predictor` fields of the configs or,
+/// by default, DEFLATE compressed when raster compression is enabled in the settings.
+///
+/// ```ignore
+/// let mut writer = GeoTiffWriter::new("output.tif", &configs)?;
+/// for row in 0..configs.rows as isize {
+///     writer.write_row(&values)?;
+/// }
+/// writer.finish()?;
+/// ```
+pub struct GeoTiffWriter {
+    pub file_name: String,
+    pub configs: RasterConfigs,
+    writer: BufWriter<File>,
+    encoder: BlockEncoder,
+    is_big_tiff: bool,
+    strip_offsets: Vec<u64>,
+    strip_byte_counts: Vec<u64>,
+    current_offset: u64,
+    bytes: ByteOrderWriter<Vec<u8>>,
+    // The value with which NaN values are written, if the nodata value is NaN and the data
+    // type is an integer type, which cannot hold NaN.
+    nan_value: Option<f64>,
+}
+
+impl GeoTiffWriter {
+    /// Creates the output file and writes its header. The configs describe the grid
+    /// extent, data type, number of bands and georeferencing of the output.
+    pub fn new(file_name: &str, configs: &RasterConfigs) -> Result<GeoTiffWriter, Error> {
+        let mut configs = configs.clone();
+        match configs.data_type {
+            DataType::F64
+            | DataType::F32
+            | DataType::U64
+            | DataType::U32
+            | DataType::U16
+            | DataType::U8
+            | DataType::I64
+            | DataType::I32
+            | DataType::I16
+            | DataType::I8 => {
+                if configs.photometric_interp != PhotometricInterpretation::Boolean {
+                    configs.photometric_interp = PhotometricInterpretation::Continuous;
+                }
+            }
+            DataType::RGB24 | DataType::RGBA32 => {
+                configs.photometric_interp = PhotometricInterpretation::RGB;
+                configs.bands = 1;
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The GeoTIFF row writer does not support the {:?} data type.", configs.data_type),
+                ));
+            }
+        }
+        configs.bands = configs.bands.max(1);
+        let nan_value = if configs.nodata.is_nan() && configs.data_type.is_integer() {
+            configs.nodata = integer_nodata(configs.data_type);
+            Some(configs.nodata)
+        } else {
+            None
+        };
+        let encoder = BlockEncoder::new(&configs, configs.columns)?;
+        let num_strips = configs.rows * configs.bands as usize;
+        let is_big_tiff = 8usize + num_strips * configs.columns * configs.data_type.get_data_size() >= 4_000_000_000;
+
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        if configs.endian == Endianness::LittleEndian {
+            write_bytes(&mut writer, "II".as_bytes())?;
+        } else {
+            write_bytes(&mut writer, "MM".as_bytes())?;
+        }
+        // The offset to the first IFD is written once the image data are complete.
+        if !is_big_tiff {
+            write_u16(&mut writer, configs.endian, 42u16)?;
+            write_u32(&mut writer, configs.endian, 0u32)?;
+        } else {
+            write_u16(&mut writer, configs.endian, 43u16)?;
+            write_u16(&mut writer, configs.endian, 8u16)?;
+            write_u16(&mut writer, configs.endian, 0u16)?;
+            write_u64(&mut writer, configs.endian, 0u64)?;
+        }
+
+        let endian = configs.endian;
+        Ok(GeoTiffWriter {
+            file_name: file_name.to_string(),
+            configs,
+            writer,
+            encoder,
+            is_big_tiff,
+            strip_offsets: Vec::with_capacity(num_strips),
+            strip_byte_counts: Vec::with_capacity(num_strips),
+            current_offset: if is_big_tiff { 16 } else { 8 },
+            bytes: ByteOrderWriter::<Vec<u8>>::new(vec![], endian),
+            nan_value,
+        })
+    }
+
+    /// Returns the number of rows written so far.
+    pub fn rows_written(&self) -> usize {
+        self.strip_offsets.len()
+    }
+
+    /// Writes the next row of the output. Values beyond the number of columns are
+    /// ignored and missing values are written as nodata.
+    pub fn write_row(&mut self, values: &[f64]) -> Result<(), Error> {
+        if self.rows_written() >= self.configs.rows * self.configs.bands as usize {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "All of the rows of the GeoTIFF file have already been written.",
+            ));
+        }
+        let bytes = &mut self.bytes;
+        let nan_value = self.nan_value;
+        let row_values = values
+            .iter()
+            .map(|&z| match nan_value {
+                Some(v) if z.is_nan() => v,
+                _ => z,
+            })
+            .chain(std::iter::repeat(self.configs.nodata))
+            .take(self.configs.columns);
+        for z in row_values {
+            write_cell_value(bytes, self.configs.data_type, z)?;
+        }
+        let strip = mem::replace(bytes, ByteOrderWriter::<Vec<u8>>::new(vec![], self.configs.endian)).into_inner();
+        let strip = self.encoder.encode(strip)?;
+        write_bytes(&mut self.writer, &strip)?;
+        self.strip_offsets.push(self.current_offset);
+        self.strip_byte_counts.push(strip.len() as u64);
+        self.current_offset += strip.len() as u64;
+        if self.encoder.is_compressed() && strip.len() % 2 != 0 {
+            // Compressed strips must start on a word (i.e. an even offset).
+            write_u8(&mut self.writer, 0u8)?;
+            self.current_offset += 1;
+        }
+        Ok(())
+    }
+
+    /// Completes the file by writing its image file directory. All of the rows of the
+    /// output must have been written.
+    pub fn finish(mut self) -> Result<(), Error> {
+        let num_strips = self.configs.rows * self.configs.bands as usize;
+        if self.rows_written() != num_strips {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Only {} of the {} rows of the GeoTIFF file were written.",
+                    self.rows_written(),
+                    num_strips
+                ),
+            ));
+        }
+        write_geotiff_ifd(
+            &mut self.writer,
+            &self.configs,
+            &self.encoder,
+            self.is_big_tiff,
+            BlockLayout::Strips,
+            self.current_offset,
+            self.strip_offsets,
+            self.strip_byte_counts,
+        )?;
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TILE_SIZE: usize = 16;
+    const TILE_BYTES: usize = TILE_SIZE * TILE_SIZE * 4;
+
+    fn write_tiled_raster(file_name: &str) {
+        let configs = RasterConfigs {
+            rows: 2 * TILE_SIZE,
+            columns: 3 * TILE_SIZE,
+            nodata: -32768.0,
+            north: 100.0,
+            south: 100.0 - (2 * TILE_SIZE) as f64,
+            west: 0.0,
+            east: (3 * TILE_SIZE) as f64,
+            resolution_x: 1.0,
+            resolution_y: 1.0,
+            data_type: DataType::F32,
+            photometric_interp: PhotometricInterpretation::Continuous,
+            ..Default::default()
+        };
+        let mut raster = Raster::initialize_using_config(file_name, &configs);
+        raster.configs.cog = Some(CogOptions {
+            tile_size: TILE_SIZE,
+            ..Default::default()
+        });
+        for row in 0..configs.rows as isize {
+            for col in 0..configs.columns as isize {
+                raster.set_value(row, col, (row * 100 + col) as f64);
+            }
+        }
+        raster.write().expect("Error writing the raster.");
+    }
+
+    #[test]
+    fn test_least_recently_used_blocks_are_evicted() {
+        let file_name = std::env::temp_dir()
+            .join(format!("whitebox_raster_lru_{}.tif", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        write_tiled_raster(&file_name);
+        let mut reader = GeoTiffReader::new(&file_name)
+            .unwrap()
+            .with_cache_size(2 * TILE_BYTES);
+        let tile = |t: usize| (5isize, (t * TILE_SIZE + 5) as isize);
+
+        // tiles 0 and 1 fill the cache; tile 0 is then used again, and so it is tile 1
+        // that is evicted to make room for tile 2
+        for t in [0, 1, 0, 2] {
+            let (row, col) = tile(t);
+            assert_eq!(reader.get_value(row, col).unwrap(), (row * 100 + col) as f64);
+        }
+        let mut cached = reader.cache.keys().copied().collect::<Vec<usize>>();
+        cached.sort();
+        assert_eq!(cached, vec![0, 2]);
+        assert_eq!(reader.cache_bytes, 2 * TILE_BYTES);
+
+        // a cache smaller than a block still holds the last block read
+        let mut reader = GeoTiffReader::new(&file_name).unwrap().with_cache_size(0);
+        for t in 0..3 {
+            let (row, col) = tile(t);
+            assert_eq!(reader.get_value(row, col).unwrap(), (row * 100 + col) as f64);
+            assert_eq!(reader.cache.keys().copied().collect::<Vec<usize>>(), vec![t]);
+        }
+        let _ = std::fs::remove_file(&file_name);
+    }
+}
@@ -17,6 +17,10 @@ pub const COMPRESS_JPEG: u16 = 7;
 pub const COMPRESS_DEFLATE: u16 = 8; // zlib compression.
 pub const COMPRESS_PACKBITS: u16 = 32773;
 pub const COMPRESS_DEFLATEOLD: u16 = 32946; // Superseded by cDeflate.
+pub const COMPRESS_LERC: u16 = 34887;
+pub const COMPRESS_LZMA: u16 = 34925;
+pub const COMPRESS_ZSTD: u16 = 50000;
+pub const COMPRESS_WEBP: u16 = 50001;
 
 pub const DT_BYTE: u16 = 1;
 pub const DT_ASCII: u16 = 2;
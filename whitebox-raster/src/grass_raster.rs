@@ -9,7 +9,7 @@ use std::io::Error;
 pub fn read_grass_raster(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read the file
     let f = File::open(file_name)?;
@@ -78,7 +78,7 @@ pub fn read_grass_raster(
                 let mut val_num;
                 for val in vec {
                     val_num = val.trim().to_string().parse::<f64>().unwrap();
-                    if val_num != configs.nodata {
+                    if !configs.is_nodata(val_num) {
                         data.push(val_num * multiplier);
                     } else {
                         data.push(val_num);
@@ -172,9 +172,9 @@ pub fn write_grass_raster<'a>(r: &'a mut Raster) -> Result<(), Error> {
     if r.configs.data_type == DataType::F32 || r.configs.data_type == DataType::F64 {
         for i in 0..num_cells {
             if col < r.configs.columns - 1 {
-                s2 += &format!("{:.*} ", 2, r.data[i]);
+                s2 += &format!("{:.*} ", 2, r.data.get_value(i));
             } else {
-                s2 += &format!("{:.*}\n", 2, r.data[i]);
+                s2 += &format!("{:.*}\n", 2, r.data.get_value(i));
             }
             col += 1;
             if col == r.configs.columns {
@@ -186,9 +186,9 @@ pub fn write_grass_raster<'a>(r: &'a mut Raster) -> Result<(), Error> {
     } else {
         for i in 0..num_cells {
             if col < r.configs.columns - 1 {
-                s2 += &format!("{:.*} ", 0, r.data[i]);
+                s2 += &format!("{:.*} ", 0, r.data.get_value(i));
             } else {
-                s2 += &format!("{:.*}\n", 0, r.data[i]);
+                s2 += &format!("{:.*}\n", 0, r.data.get_value(i));
             }
             col += 1;
             if col == r.configs.columns {
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 02/06/2017
-Last Modified: 09/02/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -20,6 +20,7 @@ mod esri_bil;
 pub mod geotiff;
 mod grass_raster;
 mod idrisi_raster;
+mod raster_data;
 mod saga_raster;
 mod surfer7_raster;
 mod surfer_ascii_raster;
@@ -29,8 +30,13 @@ use self::arcascii_raster::*;
 use self::arcbinary_raster::*;
 use self::esri_bil::*;
 use self::geotiff::*;
+pub use self::geotiff::{
+    use_streaming, CogOptions, GeoTiffReader, GeoTiffWriter, OverviewResampling, TiffCompression,
+    TiffPlanarConfig, TiffPredictor,
+};
 use self::grass_raster::*;
 use self::idrisi_raster::*;
+pub use self::raster_data::RasterData;
 use self::saga_raster::*;
 use self::surfer7_raster::*;
 use self::surfer_ascii_raster::*;
@@ -53,6 +59,31 @@ use std::sync::Arc;
 use std::thread;
 // use rayon::prelude::*;
 
+/// Returns true if `value` is the nodata value `nodata`. Unlike `==`, this is true of a NaN
+/// value when the nodata value is NaN. It takes values of any type, e.g. the f32 or integer
+/// values that tools keep in their intermediate grids.
+#[allow(clippy::eq_op)]
+pub fn is_nodata_value<T: PartialEq>(value: T, nodata: T) -> bool {
+    // NaN is the only value that is not equal to itself
+    value == nodata || (value != value && nodata != nodata)
+}
+
+// The only nodata value of Surfer grids.
+const SURFER_NODATA: f64 = 1.71041e38;
+
+/// Returns the nodata value with which the NaN cells of a raster of an integer data type are
+/// written, when its nodata value is NaN, which integer types cannot hold.
+pub(crate) fn integer_nodata(data_type: DataType) -> f64 {
+    match data_type {
+        DataType::I8 => i8::MIN as f64,
+        DataType::U8 => u8::MAX as f64,
+        DataType::U16 => u16::MAX as f64,
+        DataType::U32 => u32::MAX as f64,
+        DataType::U64 => u64::MAX as f64,
+        _ => -32768.0,
+    }
+}
+
 /// Raster is a common data structure that abstracts over several raster data formats,
 /// including GeoTIFFs, ArcGIS ASCII and binary rasters, Whitebox rasters, Idrisi
 /// rasters, Saga rasters, and GRASS ASCII rasters.
@@ -67,15 +98,30 @@ use std::thread;
 /// // and location of an existing file.
 /// let mut output = Raster::initialize_using_file(&output_file, &input);
 /// ```
+///
+/// Cell values are held in the raster's native data type (see `RasterData`), so that,
+/// for example, a `U8` raster uses one byte per cell. They are always read and written
+/// as `f64`.
+///
+/// A `Raster` may contain multiple bands (`configs.bands`), e.g. multispectral imagery
+/// read from a multi-band GeoTIFF. The bands are stored band-sequentially. Indexing,
+/// `get_value`, `set_value`, and the other cell and row accessors operate on the first
+/// band; the `get_band_value`, `set_band_value`, `get_band_row_data`, and
+/// `set_band_row_data` functions access any band.
+///
+/// Cells are compared with the nodata value using `is_nodata`, rather than `==`, since the
+/// nodata value may be NaN, as it is in floating-point rasters written by GDAL.
 #[derive(Default, Clone)]
 pub struct Raster {
     pub file_name: String,
     pub file_mode: String,
     pub raster_type: RasterType,
     pub configs: RasterConfigs,
-    data: Vec<f64>,
+    data: RasterData,
 }
 
+/// Indexing a raster stored as a 32-bit type builds a 64-bit copy of its values;
+/// `get_value` reads the native storage directly.
 impl Index<(isize, isize)> for Raster {
     type Output = f64;
 
@@ -100,10 +146,12 @@ impl Index<(isize, isize)> for Raster {
             return &self.configs.nodata;
         }
         let idx: usize = r * self.configs.columns + c;
-        &self.data[idx]
+        self.data.get_ref(idx)
     }
 }
 
+/// Mutable indexing converts the values to f64 storage; `set_value` keeps the native
+/// type whenever it can represent the value.
 impl IndexMut<(isize, isize)> for Raster {
     fn index_mut<'a>(&'a mut self, index: (isize, isize)) -> &'a mut f64 {
         let row = index.0;
@@ -123,7 +171,7 @@ impl IndexMut<(isize, isize)> for Raster {
             return &mut self.configs.nodata;
         }
         let idx = r * self.configs.columns + c;
-        &mut self.data[idx as usize]
+        self.data.get_mut(idx)
     }
 }
 
@@ -182,15 +230,12 @@ impl Raster {
                 }
             }
 
-            // The nodata value can't be NaN or Inf because Rust does not handle equality using == with either. 
-            // If the nodata value is either, modify it in memory so that the various tools will work as expected.
-            if r.configs.nodata.is_nan() || r.configs.nodata.is_infinite() {
-                r.configs.nodata = -32768.0;
-                for i in 0..r.data.len() {
-                    if r.data[i].is_nan() || r.data[i].is_infinite() {
-                        r.data[i] = -32768.0;
-                    }
-                }
+            // Formats that are read as f64 values are stored in their native type.
+            r.data.compact(r.configs.data_type);
+
+            // Only the GeoTIFF reader reads more than the first band of a file.
+            if r.data.len() < r.num_cells() * r.num_bands() {
+                r.configs.bands = 1;
             }
 
             return Ok(r);
@@ -244,15 +289,12 @@ impl Raster {
         output.configs.geo_double_params = configs.geo_double_params.clone();
         output.configs.geo_ascii_params = configs.geo_ascii_params.clone();
 
-        if output.raster_type == RasterType::SurferAscii
-            || output.raster_type == RasterType::Surfer7Binary
-        {
-            output.configs.nodata = 1.71041e38;
-        }
-        output
-            .data
-            .reserve(output.configs.rows * output.configs.columns);
-        output.data = vec![output.configs.nodata; output.configs.rows * output.configs.columns];
+        output.use_format_nodata();
+        output.data = RasterData::filled(
+            output.configs.data_type,
+            output.configs.nodata,
+            output.configs.rows * output.configs.columns,
+        );
 
         output
     }
@@ -300,20 +342,16 @@ impl Raster {
         output.configs.geo_double_params = configs.geo_double_params.clone();
         output.configs.geo_ascii_params = configs.geo_ascii_params.clone();
 
-        if output.raster_type == RasterType::SurferAscii
-            || output.raster_type == RasterType::Surfer7Binary
-        {
-            output.configs.nodata = 1.71041e38;
-        }
-        output
-            .data
-            .reserve(output.configs.rows * output.configs.columns);
-        // output.data = vec![output.configs.nodata; output.configs.rows * output.configs.columns];
+        output.data = RasterData::with_capacity(
+            output.configs.data_type,
+            output.configs.rows * output.configs.columns,
+        );
         for row in 0..output.configs.rows {
             for col in 0..output.configs.columns {
                 output.data.push(data.get_value(row as isize, col as isize).as_());
             }
         }
+        output.use_format_nodata();
 
         output
     }
@@ -360,15 +398,12 @@ impl Raster {
         output.configs.geo_double_params = input.configs.geo_double_params.clone();
         output.configs.geo_ascii_params = input.configs.geo_ascii_params.clone();
 
-        if output.raster_type == RasterType::SurferAscii
-            || output.raster_type == RasterType::Surfer7Binary
-        {
-            output.configs.nodata = 1.71041e38;
-        }
-        output
-            .data
-            .reserve(output.configs.rows * output.configs.columns);
-        output.data = vec![output.configs.nodata; output.configs.rows * output.configs.columns];
+        output.use_format_nodata();
+        output.data = RasterData::filled(
+            output.configs.data_type,
+            output.configs.nodata,
+            output.configs.rows * output.configs.columns,
+        );
         output
     }
 
@@ -418,22 +453,52 @@ impl Raster {
         output.configs.geo_double_params = configs.geo_double_params.clone();
         output.configs.geo_ascii_params = configs.geo_ascii_params.clone();
 
-        if output.raster_type == RasterType::SurferAscii
-            || output.raster_type == RasterType::Surfer7Binary
-        {
-            output.configs.nodata = 1.71041e38;
-        }
-        output
-            .data
-            .reserve_exact(output.configs.rows * output.configs.columns);
+        output.data = RasterData::with_capacity(
+            output.configs.data_type,
+            output.configs.rows * output.configs.columns,
+        );
         for row in 0..array.rows {
             for col in 0..array.columns {
                 output.data.push(array.get_value(row, col).into());
             }
         }
+        output.use_format_nodata();
         output
     }
 
+    /// Sets the name of the file that the `Raster` will be written to, e.g. for a raster
+    /// that was created in memory. The raster format is determined by the file extension.
+    pub fn set_file_name(&mut self, file_name: &str) {
+        let new_file_name = if file_name.contains(".") {
+            file_name.to_string()
+        } else {
+            // likely no extension provided; default to .tif
+            format!("{}.tif", file_name)
+        };
+        self.file_name = new_file_name.clone();
+        self.file_mode = "w".to_string();
+        self.raster_type = get_raster_type_from_file(new_file_name, "w".to_string());
+
+        self.use_format_nodata();
+    }
+
+    /// Surfer grids can only hold the NoData value 1.71041e38. If the raster is to be written
+    /// as a Surfer grid, it is given that NoData value and any NoData cells are updated.
+    fn use_format_nodata(&mut self) {
+        if (self.raster_type == RasterType::SurferAscii
+            || self.raster_type == RasterType::Surfer7Binary)
+            && self.configs.nodata != SURFER_NODATA
+        {
+            let old_nodata = self.configs.nodata;
+            for i in 0..self.data.len() {
+                if is_nodata_value(self.data.get_value(i), old_nodata) {
+                    self.data.set_value(i, SURFER_NODATA);
+                }
+            }
+            self.configs.nodata = SURFER_NODATA;
+        }
+    }
+
     /// Returns the file name of the `Raster`, without the directory and file extension.
     pub fn get_short_filename(&self) -> String {
         let path = Path::new(&self.file_name);
@@ -466,7 +531,7 @@ impl Raster {
             let r: usize = row as usize;
 
             let idx: usize = r * self.configs.columns + c;
-            return self.data[idx];
+            return self.data.get_value(idx);
         }
 
         // it's not within the area of the data
@@ -508,7 +573,7 @@ impl Raster {
             let r: usize = row as usize;
             if c < self.configs.columns && r < self.configs.rows {
                 let idx = r * self.configs.columns + c;
-                self.data[idx] = value;
+                self.data.set_value(idx, value);
             }
         }
     }
@@ -519,10 +584,11 @@ impl Raster {
             let r: usize = row as usize;
             if c < self.configs.columns && r < self.configs.rows {
                 let idx = r * self.configs.columns + c;
-                if self.data[idx] != self.configs.nodata {
-                    self.data[idx] -= value;
+                let z = self.data.get_value(idx);
+                if !self.is_nodata(z) {
+                    self.data.set_value(idx, z - value);
                 } else {
-                    self.data[idx] = value;
+                    self.data.set_value(idx, value);
                 }
             }
         }
@@ -534,15 +600,126 @@ impl Raster {
             let r: usize = row as usize;
             if c < self.configs.columns && r < self.configs.rows {
                 let idx = r * self.configs.columns + c;
-                if self.data[idx] != self.configs.nodata {
-                    self.data[idx] += value;
+                let z = self.data.get_value(idx);
+                if !self.is_nodata(z) {
+                    self.data.set_value(idx, z + value);
                 } else {
-                    self.data[idx] = value;
+                    self.data.set_value(idx, value);
                 }
             }
         }
     }
 
+    /// Returns the number of bands in the raster.
+    pub fn num_bands(&self) -> usize {
+        self.configs.bands.max(1) as usize
+    }
+
+    /// Returns the value contained within a grid cell specified by `row` and
+    /// `column` in a band (zero-based). Cells outside of the grid, or in a band
+    /// that does not exist, return the nodata value.
+    pub fn get_band_value(&self, band: usize, row: isize, column: isize) -> f64 {
+        if band < self.num_bands()
+            && column >= 0
+            && row >= 0
+            && column < self.configs.columns as isize
+            && row < self.configs.rows as isize
+        {
+            let idx = band * self.num_cells() + row as usize * self.configs.columns + column as usize;
+            return self.data.get_value(idx);
+        }
+        self.configs.nodata
+    }
+
+    /// Sets the value of a grid cell specified by `row` and `column` in a band (zero-based).
+    pub fn set_band_value(&mut self, band: usize, row: isize, column: isize, value: f64) {
+        if band < self.num_bands()
+            && column >= 0
+            && row >= 0
+            && column < self.configs.columns as isize
+            && row < self.configs.rows as isize
+        {
+            let idx = band * self.num_cells() + row as usize * self.configs.columns + column as usize;
+            self.data.set_value(idx, value);
+        }
+    }
+
+    /// Returns the values of a row of a band (zero-based).
+    pub fn get_band_row_data(&self, band: usize, row: isize) -> Vec<f64> {
+        let mut values: Vec<f64> = vec![self.configs.nodata; self.configs.columns];
+        if band < self.num_bands() && row >= 0 && row < self.configs.rows as isize {
+            let start = band * self.num_cells() + row as usize * self.configs.columns;
+            self.data.copy_to_slice(start, &mut values);
+        }
+        values
+    }
+
+    /// Sets the values of a row of a band (zero-based).
+    pub fn set_band_row_data(&mut self, band: usize, row: isize, values: Vec<f64>) {
+        if band < self.num_bands() && row >= 0 && row < self.configs.rows as isize {
+            let start = band * self.num_cells() + row as usize * self.configs.columns;
+            let n = values.len().min(self.configs.columns);
+            self.data.copy_from_slice(start, &values[..n]);
+        }
+    }
+
+    /// Sets the number of bands in the raster. The values of existing bands are retained
+    /// and any new bands are filled with the nodata value. Output rasters created using the
+    /// `initialize_using_*` functions have a single band.
+    pub fn set_num_bands(&mut self, num_bands: u8) {
+        let num_bands = num_bands.max(1);
+        self.data.resize(self.num_cells() * num_bands as usize, self.configs.nodata);
+        self.configs.bands = num_bands;
+    }
+
+    /// Reduces the raster to a single band (zero-based) of its bands.
+    pub fn select_band(&mut self, band: usize) -> Result<(), Error> {
+        if band >= self.num_bands() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Band {} is out of range; the raster contains {} band(s).", band + 1, self.num_bands()),
+            ));
+        }
+        let num_cells = self.num_cells();
+        if band > 0 {
+            self.data.copy_within(band * num_cells..(band + 1) * num_cells, 0);
+        }
+        self.data.truncate(num_cells);
+        self.configs.bands = 1;
+        self.configs.display_min = f64::INFINITY;
+        self.configs.display_max = f64::NEG_INFINITY;
+        self.update_min_max();
+        Ok(())
+    }
+
+    /// Splits a multi-band raster into single-band rasters, one for each band.
+    pub fn split_bands(mut self) -> Vec<Raster> {
+        let num_bands = self.num_bands();
+        let num_cells = self.num_cells();
+        let mut rasters = Vec::with_capacity(num_bands);
+        for band in (1..num_bands).rev() {
+            let mut r = Raster {
+                file_name: self.file_name.clone(),
+                file_mode: self.file_mode.clone(),
+                raster_type: self.raster_type.clone(),
+                configs: self.configs.clone(),
+                data: self.data.split_off(band * num_cells),
+            };
+            r.configs.bands = 1;
+            r.configs.display_min = f64::INFINITY;
+            r.configs.display_max = f64::NEG_INFINITY;
+            r.update_min_max();
+            rasters.push(r);
+        }
+        if num_bands > 1 {
+            // the data of the first band is all that remains
+            let _ = self.select_band(0);
+        }
+        rasters.push(self);
+        rasters.reverse();
+        rasters
+    }
+
     pub fn set_row_data(&mut self, row: isize, values: Vec<f64>) {
         for column in 0..values.len() {
             if row >= 0 {
@@ -550,7 +727,7 @@ impl Raster {
                 let r: usize = row as usize;
                 if c < self.configs.columns && r < self.configs.rows {
                     let idx = r * self.configs.columns + c;
-                    self.data[idx] = values[c];
+                    self.data.set_value(idx, values[c]);
                 }
             }
         }
@@ -559,9 +736,7 @@ impl Raster {
     pub fn get_row_data(&self, row: isize) -> Vec<f64> {
         let mut values: Vec<f64> = vec![self.configs.nodata; self.configs.columns];
         if row >= 0 && row < self.configs.rows as isize {
-            for column in 0..values.len() {
-                values[column] = self.data[row as usize * self.configs.columns + column];
-            }
+            self.data.copy_to_slice(row as usize * self.configs.columns, &mut values);
         }
         values
     }
@@ -577,10 +752,11 @@ impl Raster {
         }
         for column in 0..self.configs.columns {
             let idx = r * self.configs.columns + column;
-            if self.data[idx] != self.configs.nodata {
-                self.data[idx] += values[column];
+            let z = self.data.get_value(idx);
+            if !self.is_nodata(z) {
+                self.data.set_value(idx, z + values[column]);
             } else {
-                self.data[idx] = values[column];
+                self.data.set_value(idx, values[column]);
             }
         }
     }
@@ -596,10 +772,11 @@ impl Raster {
         }
         for column in 0..self.configs.columns {
             let idx = r * self.configs.columns + column;
-            if self.data[idx] != self.configs.nodata {
-                self.data[idx] -= values[column];
+            let z = self.data.get_value(idx);
+            if !self.is_nodata(z) {
+                self.data.set_value(idx, z - values[column]);
             } else {
-                self.data[idx] = values[column];
+                self.data.set_value(idx, values[column]);
             }
         }
     }
@@ -645,7 +822,7 @@ impl Raster {
         for row in 0..self.configs.rows as isize {
             for col in 0..self.configs.columns as isize {
                 z = self.get_value(row, col);
-                if z != self.configs.nodata {
+                if !self.is_nodata(z) {
                     data.set_value(row, col, z as f32);
                 }
             }
@@ -658,7 +835,7 @@ impl Raster {
         array: &'a Array2D<T>,
     ) -> Result<(), Error> {
         // quality control
-        if array.rows * array.columns != self.data.len() as isize {
+        if array.rows * array.columns != self.num_cells() as isize {
             return Err(Error::new(
                 ErrorKind::Other,
                 "Rasters must have the same dimensions and extent.",
@@ -668,7 +845,7 @@ impl Raster {
         for row in 0..array.rows {
             for col in 0..array.columns {
                 i = row as usize * self.configs.columns + col as usize;
-                self.data[i] = array.get_value(row, col).into();
+                self.data.set_value(i, array.get_value(row, col).into());
             }
         }
         self.configs.nodata = array.nodata().into();
@@ -676,7 +853,11 @@ impl Raster {
     }
 
     pub fn reinitialize_values(&mut self, value: f64) {
-        self.data = vec![value; self.configs.rows * self.configs.columns];
+        self.data = RasterData::filled(
+            self.data.data_type(),
+            value,
+            self.configs.rows * self.configs.columns * self.num_bands(),
+        );
     }
 
     pub fn get_value_as_rgba(&self, row: isize, column: isize) -> (u8, u8, u8, u8) {
@@ -697,7 +878,7 @@ impl Raster {
             return (0, 0, 0, 0);
         }
         let idx: usize = r * self.configs.columns + c;
-        let z = self.data[idx];
+        let z = self.data.get_value(idx);
 
         let r = (z as u32 & 0xFF) as u8;
         let g = ((z as u32 >> 8) & 0xFF) as u8;
@@ -714,15 +895,15 @@ impl Raster {
             if c < self.configs.columns && r < self.configs.rows {
                 let idx = r * self.configs.columns + c;
                 let (r, g, b, a) = rgba;
-                self.data[idx] += ((a << 24) | (b << 16) | (g << 8) | r) as f64;
+                let z = self.data.get_value(idx) + ((a << 24) | (b << 16) | (g << 8) | r) as f64;
+                self.data.set_value(idx, z);
             }
         }
     }
 
     /// Returns the size of the pixel data in bytes.
     pub fn get_data_size_in_bytes(&self) -> usize {
-        use std::mem;
-        mem::size_of_val(&*self.data)
+        self.data.size_in_bytes()
     }
 
     pub fn get_x_from_column(&self, column: isize) -> f64 {
@@ -750,11 +931,11 @@ impl Raster {
 
     pub fn clip_display_min_max(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in 0..d.len() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_min = d[i];
@@ -765,7 +946,7 @@ impl Raster {
 
         sum = 0;
         for i in (0..d.len()).rev() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_max = d[i];
@@ -777,11 +958,11 @@ impl Raster {
 
     pub fn clip_display_min(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in 0..d.len() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_min = d[i];
@@ -793,11 +974,11 @@ impl Raster {
 
     pub fn clip_display_max(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in (0..d.len()).rev() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_max = d[i];
@@ -845,12 +1026,12 @@ impl Raster {
 
     pub fn clip_min_by_percent(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         let mut val = 0.0;
         for i in 0..self.num_cells() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     val = d[i];
@@ -860,9 +1041,9 @@ impl Raster {
         }
 
         for i in 0..self.data.len() {
-            if self.data[i] != self.configs.nodata {
-                if self.data[i] < val {
-                    self.data[i] = val;
+            if !self.is_nodata(self.data.get_value(i)) {
+                if self.data.get_value(i) < val {
+                    self.data.set_value(i, val);
                 }
             }
         }
@@ -872,12 +1053,12 @@ impl Raster {
 
     pub fn clip_max_by_percent(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         let mut val = 0.0;
         for i in (0..self.num_cells()).rev() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     val = d[i];
@@ -887,9 +1068,9 @@ impl Raster {
         }
 
         for i in 0..self.data.len() {
-            if self.data[i] != self.configs.nodata {
-                if self.data[i] > val {
-                    self.data[i] = val;
+            if !self.is_nodata(self.data.get_value(i)) {
+                if self.data.get_value(i) > val {
+                    self.data.set_value(i, val);
                 }
             }
         }
@@ -899,12 +1080,12 @@ impl Raster {
 
     pub fn clip_min_and_max_by_percent(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         let mut lower_val = 0.0;
         for i in 0..self.num_cells() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     lower_val = d[i];
@@ -916,7 +1097,7 @@ impl Raster {
         let mut upper_val = 0.0;
         let mut sum = 0;
         for i in (0..self.num_cells()).rev() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     upper_val = d[i];
@@ -926,11 +1107,12 @@ impl Raster {
         }
 
         for i in 0..self.data.len() {
-            if self.data[i] != self.configs.nodata {
-                if self.data[i] < lower_val {
-                    self.data[i] = lower_val;
-                } else if self.data[i] > upper_val {
-                    self.data[i] = upper_val;
+            let z = self.data.get_value(i);
+            if !self.is_nodata(z) {
+                if z < lower_val {
+                    self.data.set_value(i, lower_val);
+                } else if z > upper_val {
+                    self.data.set_value(i, upper_val);
                 }
             }
         }
@@ -954,8 +1136,8 @@ impl Raster {
                 let mut max_val = f64::NEG_INFINITY;
                 let mut value: f64;
                 for i in (0..values.len()).filter(|v| v % num_procs == tid) {
-                    value = values[i];
-                    if value != nodata {
+                    value = values.get_value(i);
+                    if !is_nodata_value(value, nodata) {
                         if value < min_val {
                             min_val = value;
                         }
@@ -970,12 +1152,12 @@ impl Raster {
 
         for _ in 0..num_procs {
             let (min_val, max_val) = rx.recv().expect("Error receiving data from thread.");
-            if min_val != nodata {
+            if !is_nodata_value(min_val, nodata) {
                 if min_val < self.configs.minimum {
                     self.configs.minimum = min_val;
                 }
             }
-            if max_val != nodata {
+            if !is_nodata_value(max_val, nodata) {
                 if max_val > self.configs.maximum {
                     self.configs.maximum = max_val;
                 }
@@ -1014,7 +1196,7 @@ impl Raster {
             thread::spawn(move || {
                 let mut count = 0usize;
                 for i in (0..num_cells).filter(|r| r % num_procs == tid) {
-                    if values[i] != nodata {
+                    if !is_nodata_value(values.get_value(i), nodata) {
                         count += 1;
                     }
                 }
@@ -1046,8 +1228,9 @@ impl Raster {
                 let mut sum = 0.0f64;
                 let mut count = 0.0f64;
                 for i in (0..num_cells).filter(|r| r % num_procs == tid) {
-                    if values[i] != nodata {
-                        sum += values[i];
+                    let z = values.get_value(i);
+                    if !is_nodata_value(z, nodata) {
+                        sum += z;
                         count += 1.0;
                     }
                 }
@@ -1084,8 +1267,9 @@ impl Raster {
                 let mut sq_diff_sum = 0.0f64;
                 let mut count = 0.0f64;
                 for i in (0..num_cells).filter(|r| r % num_procs == tid) {
-                    if values[i] != nodata {
-                        sq_diff_sum += (values[i] - mean) * (values[i] - mean);
+                    let z = values.get_value(i);
+                    if !is_nodata_value(z, nodata) {
+                        sq_diff_sum += (z - mean) * (z - mean);
                         count += 1.0;
                     }
                     tx.send((sq_diff_sum, count)).unwrap();
@@ -1108,11 +1292,11 @@ impl Raster {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
         let mut lower_tail = f64::NEG_INFINITY;
         let mut upper_tail = f64::NEG_INFINITY;
-        let mut d = self.data.clone();
+        let mut d = self.data.to_vec();
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in 0..d.len() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     lower_tail = d[i];
@@ -1123,7 +1307,7 @@ impl Raster {
 
         sum = 0;
         for i in (0..d.len()).rev() {
-            if d[i] != self.configs.nodata {
+            if !self.is_nodata(d[i]) {
                 sum += 1;
                 if sum >= t {
                     upper_tail = d[i];
@@ -1136,12 +1320,41 @@ impl Raster {
     }
 
     pub fn write(&mut self) -> Result<(), Error> {
+        // Integer data types cannot hold a NaN nodata value, and so the NaN cells are written
+        // with a finite nodata value. The raster is restored once the file is written, so that
+        // it may continue to be used.
+        if !self.configs.nodata.is_nan() || !self.configs.data_type.is_integer() {
+            return self.write_file();
+        }
+        let nodata = integer_nodata(self.configs.data_type);
+        let nan_cells = (0..self.data.len())
+            .filter(|&i| self.data.get_value(i).is_nan())
+            .collect::<Vec<usize>>();
+        for &i in &nan_cells {
+            self.data.set_value(i, nodata);
+        }
+        self.configs.nodata = nodata;
+        let result = self.write_file();
+        for &i in &nan_cells {
+            self.data.set_value(i, f64::NAN);
+        }
+        self.configs.nodata = f64::NAN;
+        result
+    }
+
+    fn write_file(&mut self) -> Result<(), Error> {
         if !self.file_mode.contains("w") {
             return Err(Error::new(
                 ErrorKind::Other,
                 "Cannot write raster that is not created in write mmode ('w').",
             ));
         }
+        if self.num_bands() > 1 && self.raster_type != RasterType::GeoTiff {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "Multi-band rasters can only be written to GeoTIFF files.",
+            ));
+        }
         match self.raster_type {
             RasterType::ArcAscii => {
                 let _ = match write_arcascii(self) {
@@ -1231,28 +1444,12 @@ impl Raster {
     }
 
     pub fn is_in_geographic_coordinates(&self) -> bool {
-        if self.configs.west < -180f64
-            || self.configs.east > 180f64
-            || self.configs.north > 90f64
-            || self.configs.south < -90f64
-        {
-            return false;
-        }
-        if self.configs.epsg_code == 4322
-            || self.configs.epsg_code == 4326
-            || self.configs.epsg_code == 4629
-            || self.configs.epsg_code == 4277
-        {
-            return true;
-        }
-        let wkt = self.configs.coordinate_ref_system_wkt.to_lowercase();
-        if !wkt.contains("projcs[") && !wkt.to_lowercase().contains("not specified") {
-            return true;
-        }
-        if self.configs.xy_units.to_lowercase().contains("deg") {
-            return true;
-        }
-        false
+        self.configs.is_in_geographic_coordinates()
+    }
+
+    /// Returns true if `value` is the nodata value of the raster.
+    pub fn is_nodata(&self, value: f64) -> bool {
+        self.configs.is_nodata(value)
     }
 }
 
@@ -1262,6 +1459,7 @@ pub struct RasterConfigs {
     pub rows: usize,
     pub columns: usize,
     pub bands: u8,
+    /// The nodata value, which may be NaN. Compare values with it using `is_nodata`.
     pub nodata: f64,
     pub north: f64,
     pub south: f64,
@@ -1292,6 +1490,49 @@ pub struct RasterConfigs {
     pub geo_double_params: Vec<f64>,
     pub geo_ascii_params: String,
     pub metadata: Vec<String>,
+    /// When set, GeoTIFF output is written as a Cloud-Optimized GeoTIFF using these options.
+    pub cog: Option<CogOptions>,
+    /// The compression method of GeoTIFF output. When unspecified, output is DEFLATE
+    /// compressed if raster compression is enabled in the settings.
+    pub compression: Option<TiffCompression>,
+    /// The predictor applied to compressed GeoTIFF output.
+    pub predictor: TiffPredictor,
+    /// The arrangement of the bands of multi-band GeoTIFF output.
+    pub planar_config: TiffPlanarConfig,
+}
+
+impl RasterConfigs {
+    /// Returns true if `value` is the nodata value. Unlike `==`, this is true of a NaN value
+    /// when the nodata value is NaN.
+    pub fn is_nodata(&self, value: f64) -> bool {
+        is_nodata_value(value, self.nodata)
+    }
+
+    /// Returns true if the grid is in a geographic (i.e. angular) coordinate system.
+    pub fn is_in_geographic_coordinates(&self) -> bool {
+        if self.west < -180f64
+            || self.east > 180f64
+            || self.north > 90f64
+            || self.south < -90f64
+        {
+            return false;
+        }
+        if self.epsg_code == 4322
+            || self.epsg_code == 4326
+            || self.epsg_code == 4629
+            || self.epsg_code == 4277
+        {
+            return true;
+        }
+        let wkt = self.coordinate_ref_system_wkt.to_lowercase();
+        if !wkt.contains("projcs[") && !wkt.to_lowercase().contains("not specified") {
+            return true;
+        }
+        if self.xy_units.to_lowercase().contains("deg") {
+            return true;
+        }
+        false
+    }
 }
 
 impl Default for RasterConfigs {
@@ -1331,6 +1572,10 @@ impl Default for RasterConfigs {
             geo_double_params: vec![],
             geo_ascii_params: String::new(),
             metadata: vec![],
+            cog: None,
+            compression: None,
+            predictor: TiffPredictor::None,
+            planar_config: TiffPlanarConfig::Planar,
         }
     }
 }
@@ -0,0 +1,460 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::DataType;
+use std::mem;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// The cell values of a `Raster`, held in the native data type of the raster
+/// rather than as `f64`, e.g. a `U8` land-cover grid uses one byte per cell.
+///
+/// Values are read and written as `f64`. When a value is written that cannot be
+/// represented exactly by the current storage type, e.g. a fractional value in an
+/// integer raster or a nodata value of -32768 in a `U8` raster, the data are
+/// converted to the narrowest type that holds both the existing values and the new
+/// one. Storage is therefore never lossy, and a tool that changes the `data_type` of
+/// an output raster after creating it behaves exactly as it did with `f64` storage.
+#[derive(Clone, Default)]
+pub struct RasterData {
+    values: Values,
+    // A 64-bit copy of 32-bit values, built the first time the data are indexed.
+    view: OnceLock<Vec<f64>>,
+}
+
+#[derive(Clone)]
+enum Values {
+    F64(Vec<f64>),
+    F32(Vec<f32>),
+    I32(Vec<i32>),
+    U32(Vec<u32>),
+    I16(Vec<i16>),
+    U16(Vec<u16>),
+    I8(Vec<i8>),
+    U8(Vec<u8>),
+}
+
+impl Default for Values {
+    fn default() -> Values {
+        Values::F64(vec![])
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Storage {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    F64,
+}
+
+// Candidate storage types, from narrowest to widest.
+const STORAGE_TYPES: [Storage; 8] = [
+    Storage::U8,
+    Storage::I8,
+    Storage::U16,
+    Storage::I16,
+    Storage::U32,
+    Storage::I32,
+    Storage::F32,
+    Storage::F64,
+];
+
+impl Storage {
+    fn from_data_type(data_type: DataType) -> Storage {
+        match data_type {
+            DataType::F32 => Storage::F32,
+            DataType::I32 => Storage::I32,
+            DataType::U32 | DataType::RGB24 | DataType::RGBA32 => Storage::U32,
+            DataType::I16 => Storage::I16,
+            DataType::U16 => Storage::U16,
+            DataType::I8 => Storage::I8,
+            DataType::U8 => Storage::U8,
+            // 64-bit integers and RGB48 values were always held as f64.
+            _ => Storage::F64,
+        }
+    }
+
+    // The range of an integer type; floating-point types have none.
+    fn range(self) -> Option<(f64, f64)> {
+        match self {
+            Storage::U8 => Some((u8::MIN as f64, u8::MAX as f64)),
+            Storage::I8 => Some((i8::MIN as f64, i8::MAX as f64)),
+            Storage::U16 => Some((u16::MIN as f64, u16::MAX as f64)),
+            Storage::I16 => Some((i16::MIN as f64, i16::MAX as f64)),
+            Storage::U32 => Some((u32::MIN as f64, u32::MAX as f64)),
+            Storage::I32 => Some((i32::MIN as f64, i32::MAX as f64)),
+            Storage::F32 | Storage::F64 => None,
+        }
+    }
+
+    fn fits(self, value: f64) -> bool {
+        match self {
+            Storage::F64 => true,
+            Storage::F32 => value as f32 as f64 == value || value.is_nan(),
+            _ => {
+                let (min, max) = self.range().unwrap();
+                value.fract() == 0.0 && value >= min && value <= max
+            }
+        }
+    }
+
+    // Whether every value of the `other` type can be held by this type.
+    fn contains(self, other: Storage) -> bool {
+        match (self.range(), other.range()) {
+            (Some((min, max)), Some((other_min, other_max))) => min <= other_min && max >= other_max,
+            (None, Some((other_min, other_max))) => {
+                // f32 holds integers exactly up to 2^24
+                self == Storage::F64 || (other_min >= -16_777_216.0 && other_max <= 16_777_216.0)
+            }
+            (Some(_), None) => false,
+            (None, None) => self == other || self == Storage::F64,
+        }
+    }
+}
+
+macro_rules! dispatch {
+    ($values:expr, $v:ident => $e:expr) => {
+        match $values {
+            Values::F64($v) => $e,
+            Values::F32($v) => $e,
+            Values::I32($v) => $e,
+            Values::U32($v) => $e,
+            Values::I16($v) => $e,
+            Values::U16($v) => $e,
+            Values::I8($v) => $e,
+            Values::U8($v) => $e,
+        }
+    };
+}
+
+macro_rules! convert {
+    ($v:expr, $variant:ident, $t:ty) => {
+        Values::$variant($v.iter().map(|&z| z as $t).collect())
+    };
+}
+
+impl Values {
+    fn with_capacity(storage: Storage, capacity: usize) -> Values {
+        match storage {
+            Storage::F64 => Values::F64(Vec::with_capacity(capacity)),
+            Storage::F32 => Values::F32(Vec::with_capacity(capacity)),
+            Storage::I32 => Values::I32(Vec::with_capacity(capacity)),
+            Storage::U32 => Values::U32(Vec::with_capacity(capacity)),
+            Storage::I16 => Values::I16(Vec::with_capacity(capacity)),
+            Storage::U16 => Values::U16(Vec::with_capacity(capacity)),
+            Storage::I8 => Values::I8(Vec::with_capacity(capacity)),
+            Storage::U8 => Values::U8(Vec::with_capacity(capacity)),
+        }
+    }
+
+    fn storage(&self) -> Storage {
+        match self {
+            Values::F64(_) => Storage::F64,
+            Values::F32(_) => Storage::F32,
+            Values::I32(_) => Storage::I32,
+            Values::U32(_) => Storage::U32,
+            Values::I16(_) => Storage::I16,
+            Values::U16(_) => Storage::U16,
+            Values::I8(_) => Storage::I8,
+            Values::U8(_) => Storage::U8,
+        }
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> f64 {
+        dispatch!(self, v => v[index] as f64)
+    }
+
+    // The value must fit the storage type.
+    #[inline]
+    fn set(&mut self, index: usize, value: f64) {
+        match self {
+            Values::F64(v) => v[index] = value,
+            Values::F32(v) => v[index] = value as f32,
+            Values::I32(v) => v[index] = value as i32,
+            Values::U32(v) => v[index] = value as u32,
+            Values::I16(v) => v[index] = value as i16,
+            Values::U16(v) => v[index] = value as u16,
+            Values::I8(v) => v[index] = value as i8,
+            Values::U8(v) => v[index] = value as u8,
+        }
+    }
+
+    // The value must fit the storage type.
+    #[inline]
+    fn push(&mut self, value: f64) {
+        match self {
+            Values::F64(v) => v.push(value),
+            Values::F32(v) => v.push(value as f32),
+            Values::I32(v) => v.push(value as i32),
+            Values::U32(v) => v.push(value as u32),
+            Values::I16(v) => v.push(value as i16),
+            Values::U16(v) => v.push(value as u16),
+            Values::I8(v) => v.push(value as i8),
+            Values::U8(v) => v.push(value as u8),
+        }
+    }
+
+    fn convert(&self, storage: Storage) -> Values {
+        dispatch!(self, v => match storage {
+            Storage::F64 => convert!(v, F64, f64),
+            Storage::F32 => convert!(v, F32, f32),
+            Storage::I32 => convert!(v, I32, i32),
+            Storage::U32 => convert!(v, U32, u32),
+            Storage::I16 => convert!(v, I16, i16),
+            Storage::U16 => convert!(v, U16, u16),
+            Storage::I8 => convert!(v, I8, i8),
+            Storage::U8 => convert!(v, U8, u8),
+        })
+    }
+}
+
+// Lookup tables that allow references to the f64 equivalents of 8- and 16-bit
+// values to be returned when indexing, without a 64-bit copy of the data.
+fn lookup_table(storage: Storage) -> &'static [f64] {
+    static U8_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+    static I8_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+    static U16_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+    static I16_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+    match storage {
+        Storage::U8 => U8_TABLE.get_or_init(|| (0..=u8::MAX).map(|b| b as f64).collect()),
+        Storage::I8 => I8_TABLE.get_or_init(|| (0..=u8::MAX).map(|b| b as i8 as f64).collect()),
+        Storage::U16 => U16_TABLE.get_or_init(|| (0..=u16::MAX).map(|b| b as f64).collect()),
+        Storage::I16 => I16_TABLE.get_or_init(|| (0..=u16::MAX).map(|b| b as i16 as f64).collect()),
+        _ => panic!("Lookup tables are only used for 8- and 16-bit values."),
+    }
+}
+
+impl RasterData {
+    /// Creates an empty `RasterData` that stores values using `data_type`.
+    pub fn new(data_type: DataType) -> RasterData {
+        RasterData::with_capacity(data_type, 0)
+    }
+
+    /// Creates an empty `RasterData` that stores values using `data_type`, with space
+    /// reserved for `capacity` values.
+    pub fn with_capacity(data_type: DataType, capacity: usize) -> RasterData {
+        RasterData {
+            values: Values::with_capacity(Storage::from_data_type(data_type), capacity),
+            view: OnceLock::new(),
+        }
+    }
+
+    /// Creates a `RasterData` containing `len` copies of `value`, stored using
+    /// `data_type` if the value can be represented by it.
+    pub fn filled(data_type: DataType, value: f64, len: usize) -> RasterData {
+        let mut data = RasterData::new(data_type);
+        data.accommodate(value);
+        data.reserve_exact(len);
+        data.resize(len, value);
+        data
+    }
+
+    /// Returns the data type used to store the values. This is the native type of the
+    /// raster unless values that it cannot represent have been written.
+    pub fn data_type(&self) -> DataType {
+        match self.values.storage() {
+            Storage::F64 => DataType::F64,
+            Storage::F32 => DataType::F32,
+            Storage::I32 => DataType::I32,
+            Storage::U32 => DataType::U32,
+            Storage::I16 => DataType::I16,
+            Storage::U16 => DataType::U16,
+            Storage::I8 => DataType::I8,
+            Storage::U8 => DataType::U8,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        dispatch!(&self.values, v => v.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the size of the stored values in bytes, including any 64-bit copy of
+    /// them built by `get_ref`.
+    pub fn size_in_bytes(&self) -> usize {
+        let view = self.view.get().map_or(0, |v| mem::size_of_val(&v[..]));
+        dispatch!(&self.values, v => mem::size_of_val(&v[..])) + view
+    }
+
+    #[inline]
+    pub fn get_value(&self, index: usize) -> f64 {
+        self.values.get(index)
+    }
+
+    #[inline]
+    pub fn set_value(&mut self, index: usize, value: f64) {
+        self.accommodate(value);
+        self.values_mut().set(index, value);
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: f64) {
+        self.accommodate(value);
+        self.values_mut().push(value);
+    }
+
+    pub fn clear(&mut self) {
+        dispatch!(self.values_mut(), v => v.clear())
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        dispatch!(&mut self.values, v => v.reserve(additional))
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        dispatch!(&mut self.values, v => v.reserve_exact(additional))
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        dispatch!(self.values_mut(), v => v.truncate(len))
+    }
+
+    pub fn resize(&mut self, len: usize, value: f64) {
+        self.accommodate(value);
+        match self.values_mut() {
+            Values::F64(v) => v.resize(len, value),
+            Values::F32(v) => v.resize(len, value as f32),
+            Values::I32(v) => v.resize(len, value as i32),
+            Values::U32(v) => v.resize(len, value as u32),
+            Values::I16(v) => v.resize(len, value as i16),
+            Values::U16(v) => v.resize(len, value as u16),
+            Values::I8(v) => v.resize(len, value as i8),
+            Values::U8(v) => v.resize(len, value as u8),
+        }
+    }
+
+    /// Copies the values in `src` to the position `dest`, as with `slice::copy_within`.
+    pub fn copy_within(&mut self, src: Range<usize>, dest: usize) {
+        dispatch!(self.values_mut(), v => v.copy_within(src, dest))
+    }
+
+    /// Splits the data in two at `at`, returning the values from `at` onwards.
+    pub fn split_off(&mut self, at: usize) -> RasterData {
+        let values = match self.values_mut() {
+            Values::F64(v) => Values::F64(v.split_off(at)),
+            Values::F32(v) => Values::F32(v.split_off(at)),
+            Values::I32(v) => Values::I32(v.split_off(at)),
+            Values::U32(v) => Values::U32(v.split_off(at)),
+            Values::I16(v) => Values::I16(v.split_off(at)),
+            Values::U16(v) => Values::U16(v.split_off(at)),
+            Values::I8(v) => Values::I8(v.split_off(at)),
+            Values::U8(v) => Values::U8(v.split_off(at)),
+        };
+        RasterData {
+            values,
+            view: OnceLock::new(),
+        }
+    }
+
+    /// Copies the values starting at `start` into `values`.
+    pub fn copy_to_slice(&self, start: usize, values: &mut [f64]) {
+        let end = start + values.len();
+        dispatch!(&self.values, v => {
+            for (value, &z) in values.iter_mut().zip(&v[start..end]) {
+                *value = z as f64;
+            }
+        })
+    }
+
+    /// Sets the values starting at `start` from `values`.
+    pub fn copy_from_slice(&mut self, start: usize, values: &[f64]) {
+        if let Values::F64(v) = self.values_mut() {
+            v[start..start + values.len()].copy_from_slice(values);
+            return;
+        }
+        for (i, &value) in values.iter().enumerate() {
+            self.set_value(start + i, value);
+        }
+    }
+
+    /// Returns an iterator over the values, as f64.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        (0..self.len()).map(move |i| self.values.get(i))
+    }
+
+    /// Returns a copy of the values as f64.
+    pub fn to_vec(&self) -> Vec<f64> {
+        match &self.values {
+            Values::F64(v) => v.clone(),
+            values => dispatch!(values, v => v.iter().map(|&z| z as f64).collect()),
+        }
+    }
+
+    /// Stores the values using `data_type` if all of them can be represented by it,
+    /// e.g. after the values of a text-based format have been read as f64.
+    pub fn compact(&mut self, data_type: DataType) {
+        let target = Storage::from_data_type(data_type);
+        if target.contains(self.values.storage()) || !self.iter().all(|z| target.fits(z)) {
+            return;
+        }
+        self.values = self.values.convert(target);
+        self.view.take();
+    }
+
+    /// Returns a reference to a value as f64, for implementing `Index` on `Raster`.
+    /// Rasters stored as 32-bit types build a 64-bit copy of their values the first
+    /// time this is called, which is why the tools use `get_value` instead.
+    #[inline]
+    pub(crate) fn get_ref(&self, index: usize) -> &f64 {
+        match &self.values {
+            Values::F64(v) => &v[index],
+            Values::U8(v) => &lookup_table(Storage::U8)[v[index] as usize],
+            Values::I8(v) => &lookup_table(Storage::I8)[v[index] as u8 as usize],
+            Values::U16(v) => &lookup_table(Storage::U16)[v[index] as usize],
+            Values::I16(v) => &lookup_table(Storage::I16)[v[index] as u16 as usize],
+            _ => &self.view.get_or_init(|| self.to_vec())[index],
+        }
+    }
+
+    /// Returns a mutable reference to a value, for implementing `IndexMut` on `Raster`.
+    /// The values are converted to f64 storage, as is necessary to hand out references.
+    #[inline]
+    pub(crate) fn get_mut(&mut self, index: usize) -> &mut f64 {
+        if self.values.storage() != Storage::F64 {
+            self.values = self.values.convert(Storage::F64);
+        }
+        match self.values_mut() {
+            Values::F64(v) => &mut v[index],
+            _ => unreachable!(),
+        }
+    }
+
+    // Any change to the values invalidates the 64-bit view.
+    #[inline]
+    fn values_mut(&mut self) -> &mut Values {
+        if self.view.get().is_some() {
+            self.view.take();
+        }
+        &mut self.values
+    }
+
+    // Widens the storage type, if needed, so that `value` can be stored exactly.
+    #[inline]
+    fn accommodate(&mut self, value: f64) {
+        let storage = self.values.storage();
+        if storage.fits(value) {
+            return;
+        }
+        let target = STORAGE_TYPES
+            .iter()
+            .copied()
+            .find(|t| t.contains(storage) && t.fits(value))
+            .unwrap_or(Storage::F64);
+        self.values = self.values.convert(target);
+        self.view.take();
+    }
+}
@@ -10,7 +10,7 @@ use std::path::Path;
 pub fn read_saga(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read the header file
     // let header_file = file_name.replace(".sdat", ".sgrd");
@@ -173,8 +173,6 @@ pub fn read_saga(
         row_start = configs.rows - 1;
     }
 
-    data.reserve(configs.rows * configs.columns);
-
     // read the data file
     // let data_file = file_name.replace(".sgrd", ".sdat");
     let data_file = Path::new(&file_name)
@@ -200,11 +198,7 @@ pub fn read_saga(
     };
 
     let num_cells = configs.rows * configs.columns;
-    data.clear();
-    data.reserve(num_cells);
-    for _ in 0..num_cells {
-        data.push(configs.nodata);
-    }
+    *data = RasterData::filled(configs.data_type, configs.nodata, num_cells);
 
     let buf_size = 1_000_000usize;
     let mut j = 0;
@@ -227,7 +221,7 @@ pub fn read_saga(
             DataType::F64 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_f64()? as f64 * z_factor;
+                    data.set_value(k, bor.read_f64()? as f64 * z_factor);
 
                     j += 1;
                     if j == num_cells {
@@ -247,7 +241,7 @@ pub fn read_saga(
             DataType::F32 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_f32()? as f64 * z_factor;
+                    data.set_value(k, bor.read_f32()? as f64 * z_factor);
 
                     j += 1;
                     if j == num_cells {
@@ -267,7 +261,7 @@ pub fn read_saga(
             DataType::I32 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_i32()? as f64 * z_factor;
+                    data.set_value(k, bor.read_i32()? as f64 * z_factor);
 
                     j += 1;
                     if j == num_cells {
@@ -287,7 +281,7 @@ pub fn read_saga(
             DataType::U32 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_u32()? as f64 * z_factor;
+                    data.set_value(k, bor.read_u32()? as f64 * z_factor);
 
                     j += 1;
                     if j == num_cells {
@@ -307,7 +301,7 @@ pub fn read_saga(
             DataType::I16 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_i16()? as f64 * z_factor;
+                    data.set_value(k, bor.read_i16()? as f64 * z_factor);
 
                     j += 1;
                     if j == num_cells {
@@ -327,7 +321,7 @@ pub fn read_saga(
             DataType::U16 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_u16()? as f64 * z_factor;
+                    data.set_value(k, bor.read_u16()? as f64 * z_factor);
 
                     j += 1;
                     if j == num_cells {
@@ -347,7 +341,7 @@ pub fn read_saga(
             DataType::I8 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_i8()? as f64 * z_factor;
+                    data.set_value(k, bor.read_i8()? as f64 * z_factor);
                     j += 1;
                     if j == num_cells {
                         break;
@@ -366,7 +360,7 @@ pub fn read_saga(
             DataType::U8 => {
                 for _ in 0..buf_size {
                     k = row * configs.columns + col;
-                    data[k] = bor.read_u8()? as f64 * z_factor;
+                    data.set_value(k, bor.read_u8()? as f64 * z_factor);
                     j += 1;
                     if j == num_cells {
                         break;
@@ -418,9 +412,8 @@ pub fn read_saga(
 
 pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
     // figure out the minimum and maximum values
-    for val in &r.data {
-        let v = *val;
-        if v != r.configs.nodata {
+    for v in r.data.iter() {
+        if !r.is_nodata(v) {
             if v < r.configs.minimum {
                 r.configs.minimum = v;
             }
@@ -554,7 +547,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    u64_bytes = unsafe { mem::transmute(r.data[i]) };
+                    u64_bytes = unsafe { mem::transmute(r.data.get_value(i)) };
                     writer.write(&u64_bytes)?;
                 }
             }
@@ -563,7 +556,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    u32_bytes = unsafe { mem::transmute(r.data[i] as f32) };
+                    u32_bytes = unsafe { mem::transmute(r.data.get_value(i) as f32) };
                     writer.write(&u32_bytes)?;
                 }
             }
@@ -572,7 +565,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    u32_bytes = unsafe { mem::transmute(r.data[i] as i32) };
+                    u32_bytes = unsafe { mem::transmute(r.data.get_value(i) as i32) };
                     writer.write(&u32_bytes)?;
                 }
             }
@@ -581,7 +574,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    u32_bytes = unsafe { mem::transmute(r.data[i] as u32) };
+                    u32_bytes = unsafe { mem::transmute(r.data.get_value(i) as u32) };
                     writer.write(&u32_bytes)?;
                 }
             }
@@ -590,7 +583,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    u16_bytes = unsafe { mem::transmute(r.data[i] as i16) };
+                    u16_bytes = unsafe { mem::transmute(r.data.get_value(i) as i16) };
                     writer.write(&u16_bytes)?;
                 }
             }
@@ -599,7 +592,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    u16_bytes = unsafe { mem::transmute(r.data[i] as u16) };
+                    u16_bytes = unsafe { mem::transmute(r.data.get_value(i) as u16) };
                     writer.write(&u16_bytes)?;
                 }
             }
@@ -608,7 +601,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             for row in (0..r.configs.rows).rev() {
                 for col in 0..r.configs.columns {
                     i = row * r.configs.columns + col;
-                    writer.write(&[r.data[i] as u8])?;
+                    writer.write(&[r.data.get_value(i) as u8])?;
                 }
             }
         }
@@ -11,7 +11,7 @@ use std::mem;
 pub fn read_surfer7(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read data file
     let mut f = File::open(file_name.clone())?;
@@ -269,10 +269,10 @@ pub fn read_surfer7(
                         buffer[offset + 7],
                     ])
                 };
-                if value != configs.nodata {
-                    data[i] = value;
+                if !configs.is_nodata(value) {
+                    data.set_value(i, value);
                 } else {
-                    data[i] = configs.nodata;
+                    data.set_value(i, configs.nodata);
                 }
             }
         }
@@ -295,9 +295,9 @@ pub fn read_surfer7(
                     ])
                 };
                 if value <= configs.nodata {
-                    data[i] = value;
+                    data.set_value(i, value);
                 } else {
-                    data[i] = configs.nodata;
+                    data.set_value(i, configs.nodata);
                 }
             }
         }
@@ -310,9 +310,8 @@ pub fn read_surfer7(
 
 pub fn write_surfer7<'a>(r: &'a mut Raster) -> Result<(), Error> {
     // figure out the minimum and maximum values
-    for val in &r.data {
-        let v = *val;
-        if v != r.configs.nodata {
+    for v in r.data.iter() {
+        if !r.is_nodata(v) {
             if v < r.configs.minimum {
                 r.configs.minimum = v;
             }
@@ -385,7 +384,7 @@ pub fn write_surfer7<'a>(r: &'a mut Raster) -> Result<(), Error> {
     for row in (0..r.configs.rows).rev() {
         for col in 0..r.configs.columns {
             i = row * r.configs.columns + col;
-            u64_bytes = unsafe { mem::transmute(r.data[i]) };
+            u64_bytes = unsafe { mem::transmute(r.data.get_value(i)) };
             writer.write(&u64_bytes)?;
         }
     }
@@ -10,7 +10,7 @@ use std::io::ErrorKind;
 pub fn read_surfer_ascii_raster(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read the file
     let f = File::open(file_name)?;
@@ -94,9 +94,9 @@ pub fn read_surfer_ascii_raster(
                     i = row * configs.columns + col;
                     if !val.contains("1.71041e38") {
                         val_num = val.trim().to_string().parse::<f64>().unwrap();
-                        data[i] = val_num;
+                        data.set_value(i, val_num);
                     } else {
-                        data[i] = configs.nodata;
+                        data.set_value(i, configs.nodata);
                     }
                     col += 1;
                     if col == configs.columns {
@@ -122,9 +122,8 @@ pub fn write_surfer_ascii_raster<'a>(r: &'a mut Raster) -> Result<(), Error> {
     }
 
     // figure out the minimum and maximum values
-    for val in &r.data {
-        let v = *val;
-        if v != r.configs.nodata {
+    for v in r.data.iter() {
+        if !r.is_nodata(v) {
             if v < r.configs.minimum {
                 r.configs.minimum = v;
             }
@@ -155,14 +154,14 @@ pub fn write_surfer_ascii_raster<'a>(r: &'a mut Raster) -> Result<(), Error> {
         for col in 0..r.configs.columns {
             let i = row * r.configs.columns + col;
             if col < r.configs.columns - 1 {
-                if r.data[i] != r.configs.nodata {
-                    s2 += &format!("{:.*} ", num_decimals, r.data[i]);
+                if !r.is_nodata(r.data.get_value(i)) {
+                    s2 += &format!("{:.*} ", num_decimals, r.data.get_value(i));
                 } else {
                     s2 += &format!("1.71041e38 ");
                 }
             } else {
-                if r.data[i] != r.configs.nodata {
-                    s2 += &format!("{:.*}\n", num_decimals, r.data[i]);
+                if !r.is_nodata(r.data.get_value(i)) {
+                    s2 += &format!("{:.*}\n", num_decimals, r.data.get_value(i));
                 } else {
                     s2 += &format!("1.71041e38\n");
                 }
@@ -13,7 +13,7 @@ use std::path::Path;
 pub fn read_whitebox(
     file_name: &String,
     configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
+    data: &mut RasterData,
 ) -> Result<(), Error> {
     // read the header file
     // let header_file = file_name.replace(".tas", ".dep");
@@ -144,7 +144,7 @@ pub fn read_whitebox(
         1
     };
 
-    data.reserve(configs.rows * configs.columns);
+    *data = RasterData::with_capacity(configs.data_type, configs.rows * configs.columns);
 
     let num_cells = configs.rows * configs.columns;
     let buf_size = if num_cells > 10_000_000usize {
@@ -240,9 +240,8 @@ pub fn read_whitebox(
 
 pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
     // figure out the minimum and maximum values
-    for val in &r.data {
-        let v = *val;
-        if v != r.configs.nodata {
+    for v in r.data.iter() {
+        if !r.is_nodata(v) {
             if v < r.configs.minimum {
                 r.configs.minimum = v;
             }
@@ -431,29 +430,29 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
         DataType::F64 | DataType::U32 => {
             if r.configs.photometric_interp != PhotometricInterpretation::RGB {
                 for i in 0..num_cells {
-                    u64_bytes = unsafe { mem::transmute(r.data[i]) };
+                    u64_bytes = unsafe { mem::transmute(r.data.get_value(i)) };
                     writer.write(&u64_bytes)?;
                 }
             } else {
                 for i in 0..num_cells {
-                    u32_bytes = unsafe { mem::transmute(r.data[i] as u32) };
+                    u32_bytes = unsafe { mem::transmute(r.data.get_value(i) as u32) };
                     writer.write(&u32_bytes)?;
                 }
             }
         }
         DataType::F32 | DataType::U16 => {
             for i in 0..num_cells {
-                writer.write_f32::<LittleEndian>(r.data[i] as f32)?;
+                writer.write_f32::<LittleEndian>(r.data.get_value(i) as f32)?;
             }
         }
         DataType::I32 => {
             for i in 0..num_cells {
-                writer.write_f32::<LittleEndian>(r.data[i] as f32)?;
+                writer.write_f32::<LittleEndian>(r.data.get_value(i) as f32)?;
             }
         }
         DataType::RGBA32 => {
             for i in 0..num_cells {
-                u32_bytes = unsafe { mem::transmute(r.data[i] as u32 as i32 as f32) };
+                u32_bytes = unsafe { mem::transmute(r.data.get_value(i) as u32 as i32 as f32) };
                 writer.write(&u32_bytes)?;
             }
         }
@@ -463,21 +462,21 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
             let mut val: u32;
             let alpha_mask = (255 << 24) as u32;
             for i in 0..num_cells {
-                val = alpha_mask | (r.data[i] as u32);
+                val = alpha_mask | (r.data.get_value(i) as u32);
                 u32_bytes = unsafe { mem::transmute(val) };
                 writer.write(&u32_bytes)?;
             }
         }
         DataType::I16 => {
             for i in 0..num_cells {
-                // u16_bytes = unsafe { mem::transmute(r.data[i] as u16) };
+                // u16_bytes = unsafe { mem::transmute(r.data.get_value(i) as u16) };
                 // writer.write(&u16_bytes)?;
-                writer.write_i16::<LittleEndian>(r.data[i] as i16)?;
+                writer.write_i16::<LittleEndian>(r.data.get_value(i) as i16)?;
             }
         }
         DataType::U8 | DataType::I8 => {
             for i in 0..num_cells {
-                writer.write(&[r.data[i] as u8])?;
+                writer.write(&[r.data.get_value(i) as u8])?;
             }
         }
         _ => {
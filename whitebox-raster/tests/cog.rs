@@ -0,0 +1,311 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the structure of Cloud-Optimized GeoTIFFs: the ghost area declaring the layout,
+//! the chain of IFDs for the full-resolution image and its overviews, the placement of
+//! the IFDs before the tile data and of the tiles from the smallest overview to the
+//! full-resolution image, and the values of the overviews, which are read back from the
+//! tiles of the file.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{
+    CogOptions, DataType, GeoTiffReader, OverviewResampling, PhotometricInterpretation, Raster,
+    RasterConfigs, TiffCompression,
+};
+
+const ROWS: usize = 100;
+const COLUMNS: usize = 140;
+const BANDS: usize = 2;
+const TILE_SIZE: usize = 16;
+
+const TAG_IMAGEWIDTH: u16 = 256;
+const TAG_IMAGELENGTH: u16 = 257;
+const TAG_STRIPOFFSETS: u16 = 273;
+const TAG_TILEWIDTH: u16 = 322;
+const TAG_TILELENGTH: u16 = 323;
+const TAG_TILEOFFSETS: u16 = 324;
+const TAG_TILEBYTECOUNTS: u16 = 325;
+const TAG_NEWSUBFILETYPE: u16 = 254;
+const TAG_MODELTIEPOINT: u16 = 33922;
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+fn cell_value(band: usize, row: usize, col: usize) -> f64 {
+    (band * 100_000 + row * COLUMNS + col) as f64 + 0.5
+}
+
+fn write_cog(file_name: &str, resampling: OverviewResampling) {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: -32768.0,
+        north: 100.0,
+        south: 100.0 - ROWS as f64,
+        west: 0.0,
+        east: COLUMNS as f64,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    raster.configs.compression = Some(TiffCompression::None);
+    raster.configs.cog = Some(CogOptions {
+        tile_size: TILE_SIZE,
+        resampling,
+    });
+    raster.set_num_bands(BANDS as u8);
+    for band in 0..BANDS {
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                raster.set_band_value(band, row as isize, col as isize, cell_value(band, row, col));
+            }
+        }
+    }
+    raster.write().expect("Error writing the raster.");
+}
+
+/// An image file directory of a little-endian classic TIFF file.
+struct Ifd {
+    offset: usize,
+    // the end of the IFD, including the values stored outside of its entries
+    end: usize,
+    entries: HashMap<u16, Vec<u64>>,
+}
+
+impl Ifd {
+    fn value(&self, tag: u16) -> Option<u64> {
+        self.entries.get(&tag).map(|v| v[0])
+    }
+}
+
+fn read_u16(bytes: &[u8], i: usize) -> u16 {
+    u16::from_le_bytes([bytes[i], bytes[i + 1]])
+}
+
+fn read_u32(bytes: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]])
+}
+
+// Reads the chain of IFDs that begins at `offset`.
+fn read_ifds(bytes: &[u8], mut offset: usize) -> Vec<Ifd> {
+    let mut ifds = vec![];
+    while offset != 0 {
+        let num_entries = read_u16(bytes, offset) as usize;
+        let mut end = offset + 2 + num_entries * 12 + 4;
+        let mut entries = HashMap::new();
+        for e in 0..num_entries {
+            let entry = offset + 2 + e * 12;
+            let (tag, field_type, count) = (
+                read_u16(bytes, entry),
+                read_u16(bytes, entry + 2),
+                read_u32(bytes, entry + 4) as usize,
+            );
+            let size = match field_type {
+                3 => 2,  // SHORT
+                4 => 4,  // LONG
+                12 => 8, // DOUBLE
+                _ => 1,
+            };
+            let values_offset = if count * size <= 4 {
+                entry + 8
+            } else {
+                let values_offset = read_u32(bytes, entry + 8) as usize;
+                end = end.max(values_offset + count * size);
+                values_offset
+            };
+            let values = (0..count)
+                .map(|i| match field_type {
+                    3 => read_u16(bytes, values_offset + 2 * i) as u64,
+                    4 => read_u32(bytes, values_offset + 4 * i) as u64,
+                    _ => 0,
+                })
+                .collect();
+            entries.insert(tag, values);
+        }
+        let next = read_u32(bytes, offset + 2 + num_entries * 12) as usize;
+        ifds.push(Ifd { offset, end, entries });
+        offset = next;
+    }
+    ifds
+}
+
+// The dimensions of the full-resolution image and its overviews.
+fn level_dims() -> Vec<(usize, usize)> {
+    let mut dims = vec![(ROWS, COLUMNS)];
+    while let Some(&(rows, columns)) = dims.last() {
+        if rows <= TILE_SIZE && columns <= TILE_SIZE {
+            break;
+        }
+        dims.push((rows.div_ceil(2), columns.div_ceil(2)));
+    }
+    dims
+}
+
+// Reads a cell of a level of the file from its (uncompressed, F32) tiles.
+fn tile_value(bytes: &[u8], ifd: &Ifd, band: usize, row: usize, col: usize) -> f64 {
+    let columns = ifd.value(TAG_IMAGEWIDTH).unwrap() as usize;
+    let rows = ifd.value(TAG_IMAGELENGTH).unwrap() as usize;
+    let tiles_across = columns.div_ceil(TILE_SIZE);
+    let tiles_down = rows.div_ceil(TILE_SIZE);
+    let tile = band * tiles_across * tiles_down + (row / TILE_SIZE) * tiles_across + col / TILE_SIZE;
+    let offset = ifd.entries[&TAG_TILEOFFSETS][tile] as usize;
+    let i = offset + 4 * ((row % TILE_SIZE) * TILE_SIZE + col % TILE_SIZE);
+    f32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]) as f64
+}
+
+#[test]
+fn cog_layout_is_valid() {
+    let file_name = temp_file("cog_layout.tif");
+    write_cog(&file_name, OverviewResampling::Nearest);
+    let bytes = fs::read(&file_name).expect("Error reading the file.");
+    assert_eq!(&bytes[0..4], b"II*\0");
+
+    // The ghost area immediately follows the header and precedes the first IFD.
+    let prefix = b"GDAL_STRUCTURAL_METADATA_SIZE=";
+    assert_eq!(&bytes[8..8 + prefix.len()], prefix);
+    let size_start = 8 + prefix.len();
+    let size = std::str::from_utf8(&bytes[size_start..size_start + 6])
+        .unwrap()
+        .parse::<usize>()
+        .unwrap();
+    assert_eq!(&bytes[size_start + 6..size_start + 13], b" bytes\n");
+    let metadata_start = size_start + 13;
+    let metadata = std::str::from_utf8(&bytes[metadata_start..metadata_start + size]).unwrap();
+    assert!(metadata.contains("LAYOUT=IFDS_BEFORE_DATA\n"), "{}", metadata);
+    assert!(metadata.contains("BLOCK_ORDER=ROW_MAJOR\n"), "{}", metadata);
+    let first_ifd = read_u32(&bytes, 4) as usize;
+    assert!(first_ifd >= metadata_start + size);
+    assert!(first_ifd - (metadata_start + size) < 2);
+    assert_eq!(first_ifd % 2, 0);
+
+    // The full-resolution image is followed by each of its overviews, halving in size
+    // until the coarsest fits within a single tile.
+    let ifds = read_ifds(&bytes, first_ifd);
+    let dims = level_dims();
+    assert_eq!(ifds.len(), dims.len());
+    assert!(dims.len() > 2);
+    for (level, (ifd, &(rows, columns))) in ifds.iter().zip(&dims).enumerate() {
+        assert_eq!(ifd.value(TAG_IMAGELENGTH), Some(rows as u64), "level {}", level);
+        assert_eq!(ifd.value(TAG_IMAGEWIDTH), Some(columns as u64), "level {}", level);
+        assert_eq!(ifd.value(TAG_TILEWIDTH), Some(TILE_SIZE as u64));
+        assert_eq!(ifd.value(TAG_TILELENGTH), Some(TILE_SIZE as u64));
+        assert_eq!(ifd.value(TAG_STRIPOFFSETS), None);
+        let tiles = rows.div_ceil(TILE_SIZE) * columns.div_ceil(TILE_SIZE);
+        assert_eq!(ifd.entries[&TAG_TILEOFFSETS].len(), tiles * BANDS);
+        assert_eq!(ifd.entries[&TAG_TILEBYTECOUNTS].len(), tiles * BANDS);
+        if level == 0 {
+            assert_eq!(ifd.value(TAG_NEWSUBFILETYPE).unwrap_or(0), 0);
+            assert!(ifd.value(TAG_MODELTIEPOINT).is_some());
+        } else {
+            assert_eq!(ifd.value(TAG_NEWSUBFILETYPE), Some(1), "level {}", level);
+        }
+    }
+
+    // The IFDs, in order, precede all of the tile data.
+    let first_tile = ifds
+        .iter()
+        .flat_map(|ifd| ifd.entries[&TAG_TILEOFFSETS].iter())
+        .min()
+        .copied()
+        .unwrap() as usize;
+    for pair in ifds.windows(2) {
+        assert!(pair[0].end <= pair[1].offset);
+    }
+    assert!(ifds.last().unwrap().end <= first_tile);
+
+    // The tiles are stored from the smallest overview to the full-resolution image, and
+    // the tiles of each level in row-major order, band by band, without overlapping.
+    let mut previous_end = first_tile;
+    for ifd in ifds.iter().rev() {
+        let offsets = &ifd.entries[&TAG_TILEOFFSETS];
+        let byte_counts = &ifd.entries[&TAG_TILEBYTECOUNTS];
+        for (&offset, &count) in offsets.iter().zip(byte_counts) {
+            assert!(offset as usize >= previous_end);
+            assert_eq!(count as usize, TILE_SIZE * TILE_SIZE * 4);
+            previous_end = (offset + count) as usize;
+        }
+    }
+    assert!(previous_end <= bytes.len());
+}
+
+#[test]
+fn cog_overviews_are_read_back() {
+    for resampling in [OverviewResampling::Nearest, OverviewResampling::Average] {
+        let file_name = temp_file(&format!("cog_overviews_{:?}.tif", resampling));
+        write_cog(&file_name, resampling);
+
+        // the full-resolution image is read as any other GeoTIFF
+        let raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+        assert_eq!(raster.num_bands(), BANDS);
+        let reader = GeoTiffReader::new(&file_name).expect("Error opening the raster.");
+        assert_eq!(reader.block_size(), (TILE_SIZE, TILE_SIZE));
+
+        let bytes = fs::read(&file_name).expect("Error reading the file.");
+        let ifds = read_ifds(&bytes, read_u32(&bytes, 4) as usize);
+        let dims = level_dims();
+        for band in 0..BANDS {
+            for row in 0..ROWS {
+                for col in 0..COLUMNS {
+                    assert_eq!(
+                        tile_value(&bytes, &ifds[0], band, row, col),
+                        raster.get_band_value(band, row as isize, col as isize)
+                    );
+                }
+            }
+            // the expected levels, each derived from the next-finer level
+            let mut expected: Vec<f64> = (0..ROWS * COLUMNS)
+                .map(|i| cell_value(band, i / COLUMNS, i % COLUMNS))
+                .collect();
+            for level in 1..dims.len() {
+                let (rows, columns) = dims[level];
+                let (finer_rows, finer_columns) = dims[level - 1];
+                let finer = expected;
+                expected = vec![0f64; rows * columns];
+                for row in 0..rows {
+                    for col in 0..columns {
+                        expected[row * columns + col] = match resampling {
+                            OverviewResampling::Nearest => finer[2 * row * finer_columns + 2 * col],
+                            _ => {
+                                let mut values = vec![];
+                                for r in 2 * row..(2 * row + 2).min(finer_rows) {
+                                    for c in 2 * col..(2 * col + 2).min(finer_columns) {
+                                        values.push(finer[r * finer_columns + c]);
+                                    }
+                                }
+                                values.iter().sum::<f64>() / values.len() as f64
+                            }
+                        };
+                    }
+                }
+                for row in 0..rows {
+                    for col in 0..columns {
+                        assert_eq!(
+                            tile_value(&bytes, &ifds[level], band, row, col),
+                            expected[row * columns + col] as f32 as f64,
+                            "{:?} level {} band {} ({}, {})",
+                            resampling,
+                            level,
+                            band,
+                            row,
+                            col
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that GeoTIFFs compressed with each method, and with each predictor, are read back
+//! without changing their values, in strips and in tiles. LZMA compressed files, which
+//! are read but not written, are made by recompressing the blocks of ZSTD compressed files.
+
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use whitebox_raster::{
+    CogOptions, DataType, GeoTiffReader, PhotometricInterpretation, Raster, RasterConfigs,
+    TiffCompression, TiffPredictor,
+};
+
+const ROWS: usize = 7;
+const COLUMNS: usize = 37;
+const TILE_SIZE: usize = 16;
+
+const TAG_COMPRESSION: u16 = 259;
+const TAG_STRIPOFFSETS: u16 = 273;
+const TAG_STRIPBYTECOUNTS: u16 = 279;
+const TAG_PREDICTOR: u16 = 317;
+const TAG_TILEOFFSETS: u16 = 324;
+const TAG_TILEBYTECOUNTS: u16 = 325;
+const COMPRESS_LZMA: u16 = 34925;
+
+const DATA_TYPES: [DataType; 10] = [
+    DataType::I8,
+    DataType::U8,
+    DataType::I16,
+    DataType::U16,
+    DataType::I32,
+    DataType::U32,
+    DataType::I64,
+    DataType::U64,
+    DataType::F32,
+    DataType::F64,
+];
+
+/// The compression methods of the test files.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Method {
+    Deflate,
+    Lzw,
+    Zstd,
+    Lzma,
+}
+
+const METHODS: [Method; 4] = [Method::Deflate, Method::Lzw, Method::Zstd, Method::Lzma];
+
+const PREDICTORS: [TiffPredictor; 3] = [
+    TiffPredictor::None,
+    TiffPredictor::Horizontal,
+    TiffPredictor::FloatingPoint,
+];
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+// The value of a cell, which varies in sign and magnitude across each row, so that the
+// predictors difference samples whose bytes all change.
+fn cell_value(data_type: DataType, row: usize, col: usize) -> f64 {
+    let i = (row * COLUMNS + col) as f64;
+    match data_type {
+        DataType::I8 => (i * 7.0) % 256.0 - 128.0,
+        DataType::U8 => (i * 7.0) % 256.0,
+        DataType::I16 => (i * 997.0) % 65_536.0 - 32_768.0,
+        DataType::U16 => (i * 997.0) % 65_536.0,
+        DataType::I32 => (i * 16_777_259.0) % 4_294_967_296.0 - 2_147_483_648.0,
+        DataType::U32 => (i * 16_777_259.0) % 4_294_967_296.0,
+        DataType::I64 => (i * 1e12).powf(1.2).round() * if col.is_multiple_of(2) { 1.0 } else { -1.0 },
+        DataType::U64 => (i * 1e12).powf(1.2).round(),
+        DataType::F32 => ((i - 100.0).powi(3) / 7.0) as f32 as f64,
+        _ => (i - 100.0).powi(5) / 7.0,
+    }
+}
+
+fn write_raster(
+    file_name: &str,
+    data_type: DataType,
+    compression: TiffCompression,
+    predictor: TiffPredictor,
+    tiled: bool,
+) -> std::io::Result<()> {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: 1.0,
+        north: 100.0,
+        south: 100.0 - ROWS as f64,
+        west: 0.0,
+        east: COLUMNS as f64,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    raster.configs.compression = Some(compression);
+    raster.configs.predictor = predictor;
+    if tiled {
+        raster.configs.cog = Some(CogOptions {
+            tile_size: TILE_SIZE,
+            ..Default::default()
+        });
+    }
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            raster.set_value(row as isize, col as isize, cell_value(data_type, row, col));
+        }
+    }
+    raster.write()
+}
+
+fn read_u16(bytes: &[u8], i: usize) -> u16 {
+    u16::from_le_bytes([bytes[i], bytes[i + 1]])
+}
+
+fn read_u32(bytes: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]])
+}
+
+// Returns the positions of the IFD entries of a little-endian classic TIFF file with `tag`.
+fn find_entries(bytes: &[u8], tag: u16) -> Vec<usize> {
+    let mut entries = vec![];
+    let mut ifd = read_u32(bytes, 4) as usize;
+    while ifd != 0 {
+        let num_entries = read_u16(bytes, ifd) as usize;
+        entries.extend(
+            (0..num_entries)
+                .map(|e| ifd + 2 + e * 12)
+                .filter(|&entry| read_u16(bytes, entry) == tag),
+        );
+        ifd = read_u32(bytes, ifd + 2 + num_entries * 12) as usize;
+    }
+    entries
+}
+
+// Returns the position of each of the LONG values of an IFD entry.
+fn value_positions(bytes: &[u8], entry: usize) -> Vec<usize> {
+    let count = read_u32(bytes, entry + 4) as usize;
+    let start = if count == 1 {
+        entry + 8
+    } else {
+        read_u32(bytes, entry + 8) as usize
+    };
+    (0..count).map(|i| start + 4 * i).collect()
+}
+
+fn tag_value(file_name: &str, tag: u16) -> Option<u16> {
+    let bytes = fs::read(file_name).expect("Error reading the file.");
+    find_entries(&bytes, tag)
+        .first()
+        .map(|&entry| read_u16(&bytes, entry + 8))
+}
+
+// Recompresses each of the blocks of a ZSTD compressed file with LZMA, appending the
+// recompressed blocks to the file.
+fn recompress_with_lzma(file_name: &str) {
+    let mut bytes = fs::read(file_name).expect("Error reading the file.");
+    for (offsets_tag, byte_counts_tag) in [
+        (TAG_STRIPOFFSETS, TAG_STRIPBYTECOUNTS),
+        (TAG_TILEOFFSETS, TAG_TILEBYTECOUNTS),
+    ] {
+        let offset_entries = find_entries(&bytes, offsets_tag);
+        let byte_count_entries = find_entries(&bytes, byte_counts_tag);
+        for (offsets, byte_counts) in offset_entries.iter().zip(&byte_count_entries) {
+            let offsets = value_positions(&bytes, *offsets);
+            let byte_counts = value_positions(&bytes, *byte_counts);
+            for (&offset, &byte_count) in offsets.iter().zip(&byte_counts) {
+                let start = read_u32(&bytes, offset) as usize;
+                let end = start + read_u32(&bytes, byte_count) as usize;
+                let mut source = &bytes[start..end];
+                let mut decoder = ruzstd::decoding::StreamingDecoder::new(&mut source)
+                    .expect("Error decoding a block.");
+                let mut block = vec![];
+                decoder.read_to_end(&mut block).expect("Error decoding a block.");
+                let mut compressed = vec![];
+                lzma_rs::xz_compress(&mut &block[..], &mut compressed)
+                    .expect("Error compressing a block.");
+                let new_offset = bytes.len() as u32;
+                bytes[offset..offset + 4].copy_from_slice(&new_offset.to_le_bytes());
+                bytes[byte_count..byte_count + 4]
+                    .copy_from_slice(&(compressed.len() as u32).to_le_bytes());
+                bytes.extend(compressed);
+            }
+        }
+    }
+    for entry in find_entries(&bytes, TAG_COMPRESSION) {
+        bytes[entry + 8..entry + 10].copy_from_slice(&COMPRESS_LZMA.to_le_bytes());
+    }
+    fs::write(file_name, bytes).expect("Error writing the file.");
+}
+
+fn check_round_trip(method: Method, predictor: TiffPredictor, tiled: bool) {
+    let compression = match method {
+        Method::Deflate => TiffCompression::Deflate,
+        Method::Lzw => TiffCompression::Lzw,
+        Method::Zstd | Method::Lzma => TiffCompression::Zstd,
+    };
+    for data_type in DATA_TYPES {
+        let name = format!(
+            "compression_{:?}_{:?}_{:?}_{}.tif",
+            method, predictor, data_type, tiled
+        );
+        if predictor == TiffPredictor::FloatingPoint && !data_type.is_float() {
+            continue;
+        }
+        let file_name = temp_file(&name);
+        write_raster(&file_name, data_type, compression, predictor, tiled)
+            .expect("Error writing the raster.");
+        if method == Method::Lzma {
+            recompress_with_lzma(&file_name);
+        }
+        let expected_compression = match method {
+            Method::Lzma => COMPRESS_LZMA,
+            _ => compression.code(),
+        };
+        assert_eq!(
+            tag_value(&file_name, TAG_COMPRESSION),
+            Some(expected_compression),
+            "{}",
+            name
+        );
+        assert_eq!(
+            tag_value(&file_name, TAG_PREDICTOR).unwrap_or(1),
+            predictor.code(),
+            "{}",
+            name
+        );
+
+        let raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+        assert_eq!(raster.configs.data_type, data_type, "{}", name);
+        let mut reader = GeoTiffReader::new(&file_name).expect("Error opening the raster.");
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                let expected = cell_value(data_type, row, col);
+                assert_eq!(
+                    raster.get_value(row as isize, col as isize).to_bits(),
+                    expected.to_bits(),
+                    "{} ({}, {})",
+                    name,
+                    row,
+                    col
+                );
+                assert_eq!(
+                    reader
+                        .get_value(row as isize, col as isize)
+                        .expect("Error reading a cell.")
+                        .to_bits(),
+                    expected.to_bits(),
+                    "{} ({}, {}), streamed",
+                    name,
+                    row,
+                    col
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn compressed_strips_round_trip() {
+    for method in METHODS {
+        for predictor in PREDICTORS {
+            check_round_trip(method, predictor, false);
+        }
+    }
+}
+
+#[test]
+fn compressed_tiles_round_trip() {
+    for method in METHODS {
+        for predictor in PREDICTORS {
+            check_round_trip(method, predictor, true);
+        }
+    }
+}
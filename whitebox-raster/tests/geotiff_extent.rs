@@ -0,0 +1,164 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that the extent read from a GeoTIFF reaches the outer edges of the last row and
+//! column of cells, rather than stopping one cell short of them.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{DataType, PhotometricInterpretation, Raster, RasterConfigs};
+
+const ROWS: usize = 3;
+const COLUMNS: usize = 4;
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_extent_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+fn check_extent(raster: &Raster, west: f64, north: f64, resolution: f64) {
+    let configs = &raster.configs;
+    assert_eq!(configs.rows, ROWS);
+    assert_eq!(configs.columns, COLUMNS);
+    assert_eq!(configs.west, west);
+    assert_eq!(configs.north, north);
+    assert_eq!(configs.east, west + COLUMNS as f64 * resolution);
+    assert_eq!(configs.south, north - ROWS as f64 * resolution);
+    assert_eq!(configs.resolution_x, resolution);
+    assert_eq!(configs.resolution_y, resolution);
+
+    // the last cell is centred half a cell inside the east and south edges
+    let last_col = COLUMNS as isize - 1;
+    let last_row = ROWS as isize - 1;
+    assert_eq!(
+        raster.get_x_from_column(last_col),
+        configs.east - resolution / 2.0
+    );
+    assert_eq!(
+        raster.get_y_from_row(last_row),
+        configs.south + resolution / 2.0
+    );
+    assert_eq!(
+        raster.get_column_from_x(configs.east - resolution / 4.0),
+        last_col
+    );
+    assert_eq!(
+        raster.get_row_from_y(configs.south + resolution / 4.0),
+        last_row
+    );
+}
+
+#[test]
+fn tie_point_extent_includes_the_last_cell() {
+    let file_name = temp_file("tie_point.tif");
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: -32768.0,
+        north: 4_800_030.0,
+        south: 4_800_000.0,
+        west: 500_000.0,
+        east: 500_040.0,
+        resolution_x: 10.0,
+        resolution_y: 10.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(&file_name, &configs);
+    raster.set_value(0, 0, 1.0);
+    raster.write().expect("Error writing the raster.");
+
+    let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+    check_extent(&input, 500_000.0, 4_800_030.0, 10.0);
+}
+
+// A little-endian, single-strip, 32-bit float GeoTIFF whose position is given by a
+// ModelTransformationTag rather than by a tie point and pixel scale, and whose only geokey
+// is the model type.
+fn model_transformation_tiff(west: f64, north: f64, resolution: f64) -> Vec<u8> {
+    const NUM_ENTRIES: usize = 12;
+    let ifd_end = 8 + 2 + NUM_ENTRIES * 12 + 4;
+    let transformation_offset = ifd_end as u32;
+    let geokeys_offset = transformation_offset + 16 * 8;
+    let geokeys: [u16; 8] = [1, 1, 0, 1, 1024, 0, 1, 1];
+    let data_offset = geokeys_offset + 8 * 2;
+    let data_size = (ROWS * COLUMNS * 4) as u32;
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(b"II");
+    bytes.extend_from_slice(&42u16.to_le_bytes());
+    bytes.extend_from_slice(&8u32.to_le_bytes());
+    bytes.extend_from_slice(&(NUM_ENTRIES as u16).to_le_bytes());
+    // (tag, field type, count, value); field types 3, 4 and 12 are SHORT, LONG and DOUBLE
+    let entries: [(u16, u16, u32, u32); NUM_ENTRIES] = [
+        (256, 3, 1, COLUMNS as u32),
+        (257, 3, 1, ROWS as u32),
+        (258, 3, 1, 32),
+        (259, 3, 1, 1),
+        (262, 3, 1, 1),
+        (273, 4, 1, data_offset),
+        (277, 3, 1, 1),
+        (278, 3, 1, ROWS as u32),
+        (279, 4, 1, data_size),
+        (339, 3, 1, 3),
+        (34264, 12, 16, transformation_offset),
+        (34735, 3, 8, geokeys_offset),
+    ];
+    for (tag, field_type, count, value) in entries {
+        bytes.extend_from_slice(&tag.to_le_bytes());
+        bytes.extend_from_slice(&field_type.to_le_bytes());
+        bytes.extend_from_slice(&count.to_le_bytes());
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    let transformation = [
+        resolution,
+        0.0,
+        0.0,
+        west,
+        0.0,
+        -resolution,
+        0.0,
+        north,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    ];
+    for v in transformation {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+    for v in geokeys {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+    for i in 0..ROWS * COLUMNS {
+        bytes.extend_from_slice(&(i as f32).to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn model_transformation_extent_includes_the_last_cell() {
+    let file_name = temp_file("model_transformation.tif");
+    fs::write(&file_name, model_transformation_tiff(100.0, 50.0, 2.0))
+        .expect("Error writing the raster.");
+
+    let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+    check_extent(&input, 100.0, 50.0, 2.0);
+    assert_eq!(
+        input.get_value(ROWS as isize - 1, COLUMNS as isize - 1),
+        11.0
+    );
+}
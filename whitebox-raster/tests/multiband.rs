@@ -0,0 +1,195 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that multi-band GeoTIFFs of each data type are written and read back without
+//! changing their values, with the bands either interleaved (PlanarConfiguration = 1) or in
+//! separate planes (PlanarConfiguration = 2), and both uncompressed and compressed with a
+//! predictor, which differences the samples of interleaved bands band by band.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{
+    DataType, GeoTiffReader, PhotometricInterpretation, Raster, RasterConfigs, TiffCompression,
+    TiffPlanarConfig, TiffPredictor,
+};
+
+const ROWS: usize = 4;
+const COLUMNS: usize = 5;
+const BANDS: usize = 3;
+
+const DATA_TYPES: [DataType; 10] = [
+    DataType::I8,
+    DataType::U8,
+    DataType::I16,
+    DataType::U16,
+    DataType::I32,
+    DataType::U32,
+    DataType::I64,
+    DataType::U64,
+    DataType::F32,
+    DataType::F64,
+];
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+// The value of a cell, which every data type can hold.
+fn cell_value(data_type: DataType, band: usize, row: usize, col: usize) -> f64 {
+    let value = (band * ROWS * COLUMNS + row * COLUMNS + col) as f64;
+    match data_type {
+        DataType::I8 | DataType::I16 | DataType::I32 | DataType::I64 => -value,
+        DataType::F32 | DataType::F64 => value + 0.25,
+        _ => value,
+    }
+}
+
+fn write_multiband(
+    file_name: &str,
+    data_type: DataType,
+    planar_config: TiffPlanarConfig,
+    compressed: bool,
+) {
+    let (compression, predictor) = match (compressed, data_type.is_float()) {
+        (false, _) => (TiffCompression::None, TiffPredictor::None),
+        (true, false) => (TiffCompression::Deflate, TiffPredictor::Horizontal),
+        (true, true) => (TiffCompression::Deflate, TiffPredictor::FloatingPoint),
+    };
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: 100.0,
+        north: 100.0,
+        south: 100.0 - ROWS as f64,
+        west: 0.0,
+        east: COLUMNS as f64,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    raster.configs.compression = Some(compression);
+    raster.configs.predictor = predictor;
+    raster.configs.planar_config = planar_config;
+    raster.set_num_bands(BANDS as u8);
+    for band in 0..BANDS {
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                raster.set_band_value(
+                    band,
+                    row as isize,
+                    col as isize,
+                    cell_value(data_type, band, row, col),
+                );
+            }
+        }
+    }
+    raster.write().expect("Error writing the raster.");
+}
+
+// Returns the value of the PlanarConfiguration tag of a little-endian classic TIFF file.
+fn planar_configuration(file_name: &str) -> Option<u16> {
+    let bytes = fs::read(file_name).expect("Error reading the file.");
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+    let ifd = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+    (0..u16_at(ifd) as usize)
+        .map(|e| ifd + 2 + e * 12)
+        .find(|&entry| u16_at(entry) == 284)
+        .map(|entry| u16_at(entry + 8))
+}
+
+fn check_round_trip(planar_config: TiffPlanarConfig, compressed: bool) {
+    for data_type in DATA_TYPES {
+        let name = format!(
+            "multiband_{:?}_{:?}_{}.tif",
+            data_type, planar_config, compressed
+        );
+        let file_name = temp_file(&name);
+        write_multiband(&file_name, data_type, planar_config, compressed);
+
+        let expected_tag = match planar_config {
+            TiffPlanarConfig::Chunky => 1,
+            TiffPlanarConfig::Planar => 2,
+        };
+        assert_eq!(planar_configuration(&file_name), Some(expected_tag), "{}", name);
+
+        let raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+        assert_eq!(raster.num_bands(), BANDS, "{}", name);
+        assert_eq!(raster.configs.data_type, data_type, "{}", name);
+        let mut reader = GeoTiffReader::new(&file_name).expect("Error opening the raster.");
+        assert_eq!(reader.num_bands(), BANDS, "{}", name);
+        for band in 0..BANDS {
+            for row in 0..ROWS {
+                for col in 0..COLUMNS {
+                    let expected = cell_value(data_type, band, row, col);
+                    assert_eq!(
+                        raster.get_band_value(band, row as isize, col as isize),
+                        expected,
+                        "{} band {} ({}, {})",
+                        name,
+                        band,
+                        row,
+                        col
+                    );
+                    assert_eq!(
+                        reader
+                            .get_band_value(band, row as isize, col as isize)
+                            .expect("Error reading a cell."),
+                        expected,
+                        "{} band {} ({}, {}), streamed",
+                        name,
+                        band,
+                        row,
+                        col
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn chunky_multiband_round_trips() {
+    check_round_trip(TiffPlanarConfig::Chunky, false);
+}
+
+#[test]
+fn planar_multiband_round_trips() {
+    check_round_trip(TiffPlanarConfig::Planar, false);
+}
+
+#[test]
+fn compressed_chunky_multiband_round_trips() {
+    check_round_trip(TiffPlanarConfig::Chunky, true);
+}
+
+#[test]
+fn compressed_planar_multiband_round_trips() {
+    check_round_trip(TiffPlanarConfig::Planar, true);
+}
+
+#[test]
+fn selected_band_is_written_as_a_single_band() {
+    let file_name = temp_file("multiband_select.tif");
+    write_multiband(&file_name, DataType::I16, TiffPlanarConfig::Chunky, false);
+    let mut raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+    raster.select_band(2).expect("Error selecting the band.");
+    let output_file = temp_file("multiband_selected.tif");
+    raster.set_file_name(&output_file);
+    raster.write().expect("Error writing the raster.");
+
+    let output = Raster::new(&output_file, "r").expect("Error reading the raster.");
+    assert_eq!(output.num_bands(), 1);
+    assert_eq!(planar_configuration(&output_file), None);
+    assert_eq!(output.get_value(3, 4), cell_value(DataType::I16, 2, 3, 4));
+}
@@ -0,0 +1,222 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that rasters with NaN nodata, as GDAL writes floating-point rasters, are read and
+//! written without changing their nodata value or their valid cells.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{
+    DataType, GeoTiffReader, GeoTiffWriter, PhotometricInterpretation, Raster, RasterConfigs,
+};
+
+const ROWS: usize = 4;
+const COLUMNS: usize = 5;
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+fn configs(nodata: f64) -> RasterConfigs {
+    RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata,
+        north: 100.0,
+        south: 100.0 - ROWS as f64,
+        west: 0.0,
+        east: COLUMNS as f64,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    }
+}
+
+// The value of a cell: the first row is NaN, and the cell at (1, 1) is -32768, a valid
+// elevation that must not be taken for nodata.
+fn cell_value(row: usize, col: usize) -> f64 {
+    match (row, col) {
+        (0, _) => f64::NAN,
+        (1, 1) => -32768.0,
+        _ => (row * COLUMNS + col) as f64 + 0.5,
+    }
+}
+
+fn write_test_raster(file_name: &str, nodata: f64) {
+    let mut raster = Raster::initialize_using_config(file_name, &configs(nodata));
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            raster.set_value(row as isize, col as isize, cell_value(row, col));
+        }
+    }
+    raster.write().expect("Error writing the raster.");
+}
+
+fn check_cells(raster: &Raster) {
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            let z = raster.get_value(row as isize, col as isize);
+            if row == 0 {
+                assert!(
+                    raster.is_nodata(z),
+                    "({}, {}) is {}, not nodata",
+                    row,
+                    col,
+                    z
+                );
+            } else {
+                assert!(!raster.is_nodata(z), "({}, {}) is nodata", row, col);
+                assert_eq!(z, cell_value(row, col));
+            }
+        }
+    }
+}
+
+#[test]
+fn nan_nodata_is_kept() {
+    let file_name = temp_file("nan_nodata.tif");
+    write_test_raster(&file_name, f64::NAN);
+    let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+    assert!(input.configs.nodata.is_nan());
+    assert_eq!(input.configs.minimum, -32768.0);
+    check_cells(&input);
+}
+
+#[test]
+fn nan_nodata_round_trips() {
+    let file_name = temp_file("round_trip_input.tif");
+    write_test_raster(&file_name, f64::NAN);
+    let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+
+    // an output created from the input, as the tools create them
+    let output_file = temp_file("round_trip_output.tif");
+    let mut output = Raster::initialize_using_file(&output_file, &input);
+    assert!(output.configs.nodata.is_nan());
+    for row in 0..ROWS as isize {
+        for col in 0..COLUMNS as isize {
+            output.set_value(row, col, input.get_value(row, col));
+        }
+    }
+    output.write().expect("Error writing the raster.");
+    check_cells(&output);
+
+    let output = Raster::new(&output_file, "r").expect("Error reading the raster.");
+    assert!(output.configs.nodata.is_nan());
+    check_cells(&output);
+}
+
+#[test]
+fn integer_output_of_nan_nodata_input_is_written_with_finite_nodata() {
+    let file_name = temp_file("integer_input.tif");
+    write_test_raster(&file_name, f64::NAN);
+    let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+
+    // a classification of the input, with the input's nodata value
+    let output_file = temp_file("integer_output.tif");
+    let mut output = Raster::initialize_using_file(&output_file, &input);
+    output.configs.data_type = DataType::I16;
+    for row in 0..ROWS as isize {
+        for col in 0..COLUMNS as isize {
+            let z = input.get_value(row, col);
+            let class = if input.is_nodata(z) {
+                input.configs.nodata
+            } else {
+                (z > 10.0) as u8 as f64
+            };
+            output.set_value(row, col, class);
+        }
+    }
+    output.write().expect("Error writing the raster.");
+    // writing leaves the raster as it was
+    assert!(output.configs.nodata.is_nan());
+    assert!(output.get_value(0, 0).is_nan());
+
+    let output = Raster::new(&output_file, "r").expect("Error reading the raster.");
+    assert_eq!(output.configs.nodata, -32768.0);
+    assert_eq!(output.configs.data_type, DataType::I16);
+    for row in 0..ROWS as isize {
+        for col in 0..COLUMNS as isize {
+            let z = output.get_value(row, col);
+            assert_eq!(output.is_nodata(z), row == 0, "({}, {})", row, col);
+        }
+    }
+    assert_eq!(output.get_value(1, 1), 0.0);
+    assert_eq!(output.get_value(3, 4), 1.0);
+}
+
+#[test]
+fn nan_nodata_is_replaced_in_surfer_grids() {
+    let file_name = temp_file("surfer_input.tif");
+    write_test_raster(&file_name, f64::NAN);
+    let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+    let mut output = input.clone();
+    output.set_file_name(&temp_file("surfer_output.grd"));
+    assert_eq!(output.configs.nodata, 1.71041e38);
+    assert_eq!(output.get_value(0, 0), 1.71041e38);
+    check_cells(&output);
+}
+
+#[test]
+fn infinite_nodata_is_kept() {
+    let file_name = temp_file("infinite_nodata.tif");
+    write_test_raster(&file_name, f64::NEG_INFINITY);
+    let raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+    assert_eq!(raster.configs.nodata, f64::NEG_INFINITY);
+}
+
+#[test]
+fn streamed_nan_nodata_round_trips() {
+    let file_name = temp_file("streamed_input.tif");
+    write_test_raster(&file_name, f64::NAN);
+    let mut reader = GeoTiffReader::new(&file_name).expect("Error opening the raster.");
+    assert!(reader.configs.nodata.is_nan());
+
+    let output_file = temp_file("streamed_output.tif");
+    let mut writer =
+        GeoTiffWriter::new(&output_file, &reader.configs).expect("Error creating the raster.");
+    let integer_file = temp_file("streamed_integer_output.tif");
+    let mut configs = reader.configs.clone();
+    configs.data_type = DataType::U8;
+    let mut integer_writer =
+        GeoTiffWriter::new(&integer_file, &configs).expect("Error creating the raster.");
+    for row in 0..ROWS as isize {
+        let values = reader.get_row_data(row).expect("Error reading a row.");
+        writer.write_row(&values).expect("Error writing a row.");
+        let classes = values
+            .iter()
+            .map(|&z| if z.is_nan() { z } else { (z > 10.0) as u8 as f64 })
+            .collect::<Vec<f64>>();
+        integer_writer.write_row(&classes).expect("Error writing a row.");
+    }
+    writer.finish().expect("Error writing the raster.");
+    integer_writer.finish().expect("Error writing the raster.");
+
+    let output = Raster::new(&output_file, "r").expect("Error reading the raster.");
+    assert!(output.configs.nodata.is_nan());
+    check_cells(&output);
+
+    let output = Raster::new(&integer_file, "r").expect("Error reading the raster.");
+    assert_eq!(output.configs.nodata, 255.0);
+    assert!(output.is_nodata(output.get_value(0, 2)));
+    assert_eq!(output.get_value(1, 1), 0.0);
+    assert_eq!(output.get_value(3, 4), 1.0);
+}
+
+#[test]
+fn nan_nodata_is_written_as_gdal_writes_it() {
+    let file_name = temp_file("gdal_nodata.tif");
+    write_test_raster(&file_name, f64::NAN);
+    let bytes = fs::read(&file_name).expect("Error reading the file.");
+    // the GDAL_NODATA tag holds an ASCII string
+    assert!(bytes.windows(4).any(|w| w == b"nan\0" || w == b"nan "));
+}
@@ -0,0 +1,190 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that the windows read by GeoTiffReader match the cells of the same file read in
+//! full by Raster::new, for stripped and tiled files, for windows that cross the edges of
+//! the tiles and of the grid, and with block caches that are too small to hold more than a
+//! few blocks, so that blocks are evicted and read again.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{
+    CogOptions, DataType, GeoTiffReader, PhotometricInterpretation, Raster, RasterConfigs,
+    TiffPlanarConfig,
+};
+
+const ROWS: usize = 50;
+const COLUMNS: usize = 70;
+const BANDS: usize = 2;
+const TILE_SIZE: usize = 16;
+
+// The size of a decoded tile, in bytes.
+const TILE_BYTES: usize = TILE_SIZE * TILE_SIZE * 4;
+
+const CACHE_SIZES: [usize; 4] = [0, TILE_BYTES, 3 * TILE_BYTES, 64 * 1024 * 1024];
+
+// Windows (row, column, rows, columns) that are aligned with the tiles, that cross their
+// edges, that extend beyond the edges of the grid, and that lie outside of it.
+const WINDOWS: [(isize, isize, usize, usize); 9] = [
+    (0, 0, ROWS, COLUMNS),
+    (0, 0, TILE_SIZE, TILE_SIZE),
+    (16, 32, 16, 32),
+    (10, 12, 20, 25),
+    (15, 15, 2, 2),
+    (-3, -5, 10, 12),
+    (45, 60, 10, 15),
+    (-2, -2, ROWS + 4, COLUMNS + 4),
+    (60, 80, 5, 5),
+];
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+fn cell_value(band: usize, row: usize, col: usize) -> f64 {
+    if row == col {
+        -32768.0
+    } else {
+        (band * 10_000 + row * COLUMNS + col) as f64 * 0.5
+    }
+}
+
+fn write_test_raster(file_name: &str, cog: Option<CogOptions>, planar_config: TiffPlanarConfig) {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: -32768.0,
+        north: 100.0,
+        south: 100.0 - ROWS as f64,
+        west: 0.0,
+        east: COLUMNS as f64,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    raster.configs.cog = cog;
+    raster.configs.planar_config = planar_config;
+    raster.set_num_bands(BANDS as u8);
+    for band in 0..BANDS {
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                raster.set_band_value(band, row as isize, col as isize, cell_value(band, row, col));
+            }
+        }
+    }
+    raster.write().expect("Error writing the raster.");
+}
+
+// Writes the test files, whose names begin with `prefix`, since the tests are run in parallel.
+fn test_files(prefix: &str) -> Vec<String> {
+    let tiled = CogOptions {
+        tile_size: TILE_SIZE,
+        ..Default::default()
+    };
+    let files = [
+        ("strips.tif", None, TiffPlanarConfig::Planar),
+        ("chunky_strips.tif", None, TiffPlanarConfig::Chunky),
+        ("tiles.tif", Some(tiled), TiffPlanarConfig::Planar),
+    ];
+    files
+        .iter()
+        .map(|&(name, cog, planar_config)| {
+            let file_name = temp_file(&format!("{}_{}", prefix, name));
+            write_test_raster(&file_name, cog, planar_config);
+            file_name
+        })
+        .collect()
+}
+
+#[test]
+fn windows_match_the_full_raster() {
+    for file_name in test_files("streaming_windows") {
+        let raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+        for cache_size in CACHE_SIZES {
+            let mut reader = GeoTiffReader::new(&file_name)
+                .expect("Error opening the raster.")
+                .with_cache_size(cache_size);
+            assert_eq!(reader.num_bands(), BANDS);
+            // each window is read twice, so that the second read may use cached blocks
+            for (row, column, rows, columns) in WINDOWS.iter().chain(WINDOWS.iter().rev()) {
+                for band in 0..BANDS {
+                    let window = reader
+                        .read_band_window(band, *row, *column, *rows, *columns)
+                        .expect("Error reading a window.");
+                    assert_eq!(window.rows as usize, *rows);
+                    assert_eq!(window.columns as usize, *columns);
+                    for r in 0..*rows as isize {
+                        for c in 0..*columns as isize {
+                            assert_eq!(
+                                window.get_value(r, c),
+                                raster.get_band_value(band, row + r, column + c),
+                                "{} cache {} band {} window ({}, {}) cell ({}, {})",
+                                file_name,
+                                cache_size,
+                                band,
+                                row,
+                                column,
+                                r,
+                                c
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cells_match_the_full_raster() {
+    for file_name in test_files("streaming_cells") {
+        let raster = Raster::new(&file_name, "r").expect("Error reading the raster.");
+        let mut reader = GeoTiffReader::new(&file_name)
+            .expect("Error opening the raster.")
+            .with_cache_size(TILE_BYTES);
+        // cells are visited column by column, which moves between blocks at every cell
+        for col in -1..=COLUMNS as isize {
+            for row in -1..=ROWS as isize {
+                for band in 0..BANDS {
+                    assert_eq!(
+                        reader.get_band_value(band, row, col).expect("Error reading a cell."),
+                        raster.get_band_value(band, row, col),
+                        "{} band {} ({}, {})",
+                        file_name,
+                        band,
+                        row,
+                        col
+                    );
+                }
+            }
+        }
+        for row in 0..ROWS as isize {
+            assert_eq!(
+                reader.get_row_data(row).expect("Error reading a row."),
+                (0..COLUMNS as isize)
+                    .map(|col| raster.get_value(row, col))
+                    .collect::<Vec<f64>>()
+            );
+        }
+    }
+}
+
+#[test]
+fn tiled_files_are_read_in_tiles() {
+    let files = test_files("streaming_blocks");
+    let reader = GeoTiffReader::new(&files[2]).expect("Error opening the raster.");
+    assert_eq!(reader.block_size(), (TILE_SIZE, TILE_SIZE));
+    let reader = GeoTiffReader::new(&files[0]).expect("Error opening the raster.");
+    assert_eq!(reader.block_size(), (1, COLUMNS));
+}
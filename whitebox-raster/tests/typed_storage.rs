@@ -0,0 +1,209 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that raster values are held in the native data type of the raster, that values
+//! the native type cannot represent widen the storage rather than being truncated, and
+//! that the values of each type survive being read and written back unchanged.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{DataType, PhotometricInterpretation, Raster, RasterConfigs, RasterData};
+
+const ROWS: usize = 3;
+const COLUMNS: usize = 4;
+
+fn temp_file(name: &str) -> String {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("whitebox_raster_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir.join(name).to_string_lossy().to_string()
+}
+
+// Values of each type, including the extremes of its range.
+fn test_values(data_type: DataType) -> Vec<f64> {
+    match data_type {
+        DataType::I8 => vec![i8::MIN as f64, -1.0, 0.0, 1.0, i8::MAX as f64],
+        DataType::U16 => vec![0.0, 1.0, 255.0, 256.0, u16::MAX as f64],
+        DataType::I32 => vec![i32::MIN as f64, -65_536.0, 0.0, 16_777_217.0, i32::MAX as f64],
+        DataType::F32 => vec![
+            f32::MIN as f64,
+            -0.1f32 as f64,
+            f32::MIN_POSITIVE as f64,
+            f32::EPSILON as f64,
+            f32::MAX as f64,
+        ],
+        DataType::F64 => vec![
+            f64::MIN,
+            -0.1,
+            1e-300,
+            123_456_789.123_456_79,
+            f64::MAX,
+        ],
+        _ => unreachable!(),
+    }
+}
+
+const DATA_TYPES: [DataType; 5] = [
+    DataType::I8,
+    DataType::U16,
+    DataType::I32,
+    DataType::F32,
+    DataType::F64,
+];
+
+fn cell_value(data_type: DataType, row: usize, col: usize) -> f64 {
+    let values = test_values(data_type);
+    values[(row * COLUMNS + col) % values.len()]
+}
+
+fn new_raster(file_name: &str, data_type: DataType) -> Raster {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: 2.0,
+        north: 100.0,
+        south: 100.0 - ROWS as f64,
+        west: 0.0,
+        east: COLUMNS as f64,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            raster.set_value(row as isize, col as isize, cell_value(data_type, row, col));
+        }
+    }
+    raster
+}
+
+fn check_values(raster: &Raster, data_type: DataType) {
+    assert_eq!(
+        raster.get_data_size_in_bytes(),
+        ROWS * COLUMNS * data_type.get_data_size(),
+        "{:?}",
+        data_type
+    );
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            let z = raster.get_value(row as isize, col as isize);
+            let expected = cell_value(data_type, row, col);
+            assert_eq!(
+                z.to_bits(),
+                expected.to_bits(),
+                "{:?} ({}, {}) is {}, not {}",
+                data_type,
+                row,
+                col,
+                z,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn values_are_stored_in_the_native_type() {
+    for data_type in DATA_TYPES {
+        let data = RasterData::filled(data_type, 0.0, 10);
+        assert_eq!(data.data_type(), data_type);
+        assert_eq!(data.size_in_bytes(), 10 * data_type.get_data_size());
+
+        let raster = new_raster(&temp_file("native.tif"), data_type);
+        check_values(&raster, data_type);
+    }
+}
+
+#[test]
+fn values_outside_the_native_type_widen_the_storage() {
+    let cases = [
+        (DataType::I8, 200.0, DataType::I16),
+        (DataType::U16, -1.0, DataType::I32),
+        (DataType::I32, 0.5, DataType::F64),
+        (DataType::F32, 0.1, DataType::F64),
+    ];
+    for (data_type, value, widened) in cases {
+        let mut data = RasterData::filled(data_type, 1.0, 4);
+        data.set_value(3, value);
+        assert_eq!(data.data_type(), widened, "{:?}", data_type);
+        assert_eq!(data.get_value(3), value);
+        assert_eq!(data.get_value(0), 1.0);
+
+        // values are narrowed again only when every value fits the native type
+        data.compact(data_type);
+        assert_eq!(data.data_type(), widened, "{:?}", data_type);
+        data.set_value(3, 1.0);
+        data.compact(data_type);
+        assert_eq!(data.data_type(), data_type, "{:?}", data_type);
+    }
+}
+
+#[test]
+fn values_are_written_back_losslessly() {
+    for data_type in DATA_TYPES {
+        let file_name = temp_file(&format!("typed_{:?}.tif", data_type));
+        let mut raster = new_raster(&file_name, data_type);
+        raster.write().expect("Error writing the raster.");
+
+        let input = Raster::new(&file_name, "r").expect("Error reading the raster.");
+        assert_eq!(input.configs.data_type, data_type);
+        check_values(&input, data_type);
+
+        // an output created from the input, as the tools create them
+        let output_file = temp_file(&format!("typed_{:?}_output.tif", data_type));
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        for row in 0..ROWS as isize {
+            for col in 0..COLUMNS as isize {
+                output.set_value(row, col, input.get_value(row, col));
+            }
+        }
+        check_values(&output, data_type);
+        output.write().expect("Error writing the raster.");
+
+        let output = Raster::new(&output_file, "r").expect("Error reading the raster.");
+        assert_eq!(output.configs.data_type, data_type);
+        check_values(&output, data_type);
+    }
+}
+
+#[test]
+fn reading_and_writing_values_keeps_the_native_storage() {
+    for data_type in DATA_TYPES {
+        let mut raster = new_raster(&temp_file("access.tif"), data_type);
+        let size = ROWS * COLUMNS * data_type.get_data_size();
+        assert_eq!(raster.get_data_size_in_bytes(), size, "{:?}", data_type);
+
+        // a neighbourhood scan of the kind the tools perform, including cells off the grid
+        for row in -1..=ROWS as isize {
+            for col in -1..=COLUMNS as isize {
+                let z = raster.get_value(row, col);
+                if row < 0 || col < 0 || row == ROWS as isize || col == COLUMNS as isize {
+                    assert_eq!(z, raster.configs.nodata);
+                }
+            }
+        }
+        for row in 0..ROWS as isize {
+            for col in 0..COLUMNS as isize {
+                let z = raster.get_value(row, col);
+                raster.set_value(row, col, z);
+            }
+        }
+        // writes off the grid are ignored, and so cannot widen the storage
+        raster.set_value(-1, 0, 0.5);
+        assert_eq!(raster.get_data_size_in_bytes(), size, "{:?}", data_type);
+        check_values(&raster, data_type);
+    }
+
+    // indexing a 32-bit raster, by contrast, builds a 64-bit copy of its values
+    let raster = new_raster(&temp_file("index.tif"), DataType::F32);
+    assert_eq!(raster[(1, 1)], cell_value(DataType::F32, 1, 1));
+    assert_eq!(raster.get_data_size_in_bytes(), ROWS * COLUMNS * 12);
+}
@@ -0,0 +1,44 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+/*!
+The whitebox_tools library makes the WhiteboxTools tools available to Rust programs. Every
+tool can be run with command-line style arguments, using `ToolManager::run_tool`, and
+some tools can also be run on in-memory data, without reading or writing any files. For
+example, the following derives a flow accumulation raster from a DEM:
+
+```no_run
+use std::sync::Arc;
+use whitebox_raster::Raster;
+use whitebox_tools::tools::hydro_analysis::*;
+
+let dem = Arc::new(Raster::new("dem.tif", "r").unwrap());
+let breached = BreachDepressionsLeastCost::new()
+    .process(dem, &BreachDepressionsLeastCostParameters::default(), false)
+    .unwrap();
+let pointer = D8Pointer::new()
+    .process(Arc::new(breached), &D8PointerParameters::default(), false)
+    .unwrap();
+let params = D8FlowAccumulationParameters {
+    pntr: true,
+    ..Default::default()
+};
+let mut flow_accum = D8FlowAccumulation::new()
+    .process(Arc::new(pointer), &params, false)
+    .unwrap();
+flow_accum.set_file_name("flow_accum.tif");
+flow_accum.write().unwrap();
+```
+*/
+
+pub mod tools;
+
+use nalgebra as na;
+
+#[macro_use]
+extern crate serde_derive;
@@ -19,37 +19,34 @@ by the WhiteboxTools library:
 | ----------------- | ------------------------------------------------------------------------------------------------- |
 | --cd, --wd        | Changes the working directory; used in conjunction with --run flag.                               |
 | -h, --help        | Prints help information.                                                                          |
+| --halo            | Used with --tile_size; the number of cells of overlap added around each tile; --halo=16.          |
+| --json            | Used with --run; reports progress, warnings, output files, and a summary as JSON lines.           |
 | -l, --license     | Prints the whitebox-tools license. Tool names may also be used, --license=\"Slope\"               |
 | --listtools       | Lists all available tools, with tool descriptions. Keywords may also be used, --listtools slope.  |
 | -r, --run         | Runs a tool; used in conjunction with --cd flag; -r="LidarInfo".                                  |
+| --tile_size       | Used with --run; runs a raster tool over tiles of this many cells and mosaics the output.         |
 | --toolbox         | Prints the toolbox associated with a tool; --toolbox=Slope.                                       |
 | --toolhelp        | Prints the help associated with a tool; --toolhelp="LidarInfo".                                   |
 | --toolparameters  | Prints the parameters (in json form) for a specific tool; --toolparameters=\"LidarInfo\".         |
 | -v                | Verbose mode. With this flag set to false, tool outputs will not be printed. -v=true, -v=false    |
 | --viewcode        | Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".                       |
 | --version         | Prints the version information.                                                                   |
+| --workflow        | Runs the tool steps of a workflow JSON file; --workflow=\"dem_workflow.json\".                    |
+
+When a tool fails, the exit code of the whitebox_tools process identifies the category of the error:
+1 (tool failure), 2 (bad argument), 3 (I/O), 4 (unsupported format), or 5 (license).
 
 */
 
-// pub mod algorithms;
-// pub mod lidar;
-// pub mod raster;
-// pub mod rendering;
-// pub mod spatial_ref_system;
-// pub mod structures;
-pub mod tools;
-// pub mod utils;
-// pub mod vector;
-
-use crate::tools::ToolManager;
-use nalgebra as na;
-// use rstar;
+// The tools are defined in the whitebox_tools library (lib.rs).
+use whitebox_tools::tools::{
+    panic_message, TilingOptions, ToolErrorKind, ToolEvent, ToolManager, JSON_CHILD_ENV_VAR,
+};
 use std::env;
 use std::io::Error;
+use std::panic;
 use std::path;
-
-#[macro_use]
-extern crate serde_derive;
+use std::process;
 
 // extern crate late_static;
 // use late_static::LateStatic;
@@ -67,9 +64,27 @@ extern crate serde_derive;
 /// ```
 
 fn main() {
-    match run() {
-        Ok(()) => {}
-        Err(err) => panic!("{}", err),
+    // Failures are reported with an exit code that identifies their category (see
+    // ToolErrorKind). Most tools report errors by panicking, in which case the default panic
+    // hook has already printed the message.
+    if env::var_os(JSON_CHILD_ENV_VAR).is_some() {
+        // Report panics as error events, which --json mode, workflows, and tiled
+        // processing pass on.
+        panic::set_hook(Box::new(|info| {
+            let kind = ToolErrorKind::of_panic(info.payload());
+            ToolEvent::error(kind, &panic_message(info.payload())).report();
+        }));
+    }
+    let exit_code = match panic::catch_unwind(run) {
+        Ok(Ok(())) => 0,
+        Ok(Err(err)) => {
+            eprintln!("Error: {}", err);
+            ToolErrorKind::of(&err).exit_code()
+        }
+        Err(payload) => ToolErrorKind::of_panic(payload.as_ref()).exit_code(),
+    };
+    if exit_code != 0 {
+        process::exit(exit_code);
     }
 }
 
@@ -97,6 +112,9 @@ fn run() -> Result<(), Error> {
     let mut list_tools = false;
     let mut keywords: Vec<String> = vec![];
     let mut view_code = false;
+    let mut json_output = false;
+    let mut workflow_file = String::new();
+    let mut tiling: Option<TilingOptions> = None;
     let mut tool_args_vec: Vec<String> = vec![];
     // let mut verbose = false;
     let mut finding_working_dir = false;
@@ -258,6 +276,37 @@ fn run() -> Result<(), Error> {
                 license();
             }
             return Ok(());
+        } else if flag_val == "-json" {
+            json_output = true;
+        } else if flag_val.starts_with("-workflow") {
+            let mut v = arg
+                .replace("--workflow", "")
+                .replace("-workflow", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            workflow_file = v;
+        } else if flag_val.starts_with("-tile_size") || flag_val.starts_with("-halo") {
+            let is_halo = flag_val.starts_with("-halo");
+            let mut v = arg
+                .replace("--tile_size", "")
+                .replace("-tile_size", "")
+                .replace("--halo", "")
+                .replace("-halo", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().parse::<usize>().unwrap_or_else(|_| panic!("Error parsing {}", v));
+            let options = tiling.get_or_insert(TilingOptions::default());
+            if is_halo {
+                options.halo = val;
+            } else {
+                options.tile_size = val;
+            }
         } else if arg.starts_with("-compress_raster") || arg.starts_with("--compress_raster") {
             let mut v = arg
                 .replace("--compress_rasters", "")
@@ -360,11 +409,24 @@ fn run() -> Result<(), Error> {
         whitebox_common::configs::save_configs(&configs)?;
     }
 
-    let tm = ToolManager::new(&configs.working_directory, &configs.verbose_mode)?;
-    if run_tool {
+    // A process that runs a tool on behalf of --json mode always reports its progress.
+    let verbose = configs.verbose_mode || env::var_os(JSON_CHILD_ENV_VAR).is_some();
+    let tm = ToolManager::new(&configs.working_directory, &verbose)?;
+    if !workflow_file.is_empty() {
+        return tm.run_workflow(&workflow_file);
+    } else if run_tool {
         if tool_name.is_empty() && keywords.len() > 0 {
             tool_name = keywords[0].clone();
         }
+        if let Some(options) = tiling {
+            return tm.run_tool_tiled(tool_name, tool_args_vec, options);
+        }
+        if json_output {
+            return tm.run_tool_json(tool_name, tool_args_vec);
+        }
+        if env::var_os(JSON_CHILD_ENV_VAR).is_some() {
+            return tm.run_tool_reporting_events(tool_name, tool_args_vec);
+        }
         return tm.run_tool(tool_name, tool_args_vec);
     } else if tool_help {
         if tool_name.is_empty() && keywords.len() > 0 {
@@ -413,17 +475,25 @@ fn help() {
 The following commands are recognized:
 --cd, --wd          Changes the working directory; used in conjunction with --run flag.
 --compress_rasters  Sets the compress_raster option in the settings.json file; determines if newly created rasters are compressed. e.g. --compress_rasters=true
+--cog               Writes GeoTIFF outputs as tiled Cloud-Optimized GeoTIFFs with internal overviews, created using nearest, average, or mode resampling. e.g. --cog=average
 -h, --help          Prints help information.
+--halo              Used with --tile_size; the number of cells of overlap added around each tile, at least the radius of the tool's filter. e.g. --halo=16
+--json              Used with --run; reports progress, warnings, output files, and a summary as JSON lines.
 -l, --license       Prints the whitebox-tools license. Tool names may also be used, --license=\"Slope\"
 --listtools         Lists all available tools. Keywords may also be used, --listtools slope.
 --max_procs         Sets the maximum number of processors used. -1 = all available processors. e.g. --max_procs=2
 -r, --run           Runs a tool; used in conjunction with --wd flag; -r=\"LidarInfo\".
+--tile_size         Used with --run; runs a raster tool over tiles of this many cells, in parallel, and mosaics the output. The input may also be a directory of GeoTIFF tiles. e.g. --tile_size=2048
 --toolbox           Prints the toolbox associated with a tool; --toolbox=Slope.
 --toolhelp          Prints the help associated with a tool; --toolhelp=\"LidarInfo\".
 --toolparameters    Prints the parameters (in json form) for a specific tool; --toolparameters=\"LidarInfo\".
 -v                  Verbose mode. Without this flag, tool outputs will not be printed.
 --viewcode          Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".
 --version           Prints the version information.
+--workflow          Runs the tool steps described in a workflow JSON file, skipping steps that are up to date; --workflow=\"dem_workflow.json\".
+
+Exit Codes:
+0 = success, 1 = tool failure, 2 = bad argument, 3 = I/O error, 4 = unsupported format, 5 = license error
 
 Example Usage:
 >> .*EXE_NAME -r=lidar_info --cd=\"*path*to*data*\" -i=input.las --vlr --geokeys
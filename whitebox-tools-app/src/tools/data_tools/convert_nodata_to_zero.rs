@@ -203,8 +203,8 @@ impl WhiteboxTool for ConvertNodataToZero {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input[(row, col)] != nodata {
-                            data[col as usize] = input[(row, col)];
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
+                            data[col as usize] = input.get_value(row, col);
                         } else {
                             data[col as usize] = 0.0f64;
                         }
@@ -405,6 +405,7 @@ impl WhiteboxTool for MergeTableWithCsv {
 
             pkey_value = match input.attributes.get_value(record_num, &primary_key) {
                 FieldData::Int(v) => v.to_string(),
+                FieldData::Int64(v) => v.to_string(),
                 FieldData::Real(v) => v.to_string(),
                 FieldData::Text(v) => v.to_string(),
                 FieldData::Date(v) => v.to_string(),
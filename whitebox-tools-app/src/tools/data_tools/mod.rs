@@ -19,6 +19,9 @@ mod raster_to_vector_points;
 mod raster_to_vector_polygons;
 mod reinitialize_attribute_table;
 mod remove_polygon_holes;
+mod reproject_lidar;
+mod reproject_raster;
+mod reproject_vector;
 mod set_nodata_value;
 mod singlepart_to_multipart;
 mod vector_lines_to_raster;
@@ -46,6 +49,9 @@ pub use self::raster_to_vector_points::RasterToVectorPoints;
 pub use self::raster_to_vector_polygons::RasterToVectorPolygons;
 pub use self::reinitialize_attribute_table::ReinitializeAttributeTable;
 pub use self::remove_polygon_holes::RemovePolygonHoles;
+pub use self::reproject_lidar::ReprojectLidar;
+pub use self::reproject_raster::ReprojectRaster;
+pub use self::reproject_vector::ReprojectVector;
 pub use self::set_nodata_value::SetNodataValue;
 pub use self::singlepart_to_multipart::SinglePartToMultiPart;
 pub use self::vector_lines_to_raster::VectorLinesToRaster;
@@ -185,7 +185,7 @@ impl WhiteboxTool for ModifyNoDataValue {
         for row in 0..rows {
             for col in 0..columns {
                 value = input.get_value(row, col);
-                if value == nodata {
+                if is_nodata_value(value, nodata) {
                     input.set_value(row, col, new_nodata_value);
                 }
             }
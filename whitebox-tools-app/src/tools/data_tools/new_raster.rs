@@ -12,7 +12,7 @@ use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
-use whitebox_vector::Shapefile;
+use whitebox_vector::{get_vector_format_from_file, Shapefile, VectorFormat};
 
 /// This tool can be used to create a new raster with the same coordinates and dimensions
 /// (i.e. rows and columns) as an existing base image, or the same spatial extent as an input
@@ -239,10 +239,7 @@ impl WhiteboxTool for NewRasterFromBase {
         }
 
         // Get the spatial extent
-        let mut output = if base_file.to_lowercase().ends_with(".shp") {
-            // Note that this only works because at the moment, Shapefiles are the only supported vector.
-            // If additional vector formats are added in the future, this will need updating.
-
+        let mut output = if get_vector_format_from_file(&base_file) != VectorFormat::Unknown {
             if cell_size <= 0f64 {
                 return Err(Error::new(
                     ErrorKind::InvalidInput,
@@ -282,7 +279,7 @@ impl WhiteboxTool for NewRasterFromBase {
             Raster::initialize_using_file(&output_file, &base)
         };
 
-        if output.configs.nodata != nodata || out_val != nodata {
+        if !is_nodata_value(output.configs.nodata, nodata) || !is_nodata_value(out_val, nodata) {
             output.configs.nodata = nodata;
             output.reinitialize_values(out_val);
         }
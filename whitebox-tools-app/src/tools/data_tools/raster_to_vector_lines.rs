@@ -237,11 +237,11 @@ impl WhiteboxTool for RasterToVectorLines {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata_value(z, nodata) {
                     count = 0i8;
                     for i in 0..8 {
                         zn = input.get_value(row + dy[i], col + dx[i]);
-                        if zn != 0f64 && zn != nodata {
+                        if zn != 0f64 && !is_nodata_value(zn, nodata) {
                             count += 1;
                         }
                     }
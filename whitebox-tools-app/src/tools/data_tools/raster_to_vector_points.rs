@@ -219,7 +219,7 @@ impl WhiteboxTool for RasterToVectorPoints {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0f64 && z != nodata {
+                if z != 0.0f64 && !is_nodata_value(z, nodata) {
                     x = input.get_x_from_column(col);
                     y = input.get_y_from_row(row);
                     output.add_point_record(x, y);
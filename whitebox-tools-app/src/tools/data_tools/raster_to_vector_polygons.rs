@@ -242,7 +242,7 @@ impl WhiteboxTool for RasterToVectorPolygons {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata && z != 0f64 && visited.get_value(row, col) != 1 {
+                if !is_nodata_value(z, nodata) && z != 0f64 && visited.get_value(row, col) != 1 {
                     clump_to_value.push(z);
                     clumps.set_value(row, col, clump_val);
                     visited.set_value(row, col, 1);
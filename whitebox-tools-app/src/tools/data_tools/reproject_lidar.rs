@@ -0,0 +1,328 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use whitebox_common::spatial_ref_system::{CoordinateTransformation, SpatialReference};
+use whitebox_lidar::*;
+
+/// This tool transforms the points of a LiDAR file (`--input`) into a different coordinate
+/// reference system, specified by an EPSG code (`--epsg`). The coordinate system of the
+/// input is read from its GeoTIFF key or WKT variable length records (VLRs); it may be
+/// overridden with the `--src_epsg` parameter, which is necessary for files that do not
+/// contain projection VLRs. Only the horizontal coordinates are transformed; elevations
+/// are copied unchanged, as are all other point attributes.
+///
+/// The projection VLRs of the input are replaced in the output file by a record describing
+/// the target system. The coordinate offsets of the output header are reset to suit the
+/// transformed coordinates, and the scale factors are set to 0.0000001 for geographic
+/// outputs and retained (or set to 0.001, when the input is geographic) for projected ones.
+///
+/// # See Also
+/// `ReprojectRaster`, `ReprojectVector`
+pub struct ReprojectLidar {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ReprojectLidar {
+    pub fn new() -> ReprojectLidar {
+        // public constructor
+        let name = "ReprojectLidar".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Transforms a LiDAR file into a different coordinate reference system.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target EPSG Code".to_owned(),
+            flags: vec!["--epsg".to_owned()],
+            description: "EPSG code of the output coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Source EPSG Code (optional)".to_owned(),
+            flags: vec!["--src_epsg".to_owned()],
+            description: "Optional EPSG code of the input, overriding the input's own spatial reference.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=input.las -o=output.las --epsg=32617",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ReprojectLidar {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ReprojectLidar {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut epsg = 0u16;
+        let mut src_epsg = 0u16;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-epsg" || flag_val == "-src_epsg" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                let code = val.trim().parse::<u16>().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Error parsing {} ({}).", flag_val, val),
+                    )
+                })?;
+                if flag_val == "-epsg" {
+                    epsg = code;
+                } else {
+                    src_epsg = code;
+                }
+            }
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if epsg == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The target EPSG code (--epsg) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading input LiDAR file...");
+        }
+        let input = LasFile::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let src_srs = if src_epsg > 0 {
+            SpatialReference::from_epsg(src_epsg)?
+        } else if input.get_epsg_code() > 0 {
+            SpatialReference::from_epsg(input.get_epsg_code())?
+        } else {
+            SpatialReference::from_wkt(&input.wkt).map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "The coordinate reference system of the input LiDAR file could not be determined. Please specify --src_epsg.",
+                )
+            })?
+        };
+        let dst_srs = SpatialReference::from_epsg(epsg)?;
+        let transform = CoordinateTransformation::new(&src_srs, &dst_srs);
+        if transform.is_missing_datum_shift() && verbose {
+            println!("Warning: The datum shift between the input and output systems is unknown; the datums are assumed to be coincident.");
+        }
+
+        let num_points = input.header.number_of_points as usize;
+        let mut coords: Vec<(f64, f64)> = Vec::with_capacity(num_points);
+        let mut min_x = f64::INFINITY;
+        let mut min_y = f64::INFINITY;
+        for i in 0..num_points {
+            let p = input.get_transformed_coords(i);
+            let (x, y) = transform.transform(p.x, p.y).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error transforming point {}: {}", i + 1, e),
+                )
+            })?;
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            coords.push((x, y));
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_points as f64) as usize;
+                if progress != old_progress {
+                    println!("Transforming points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        output.set_crs_from_epsg(epsg)?;
+        if dst_srs.is_geographic() {
+            output.header.x_scale_factor = 0.0000001;
+            output.header.y_scale_factor = 0.0000001;
+        } else if src_srs.is_geographic() {
+            output.header.x_scale_factor = 0.001;
+            output.header.y_scale_factor = 0.001;
+        }
+        output.header.x_offset = if num_points > 0 { min_x.floor() } else { 0f64 };
+        output.header.y_offset = if num_points > 0 { min_y.floor() } else { 0f64 };
+
+        for (i, &(x, y)) in coords.iter().enumerate() {
+            let mut record = input.get_record(i);
+            let mut point_data = record.get_point_data();
+            point_data.x =
+                ((x - output.header.x_offset) / output.header.x_scale_factor).round() as i32;
+            point_data.y =
+                ((y - output.header.y_offset) / output.header.y_scale_factor).round() as i32;
+            record.set_point_data(point_data);
+            output.add_point_record(record);
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_points as f64) as usize;
+                if progress != old_progress {
+                    println!("Writing points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Writing output LAS file...");
+        }
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Complete!")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
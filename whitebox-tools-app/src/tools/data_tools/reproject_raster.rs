@@ -0,0 +1,550 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use whitebox_common::spatial_ref_system::{
+    esri_wkt_from_epsg, CoordinateTransformation, SpatialReference,
+};
+use whitebox_raster::*;
+
+/// This tool transforms a raster (`--input`) into a different coordinate reference system,
+/// specified by an EPSG code (`--epsg`). The coordinate system of the input raster is read
+/// from its EPSG code or, failing that, its well-known text (WKT) description; it may be
+/// overridden with the `--src_epsg` parameter, which is necessary for rasters that do not
+/// carry any spatial reference information.
+///
+/// The output extent is found by transforming points sampled along the edges and through
+/// the interior of the input grid. The output grid resolution may be specified using
+/// `--cell_size` (in the units of the target system); by default, a resolution is chosen
+/// that preserves the number of grid cells in the input. The value of each output cell is
+/// obtained by transforming the cell centre back into the input system and sampling the
+/// input raster with the chosen `--method`: nearest neighbour ('nn'), which should be used
+/// for categorical data, 'bilinear' interpolation, or cubic convolution ('cc').
+///
+/// Datum shifts are supported for commonly encountered datums (e.g. NAD27, OSGB36, ED50).
+/// Realizations of the ITRS, such as NAD83, ETRS89, and GDA94, are treated as being
+/// coincident with WGS84.
+///
+/// # See Also
+/// `ReprojectVector`, `ReprojectLidar`, `Resample`
+pub struct ReprojectRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ReprojectRaster {
+    pub fn new() -> ReprojectRaster {
+        // public constructor
+        let name = "ReprojectRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Transforms a raster into a different coordinate reference system.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target EPSG Code".to_owned(),
+            flags: vec!["--epsg".to_owned()],
+            description: "EPSG code of the output coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Source EPSG Code (optional)".to_owned(),
+            flags: vec!["--src_epsg".to_owned()],
+            description: "Optional EPSG code of the input, overriding the input's own spatial reference.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Resampling Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Resampling method; options include 'nn' (nearest neighbour), 'bilinear', and 'cc' (cubic convolution)".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "nn".to_owned(),
+                "bilinear".to_owned(),
+                "cc".to_owned(),
+            ]),
+            default_value: Some("cc".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of the output raster, in the units of the target coordinate system.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i=dem.tif -o=dem_utm.tif --epsg=32617 --method=bilinear", short_exe, name).replace("*", &sep);
+
+        ReprojectRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ReprojectRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut epsg = 0u16;
+        let mut src_epsg = 0u16;
+        let mut method = String::from("cc");
+        let mut cell_size = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-epsg" || flag_val == "-src_epsg" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                let code = val.trim().parse::<u16>().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Error parsing {} ({}).", flag_val, val),
+                    )
+                })?;
+                if flag_val == "-epsg" {
+                    epsg = code;
+                } else {
+                    src_epsg = code;
+                }
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
+                {
+                    method = "nn".to_string();
+                } else if method.to_lowercase().contains("bilinear")
+                    || method.to_lowercase().contains("bi")
+                {
+                    method = "bilinear".to_string();
+                } else if method.to_lowercase().contains("cc")
+                    || method.to_lowercase().contains("cubic")
+                {
+                    method = "cc".to_string();
+                }
+            } else if flag_val == "-cell_size" {
+                cell_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if epsg == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The target EPSG code (--epsg) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let src_srs = if src_epsg > 0 {
+            SpatialReference::from_epsg(src_epsg)?
+        } else if input.configs.epsg_code > 0 {
+            SpatialReference::from_epsg(input.configs.epsg_code)?
+        } else {
+            SpatialReference::from_wkt(&input.configs.coordinate_ref_system_wkt)
+                .or_else(|_| SpatialReference::from_wkt(&input.configs.projection))
+                .map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "The coordinate reference system of the input raster could not be determined. Please specify --src_epsg.",
+                    )
+                })?
+        };
+        let dst_srs = SpatialReference::from_epsg(epsg)?;
+        let forward = CoordinateTransformation::new(&src_srs, &dst_srs);
+        if forward.is_missing_datum_shift() && verbose {
+            println!("Warning: The datum shift between the input and output systems is unknown; the datums are assumed to be coincident.");
+        }
+
+        // Find the output extent from points sampled along the edges and through the
+        // interior of the input grid.
+        let rows_in = input.configs.rows as isize;
+        let columns_in = input.configs.columns as isize;
+        let (west, east) = (input.configs.west, input.configs.east);
+        let (south, north) = (input.configs.south, input.configs.north);
+        let num_samples = 100usize;
+        let mut min_x = f64::INFINITY;
+        let mut min_y = f64::INFINITY;
+        let mut max_x = f64::NEG_INFINITY;
+        let mut max_y = f64::NEG_INFINITY;
+        for i in 0..=num_samples {
+            for j in 0..=num_samples {
+                if i % 10 != 0 && j % 10 != 0 && i != num_samples && j != num_samples {
+                    continue; // edges plus a coarser interior lattice
+                }
+                let x = west + (east - west) * i as f64 / num_samples as f64;
+                let y = south + (north - south) * j as f64 / num_samples as f64;
+                if let Ok((x2, y2)) = forward.transform(x, y) {
+                    if x2.is_finite() && y2.is_finite() {
+                        min_x = min_x.min(x2);
+                        max_x = max_x.max(x2);
+                        min_y = min_y.min(y2);
+                        max_y = max_y.max(y2);
+                    }
+                }
+            }
+        }
+        if !min_x.is_finite() || !max_x.is_finite() || max_x <= min_x || max_y <= min_y {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input raster could not be transformed into the target coordinate system.",
+            ));
+        }
+
+        if cell_size <= 0f64 {
+            // preserve the number of grid cells
+            cell_size =
+                ((max_x - min_x) * (max_y - min_y) / (rows_in as f64 * columns_in as f64)).sqrt();
+        }
+        let rows = ((max_y - min_y) / cell_size).ceil() as isize;
+        let columns = ((max_x - min_x) / cell_size).ceil() as isize;
+
+        let mut configs = input.configs.clone();
+        configs.rows = rows as usize;
+        configs.columns = columns as usize;
+        configs.north = max_y;
+        configs.south = max_y - rows as f64 * cell_size;
+        configs.west = min_x;
+        configs.east = min_x + columns as f64 * cell_size;
+        configs.resolution_x = cell_size;
+        configs.resolution_y = cell_size;
+        if method != "nn" {
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            if !configs.data_type.is_float() {
+                configs.data_type = DataType::F32;
+            }
+        }
+        configs.epsg_code = epsg;
+        configs.coordinate_ref_system_wkt = esri_wkt_from_epsg(epsg);
+        configs.projection = configs.coordinate_ref_system_wkt.clone();
+        configs.xy_units = if dst_srs.is_geographic() {
+            "degrees".to_string()
+        } else {
+            "metres".to_string()
+        };
+        configs.model_tiepoint.clear();
+        configs.model_pixel_scale = [0f64; 3];
+        configs.model_transformation = [0f64; 16];
+        configs.geo_key_directory.clear();
+        configs.geo_double_params.clear();
+        configs.geo_ascii_params.clear();
+
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let nodata = output.configs.nodata;
+
+        let inverse = Arc::new(CoordinateTransformation::new(&dst_srs, &src_srs));
+        let input = Arc::new(input);
+        let method = Arc::new(method);
+        let mut num_procs = num_cpus::get() as isize;
+        let configs = whitebox_common::configs::get_configs()?;
+        let max_procs = configs.max_procs;
+        if max_procs > 0 && max_procs < num_procs {
+            num_procs = max_procs;
+        }
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let inverse = inverse.clone();
+            let method = method.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata_in = input.configs.nodata;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let y = max_y - (row as f64 + 0.5) * cell_size;
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let x = min_x + (col as f64 + 0.5) * cell_size;
+                        if let Ok((x_src, y_src)) = inverse.transform(x, y) {
+                            // fractional row and column, relative to cell centres
+                            let row_src = (input.configs.north - y_src) / input.configs.resolution_y - 0.5;
+                            let col_src = (x_src - input.configs.west) / input.configs.resolution_x - 0.5;
+                            let z = sample(&input, row_src, col_src, &method);
+                            if !is_nodata_value(z, nodata_in) {
+                                data[col as usize] = z;
+                            }
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Target EPSG code: {}", epsg));
+        output.add_metadata_entry(format!("Resampling method: {}", method));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Samples the input at a fractional row and column (relative to cell centres). Bilinear
+/// and cubic convolution fall back to simpler methods near NoData cells and grid edges.
+fn sample(input: &Raster, row: f64, col: f64, method: &str) -> f64 {
+    let nodata = input.configs.nodata;
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let row_nn = row.round() as isize;
+    let col_nn = col.round() as isize;
+    if row_nn < 0 || col_nn < 0 || row_nn >= rows || col_nn >= columns {
+        return nodata;
+    }
+    let nearest = input.get_value(row_nn, col_nn);
+    if method == "nn" {
+        return nearest;
+    }
+    let r0 = row.floor() as isize;
+    let c0 = col.floor() as isize;
+    let dr = row - r0 as f64;
+    let dc = col - c0 as f64;
+    let in_grid = |r: isize, c: isize| r >= 0 && c >= 0 && r < rows && c < columns;
+
+    if method == "cc" && in_grid(r0 - 1, c0 - 1) && in_grid(r0 + 2, c0 + 2) {
+        let wr = cubic_weights(dr);
+        let wc = cubic_weights(dc);
+        let mut z = 0f64;
+        let mut valid = true;
+        'outer: for (i, wi) in wr.iter().enumerate() {
+            for (j, wj) in wc.iter().enumerate() {
+                let v = input.get_value(r0 - 1 + i as isize, c0 - 1 + j as isize);
+                if is_nodata_value(v, nodata) {
+                    valid = false;
+                    break 'outer;
+                }
+                z += wi * wj * v;
+            }
+        }
+        if valid {
+            return z;
+        }
+    }
+
+    if in_grid(r0, c0) && in_grid(r0 + 1, c0 + 1) {
+        let z00 = input.get_value(r0, c0);
+        let z01 = input.get_value(r0, c0 + 1);
+        let z10 = input.get_value(r0 + 1, c0);
+        let z11 = input.get_value(r0 + 1, c0 + 1);
+        if !is_nodata_value(z00, nodata)
+            && !is_nodata_value(z01, nodata)
+            && !is_nodata_value(z10, nodata)
+            && !is_nodata_value(z11, nodata)
+        {
+            return z00 * (1f64 - dr) * (1f64 - dc)
+                + z01 * (1f64 - dr) * dc
+                + z10 * dr * (1f64 - dc)
+                + z11 * dr * dc;
+        }
+    }
+    nearest
+}
+
+/// Cubic convolution weights (a = -0.5) for the four samples around a fractional offset.
+fn cubic_weights(t: f64) -> [f64; 4] {
+    let a = -0.5f64;
+    let w = |d: f64| {
+        let d = d.abs();
+        if d <= 1f64 {
+            (a + 2f64) * d * d * d - (a + 3f64) * d * d + 1f64
+        } else if d < 2f64 {
+            a * d * d * d - 5f64 * a * d * d + 8f64 * a * d - 4f64 * a
+        } else {
+            0f64
+        }
+    };
+    [w(1f64 + t), w(t), w(1f64 - t), w(2f64 - t)]
+}
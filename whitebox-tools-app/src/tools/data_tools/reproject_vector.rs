@@ -0,0 +1,312 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use whitebox_common::spatial_ref_system::{
+    esri_wkt_from_epsg, CoordinateTransformation, SpatialReference,
+};
+use whitebox_vector::*;
+
+/// This tool transforms the features of a vector (`--input`) into a different coordinate
+/// reference system, specified by an EPSG code (`--epsg`). The coordinate system of the
+/// input is read from its projection (.prj) file; it may be overridden with the `--src_epsg`
+/// parameter, which is necessary for files that do not have a projection file. Each vertex
+/// is transformed individually, so that the output has the same number of vertices as the
+/// input; Z and M values and the attribute table are copied unchanged. The output
+/// projection file is written in the ESRI WKT form of the target system.
+///
+/// # See Also
+/// `ReprojectRaster`, `ReprojectLidar`
+pub struct ReprojectVector {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ReprojectVector {
+    pub fn new() -> ReprojectVector {
+        // public constructor
+        let name = "ReprojectVector".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Transforms a vector into a different coordinate reference system.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target EPSG Code".to_owned(),
+            flags: vec!["--epsg".to_owned()],
+            description: "EPSG code of the output coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Source EPSG Code (optional)".to_owned(),
+            flags: vec!["--src_epsg".to_owned()],
+            description: "Optional EPSG code of the input, overriding the input's own spatial reference.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=roads.shp -o=roads_wgs84.shp --epsg=4326",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ReprojectVector {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ReprojectVector {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut epsg = 0u16;
+        let mut src_epsg = 0u16;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-epsg" || flag_val == "-src_epsg" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                let code = val.trim().parse::<u16>().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Error parsing {} ({}).", flag_val, val),
+                    )
+                })?;
+                if flag_val == "-epsg" {
+                    epsg = code;
+                } else {
+                    src_epsg = code;
+                }
+            }
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if epsg == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The target EPSG code (--epsg) must be specified.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        let input = Shapefile::read(&input_file)?;
+
+        let src_srs = if src_epsg > 0 {
+            SpatialReference::from_epsg(src_epsg)?
+        } else {
+            SpatialReference::from_wkt(&input.projection).map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "The coordinate reference system of the input vector could not be determined. Please specify --src_epsg.",
+                )
+            })?
+        };
+        let dst_srs = SpatialReference::from_epsg(epsg)?;
+        let transform = CoordinateTransformation::new(&src_srs, &dst_srs);
+        if transform.is_missing_datum_shift() && verbose {
+            println!("Warning: The datum shift between the input and output systems is unknown; the datums are assumed to be coincident.");
+        }
+
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+        output.projection = esri_wkt_from_epsg(epsg);
+
+        for record_num in 0..input.num_records {
+            let mut record = input.get_record(record_num).clone();
+            if record.shape_type != ShapeType::Null {
+                record.x_min = f64::INFINITY;
+                record.x_max = f64::NEG_INFINITY;
+                record.y_min = f64::INFINITY;
+                record.y_max = f64::NEG_INFINITY;
+                for p in record.points.iter_mut() {
+                    let (x, y) = transform.transform(p.x, p.y).map_err(|e| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            format!("Error transforming feature {}: {}", record_num + 1, e),
+                        )
+                    })?;
+                    p.x = x;
+                    p.y = y;
+                    record.x_min = record.x_min.min(x);
+                    record.x_max = record.x_max.max(x);
+                    record.y_min = record.y_min.min(y);
+                    record.y_max = record.y_max.max(y);
+                }
+            }
+            output.add_record(record);
+            output
+                .attributes
+                .add_record(input.attributes.get_record(record_num), false);
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
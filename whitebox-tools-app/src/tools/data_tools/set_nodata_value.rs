@@ -256,7 +256,7 @@ impl WhiteboxTool for SetNodataValue {
                     let mut data = vec![back_value; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != back_value && z != nodata {
+                        if z != back_value && !is_nodata_value(z, nodata) {
                             data[col as usize] = z;
                         }
                     }
@@ -344,7 +344,7 @@ impl WhiteboxTool for VectorLinesToRaster {
             Raster::initialize_using_config(&output_file, &configs)
         };
 
-        if background_val != nodata {
+        if !is_nodata_value(background_val, nodata) {
             output.reinitialize_values(background_val);
         }
 
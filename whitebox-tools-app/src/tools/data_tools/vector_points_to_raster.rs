@@ -354,7 +354,7 @@ impl WhiteboxTool for VectorPointsToRaster {
             Raster::initialize_using_config(&output_file, &configs)
         };
 
-        if background_val != nodata {
+        if !is_nodata_value(background_val, nodata) {
             output.reinitialize_values(background_val);
         }
 
@@ -431,7 +431,7 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata_value(z, nodata) {
                         output.set_value(row, col, attribute_data[record_num]);
                     }
                 }
@@ -457,7 +457,10 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata || attribute_data[record_num] < z {
+                    if z == background_val
+                        || is_nodata_value(z, nodata)
+                        || attribute_data[record_num] < z
+                    {
                         output.set_value(row, col, attribute_data[record_num]);
                     }
                 }
@@ -483,7 +486,10 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata || attribute_data[record_num] > z {
+                    if z == background_val
+                        || is_nodata_value(z, nodata)
+                        || attribute_data[record_num] > z
+                    {
                         output.set_value(row, col, attribute_data[record_num]);
                     }
                 }
@@ -509,7 +515,7 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata_value(z, nodata) {
                         output.set_value(row, col, attribute_data[record_num]);
                     } else {
                         output.set_value(row, col, z + attribute_data[record_num]);
@@ -537,7 +543,7 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata_value(z, nodata) {
                         output.set_value(row, col, 1f64);
                     } else {
                         output.set_value(row, col, z + 1f64);
@@ -567,7 +573,7 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata_value(z, nodata) {
                         output.set_value(row, col, attribute_data[record_num]);
                     } else {
                         output.set_value(row, col, z + attribute_data[record_num]);
@@ -286,6 +286,7 @@ impl WhiteboxTool for VectorPolygonsToRaster {
             for record_num in 0..vector_data.num_records {
                 key = match vector_data.attributes.get_value(record_num, &field_name) {
                     FieldData::Int(val) => val.to_string(),
+                    FieldData::Int64(val) => val.to_string(),
                     FieldData::Real(val) => val.to_string(),
                     FieldData::Text(val) => val.to_string(),
                     FieldData::Date(val) => val.to_string(),
@@ -339,7 +340,7 @@ impl WhiteboxTool for VectorPolygonsToRaster {
             Raster::initialize_using_config(&output_file, &configs)
         };
 
-        if background_val != nodata {
+        if !is_nodata_value(background_val, nodata) {
             output.reinitialize_values(background_val);
         }
 
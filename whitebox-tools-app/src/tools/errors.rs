@@ -0,0 +1,148 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use std::any::Any;
+use std::error;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+
+/// The category of a failed tool run. Each category has its own process exit code, so
+/// that scripts can respond to failures without parsing the error messages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToolErrorKind {
+    /// The tool failed for another reason, e.g. a panic within the tool.
+    ToolFailure,
+    /// The tool name or one of its arguments is missing or invalid.
+    BadArgument,
+    /// A file could not be found, read, or written.
+    Io,
+    /// An input file is of an unsupported format, or is malformed.
+    UnsupportedFormat,
+    /// The tool is part of an extension product and there is no valid license.
+    License,
+}
+
+impl ToolErrorKind {
+    /// Returns the exit code of the whitebox_tools process for errors of this kind.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ToolErrorKind::ToolFailure => 1,
+            ToolErrorKind::BadArgument => 2,
+            ToolErrorKind::Io => 3,
+            ToolErrorKind::UnsupportedFormat => 4,
+            ToolErrorKind::License => 5,
+        }
+    }
+
+    /// Returns the kind of error indicated by an exit code of the whitebox_tools process, or
+    /// of a plugin, if it is one of the codes returned by `exit_code`.
+    pub fn from_exit_code(code: i32) -> Option<ToolErrorKind> {
+        match code {
+            1 => Some(ToolErrorKind::ToolFailure),
+            2 => Some(ToolErrorKind::BadArgument),
+            3 => Some(ToolErrorKind::Io),
+            4 => Some(ToolErrorKind::UnsupportedFormat),
+            5 => Some(ToolErrorKind::License),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the category, as used in JSON output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolErrorKind::ToolFailure => "tool_failure",
+            ToolErrorKind::BadArgument => "bad_argument",
+            ToolErrorKind::Io => "io",
+            ToolErrorKind::UnsupportedFormat => "unsupported_format",
+            ToolErrorKind::License => "license",
+        }
+    }
+
+    /// Classifies an error returned by a tool. Errors created with `ToolError::new_error`
+    /// carry their kind; other errors are classified by their `ErrorKind`, and any that do
+    /// not identify a category are tool failures.
+    pub fn of(err: &Error) -> ToolErrorKind {
+        if let Some(tool_error) = err.get_ref().and_then(|e| e.downcast_ref::<ToolError>()) {
+            return tool_error.kind;
+        }
+        match err.kind() {
+            ErrorKind::InvalidInput => ToolErrorKind::BadArgument,
+            ErrorKind::InvalidData | ErrorKind::Unsupported => ToolErrorKind::UnsupportedFormat,
+            ErrorKind::NotFound
+            | ErrorKind::PermissionDenied
+            | ErrorKind::AlreadyExists
+            | ErrorKind::UnexpectedEof
+            | ErrorKind::WriteZero
+            | ErrorKind::BrokenPipe => ToolErrorKind::Io,
+            _ => ToolErrorKind::ToolFailure,
+        }
+    }
+
+    /// Classifies a panic by its payload. A tool can give the kind of an error that it
+    /// reports by panicking with a `ToolError`, using `std::panic::panic_any`; any other
+    /// panic is a tool failure.
+    pub fn of_panic(payload: &(dyn Any + Send)) -> ToolErrorKind {
+        match payload.downcast_ref::<ToolError>() {
+            Some(tool_error) => tool_error.kind,
+            None => ToolErrorKind::ToolFailure,
+        }
+    }
+}
+
+impl fmt::Display for ToolErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// An error with a `ToolErrorKind`. It is carried within a `std::io::Error`, which is the
+/// error type returned by tools, and its kind is recovered using `ToolErrorKind::of`.
+#[derive(Debug)]
+pub struct ToolError {
+    pub kind: ToolErrorKind,
+    pub message: String,
+}
+
+impl ToolError {
+    /// Creates an `std::io::Error` holding a `ToolError`. The `ErrorKind` of the returned
+    /// error corresponds to `kind`, for callers that do not look for the `ToolError`.
+    pub fn new_error<S: Into<String>>(kind: ToolErrorKind, message: S) -> Error {
+        let error_kind = match kind {
+            ToolErrorKind::BadArgument => ErrorKind::InvalidInput,
+            ToolErrorKind::UnsupportedFormat => ErrorKind::InvalidData,
+            ToolErrorKind::License => ErrorKind::PermissionDenied,
+            ToolErrorKind::Io | ToolErrorKind::ToolFailure => ErrorKind::Other,
+        };
+        Error::new(
+            error_kind,
+            ToolError {
+                kind,
+                message: message.into(),
+            },
+        )
+    }
+}
+
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl error::Error for ToolError {}
+
+/// Returns the message of a panic, whether its payload is a string or a `ToolError`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(tool_error) = payload.downcast_ref::<ToolError>() {
+        return tool_error.message.clone();
+    }
+    match payload.downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => payload.downcast_ref::<String>().cloned().unwrap_or_default(),
+    }
+}
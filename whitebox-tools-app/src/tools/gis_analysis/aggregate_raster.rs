@@ -289,7 +289,7 @@ impl WhiteboxTool for AggregateRaster {
                                 for r in row_in..row_in + agg_factor {
                                     for c in col_in..col_in + agg_factor {
                                         z = input.get_value(r, c);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             stat += z;
                                             count += 1f64;
                                         }
@@ -337,7 +337,7 @@ impl WhiteboxTool for AggregateRaster {
                                 for r in row_in..row_in + agg_factor {
                                     for c in col_in..col_in + agg_factor {
                                         z = input.get_value(r, c);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             stat += z;
                                             count += 1f64;
                                         }
@@ -384,7 +384,7 @@ impl WhiteboxTool for AggregateRaster {
                                 for r in row_in..row_in + agg_factor {
                                     for c in col_in..col_in + agg_factor {
                                         z = input.get_value(r, c);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             if z > stat {
                                                 stat = z;
                                             }
@@ -433,7 +433,7 @@ impl WhiteboxTool for AggregateRaster {
                                 for r in row_in..row_in + agg_factor {
                                     for c in col_in..col_in + agg_factor {
                                         z = input.get_value(r, c);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             if z < stat {
                                                 stat = z;
                                             }
@@ -484,7 +484,7 @@ impl WhiteboxTool for AggregateRaster {
                                 for r in row_in..row_in + agg_factor {
                                     for c in col_in..col_in + agg_factor {
                                         z = input.get_value(r, c);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             if z > max_val {
                                                 max_val = z;
                                             }
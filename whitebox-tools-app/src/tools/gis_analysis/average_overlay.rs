@@ -232,13 +232,13 @@ impl WhiteboxTool for AverageOverlay {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != in_nodata {
-                            if output[(row, col)] != out_nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, in_nodata) {
+                            if !is_nodata_value(output.get_value(row, col), out_nodata) {
                                 output.increment(row, col, z);
                                 n.increment(row, col, 1i16);
                             } else {
-                                output[(row, col)] = z;
+                                output.set_value(row, col, z);
                                 n[(row, col)] = 1i16;
                             }
                         }
@@ -257,12 +257,12 @@ impl WhiteboxTool for AverageOverlay {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = output[(row, col)];
-                if z != out_nodata {
+                z = output.get_value(row, col);
+                if !is_nodata_value(z, out_nodata) {
                     if n[(row, col)] > 0i16 {
-                        output[(row, col)] = z / n[(row, col)] as f64;
+                        output.set_value(row, col, z / n[(row, col)] as f64);
                     } else {
-                        output[(row, col)] = 0.0f64;
+                        output.set_value(row, col, 0.0f64);
                     }
                 }
             }
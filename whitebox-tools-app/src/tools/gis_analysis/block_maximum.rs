@@ -348,7 +348,7 @@ impl WhiteboxTool for BlockMaximumGridding {
                 col = (((columns - 1) as f64 * (x - west) / ew_range).floor()) as isize;
                 row = (((rows - 1) as f64 * (north - y) / ns_range).floor()) as isize;
                 z_current = output.get_value(row, col);
-                if z_current == nodata || z > z_current {
+                if is_nodata_value(z_current, nodata) || z > z_current {
                     output.set_value(row, col, z);
                 }
 
@@ -384,7 +384,7 @@ impl WhiteboxTool for BlockMaximumGridding {
                     row = (((rows - 1) as f64 * (north - y) / ns_range).floor()) as isize;
 
                     z_current = output.get_value(row, col);
-                    if z_current == nodata || z > z_current {
+                    if is_nodata_value(z_current, nodata) || z > z_current {
                         output.set_value(row, col, z);
                     }
                 }
@@ -345,7 +345,7 @@ impl WhiteboxTool for BlockMinimumGridding {
                 row = (((rows - 1) as f64 * (north - y) / ns_range).floor()) as isize;
 
                 z_current = output.get_value(row, col);
-                if z_current == nodata || z < z_current {
+                if is_nodata_value(z_current, nodata) || z < z_current {
                     output.set_value(row, col, z);
                 }
 
@@ -381,7 +381,7 @@ impl WhiteboxTool for BlockMinimumGridding {
                     row = (((rows - 1) as f64 * (north - y) / ns_range).floor()) as isize;
 
                     z_current = output.get_value(row, col);
-                    if z_current == nodata || z < z_current {
+                    if is_nodata_value(z_current, nodata) || z < z_current {
                         output.set_value(row, col, z);
                     }
                 }
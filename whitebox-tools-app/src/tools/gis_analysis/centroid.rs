@@ -215,8 +215,8 @@ impl WhiteboxTool for Centroid {
         let mut a: usize;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z > 0f64 && z != nodata {
+                z = input.get_value(row, col);
+                if z > 0f64 && !is_nodata_value(z, nodata) {
                     a = (z - min_val as f64) as usize;
                     total_columns[a] += col as usize;
                     total_rows[a] += row as usize;
@@ -252,11 +252,14 @@ impl WhiteboxTool for Clump {
         let mut count: usize; // this is just used to update the progress after every 1000 cells solved.
         for row in 0..rows {
             for col in 0..columns {
-                zin = input[(row, col)];
-                zout = output[(row, col)];
-                if zin != nodata && zin != back_val && zout == out_nodata {
+                zin = input.get_value(row, col);
+                zout = output.get_value(row, col);
+                if !is_nodata_value(zin, nodata)
+                    && zin != back_val
+                    && is_nodata_value(zout, out_nodata)
+                {
                     fid += 1f64;
-                    output[(row, col)] = fid;
+                    output.set_value(row, col, fid);
                     num_solved_cells += 1;
                     stack.push((row, col));
                     count = 0;
@@ -278,20 +281,20 @@ impl WhiteboxTool for Clump {
                             }
                         }
                         for i in 0..num_neighbours {
-                            zn = input[(r + dy[i], c + dx[i])];
-                            zout = output[(r + dy[i], c + dx[i])];
-                            if zn == zin && zout == out_nodata {
-                                output[(r + dy[i], c + dx[i])] = fid;
+                            zn = input.get_value(r + dy[i], c + dx[i]);
+                            zout = output.get_value(r + dy[i], c + dx[i]);
+                            if zn == zin && is_nodata_value(zout, out_nodata) {
+                                output.set_value(r + dy[i], c + dx[i], fid);
                                 num_solved_cells += 1;
                                 stack.push((r + dy[i], c + dx[i]));
                             }
                         }
                     }
-                } else if zin == nodata {
+                } else if is_nodata_value(zin, nodata) {
                     num_solved_cells += 1;
                 } else if zin == back_val {
                     num_solved_cells += 1;
-                    output[(row, col)] = back_val;
+                    output.set_value(row, col, back_val);
                 }
             }
             if verbose {
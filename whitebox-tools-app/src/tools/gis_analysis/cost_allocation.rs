@@ -252,19 +252,19 @@ impl WhiteboxTool for CostAllocation {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                z = pntr[(row, col)];
-                if z != pntr_nodata {
+                z = pntr.get_value(row, col);
+                if !is_nodata_value(z, pntr_nodata) {
                     if z > 0.0 {
                         flow_dir[(row, col)] = pntr_matches[z as usize];
                     } else {
                         flow_dir[(row, col)] = -1i8;
                     }
                 } else {
-                    output[(row, col)] = nodata;
+                    output.set_value(row, col, nodata);
                 }
-                z = pourpts[(row, col)];
-                if z != nodata && z > 0.0 {
-                    output[(row, col)] = z;
+                z = pourpts.get_value(row, col);
+                if !is_nodata_value(z, nodata) && z > 0.0 {
+                    output.set_value(row, col, z);
                 }
             }
             if verbose {
@@ -282,7 +282,7 @@ impl WhiteboxTool for CostAllocation {
         let mut outlet_id: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if output[(row, col)] == low_value {
+                if output.get_value(row, col) == low_value {
                     // && flow_dir[(row, col)] != -2i8 {
                     flag = false;
                     x = col;
@@ -297,7 +297,7 @@ impl WhiteboxTool for CostAllocation {
                             y += d_y[dir as usize];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            z = output[(y, x)];
+                            z = output.get_value(y, x);
                             if z != low_value {
                                 outlet_id = z;
                                 flag = true;
@@ -310,7 +310,7 @@ impl WhiteboxTool for CostAllocation {
                     flag = false;
                     x = col;
                     y = row;
-                    output[(y, x)] = outlet_id;
+                    output.set_value(y, x, outlet_id);
                     while !flag {
                         // find its downslope neighbour
                         dir = flow_dir[(y, x)];
@@ -320,13 +320,13 @@ impl WhiteboxTool for CostAllocation {
                             y += d_y[dir as usize];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            if output[(y, x)] != low_value {
+                            if output.get_value(y, x) != low_value {
                                 flag = true;
                             }
                         } else {
                             flag = true;
                         }
-                        output[(y, x)] = outlet_id;
+                        output.set_value(y, x, outlet_id);
                     }
                 }
             }
@@ -276,7 +276,9 @@ impl WhiteboxTool for CostDistance {
         let mut solved_cells = 0;
         for row in 0..rows {
             for col in 0..columns {
-                if source.get_value(row, col) > 0.0 && cost.get_value(row, col) != nodata {
+                if source.get_value(row, col) > 0.0
+                    && !is_nodata_value(cost.get_value(row, col), nodata)
+                {
                     output.set_value(row, col, 0.0);
                     backlink.set_value(row, col, 0.0);
                     minheap.push(GridCell {
@@ -285,7 +287,7 @@ impl WhiteboxTool for CostDistance {
                         priority: 0f64,
                     });
                     solved_cells += 1;
-                } else if cost.get_value(row, col) == nodata {
+                } else if is_nodata_value(cost.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                     solved_cells += 1;
                 }
@@ -333,7 +335,7 @@ impl WhiteboxTool for CostDistance {
                 for n in 0..8 {
                     col_n = col + dx[n];
                     row_n = row + dy[n];
-                    if output.get_value(row_n, col_n) != nodata {
+                    if !is_nodata_value(output.get_value(row_n, col_n), nodata) {
                         cost2 = cost.get_value(row_n, col_n);
                         new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
                         if new_cost < output.get_value(row_n, col_n) {
@@ -390,7 +392,7 @@ impl WhiteboxTool for CostDistance {
             for row in 0..rows {
                 for col in 0..columns {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -423,7 +425,7 @@ impl WhiteboxTool for CostDistance {
             for row in (0..rows).rev() {
                 for col in (0..columns).rev() {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -456,7 +458,7 @@ impl WhiteboxTool for CostDistance {
             for row in 0..rows {
                 for col in (0..columns).rev() {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -489,7 +491,7 @@ impl WhiteboxTool for CostDistance {
             for row in (0..rows).rev() {
                 for col in 0..columns {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -524,7 +526,7 @@ impl WhiteboxTool for CostDistance {
             for col in 0..columns {
                 for row in 0..rows {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -557,7 +559,7 @@ impl WhiteboxTool for CostDistance {
             for col in (0..columns).rev() {
                 for row in (0..rows).rev() {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -590,7 +592,7 @@ impl WhiteboxTool for CostDistance {
             for col in (0..columns).rev() {
                 for row in 0..rows {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -623,7 +625,7 @@ impl WhiteboxTool for CostDistance {
             for col in 0..columns {
                 for row in (0..rows).rev() {
                     accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
+                    if accum_val < background_val && !is_nodata_value(accum_val, nodata) {
                         cost1 = cost[(row, col)];
                         for n in 0..8 {
                             col_n = col + dx[n];
@@ -266,19 +266,21 @@ impl WhiteboxTool for CostPathway {
         let mut dir: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if destination[(row, col)] > 0.0 && backlink[(row, col)] != nodata {
+                if destination.get_value(row, col) > 0.0
+                    && !is_nodata_value(backlink.get_value(row, col), nodata)
+                {
                     flag = false;
                     x = col;
                     y = row;
                     while !flag {
-                        if output[(y, x)] == background_val {
-                            output[(y, x)] = 1.0;
+                        if output.get_value(y, x) == background_val {
+                            output.set_value(y, x, 1.0);
                         } else {
                             output.increment(y, x, 1.0);
                         }
                         // find its downslope neighbour
-                        dir = backlink[(y, x)];
-                        if dir != nodata && dir > 0.0 {
+                        dir = backlink.get_value(y, x);
+                        if !is_nodata_value(dir, nodata) && dir > 0.0 {
                             // move x and y accordingly
                             x += dx[pntr_matches[dir as usize]];
                             y += dy[pntr_matches[dir as usize]];
@@ -286,8 +288,8 @@ impl WhiteboxTool for CostPathway {
                             flag = true;
                         }
                     }
-                } else if backlink[(row, col)] == nodata {
-                    output[(row, col)] = nodata;
+                } else if is_nodata_value(backlink.get_value(row, col), nodata) {
+                    output.set_value(row, col, nodata);
                 }
             }
             if verbose {
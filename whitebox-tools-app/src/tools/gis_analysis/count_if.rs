@@ -252,9 +252,9 @@ impl WhiteboxTool for CountIf {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z == comparison_value && z != in_nodata {
-                            if output.get_value(row, col) != out_nodata {
+                        z = input.get_value(row, col);
+                        if z == comparison_value && !is_nodata_value(z, in_nodata) {
+                            if !is_nodata_value(output.get_value(row, col), out_nodata) {
                                 output.increment(row, col, 1f64);
                             } else {
                                 output.set_value(row, col, 1f64);
@@ -247,10 +247,7 @@ impl WhiteboxTool for CreateHexagonalVectorGrid {
         }
 
         // Get the spatial extent
-        let (extent, proj_info) = if input_file.to_lowercase().ends_with(".shp") {
-            // Note that this only works because at the moment, Shapefiles are the only supported vector.
-            // If additional vector formats are added in the future, this will need updating.
-
+        let (extent, proj_info) = if get_vector_format_from_file(&input_file) != VectorFormat::Unknown {
             let input = Shapefile::read(&input_file)?;
             (
                 BoundingBox::new(
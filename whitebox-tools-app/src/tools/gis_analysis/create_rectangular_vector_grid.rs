@@ -287,7 +287,7 @@ impl WhiteboxTool for CreateRectangularVectorGrid {
         }
 
         // Get the spatial extent
-        let (extent, proj_info) = if input_file.to_lowercase().ends_with(".shp") {
+        let (extent, proj_info) = if get_vector_format_from_file(&input_file) != VectorFormat::Unknown {
             let input = Shapefile::read(&input_file)?;
             (
                 BoundingBox::new(
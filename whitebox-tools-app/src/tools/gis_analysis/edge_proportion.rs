@@ -231,13 +231,13 @@ impl WhiteboxTool for EdgeProportion {
                 let mut bin: usize;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z > 0f64 && z != nodata {
+                        z = input.get_value(row, col);
+                        if z > 0f64 && !is_nodata_value(z, nodata) {
                             bin = z.floor() as usize;
                             num_cells[bin] += 1;
                             is_edge = false;
                             for n in 0..8 {
-                                zn = input[(row + dy[n], col + dx[n])];
+                                zn = input.get_value(row + dy[n], col + dx[n]);
                                 if zn != z {
                                     is_edge = true;
                                     break;
@@ -289,8 +289,8 @@ impl WhiteboxTool for EdgeProportion {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z > 0f64 && z != nodata {
+                        z = input.get_value(row, col);
+                        if z > 0f64 && !is_nodata_value(z, nodata) {
                             bin = z.floor() as usize;
                             data[col as usize] = edge_props[bin];
                         }
@@ -340,7 +340,7 @@ impl WhiteboxTool for EdgeProportion {
         if output_text {
             println!("Edge Proportion\nPatch ID\tValue");
             for bin in 0..max_val + 1 {
-                if edge_props[bin] > 0f64 && edge_props[bin] != nodata {
+                if edge_props[bin] > 0f64 && !is_nodata_value(edge_props[bin], nodata) {
                     println!("{}\t{}", bin, edge_props[bin]);
                 }
             }
@@ -210,12 +210,12 @@ impl WhiteboxTool for EuclideanAllocation {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata_value(z, nodata) {
                     distance[(row, col)] = 0.0;
-                    allocation[(row, col)] = input[(row, col)];
+                    allocation.set_value(row, col, input.get_value(row, col));
                 } else {
                     distance[(row, col)] = inf_val;
-                    allocation[(row, col)] = inf_val;
+                    allocation.set_value(row, col, inf_val);
                 }
             }
             if verbose {
@@ -237,7 +237,7 @@ impl WhiteboxTool for EuclideanAllocation {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata_value(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -257,7 +257,7 @@ impl WhiteboxTool for EuclideanAllocation {
                         y = row + d_y[which_cell];
                         r_x[(row, col)] = r_x[(y, x)] + g_x[which_cell];
                         r_y[(row, col)] = r_y[(y, x)] + g_y[which_cell];
-                        allocation[(row, col)] = allocation[(y, x)];
+                        allocation.set_value(row, col, allocation.get_value(y, x));
                     }
                 }
             }
@@ -280,7 +280,7 @@ impl WhiteboxTool for EuclideanAllocation {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata_value(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -300,7 +300,7 @@ impl WhiteboxTool for EuclideanAllocation {
                         y = row + d_y[which_cell];
                         r_x[(row, col)] = r_x[(y, x)] + g_x[which_cell];
                         r_y[(row, col)] = r_y[(y, x)] + g_y[which_cell];
-                        allocation[(row, col)] = allocation[(y, x)];
+                        allocation.set_value(row, col, allocation.get_value(y, x));
                     }
                 }
             }
@@ -316,8 +316,8 @@ impl WhiteboxTool for EuclideanAllocation {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
-                    allocation[(row, col)] = nodata;
+                if z != 0.0 && !is_nodata_value(z, nodata) {
+                    allocation.set_value(row, col, nodata);
                 }
             }
             if verbose {
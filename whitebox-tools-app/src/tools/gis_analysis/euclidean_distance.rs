@@ -221,7 +221,7 @@ impl WhiteboxTool for EuclideanDistance {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata_value(z, nodata) {
                     output.set_value(row, col, 0.0);
                 } else {
                     output.set_value(row, col, inf_val);
@@ -246,7 +246,7 @@ impl WhiteboxTool for EuclideanDistance {
                         x = col + dx[i];
                         y = row + dy[i];
                         z2 = output.get_value(y, x);
-                        if z2 != nodata {
+                        if !is_nodata_value(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * rx.get_value(y, x) + 1.0,
                                 1 => 2.0 * (rx.get_value(y, x) + ry.get_value(y, x) + 1.0),
@@ -288,7 +288,7 @@ impl WhiteboxTool for EuclideanDistance {
                         x = col + dx[i];
                         y = row + dy[i];
                         z2 = output.get_value(y, x);
-                        if z2 != nodata {
+                        if !is_nodata_value(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (rx.get_value(y, x) + ry.get_value(y, x) + 1.0),
                                 4 => 2.0 * rx.get_value(y, x) + 1.0,
@@ -303,7 +303,7 @@ impl WhiteboxTool for EuclideanDistance {
                         }
                     }
                     if z_min < z {
-                        output[(row, col)] = z_min;
+                        output.set_value(row, col, z_min);
                         x = col + dx[which_cell];
                         y = row + dy[which_cell];
                         rx.set_value(row, col, rx.get_value(y, x) + gx[which_cell]);
@@ -323,7 +323,7 @@ impl WhiteboxTool for EuclideanDistance {
         let cell_size = (input.configs.resolution_x + input.configs.resolution_y) / 2.0;
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata_value(input.get_value(row, col), nodata) {
                     output.set_value(row, col, output.get_value(row, col).sqrt() * cell_size);
                 } else {
                     output.set_value(row, col, nodata);
@@ -190,6 +190,10 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
 
         let sep: String = path::MAIN_SEPARATOR.to_string();
 
+        if !points_file.contains(&sep) && !points_file.contains("/") {
+            points_file = format!("{}{}", working_directory, points_file);
+        }
+
         let start = Instant::now();
 
         let mut cmd = input_files.split(";");
@@ -246,7 +246,7 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
         for row in 0..rows {
             for col in 0..columns {
                 value = input.get_value(row, col);
-                if value != nodata {
+                if !is_nodata_value(value, nodata) {
                     bin = (value - min_val) as usize;
                     histo[bin] += 1;
                 }
@@ -268,7 +268,7 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
                 .par_iter()
                 .map(|value| {
                     let mut ret_value = nodata;
-                    if *value != nodata {
+                    if !is_nodata_value(*value, nodata) {
                         let bin = (value - min_val) as usize;
                         if histo[bin] >= threshold {
                             ret_value = *value;
@@ -261,7 +261,7 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             if z < low_z {
                                 low_z = z;
                                 low_col = col;
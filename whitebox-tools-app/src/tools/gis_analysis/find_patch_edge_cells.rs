@@ -210,7 +210,7 @@ impl WhiteboxTool for FindPatchOrClassEdgeCells {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata_value(z, nodata) {
                             zout = 0f64;
                             for n in 0..8 {
                                 zn = input.get_value(row + dy[n], col + dx[n]);
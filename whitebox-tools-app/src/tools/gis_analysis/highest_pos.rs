@@ -228,11 +228,11 @@ impl WhiteboxTool for HighestPosition {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != in_nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, in_nodata) {
                             if z > high_val[(row, col)] {
                                 high_val[(row, col)] = z;
-                                output[(row, col)] = i as f64;
+                                output.set_value(row, col, i as f64);
                             }
                         }
                     }
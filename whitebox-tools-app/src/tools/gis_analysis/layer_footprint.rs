@@ -199,7 +199,7 @@ impl WhiteboxTool for LayerFootprint {
         }
 
         // is it a vector or a raster file?
-        if input_file.to_lowercase().ends_with(".shp") {
+        if get_vector_format_from_file(&input_file) != VectorFormat::Unknown {
             // The input file is a vector
             let input = Shapefile::read(&input_file)?;
 
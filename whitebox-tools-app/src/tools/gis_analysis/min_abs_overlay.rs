@@ -225,15 +225,15 @@ impl WhiteboxTool for MinAbsoluteOverlay {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        in_val = input[(row, col)];
-                        if in_val != in_nodata {
-                            out_val = output[(row, col)];
-                            if out_val != out_nodata {
+                        in_val = input.get_value(row, col);
+                        if !is_nodata_value(in_val, in_nodata) {
+                            out_val = output.get_value(row, col);
+                            if !is_nodata_value(out_val, out_nodata) {
                                 if in_val.abs() < out_val {
-                                    output[(row, col)] = in_val.abs();
+                                    output.set_value(row, col, in_val.abs());
                                 }
                             } else {
-                                output[(row, col)] = in_val.abs();
+                                output.set_value(row, col, in_val.abs());
                             }
                         }
                     }
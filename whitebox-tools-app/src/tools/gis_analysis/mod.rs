@@ -5,7 +5,7 @@ mod block_maximum;
 mod block_minimum;
 mod boundary_shape_complexity;
 mod buffer_raster;
-// mod buffer_vector;
+mod buffer_vector;
 mod centroid;
 mod centroid_vector;
 mod clip;
@@ -57,6 +57,7 @@ mod minimum_convex_hull;
 mod multiply_overlay;
 mod narrowness_index;
 mod natural_neighbour_interpolation;
+mod near_feature;
 mod nearest_neighbour_gridding;
 mod patch_orientation;
 mod percent_equal_to;
@@ -78,9 +79,12 @@ mod reclass;
 mod reclass_equal_interval;
 mod reclass_from_file;
 mod related_circumscribing_circle;
+mod select_by_attribute;
+mod select_by_location;
 mod shape_complexity_index;
 mod shape_complexity_raster;
 mod smooth_vectors;
+mod spatial_join;
 mod split_with_lines;
 mod sum_overlay;
 mod symmetrical_difference;
@@ -99,7 +103,7 @@ pub use self::block_maximum::BlockMaximumGridding;
 pub use self::block_minimum::BlockMinimumGridding;
 pub use self::boundary_shape_complexity::BoundaryShapeComplexity;
 pub use self::buffer_raster::BufferRaster;
-// pub use self::buffer_vector::BufferVector;
+pub use self::buffer_vector::BufferVector;
 pub use self::centroid::Centroid;
 pub use self::centroid_vector::CentroidVector;
 pub use self::clip::Clip;
@@ -151,6 +155,7 @@ pub use self::minimum_convex_hull::MinimumConvexHull;
 pub use self::multiply_overlay::MultiplyOverlay;
 pub use self::narrowness_index::NarrownessIndex;
 pub use self::natural_neighbour_interpolation::NaturalNeighbourInterpolation;
+pub use self::near_feature::NearFeature;
 pub use self::nearest_neighbour_gridding::NearestNeighbourGridding;
 pub use self::patch_orientation::PatchOrientation;
 pub use self::percent_equal_to::PercentEqualTo;
@@ -172,9 +177,12 @@ pub use self::reclass::Reclass;
 pub use self::reclass_equal_interval::ReclassEqualInterval;
 pub use self::reclass_from_file::ReclassFromFile;
 pub use self::related_circumscribing_circle::RelatedCircumscribingCircle;
+pub use self::select_by_attribute::SelectByAttribute;
+pub use self::select_by_location::SelectByLocation;
 pub use self::shape_complexity_index::ShapeComplexityIndex;
 pub use self::shape_complexity_raster::ShapeComplexityIndexRaster;
 pub use self::smooth_vectors::SmoothVectors;
+pub use self::spatial_join::SpatialJoin;
 pub use self::split_with_lines::SplitWithLines;
 pub use self::sum_overlay::SumOverlay;
 pub use self::symmetrical_difference::SymmetricalDifference;
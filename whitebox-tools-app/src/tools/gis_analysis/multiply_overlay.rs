@@ -229,9 +229,9 @@ impl WhiteboxTool for MultiplyOverlay {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != in_nodata {
+                        if !is_nodata_value(z, in_nodata) {
                             existing_value = output.get_value(row, col);
-                            if existing_value != out_nodata {
+                            if !is_nodata_value(existing_value, out_nodata) {
                                 new_value = existing_value * z;
                                 output.set_value(row, col, new_value);
                             } else {
@@ -238,9 +238,9 @@ impl WhiteboxTool for NarrownessIndex {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z == 0.0 || z == nodata {
-                    output[(row, col)] = 0.0;
+                z = input.get_value(row, col);
+                if z == 0.0 || is_nodata_value(z, nodata) {
+                    output.set_value(row, col, 0.0);
                 } else {
                     bin = (z - min_val).floor() as usize;
                     area_data[bin] += 1;
@@ -248,16 +248,16 @@ impl WhiteboxTool for NarrownessIndex {
                     // is it an edge cell?
                     is_edge = false;
                     for a in 0..8 {
-                        z2 = input[(row + d_y[a], col + d_x[a])];
+                        z2 = input.get_value(row + d_y[a], col + d_x[a]);
                         if z2 != z {
                             is_edge = true;
                             break;
                         }
                     }
                     if !is_edge {
-                        output[(row, col)] = inf_val;
+                        output.set_value(row, col, inf_val);
                     } else {
-                        output[(row, col)] = cell_size;
+                        output.set_value(row, col, cell_size);
                         max_width[bin] = cell_size;
                     }
                 }
@@ -273,15 +273,15 @@ impl WhiteboxTool for NarrownessIndex {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = output[(row, col)];
+                z = output.get_value(row, col);
                 if z != 0.0 {
                     z_min = inf_val;
                     which_cell = 0;
                     for i in 0..4 {
                         x = col + d_x[i];
                         y = row + d_y[i];
-                        z2 = output[(y, x)];
-                        if z2 != out_nodata {
+                        z2 = output.get_value(y, x);
+                        if !is_nodata_value(z2, out_nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -296,7 +296,7 @@ impl WhiteboxTool for NarrownessIndex {
                         }
                     }
                     if z_min < z {
-                        output[(row, col)] = z_min;
+                        output.set_value(row, col, z_min);
                         x = col + d_x[which_cell];
                         y = row + d_y[which_cell];
                         r_x[(row, col)] = r_x[(y, x)] + g_x[which_cell];
@@ -315,15 +315,15 @@ impl WhiteboxTool for NarrownessIndex {
 
         for row in (0..rows).rev() {
             for col in (0..columns).rev() {
-                z = output[(row, col)];
+                z = output.get_value(row, col);
                 if z != 0.0 {
                     z_min = inf_val;
                     which_cell = 0;
                     for i in 4..8 {
                         x = col + d_x[i];
                         y = row + d_y[i];
-                        z2 = output[(y, x)];
-                        if z2 != out_nodata {
+                        z2 = output.get_value(y, x);
+                        if !is_nodata_value(z2, out_nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -338,7 +338,7 @@ impl WhiteboxTool for NarrownessIndex {
                         }
                     }
                     if z_min < z {
-                        output[(row, col)] = z_min;
+                        output.set_value(row, col, z_min);
                         x = col + d_x[which_cell];
                         y = row + d_y[which_cell];
                         r_x[(row, col)] = r_x[(y, x)] + g_x[which_cell];
@@ -357,19 +357,19 @@ impl WhiteboxTool for NarrownessIndex {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     if z != 0f64 {
-                        output[(row, col)] = output[(row, col)].sqrt() * cell_size;
+                        output.set_value(row, col, output.get_value(row, col).sqrt() * cell_size);
                         bin = (z - min_val).floor() as usize;
-                        if output[(row, col)] > max_width[bin] {
-                            max_width[bin] = output[(row, col)];
+                        if output.get_value(row, col) > max_width[bin] {
+                            max_width[bin] = output.get_value(row, col);
                         }
                     } else {
-                        output[(row, col)] = 0f64;
+                        output.set_value(row, col, 0f64);
                     }
                 } else {
-                    output[(row, col)] = out_nodata;
+                    output.set_value(row, col, out_nodata);
                 }
             }
             if verbose {
@@ -389,10 +389,10 @@ impl WhiteboxTool for NarrownessIndex {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata && z != 0f64 {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) && z != 0f64 {
                     bin = (z - min_val).floor() as usize;
-                    output[(row, col)] = max_width[bin];
+                    output.set_value(row, col, max_width[bin]);
                 }
             }
             if verbose {
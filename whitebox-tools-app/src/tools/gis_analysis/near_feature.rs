@@ -0,0 +1,508 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::spatial_join::unique_field_name;
+use whitebox_common::algorithms::{map_features, FeatureIndex};
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use num_cpus;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// This tool finds the nearest feature in a near vector file (`--near`) to each of the features
+/// in an input vector file (`--input`), e.g. the nearest stream to each well, or the nearest
+/// road to each building. The input and near files may contain points, lines, or polygons, in
+/// any combination, and distances are measured between the nearest points of the features,
+/// rather than their centres, so that the distance is zero where they intersect. Distances are
+/// in the units of the input coordinates.
+///
+/// The output vector file (`--output`) contains a copy of each input feature, with the
+/// following fields added to its attributes:
+///
+/// | Field | Description |
+/// |-------|-------------|
+/// | NEAR_FID | The record number of the nearest feature in the near file (starting at 1) |
+/// | NEAR_DIST | The distance to the nearest feature |
+/// | NEAR_ANGLE | The direction of the nearest point of the nearest feature, in degrees clockwise from north |
+/// | NEAR_X, NEAR_Y | The coordinates of the nearest point of the nearest feature |
+///
+/// The direction is measured from the nearest point of the input feature, and is zero where
+/// the features intersect. Where several features are equally near, the nearest is the first
+/// of these in the near file. If the maximum search distance (`--max_dist`) is greater than
+/// zero, features further than this are ignored, and the fields are null for input features
+/// without a near feature within the distance. If the input and near files are the same, the
+/// nearest feature to each feature is the nearest of the others, rather than itself.
+///
+/// Optionally, the `--k` nearest features to each input feature may be written to a CSV table
+/// (`--table`). The table contains a row for each pair of features, with the record number of
+/// the input feature (`FID`), the rank of the near feature (`RANK`, starting at 1), the
+/// `NEAR_FID`, `NEAR_DIST`, and `NEAR_ANGLE` values described above, and the coordinates of the
+/// nearest points of the input (`FROM_X`, `FROM_Y`) and near (`NEAR_X`, `NEAR_Y`) features.
+///
+/// # See Also
+/// `SpatialJoin`, `SelectByLocation`, `EuclideanDistance`
+pub struct NearFeature {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+/// A near feature, with the nearest points of the input and near features.
+struct Near {
+    record_num: usize,
+    distance: f64,
+    from: Point2D,
+    to: Point2D,
+}
+
+impl Near {
+    /// The direction from the input feature to the near feature, in degrees clockwise from north.
+    fn angle(&self) -> f64 {
+        if self.distance == 0f64 {
+            return 0f64;
+        }
+        let angle = (self.to.x - self.from.x)
+            .atan2(self.to.y - self.from.y)
+            .to_degrees();
+        if angle < 0f64 {
+            angle + 360f64
+        } else {
+            angle
+        }
+    }
+}
+
+impl NearFeature {
+    pub fn new() -> NearFeature {
+        // public constructor
+        let name = "NearFeature".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Finds the nearest feature in one vector to each feature in another, with its distance and direction."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Near Vector File".to_owned(),
+            flags: vec!["--near".to_owned()],
+            description: "Input vector file containing the near features.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum distance to a near feature (zero for no limit).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output CSV Table (optional)".to_owned(),
+            flags: vec!["--table".to_owned()],
+            description: "Optional output CSV file of the k nearest features to each input feature.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Nearest Features (k)".to_owned(),
+            flags: vec!["--k".to_owned()],
+            description: "Number of nearest features to each input feature in the output table.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=wells.shp --near=streams.shp -o=out_file.shp --max_dist=5000.0 --table=nearest.csv --k=3",
+            short_exe, name
+        ).replace("*", &sep);
+
+        NearFeature {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for NearFeature {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut near_file = String::new();
+        let mut output_file = String::new();
+        let mut table_file = String::new();
+        let mut max_distance = 0f64;
+        let mut k = 1usize;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-near" {
+                near_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-max_dist" {
+                max_distance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-table" {
+                table_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-k" {
+                k = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<usize>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<usize>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !near_file.contains(&sep) && !near_file.contains("/") {
+            near_file = format!("{}{}", working_directory, near_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        let write_table = !table_file.trim().is_empty();
+        if write_table && !table_file.contains(&sep) && !table_file.contains("/") {
+            table_file = format!("{}{}", working_directory, table_file);
+        }
+
+        if !max_distance.is_finite() || max_distance < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum search distance must be zero or greater.",
+            ));
+        }
+        if k == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of nearest features (--k) must be at least 1.",
+            ));
+        }
+        // only the nearest feature is needed for the output attributes
+        let k = if write_table { k } else { 1 };
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Shapefile::read(&input_file)?);
+        let near = Shapefile::read(&near_file)?;
+        // a feature is not its own nearest feature. The files may be 'file.gpkg:layer' names.
+        let file_and_layer = |file_name: &str| {
+            let (file_name, layer) = split_layer_name(file_name);
+            let file_name = path::Path::new(&file_name)
+                .canonicalize()
+                .unwrap_or_else(|_| path::PathBuf::from(&file_name));
+            (file_name, layer)
+        };
+        let (input_path, input_layer) = file_and_layer(&input_file);
+        let (near_path, near_layer) = file_and_layer(&near_file);
+        // a GeoPackage layer need only be named if the file contains several layers
+        let same_file = input_path == near_path
+            && match (input_layer, near_layer) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(&b),
+                _ => true,
+            };
+
+        if verbose {
+            println!("Finding the nearest features...")
+        };
+
+        let near_index = FeatureIndex::new(
+            (0..near.num_records)
+                .map(|record_num| near.get_record(record_num).get_feature_geometry())
+                .collect(),
+        );
+        let max_distance = if max_distance > 0f64 {
+            max_distance
+        } else {
+            f64::INFINITY
+        };
+
+        let mut num_procs = num_cpus::get();
+        let configs = whitebox_common::configs::get_configs()?;
+        let max_procs = configs.max_procs as usize;
+        if max_procs > 0 && max_procs < num_procs {
+            num_procs = max_procs;
+        }
+        let num_records = input.num_records;
+        // the k nearest features to each input feature, in order of their distance from it
+        let all_nearest: Vec<Vec<Near>> = {
+            let input = input.clone();
+            map_features(
+                num_records,
+                num_procs,
+                move |record_num| {
+                    let target = input.get_record(record_num).get_feature_geometry();
+                    let exclude = if same_file { Some(record_num) } else { None };
+                    near_index
+                        .nearest(&target, k, max_distance, exclude)
+                        .into_iter()
+                        .map(|(record_num, distance, from, to)| Near {
+                            record_num,
+                            distance,
+                            from,
+                            to,
+                        })
+                        .collect()
+                },
+                |n| {
+                    if verbose {
+                        let progress = (100.0_f64 * n as f64 / num_records as f64) as usize;
+                        if progress != old_progress {
+                            println!("Progress: {}%", progress);
+                            old_progress = progress;
+                        }
+                    }
+                },
+            )
+        };
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        let new_fields = [
+            AttributeField::new("NEAR_FID", FieldDataType::Int, 7u8, 0u8),
+            AttributeField::new("NEAR_DIST", FieldDataType::Real, 12u8, 4u8),
+            AttributeField::new("NEAR_ANGLE", FieldDataType::Real, 8u8, 3u8),
+            AttributeField::new("NEAR_X", FieldDataType::Real, 16u8, 4u8),
+            AttributeField::new("NEAR_Y", FieldDataType::Real, 16u8, 4u8),
+        ];
+        for field in &new_fields {
+            let mut field = field.clone();
+            field.name = unique_field_name(&field.name, &output);
+            output.attributes.add_field(&field);
+        }
+
+        for (record_num, nearest) in all_nearest.iter().enumerate() {
+            let mut atts = input.attributes.get_record(record_num);
+            match nearest.first() {
+                Some(n) => {
+                    atts.push(FieldData::Int(n.record_num as i32 + 1));
+                    atts.push(FieldData::Real(n.distance));
+                    atts.push(FieldData::Real(n.angle()));
+                    atts.push(FieldData::Real(n.to.x));
+                    atts.push(FieldData::Real(n.to.y));
+                }
+                None => {
+                    // input features without a near feature have null near attributes
+                    atts.resize(output.attributes.get_num_fields(), FieldData::Null);
+                }
+            }
+            output.add_record(input.get_record(record_num).clone());
+            output.attributes.add_record(atts, false);
+        }
+
+        if write_table {
+            if verbose {
+                println!("Writing the table...")
+            };
+            let f = File::create(&table_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all(
+                "FID,RANK,NEAR_FID,NEAR_DIST,NEAR_ANGLE,FROM_X,FROM_Y,NEAR_X,NEAR_Y\n".as_bytes(),
+            )?;
+            for (record_num, nearest) in all_nearest.iter().enumerate() {
+                for (rank, n) in nearest.iter().enumerate() {
+                    let s = format!(
+                        "{},{},{},{},{},{},{},{},{}\n",
+                        record_num + 1,
+                        rank + 1,
+                        n.record_num + 1,
+                        n.distance,
+                        n.angle(),
+                        n.from.x,
+                        n.from.y,
+                        n.to.x,
+                        n.to.y
+                    );
+                    writer.write_all(s.as_bytes())?;
+                }
+            }
+            writer.flush()?;
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
@@ -241,11 +241,11 @@ impl WhiteboxTool for PercentLessThan {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != in_nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, in_nodata) {
                             n_images[(row, col)] += 1;
-                            if z < comparison[(row, col)] {
-                                output[(row, col)] += 1.0;
+                            if z < comparison.get_value(row, col) {
+                                output.set_value(row, col, output.get_value(row, col) + 1.0);
                             }
                         }
                     }
@@ -263,13 +263,16 @@ impl WhiteboxTool for PercentLessThan {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = comparison[(row, col)];
-                if z != nodata {
+                z = comparison.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     if n_images[(row, col)] > 0 {
-                        output[(row, col)] =
-                            100.0 * output[(row, col)] / n_images[(row, col)] as f64;
+                        output.set_value(
+                            row,
+                            col,
+                            100.0 * output.get_value(row, col) / n_images[(row, col)] as f64,
+                        );
                     } else {
-                        output[(row, col)] = 0f64;
+                        output.set_value(row, col, 0f64);
                     }
                 }
             }
@@ -241,10 +241,10 @@ impl WhiteboxTool for PickFromList {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        if position[(row, col)] == j {
-                            in_val = input[(row, col)];
-                            if in_val != in_nodata {
-                                output[(row, col)] = in_val;
+                        if position.get_value(row, col) == j {
+                            in_val = input.get_value(row, col);
+                            if !is_nodata_value(in_val, in_nodata) {
+                                output.set_value(row, col, in_val);
                             }
                         }
                     }
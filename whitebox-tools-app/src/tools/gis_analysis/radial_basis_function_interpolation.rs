@@ -435,6 +435,7 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
                     } else {
                         let val = match input.attributes.get_value(record_num, &field_name) {
                             FieldData::Int(val) => val as f64,
+                            FieldData::Int64(val) => val as f64,
                             FieldData::Real(val) => val,
                             FieldData::Null => continue,
                             _ => {
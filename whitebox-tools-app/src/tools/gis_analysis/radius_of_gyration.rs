@@ -232,7 +232,7 @@ impl WhiteboxTool for RadiusOfGyration {
                     let mut total_n = vec![0usize; range + 1];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata_value(z, nodata) {
                             a = (z - min_val as f64) as usize;
                             total_columns[a] += col as usize;
                             total_rows[a] += row as usize;
@@ -290,7 +290,7 @@ impl WhiteboxTool for RadiusOfGyration {
                     let mut gyradius = vec![0f64; range + 1];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata_value(z, nodata) {
                             a = (z - min_val as f64) as usize;
                             gyradius[a] = ((col as f64 - centroid_x[a]) * resolution_x)
                                 * ((col as f64 - centroid_x[a]) * resolution_x)
@@ -330,8 +330,8 @@ impl WhiteboxTool for RadiusOfGyration {
         let mut a: usize;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z > 0f64 && z != nodata {
+                z = input.get_value(row, col);
+                if z > 0f64 && !is_nodata_value(z, nodata) {
                     a = (z - min_val as f64) as usize;
                     output.set_value(row, col, gyradius[a]);
                 } else {
@@ -282,7 +282,7 @@ impl WhiteboxTool for RasterArea {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val != nodata && val != back_val && val >= min_val && val <= max_val
+                            if !is_nodata_value(val, nodata) && val != back_val && val >= min_val && val <= max_val
                             {
                                 bin = (val - min_val).floor() as usize;
                                 freq_data[bin] += 1;
@@ -321,7 +321,11 @@ impl WhiteboxTool for RasterArea {
                 for row in 0..rows {
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata && val != back_val && val >= min_val && val <= max_val {
+                        if !is_nodata_value(val, nodata)
+                            && val != back_val
+                            && val >= min_val
+                            && val <= max_val
+                        {
                             bin = (val - min_val).floor() as usize;
                             output.set_value(row, col, freq_data[bin] as f64);
                         }
@@ -399,7 +403,7 @@ impl WhiteboxTool for RasterArea {
                         }
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val != nodata && val != back_val && val >= min_val && val <= max_val
+                            if !is_nodata_value(val, nodata) && val != back_val && val >= min_val && val <= max_val
                             {
                                 bin = (val - min_val).floor() as usize;
                                 area_data[bin] += cell_area;
@@ -441,7 +445,11 @@ impl WhiteboxTool for RasterArea {
                 for row in 0..rows {
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata && val != back_val && val >= min_val && val <= max_val {
+                        if !is_nodata_value(val, nodata)
+                            && val != back_val
+                            && val >= min_val
+                            && val <= max_val
+                        {
                             bin = (val - min_val).floor() as usize;
                             output.set_value(row, col, area_data[bin]);
                         }
@@ -564,7 +564,11 @@ impl WhiteboxTool for RasterPerimeter {
                     res = (resx + resy) / 2f64;
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata && val != back_val && val >= min_val && val <= max_val {
+                        if !is_nodata_value(val, nodata)
+                            && val != back_val
+                            && val >= min_val
+                            && val <= max_val
+                        {
                             bin = (val - min_val).floor() as usize;
                             val2 = 0;
                             for n in 0..8 {
@@ -614,7 +618,11 @@ impl WhiteboxTool for RasterPerimeter {
             for row in 0..rows {
                 for col in 0..columns {
                     val = input.get_value(row, col);
-                    if val != nodata && val != back_val && val >= min_val && val <= max_val {
+                    if !is_nodata_value(val, nodata)
+                        && val != back_val
+                        && val >= min_val
+                        && val <= max_val
+                    {
                         bin = (val - min_val).floor() as usize;
                         output.set_value(row, col, data[bin] as f64);
                     }
@@ -290,8 +290,8 @@ impl WhiteboxTool for ReclassFromFile {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 // This is a shortcut intended to take advantage of the inherent
                                 // spatial autocorrelation in spatial distributions to speed up
                                 // the search for the appropriate range bin.
@@ -357,8 +357,8 @@ impl WhiteboxTool for ReclassFromFile {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 // is z in the hashmap?
                                 if assign_map.contains_key(&((z * multiplier).round() as i64)) {
                                     z = *assign_map
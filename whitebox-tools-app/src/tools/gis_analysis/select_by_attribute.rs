@@ -0,0 +1,327 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool copies the features of an input vector file (`--input`) whose attributes satisfy
+/// a logical expression (`--statement`) to an output vector file (`--output`). The expression
+/// is written in a SQL-like language, e.g. `CLASS = 2 AND NAME LIKE 'f%'`, and is parsed once,
+/// before any of the records are read, so that misspelled field names and syntax errors are
+/// reported immediately.
+///
+/// Field names are written as they are, or within double quotes if they contain spaces or
+/// are one of the keywords below (e.g. `"DATE"`). Text values are written within single
+/// quotes (with a quote in the text written as two quotes), and dates as `DATE 'YYYY-MM-DD'`.
+/// Text is also converted to a date where it is compared with a date field, e.g.
+/// `SURVEYED >= '2020-01-01'`. The expression may contain the following operators, from
+/// the lowest to the highest precedence:
+///
+/// | Operator | Description |
+/// |----------|-------------|
+/// | `OR`, `\|\|` | True if either side is true |
+/// | `AND`, `&&` | True if both sides are true |
+/// | `NOT`, `!` | True if the expression that follows is false |
+/// | `=`, `!=`, `<>`, `<`, `<=`, `>`, `>=` | Compare numbers, text, dates, or true/false values |
+/// | `IS NULL`, `IS NOT NULL` | True if a value is, or is not, missing |
+/// | `IN (a, b, ...)`, `NOT IN (...)` | True if a value is, or is not, one of a list |
+/// | `LIKE`, `NOT LIKE` | Match text with a pattern, in which `%` matches any text and `_` any single character |
+/// | `BETWEEN a AND b`, `NOT BETWEEN` | True if a value is, or is not, between two others, inclusive |
+/// | `+`, `-` | Add or subtract numbers, join text, or add or subtract days from dates |
+/// | `*`, `/`, `%` | Multiply, divide, or find the remainder of numbers |
+///
+/// The following functions are also available:
+///
+/// | Function | Description |
+/// |----------|-------------|
+/// | `upper(s)`, `lower(s)`, `trim(s)` | Text in upper or lower case, or without leading and trailing spaces |
+/// | `length(s)` | The number of characters in text |
+/// | `substr(s, start, length)` | Part of text, starting at character `start` (the first is 1), optionally of a given length |
+/// | `contains(s, t)`, `starts_with(s, t)`, `ends_with(s, t)` | True if text contains, starts with, or ends with other text |
+/// | `concat(a, b, ...)` | Joins its arguments as text, skipping null values |
+/// | `coalesce(a, b, ...)` | The first of its arguments that is not null |
+/// | `abs(x)`, `floor(x)`, `ceil(x)`, `round(x, digits)` | Numeric functions; `digits` is optional |
+/// | `date(s)`, `date(year, month, day)` | A date, from text or from its parts |
+/// | `year(d)`, `month(d)`, `day(d)` | A part of a date |
+/// | `today()` | The current date |
+///
+/// Missing (null) values follow the rules of SQL: a comparison, arithmetic operation, or
+/// function (other than `concat` and `coalesce`) involving a null value is also null, as is
+/// `NOT` of a null value, and features are only selected if the expression is true. For
+/// example, `VALUE > 10` and `NOT VALUE > 10` are both false for a feature with no `VALUE`;
+/// use `VALUE IS NULL` to select such features. Numeric division by zero is also null. An
+/// expression that compares values of different types, e.g. `NAME > 5`, is an error.
+///
+/// If none of the features are selected, a warning is issued and no output file is written.
+///
+/// The following are examples of valid statements:
+///
+/// ```text
+/// HEIGHT >= 300.0
+///
+/// CROP = 'corn' OR CROP IN ('wheat', 'barley')
+///
+/// (ELEV BETWEEN 500 AND 525) AND upper(trim(OWNER)) LIKE 'SMITH%'
+///
+/// SURVEYED IS NULL OR SURVEYED < DATE '2015-06-01'
+///
+/// "Land Use" IS NOT NULL AND year(SURVEYED) = 2020
+/// ```
+///
+/// # See Also
+/// `SelectByLocation`, `ExtractByAttribute`, `Clip`
+pub struct SelectByAttribute {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SelectByAttribute {
+    pub fn new() -> SelectByAttribute {
+        // public constructor
+        let name = "SelectByAttribute".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Extracts the features of a vector file whose attributes satisfy a logical expression."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statement".to_owned(),
+            flags: vec!["-s".to_owned(), "--statement".to_owned()],
+            description: "Logical expression, e.g. CLASS = 2 AND NAME LIKE 'f%'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.shp -o=out_file.shp --statement=\"ELEV > 500.0 AND LANDUSE IN ('forest', 'wetland')\"",
+            short_exe, name
+        ).replace("*", &sep);
+
+        SelectByAttribute {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SelectByAttribute {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut statement = String::new();
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-s" || flag_val == "-statement" {
+                // the statement may contain quotes and equals signs of its own
+                statement = match args[i].split_once("=") {
+                    Some((_, value)) => value.to_string(),
+                    None => args[i + 1].to_string(),
+                };
+            }
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if statement.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A statement (--statement) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let expression = AttributeExpression::parse(&statement, &input.attributes)?;
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        let mut num_selected = 0;
+        for record_num in 0..input.num_records {
+            let atts = input.attributes.get_record(record_num);
+            if expression.is_match(&atts)? {
+                output.add_record(input.get_record(record_num).clone());
+                output.attributes.add_record(atts, false);
+                num_selected += 1;
+            }
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Number of selected features: {}", num_selected);
+        }
+        if num_selected > 0 {
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        } else {
+            println!("WARNING: No features were selected and therefore no output file will be written.");
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
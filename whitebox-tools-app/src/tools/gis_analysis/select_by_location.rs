@@ -0,0 +1,428 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{map_features, FeatureIndex};
+use crate::tools::*;
+use whitebox_vector::*;
+use num_cpus;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// This tool copies the features of an input vector file (`--input`) that have a spatial
+/// relation (`--relation`) with at least one of the features of a second, selecting, vector
+/// file (`--select`) to an output vector file (`--output`), with their attributes. The input
+/// and selecting files may contain points, lines, or polygons, in any combination. The
+/// relations are:
+///
+/// | Relation | Input features that are selected |
+/// |----------|----------------------------------|
+/// | intersects | Those that share at least one point with a selecting feature (the default) |
+/// | within | Those within a selecting feature, e.g. the points inside a polygon |
+/// | contains | Those that contain a selecting feature, e.g. the polygons that contain a point |
+/// | touches | Those that meet a selecting feature only at their boundaries |
+/// | within_distance | Those within `--dist` of a selecting feature |
+///
+/// A feature is within another if none of its points are outside of it and their interiors
+/// intersect, so that a line along the boundary of a polygon is not within it, but touches
+/// it. Distances are measured between the nearest points of the features, rather than their
+/// centres, and are in the units of the input coordinates.
+///
+/// If the `--invert` flag is specified, the input features that do not have the relation
+/// with any of the selecting features are output instead, e.g. the wells that are not within
+/// any of the parcels. If none of the features are selected, a warning is issued and no
+/// output file is written. Use the `SelectByAttribute` tool to select features using their
+/// attributes, and the `SpatialJoin` tool to transfer the attributes of the selecting features.
+///
+/// # See Also
+/// `SelectByAttribute`, `SpatialJoin`, `Clip`
+pub struct SelectByLocation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Relation {
+    Intersects,
+    Within,
+    Contains,
+    Touches,
+    WithinDistance,
+}
+
+impl SelectByLocation {
+    pub fn new() -> SelectByLocation {
+        // public constructor
+        let name = "SelectByLocation".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Extracts the features of a vector file that have a spatial relation with the features of another."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file, from which features are selected.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Selecting Vector File".to_owned(),
+            flags: vec!["--select".to_owned()],
+            description: "Input vector file containing the selecting features.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spatial Relation".to_owned(),
+            flags: vec!["--relation".to_owned()],
+            description: "Spatial relation that an input feature must have with a selecting feature; options include 'intersects' (default), 'within', 'contains', 'touches', and 'within_distance'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "intersects".to_owned(),
+                "within".to_owned(),
+                "contains".to_owned(),
+                "touches".to_owned(),
+                "within_distance".to_owned(),
+            ]),
+            default_value: Some("intersects".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Search Distance".to_owned(),
+            flags: vec!["--dist".to_owned()],
+            description: "Search distance for the 'within_distance' relation.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Select features without the relation?".to_owned(),
+            flags: vec!["--invert".to_owned()],
+            description: "Select the input features that do not have the relation with any selecting feature.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=wells.shp --select=streams.shp -o=out_file.shp --relation=within_distance --dist=100.0",
+            short_exe, name
+        ).replace("*", &sep);
+
+        SelectByLocation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SelectByLocation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut select_file = String::new();
+        let mut output_file = String::new();
+        let mut relation = Relation::Intersects;
+        let mut distance = 0f64;
+        let mut invert = false;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-select" {
+                select_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-relation" {
+                let relation_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .to_lowercase();
+                relation = if relation_str.contains("dist") {
+                    Relation::WithinDistance
+                } else if relation_str.contains("within") {
+                    Relation::Within
+                } else if relation_str.contains("contain") {
+                    Relation::Contains
+                } else if relation_str.contains("touch") {
+                    Relation::Touches
+                } else {
+                    Relation::Intersects
+                };
+            } else if flag_val == "-dist" || flag_val == "-distance" {
+                distance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-invert" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    invert = true;
+                }
+            }
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !select_file.contains(&sep) && !select_file.contains("/") {
+            select_file = format!("{}{}", working_directory, select_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if !distance.is_finite() || distance < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The search distance must be zero or greater.",
+            ));
+        }
+        if relation == Relation::WithinDistance && distance == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The 'within_distance' relation requires a search distance (--dist) greater than zero.",
+            ));
+        }
+        let search_distance = if relation == Relation::WithinDistance {
+            distance
+        } else {
+            0f64
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Shapefile::read(&input_file)?);
+        let select = Shapefile::read(&select_file)?;
+
+        if verbose {
+            println!("Finding the spatial relations...")
+        };
+
+        let select_index = FeatureIndex::new(
+            (0..select.num_records)
+                .map(|record_num| select.get_record(record_num).get_feature_geometry())
+                .collect(),
+        );
+
+        let mut num_procs = num_cpus::get();
+        let configs = whitebox_common::configs::get_configs()?;
+        let max_procs = configs.max_procs as usize;
+        if max_procs > 0 && max_procs < num_procs {
+            num_procs = max_procs;
+        }
+        let num_records = input.num_records;
+        let is_selected: Vec<bool> = {
+            let input = input.clone();
+            map_features(
+                num_records,
+                num_procs,
+                move |record_num| {
+                    let feature = input.get_record(record_num).get_feature_geometry();
+                    let is_related = select_index
+                        .candidates(&feature, search_distance)
+                        .into_iter()
+                        .any(|j| {
+                            let other = select_index.get(j);
+                            match relation {
+                                Relation::Intersects => feature.intersects(other),
+                                Relation::Within => feature.within(other),
+                                Relation::Contains => feature.contains(other),
+                                Relation::Touches => feature.touches(other),
+                                Relation::WithinDistance => feature.distance(other) <= distance,
+                            }
+                        });
+                    is_related != invert
+                },
+                |n| {
+                    if verbose {
+                        let progress = (100.0_f64 * n as f64 / num_records as f64) as usize;
+                        if progress != old_progress {
+                            println!("Progress: {}%", progress);
+                            old_progress = progress;
+                        }
+                    }
+                },
+            )
+        };
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        let mut num_selected = 0;
+        for record_num in (0..input.num_records).filter(|r| is_selected[*r]) {
+            output.add_record(input.get_record(record_num).clone());
+            output
+                .attributes
+                .add_record(input.attributes.get_record(record_num), false);
+            num_selected += 1;
+        }
+
+        if verbose {
+            println!("Number of selected features: {}", num_selected);
+        }
+        if num_selected > 0 {
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        } else {
+            println!("WARNING: No features were selected and therefore no output file will be written.");
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
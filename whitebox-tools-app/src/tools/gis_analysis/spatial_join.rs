@@ -0,0 +1,693 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{map_features, FeatureIndex};
+use crate::tools::*;
+use whitebox_vector::*;
+use num_cpus;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// This tool transfers the attributes of the features in a join vector file (`--join`) to the
+/// features of an input, or target, vector file (`-i`) based on their spatial relation.
+/// Unlike the `JoinTables` and `MergeTableWithCsv` tools, which match records using a
+/// common key field, the records are matched by location. The input and join files may
+/// contain points, lines, or polygons, in any combination.
+///
+/// The `--predicate` parameter sets the spatial relation that a join feature must have with
+/// a target feature to be matched to it. The options are:
+///
+/// | Predicate | Join features that are matched to a target feature |
+/// |-----------|----------------------------------------------------|
+/// | intersects | Those that share at least one point with it (the default) |
+/// | within | Those that it is within, e.g. the polygon containing a target point |
+/// | contains | Those within it, e.g. the points inside a target polygon |
+/// | nearest | The nearest one, within `--dist` if this is greater than zero |
+/// | within_distance | Those within `--dist` of it |
+///
+/// A feature is within another if none of its points are outside of it and their interiors
+/// intersect, so that a line along the boundary of a polygon is not within it. Distances are
+/// measured between the nearest points of the features, rather than their centres, and are
+/// in the units of the input coordinates. Where several join features are equally near a
+/// target, the nearest is the first of these in the join file.
+///
+/// If the `--join_operation` is 'one_to_one' (the default), the output contains a copy of each
+/// target feature, with a `JOIN_COUNT` field containing the number of matching join features.
+/// The attributes of these features are combined using the `--aggregate` method, which may be
+/// 'first' (the default), copying the attributes of the first matching feature in the join
+/// file; 'sum', 'mean', 'min', or 'max', applied to the numeric join fields only, ignoring
+/// null values; or 'count', which adds no fields other than `JOIN_COUNT`. If the
+/// `--join_operation` is 'one_to_many', the output contains a copy of the target feature for
+/// each matching join feature, with the attributes of both and a `JOIN_FID` field containing
+/// the record number of the join feature (starting at 1). In either case, target features
+/// without any matching join features are output with null join attributes, and the 'nearest'
+/// predicate adds a `JOIN_DIST` field containing the distance to the join feature.
+///
+/// By default, all of the fields of the join file other than `FID` are joined. The `--fields`
+/// parameter may be used to specify a comma-separated list of the fields to join instead.
+/// Join fields with the same name as an existing field are renamed with a numeric suffix.
+///
+/// # See Also
+/// `JoinTables`, `MergeTableWithCsv`, `Intersect`, `Clip`
+pub struct SpatialJoin {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Predicate {
+    Intersects,
+    Within,
+    Contains,
+    Nearest,
+    WithinDistance,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Aggregate {
+    First,
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl SpatialJoin {
+    pub fn new() -> SpatialJoin {
+        // public constructor
+        let name = "SpatialJoin".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description =
+            "Joins the attributes of the features in one vector to those of another, based on their spatial relation."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Target Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file, to which the join attributes are added.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Join Vector File".to_owned(),
+            flags: vec!["--join".to_owned()],
+            description: "Input vector file from which the attributes are joined.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spatial Predicate".to_owned(),
+            flags: vec!["--predicate".to_owned()],
+            description: "Spatial relation used to match join features to target features; options include 'intersects' (default), 'within', 'contains', 'nearest', and 'within_distance'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "intersects".to_owned(),
+                "within".to_owned(),
+                "contains".to_owned(),
+                "nearest".to_owned(),
+                "within_distance".to_owned(),
+            ]),
+            default_value: Some("intersects".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Search Distance".to_owned(),
+            flags: vec!["--dist".to_owned()],
+            description: "Search distance for the 'within_distance' predicate, or the maximum distance for the 'nearest' predicate (zero for no limit).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Join Operation".to_owned(),
+            flags: vec!["--join_operation".to_owned()],
+            description: "Output one record per target feature ('one_to_one', default) or per matching pair of features ('one_to_many').".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "one_to_one".to_owned(),
+                "one_to_many".to_owned(),
+            ]),
+            default_value: Some("one_to_one".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Aggregation Method".to_owned(),
+            flags: vec!["--aggregate".to_owned()],
+            description: "Method used to combine the attributes of multiple matching features in a one-to-one join; options include 'first' (default), 'count', 'sum', 'mean', 'min', and 'max'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "first".to_owned(),
+                "count".to_owned(),
+                "sum".to_owned(),
+                "mean".to_owned(),
+                "min".to_owned(),
+                "max".to_owned(),
+            ]),
+            default_value: Some("first".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Join Fields".to_owned(),
+            flags: vec!["--fields".to_owned()],
+            description: "Comma-separated list of the join fields to include (all fields other than FID if not specified).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=parcels.shp --join=wells.shp -o=out_file.shp --predicate=contains --aggregate=sum --fields=YIELD",
+            short_exe, name
+        ).replace("*", &sep);
+
+        SpatialJoin {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SpatialJoin {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut join_file = String::new();
+        let mut output_file = String::new();
+        let mut predicate = Predicate::Intersects;
+        let mut distance = 0f64;
+        let mut one_to_many = false;
+        let mut aggregate = Aggregate::First;
+        let mut fields_str = String::new();
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-join" {
+                join_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-predicate" {
+                let predicate_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .to_lowercase();
+                predicate = if predicate_str.contains("dist") {
+                    Predicate::WithinDistance
+                } else if predicate_str.contains("within") {
+                    Predicate::Within
+                } else if predicate_str.contains("contain") {
+                    Predicate::Contains
+                } else if predicate_str.contains("near") {
+                    Predicate::Nearest
+                } else {
+                    Predicate::Intersects
+                };
+            } else if flag_val == "-dist" || flag_val == "-distance" {
+                distance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-join_operation" {
+                let operation_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .to_lowercase();
+                one_to_many = operation_str.contains("many");
+            } else if flag_val == "-aggregate" {
+                let aggregate_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .to_lowercase();
+                aggregate = if aggregate_str.contains("count") {
+                    Aggregate::Count
+                } else if aggregate_str.contains("sum") {
+                    Aggregate::Sum
+                } else if aggregate_str.contains("mean") || aggregate_str.contains("av") {
+                    Aggregate::Mean
+                } else if aggregate_str.contains("min") {
+                    Aggregate::Min
+                } else if aggregate_str.contains("max") {
+                    Aggregate::Max
+                } else {
+                    Aggregate::First
+                };
+            } else if flag_val == "-fields" {
+                fields_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !join_file.contains(&sep) && !join_file.contains("/") {
+            join_file = format!("{}{}", working_directory, join_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if !distance.is_finite() || distance < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The search distance must be zero or greater.",
+            ));
+        }
+        if predicate == Predicate::WithinDistance && distance == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The 'within_distance' predicate requires a search distance (--dist) greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Shapefile::read(&input_file)?);
+        let join = Shapefile::read(&join_file)?;
+
+        // which of the join fields are joined?
+        let join_fields: Vec<usize> = if fields_str.trim().is_empty() {
+            (0..join.attributes.get_num_fields())
+                .filter(|f| join.attributes.get_field(*f).name.to_lowercase() != "fid")
+                .collect()
+        } else {
+            let mut cmd = fields_str.split(";");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = fields_str.split(",");
+                vec = cmd.collect::<Vec<&str>>();
+            }
+            let mut fields = vec![];
+            for field_name in vec {
+                match join.attributes.get_field_num(field_name.trim()) {
+                    Some(f) => fields.push(f),
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!(
+                                "The field '{}' was not located in the join attribute table.",
+                                field_name.trim()
+                            ),
+                        ));
+                    }
+                }
+            }
+            fields
+        };
+        // only the numeric fields can be summarized
+        let join_fields: Vec<usize> = if one_to_many {
+            join_fields
+        } else {
+            match aggregate {
+                Aggregate::First => join_fields,
+                Aggregate::Count => vec![],
+                _ => join_fields
+                    .into_iter()
+                    .filter(|f| join.attributes.is_field_numeric(*f))
+                    .collect(),
+            }
+        };
+
+        if verbose {
+            println!("Finding the spatial relations...")
+        };
+
+        let join_index = FeatureIndex::new(
+            (0..join.num_records)
+                .map(|record_num| join.get_record(record_num).get_feature_geometry())
+                .collect(),
+        );
+
+        let mut num_procs = num_cpus::get();
+        let configs = whitebox_common::configs::get_configs()?;
+        let max_procs = configs.max_procs as usize;
+        if max_procs > 0 && max_procs < num_procs {
+            num_procs = max_procs;
+        }
+        let num_records = input.num_records;
+        // the matching join records for each input record, with their distances from it
+        let all_matches: Vec<Vec<(usize, f64)>> = {
+            let input = input.clone();
+            map_features(
+                num_records,
+                num_procs,
+                move |record_num| {
+                    let target = input.get_record(record_num).get_feature_geometry();
+                    if predicate == Predicate::Nearest {
+                        let max_distance = if distance > 0f64 {
+                            distance
+                        } else {
+                            f64::INFINITY
+                        };
+                        join_index
+                            .nearest(&target, 1, max_distance, None)
+                            .into_iter()
+                            .map(|n| (n.0, n.1))
+                            .collect()
+                    } else {
+                        let search_distance = if predicate == Predicate::WithinDistance {
+                            distance
+                        } else {
+                            0f64
+                        };
+                        join_index
+                            .candidates(&target, search_distance)
+                            .into_iter()
+                            .filter(|j| {
+                                let other = join_index.get(*j);
+                                match predicate {
+                                    Predicate::Intersects => target.intersects(other),
+                                    Predicate::Within => target.within(other),
+                                    Predicate::Contains => target.contains(other),
+                                    _ => target.distance(other) <= distance,
+                                }
+                            })
+                            .map(|j| (j, 0f64))
+                            .collect()
+                    }
+                },
+                |n| {
+                    if verbose {
+                        let progress = (100.0_f64 * n as f64 / num_records as f64) as usize;
+                        if progress != old_progress {
+                            println!("Progress: {}%", progress);
+                            old_progress = progress;
+                        }
+                    }
+                },
+            )
+        };
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        let new_field = |output: &mut Shapefile, field: &AttributeField| {
+            let mut field = field.clone();
+            field.name = unique_field_name(&field.name, output);
+            output.attributes.add_field(&field);
+        };
+        if one_to_many {
+            new_field(
+                &mut output,
+                &AttributeField::new("JOIN_FID", FieldDataType::Int, 7u8, 0u8),
+            );
+        } else {
+            new_field(
+                &mut output,
+                &AttributeField::new("JOIN_COUNT", FieldDataType::Int, 7u8, 0u8),
+            );
+        }
+        if predicate == Predicate::Nearest {
+            new_field(
+                &mut output,
+                &AttributeField::new("JOIN_DIST", FieldDataType::Real, 12u8, 4u8),
+            );
+        }
+        for f in &join_fields {
+            let mut field = join.attributes.get_field(*f).clone();
+            if !one_to_many {
+                match aggregate {
+                    Aggregate::Mean => {
+                        field = AttributeField::new(
+                            &field.name,
+                            FieldDataType::Real,
+                            field.field_length.max(12u8),
+                            field.decimal_count.max(4u8),
+                        );
+                    }
+                    Aggregate::Sum => {
+                        field.field_length = field.field_length.max(12u8);
+                    }
+                    _ => {}
+                }
+            }
+            new_field(&mut output, &field);
+        }
+
+        let nearest_distance = |join_match: &(usize, f64)| {
+            if predicate == Predicate::Nearest {
+                vec![FieldData::Real(join_match.1)]
+            } else {
+                vec![]
+            }
+        };
+        for (record_num, matches) in all_matches.iter().enumerate() {
+            let record = input.get_record(record_num);
+            if matches.is_empty() {
+                // targets without a match are kept, with null join attributes
+                let mut atts = input.attributes.get_record(record_num);
+                if !one_to_many {
+                    atts.push(FieldData::Int(0));
+                }
+                atts.resize(output.attributes.get_num_fields(), FieldData::Null);
+                output.add_record(record.clone());
+                output.attributes.add_record(atts, false);
+            } else if one_to_many {
+                for join_match in matches {
+                    let mut atts = input.attributes.get_record(record_num);
+                    atts.push(FieldData::Int(join_match.0 as i32 + 1));
+                    atts.extend(nearest_distance(join_match));
+                    let join_atts = join.attributes.get_record(join_match.0);
+                    atts.extend(join_fields.iter().map(|f| join_atts[*f].clone()));
+                    output.add_record(record.clone());
+                    output.attributes.add_record(atts, false);
+                }
+            } else {
+                let mut atts = input.attributes.get_record(record_num);
+                atts.push(FieldData::Int(matches.len() as i32));
+                atts.extend(nearest_distance(&matches[0]));
+                let join_atts: Vec<Vec<FieldData>> = matches
+                    .iter()
+                    .map(|m| join.attributes.get_record(m.0))
+                    .collect();
+                for f in &join_fields {
+                    let value = if aggregate == Aggregate::First {
+                        join_atts[0][*f].clone()
+                    } else {
+                        let is_integer = join.attributes.get_field(*f).decimal_count == 0
+                            && aggregate != Aggregate::Mean;
+                        summarize(join_atts.iter().map(|atts| &atts[*f]), aggregate, is_integer)
+                    };
+                    atts.push(value);
+                }
+                output.add_record(record.clone());
+                output.attributes.add_record(atts, false);
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends a numeric suffix to a field name that is already in the table, keeping within
+/// the 10 character limit of DBF field names.
+pub(super) fn unique_field_name(name: &str, output: &Shapefile) -> String {
+    let mut new_name = name.to_string();
+    let mut i = 1;
+    while output.attributes.get_field_num(&new_name).is_some() {
+        let suffix = format!("_{}", i);
+        let stem: String = name.chars().take(10 - suffix.len()).collect();
+        new_name = format!("{}{}", stem, suffix);
+        i += 1;
+    }
+    new_name
+}
+
+/// Combines the non-null numeric values of a field.
+fn summarize<'a>(
+    values: impl Iterator<Item = &'a FieldData>,
+    aggregate: Aggregate,
+    is_integer: bool,
+) -> FieldData {
+    let values: Vec<f64> = values
+        .filter_map(|value| match value {
+            FieldData::Int(v) => Some(*v as f64),
+            FieldData::Int64(v) => Some(*v as f64),
+            FieldData::Real(v) => Some(*v),
+            _ => None,
+        })
+        .collect();
+    if values.is_empty() {
+        return FieldData::Null;
+    }
+    let value = match aggregate {
+        Aggregate::Sum => values.iter().sum(),
+        Aggregate::Mean => values.iter().sum::<f64>() / values.len() as f64,
+        Aggregate::Min => values.iter().cloned().fold(f64::INFINITY, f64::min),
+        _ => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+    };
+    if !is_integer {
+        FieldData::Real(value)
+    } else if value.abs() <= i32::MAX as f64 {
+        FieldData::Int(value as i32)
+    } else {
+        FieldData::Int64(value as i64)
+    }
+}
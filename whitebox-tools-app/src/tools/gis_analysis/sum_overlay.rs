@@ -225,8 +225,8 @@ impl WhiteboxTool for SumOverlay {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != in_nodata {
-                            if output.get_value(row, col) != out_nodata {
+                        if !is_nodata_value(z, in_nodata) {
+                            if !is_nodata_value(output.get_value(row, col), out_nodata) {
                                 output.increment(row, col, z);
                             } else {
                                 output.set_value(row, col, z);
@@ -225,9 +225,9 @@ impl WhiteboxTool for UpdateNodataCells {
         for row in 0..rows {
             for col in 0..columns {
                 z1 = input1.get_value(row, col);
-                if z1 == nodata1 {
+                if is_nodata_value(z1, nodata1) {
                     z2 = input2.get_value(row, col);
-                    if z2 != nodata2 {
+                    if !is_nodata_value(z2, nodata2) {
                         output.set_value(row, col, z2);
                     }
                 }
@@ -373,9 +373,9 @@ impl WhiteboxTool for WeightedOverlay {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        if output.get_value(row, col) != out_nodata {
+                        if !is_nodata_value(output.get_value(row, col), out_nodata) {
                             in_val = input.get_value(row, col);
-                            if in_val != in_nodata {
+                            if !is_nodata_value(in_val, in_nodata) {
                                 in_val = (in_val - min_val) / range;
                                 if cost[j] {
                                     in_val = 1.0 - in_val;
@@ -435,11 +435,11 @@ impl WhiteboxTool for WeightedOverlay {
                 for row in 0..rows {
                     for col in 0..columns {
                         in_val = input.get_value(row, col);
-                        if in_val != in_nodata && in_val <= 0f64 {
-                            if output.get_value(row, col) != out_nodata {
+                        if !is_nodata_value(in_val, in_nodata) && in_val <= 0f64 {
+                            if !is_nodata_value(output.get_value(row, col), out_nodata) {
                                 output.set_value(row, col, 0f64);
                             }
-                        } else if in_val == in_nodata {
+                        } else if is_nodata_value(in_val, in_nodata) {
                             output.set_value(row, col, out_nodata);
                         } // else it stays unaltered
                     }
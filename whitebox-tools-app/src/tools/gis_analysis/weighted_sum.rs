@@ -273,12 +273,12 @@ impl WhiteboxTool for WeightedSum {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        if output[(row, col)] != out_nodata {
-                            in_val = input[(row, col)];
-                            if in_val != in_nodata {
+                        if !is_nodata_value(output.get_value(row, col), out_nodata) {
+                            in_val = input.get_value(row, col);
+                            if !is_nodata_value(in_val, in_nodata) {
                                 output.increment(row, col, in_val * weights[j]);
                             } else {
-                                output[(row, col)] = out_nodata;
+                                output.set_value(row, col, out_nodata);
                             }
                         }
                     }
@@ -233,14 +233,14 @@ impl WhiteboxTool for AverageFlowpathSlope {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
-                                z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
+                                z_n = input.get_value(row + d_y[i], col + d_x[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -301,8 +301,8 @@ impl WhiteboxTool for AverageFlowpathSlope {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + d_y[i], col + d_x[i])] == inflowing_vals[i] {
@@ -333,7 +333,7 @@ impl WhiteboxTool for AverageFlowpathSlope {
                     stack.push((row, col));
                     total_flowpath_length[(row, col)] = 0.0;
                     num_flowpaths[(row, col)] = 1;
-                    total_upslope_divide_elev[(row, col)] = input[(row, col)];
+                    total_upslope_divide_elev[(row, col)] = input.get_value(row, col);
                 } else if num_inflowing[(row, col)] == -1i8 {
                     num_solved_cells += 1;
                 }
@@ -378,7 +378,7 @@ impl WhiteboxTool for AverageFlowpathSlope {
                 row_n = row + d_y[dir as usize];
                 col_n = col + d_x[dir as usize];
                 length = total_flowpath_length[(row, col)] + grid_lengths[dir as usize];
-                if total_flowpath_length[(row_n, col_n)] == nodata {
+                if is_nodata_value(total_flowpath_length[(row_n, col_n)], nodata) {
                     total_flowpath_length[(row_n, col_n)] = length;
                 } else {
                     total_flowpath_length.increment(row_n, col_n, length);
@@ -395,11 +395,14 @@ impl WhiteboxTool for AverageFlowpathSlope {
             }
 
             z_mean = total_upslope_divide_elev[(row, col)] / num_flowpaths[(row, col)] as f64;
-            z_diff = z_mean - input[(row, col)];
-            output[(row, col)] = (z_diff
-                / (total_flowpath_length[(row, col)] / num_flowpaths[(row, col)] as f64))
-                .atan()
-                .to_degrees();
+            z_diff = z_mean - input.get_value(row, col);
+            output.set_value(
+                row,
+                col,
+                (z_diff / (total_flowpath_length[(row, col)] / num_flowpaths[(row, col)] as f64))
+                    .atan()
+                    .to_degrees(),
+            );
 
             if verbose {
                 num_solved_cells += 1;
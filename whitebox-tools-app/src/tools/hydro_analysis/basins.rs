@@ -262,17 +262,17 @@ impl WhiteboxTool for Basins {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                z = pntr[(row, col)];
-                if z != nodata {
+                z = pntr.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     if z > 0.0 {
                         flow_dir[(row, col)] = pntr_matches[z as usize];
                     } else {
                         flow_dir[(row, col)] = -1i8;
                         basin_id += 1f64;
-                        output[(row, col)] = basin_id;
+                        output.set_value(row, col, basin_id);
                     }
                 } else {
-                    output[(row, col)] = nodata;
+                    output.set_value(row, col, nodata);
                 }
             }
             if verbose {
@@ -290,7 +290,7 @@ impl WhiteboxTool for Basins {
         let mut outlet_id: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if output[(row, col)] == low_value {
+                if output.get_value(row, col) == low_value {
                     // && flow_dir[(row, col)] != -2i8 {
                     flag = false;
                     x = col;
@@ -305,7 +305,7 @@ impl WhiteboxTool for Basins {
                             y += dy[dir as usize];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            z = output[(y, x)];
+                            z = output.get_value(y, x);
                             if z != low_value {
                                 outlet_id = z;
                                 flag = true;
@@ -318,7 +318,7 @@ impl WhiteboxTool for Basins {
                     flag = false;
                     x = col;
                     y = row;
-                    output[(y, x)] = outlet_id;
+                    output.set_value(y, x, outlet_id);
                     while !flag {
                         // find its downslope neighbour
                         dir = flow_dir[(y, x)];
@@ -328,13 +328,13 @@ impl WhiteboxTool for Basins {
                             y += dy[dir as usize];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            if output[(y, x)] != low_value {
+                            if output.get_value(y, x) != low_value {
                                 flag = true;
                             }
                         } else {
                             flag = true;
                         }
-                        output[(y, x)] = outlet_id;
+                        output.set_value(y, x, outlet_id);
                     }
                 }
             }
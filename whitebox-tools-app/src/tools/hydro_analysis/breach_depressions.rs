@@ -333,15 +333,15 @@ impl WhiteboxTool for BreachDepressions {
             for row in 1..rows - 1 {
                 for col in 1..columns - 1 {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         flag = true;
                         min_zn = f64::INFINITY;
                         for n in 0..8 {
                             z_n = input.get_value(row + dy[n], col + dx[n]);
-                            if z_n < min_zn && z_n != nodata {
+                            if z_n < min_zn && !is_nodata_value(z_n, nodata) {
                                 min_zn = z_n;
                             }
-                            if z_n < z && z_n != nodata {
+                            if z_n < z && !is_nodata_value(z_n, nodata) {
                                 flag = false;
                                 break;
                             }
@@ -417,7 +417,7 @@ impl WhiteboxTool for BreachDepressions {
                 zin_n = input.get_value(row_n, col_n);
                 zout_n = output.get_value(row_n, col_n);
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata_value(zin_n, nodata) {
                         output.set_value(row_n, col_n, nodata);
                         queue.push_back((row_n, col_n));
                     } else {
@@ -461,7 +461,7 @@ impl WhiteboxTool for BreachDepressions {
                     zout_n = output.get_value(row_n, col_n);
                     if zout_n == background_val {
                         zin_n = input.get_value(row_n, col_n);
-                        if zin_n != nodata {
+                        if !is_nodata_value(zin_n, nodata) {
                             flow_dir.set_value(row_n, col_n, back_link[n]);
                             output.set_value(row_n, col_n, zin_n);
                             minheap.push(GridCell {
@@ -502,7 +502,7 @@ impl WhiteboxTool for BreachDepressions {
                                 for n2 in 0..8 {
                                     let row2 = cell.0 + dy[n2];
                                     let col2 = cell.1 + dx[n2];
-                                    if input.get_value(row2, col2) == nodata
+                                    if is_nodata_value(input.get_value(row2, col2), nodata)
                                         && output.get_value(row2, col2) == background_val
                                     {
                                         if row2 >= 0 && row2 < rows && col2 >= 0 && col2 < columns {
@@ -548,7 +548,7 @@ impl WhiteboxTool for BreachDepressions {
                     zout_n = output.get_value(row_n, col_n);
                     if zout_n == background_val {
                         zin_n = input.get_value(row_n, col_n);
-                        if zin_n != nodata {
+                        if !is_nodata_value(zin_n, nodata) {
                             flow_dir.set_value(row_n, col_n, back_link[n]);
                             output.set_value(row_n, col_n, zin_n);
                             minheap.push(GridCell {
@@ -711,7 +711,7 @@ impl WhiteboxTool for BreachDepressions {
                                 for n2 in 0..8 {
                                     let row2 = cell.0 + dy[n2];
                                     let col2 = cell.1 + dx[n2];
-                                    if input.get_value(row2, col2) == nodata
+                                    if is_nodata_value(input.get_value(row2, col2), nodata)
                                         && output.get_value(row2, col2) == background_val
                                     {
                                         if row2 >= 0 && row2 < rows && col2 >= 0 && col2 < columns {
@@ -754,7 +754,7 @@ impl WhiteboxTool for BreachDepressions {
                             row_n = row + dy[dir as usize];
                             col_n = col + dx[dir as usize];
                             z_n = output.get_value(row_n, col_n);
-                            if z_n != nodata {
+                            if !is_nodata_value(z_n, nodata) {
                                 if z <= z_n + small_num {
                                     output.set_value(row, col, z_n + small_num);
                                 }
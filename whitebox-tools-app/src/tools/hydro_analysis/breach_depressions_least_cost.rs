@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/11/2019
-Last Modified: 24/11/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -100,6 +100,36 @@ pub struct BreachDepressionsLeastCost {
     example_usage: String,
 }
 
+/// The parameters of the `BreachDepressionsLeastCost` tool, used when it is run on in-memory
+/// data with `BreachDepressionsLeastCost::process`.
+#[derive(Clone, Debug)]
+pub struct BreachDepressionsLeastCostParameters {
+    /// The maximum search distance for breach paths, in cells.
+    pub dist: isize,
+    /// The maximum cost of a breach path; pits that cannot be breached within this cost are
+    /// left unbreached.
+    pub max_cost: Option<f64>,
+    /// Minimize the breach distances, rather than the elevation changes.
+    pub min_dist: bool,
+    /// The elevation increment applied to flats; calculated from the DEM if `None`, which
+    /// is preferred in most applications.
+    pub flat_increment: Option<f64>,
+    /// Fill any depressions that remain unbreached.
+    pub fill: bool,
+}
+
+impl Default for BreachDepressionsLeastCostParameters {
+    fn default() -> BreachDepressionsLeastCostParameters {
+        BreachDepressionsLeastCostParameters {
+            dist: 20,
+            max_cost: None,
+            min_dist: false,
+            flat_increment: None,
+            fill: false,
+        }
+    }
+}
+
 impl BreachDepressionsLeastCost {
     pub fn new() -> BreachDepressionsLeastCost {
         // public constructor
@@ -202,150 +232,23 @@ impl BreachDepressionsLeastCost {
             example_usage: usage,
         }
     }
-}
 
-impl WhiteboxTool for BreachDepressionsLeastCost {
-    fn get_source_file(&self) -> String {
-        String::from(file!())
-    }
-
-    fn get_tool_name(&self) -> String {
-        self.name.clone()
-    }
-
-    fn get_tool_description(&self) -> String {
-        self.description.clone()
-    }
-
-    fn get_tool_parameters(&self) -> String {
-        match serde_json::to_string(&self.parameters) {
-            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
-            Err(err) => return format!("{:?}", err),
-        }
-    }
-
-    fn get_example_usage(&self) -> String {
-        self.example_usage.clone()
-    }
-
-    fn get_toolbox(&self) -> String {
-        self.toolbox.clone()
-    }
-
-    fn run<'a>(
+    /// Breaches the depressions in a DEM held in memory, and returns the breached DEM without
+    /// writing it to disk. The returned raster should be named with `Raster::set_file_name`
+    /// before it is written.
+    pub fn process(
         &self,
-        args: Vec<String>,
-        working_directory: &'a str,
+        input: Arc<Raster>,
+        params: &BreachDepressionsLeastCostParameters,
         verbose: bool,
-    ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut max_cost = f64::INFINITY;
-        let mut max_dist = 20isize;
-        let mut flat_increment = f64::NAN;
-        let mut fill_deps = false;
-        let mut minimize_dist = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-dist" {
-                max_dist = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-max_cost" {
-                max_cost = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-flat_increment" {
-                flat_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_dist" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    minimize_dist = true;
-                }
-            } else if flag_val == "-fill" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    fill_deps = true;
-                }
-            }
-        }
-
-        if verbose {
-            let tool_name = self.get_tool_name();
-            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
-            // 28 = length of the 'Powered by' by statement.
-            println!("{}", "*".repeat(welcome_len));
-            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
-            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
-            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
-            println!("{}", "*".repeat(welcome_len));
-        }
-
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
+    ) -> Result<Raster, Error> {
+        let max_dist = params.dist;
+        let max_cost = params.max_cost.unwrap_or(f64::INFINITY);
+        let minimize_dist = params.min_dist;
+        let fill_deps = params.fill;
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
-
-        if verbose {
-            println!("Reading data...")
-        };
-
-        let input = Arc::new(Raster::new(&input_file, "r").expect("Error reading input raster"));
-
-        let start = Instant::now();
-
         let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
@@ -376,15 +279,10 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             num_procs = max_procs;
         }
 
-        let small_num = if !flat_increment.is_nan() || flat_increment == 0f64 {
-            flat_increment
-        } else {
-            let elev_digits = (input.configs.maximum as i32).to_string().len();
-            let elev_multiplier = 10.0_f64.powi((9 - elev_digits) as i32);
-            1.0_f64 / elev_multiplier as f64 * diagres.ceil()
-        };
+        let small_num = self.get_flat_increment(&input, params);
 
-        let mut output = Raster::initialize_using_file(&output_file, &input);
+        // The output is named by the caller before it is written.
+        let mut output = Raster::initialize_using_file("output.tif", &input);
         // Even if the input is f32, the output will need to be 64-bit to represent the small elevation differences
         output.configs.data_type = DataType::F64;
         let display_min = input.configs.display_min;
@@ -405,7 +303,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                     let mut pits = vec![];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             flag = true;
                             min_zn = f64::INFINITY;
                             for n in 0..8 {
@@ -413,7 +311,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 if zn < min_zn {
                                     min_zn = zn;
                                 }
-                                if zn == nodata {
+                                if is_nodata_value(zn, nodata) {
                                     // It's an edge cell.
                                     flag = false;
                                     break;
@@ -483,7 +381,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             flag = true;
             for n in 0..8 {
                 zn = output.get_value(row + dy[n], col + dx[n]);
-                if zn < z && zn != nodata {
+                if zn < z && !is_nodata_value(zn, nodata) {
                     // It has a lower non-nodata cell
                     // Resolving some other pit cell resulted in a solution for this one.
                     num_solved += 1;
@@ -528,7 +426,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                             backlink.set_value(rn, cn, backlink_dir[n]);
                             zn = output.get_value(rn, cn);
                             zout = z - (length_n as f64 * small_num);
-                            if zn > zout && zn != nodata {
+                            if zn > zout && !is_nodata_value(zn, nodata) {
                                 cost2 = zn - zout;
                                 new_cost = if minimize_dist {
                                     accum + (cost1 + cost2) / 2f64 * cost_dist[n]
@@ -543,7 +441,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                         priority: new_cost,
                                     });
                                 }
-                            } else if zn <= zout || zn == nodata {
+                            } else if zn <= zout || is_nodata_value(zn, nodata) {
                                 // We're at a cell that we can breach to
                                 while flag {
                                     // Find which cell to go to from here
@@ -617,11 +515,11 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                     for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
                         for col in 1..columns - 1 {
                             z = output2.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 flag = true;
                                 for n in 0..8 {
                                     zn = output2.get_value(row + dy[n], col + dx[n]);
-                                    if zn < z || zn == nodata {
+                                    if zn < z || is_nodata_value(zn, nodata) {
                                         // It either has a lower neighbour or is an edge cell.
                                         flag = false;
                                         break;
@@ -634,6 +532,9 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                             }
                         }
                     }
+                    // The Arc must be released before the pits are sent, since it is unwrapped
+                    // once they have all been received.
+                    drop(output2);
                     tx.send(pits).unwrap();
                 });
             }
@@ -698,14 +599,14 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 if visited.get_value(rn, cn) == 0 {
                                     zn = output.get_value(rn, cn);
                                     if !outlet_found {
-                                        if zn >= z && zn != nodata {
+                                        if zn >= z && !is_nodata_value(zn, nodata) {
                                             minheap.push(GridCell {
                                                 row: rn,
                                                 column: cn,
                                                 priority: zn,
                                             });
                                             visited.set_value(rn, cn, 1);
-                                        } else if zn != nodata {
+                                        } else if !is_nodata_value(zn, nodata) {
                                             // zn < z
                                             // 'cell' has a lower neighbour that hasn't already passed through minheap.
                                             // Therefore, 'cell' is a pour point cell.
@@ -806,7 +707,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                         rn = cell.0 + dy[n];
                         cn = cell.1 + dx[n];
                         zn = output.get_value(rn, cn);
-                        if zn < z && zn != nodata {
+                        if zn < z && !is_nodata_value(zn, nodata) {
                             flag = true;
                             break;
                         }
@@ -860,7 +761,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 cn = cell2.column + dx[n];
                                 if flats.get_value(rn, cn) != 3 {
                                     zn = output.get_value(rn, cn);
-                                    if zn == z && zn != nodata {
+                                    if zn == z && !is_nodata_value(zn, nodata) {
                                         // queue.push_back((rn, cn, z));
                                         minheap2.push(GridCell2 {
                                             row: rn,
@@ -882,7 +783,10 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 cn = cell2.column + dx[n];
                                 if flats.get_value(rn, cn) != 3 {
                                     zn = output.get_value(rn, cn);
-                                    if zn < z + small_num && zn >= cell2.z && zn != nodata {
+                                    if zn < z + small_num
+                                        && zn >= cell2.z
+                                        && !is_nodata_value(zn, nodata)
+                                    {
                                         // queue.push_back((rn, cn, cell2.2));
                                         minheap2.push(GridCell2 {
                                             row: rn,
@@ -910,13 +814,183 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             }
         }
 
-        let elapsed_time = get_formatted_elapsed_time(start);
         output.configs.display_min = display_min;
         output.configs.display_max = display_max;
         output.add_metadata_entry(format!(
             "Created by whitebox_tools\' {} tool",
             self.get_tool_name()
         ));
+
+        Ok(output)
+    }
+
+    /// Returns the elevation increment used to ensure flow across flats, which is calculated
+    /// from the DEM unless it is specified.
+    fn get_flat_increment(&self, input: &Raster, params: &BreachDepressionsLeastCostParameters) -> f64 {
+        match params.flat_increment {
+            Some(flat_increment) => flat_increment,
+            None => {
+                let resx = input.configs.resolution_x;
+                let resy = input.configs.resolution_y;
+                let diagres = (resx * resx + resy * resy).sqrt();
+                let elev_digits = (input.configs.maximum as i32).to_string().len();
+                let elev_multiplier = 10.0_f64.powi((9 - elev_digits) as i32);
+                1.0_f64 / elev_multiplier * diagres.ceil()
+            }
+        }
+    }
+}
+
+impl WhiteboxTool for BreachDepressionsLeastCost {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut max_cost = f64::INFINITY;
+        let mut max_dist = 20isize;
+        let mut flat_increment = f64::NAN;
+        let mut fill_deps = false;
+        let mut minimize_dist = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dist" {
+                max_dist = if keyval {
+                    vec[1].to_string().parse::<isize>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<isize>().unwrap()
+                };
+            } else if flag_val == "-max_cost" {
+                max_cost = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-flat_increment" {
+                flat_increment = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-min_dist" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    minimize_dist = true;
+                }
+            } else if flag_val == "-fill" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    fill_deps = true;
+                }
+            }
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r").expect("Error reading input raster"));
+
+        let start = Instant::now();
+        let params = BreachDepressionsLeastCostParameters {
+            dist: max_dist,
+            max_cost: if max_cost.is_finite() { Some(max_cost) } else { None },
+            min_dist: minimize_dist,
+            flat_increment: if flat_increment.is_nan() { None } else { Some(flat_increment) },
+            fill: fill_deps,
+        };
+        let small_num = self.get_flat_increment(&input, &params);
+        let mut output = self.process(input, &params, verbose)?;
+        output.set_file_name(&output_file);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
         output.add_metadata_entry(format!("Input file: {}", input_file));
         output.add_metadata_entry(format!("Maximum search distance: {}", max_dist));
         output.add_metadata_entry(format!("Maximum breach cost: {}", max_cost));
@@ -205,12 +205,12 @@ impl WhiteboxTool for BreachSingleCellPits {
         let mut flag: bool;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     flag = true;
                     for i in 0..8 {
-                        zn = input[(row + dy[i], col + dx[i])];
-                        if zn < z && zn != nodata {
+                        zn = input.get_value(row + dy[i], col + dx[i]);
+                        if zn < z && !is_nodata_value(zn, nodata) {
                             flag = false;
                             break;
                         }
@@ -218,10 +218,13 @@ impl WhiteboxTool for BreachSingleCellPits {
                     if flag {
                         // it's a pit cell
                         for i in 0..16 {
-                            zn = input[(row + dy2[i], col + dx2[i])];
-                            if zn < z && zn != nodata {
-                                output[(row + dy[breachcell[i]], col + dx[breachcell[i]])] =
-                                    (z + zn) / 2f64;
+                            zn = input.get_value(row + dy2[i], col + dx2[i]);
+                            if zn < z && !is_nodata_value(zn, nodata) {
+                                output.set_value(
+                                    row + dy[breachcell[i]],
+                                    col + dx[breachcell[i]],
+                                    (z + zn) / 2f64,
+                                );
                             }
                         }
                     }
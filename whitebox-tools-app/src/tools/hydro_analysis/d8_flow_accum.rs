@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/016/2017
-Last Modified: 29/08/2021
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -60,6 +60,44 @@ pub struct D8FlowAccumulation {
     example_usage: String,
 }
 
+/// The type of flow accumulation values output by the `D8FlowAccumulation` tool.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum D8FlowAccumulationOutputType {
+    /// The number of inflowing grid cells.
+    Cells,
+    /// The upslope area.
+    CatchmentArea,
+    /// The upslope area divided by the flow width.
+    SpecificContributingArea,
+}
+
+/// The parameters of the `D8FlowAccumulation` tool, used when it is run on in-memory data
+/// with `D8FlowAccumulation::process`.
+#[derive(Clone, Debug)]
+pub struct D8FlowAccumulationParameters {
+    pub out_type: D8FlowAccumulationOutputType,
+    /// Log-transform the output values.
+    pub log: bool,
+    /// Clip the upper tail of the display range by 1%.
+    pub clip: bool,
+    /// The input is a D8 flow pointer raster, rather than a DEM.
+    pub pntr: bool,
+    /// The input D8 pointer uses the Esri pointer scheme; implies `pntr`.
+    pub esri_pntr: bool,
+}
+
+impl Default for D8FlowAccumulationParameters {
+    fn default() -> D8FlowAccumulationParameters {
+        D8FlowAccumulationParameters {
+            out_type: D8FlowAccumulationOutputType::SpecificContributingArea,
+            log: false,
+            clip: false,
+            pntr: false,
+            esri_pntr: false,
+        }
+    }
+}
+
 impl D8FlowAccumulation {
     pub fn new() -> D8FlowAccumulation {
         // public constructor
@@ -157,141 +195,39 @@ impl D8FlowAccumulation {
             example_usage: usage,
         }
     }
-}
-
-impl WhiteboxTool for D8FlowAccumulation {
-    fn get_source_file(&self) -> String {
-        String::from(file!())
-    }
-
-    fn get_tool_name(&self) -> String {
-        self.name.clone()
-    }
 
-    fn get_tool_description(&self) -> String {
-        self.description.clone()
-    }
-
-    fn get_tool_parameters(&self) -> String {
-        match serde_json::to_string(&self.parameters) {
-            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
-            Err(err) => return format!("{:?}", err),
+    /// Calculates a D8 flow accumulation raster from a DEM, or D8 pointer raster, held in
+    /// memory, and returns it without writing it to disk. The returned raster should be named
+    /// with `Raster::set_file_name` before it is written.
+    pub fn process(
+        &self,
+        input: Arc<Raster>,
+        params: &D8FlowAccumulationParameters,
+        verbose: bool,
+    ) -> Result<Raster, Error> {
+        let (output, interior_pit_found) = self.accumulate(input, params, verbose)?;
+        if interior_pit_found && verbose {
+            println!("WARNING: Interior pit cells were found within the input DEM. It is likely that the DEM needs to be processed to remove topographic depressions and flats prior to running this tool.");
         }
+        Ok(output)
     }
 
-    fn get_example_usage(&self) -> String {
-        self.example_usage.clone()
-    }
-
-    fn get_toolbox(&self) -> String {
-        self.toolbox.clone()
-    }
-
-    fn run<'a>(
+    /// Performs the flow accumulation, returning the output raster and whether any interior
+    /// pit cells were found in the input.
+    fn accumulate(
         &self,
-        args: Vec<String>,
-        working_directory: &'a str,
+        input: Arc<Raster>,
+        params: &D8FlowAccumulationParameters,
         verbose: bool,
-    ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut out_type = String::from("sca");
-        let mut log_transform = false;
-        let mut clip_max = false;
-        let mut pntr_input = false;
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-out_type" {
-                if keyval {
-                    out_type = vec[1].to_lowercase();
-                } else {
-                    out_type = args[i + 1].to_lowercase();
-                }
-                if out_type.contains("specific") || out_type.contains("sca") {
-                    out_type = String::from("sca");
-                } else if out_type.contains("cells") {
-                    out_type = String::from("cells");
-                } else {
-                    out_type = String::from("ca");
-                }
-            } else if flag_val == "-log" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    log_transform = true;
-                }
-            } else if flag_val == "-clip" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_max = true;
-                }
-            } else if flag_val == "-pntr" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    pntr_input = true;
-                }
-            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                    pntr_input = true;
-                }
-            }
-        }
-
-        if verbose {
-            let tool_name = self.get_tool_name();
-            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
-            // 28 = length of the 'Powered by' by statement.
-            println!("{}", "*".repeat(welcome_len));
-            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
-            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
-            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
-            println!("{}", "*".repeat(welcome_len));
-        }
-
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
+    ) -> Result<(Raster, bool), Error> {
+        let out_type = params.out_type;
+        let log_transform = params.log;
+        let clip_max = params.clip;
+        let pntr_input = params.pntr || params.esri_pntr;
+        let esri_style = params.esri_pntr;
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
-
-        if verbose {
-            println!("Reading data...")
-        };
-
-        let input = Arc::new(Raster::new(&input_file, "r")?); // Memory: 8 bytes per grid cell.
-
-        let start = Instant::now();
         let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let num_cells = rows * columns;
@@ -338,13 +274,13 @@ impl WhiteboxTool for D8FlowAccumulation {
                         let mut data: Vec<i8> = vec![-2i8; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 dir = 0i8;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
-                                    z_n = input[(row + dy[i], col + dx[i])];
-                                    if z_n != nodata {
+                                    z_n = input.get_value(row + dy[i], col + dx[i]);
+                                    if !is_nodata_value(z_n, nodata) {
                                         slope = (z - z_n) / grid_lengths[i];
                                         if slope > max_slope && slope > 0f64 {
                                             max_slope = slope;
@@ -429,7 +365,7 @@ impl WhiteboxTool for D8FlowAccumulation {
                         let mut data: Vec<i8> = vec![-2i8; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 if z > 0f64 {
                                     data[col as usize] = pntr_matches[z as usize];
                                 } else {
@@ -437,7 +373,10 @@ impl WhiteboxTool for D8FlowAccumulation {
                                     // is this no-flow cell interior?
                                     neighbouring_nodata = false;
                                     for i in 0..8 {
-                                        if input.get_value(row + dy[i], col + dx[i]) == nodata {
+                                        if is_nodata_value(
+                                            input.get_value(row + dy[i], col + dx[i]),
+                                            nodata,
+                                        ) {
                                             neighbouring_nodata = true;
                                             break;
                                         }
@@ -471,7 +410,8 @@ impl WhiteboxTool for D8FlowAccumulation {
 
         let input_configs = input.configs.clone(); // Memory: -8 bytes per pixel = 1 bytes per pixel
         drop(input);
-        let mut output = Raster::initialize_using_config(&output_file, &input_configs); // Memory: +8 bytes per grid cell = 9 bytes per grid cell
+        // The output is named by the caller before it is written.
+        let mut output = Raster::initialize_using_config("output.tif", &input_configs); // Memory: +8 bytes per grid cell = 9 bytes per grid cell
         let out_nodata = -32768f64;
         output.configs.nodata = out_nodata;
         output.configs.photometric_interp = PhotometricInterpretation::Continuous; // if the input is a pointer, this may not be the case by default.
@@ -548,7 +488,7 @@ impl WhiteboxTool for D8FlowAccumulation {
             let cell = stack.pop().expect("Error during pop operation.");
             row = cell.0;
             col = cell.1;
-            fa = output[(row, col)];
+            fa = output.get_value(row, col);
             num_inflowing.decrement(row, col, 1i8);
             dir = flow_dir.get_value(row, col);
             if dir >= 0 {
@@ -598,10 +538,10 @@ impl WhiteboxTool for D8FlowAccumulation {
             avg_cell_size,
             avg_cell_size,
         ];
-        if out_type == "cells" {
+        if out_type == D8FlowAccumulationOutputType::Cells {
             cell_area = 1.0;
             flow_widths = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
-        } else if out_type == "ca" {
+        } else if out_type == D8FlowAccumulationOutputType::CatchmentArea {
             flow_widths = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
         }
 
@@ -614,11 +554,18 @@ impl WhiteboxTool for D8FlowAccumulation {
                     } else {
                         dir = flow_dir.get_value(row, col);
                         if dir >= 0 {
-                            output[(row, col)] =
-                                (output[(row, col)] * cell_area / flow_widths[dir as usize]).ln();
+                            output.set_value(
+                                row,
+                                col,
+                                (output.get_value(row, col) * cell_area / flow_widths[dir as usize])
+                                    .ln(),
+                            );
                         } else {
-                            output[(row, col)] =
-                                (output[(row, col)] * cell_area / flow_widths[3]).ln();
+                            output.set_value(
+                                row,
+                                col,
+                                (output.get_value(row, col) * cell_area / flow_widths[3]).ln(),
+                            );
                         }
                     }
                 }
@@ -669,11 +616,163 @@ impl WhiteboxTool for D8FlowAccumulation {
         if clip_max {
             output.clip_display_max(1.0);
         }
-        let elapsed_time = get_formatted_elapsed_time(start);
         output.add_metadata_entry(format!(
             "Created by whitebox_tools\' {} tool",
             self.get_tool_name()
         ));
+
+        Ok((output, interior_pit_found))
+    }
+}
+
+impl WhiteboxTool for D8FlowAccumulation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut out_type = String::from("sca");
+        let mut log_transform = false;
+        let mut clip_max = false;
+        let mut pntr_input = false;
+        let mut esri_style = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-out_type" {
+                if keyval {
+                    out_type = vec[1].to_lowercase();
+                } else {
+                    out_type = args[i + 1].to_lowercase();
+                }
+                if out_type.contains("specific") || out_type.contains("sca") {
+                    out_type = String::from("sca");
+                } else if out_type.contains("cells") {
+                    out_type = String::from("cells");
+                } else {
+                    out_type = String::from("ca");
+                }
+            } else if flag_val == "-log" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    log_transform = true;
+                }
+            } else if flag_val == "-clip" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    clip_max = true;
+                }
+            } else if flag_val == "-pntr" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    pntr_input = true;
+                }
+            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    esri_style = true;
+                    pntr_input = true;
+                }
+            }
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?); // Memory: 8 bytes per grid cell.
+
+        let start = Instant::now();
+        let out_type = if out_type == "cells" {
+            D8FlowAccumulationOutputType::Cells
+        } else if out_type == "ca" {
+            D8FlowAccumulationOutputType::CatchmentArea
+        } else {
+            D8FlowAccumulationOutputType::SpecificContributingArea
+        };
+        let params = D8FlowAccumulationParameters {
+            out_type,
+            log: log_transform,
+            clip: clip_max,
+            pntr: pntr_input,
+            esri_pntr: esri_style,
+        };
+        let (mut output, interior_pit_found) = self.accumulate(input, &params, verbose)?;
+        output.set_file_name(&output_file);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
         output.add_metadata_entry(format!("Input file: {}", input_file));
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
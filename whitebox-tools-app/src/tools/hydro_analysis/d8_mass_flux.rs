@@ -318,14 +318,14 @@ impl WhiteboxTool for D8MassFlux {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
-                                z_n = input[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                z_n = input.get_value(row + dy[i], col + dx[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -386,8 +386,8 @@ impl WhiteboxTool for D8MassFlux {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + dy[i], col + dx[i])] == inflowing_vals[i] {
@@ -437,14 +437,14 @@ impl WhiteboxTool for D8MassFlux {
         }
         let load_nodata = absorption.configs.nodata;
 
-        if load_nodata == nodata {
+        if is_nodata_value(load_nodata, nodata) {
             output.set_data_from_raster(&loading)?;
         } else {
             let mut load: f64;
             for row in 0..rows {
                 for col in 0..columns {
                     load = loading.get_value(row, col);
-                    if load != load_nodata {
+                    if !is_nodata_value(load, load_nodata) {
                         output.set_value(row, col, load);
                     } else {
                         output.set_value(row, col, nodata);
@@ -499,7 +499,7 @@ impl WhiteboxTool for D8MassFlux {
 
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) == nodata {
+                if is_nodata_value(input.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                 }
             }
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 16/06/2017
-Last Modified: 18/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -56,6 +56,14 @@ pub struct D8Pointer {
     example_usage: String,
 }
 
+/// The parameters of the `D8Pointer` tool, used when it is run on in-memory data with
+/// `D8Pointer::process`.
+#[derive(Clone, Debug, Default)]
+pub struct D8PointerParameters {
+    /// Use the Esri pointer scheme, rather than the WhiteboxTools scheme.
+    pub esri_pntr: bool,
+}
+
 impl D8Pointer {
     pub fn new() -> D8Pointer {
         // public constructor
@@ -118,6 +126,123 @@ impl D8Pointer {
             example_usage: usage,
         }
     }
+
+    /// Calculates a D8 flow pointer raster from a DEM held in memory, and returns it without
+    /// writing it to disk. The returned raster should be named with `Raster::set_file_name`
+    /// before it is written.
+    pub fn process(
+        &self,
+        input: Arc<Raster>,
+        params: &D8PointerParameters,
+        verbose: bool,
+    ) -> Result<Raster, Error> {
+        let esri_pntr = params.esri_pntr;
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let cell_size_x = input.configs.resolution_x;
+        let cell_size_y = input.configs.resolution_y;
+        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+
+        // let mut output = Raster::initialize_using_file(&output_file, &input);
+        let rows = input.configs.rows as isize;
+        let nodata = input.configs.nodata;
+        let out_nodata = -32768i16;
+        let columns = input.configs.columns as isize;
+
+        let mut num_procs = num_cpus::get() as isize;
+        let configs = whitebox_common::configs::get_configs()?;
+        let max_procs = configs.max_procs;
+        if max_procs > 0 && max_procs < num_procs {
+            num_procs = max_procs;
+        }
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx1 = tx.clone();
+            thread::spawn(move || {
+                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+                let grid_lengths = [
+                    diag_cell_size,
+                    cell_size_x,
+                    diag_cell_size,
+                    cell_size_y,
+                    diag_cell_size,
+                    cell_size_x,
+                    diag_cell_size,
+                    cell_size_y,
+                ];
+                // let out_vals = match esri_pntr {
+                //     true => [128f64, 1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64],
+                //     false => [1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64, 128f64],
+                // };
+                let out_vals = match esri_pntr {
+                    true => [128i16, 1, 2, 4, 8, 16, 32, 64],
+                    false => [1i16, 2, 4, 8, 16, 32, 64, 128],
+                };
+                let (mut z, mut z_n, mut slope): (f64, f64, f64);
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    // let mut data = vec![out_nodata; columns as usize];
+                    let mut data = vec![out_nodata; columns as usize];
+                    for col in 0..columns {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
+                            let mut dir = 0;
+                            let mut max_slope = f64::MIN;
+                            for i in 0..8 {
+                                z_n = input.get_value(row + d_y[i], col + d_x[i]);
+                                if !is_nodata_value(z_n, nodata) {
+                                    slope = (z - z_n) / grid_lengths[i];
+                                    if slope > max_slope && slope > 0f64 {
+                                        max_slope = slope;
+                                        dir = i;
+                                    }
+                                }
+                            }
+                            if max_slope >= 0f64 {
+                                data[col as usize] = out_vals[dir]; //(1 << dir) as f64;
+                            } else {
+                                data[col as usize] = 0i16; // 0f64;
+                            }
+                        }
+                    }
+                    tx1.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output: Array2D<i16> = Array2D::new(rows, columns, out_nodata, out_nodata)?;
+        for row in 0..rows {
+            let data = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(data.0, data.1);
+
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let in_configs = input.configs.clone();
+        drop(input);
+
+        // The output is named by the caller before it is written.
+        let mut output_raster = Raster::initialize_using_array2d("output.tif", &in_configs, output);
+
+        output_raster.configs.nodata = out_nodata as f64;
+        output_raster.configs.data_type = DataType::I16;
+        output_raster.configs.palette = "qual.plt".to_string();
+        output_raster.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output_raster.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+
+        Ok(output_raster)
+    }
 }
 
 impl WhiteboxTool for D8Pointer {
@@ -211,9 +336,6 @@ impl WhiteboxTool for D8Pointer {
 
         let sep: String = path::MAIN_SEPARATOR.to_string();
 
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
         if !input_file.contains(&sep) && !input_file.contains("/") {
             input_file = format!("{}{}", working_directory, input_file);
         }
@@ -228,106 +350,13 @@ impl WhiteboxTool for D8Pointer {
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
         let start = Instant::now();
-        let cell_size_x = input.configs.resolution_x;
-        let cell_size_y = input.configs.resolution_y;
-        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
-
-        // let mut output = Raster::initialize_using_file(&output_file, &input);
-        let rows = input.configs.rows as isize;
-        let nodata = input.configs.nodata;
-        let out_nodata = -32768i16;
-        let columns = input.configs.columns as isize;
-
-        let mut num_procs = num_cpus::get() as isize;
-        let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
-                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
-                let grid_lengths = [
-                    diag_cell_size,
-                    cell_size_x,
-                    diag_cell_size,
-                    cell_size_y,
-                    diag_cell_size,
-                    cell_size_x,
-                    diag_cell_size,
-                    cell_size_y,
-                ];
-                // let out_vals = match esri_style {
-                //     true => [128f64, 1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64],
-                //     false => [1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64, 128f64],
-                // };
-                let out_vals = match esri_style {
-                    true => [128i16, 1, 2, 4, 8, 16, 32, 64],
-                    false => [1i16, 2, 4, 8, 16, 32, 64, 128],
-                };
-                let (mut z, mut z_n, mut slope): (f64, f64, f64);
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    // let mut data = vec![out_nodata; columns as usize];
-                    let mut data = vec![out_nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
-                            let mut dir = 0;
-                            let mut max_slope = f64::MIN;
-                            for i in 0..8 {
-                                z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
-                                    slope = (z - z_n) / grid_lengths[i];
-                                    if slope > max_slope && slope > 0f64 {
-                                        max_slope = slope;
-                                        dir = i;
-                                    }
-                                }
-                            }
-                            if max_slope >= 0f64 {
-                                data[col as usize] = out_vals[dir]; //(1 << dir) as f64;
-                            } else {
-                                data[col as usize] = 0i16; // 0f64;
-                            }
-                        }
-                    }
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
-
-        let mut output: Array2D<i16> = Array2D::new(rows, columns, out_nodata, out_nodata)?;
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
-
-        let in_configs = input.configs.clone();
-        drop(input);
-
-        let mut output_raster = Raster::initialize_using_array2d(&output_file, &in_configs, output);
+        let params = D8PointerParameters {
+            esri_pntr: esri_style,
+        };
+        let mut output_raster = self.process(input, &params, verbose)?;
+        output_raster.set_file_name(&output_file);
 
         let elapsed_time = get_formatted_elapsed_time(start);
-        output_raster.configs.nodata = out_nodata as f64;
-        output_raster.configs.data_type = DataType::I16;
-        output_raster.configs.palette = "qual.plt".to_string();
-        output_raster.configs.photometric_interp = PhotometricInterpretation::Categorical;
-        output_raster.add_metadata_entry(format!(
-            "Created by whitebox_tools\' {} tool",
-            self.get_tool_name()
-        ));
         output_raster.add_metadata_entry(format!("Input file: {}", input_file));
         if esri_style {
             output_raster.add_metadata_entry("ESRI-style output: true".to_string());
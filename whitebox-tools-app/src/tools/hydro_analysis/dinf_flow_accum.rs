@@ -359,17 +359,19 @@ impl WhiteboxTool for DInfFlowAccumulation {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            e0 = input[(row, col)];
-                            if e0 != nodata {
+                            e0 = input.get_value(row, col);
+                            if !is_nodata_value(e0, nodata) {
                                 dir = 360.0;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
                                     ac = ac_vals[i];
                                     af = af_vals[i];
-                                    e1 = input[(row + e1_row[i], col + e1_col[i])];
-                                    e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                    if e1 != nodata && e2 != nodata {
+                                    e1 = input.get_value(row + e1_row[i], col + e1_col[i]);
+                                    e2 = input.get_value(row + e2_row[i], col + e2_col[i]);
+                                    if !is_nodata_value(e1, nodata)
+                                        && !is_nodata_value(e2, nodata)
+                                    {
                                         if e0 > e1 && e0 > e2 {
                                             s1 = (e0 - e1) / grid_res;
                                             s2 = (e1 - e2) / grid_res;
@@ -477,7 +479,7 @@ impl WhiteboxTool for DInfFlowAccumulation {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         dir = flow_dir[(row, col)];
-                        if dir != nodata {
+                        if !is_nodata_value(dir, nodata) {
                             count = 0;
                             for i in 0..8 {
                                 dir = flow_dir[(row + d_y[i], col + d_x[i])];
@@ -536,7 +538,7 @@ impl WhiteboxTool for DInfFlowAccumulation {
             let cell = stack.pop().expect("Error during pop operation.");
             row = cell.0;
             col = cell.1;
-            fa = output[(row, col)];
+            fa = output.get_value(row, col);
             num_inflowing[(row, col)] = -1i8;
 
             dir = flow_dir[(row, col)];
@@ -659,10 +661,14 @@ impl WhiteboxTool for DInfFlowAccumulation {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
-                        output[(row, col)] = (output[(row, col)] * cell_area / avg_cell_size).ln();
+                        output.set_value(
+                            row,
+                            col,
+                            (output.get_value(row, col) * cell_area / avg_cell_size).ln(),
+                        );
                     }
                 }
 
@@ -677,10 +683,14 @@ impl WhiteboxTool for DInfFlowAccumulation {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
-                        output[(row, col)] = output[(row, col)] * cell_area / avg_cell_size;
+                        output.set_value(
+                            row,
+                            col,
+                            output.get_value(row, col) * cell_area / avg_cell_size,
+                        );
                     }
                 }
 
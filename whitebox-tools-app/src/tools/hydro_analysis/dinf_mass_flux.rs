@@ -324,17 +324,17 @@ impl WhiteboxTool for DInfMassFlux {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        e0 = input[(row, col)];
-                        if e0 != nodata {
+                        e0 = input.get_value(row, col);
+                        if !is_nodata_value(e0, nodata) {
                             dir = 360.0;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 ac = ac_vals[i];
                                 af = af_vals[i];
-                                e1 = input[(row + e1_row[i], col + e1_col[i])];
-                                e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                if e1 != nodata && e2 != nodata {
+                                e1 = input.get_value(row + e1_row[i], col + e1_col[i]);
+                                e2 = input.get_value(row + e2_row[i], col + e2_col[i]);
+                                if !is_nodata_value(e1, nodata) && !is_nodata_value(e2, nodata) {
                                     if e0 > e1 && e0 > e2 {
                                         s1 = (e0 - e1) / grid_res;
                                         if s1 == 0.0 {
@@ -442,7 +442,7 @@ impl WhiteboxTool for DInfMassFlux {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         dir = flow_dir[(row, col)];
-                        if dir != nodata {
+                        if !is_nodata_value(dir, nodata) {
                             count = 0;
                             for i in 0..8 {
                                 dir = flow_dir[(row + d_y[i], col + d_x[i])];
@@ -500,7 +500,7 @@ impl WhiteboxTool for DInfMassFlux {
         }
         let load_nodata = absorption.configs.nodata;
 
-        if load_nodata == nodata {
+        if is_nodata_value(load_nodata, nodata) {
             output.set_data_from_raster(&loading)?;
         // let _ = match output.set_data_from_raster(&loading) {
         //     Ok(_) => // do nothing,
@@ -511,7 +511,7 @@ impl WhiteboxTool for DInfMassFlux {
             for row in 0..rows {
                 for col in 0..columns {
                     load = loading.get_value(row, col);
-                    if load != load_nodata {
+                    if !is_nodata_value(load, load_nodata) {
                         output.set_value(row, col, load);
                     } else {
                         output.set_value(row, col, nodata);
@@ -635,7 +635,7 @@ impl WhiteboxTool for DInfMassFlux {
 
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) == nodata {
+                if is_nodata_value(input.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                 }
             }
@@ -248,17 +248,17 @@ impl WhiteboxTool for DInfPointer {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        e0 = input[(row, col)];
-                        if e0 != nodata {
+                        e0 = input.get_value(row, col);
+                        if !is_nodata_value(e0, nodata) {
                             dir = 360.0;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 ac = ac_vals[i];
                                 af = af_vals[i];
-                                e1 = input[(row + e1_row[i], col + e1_col[i])];
-                                e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                if e1 != nodata && e2 != nodata {
+                                e1 = input.get_value(row + e1_row[i], col + e1_col[i]);
+                                e2 = input.get_value(row + e2_row[i], col + e2_col[i]);
+                                if !is_nodata_value(e1, nodata) && !is_nodata_value(e2, nodata) {
                                     if e0 > e1 && e0 > e2 {
                                         s1 = (e0 - e1) / grid_res;
                                         s2 = (e1 - e2) / grid_res;
@@ -285,13 +285,13 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                         let mut data: Vec<i8> = vec![flow_nodata; columns as usize];
                         for col in 0..columns {
                             z = dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 dir = 0i8;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
                                     z_n = dem.get_value(row + dy[i], col + dx[i]);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         slope = (z - z_n) / grid_lengths[i];
                                         if slope > max_slope && slope > 0f64 {
                                             max_slope = slope;
@@ -332,12 +332,12 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                 for col in 0..columns {
                     // stream cells get added to the stack; nodata cells get assigned that in the output
                     if streams.get_value(row, col) > 0f64
-                        && streams.get_value(row, col) != streams_nodata
+                        && !is_nodata_value(streams.get_value(row, col), streams_nodata)
                     {
                         output.set_value(row, col, 0f64);
                         stack.push((row, col, 0f64));
                     }
-                    if dem.get_value(row, col) == nodata {
+                    if is_nodata_value(dem.get_value(row, col), nodata) {
                         output.set_value(row, col, nodata);
                         num_solved_cells += 1;
                     }
@@ -387,7 +387,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                     if flow_dir.get_value(row_n, col_n) == inflowing_vals[n]
                         && output.get_value(row_n, col_n) == background_value
                     {
-                        if stream_dist != nodata {
+                        if !is_nodata_value(stream_dist, nodata) {
                             dist = stream_dist + grid_lengths[n];
                             output.set_value(row_n, col_n, dist);
                             stack.push((row_n, col_n, dist));
@@ -491,17 +491,19 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            e0 = input[(row, col)];
-                            if e0 != nodata {
+                            e0 = input.get_value(row, col);
+                            if !is_nodata_value(e0, nodata) {
                                 dir = 360.0;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
                                     ac = ac_vals[i];
                                     af = af_vals[i];
-                                    e1 = input[(row + e1_row[i], col + e1_col[i])];
-                                    e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                    if e1 != nodata && e2 != nodata {
+                                    e1 = input.get_value(row + e1_row[i], col + e1_col[i]);
+                                    e2 = input.get_value(row + e2_row[i], col + e2_col[i]);
+                                    if !is_nodata_value(e1, nodata)
+                                        && !is_nodata_value(e2, nodata)
+                                    {
                                         if e0 > e1 && e0 > e2 {
                                             s1 = (e0 - e1) / grid_res;
                                             s2 = (e1 - e2) / grid_res;
@@ -597,7 +599,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
             for row in 0..rows {
                 for col in 0..columns {
                     z = dem.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         dir = flow_dir.get_value(row, col);
                         if dir != -1.0 {
                             if dir == 0.0
@@ -351,7 +351,10 @@ impl WhiteboxTool for DownslopeFlowpathLength {
         for row in 0..rows {
             for col in 0..columns {
                 current_id = watersheds.get_value(row, col) as f64;
-                if pntr.get_value(row, col) >= 0.0 && pntr.get_value(row, col) != nodata && current_id > 0f64 {
+                if pntr.get_value(row, col) >= 0.0
+                    && !is_nodata_value(pntr.get_value(row, col), nodata)
+                    && current_id > 0f64
+                {
                     dist = 0f64;
                     flag = false;
                     x = col;
@@ -359,7 +362,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
                     while !flag {
                         // find its downslope neighbour
                         dir = pntr.get_value(y, x);
-                        if dir > 0f64 && dir != nodata {
+                        if dir > 0f64 && !is_nodata_value(dir, nodata) {
                             if dir > 128f64 || pntr_matches[dir as usize] == 999 {
                                 return Err(Error::new(ErrorKind::InvalidInput,
                                     "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
@@ -390,7 +393,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
 
                         // find its downslope neighbour
                         dir = pntr.get_value(y, x);
-                        if dir > 0f64 && dir != nodata {
+                        if dir > 0f64 && !is_nodata_value(dir, nodata) {
                             // move x and y accordingly
                             c = pntr_matches[dir as usize];
                             x += dx[c];
@@ -273,14 +273,14 @@ impl WhiteboxTool for ElevationAboveStream {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![flow_nodata; columns as usize];
                     for col in 0..columns {
-                        z = dem[(row, col)];
-                        if z != nodata {
+                        z = dem.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
-                                z_n = dem[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                z_n = dem.get_value(row + dy[i], col + dx[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -319,18 +319,20 @@ impl WhiteboxTool for ElevationAboveStream {
                 interior_pit_found = true;
             }
             for col in 0..columns {
-                if streams[(row, col)] > 0f64 && streams[(row, col)] != streams_nodata {
-                    output[(row, col)] = 0f64;
-                    stack.push((row, col, dem[(row, col)]));
+                if streams.get_value(row, col) > 0f64
+                    && !is_nodata_value(streams.get_value(row, col), streams_nodata)
+                {
+                    output.set_value(row, col, 0f64);
+                    stack.push((row, col, dem.get_value(row, col)));
                 }
-                if dem[(row, col)] == nodata {
-                    output[(row, col)] = nodata;
+                if is_nodata_value(dem.get_value(row, col), nodata) {
+                    output.set_value(row, col, nodata);
                     num_solved_cells += 1;
                 }
                 if flow_dir[(row, col)] == -1 {
-                    if output[(row, col)] != 0f64 {
+                    if output.get_value(row, col) != 0f64 {
                         stack.push((row, col, nodata));
-                        output[(row, col)] = nodata;
+                        output.set_value(row, col, nodata);
                         num_solved_cells += 1;
                     }
                 }
@@ -357,13 +359,13 @@ impl WhiteboxTool for ElevationAboveStream {
                 row_n = row + dy[n];
                 col_n = col + dx[n];
                 if flow_dir[(row_n, col_n)] == inflowing_vals[n]
-                    && output[(row_n, col_n)] == background_value
+                    && output.get_value(row_n, col_n) == background_value
                 {
                     stack.push((row_n, col_n, stream_elev));
-                    if stream_elev != nodata {
-                        output[(row_n, col_n)] = dem[(row_n, col_n)] - stream_elev;
+                    if !is_nodata_value(stream_elev, nodata) {
+                        output.set_value(row_n, col_n, dem.get_value(row_n, col_n) - stream_elev);
                     } else {
-                        output[(row_n, col_n)] = nodata;
+                        output.set_value(row_n, col_n, nodata);
                     }
                 }
             }
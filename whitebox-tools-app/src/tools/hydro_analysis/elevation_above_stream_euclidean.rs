@@ -242,7 +242,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata_value(z, nodata) {
                     distance.set_value(row, col, 0.0);
                     allocation.set_value(row, col, dem.get_value(row, col));
                 } else {
@@ -269,7 +269,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata_value(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -312,7 +312,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata_value(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -347,8 +347,8 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z == nodata {
+                z = input.get_value(row, col);
+                if is_nodata_value(z, nodata) {
                     allocation.set_value(row, col, nodata);
                 } else {
                     allocation.set_value(
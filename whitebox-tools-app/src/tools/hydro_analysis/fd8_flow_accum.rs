@@ -351,11 +351,11 @@ impl WhiteboxTool for FD8FlowAccumulation {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 zn = input.get_value(row + d_y[i], col + d_x[i]);
-                                if zn > z && zn != nodata {
+                                if zn > z && !is_nodata_value(zn, nodata) {
                                     count += 1;
                                 }
                             }
@@ -417,8 +417,8 @@ impl WhiteboxTool for FD8FlowAccumulation {
             let cell = stack.pop().expect("Error during pop operation.");
             row = cell.0;
             col = cell.1;
-            z = input[(row, col)];
-            fa = output[(row, col)];
+            z = input.get_value(row, col);
+            fa = output.get_value(row, col);
             num_inflowing[(row, col)] = -1i8;
 
             total_weights = 0.0;
@@ -428,8 +428,8 @@ impl WhiteboxTool for FD8FlowAccumulation {
                 for i in 0..8 {
                     row_n = row + d_y[i];
                     col_n = col + d_x[i];
-                    z_n = input[(row_n, col_n)];
-                    if z_n < z && z_n != nodata {
+                    z_n = input.get_value(row_n, col_n);
+                    if z_n < z && !is_nodata_value(z_n, nodata) {
                         slope = (z - z_n) / grid_lengths[i];
                         weights[i] = slope.powf(exponent);
                         total_weights += weights[i];
@@ -441,8 +441,8 @@ impl WhiteboxTool for FD8FlowAccumulation {
                 dir = 0i8;
                 max_slope = f64::MIN;
                 for i in 0..8 {
-                    z_n = input[(row + d_y[i], col + d_x[i])];
-                    if z_n != nodata {
+                    z_n = input.get_value(row + d_y[i], col + d_x[i]);
+                    if !is_nodata_value(z_n, nodata) {
                         slope = (z - z_n) / grid_lengths[i];
                         if slope > 0f64 {
                             downslope[i] = true;
@@ -495,10 +495,14 @@ impl WhiteboxTool for FD8FlowAccumulation {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
-                        output[(row, col)] = (output[(row, col)] * cell_area / avg_cell_size).ln();
+                        output.set_value(
+                            row,
+                            col,
+                            (output.get_value(row, col) * cell_area / avg_cell_size).ln(),
+                        );
                     }
                 }
 
@@ -513,10 +517,14 @@ impl WhiteboxTool for FD8FlowAccumulation {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
-                        output[(row, col)] = output[(row, col)] * cell_area / avg_cell_size;
+                        output.set_value(
+                            row,
+                            col,
+                            output.get_value(row, col) * cell_area / avg_cell_size,
+                        );
                     }
                 }
 
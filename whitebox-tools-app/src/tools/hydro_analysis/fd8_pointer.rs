@@ -231,12 +231,12 @@ impl WhiteboxTool for FD8Pointer {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![out_nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0_f64;
                             for n in 0..8 {
-                                zn = input[(row + dy[n], col + dx[n])];
-                                if zn < z && zn != nodata {
+                                zn = input.get_value(row + dy[n], col + dx[n]);
+                                if zn < z && !is_nodata_value(zn, nodata) {
                                     dir += (1 << n) as f64;
                                 }
                             }
@@ -452,9 +452,13 @@ impl WhiteboxTool for FillBurn {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = dem.get_value(row, col);
-                        if raster_streams.get_value(row, col) == 0u8 && z != nodata {
+                        if raster_streams.get_value(row, col) == 0u8
+                            && !is_nodata_value(z, nodata)
+                        {
                             data[col as usize] = z;
-                        } else if raster_streams.get_value(row, col) == 1u8 && z != nodata {
+                        } else if raster_streams.get_value(row, col) == 1u8
+                            && !is_nodata_value(z, nodata)
+                        {
                             data[col as usize] = z - 10000f64;
                         }
                     }
@@ -529,7 +533,7 @@ impl WhiteboxTool for FillBurn {
                 row_n = row + dy[n];
                 col_n = col + dx[n];
                 if in_queue.get_value(row_n, col_n) == 0u8 {
-                    if dem.get_value(row_n, col_n) == nodata {
+                    if is_nodata_value(dem.get_value(row_n, col_n), nodata) {
                         queue.push_back((row_n, col_n));
                     } else {
                         // Push it onto the priority queue for the priority flood operation
@@ -572,7 +576,7 @@ impl WhiteboxTool for FillBurn {
                 if in_queue.get_value(row_n, col_n) == 0u8 {
                     zout_n = output.get_value(row_n, col_n);
                     // zin_n = input[(row_n, col_n)];
-                    if zout_n != nodata {
+                    if !is_nodata_value(zout_n, nodata) {
                         if zout_n < (zout + small_num) {
                             zout_n = zout + small_num;
                         } // We're in a depression. Raise the elevation.
@@ -607,7 +611,9 @@ impl WhiteboxTool for FillBurn {
         let mut min_diff = f64::INFINITY;
         for row in 0..rows {
             for col in 0..columns {
-                if raster_streams.get_value(row, col) > 0u8 && dem.get_value(row, col) != nodata {
+                if raster_streams.get_value(row, col) > 0u8
+                    && !is_nodata_value(dem.get_value(row, col), nodata)
+                {
                     z = dem.get_value(row, col) - output.get_value(row, col);
                     if z < min_diff {
                         min_diff = z;
@@ -627,7 +633,9 @@ impl WhiteboxTool for FillBurn {
 
         for row in 0..rows {
             for col in 0..columns {
-                if raster_streams.get_value(row, col) > 0u8 && dem.get_value(row, col) != nodata {
+                if raster_streams.get_value(row, col) > 0u8
+                    && !is_nodata_value(dem.get_value(row, col), nodata)
+                {
                     z = output.get_value(row, col) + min_diff;
                     output.set_value(row, col, z);
                 }
@@ -328,11 +328,11 @@ impl WhiteboxTool for FillDepressions {
                 for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
                     for col in 1..columns - 1 {
                         z = output2.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             flag = true;
                             for n in 0..8 {
                                 zn = output2.get_value(row + dy[n], col + dx[n]);
-                                if zn < z || zn == nodata {
+                                if zn < z || is_nodata_value(zn, nodata) {
                                     // It either has a lower neighbour or is an edge cell.
                                     flag = false;
                                     break;
@@ -345,6 +345,9 @@ impl WhiteboxTool for FillDepressions {
                         }
                     }
                 }
+                // The Arc must be released before the pits are sent, since it is unwrapped
+                // once they have all been received.
+                drop(output2);
                 tx.send(pits).unwrap();
             });
         }
@@ -421,14 +424,14 @@ impl WhiteboxTool for FillDepressions {
                             if visited.get_value(rn, cn) == 0 {
                                 zn = output.get_value(rn, cn);
                                 if !outlet_found {
-                                    if zn >= z && zn != nodata {
+                                    if zn >= z && !is_nodata_value(zn, nodata) {
                                         minheap.push(GridCell {
                                             row: rn,
                                             column: cn,
                                             priority: zn,
                                         });
                                         visited.set_value(rn, cn, 1);
-                                    } else if zn != nodata {
+                                    } else if !is_nodata_value(zn, nodata) {
                                         // zn < z
                                         // 'cell' has a lower neighbour that hasn't already passed through minheap.
                                         // Therefore, 'cell' is a pour point cell.
@@ -543,7 +546,7 @@ impl WhiteboxTool for FillDepressions {
                     rn = cell.0 + dy[n];
                     cn = cell.1 + dx[n];
                     zn = output.get_value(rn, cn);
-                    if zn < z && zn != nodata {
+                    if zn < z && !is_nodata_value(zn, nodata) {
                         flag = true;
                         break;
                     }
@@ -597,7 +600,7 @@ impl WhiteboxTool for FillDepressions {
                             cn = cell2.column + dx[n];
                             if flats.get_value(rn, cn) != 3 {
                                 zn = output.get_value(rn, cn);
-                                if zn == z && zn != nodata {
+                                if zn == z && !is_nodata_value(zn, nodata) {
                                     // queue.push_back((rn, cn, z));
                                     minheap2.push(GridCell2 {
                                         row: rn,
@@ -619,7 +622,10 @@ impl WhiteboxTool for FillDepressions {
                             cn = cell2.column + dx[n];
                             if flats.get_value(rn, cn) != 3 {
                                 zn = output.get_value(rn, cn);
-                                if zn < z + small_num && zn >= cell2.z && zn != nodata {
+                                if zn < z + small_num
+                                    && zn >= cell2.z
+                                    && !is_nodata_value(zn, nodata)
+                                {
                                     // queue.push_back((rn, cn, cell2.2));
                                     minheap2.push(GridCell2 {
                                         row: rn,
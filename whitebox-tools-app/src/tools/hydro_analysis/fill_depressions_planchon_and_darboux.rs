@@ -279,7 +279,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
         for row in 0..rows {
             z = input.get_value(row, 0);
             w = output.get_value(row, 0);
-            if z != nodata {
+            if !is_nodata_value(z, nodata) {
                 output.set_value(row, 0, z);
             } else if w == large_value {
                 output.set_value(row, 0, nodata_output);
@@ -291,7 +291,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata_value(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -304,7 +304,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
 
             z = input.get_value(row, columns - 1);
             w = output.get_value(row, columns - 1);
-            if z != nodata {
+            if !is_nodata_value(z, nodata) {
                 output.set_value(row, columns - 1, z);
             } else if w == large_value {
                 output.set_value(row, columns - 1, nodata_output);
@@ -316,7 +316,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata_value(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -331,7 +331,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
         for col in 0..columns {
             z = input.get_value(0, col);
             w = output.get_value(0, col);
-            if z != nodata {
+            if !is_nodata_value(z, nodata) {
                 output.set_value(0, col, z);
             } else if w == large_value {
                 output.set_value(0, col, nodata_output);
@@ -343,7 +343,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata_value(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -356,7 +356,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
 
             z = input.get_value(rows - 1, col);
             w = output.get_value(rows - 1, col);
-            if z != nodata {
+            if !is_nodata_value(z, nodata) {
                 output.set_value(rows - 1, col, z);
             } else if w == large_value {
                 output.set_value(rows - 1, col, nodata_output);
@@ -368,7 +368,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata_value(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -392,13 +392,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in 1..columns - 1 {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata_value(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata_value(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -431,13 +431,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in (1..columns - 1).rev() {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata_value(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata_value(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -470,13 +470,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in (1..columns - 1).rev() {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata_value(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata_value(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -510,13 +510,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in 1..columns - 1 {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata_value(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata_value(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -213,8 +213,8 @@ impl WhiteboxTool for FillSingleCellPits {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             flag = true;
                             min_zn = f64::INFINITY;
                             for n in 0..8 {
@@ -222,7 +222,7 @@ impl WhiteboxTool for FillSingleCellPits {
                                 if zn < min_zn {
                                     min_zn = zn;
                                 }
-                                if zn != nodata && zn < z {
+                                if !is_nodata_value(zn, nodata) && zn < z {
                                     flag = false;
                                     break;
                                 }
@@ -211,11 +211,11 @@ impl WhiteboxTool for FindNoFlowCells {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             has_no_lower_neighbour = 1.0;
                             for n in 0..8 {
                                 zn = input.get_value(row + dy[n], col + dx[n]);
-                                if zn < z && zn != nodata {
+                                if zn < z && !is_nodata_value(zn, nodata) {
                                     has_no_lower_neighbour = nodata;
                                     break;
                                 }
@@ -192,6 +192,10 @@ impl WhiteboxTool for FindParallelFlow {
         if !d8_file.contains(&sep) && !d8_file.contains("/") {
             d8_file = format!("{}{}", working_directory, d8_file);
         }
+        if !streams_file.is_empty() && !streams_file.contains(&sep) && !streams_file.contains("/")
+        {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
         if !output_file.contains(&sep) && !output_file.contains("/") {
             output_file = format!("{}{}", working_directory, output_file);
         }
@@ -253,18 +257,21 @@ impl WhiteboxTool for FindParallelFlow {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = pntr[(row, col)];
+                        z = pntr.get_value(row, col);
                         stream_val = streams[(row, col)];
-                        if z != nodata && stream_val != streams_nodata && stream_val > 0f64 {
+                        if !is_nodata_value(z, nodata)
+                            && !is_nodata_value(stream_val, streams_nodata)
+                            && stream_val > 0f64
+                        {
                             is_parallel = false;
                             for n in 0..8 {
                                 if z != outflowing_vals[n] {
-                                    zn = pntr[(row + dy[n], col + dx[n])];
+                                    zn = pntr.get_value(row + dy[n], col + dx[n]);
                                     stream_valn = streams[(row + dy[n], col + dx[n])];
                                     if zn == z
                                         && zn != inflowing_vals[n]
                                         && stream_valn > 0f64
-                                        && stream_valn != streams_nodata
+                                        && !is_nodata_value(stream_valn, streams_nodata)
                                     {
                                         is_parallel = true;
                                         break;
@@ -331,7 +331,7 @@ impl WhiteboxTool for FlattenLakes {
                                         let col = input.get_column_from_x(x_prime);
 
                                         z = input.get_value(row, col);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             if z < min_elevs[feature_num] {
                                                 min_elevs[feature_num] = z;
                                             }
@@ -362,7 +362,7 @@ impl WhiteboxTool for FlattenLakes {
                                         let row = output.get_row_from_y(y_prime);
 
                                         z = input.get_value(row, col);
-                                        if z != nodata {
+                                        if !is_nodata_value(z, nodata) {
                                             if z < min_elevs[feature_num] {
                                                 min_elevs[feature_num] = z;
                                             }
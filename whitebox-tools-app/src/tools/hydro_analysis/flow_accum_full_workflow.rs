@@ -354,11 +354,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             for c in 0..8 {
-                                n[c] = input[(row + dy[c], col + dx[c])];
-                                if n[c] != nodata {
+                                n[c] = input.get_value(row + dy[c], col + dx[c]);
+                                if !is_nodata_value(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z * z_factor;
@@ -454,11 +454,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
             for n in 0..8 {
                 row_n = row + dy[n];
                 col_n = col + dx[n];
-                zin_n = input[(row_n, col_n)];
-                zout_n = output[(row_n, col_n)];
+                zin_n = input.get_value(row_n, col_n);
+                zout_n = output.get_value(row_n, col_n);
                 if zout_n == background_val {
-                    if zin_n == nodata {
-                        output[(row_n, col_n)] = nodata;
+                    if is_nodata_value(zin_n, nodata) {
+                        output.set_value(row_n, col_n, nodata);
                         queue.push_back((row_n, col_n));
                     } else {
                         // see if it's the lowest of its neighbours
@@ -466,13 +466,15 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                         for p in 0..8 {
                             y = row_n + dy[p];
                             x = col_n + dx[p];
-                            if input[(y, x)] < zin_n && input[(y, x)] != nodata {
+                            if input.get_value(y, x) < zin_n
+                                && !is_nodata_value(input.get_value(y, x), nodata)
+                            {
                                 is_lowest = false;
                                 break;
                             }
                         }
                         if is_lowest {
-                            output[(row_n, col_n)] = zin_n;
+                            output.set_value(row_n, col_n, zin_n);
                             // Push it onto the priority queue for the priority flood operation
                             minheap.push(GridCell {
                                 row: row_n,
@@ -507,21 +509,21 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
             let cell = minheap.pop().expect("Error during pop operation.");
             row = cell.row;
             col = cell.column;
-            zout = output[(row, col)];
+            zout = output.get_value(row, col);
             for n in 0..8 {
                 row_n = row + dy[n];
                 col_n = col + dx[n];
-                zout_n = output[(row_n, col_n)];
+                zout_n = output.get_value(row_n, col_n);
                 if zout_n == background_val {
-                    zin_n = input[(row_n, col_n)];
-                    if zin_n != nodata {
+                    zin_n = input.get_value(row_n, col_n);
+                    if !is_nodata_value(zin_n, nodata) {
                         flow_dir[(row_n, col_n)] = back_link[n];
 
                         // if zin_n < (zout + small_num) { zin_n = zout + small_num; } // We're in a depression. Raise the elevation.
                         // output[(row_n, col_n)] = zin_n;
                         // minheap.push(GridCell{ row: row_n, column: col_n, priority: zin_n });
 
-                        output[(row_n, col_n)] = zin_n;
+                        output.set_value(row_n, col_n, zin_n);
                         minheap.push(GridCell {
                             row: row_n,
                             column: col_n,
@@ -531,7 +533,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                             // Trace the flowpath back to a lower cell, if it exists.
                             x = col_n;
                             y = row_n;
-                            z_target = output[(row_n, col_n)];
+                            z_target = output.get_value(row_n, col_n);
                             flag = true;
                             while flag {
                                 dir = flow_dir[(y, x)];
@@ -539,8 +541,8 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                                     y += dy[dir as usize];
                                     x += dx[dir as usize];
                                     z_target -= small_num;
-                                    if output[(y, x)] > z_target {
-                                        output[(y, x)] = z_target;
+                                    if output.get_value(y, x) > z_target {
+                                        output.set_value(y, x, z_target);
                                     } else {
                                         flag = false;
                                     }
@@ -551,10 +553,13 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                         }
                     } else {
                         // Interior nodata cells are still treated as nodata and are not filled.
-                        output[(row_n, col_n)] = nodata;
+                        output.set_value(row_n, col_n, nodata);
                         num_solved_cells += 1;
                     }
-                } else if zout_n > zout && zout_n != nodata && aspect[(row_n, col_n)] != nodata {
+                } else if zout_n > zout
+                    && !is_nodata_value(zout_n, nodata)
+                    && !is_nodata_value(aspect[(row_n, col_n)], nodata)
+                {
                     /* Check to see if the flow direction could be improved; if so, capture its flow.
                     This is the main logic for the flow direction calculation. Basically,
                     we link cells to the neighbour that has the closest flow direction to the
@@ -647,9 +652,9 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                         dir_no = flow_dir.get_value(row + dy[r1 as usize], col + dx[r1 as usize]); // right
                         zr = output.get_value(row + dy[r1 as usize], col + dx[r1 as usize]);
                         zn = output.get_value(row + dy[dir as usize], col + dx[dir as usize]);
-                        if dir_n == r2 && zr != nodata && zr <= zn { // left -> right cross && not nodata && is lower
+                        if dir_n == r2 && !is_nodata_value(zr, nodata) && zr <= zn { // left -> right cross && not nodata && is lower
                             new_val = r1;
-                        } else if dir_no == l2 && zl != nodata && zl <= zn { // right -> left cross && not nodata && is lower
+                        } else if dir_no == l2 && !is_nodata_value(zl, nodata) && zl <= zn { // right -> left cross && not nodata && is lower
                             new_val = l1;
                         } else { // keep original value
                             new_val = dir;
@@ -763,8 +768,8 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + dy[i], col + dx[i])] == inflowing_vals[i] {
@@ -812,7 +817,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
             let cell = stack.pop().expect("Error during pop operation.");
             row = cell.0;
             col = cell.1;
-            fa = output[(row, col)];
+            fa = output.get_value(row, col);
             num_inflowing.decrement(row, col, 1i8);
             dir = flow_dir[(row, col)];
             if dir >= 0 {
@@ -862,18 +867,25 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
                         let dir = flow_dir[(row, col)];
                         if dir >= 0 {
-                            output[(row, col)] =
-                                (output[(row, col)] * cell_area / flow_widths[dir as usize]).ln();
-                            pntr[(row, col)] = pntr_vals[flow_dir[(row, col)] as usize];
+                            output.set_value(
+                                row,
+                                col,
+                                (output.get_value(row, col) * cell_area / flow_widths[dir as usize])
+                                    .ln(),
+                            );
+                            pntr.set_value(row, col, pntr_vals[flow_dir[(row, col)] as usize]);
                         } else {
-                            output[(row, col)] =
-                                (output[(row, col)] * cell_area / flow_widths[3]).ln();
-                            pntr[(row, col)] = 0f64;
+                            output.set_value(
+                                row,
+                                col,
+                                (output.get_value(row, col) * cell_area / flow_widths[3]).ln(),
+                            );
+                            pntr.set_value(row, col, 0f64);
                         }
                     }
                 }
@@ -889,17 +901,24 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
                         let dir = flow_dir[(row, col)];
                         if dir >= 0 {
-                            output[(row, col)] =
-                                output[(row, col)] * cell_area / flow_widths[dir as usize];
-                            pntr[(row, col)] = pntr_vals[flow_dir[(row, col)] as usize];
+                            output.set_value(
+                                row,
+                                col,
+                                output.get_value(row, col) * cell_area / flow_widths[dir as usize],
+                            );
+                            pntr.set_value(row, col, pntr_vals[flow_dir[(row, col)] as usize]);
                         } else {
-                            output[(row, col)] = output[(row, col)] * cell_area / flow_widths[3];
-                            pntr[(row, col)] = 0f64;
+                            output.set_value(
+                                row,
+                                col,
+                                output.get_value(row, col) * cell_area / flow_widths[3],
+                            );
+                            pntr.set_value(row, col, 0f64);
                         }
                     }
                 }
@@ -249,15 +249,17 @@ impl WhiteboxTool for FlowLengthDiff {
         let (mut x, mut y): (isize, isize);
         for row in 0..rows {
             for col in 0..columns {
-                if pntr[(row, col)] >= 0.0 && pntr[(row, col)] != nodata {
+                if pntr.get_value(row, col) >= 0.0
+                    && !is_nodata_value(pntr.get_value(row, col), nodata)
+                {
                     dist = 0f64;
                     flag = false;
                     x = col;
                     y = row;
                     while !flag {
                         // find its downslope neighbour
-                        dir = pntr[(y, x)];
-                        if dir > 0f64 && dir != nodata {
+                        dir = pntr.get_value(y, x);
+                        if dir > 0f64 && !is_nodata_value(dir, nodata) {
                             if dir > 128f64 || pntr_matches[dir as usize] == 999 {
                                 return Err(Error::new(ErrorKind::InvalidInput,
                                     "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
@@ -285,8 +287,8 @@ impl WhiteboxTool for FlowLengthDiff {
                         dfl[(y, x)] = dist;
 
                         // find its downslope neighbour
-                        dir = pntr[(y, x)];
-                        if dir > 0f64 && dir != nodata {
+                        dir = pntr.get_value(y, x);
+                        if dir > 0f64 && !is_nodata_value(dir, nodata) {
                             // move x and y accordingly
                             c = pntr_matches[dir as usize];
                             x += dx[c];
@@ -325,24 +327,24 @@ impl WhiteboxTool for FlowLengthDiff {
         for row in 0..rows {
             for col in 0..columns {
                 z = dfl[(row, col)];
-                if z != out_nodata {
+                if !is_nodata_value(z, out_nodata) {
                     max_abs_diff = f64::NEG_INFINITY;
                     // Use 4-neighbour connectedness
                     for n in (0..8).filter(|x| x % 2 == 1) {
                         zn = dfl[(row + dy[n], col + dx[n])];
-                        if zn != out_nodata {
+                        if !is_nodata_value(zn, out_nodata) {
                             if (z - zn).abs() > max_abs_diff {
                                 max_abs_diff = (z - zn).abs();
                             }
                         }
                     }
                     if max_abs_diff != f64::NEG_INFINITY {
-                        output[(row, col)] = max_abs_diff;
+                        output.set_value(row, col, max_abs_diff);
                     } else {
-                        output[(row, col)] = out_nodata;
+                        output.set_value(row, col, out_nodata);
                     }
                 } else {
-                    output[(row, col)] = out_nodata;
+                    output.set_value(row, col, out_nodata);
                 }
             }
             if verbose {
@@ -264,11 +264,13 @@ impl WhiteboxTool for Hillslopes {
         let mut current_id = 1f64;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 && streams[(row, col)] != nodata {
+                if streams.get_value(row, col) > 0.0
+                    && !is_nodata_value(streams.get_value(row, col), nodata)
+                {
                     count = 0i8;
                     for i in 0..8 {
-                        if streams[(row + dy[i], col + dx[i])] > 0.0
-                            && pntr[(row + dy[i], col + dx[i])] == inflowing_vals[i]
+                        if streams.get_value(row + dy[i], col + dx[i]) > 0.0
+                            && pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                         {
                             count += 1;
                         }
@@ -282,7 +284,7 @@ impl WhiteboxTool for Hillslopes {
                         current_id += 1f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
                         pourpts[(row, col)] = 0.0;
                     } else {
                         pourpts[(row, col)] = nodata;
@@ -341,7 +343,7 @@ impl WhiteboxTool for Hillslopes {
             val = pourpts[(row, col)];
 
             // find the downstream cell
-            dir = pntr[(row, col)] as usize;
+            dir = pntr.get_value(row, col) as usize;
             if dir > 0 {
                 if dir > 128 || pntr_matches[dir] == 999 {
                     return Err(Error::new(ErrorKind::InvalidInput,
@@ -353,7 +355,7 @@ impl WhiteboxTool for Hillslopes {
                 if num_inflowing[(row_n, col_n)] > 1 {
                     current_id += 1f64;
                     pourpts[(row_n, col_n)] = current_id;
-                } else if pourpts[(row_n, col_n)] == nodata {
+                } else if is_nodata_value(pourpts[(row_n, col_n)], nodata) {
                     pourpts[(row_n, col_n)] = val;
                 }
 
@@ -392,12 +394,12 @@ impl WhiteboxTool for Hillslopes {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if pntr[(row, col)] == pntr_nodata {
-                    output[(row, col)] = nodata;
+                if is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                    output.set_value(row, col, nodata);
                 }
                 z = pourpts[(row, col)];
-                if z != nodata && z > 0.0 {
-                    output[(row, col)] = z;
+                if !is_nodata_value(z, nodata) && z > 0.0 {
+                    output.set_value(row, col, z);
                 }
             }
             if verbose {
@@ -414,20 +416,20 @@ impl WhiteboxTool for Hillslopes {
         let mut outlet_id: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if output[(row, col)] == low_value {
+                if output.get_value(row, col) == low_value {
                     flag = false;
                     x = col;
                     y = row;
                     outlet_id = nodata;
                     while !flag {
-                        dir = pntr[(y, x)] as usize;
+                        dir = pntr.get_value(y, x) as usize;
                         if dir > 0 {
                             c = pntr_matches[dir];
                             y += dy[c];
                             x += dx[c];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            z = output[(y, x)];
+                            z = output.get_value(y, x);
                             if z != low_value {
                                 outlet_id = z;
                                 flag = true;
@@ -440,23 +442,23 @@ impl WhiteboxTool for Hillslopes {
                     flag = false;
                     x = col;
                     y = row;
-                    output[(y, x)] = outlet_id;
+                    output.set_value(y, x, outlet_id);
                     while !flag {
                         // find its downslope neighbour
-                        dir = pntr[(y, x)] as usize;
+                        dir = pntr.get_value(y, x) as usize;
                         if dir > 0 {
                             c = pntr_matches[dir];
                             y += dy[c];
                             x += dx[c];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            if output[(y, x)] != low_value {
+                            if output.get_value(y, x) != low_value {
                                 flag = true;
                             }
                         } else {
                             flag = true;
                         }
-                        output[(y, x)] = outlet_id;
+                        output.set_value(y, x, outlet_id);
                     }
                 }
             }
@@ -472,8 +474,10 @@ impl WhiteboxTool for Hillslopes {
         // Replace all stream cells with 0's
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0f64 && streams[(row, col)] != nodata {
-                    output[(row, col)] = 0f64;
+                if streams.get_value(row, col) > 0f64
+                    && !is_nodata_value(streams.get_value(row, col), nodata)
+                {
+                    output.set_value(row, col, 0f64);
                 }
             }
             if verbose {
@@ -503,33 +507,37 @@ impl WhiteboxTool for Hillslopes {
         for row in 0..rows {
             for col in 0..columns {
                 if visited[(row, col)] > 0
-                    && pntr[(row, col)] != pntr_nodata
-                    && output[(row, col)] > 0f64
+                    && !is_nodata_value(pntr.get_value(row, col), pntr_nodata)
+                    && output.get_value(row, col) > 0f64
                 {
                     current_id += 1f64;
-                    old_id = output[(row, col)];
+                    old_id = output.get_value(row, col);
                     stack.push((row, col));
                     while !stack.is_empty() {
                         let cell = stack.pop().expect("Error during pop operation.");
                         row2 = cell.0;
                         col2 = cell.1;
-                        output[(row2, col2)] = current_id;
+                        output.set_value(row2, col2, current_id);
                         visited[(row2, col2)] = 0;
 
                         for n in 0..8 {
                             y = row2 + dy[n];
                             x = col2 + dx[n];
-                            if output[(y, x)] == old_id && visited[(y, x)] > 0 {
+                            if output.get_value(y, x) == old_id && visited[(y, x)] > 0 {
                                 let diag = card1[n];
                                 if diag == 8 {
                                     // its a cardinal direction
                                     stack.push((y, x));
                                 } else {
                                     // clumping can't cross a stream via a diagonal
-                                    if streams[(row2 + dy[card2[diag]], col2 + dx[card2[diag]])]
-                                        == 0f64
-                                        || streams[(row2 + dy[card3[diag]], col2 + dx[card3[diag]])]
-                                            == 0f64
+                                    if streams.get_value(
+                                        row2 + dy[card2[diag]],
+                                        col2 + dx[card2[diag]],
+                                    ) == 0f64
+                                        || streams.get_value(
+                                            row2 + dy[card3[diag]],
+                                            col2 + dx[card3[diag]],
+                                        ) == 0f64
                                     {
                                         stack.push((y, x));
                                     }
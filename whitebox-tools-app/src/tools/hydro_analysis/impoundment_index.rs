@@ -396,7 +396,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     for dir in 0..4 {
                         // what's the perpendicular direction?
                         perp_dir1 = perpendicular1[dir];
@@ -412,7 +412,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n += dy[perp_dir1 as usize];
                             c_n += dx[perp_dir1 as usize];
                             z_n = input.get_value(r_n, c_n);
-                            if z_n != nodata {
+                            if !is_nodata_value(z_n, nodata) {
                                 dam_profile[half_dam_length + i as usize] = z_n;
                             } else {
                                 dam_profile[half_dam_length + i as usize] = f64::NEG_INFINITY;
@@ -421,7 +421,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n2 += dy[perp_dir2 as usize];
                             c_n2 += dx[perp_dir2 as usize];
                             z_n = input.get_value(r_n2, c_n2);
-                            if z_n != nodata {
+                            if !is_nodata_value(z_n, nodata) {
                                 dam_profile[half_dam_length - i] = z_n;
                             } else {
                                 dam_profile[half_dam_length - i] = f64::NEG_INFINITY;
@@ -460,7 +460,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n += dy[perp_dir1 as usize];
                             c_n += dx[perp_dir1 as usize];
                             z_n = input.get_value(r_n, c_n);
-                            if z_n != nodata {
+                            if !is_nodata_value(z_n, nodata) {
                                 if dam_profile_filled[half_dam_length + i as usize]
                                     > crest_elev.get_value(r_n, c_n)
                                 {
@@ -475,7 +475,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n2 += dy[perp_dir2 as usize];
                             c_n2 += dx[perp_dir2 as usize];
                             z_n = input.get_value(r_n2, c_n2);
-                            if z_n != nodata {
+                            if !is_nodata_value(z_n, nodata) {
                                 if dam_profile_filled[half_dam_length - i as usize]
                                     > crest_elev.get_value(r_n2, c_n2)
                                 {
@@ -573,7 +573,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 zin_n = input.get_value(row_n, col_n);
                 zout_n = filled_dem.get_value(row_n, col_n);
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata_value(zin_n, nodata) {
                         filled_dem.set_value(row_n, col_n, nodata);
                         queue.push_back((row_n, col_n));
                         num_nodata_cells += 1;
@@ -619,7 +619,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 zout_n = filled_dem.get_value(row_n, col_n);
                 if zout_n == background_val {
                     zin_n = crest_elev.get_value(row_n, col_n);
-                    if zin_n != nodata {
+                    if !is_nodata_value(zin_n, nodata) {
                         flow_dir.set_value(row_n, col_n, back_link[n]);
                         count += 1;
                         if zin_n < zout {
@@ -787,7 +787,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         output_hgt.set_value(row, col, crest_elev.get_value(row, col) - z);
 
                         if output_hgt.get_value(row, col) <= 0f64 {
@@ -841,7 +841,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let mut vol_f32: f32;
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata_value(input.get_value(row, col), nodata) {
                     area = out_area.get_value(row, col);
                     if area != 0f32 {
                         vol_f32 = out_volume.get_value(row, col);
@@ -289,7 +289,7 @@ impl WhiteboxTool for InsertDams {
                     ..=(target_col + half_dam_length as isize)
                 {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         // dam_z = z;
                         for dir in 0..4 {
                             profile_intersects_target = false;
@@ -313,7 +313,7 @@ impl WhiteboxTool for InsertDams {
                                     target_cell = half_dam_length + i as usize;
                                 }
                                 z_n = input.get_value(r_n, c_n);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     dam_profile[half_dam_length + i as usize] = z_n;
                                 } else {
                                     dam_profile[half_dam_length + i as usize] = f64::NEG_INFINITY;
@@ -326,7 +326,7 @@ impl WhiteboxTool for InsertDams {
                                     target_cell = half_dam_length - i as usize;
                                 }
                                 z_n = input.get_value(r_n2, c_n2);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     dam_profile[half_dam_length - i] = z_n;
                                 } else {
                                     dam_profile[half_dam_length - i] = f64::NEG_INFINITY;
@@ -393,7 +393,7 @@ impl WhiteboxTool for InsertDams {
                     r_n += dy[perp_dir1 as usize];
                     c_n += dx[perp_dir1 as usize];
                     z_n = input.get_value(r_n, c_n);
-                    if z_n != nodata {
+                    if !is_nodata_value(z_n, nodata) {
                         if best_dam_profile_filled[half_dam_length + i as usize]
                             > output.get_value(r_n, c_n)
                         {
@@ -420,7 +420,7 @@ impl WhiteboxTool for InsertDams {
                     r_n2 += dy[perp_dir2 as usize];
                     c_n2 += dx[perp_dir2 as usize];
                     z_n = input.get_value(r_n2, c_n2);
-                    if z_n != nodata {
+                    if !is_nodata_value(z_n, nodata) {
                         if best_dam_profile_filled[half_dam_length - i as usize]
                             > output.get_value(r_n2, c_n2)
                         {
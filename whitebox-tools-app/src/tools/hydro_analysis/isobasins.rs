@@ -280,14 +280,14 @@ impl WhiteboxTool for Isobasins {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
-                                z_n = input[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                z_n = input.get_value(row + dy[i], col + dx[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -344,7 +344,7 @@ impl WhiteboxTool for Isobasins {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     count = 0i8;
                     for i in 0..8 {
                         if flow_dir.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i] {
@@ -449,7 +449,7 @@ impl WhiteboxTool for Isobasins {
                     stack.push((row_n, col_n));
                 }
             } else {
-                if output.get_value(row, col) == out_nodata {
+                if is_nodata_value(output.get_value(row, col), out_nodata) {
                     output.set_value(row, col, outlet_id);
                     outlet_id += 1f64;
                     outlet_row.push(row);
@@ -478,7 +478,9 @@ impl WhiteboxTool for Isobasins {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata && output.get_value(row, col) == out_nodata {
+                if !is_nodata_value(z, nodata)
+                    && is_nodata_value(output.get_value(row, col), out_nodata)
+                {
                     // trace the flowpath from this cell until you find an outlet ID in the output
                     outlet_id = nodata;
                     flag = true;
@@ -494,7 +496,7 @@ impl WhiteboxTool for Isobasins {
 
                             // if the new cell already has a value in the output, use that as the outletID
                             z = output.get_value(row_n, col_n);
-                            if z != out_nodata {
+                            if !is_nodata_value(z, out_nodata) {
                                 outlet_id = z;
                                 flag = false;
                             }
@@ -517,7 +519,7 @@ impl WhiteboxTool for Isobasins {
 
                             // if the new cell already has a value in the output, use that as the outletID
                             z = output.get_value(row_n, col_n);
-                            if z != out_nodata {
+                            if !is_nodata_value(z, out_nodata) {
                                 outlet_id = z;
                                 flag = false;
                             }
@@ -545,10 +547,10 @@ impl WhiteboxTool for Isobasins {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output.get_value(row, col);
-                    if z != out_nodata {
+                    if !is_nodata_value(z, out_nodata) {
                         for i in 0..8 {
                             z_n = output.get_value(row + dy[i], col + dx[i]);
-                            if z_n != z && z_n != out_nodata {
+                            if z_n != z && !is_nodata_value(z_n, out_nodata) {
                                 // neighbouring cell is in a different basin
                                 if flow_dir.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                                 {
@@ -284,7 +284,7 @@ impl WhiteboxTool for JensonSnapPourPoints {
             for c in (col - snap_dist_int)..(col + snap_dist_int + 1) {
                 for r in (row - snap_dist_int)..(row + snap_dist_int + 1) {
                     zn = streams.get_value(r, c);
-                    if zn > 0f64 && zn != nodata {
+                    if zn > 0f64 && !is_nodata_value(zn, nodata) {
                         // it's a stream
                         x = streams.get_x_from_column(c);
                         y = streams.get_y_from_row(r);
@@ -286,13 +286,15 @@ impl WhiteboxTool for LongestFlowpath {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata && basins.get_value(row, col) != basin_nodata {
+                        if !is_nodata_value(z, nodata)
+                            && !is_nodata_value(basins.get_value(row, col), basin_nodata)
+                        {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = input.get_value(row + d_y[i], col + d_x[i]);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -350,8 +352,8 @@ impl WhiteboxTool for LongestFlowpath {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir.get_value(row + d_y[i], col + d_x[i])
@@ -536,7 +538,7 @@ impl WhiteboxTool for LongestFlowpath {
             first_cell_encountered = false;
             flag = true;
             while flag {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata_value(input.get_value(row, col), nodata) {
                     dir = flow_dir.get_value(row, col);
                     already_added_point = if dir != prev_dir || !first_cell_encountered {
                         prev_dir = dir;
@@ -573,7 +575,7 @@ impl WhiteboxTool for LongestFlowpath {
                             if dir >= 0i8 {
                                 row_n = row_n + d_y[dir as usize];
                                 col_n = col_n + d_x[dir as usize];
-                                if basins.get_value(row_n, col_n) != basin_nodata {
+                                if !is_nodata_value(basins.get_value(row_n, col_n), basin_nodata) {
                                     x = input.get_x_from_column(col_n);
                                     y = input.get_y_from_row(row_n);
                                     points.push(Point2D::new(x, y));
@@ -233,14 +233,14 @@ impl WhiteboxTool for MaxUpslopeFlowpathLength {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
-                                z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
+                                z_n = input.get_value(row + d_y[i], col + d_x[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -301,8 +301,8 @@ impl WhiteboxTool for MaxUpslopeFlowpathLength {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + d_y[i], col + d_x[i])] == inflowing_vals[i] {
@@ -329,7 +329,7 @@ impl WhiteboxTool for MaxUpslopeFlowpathLength {
             for col in 0..columns {
                 if num_inflowing[(row, col)] == 0i8 {
                     stack.push((row, col));
-                    output[(row, col)] = 0.0;
+                    output.set_value(row, col, 0.0);
                 } else if num_inflowing[(row, col)] == -1i8 {
                     num_solved_cells += 1;
                 }
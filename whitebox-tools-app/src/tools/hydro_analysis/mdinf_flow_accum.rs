@@ -342,8 +342,8 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if input.get_value(row + d_y[i], col + d_x[i]) > z {
@@ -452,10 +452,10 @@ impl WhiteboxTool for MDInfFlowAccumulation {
 
                     p1 = input.get_value(row + yd[i], col + xd[i]);
                     p2 = input.get_value(row + yd[ii], col + xd[ii]);
-                    if p1 < z && p1 != nodata {
+                    if p1 < z && !is_nodata_value(p1, nodata) {
                         downslope[c] = true;
                     }
-                    if p1 != nodata && p2 != nodata {
+                    if !is_nodata_value(p1, nodata) && !is_nodata_value(p2, nodata) {
                         // Calculate the elevation difference between the center point and the points p1 and p2
                         z1 = p1 - z;
                         z2 = p2 - z;
@@ -497,7 +497,7 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                         r_facet[c] = hr;
                         s_facet[c] = hs;
                     } else {
-                        if p1 != nodata && p1 < z {
+                        if !is_nodata_value(p1, nodata) && p1 < z {
                             hr = (i as f64) / 4.0 * PI;
                             hs = (z - p1) / (dd[ii] * grid_res);
 
@@ -522,14 +522,16 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                         } else if r_facet[i] == r_facet[ii] {
                             // If two adjacent triangular facets have the same downslope direction
                             s_facet[i]
-                        } else if (s_facet[ii] == nodata)
+                        } else if (is_nodata_value(s_facet[ii], nodata))
                             && (r_facet[i] == ((i + 1) as f64 * quarter_pi))
                         {
                             // If the downslope direction is on the border of the current triangular facet, and the corresponding neigbour's downslope is NoData
                             s_facet[i]
                         } else {
                             ii = (i + 7) % 8;
-                            if (s_facet[ii] == nodata) && (r_facet[i] == (i as f64 * quarter_pi)) {
+                            if (is_nodata_value(s_facet[ii], nodata))
+                                && (r_facet[i] == (i as f64 * quarter_pi))
+                            {
                                 // If the downslope direction is on the other border of the current triangular facet, and the corresponding neigbour's downslope is NoData
                                 s_facet[i]
                             } else {
@@ -604,7 +606,7 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                 max_slope = f64::MIN;
                 for i in 0..8 {
                     z_n = input.get_value(row + d_y[i], col + d_x[i]);
-                    if z_n != nodata {
+                    if !is_nodata_value(z_n, nodata) {
                         slope = (z - z_n) / grid_lengths[i];
                         if slope > 0f64 {
                             downslope[i] = true;
@@ -657,10 +659,14 @@ impl WhiteboxTool for MDInfFlowAccumulation {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
-                        output[(row, col)] = (output[(row, col)] * cell_area / avg_cell_size).ln();
+                        output.set_value(
+                            row,
+                            col,
+                            (output.get_value(row, col) * cell_area / avg_cell_size).ln(),
+                        );
                     }
                 }
 
@@ -675,10 +681,14 @@ impl WhiteboxTool for MDInfFlowAccumulation {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
-                        output[(row, col)] = nodata;
+                    if is_nodata_value(input.get_value(row, col), nodata) {
+                        output.set_value(row, col, nodata);
                     } else {
-                        output[(row, col)] = output[(row, col)] * cell_area / avg_cell_size;
+                        output.set_value(
+                            row,
+                            col,
+                            output.get_value(row, col) * cell_area / avg_cell_size,
+                        );
                     }
                 }
 
@@ -56,12 +56,16 @@ pub use self::average_flowpath_slope::AverageFlowpathSlope;
 pub use self::average_upslope_flowpath_length::AverageUpslopeFlowpathLength;
 pub use self::basins::Basins;
 pub use self::breach_depressions::BreachDepressions;
-pub use self::breach_depressions_least_cost::BreachDepressionsLeastCost;
+pub use self::breach_depressions_least_cost::{
+    BreachDepressionsLeastCost, BreachDepressionsLeastCostParameters,
+};
 pub use self::breach_pits::BreachSingleCellPits;
 pub use self::burn_streams_at_roads::BurnStreamsAtRoads;
-pub use self::d8_flow_accum::D8FlowAccumulation;
+pub use self::d8_flow_accum::{
+    D8FlowAccumulation, D8FlowAccumulationOutputType, D8FlowAccumulationParameters,
+};
 pub use self::d8_mass_flux::D8MassFlux;
-pub use self::d8_pointer::D8Pointer;
+pub use self::d8_pointer::{D8Pointer, D8PointerParameters};
 pub use self::depth_in_sink::DepthInSink;
 pub use self::dinf_flow_accum::DInfFlowAccumulation;
 pub use self::dinf_mass_flux::DInfMassFlux;
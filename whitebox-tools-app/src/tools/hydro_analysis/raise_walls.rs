@@ -360,7 +360,9 @@ impl WhiteboxTool for RaiseWalls {
                                     x_prime = x1 + (row_y_coord - y1) / (y2 - y1) * (x2 - x1);
                                     let col = output.get_column_from_x(x_prime);
                                     z = output.get_value(row, col);
-                                    if z != nodata && walled.get_value(row, col) == 0u8 {
+                                    if !is_nodata_value(z, nodata)
+                                        && walled.get_value(row, col) == 0u8
+                                    {
                                         output.set_value(row, col, z + wall_height);
                                         output_something = true;
                                         walled.set_value(row, col, 1u8);
@@ -387,7 +389,9 @@ impl WhiteboxTool for RaiseWalls {
                                     let row = output.get_row_from_y(y_prime);
 
                                     z = output.get_value(row, col);
-                                    if z != nodata && walled.get_value(row, col) == 0u8 {
+                                    if !is_nodata_value(z, nodata)
+                                        && walled.get_value(row, col) == 0u8
+                                    {
                                         output.set_value(row, col, z + wall_height);
                                         output_something = true;
                                         walled.set_value(row, col, 1u8);
@@ -260,13 +260,13 @@ impl WhiteboxTool for Rho8Pointer {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![out_nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             let mut dir = 0;
                             let mut max_slope = f64::MIN;
                             for i in 0..8 {
-                                z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
+                                z_n = input.get_value(row + d_y[i], col + d_x[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = match i {
                                         1 | 3 | 5 | 7 => z - z_n,
                                         _ => (z - z_n) / (2f64 - rng.gen_range(0f64..1f64)), //between.ind_sample(&mut rng)),
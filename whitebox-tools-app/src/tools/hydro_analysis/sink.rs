@@ -238,11 +238,11 @@ impl WhiteboxTool for Sink {
                 for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
                     for col in 1..columns - 1 {
                         z = filled_dem2.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             flag = true;
                             for n in 0..8 {
                                 zn = filled_dem2.get_value(row + dy[n], col + dx[n]);
-                                if zn < z || zn == nodata {
+                                if zn < z || is_nodata_value(zn, nodata) {
                                     // It either has a lower neighbour or is an edge cell.
                                     flag = false;
                                     break;
@@ -255,6 +255,9 @@ impl WhiteboxTool for Sink {
                         }
                     }
                 }
+                // The Arc must be released before the pits are sent, since it is unwrapped
+                // once they have all been received.
+                drop(filled_dem2);
                 tx.send(pits).unwrap();
             });
         }
@@ -321,14 +324,14 @@ impl WhiteboxTool for Sink {
                             if visited.get_value(rn, cn) == 0 {
                                 zn = filled_dem.get_value(rn, cn);
                                 if !outlet_found {
-                                    if zn >= z && zn != nodata {
+                                    if zn >= z && !is_nodata_value(zn, nodata) {
                                         minheap.push(GridCell {
                                             row: rn,
                                             column: cn,
                                             priority: zn,
                                         });
                                         visited.set_value(rn, cn, 1);
-                                    } else if zn != nodata {
+                                    } else if !is_nodata_value(zn, nodata) {
                                         // zn < z
                                         // 'cell' has a lower neighbour that hasn't already passed through minheap.
                                         // Therefore, 'cell' is a pour point cell.
@@ -282,7 +282,7 @@ impl WhiteboxTool for SnapPourPoints {
             for x in (col - snap_dist_int)..(col + snap_dist_int + 1) {
                 for y in (row - snap_dist_int)..(row + snap_dist_int + 1) {
                     zn = flow_accum.get_value(y, x);
-                    if zn > max_accum && zn != nodata {
+                    if zn > max_accum && !is_nodata_value(zn, nodata) {
                         max_accum = zn;
                         xn = x;
                         yn = y;
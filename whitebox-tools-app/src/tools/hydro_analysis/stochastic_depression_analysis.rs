@@ -386,7 +386,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         for row in 0..rows {
             for col in 0..columns {
                 z = input1.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     input.set_value(row, col, (z * multiplier) as i32);
                 } else {
                     num_nodata += 1;
@@ -635,7 +635,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata_i32 {
+                    if !is_nodata_value(z, nodata_i32) {
                         e = error_model.get_value(row, col);
                         error_model.set_value(row, col, z + e);
                     } else {
@@ -692,7 +692,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                     zin_n = error_model.get_value(row_n, col_n);
                     zout_n = dep_filled.get_value(row_n, col_n);
                     if zout_n == background_val {
-                        if zin_n == nodata_i32 {
+                        if is_nodata_value(zin_n, nodata_i32) {
                             dep_filled.set_value(row_n, col_n, nodata_i32);
                             queue.push_back((row_n, col_n));
                         } else {
@@ -721,7 +721,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                     zout_n = dep_filled.get_value(row_n, col_n);
                     if zout_n == background_val {
                         zin_n = error_model.get_value(row_n, col_n);
-                        if zin_n != nodata_i32 {
+                        if !is_nodata_value(zin_n, nodata_i32) {
                             if zin_n < zout {
                                 zin_n = zout;
                                 // Depression cell; increase its value in output
@@ -756,7 +756,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         let mut output = Raster::initialize_using_config(&output_file, &output_config);
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata_i32 {
+                if !is_nodata_value(input.get_value(row, col), nodata_i32) {
                     output.set_value(row, col, freq_dep.get_value(row, col) as f64 / iters);
                 } else {
                     output.set_value(row, col, nodata);
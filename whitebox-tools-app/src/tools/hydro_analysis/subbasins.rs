@@ -261,11 +261,11 @@ impl WhiteboxTool for Subbasins {
         let mut current_id = 1f64;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 {
+                if streams.get_value(row, col) > 0.0 {
                     count = 0i8;
                     for i in 0..8 {
-                        if streams[(row + dy[i], col + dx[i])] > 0.0
-                            && pntr[(row + dy[i], col + dx[i])] == inflowing_vals[i]
+                        if streams.get_value(row + dy[i], col + dx[i]) > 0.0
+                            && pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                         {
                             count += 1;
                         }
@@ -278,7 +278,7 @@ impl WhiteboxTool for Subbasins {
                         current_id += 1f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
                         pourpts[(row, col)] = 0.0;
                     } else {
                         pourpts[(row, col)] = nodata;
@@ -337,7 +337,7 @@ impl WhiteboxTool for Subbasins {
             val = pourpts[(row, col)];
 
             // find the downstream cell
-            dir = pntr[(row, col)] as usize;
+            dir = pntr.get_value(row, col) as usize;
             if dir > 0 {
                 if dir > 128 || pntr_matches[dir] == 999 {
                     return Err(Error::new(ErrorKind::InvalidInput,
@@ -349,7 +349,7 @@ impl WhiteboxTool for Subbasins {
                 if num_inflowing[(row_n, col_n)] > 1 {
                     current_id += 1f64;
                     pourpts[(row_n, col_n)] = current_id;
-                } else if pourpts[(row_n, col_n)] == nodata {
+                } else if is_nodata_value(pourpts[(row_n, col_n)], nodata) {
                     pourpts[(row_n, col_n)] = val;
                 }
 
@@ -379,12 +379,12 @@ impl WhiteboxTool for Subbasins {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if pntr[(row, col)] == pntr_nodata {
-                    output[(row, col)] = nodata;
+                if is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                    output.set_value(row, col, nodata);
                 }
                 z = pourpts[(row, col)];
-                if z != nodata && z > 0.0 {
-                    output[(row, col)] = z;
+                if !is_nodata_value(z, nodata) && z > 0.0 {
+                    output.set_value(row, col, z);
                 }
             }
             if verbose {
@@ -401,20 +401,20 @@ impl WhiteboxTool for Subbasins {
         let mut outlet_id: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if output[(row, col)] == low_value {
+                if output.get_value(row, col) == low_value {
                     flag = false;
                     x = col;
                     y = row;
                     outlet_id = nodata;
                     while !flag {
-                        dir = pntr[(y, x)] as usize;
+                        dir = pntr.get_value(y, x) as usize;
                         if dir > 0 {
                             c = pntr_matches[dir];
                             y += dy[c];
                             x += dx[c];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            z = output[(y, x)];
+                            z = output.get_value(y, x);
                             if z != low_value {
                                 outlet_id = z;
                                 flag = true;
@@ -427,23 +427,23 @@ impl WhiteboxTool for Subbasins {
                     flag = false;
                     x = col;
                     y = row;
-                    output[(y, x)] = outlet_id;
+                    output.set_value(y, x, outlet_id);
                     while !flag {
                         // find its downslope neighbour
-                        dir = pntr[(y, x)] as usize;
+                        dir = pntr.get_value(y, x) as usize;
                         if dir > 0 {
                             c = pntr_matches[dir];
                             y += dy[c];
                             x += dx[c];
 
                             // if the new cell already has a value in the output, use that as the outletID
-                            if output[(y, x)] != low_value {
+                            if output.get_value(y, x) != low_value {
                                 flag = true;
                             }
                         } else {
                             flag = true;
                         }
-                        output[(y, x)] = outlet_id;
+                        output.set_value(y, x, outlet_id);
                     }
                 }
             }
@@ -365,7 +365,7 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
         for i in 0..seed_cols.len() {
             let row = seed_rows[i];
             let col = seed_cols[i];
-            if flowdir.get_value(row, col) != nodata {
+            if !is_nodata_value(flowdir.get_value(row, col), nodata) {
                 flag = false;
                 x = col;
                 y = row;
@@ -377,7 +377,7 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
                     }
                     // find its downslope neighbour
                     dir = flowdir.get_value(y, x);
-                    if dir != nodata && dir > 0.0 {
+                    if !is_nodata_value(dir, nodata) && dir > 0.0 {
                         // move x and y accordingly
                         x += dx[pntr_matches[dir as usize]];
                         y += dy[pntr_matches[dir as usize]];
@@ -397,7 +397,7 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
 
         for row in 0..rows {
             for col in 0..columns {
-                if flowdir.get_value(row, col) == nodata {
+                if is_nodata_value(flowdir.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                 }
             }
@@ -300,7 +300,7 @@ impl WhiteboxTool for UnnestBasins {
         for row in 0..rows {
             for col in 0..columns {
                 z = pntr.get_value(row, col);
-                if z != pntr_nodata {
+                if !is_nodata_value(z, pntr_nodata) {
                     if z > 0.0 {
                         flow_dir.set_value(row, col, pntr_matches[z as usize]);
                     } else {
@@ -240,9 +240,9 @@ impl WhiteboxTool for UpslopeDepressionStorage {
         for row in 0..rows {
             for col in 0..columns {
                 z = filled.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     for n in 0..8 {
-                        if filled.get_value(row + dy[n], col + dx[n]) == nodata {
+                        if is_nodata_value(filled.get_value(row + dy[n], col + dx[n]), nodata) {
                             minheap.push(GridCell {
                                 row: row,
                                 column: col,
@@ -449,11 +449,11 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = filled.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             count = 0i8;
                             for n in 0..8 {
                                 zn = filled.get_value(row + dy[n], col + dx[n]);
-                                if zn > z && zn != nodata {
+                                if zn > z && !is_nodata_value(zn, nodata) {
                                     count += 1;
                                 }
                             }
@@ -510,7 +510,7 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                 rn = row + dy[n];
                 cn = col + dx[n];
                 zn = filled.get_value(rn, cn);
-                if zn < z && zn != nodata {
+                if zn < z && !is_nodata_value(zn, nodata) {
                     weights[n] = (z - zn).powf(exponent);
                     total_weights += weights[n];
                     downslope[n] = true;
@@ -548,7 +548,7 @@ impl WhiteboxTool for UpslopeDepressionStorage {
         for row in 0..rows {
             for col in 0..columns {
                 z = filled.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     output.set_value(
                         row,
                         col,
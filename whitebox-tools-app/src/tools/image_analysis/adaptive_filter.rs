@@ -321,7 +321,7 @@ impl WhiteboxTool for AdaptiveFilter {
         } else {
             Box::new(|row: isize, col: isize| -> f64 {
                 let value = input.get_value(row, col);
-                if value != nodata {
+                if !is_nodata_value(value, nodata) {
                     return value2i(value);
                 }
                 nodata
@@ -339,7 +339,7 @@ impl WhiteboxTool for AdaptiveFilter {
             sum_n = 0;
             for col in 0..columns {
                 val = input_fn(row, col);
-                if val == nodata {
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     val -= min_val;
@@ -392,7 +392,7 @@ impl WhiteboxTool for AdaptiveFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -405,7 +405,7 @@ impl WhiteboxTool for AdaptiveFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -437,7 +437,7 @@ impl WhiteboxTool for AdaptiveFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint_x - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -248,8 +248,8 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                 let mut b_sqr_total = 0f64;
                 for row in (0..rows).filter(|rt| rt % num_procs == tid) {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             num_pixels += 1f64;
                             r = z as u32 & 0xFF;
                             g = (z as u32 >> 8) & 0xFF;
@@ -407,8 +407,8 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                 for row in (0..rows).filter(|rt| rt % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             r = z as u32 & 0xFF;
                             g = (z as u32 >> 8) & 0xFF;
                             b = (z as u32 >> 16) & 0xFF;
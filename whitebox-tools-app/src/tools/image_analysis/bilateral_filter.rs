@@ -339,7 +339,7 @@ impl WhiteboxTool for BilateralFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -352,7 +352,7 @@ impl WhiteboxTool for BilateralFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -371,14 +371,14 @@ impl WhiteboxTool for BilateralFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             //fill weights_i with the appropriate intensity weights
                             sum = 0.0;
                             for a in 0..num_pixels_in_filter {
                                 x = col + dx[a];
                                 y = row + dy[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     weight = recip_root_2_pi_times_sigma_i
                                         * (-1.0 * ((zn - z) * (zn - z)) / two_sigma_sqr_i).exp();
                                     weight *= weights_d[a];
@@ -392,7 +392,7 @@ impl WhiteboxTool for BilateralFilter {
                                 x = col + dx[a];
                                 y = row + dy[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     z_final += weights_i[a] * zn / sum;
                                 }
                             }
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 29/04/2018
-Last Modified: 29/04/2018
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -21,7 +21,9 @@ use std::path;
 /// the multispectral data for each date. Note that there must be the same number of
 /// image files (bands) for the two dates and they must be entered in the same order,
 /// i.e. if three bands, red, green, and blue are entered for date one, these same
-/// bands must be entered in the same order for date two.
+/// bands must be entered in the same order for date two. Each band of a multi-band
+/// image (e.g. a multispectral stack stored in a single GeoTIFF) is treated as a separate
+/// image, such that a single multi-band image may be entered for each date.
 ///
 /// CVA outputs two image files. The first image contains the change vector length,
 /// i.e. magnitude, for each pixel in the multi-spectral dataset. The second image
@@ -235,31 +237,45 @@ impl WhiteboxTool for ChangeVectorAnalysis {
             cmd = input1_files_str.split(",");
             input1_files = cmd.collect::<Vec<&str>>();
         }
-        let num_files = input1_files.len();
-        if num_files == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "At least one input for each date are required to operate this tool.",
-            ));
-        }
-
         cmd = input2_files_str.split(";");
         let mut input2_files = cmd.collect::<Vec<&str>>();
         if input2_files.len() == 1 {
             cmd = input2_files_str.split(",");
             input2_files = cmd.collect::<Vec<&str>>();
         }
-        if input2_files.len() == 0 {
+
+        // Read the images for each date. Each band of a multi-band image is treated as a separate image.
+        let read_images = |files: &Vec<&str>| -> Result<Vec<Raster>, Error> {
+            let mut rasters = vec![];
+            for file in files {
+                if !file.trim().is_empty() {
+                    let mut input_file = file.trim().to_owned();
+                    if !input_file.contains(&sep) && !input_file.contains("/") {
+                        input_file = format!("{}{}", working_directory, input_file);
+                    }
+                    if verbose {
+                        println!("Reading {}...", input_file);
+                    }
+                    rasters.extend(Raster::new(&input_file, "r")?.split_bands());
+                }
+            }
+            Ok(rasters)
+        };
+        let input1_rasters = read_images(&input1_files)?;
+        let input2_rasters = read_images(&input2_files)?;
+
+        let num_files = input1_rasters.len();
+        if num_files == 0 || input2_rasters.is_empty() {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
                 "At least one input for each date are required to operate this tool.",
             ));
         }
 
-        if num_files != input2_files.len() {
+        if num_files != input2_rasters.len() {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
-                "There must be the same number of input files for each date.",
+                "There must be the same number of input images (bands) for each date.",
             ));
         }
 
@@ -268,21 +284,14 @@ impl WhiteboxTool for ChangeVectorAnalysis {
             direction_array.push(2f64.powf(i as f64));
         }
 
-        // We will need to read one of the files in to get the rows, columns, and nodata values
-        // in order to create the output files.
-        let mut input1_file = input1_files[0].trim().to_owned();
-        if !input1_file.contains(&sep) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
-        let input1 = Raster::new(&input1_file, "r")?;
-        let rows = input1.configs.rows as isize;
-        let columns = input1.configs.columns as isize;
-        let nodata = input1.configs.nodata;
+        let rows = input1_rasters[0].configs.rows as isize;
+        let columns = input1_rasters[0].configs.columns as isize;
+        let nodata = input1_rasters[0].configs.nodata;
 
         // Create the output files
-        let mut out_magnitude = Raster::initialize_using_file(&magnitude_file, &input1);
+        let mut out_magnitude = Raster::initialize_using_file(&magnitude_file, &input1_rasters[0]);
         out_magnitude.configs.data_type = DataType::F32;
-        let mut out_direction = Raster::initialize_using_file(&direction_file, &input1);
+        let mut out_direction = Raster::initialize_using_file(&direction_file, &input1_rasters[0]);
         out_direction.reinitialize_values(0f64);
 
         let mut nodata_detected: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
@@ -295,59 +304,47 @@ impl WhiteboxTool for ChangeVectorAnalysis {
 
         for i in 0..num_files {
             if verbose {
-                println!("Reading file {} of {}", i + 1, num_files);
+                println!("Processing band {} of {}", i + 1, num_files);
+            }
+            let input1 = &input1_rasters[i];
+            let input2 = &input2_rasters[i];
+
+            // make sure the images have the right rows and columns
+            if input1.configs.rows as isize != rows
+                || input1.configs.columns as isize != columns
+                || input2.configs.rows as isize != rows
+                || input2.configs.columns as isize != columns
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "All of the input files must share the same extent (rows and columns).",
+                ));
             }
-            if !input1_files[i].trim().is_empty() && !input2_files[i].trim().is_empty() {
-                let mut input1_file = input1_files[i].trim().to_owned();
-                if !input1_file.contains(&sep) && !input1_file.contains("/") {
-                    input1_file = format!("{}{}", working_directory, input1_file);
-                }
-
-                let mut input2_file = input2_files[i].trim().to_owned();
-                if !input2_file.contains(&sep) && !input2_file.contains("/") {
-                    input2_file = format!("{}{}", working_directory, input2_file);
-                }
-
-                let input1 = Raster::new(&input1_file, "r")?;
-                let input2 = Raster::new(&input2_file, "r")?;
-
-                // make sure the images have the right rows and columns
-                if input1.configs.rows as isize != rows
-                    || input1.configs.columns as isize != columns
-                    || input2.configs.rows as isize != rows
-                    || input2.configs.columns as isize != columns
-                {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All of the input files must share the same extent (rows and columns).",
-                    ));
-                }
 
-                let nodata1 = input1.configs.nodata;
-                let nodata2 = input2.configs.nodata;
-
-                let (mut z1, mut z2): (f64, f64);
-                let mut z: f64;
-                for row in 0..rows {
-                    for col in 0..columns {
-                        z1 = input1.get_value(row, col);
-                        z2 = input2.get_value(row, col);
-                        if z1 != nodata1 && z2 != nodata2 {
-                            z = z2 - z1;
-                            out_magnitude.increment(row, col, z * z);
-                            if z >= 0f64 {
-                                out_direction.increment(row, col, direction_array[i]);
-                            }
-                        } else {
-                            nodata_detected.set_value(row, col, 1i8);
+            let nodata1 = input1.configs.nodata;
+            let nodata2 = input2.configs.nodata;
+
+            let (mut z1, mut z2): (f64, f64);
+            let mut z: f64;
+            for row in 0..rows {
+                for col in 0..columns {
+                    z1 = input1.get_value(row, col);
+                    z2 = input2.get_value(row, col);
+                    if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
+                        z = z2 - z1;
+                        out_magnitude.increment(row, col, z * z);
+                        if z >= 0f64 {
+                            out_direction.increment(row, col, direction_array[i]);
                         }
+                    } else {
+                        nodata_detected.set_value(row, col, 1i8);
                     }
-                    if verbose {
-                        progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                            old_progress = progress;
-                        }
+                }
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
                     }
                 }
             }
@@ -306,7 +306,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -319,7 +319,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -350,7 +350,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                             max_val = f64::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input_fn(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -370,7 +370,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input_fn(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -384,7 +384,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                             }
                         }
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             min_val = f64::INFINITY;
                             max_val = f64::NEG_INFINITY;
                             min_val2 = min_val;
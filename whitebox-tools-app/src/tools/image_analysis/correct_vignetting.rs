@@ -331,7 +331,7 @@ impl WhiteboxTool for CorrectVignetting {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -349,7 +349,7 @@ impl WhiteboxTool for CorrectVignetting {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input_fn(row, col);
-                        if z_in != nodata {
+                        if !is_nodata_value(z_in, nodata) {
                             dist = ((row as f64 - pp_y) * (row as f64 - pp_y)
                                 + (col as f64 - pp_x) * (col as f64 - pp_x))
                                 .sqrt();
@@ -420,7 +420,7 @@ impl WhiteboxTool for CorrectVignetting {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value);
                             return ret;
@@ -434,7 +434,7 @@ impl WhiteboxTool for CorrectVignetting {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = unscaled_data.get_value(row, col);
-                        if z_in != nodata {
+                        if !is_nodata_value(z_in, nodata) {
                             z_out = min_in + (z_in - min_out) / range_out * range_in;
                             data[col as usize] = output_fn(row, col, z_out);
                         }
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 19/07/2017
-Last Modified: 18/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -26,6 +26,10 @@ use std::thread;
 /// will be opaque. This can be useful for cropping an image to an irregular-shaped boundary. The opacity
 /// channel can also be used to create transparent gradients in the composite image.
 ///
+/// The red image (`--red`) may also be a multi-band image (e.g. a multispectral stack stored in a single
+/// GeoTIFF), in which case the green (`--green`) and blue (`--blue`) images may be left unspecified and the
+/// first three bands of the red image are used for the red, green, and blue channels respectively.
+///
 /// A balance contrast enhancement (BCE) can optionally be performed on the bands prior to creation of
 /// the colour composite. While this operation will add to the runtime of `CreateColourComposite`, if
 /// the individual input bands have not already had contrast enhancements, then it is advisable that
@@ -69,19 +73,19 @@ impl CreateColourComposite {
         parameters.push(ToolParameter {
             name: "Input Green Band Image File".to_owned(),
             flags: vec!["--green".to_owned()],
-            description: "Input green band image file.".to_owned(),
+            description: "Input green band image file. Optional when the red image is a multi-band image.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
-            optional: false,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
             name: "Input Blue Band Image File".to_owned(),
             flags: vec!["--blue".to_owned()],
-            description: "Input blue band image file.".to_owned(),
+            description: "Input blue band image file. Optional when the red image is a multi-band image.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
-            optional: false,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
@@ -136,7 +140,8 @@ impl CreateColourComposite {
             short_exe += ".exe";
         }
         let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --red=band3.tif --green=band2.tif --blue=band1.tif -o=output.tif
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" --red=band3.tif --green=band2.tif --blue=band1.tif --opacity=a.tif -o=output.tif --enhance --zeros", short_exe, name).replace("*", &sep);
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --red=band3.tif --green=band2.tif --blue=band1.tif --opacity=a.tif -o=output.tif --enhance --zeros
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --red=stack.tif -o=output.tif --enhance", short_exe, name).replace("*", &sep);
 
         CreateColourComposite {
             name: name,
@@ -265,14 +270,25 @@ impl WhiteboxTool for CreateColourComposite {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
+        // When neither the green nor blue images are specified, the red image must be multi-band.
+        let multi_band_input = input2_file.trim().is_empty() && input3_file.trim().is_empty();
+        if !multi_band_input && (input2_file.trim().is_empty() || input3_file.trim().is_empty()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Both the green and blue images must be specified, unless the red image is a multi-band image.",
+            ));
+        }
+
         if !input1_file.contains(&sep) && !input1_file.contains("/") {
             input1_file = format!("{}{}", working_directory, input1_file);
         }
-        if !input2_file.contains(&sep) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
-        if !input3_file.contains(&sep) && !input3_file.contains("/") {
-            input3_file = format!("{}{}", working_directory, input3_file);
+        if !multi_band_input {
+            if !input2_file.contains(&sep) && !input2_file.contains("/") {
+                input2_file = format!("{}{}", working_directory, input2_file);
+            }
+            if !input3_file.contains(&sep) && !input3_file.contains("/") {
+                input3_file = format!("{}{}", working_directory, input3_file);
+            }
         }
         if input4_used {
             if !input4_file.contains(&sep) && !input4_file.contains("/") {
@@ -283,18 +299,37 @@ impl WhiteboxTool for CreateColourComposite {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
-        if verbose {
-            println!("Reading red band data...")
-        };
-        let input_r = Arc::new(Raster::new(&input1_file, "r")?);
-        if verbose {
-            println!("Reading green band data...")
-        };
-        let input_g = Arc::new(Raster::new(&input2_file, "r")?);
-        if verbose {
-            println!("Reading blue band data...")
+        let (input_r, input_g, input_b) = if multi_band_input {
+            if verbose {
+                println!("Reading multi-band image data...")
+            };
+            let mut bands = Raster::new(&input1_file, "r")?.split_bands();
+            if bands.len() < 3 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The green and blue images must be specified unless the red image is a multi-band image with at least three bands.",
+                ));
+            }
+            bands.truncate(3);
+            let input_b = Arc::new(bands.pop().unwrap());
+            let input_g = Arc::new(bands.pop().unwrap());
+            let input_r = Arc::new(bands.pop().unwrap());
+            (input_r, input_g, input_b)
+        } else {
+            if verbose {
+                println!("Reading red band data...")
+            };
+            let input_r = Arc::new(Raster::new(&input1_file, "r")?);
+            if verbose {
+                println!("Reading green band data...")
+            };
+            let input_g = Arc::new(Raster::new(&input2_file, "r")?);
+            if verbose {
+                println!("Reading blue band data...")
+            };
+            let input_b = Arc::new(Raster::new(&input3_file, "r")?);
+            (input_r, input_g, input_b)
         };
-        let input_b = Arc::new(Raster::new(&input3_file, "r")?);
 
         let rows = input_r.configs.rows as isize;
         let columns = input_r.configs.columns as isize;
@@ -380,10 +415,13 @@ impl WhiteboxTool for CreateColourComposite {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata_r; columns as usize];
                     for col in 0..columns {
-                        red_val = input_r[(row, col)];
-                        green_val = input_g[(row, col)];
-                        blue_val = input_b[(row, col)];
-                        if red_val != nodata_r && green_val != nodata_g && blue_val != nodata_b {
+                        red_val = input_r.get_value(row, col);
+                        green_val = input_g.get_value(row, col);
+                        blue_val = input_b.get_value(row, col);
+                        if !is_nodata_value(red_val, nodata_r)
+                            && !is_nodata_value(green_val, nodata_g)
+                            && !is_nodata_value(blue_val, nodata_b)
+                        {
                             red_val = (red_val - red_min) / red_range * 255f64;
                             if red_val < 0f64 {
                                 red_val = 0f64;
@@ -467,8 +505,8 @@ impl WhiteboxTool for CreateColourComposite {
 
             for row in 0..rows {
                 for col in 0..columns {
-                    z = output[(row, col)];
-                    if z != nodata_r {
+                    z = output.get_value(row, col);
+                    if !is_nodata_value(z, nodata_r) {
                         num_pixels += 1f64;
                         r = z as u32 & 0xFF;
                         g = (z as u32 >> 8) & 0xFF;
@@ -536,8 +574,8 @@ impl WhiteboxTool for CreateColourComposite {
 
             for row in 0..rows {
                 for col in 0..columns {
-                    z = output[(row, col)];
-                    if z != nodata_r {
+                    z = output.get_value(row, col);
+                    if !is_nodata_value(z, nodata_r) {
                         r = z as u32 & 0xFF;
                         g = (z as u32 >> 8) & 0xFF;
                         b = (z as u32 >> 16) & 0xFF;
@@ -571,8 +609,11 @@ impl WhiteboxTool for CreateColourComposite {
                         g_out = g_outf as u32;
                         b_out = b_outf as u32;
 
-                        output[(row, col)] =
-                            ((a << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64
+                        output.set_value(
+                            row,
+                            col,
+                            ((a << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64,
+                        );
                     }
                 }
                 if verbose {
@@ -290,8 +290,8 @@ impl WhiteboxTool for DirectDecorrelationStretch {
                     let mut histo_blue = [0usize; 256];
                     let mut num_cells = 0;
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             red = z as u32 & 0xFF;
                             green = (z as u32 >> 8) & 0xFF;
                             blue = (z as u32 >> 16) & 0xFF;
@@ -469,8 +469,8 @@ impl WhiteboxTool for DirectDecorrelationStretch {
                 for row in (0..rows).filter(|row_val| row_val % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             red = red_band[(row, col)] as u32;
                             red = red.clamp(stretch_min as u32, stretch_max as u32);
 
@@ -313,7 +313,7 @@ impl WhiteboxTool for DiversityFilter {
                             // remove the trailing column from the histo
                             for row2 in start_row..=end_row {
                                 z = input.get_value(row2, start_col - 1);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     histo[bin_val] -= 1;
                                     if histo[bin_val] == 0 {
@@ -325,7 +325,7 @@ impl WhiteboxTool for DiversityFilter {
                             // add the leading column to the histo
                             for row2 in start_row..=end_row {
                                 z = input.get_value(row2, end_col);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     if histo[bin_val] == 0 {
                                         diversity += 1;
@@ -340,7 +340,7 @@ impl WhiteboxTool for DiversityFilter {
                             for col2 in start_col..=end_col {
                                 for row2 in start_row..=end_row {
                                     z = input.get_value(row2, col2);
-                                    if z != nodata {
+                                    if !is_nodata_value(z, nodata) {
                                         bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                         if histo[bin_val] == 0 {
                                             diversity += 1;
@@ -350,7 +350,7 @@ impl WhiteboxTool for DiversityFilter {
                                 }
                             }
                         }
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             data[col as usize] = diversity as f64;
                         }
                     }
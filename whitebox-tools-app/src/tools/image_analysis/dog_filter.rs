@@ -412,7 +412,7 @@ impl WhiteboxTool for DiffOfGaussianFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -427,14 +427,14 @@ impl WhiteboxTool for DiffOfGaussianFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum = 0.0;
                             z_final1 = 0.0;
                             for a in 0..num_pixels_in_filter1 {
                                 x = col + d_x1[a];
                                 y = row + d_y1[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     sum += weights1[a];
                                     z_final1 += weights1[a] * zn;
                                 }
@@ -447,7 +447,7 @@ impl WhiteboxTool for DiffOfGaussianFilter {
                                 x = col + d_x2[a];
                                 y = row + d_y2[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     sum += weights2[a];
                                     z_final2 += weights2[a] * zn;
                                 }
@@ -284,7 +284,7 @@ impl WhiteboxTool for EdgePreservingMeanFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -297,7 +297,7 @@ impl WhiteboxTool for EdgePreservingMeanFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -327,12 +327,12 @@ impl WhiteboxTool for EdgePreservingMeanFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum_w = 0f64;
                             sum = 0f64;
                             for n in 0..num_pixels_in_filter {
                                 zn = input_fn(row + dy[n], col + dx[n]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     diff = (zn - z).abs();
                                     if diff <= threshold {
                                         sum_w += 1f64;
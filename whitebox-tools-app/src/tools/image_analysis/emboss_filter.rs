@@ -329,7 +329,7 @@ impl WhiteboxTool for EmbossFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -356,11 +356,11 @@ impl WhiteboxTool for EmbossFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum = 0.0;
                             for n in 0..num_pixels_in_filter {
                                 zn = input_fn(row + dy[n], col + dx[n]);
-                                if zn == nodata {
+                                if is_nodata_value(zn, nodata) {
                                     zn = z;
                                 }
                                 sum += zn * weights[n];
@@ -275,7 +275,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     // It's an RGB image. Get the intensity value from the IHS decomposition of the RGB value.
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -288,7 +288,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     sum_n = 0;
                     for col in 0..columns {
                         val = input_fn(row, col);
-                        if val == nodata {
+                        if is_nodata_value(val, nodata) {
                             val = 0f64;
                         } else {
                             sum_n += 1;
@@ -312,7 +312,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     sum = 0f64;
                     for col in 0..columns {
                         val = output.get_value(row, col);
-                        if val == nodata {
+                        if is_nodata_value(val, nodata) {
                             val = 0f64;
                         }
                         sum += val;
@@ -340,7 +340,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -386,7 +386,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -406,7 +406,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
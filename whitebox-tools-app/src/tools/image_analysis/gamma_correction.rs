@@ -250,7 +250,7 @@ impl WhiteboxTool for GammaCorrection {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -261,7 +261,7 @@ impl WhiteboxTool for GammaCorrection {
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value / 1f64.powf(gamma));
                             return ret;
@@ -275,7 +275,7 @@ impl WhiteboxTool for GammaCorrection {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input_fn(row, col);
-                        if z_in != nodata {
+                        if !is_nodata_value(z_in, nodata) {
                             z_out = z_in.powf(gamma);
                             data[col as usize] = output_fn(row, col, z_out);
                         }
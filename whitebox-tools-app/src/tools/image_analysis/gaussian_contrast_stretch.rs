@@ -266,7 +266,7 @@ impl WhiteboxTool for GaussianContrastStretch {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata_value(value, nodata) {
                         x = value2i(value); // gets the intensity
                         if x < min_val {
                             min_val = x;
@@ -291,7 +291,7 @@ impl WhiteboxTool for GaussianContrastStretch {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     if is_rgb_image {
                         z = value2i(z); // gets the intensity
                     }
@@ -401,7 +401,7 @@ impl WhiteboxTool for GaussianContrastStretch {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let v = value2i(value);
                             return v;
                         }
@@ -415,7 +415,7 @@ impl WhiteboxTool for GaussianContrastStretch {
                     })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, (value + 3f64) / 6f64);
                             return ret;
@@ -434,7 +434,7 @@ impl WhiteboxTool for GaussianContrastStretch {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             bin_num = ((z - min_val) / bin_size) as usize;
                             if bin_num > num_bins_less_one {
                                 bin_num = num_bins_less_one;
@@ -338,7 +338,7 @@ impl WhiteboxTool for HighPassBilateralFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -356,14 +356,14 @@ impl WhiteboxTool for HighPassBilateralFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             //fill weights_i with the appropriate intensity weights
                             sum = 0.0;
                             for a in 0..num_pixels_in_filter {
                                 x = col + dx[a];
                                 y = row + dy[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     weight = recip_root_2_pi_times_sigma_i
                                         * (-1.0 * ((zn - z) * (zn - z)) / two_sigma_sqr_i).exp();
                                     weight *= weights_d[a];
@@ -377,7 +377,7 @@ impl WhiteboxTool for HighPassBilateralFilter {
                                 x = col + dx[a];
                                 y = row + dy[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     z_final += weights_i[a] * zn / sum;
                                 }
                             }
@@ -292,7 +292,7 @@ impl WhiteboxTool for HighPassFilter {
         } else {
             Box::new(|row: isize, col: isize| -> f64 {
                 let value = input.get_value(row, col);
-                if value != nodata {
+                if !is_nodata_value(value, nodata) {
                     return value2i(value);
                 }
                 nodata
@@ -313,7 +313,7 @@ impl WhiteboxTool for HighPassFilter {
             sum_n = 0;
             for col in 0..columns {
                 val = input_fn(row, col);
-                if val == nodata {
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     val -= min_val;
@@ -363,7 +363,7 @@ impl WhiteboxTool for HighPassFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -393,7 +393,7 @@ impl WhiteboxTool for HighPassFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint_x - 1;
                             if x1 < 0 {
                                 x1 = 0;
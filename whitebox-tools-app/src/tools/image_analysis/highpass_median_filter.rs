@@ -352,7 +352,7 @@ impl WhiteboxTool for HighPassMedianFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -365,7 +365,7 @@ impl WhiteboxTool for HighPassMedianFilter {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
@@ -401,7 +401,7 @@ impl WhiteboxTool for HighPassMedianFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value + 0.5f64); // includes an offset of 0.5 so there are no negative i values.
                         }
@@ -429,12 +429,12 @@ impl WhiteboxTool for HighPassMedianFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         bin_val = binned_data.get_value(row, col);
-                        if bin_val != bin_nodata {
-                            if old_median != bin_nodata {
+                        if !is_nodata_value(bin_val, bin_nodata) {
+                            if !is_nodata_value(old_median, bin_nodata) {
                                 // remove the trailing column from the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col - midpoint_x - 1);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] -= 1;
                                         n -= 1.0;
                                         if bin_val_n < old_median {
@@ -446,7 +446,7 @@ impl WhiteboxTool for HighPassMedianFilter {
                                 // add the leading column to the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col + midpoint_x);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] += 1;
                                         n += 1.0;
                                         if bin_val_n < old_median {
@@ -488,7 +488,7 @@ impl WhiteboxTool for HighPassMedianFilter {
                                 for col2 in start_col..end_col + 1 {
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col2);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata_value(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] += 1;
                                             n += 1f64;
                                         }
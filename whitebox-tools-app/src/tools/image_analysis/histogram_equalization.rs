@@ -282,7 +282,7 @@ impl WhiteboxTool for HistogramEqualization {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata_value(value, nodata) {
                         n += 1f64;
                         bin = ((value - min_value) / bin_size).floor() as usize;
                         histo[bin] += 1f64;
@@ -306,7 +306,7 @@ impl WhiteboxTool for HistogramEqualization {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata_value(value, nodata) {
                         v = value2i(value);
                         n += 1f64;
                         bin = (v * (num_bins - 1) as f64).floor() as usize;
@@ -361,7 +361,7 @@ impl WhiteboxTool for HistogramEqualization {
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             // convert the value into an rgb value based on modified hsi values.
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value / num_tones_less_one);
@@ -378,8 +378,8 @@ impl WhiteboxTool for HistogramEqualization {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z_in = input[(row, col)];
-                        if z_in != nodata {
+                        z_in = input.get_value(row, col);
+                        if !is_nodata_value(z_in, nodata) {
                             bin = input_fn(row, col);
                             z_out = ((cdf[bin] - min_nonempty_bin) / num_cells_less_one
                                 * num_tones_less_one)
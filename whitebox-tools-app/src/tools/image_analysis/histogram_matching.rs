@@ -259,8 +259,8 @@ impl WhiteboxTool for HistogramMatching {
         let mut bin_num;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     numcells += 1f64;
                     bin_num = ((z - min_value) / bin_size) as usize;
                     if bin_num > num_bins_less_one {
@@ -404,8 +404,8 @@ impl WhiteboxTool for HistogramMatching {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             bin_num = ((z - min_value) / bin_size) as usize;
                             if bin_num > num_bins_less_one {
                                 bin_num = num_bins_less_one;
@@ -255,8 +255,8 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
         let mut bin_num;
         for row in 0..rows1 {
             for col in 0..columns1 {
-                z = input1[(row, col)];
-                if z != nodata1 {
+                z = input1.get_value(row, col);
+                if !is_nodata_value(z, nodata1) {
                     numcells1 += 1f64;
                     bin_num = ((z - min_value1) / bin_size) as usize;
                     if bin_num > num_bins_less_one1 {
@@ -296,8 +296,8 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
 
         for row in 0..rows2 {
             for col in 0..columns2 {
-                z = input2[(row, col)];
-                if z != nodata2 {
+                z = input2.get_value(row, col);
+                if !is_nodata_value(z, nodata2) {
                     numcells2 += 1f64;
                     bin_num = ((z - min_value2) / bin_size) as usize;
                     if bin_num > num_bins_less_one2 {
@@ -434,8 +434,8 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
                 for row in (0..rows1).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata1; columns1 as usize];
                     for col in 0..columns1 {
-                        z = input1[(row, col)];
-                        if z != nodata1 {
+                        z = input1.get_value(row, col);
+                        if !is_nodata_value(z, nodata1) {
                             bin_num = ((z - min_value1) / bin_size) as usize;
                             if bin_num > num_bins_less_one1 {
                                 bin_num = num_bins_less_one1;
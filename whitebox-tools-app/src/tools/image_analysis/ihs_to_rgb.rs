@@ -370,10 +370,13 @@ impl WhiteboxTool for IhsToRgb {
                         let mut green_data = vec![nodata_i; columns as usize];
                         let mut blue_data = vec![nodata_i; columns as usize];
                         for col in 0..columns {
-                            i = input_i[(row, col)];
-                            h = input_h[(row, col)];
-                            s = input_s[(row, col)];
-                            if i != nodata_i && h != nodata_h && s != nodata_s {
+                            i = input_i.get_value(row, col);
+                            h = input_h.get_value(row, col);
+                            s = input_s.get_value(row, col);
+                            if !is_nodata_value(i, nodata_i)
+                                && !is_nodata_value(h, nodata_h)
+                                && !is_nodata_value(s, nodata_s)
+                            {
                                 let (r, g, b) = hsi2rgb(h, s, i);
 
                                 red_data[col as usize] = r as f64;
@@ -506,10 +509,13 @@ impl WhiteboxTool for IhsToRgb {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data = vec![0f64; columns as usize];
                         for col in 0..columns {
-                            i = input_i[(row, col)];
-                            h = input_h[(row, col)];
-                            s = input_s[(row, col)];
-                            if i != nodata_i && h != nodata_h && s != nodata_s {
+                            i = input_i.get_value(row, col);
+                            h = input_h.get_value(row, col);
+                            s = input_s.get_value(row, col);
+                            if !is_nodata_value(i, nodata_i)
+                                && !is_nodata_value(h, nodata_h)
+                                && !is_nodata_value(s, nodata_s)
+                            {
                                 value = hsi2value(h, s, i);
                                 data[col as usize] = value;
                             }
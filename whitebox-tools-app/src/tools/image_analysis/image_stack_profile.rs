@@ -274,7 +274,7 @@ impl WhiteboxTool for ImageStackProfile {
                     row = image.get_row_from_y(record.points[0].y);
                     col = image.get_column_from_x(record.points[0].x);
                     z = image.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         xdata[record_num][i] = (i + 1) as f64;
                         ydata[record_num][i] = z;
                     } else {
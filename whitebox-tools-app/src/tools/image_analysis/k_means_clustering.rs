@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 27/12/2017
-Last Modified: 24/02/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -37,6 +37,10 @@ use std::thread;
 /// number of pixels in the image that are changed among the class values between consecutive iterations.
 /// Lastly, the user must specify the minimum allowable number of pixels in a cluster (`--min_class_size`).
 ///
+/// Each band of a multi-band input image (e.g. a Landsat or Sentinel stack stored in a single GeoTIFF) is
+/// treated as a separate input, such that a single multi-band image may be used in place of a list of
+/// single-band images.
+///
 /// Note, each of the input images must have the same number of rows and columns and the same spatial extent
 /// because the analysis is performed on a pixel-by-pixel basis. **NoData** values in any of the input images
 /// will result in the removal of the corresponding pixel from the analysis.
@@ -333,12 +337,6 @@ impl WhiteboxTool for KMeansClustering {
             cmd = input_files_str.split(",");
             input_files = cmd.collect::<Vec<&str>>();
         }
-        let num_files = input_files.len();
-        if num_files < 2 {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                                "There is something incorrect about the input files. At least two inputs are required to operate this tool."));
-        }
-
         if max_iterations < 2 || max_iterations > 250 {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -358,48 +356,68 @@ impl WhiteboxTool for KMeansClustering {
         let mut rows = -1isize;
         let mut columns = -1isize;
 
-        let mut nodata: Vec<f64> = Vec::with_capacity(num_files);
-        let mut minimum: Vec<f64> = Vec::with_capacity(num_files);
-        let mut maximum: Vec<f64> = Vec::with_capacity(num_files);
-        let mut input_raster: Vec<Raster> = Vec::with_capacity(num_files);
-
-        for i in 0..num_files {
+        let mut input_raster: Vec<Raster> = Vec::with_capacity(input_files.len());
+        let mut image_names = vec![];
+        for i in 0..input_files.len() {
             if verbose {
-                println!("Reading file {} of {}", i + 1, num_files);
+                println!("Reading file {} of {}", i + 1, input_files.len());
             }
             if !input_files[i].trim().is_empty() {
                 let mut input_file = input_files[i].trim().to_owned();
                 if !input_file.contains(&sep) && !input_file.contains("/") {
                     input_file = format!("{}{}", working_directory, input_file);
                 }
-                input_raster.push(Raster::new(&input_file, "r")?);
-                nodata.push(input_raster[i].configs.nodata);
-                minimum.push(input_raster[i].configs.minimum);
-                maximum.push(input_raster[i].configs.maximum);
-
-                if rows == -1 || columns == -1 {
-                    rows = input_raster[i].configs.rows as isize;
-                    columns = input_raster[i].configs.columns as isize;
-                    if num_classes < 2 || num_classes as isize > (rows * columns) {
-                        return Err(Error::new(
-                            ErrorKind::InvalidInput,
-                            "Number of classes should be between 2 and rows x columns.",
-                        ));
-                    }
-                    if min_class_size > ((rows * columns) as usize / num_classes) {
-                        return Err(Error::new(
-                            ErrorKind::InvalidInput,
-                            "Min class size should be less than rows x columns / num_classes.",
-                        ));
-                    }
-                } else {
-                    if input_raster[i].configs.rows as isize != rows
-                        || input_raster[i].configs.columns as isize != columns
-                    {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                // each band of a multi-band image is treated as a separate image
+                let input = Raster::new(&input_file, "r")?;
+                let num_bands = input.num_bands();
+                for band in 0..num_bands {
+                    if num_bands > 1 {
+                        image_names.push(format!("{} (band {})", input_files[i].trim(), band + 1));
+                    } else {
+                        image_names.push(input_files[i].trim().to_string());
                     }
                 }
+                input_raster.extend(input.split_bands());
+            }
+        }
+
+        let num_files = input_raster.len();
+        if num_files < 2 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "There is something incorrect about the input files. At least two input images, or a multi-band image with at least two bands, are required to operate this tool."));
+        }
+
+        let mut nodata: Vec<f64> = Vec::with_capacity(num_files);
+        let mut minimum: Vec<f64> = Vec::with_capacity(num_files);
+        let mut maximum: Vec<f64> = Vec::with_capacity(num_files);
+
+        for i in 0..num_files {
+            nodata.push(input_raster[i].configs.nodata);
+            minimum.push(input_raster[i].configs.minimum);
+            maximum.push(input_raster[i].configs.maximum);
+
+            if rows == -1 || columns == -1 {
+                rows = input_raster[i].configs.rows as isize;
+                columns = input_raster[i].configs.columns as isize;
+                if num_classes < 2 || num_classes as isize > (rows * columns) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Number of classes should be between 2 and rows x columns.",
+                    ));
+                }
+                if min_class_size > ((rows * columns) as usize / num_classes) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Min class size should be less than rows x columns / num_classes.",
+                    ));
+                }
+            } else {
+                if input_raster[i].configs.rows as isize != rows
+                    || input_raster[i].configs.columns as isize != columns
+                {
+                    return Err(Error::new(ErrorKind::InvalidInput,
+                        "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                }
             }
         }
 
@@ -486,7 +504,7 @@ impl WhiteboxTool for KMeansClustering {
                             is_valid_data = true;
                             for i in 0..num_files {
                                 value[i] = input_raster[i].get_value(row, col);
-                                if value[i] == nodata[i] {
+                                if is_nodata_value(value[i], nodata[i]) {
                                     is_valid_data = false;
                                     break;
                                 }
@@ -536,7 +554,7 @@ impl WhiteboxTool for KMeansClustering {
                         which_class = data[col as usize] as usize;
                         z = output.get_value(row, col);
                         class = z as usize - 1usize;
-                        if z == out_nodata || which_class != class {
+                        if is_nodata_value(z, out_nodata) || which_class != class {
                             cells_changed += 1f64;
                             output.set_value(row, col, which_class as f64 + 1f64);
                         }
@@ -741,7 +759,7 @@ impl WhiteboxTool for KMeansClustering {
                     &format!(
                         "<strong>Image {}</strong>: {}<br>",
                         i + 1,
-                        input_files[i]
+                        image_names[i]
                     )
                     .as_bytes(),
                 )?;
@@ -338,7 +338,7 @@ impl WhiteboxTool for KNearestMeanFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -351,7 +351,7 @@ impl WhiteboxTool for KNearestMeanFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -377,10 +377,10 @@ impl WhiteboxTool for KNearestMeanFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for i in 0..num_cells {
                                 zn = input_fn(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     neighbour_vals[i] = zn;
                                 } else {
                                     neighbour_vals[i] = f64::INFINITY;
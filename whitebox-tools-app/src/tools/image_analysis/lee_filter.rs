@@ -344,7 +344,7 @@ impl WhiteboxTool for LeeSigmaFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -357,7 +357,7 @@ impl WhiteboxTool for LeeSigmaFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -388,7 +388,7 @@ impl WhiteboxTool for LeeSigmaFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             upper_value = z + sigma;
                             lower_value = z - sigma;
 
@@ -396,7 +396,10 @@ impl WhiteboxTool for LeeSigmaFilter {
                             sum = 0f64;
                             for a in 0..num_cells {
                                 zn = input_fn(row + dy[a], col + dx[a]);
-                                if zn >= lower_value && zn <= upper_value && zn != nodata {
+                                if zn >= lower_value
+                                    && zn <= upper_value
+                                    && !is_nodata_value(zn, nodata)
+                                {
                                     n += 1f64;
                                     sum += zn;
                                 }
@@ -409,7 +412,7 @@ impl WhiteboxTool for LeeSigmaFilter {
                                 sum = 0f64;
                                 for a in 0..8 {
                                     zn = input_fn(row + dy1[a], col + dx1[a]);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         n += 1f64;
                                         sum += zn;
                                     }
@@ -322,12 +322,12 @@ impl WhiteboxTool for LineDetectionFilter {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 sum = 0.0;
                                 for i in 0..num_pixels_in_filter {
-                                    zn = input[(row + dy[i], col + dx[i])];
-                                    if zn == nodata {
+                                    zn = input.get_value(row + dy[i], col + dx[i]);
+                                    if is_nodata_value(zn, nodata) {
                                         zn = z; // replace it with z
                                     }
                                     sum += zn * weights[i];
@@ -341,12 +341,12 @@ impl WhiteboxTool for LineDetectionFilter {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 sum = 0.0;
                                 for i in 0..num_pixels_in_filter {
-                                    zn = input[(row + dy[i], col + dx[i])];
-                                    if zn == nodata {
+                                    zn = input.get_value(row + dy[i], col + dx[i]);
+                                    if is_nodata_value(zn, nodata) {
                                         zn = z; // replace it with z
                                     }
                                     sum += zn * weights[i];
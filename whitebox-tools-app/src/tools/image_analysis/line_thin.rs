@@ -210,9 +210,11 @@ impl WhiteboxTool for LineThinning {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input[(row, col)] > 0.0 && input[(row, col)] != nodata {
+                        if input.get_value(row, col) > 0.0
+                            && !is_nodata_value(input.get_value(row, col), nodata)
+                        {
                             data[col as usize] = 1.0;
-                        } else if input[(row, col)] == 0.0 {
+                        } else if input.get_value(row, col) == 0.0 {
                             data[col as usize] = 0.0;
                         }
                     }
@@ -267,7 +269,7 @@ impl WhiteboxTool for LineThinning {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = output.get_value(row, col);
-                        if z > 0.0 && z != nodata {
+                        if z > 0.0 && !is_nodata_value(z, nodata) {
                             // fill the neighbours array
                             for i in 0..8 {
                                 neighbours[i] = output.get_value(row + dy[i], col + dx[i]);
@@ -293,7 +295,7 @@ impl WhiteboxTool for LineThinning {
                                 }
 
                                 if pattern_match {
-                                    output[(row, col)] = 0.0;
+                                    output.set_value(row, col, 0.0);
                                     did_something = true;
                                 }
                             }
@@ -320,7 +320,7 @@ impl WhiteboxTool for LaplacianOfGaussianFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -334,14 +334,14 @@ impl WhiteboxTool for LaplacianOfGaussianFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum = 0.0;
                             z_final = 0.0;
                             for a in 0..num_pixels_in_filter {
                                 x = col + d_x[a];
                                 y = row + d_y[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     sum += weights[a];
                                     z_final += weights[a] * zn;
                                 }
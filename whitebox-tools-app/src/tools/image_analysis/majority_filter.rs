@@ -335,7 +335,7 @@ impl WhiteboxTool for MajorityFilter {
                             // remove the trailing column from the histo
                             for row2 in start_row..end_row + 1 {
                                 z = input.get_value(row2, start_col - 1);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     histo[bin_val] -= 1;
                                     if histo[bin_val] == 0 {
@@ -347,7 +347,7 @@ impl WhiteboxTool for MajorityFilter {
                             // add the leading column to the histo
                             for row2 in start_row..end_row + 1 {
                                 z = input.get_value(row2, end_col);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     histo[bin_val] += 1;
                                     if histo[bin_val] > histo[mode_bin] {
@@ -376,7 +376,7 @@ impl WhiteboxTool for MajorityFilter {
                             for col2 in start_col..end_col + 1 {
                                 for row2 in start_row..end_row + 1 {
                                     z = input.get_value(row2, col2);
-                                    if z != nodata {
+                                    if !is_nodata_value(z, nodata) {
                                         bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                         histo[bin_val] += 1;
                                         if histo[bin_val] > mode_freq {
@@ -390,7 +390,7 @@ impl WhiteboxTool for MajorityFilter {
                                 }
                             }
                         }
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             data[col as usize] = (mode_bin as f64 + min_val_mult) / multiplier;
                         }
                     }
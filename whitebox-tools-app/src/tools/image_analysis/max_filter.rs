@@ -304,7 +304,7 @@ impl WhiteboxTool for MaximumFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -317,7 +317,7 @@ impl WhiteboxTool for MaximumFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -344,7 +344,7 @@ impl WhiteboxTool for MaximumFilter {
                             max_val = f64::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input_fn(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n > max_val {
                                         max_val = z_n;
                                     }
@@ -359,7 +359,7 @@ impl WhiteboxTool for MaximumFilter {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input_fn(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -369,7 +369,7 @@ impl WhiteboxTool for MaximumFilter {
                             }
                         }
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             max_val = f64::NEG_INFINITY;
                             for i in 0..filter_size_x {
                                 if filter_max_vals[i] > max_val {
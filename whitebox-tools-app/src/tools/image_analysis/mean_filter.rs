@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 25/06/2017
-Last Modified: 22/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -14,6 +14,7 @@ use std::f64;
 use std::f64::consts::PI;
 use std::i32;
 use std::io::{Error, ErrorKind};
+use std::ops::Range;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
@@ -271,171 +272,62 @@ impl WhiteboxTool for MeanFilter {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
-        if verbose {
-            println!("Reading data...")
-        };
-
-        let input = Arc::new(Raster::new(&input_file, "r")?);
-
-        let start = Instant::now();
-
-        let rows = input.configs.rows as isize;
-        let columns = input.configs.columns as isize;
-        let nodata = input.configs.nodata;
-
-        let is_rgb_image = if input.configs.data_type == DataType::RGB24
-            || input.configs.data_type == DataType::RGBA32
-            || input.configs.photometric_interp == PhotometricInterpretation::RGB
-        {
-            true
-        } else {
-            false
-        };
-
-        let min_val = if !is_rgb_image {
-            input.configs.minimum
-        } else {
-            0f64
-        };
-
-        // create the integral images
-        let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
-        let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
-
-        let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-            Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-        } else {
-            Box::new(|row: isize, col: isize| -> f64 {
-                let value = input.get_value(row, col);
-                if value != nodata {
-                    return value2i(value);
-                }
-                nodata
-            })
-        };
-
-        let mut val: f64;
-        let mut sum: f64;
-        let mut sum_n: i32;
-        let mut i_prev: f64;
-        let mut n_prev: i32;
-        for row in 0..rows {
-            sum = 0f64;
-            sum_n = 0;
-            for col in 0..columns {
-                val = input_fn(row, col);
-                if val == nodata {
-                    val = 0f64;
-                } else {
-                    val -= min_val;
-                    sum_n += 1;
-                }
-                sum += val;
-                if row > 0 {
-                    i_prev = integral[(row - 1, col)];
-                    n_prev = integral_n[(row - 1, col)];
-                    integral[(row, col)] = sum + i_prev;
-                    integral_n[(row, col)] = sum_n + n_prev;
-                } else {
-                    integral[(row, col)] = sum;
-                    integral_n[(row, col)] = sum_n;
-                }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
-
-        let i = Arc::new(integral); // wrap integral in an Arc
-        let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
-        let mut output = Raster::initialize_using_file(&output_file, &input);
-        let (tx, rx) = mpsc::channel();
         let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
         let max_procs = configs.max_procs;
         if max_procs > 0 && max_procs < num_procs {
             num_procs = max_procs;
         }
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let i = i.clone();
-            let i_n = i_n.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
 
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
+        if use_streaming(&input_file, &output_file) {
+            // The image is too large to hold in memory and is instead processed in bands of rows.
+            let start = Instant::now();
+            let mut reader = GeoTiffReader::new(&input_file)?;
+            let rows = reader.configs.rows as isize;
+            let mut writer = GeoTiffWriter::new(&output_file, &reader.configs)?;
+            let halo = midpoint_y + 1;
+            let mut band_start = 0isize;
+            reader.stream_rows(&mut writer, halo as usize, |band, first_row, last_row| {
+                // the rows of the band that lie within the grid
+                let grid_rows = (halo - band_start).max(0)..(rows - band_start + halo).min(band.configs.rows as isize);
+                let rx = filter_rows(band, first_row..last_row, grid_rows, midpoint_x, midpoint_y, num_procs, false)?;
+                let mut data = vec![vec![]; (last_row - first_row) as usize];
+                for row in first_row..last_row {
+                    let (r, values) = rx.recv().expect("Error receiving data from thread.");
+                    data[(r - first_row) as usize] = values;
+                    if verbose {
+                        let progress = (100.0_f64 * (band_start + row - first_row) as f64 / (rows - 1).max(1) as f64) as usize;
+                        if progress != old_progress {
+                            println!("Progress: {}%", progress);
+                            old_progress = progress;
                         }
-                        nodata
-                    })
-                };
-
-                let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                let mut n: i32;
-                let mut sum: f64;
-                let mut mean: f64;
-                let mut z: f64;
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    y1 = row - midpoint_y - 1;
-                    if y1 < 0 {
-                        y1 = 0;
-                    }
-
-                    y2 = row + midpoint_y;
-                    if y2 >= rows {
-                        y2 = rows - 1;
                     }
-                    let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input_fn(row, col);
-                        if z != nodata {
-                            x1 = col - midpoint_x - 1;
-                            if x1 < 0 {
-                                x1 = 0;
-                            }
-
-                            x2 = col + midpoint_x;
-                            if x2 >= columns {
-                                x2 = columns - 1;
-                            }
-                            n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
-                            if n > 0 {
-                                sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
-                                mean = sum / n as f64 + min_val;
-                                data[col as usize] = output_fn(row, col, mean);
-                            } else {
-                                data[col as usize] = output_fn(row, col, 0f64);
-                            }
-                        }
-                    }
-
-                    tx1.send((row, data)).unwrap();
                 }
-            });
+                band_start += last_row - first_row;
+                Ok(data)
+            })?;
+            writer.finish()?;
+
+            let elapsed_time = get_formatted_elapsed_time(start);
+            if verbose {
+                println!("Output file written");
+                println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time));
+            }
+            return Ok(());
         }
 
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let rx = filter_rows(input.clone(), 0..rows, 0..rows, midpoint_x, midpoint_y, num_procs, verbose)?;
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
@@ -481,6 +373,175 @@ impl WhiteboxTool for MeanFilter {
     }
 }
 
+/// Applies the mean filter to a range of rows of `input`. The filter window is truncated
+/// at the edges of the grid, which are given by `grid_rows` and the columns of `input`.
+/// Each row is sent on the returned channel, in no particular order, as it is completed.
+fn filter_rows(
+    input: Arc<Raster>,
+    rows: Range<isize>,
+    grid_rows: Range<isize>,
+    midpoint_x: isize,
+    midpoint_y: isize,
+    num_procs: isize,
+    verbose: bool,
+) -> Result<mpsc::Receiver<(isize, Vec<f64>)>, Error> {
+    let (first_row, last_row) = (rows.start, rows.end);
+    let (grid_start, grid_end) = (grid_rows.start, grid_rows.end);
+    let mut old_progress: usize = 1;
+
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+
+    let is_rgb_image = if input.configs.data_type == DataType::RGB24
+        || input.configs.data_type == DataType::RGBA32
+        || input.configs.photometric_interp == PhotometricInterpretation::RGB
+    {
+        true
+    } else {
+        false
+    };
+
+    let min_val = if !is_rgb_image {
+        input.configs.minimum
+    } else {
+        0f64
+    };
+
+    // create the integral images
+    let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+    let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
+
+    let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
+        Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+    } else {
+        Box::new(|row: isize, col: isize| -> f64 {
+            let value = input.get_value(row, col);
+            if !is_nodata_value(value, nodata) {
+                return value2i(value);
+            }
+            nodata
+        })
+    };
+
+    let mut val: f64;
+    let mut sum: f64;
+    let mut sum_n: i32;
+    let mut i_prev: f64;
+    let mut n_prev: i32;
+    for row in 0..rows {
+        sum = 0f64;
+        sum_n = 0;
+        for col in 0..columns {
+            val = input_fn(row, col);
+            if is_nodata_value(val, nodata) {
+                val = 0f64;
+            } else {
+                val -= min_val;
+                sum_n += 1;
+            }
+            sum += val;
+            if row > 0 {
+                i_prev = integral[(row - 1, col)];
+                n_prev = integral_n[(row - 1, col)];
+                integral[(row, col)] = sum + i_prev;
+                integral_n[(row, col)] = sum_n + n_prev;
+            } else {
+                integral[(row, col)] = sum;
+                integral_n[(row, col)] = sum_n;
+            }
+        }
+        if verbose {
+            let progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Creating integral images: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    let i = Arc::new(integral); // wrap integral in an Arc
+    let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let i = i.clone();
+        let i_n = i_n.clone();
+        let tx1 = tx.clone();
+        thread::spawn(move || {
+            let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
+                Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+            } else {
+                Box::new(|row: isize, col: isize| -> f64 {
+                    let value = input.get_value(row, col);
+                    if !is_nodata_value(value, nodata) {
+                        return value2i(value);
+                    }
+                    nodata
+                })
+            };
+
+            let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
+                // simply return the value.
+                Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
+            } else {
+                // convert it back into an rgb value, using the modified intensity value.
+                Box::new(|row: isize, col: isize, value: f64| -> f64 {
+                    if !is_nodata_value(value, nodata) {
+                        let (h, s, _) = value2hsi(input.get_value(row, col));
+                        return hsi2value(h, s, value);
+                    }
+                    nodata
+                })
+            };
+
+            let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
+            let mut n: i32;
+            let mut sum: f64;
+            let mut mean: f64;
+            let mut z: f64;
+            for row in (first_row..last_row).filter(|r| r % num_procs == tid) {
+                y1 = row - midpoint_y - 1;
+                if y1 < grid_start {
+                    y1 = grid_start;
+                }
+
+                y2 = row + midpoint_y;
+                if y2 >= grid_end {
+                    y2 = grid_end - 1;
+                }
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    z = input_fn(row, col);
+                    if !is_nodata_value(z, nodata) {
+                        x1 = col - midpoint_x - 1;
+                        if x1 < 0 {
+                            x1 = 0;
+                        }
+
+                        x2 = col + midpoint_x;
+                        if x2 >= columns {
+                            x2 = columns - 1;
+                        }
+                        n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
+                        if n > 0 {
+                            sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
+                            mean = sum / n as f64 + min_val;
+                            data[col as usize] = output_fn(row, col, mean);
+                        } else {
+                            data[col as usize] = output_fn(row, col, 0f64);
+                        }
+                    }
+                }
+
+                tx1.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    Ok(rx)
+}
+
 fn value2i(value: f64) -> f64 {
     let r = (value as u32 & 0xFF) as f64 / 255f64;
     let g = ((value as u32 >> 8) & 0xFF) as f64 / 255f64;
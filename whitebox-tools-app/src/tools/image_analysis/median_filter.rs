@@ -368,7 +368,7 @@ impl WhiteboxTool for MedianFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -381,7 +381,7 @@ impl WhiteboxTool for MedianFilter {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
@@ -417,7 +417,7 @@ impl WhiteboxTool for MedianFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -445,12 +445,12 @@ impl WhiteboxTool for MedianFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         bin_val = binned_data.get_value(row, col);
-                        if bin_val != bin_nodata {
-                            if old_median != bin_nodata {
+                        if !is_nodata_value(bin_val, bin_nodata) {
+                            if !is_nodata_value(old_median, bin_nodata) {
                                 // remove the trailing column from the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col - midpoint_x - 1);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] -= 1;
                                         n -= 1.0;
                                         if bin_val_n < old_median {
@@ -462,7 +462,7 @@ impl WhiteboxTool for MedianFilter {
                                 // add the leading column to the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col + midpoint_x);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] += 1;
                                         n += 1.0;
                                         if bin_val_n < old_median {
@@ -504,7 +504,7 @@ impl WhiteboxTool for MedianFilter {
                                 for col2 in start_col..end_col + 1 {
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col2);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata_value(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] += 1;
                                             n += 1f64;
                                         }
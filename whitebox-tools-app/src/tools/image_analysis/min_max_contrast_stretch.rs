@@ -336,7 +336,7 @@ impl WhiteboxTool for MinMaxContrastStretch {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let v = value2i(value);
                             return v;
                         }
@@ -348,7 +348,7 @@ impl WhiteboxTool for MinMaxContrastStretch {
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value / num_tones);
                             return ret;
@@ -363,7 +363,7 @@ impl WhiteboxTool for MinMaxContrastStretch {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input_fn(row, col);
-                        if z_in != nodata {
+                        if !is_nodata_value(z_in, nodata) {
                             z_out = ((z_in - min_val) / value_range * num_tones).floor();
                             if z_out < 0f64 {
                                 z_out = 0f64;
@@ -499,7 +499,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                             is_valid_data = true;
                             for i in 0..num_files {
                                 value[i] = input_raster[i].get_value(row, col);
-                                if value[i] == nodata[i] {
+                                if is_nodata_value(value[i], nodata[i]) {
                                     is_valid_data = false;
                                     break;
                                 }
@@ -541,7 +541,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                         which_class = data[col as usize] as usize;
                         z = output.get_value(row, col);
                         class = z as usize - 1usize;
-                        if z == out_nodata || which_class != class {
+                        if is_nodata_value(z, out_nodata) || which_class != class {
                             cells_changed += 1f64;
                             output.set_value(row, col, which_class as f64 + 1f64);
                         }
@@ -487,7 +487,7 @@ impl WhiteboxTool for Mosaic {
                                 col_src = inputs[i].get_column_from_x(x[col as usize]);
                                 z = inputs[i].get_value(row_src, col_src);
 
-                                if z != nodata_vals[i] {
+                                if !is_nodata_value(z, nodata_vals[i]) {
                                     data[col as usize] = z;
                                     break;
                                 }
@@ -501,7 +501,7 @@ impl WhiteboxTool for Mosaic {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
-                    if data[col as usize] != nodata {
+                    if !is_nodata_value(data[col as usize], nodata) {
                         output.set_value(row, col, data[col as usize]);
                     }
                 }
@@ -569,10 +569,12 @@ impl WhiteboxTool for Mosaic {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata_vals[i])
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata_value(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -597,7 +599,7 @@ impl WhiteboxTool for Mosaic {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata_value(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
@@ -660,10 +662,12 @@ impl WhiteboxTool for Mosaic {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata_vals[i])
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata_value(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -688,7 +692,7 @@ impl WhiteboxTool for Mosaic {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata_value(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
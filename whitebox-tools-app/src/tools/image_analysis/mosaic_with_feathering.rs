@@ -451,7 +451,7 @@ impl WhiteboxTool for MosaicWithFeathering {
                             row_src2 = input2.get_row_from_y(y[row as usize]);
                             col_src2 = input2.get_column_from_x(x[col as usize]);
                             z2 = input2.get_value(row_src2, col_src2);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 dist1 = dist1_raster.get_value(row_src1, col_src1) as f64;
                                 dist2 = dist2_raster.get_value(row_src2, col_src2) as f64;
                                 sum_dist =
@@ -478,9 +478,9 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     data[col as usize] =
                                         ((255u32 << 24) | (blue << 16) | (green << 8) | red) as f64;
                                 }
-                            } else if z1 != nodata1 {
+                            } else if !is_nodata_value(z1, nodata1) {
                                 data[col as usize] = z1;
-                            } else if z2 != nodata2 {
+                            } else if !is_nodata_value(z2, nodata2) {
                                 data[col as usize] = z2;
                             }
                         }
@@ -576,10 +576,12 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src1;
                                     dx = col_n as f64 - col_src1;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata1 {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata1)
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata1 {
+                                    } else if is_nodata_value(neighbour[n][0], nodata1) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -601,10 +603,12 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src2;
                                     dx = col_n as f64 - col_src2;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata2 {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata2)
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata2 {
+                                    } else if is_nodata_value(neighbour[n][0], nodata2) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -643,11 +647,13 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src1;
                                     dx = col_n as f64 - col_src1;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata1 {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata1)
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
                                         image1_valid = true;
-                                    } else if neighbour[n][0] == nodata1 {
+                                    } else if is_nodata_value(neighbour[n][0], nodata1) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -678,11 +684,13 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src2;
                                     dx = col_n as f64 - col_src2;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata2 {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata2)
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
                                         image2_valid = true;
-                                    } else if neighbour[n][0] == nodata2 {
+                                    } else if is_nodata_value(neighbour[n][0], nodata2) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -341,9 +341,11 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nir_nodata; columns as usize];
                     for col in 0..columns {
-                        z_nir = nir[(row, col)];
-                        z_red = red[(row, col)];
-                        if z_nir != nir_nodata && z_red != red_nodata {
+                        z_nir = nir.get_value(row, col);
+                        z_red = red.get_value(row, col);
+                        if !is_nodata_value(z_nir, nir_nodata)
+                            && !is_nodata_value(z_red, red_nodata)
+                        {
                             if z_nir + z_red != 0.0 || correction_factor > 0f64 {
                                 data[col as usize] =
                                     (z_nir - z_red) / (z_nir + z_red + correction_factor);
@@ -309,7 +309,7 @@ impl WhiteboxTool for OlympicFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -322,7 +322,7 @@ impl WhiteboxTool for OlympicFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -361,7 +361,7 @@ impl WhiteboxTool for OlympicFilter {
                             n = 0;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input_fn(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -387,7 +387,7 @@ impl WhiteboxTool for OlympicFilter {
                                 n = 0;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input_fn(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -405,7 +405,7 @@ impl WhiteboxTool for OlympicFilter {
                             }
                         }
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             min_val = f64::INFINITY;
                             max_val = f64::NEG_INFINITY;
                             sum = 0.0;
@@ -310,7 +310,7 @@ impl WhiteboxTool for Opening {
                             min_val = f64::INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -325,7 +325,7 @@ impl WhiteboxTool for Opening {
                                 min_val = f64::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -335,7 +335,7 @@ impl WhiteboxTool for Opening {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             min_val = f64::INFINITY;
                             for i in 0..filter_size_x {
                                 if filter_min_vals[i] < min_val {
@@ -391,7 +391,7 @@ impl WhiteboxTool for Opening {
                             max_val = f64::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n > max_val {
                                         max_val = z_n;
                                     }
@@ -406,7 +406,7 @@ impl WhiteboxTool for Opening {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -416,7 +416,7 @@ impl WhiteboxTool for Opening {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             max_val = f64::NEG_INFINITY;
                             for i in 0..filter_size_x {
                                 if filter_max_vals[i] > max_val {
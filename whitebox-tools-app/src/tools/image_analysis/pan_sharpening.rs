@@ -379,10 +379,13 @@ impl WhiteboxTool for PanchromaticSharpening {
             let b_range = input_b.configs.display_max - input_b.configs.display_min;
             for row in 0..rows_ms {
                 for col in 0..columns_ms {
-                    r = input_r[(row, col)];
-                    g = input_g[(row, col)];
-                    b = input_b[(row, col)];
-                    if r != nodata_r && g != nodata_g && b != nodata_b {
+                    r = input_r.get_value(row, col);
+                    g = input_g.get_value(row, col);
+                    b = input_b.get_value(row, col);
+                    if !is_nodata_value(r, nodata_r)
+                        && !is_nodata_value(g, nodata_g)
+                        && !is_nodata_value(b, nodata_b)
+                    {
                         r = (r - r_min) / r_range * 255f64;
                         if r < 0f64 {
                             r = 0f64;
@@ -462,10 +465,12 @@ impl WhiteboxTool for PanchromaticSharpening {
                         for col in 0..columns_pan {
                             x = pan.get_x_from_column(col);
                             source_col = get_column_from_x(x);
-                            z_pan = pan[(row, col)];
+                            z_pan = pan.get_value(row, col);
                             z_ms = input[(source_row, source_col)];
 
-                            if z_ms != nodata_ms && z_pan != nodata_pan {
+                            if !is_nodata_value(z_ms, nodata_ms)
+                                && !is_nodata_value(z_pan, nodata_pan)
+                            {
                                 p = (z_pan - pan_min) / pan_range;
                                 if p < 0f64 {
                                     p = 0f64;
@@ -529,7 +534,7 @@ impl WhiteboxTool for PanchromaticSharpening {
                     for row in (0..rows_ms).filter(|row_val| row_val % num_procs == tid) {
                         for col in 0..columns_ms {
                             z = input[(row, col)];
-                            if z != nodata_ms {
+                            if !is_nodata_value(z, nodata_ms) {
                                 r = (z as u32 & 0xFF) as f64;
                                 g = ((z as u32 >> 8) & 0xFF) as f64;
                                 b = ((z as u32 >> 16) & 0xFF) as f64;
@@ -590,9 +595,11 @@ impl WhiteboxTool for PanchromaticSharpening {
                         for col in 0..columns_pan {
                             x = pan.get_x_from_column(col);
                             source_col = get_column_from_x(x);
-                            z_pan = pan[(row, col)];
+                            z_pan = pan.get_value(row, col);
                             z_ms = input[(source_row, source_col)];
-                            if z_ms != nodata_ms && z_pan != nodata_pan {
+                            if !is_nodata_value(z_ms, nodata_ms)
+                                && !is_nodata_value(z_pan, nodata_pan)
+                            {
                                 p = (z_pan - pan_min) / pan_range;
                                 if p < 0f64 {
                                     p = 0f64;
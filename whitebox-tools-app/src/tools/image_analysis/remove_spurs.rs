@@ -239,9 +239,11 @@ impl WhiteboxTool for RemoveSpurs {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input[(row, col)] > 0.0 && input[(row, col)] != nodata {
+                        if input.get_value(row, col) > 0.0
+                            && !is_nodata_value(input.get_value(row, col), nodata)
+                        {
                             data[col as usize] = 1.0;
-                        } else if input[(row, col)] == 0.0 {
+                        } else if input.get_value(row, col) == 0.0 {
                             data[col as usize] = 0.0;
                         }
                     }
@@ -288,11 +290,11 @@ impl WhiteboxTool for RemoveSpurs {
                 for a in 0..8 {
                     for row in 0..rows {
                         for col in 0..columns {
-                            z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            z = output.get_value(row, col);
+                            if z > 0.0 && !is_nodata_value(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
-                                    neighbours[i] = output[(row + dy[i], col + dx[i])];
+                                    neighbours[i] = output.get_value(row + dy[i], col + dx[i]);
                                 }
 
                                 // scan through element
@@ -303,7 +305,7 @@ impl WhiteboxTool for RemoveSpurs {
                                     }
                                 }
                                 if pattern_match {
-                                    output[(row, col)] = 0.0;
+                                    output.set_value(row, col, 0.0);
                                     did_something = true;
                                 }
                             }
@@ -321,11 +323,11 @@ impl WhiteboxTool for RemoveSpurs {
                 for a in 0..8 {
                     for row in (0..rows).rev() {
                         for col in (0..columns).rev() {
-                            z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            z = output.get_value(row, col);
+                            if z > 0.0 && !is_nodata_value(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
-                                    neighbours[i] = output[(row + dy[i], col + dx[i])];
+                                    neighbours[i] = output.get_value(row + dy[i], col + dx[i]);
                                 }
 
                                 // scan through element
@@ -336,7 +338,7 @@ impl WhiteboxTool for RemoveSpurs {
                                     }
                                 }
                                 if pattern_match {
-                                    output[(row, col)] = 0.0;
+                                    output.set_value(row, col, 0.0);
                                     did_something = true;
                                 }
                             }
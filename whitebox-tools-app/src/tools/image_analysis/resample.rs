@@ -413,7 +413,7 @@ impl WhiteboxTool for Resample {
                                 // row_src = ((inputs[i].configs.north - y[row as usize]) / inputs[i].configs.resolution_y).round() as isize;
                                 // col_src = ((x[col as usize] - inputs[i].configs.west) / inputs[i].configs.resolution_x).round() as isize;
                                 z = inputs[i].get_value(row_src, col_src);
-                                if z != nodata_vals[i] {
+                                if !is_nodata_value(z, nodata_vals[i]) {
                                     data[col as usize] = z;
                                     break;
                                 }
@@ -426,7 +426,7 @@ impl WhiteboxTool for Resample {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
-                    if data[col as usize] != nodata {
+                    if !is_nodata_value(data[col as usize], nodata) {
                         output.set_value(row, col, data[col as usize]);
                     }
                 }
@@ -483,10 +483,12 @@ impl WhiteboxTool for Resample {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata_vals[i])
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata_value(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -511,7 +513,7 @@ impl WhiteboxTool for Resample {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata_value(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
@@ -566,10 +568,12 @@ impl WhiteboxTool for Resample {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64
+                                        && !is_nodata_value(neighbour[n][0], nodata_vals[i])
+                                    {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata_value(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -594,7 +598,7 @@ impl WhiteboxTool for Resample {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata_value(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
@@ -392,10 +392,13 @@ impl WhiteboxTool for RgbToIhs {
                         let mut hue_data = vec![nodata_r; columns as usize];
                         let mut saturation_data = vec![nodata_r; columns as usize];
                         for col in 0..columns {
-                            red = input_r[(row, col)];
-                            green = input_g[(row, col)];
-                            blue = input_b[(row, col)];
-                            if red != nodata_r && green != nodata_g && blue != nodata_b {
+                            red = input_r.get_value(row, col);
+                            green = input_g.get_value(row, col);
+                            blue = input_b.get_value(row, col);
+                            if !is_nodata_value(red, nodata_r)
+                                && !is_nodata_value(green, nodata_g)
+                                && !is_nodata_value(blue, nodata_b)
+                            {
                                 // r = ((red - red_min) / (red_max - red_min) * 255f64) as u32;
                                 // if r > 255u32 {
                                 //     r = 255u32;
@@ -543,8 +546,8 @@ impl WhiteboxTool for RgbToIhs {
                     let mut z: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 r = (z as u32 & 0xFF) as f64;
                                 g = ((z as u32 >> 8) & 0xFF) as f64;
                                 b = ((z as u32 >> 16) & 0xFF) as f64;
@@ -611,8 +614,8 @@ impl WhiteboxTool for RgbToIhs {
                         let mut hue_data = vec![nodata; columns as usize];
                         let mut saturation_data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 // r = (z as u32 & 0xFF) as f64;
                                 // g = ((z as u32 >> 8) & 0xFF) as f64;
                                 // b = ((z as u32 >> 16) & 0xFF) as f64;
@@ -271,7 +271,7 @@ impl WhiteboxTool for RobertsCrossFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -284,7 +284,7 @@ impl WhiteboxTool for RobertsCrossFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -297,17 +297,17 @@ impl WhiteboxTool for RobertsCrossFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z1 = input_fn(row, col);
-                        if z1 != nodata {
+                        if !is_nodata_value(z1, nodata) {
                             z2 = input_fn(row, col + 1);
-                            if z2 == nodata {
+                            if is_nodata_value(z2, nodata) {
                                 z2 = z1;
                             }
                             z3 = input_fn(row + 1, col);
-                            if z3 == nodata {
+                            if is_nodata_value(z3, nodata) {
                                 z3 = z1;
                             }
                             z4 = input_fn(row + 1, col + 1);
-                            if z4 == nodata {
+                            if is_nodata_value(z4, nodata) {
                                 z4 = z1;
                             }
 
@@ -274,7 +274,7 @@ impl WhiteboxTool for ScharrFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -295,12 +295,12 @@ impl WhiteboxTool for ScharrFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             slope_x = 0.0;
                             slope_y = 0.0;
                             for i in 0..num_pixels_in_filter {
                                 zn = input_fn(row + dy[i], col + dx[i]);
-                                if zn == nodata {
+                                if is_nodata_value(zn, nodata) {
                                     zn = z; // replace it with z
                                 }
                                 slope_x += zn * mask_x[i];
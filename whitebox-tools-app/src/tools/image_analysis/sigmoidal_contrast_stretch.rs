@@ -317,7 +317,7 @@ impl WhiteboxTool for SigmoidalContrastStretch {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata_value(value, nodata) {
                         let v = value2i(value);
                         if v < min_val {
                             min_val = v;
@@ -360,7 +360,7 @@ impl WhiteboxTool for SigmoidalContrastStretch {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let v = value2i(value);
                             return v;
                         }
@@ -372,7 +372,7 @@ impl WhiteboxTool for SigmoidalContrastStretch {
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value / num_tones);
                             return ret;
@@ -387,7 +387,7 @@ impl WhiteboxTool for SigmoidalContrastStretch {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input_fn(row, col); //input[(row, col)];
-                        if z_in != nodata {
+                        if !is_nodata_value(z_in, nodata) {
                             z_out = (z_in - min_val) / value_range;
                             z_out = (1f64 / (1f64 + (gain * (cutoff - z_out)).exp()) - a) / b;
                             if z_out < 0f64 {
@@ -255,7 +255,7 @@ impl WhiteboxTool for SplitColourComposite {
                     let mut data_b = vec![output_nodata; columns as usize];
                     for col in 0..columns {
                         in_val = input.get_value(row, col);
-                        if in_val != nodata {
+                        if !is_nodata_value(in_val, nodata) {
                             val = in_val as u32;
                             red = val & 0xFF;
                             green = (val >> 8) & 0xFF;
@@ -341,7 +341,7 @@ impl WhiteboxTool for TophatTransform {
                                 min_val = f64::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -356,7 +356,7 @@ impl WhiteboxTool for TophatTransform {
                                     min_val = f64::INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata_value(z_n, nodata) {
                                             if z_n < min_val {
                                                 min_val = z_n;
                                             }
@@ -366,7 +366,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 min_val = f64::INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_min_vals[i] < min_val {
@@ -424,7 +424,7 @@ impl WhiteboxTool for TophatTransform {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -439,7 +439,7 @@ impl WhiteboxTool for TophatTransform {
                                     max_val = f64::NEG_INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata_value(z_n, nodata) {
                                             if z_n > max_val {
                                                 max_val = z_n;
                                             }
@@ -449,7 +449,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 max_val = f64::NEG_INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_max_vals[i] > max_val {
@@ -457,7 +457,7 @@ impl WhiteboxTool for TophatTransform {
                                     }
                                 }
                                 if max_val > f64::NEG_INFINITY {
-                                    data[col as usize] = input_data[(row, col)] - max_val;
+                                    data[col as usize] = input_data.get_value(row, col) - max_val;
                                 }
                             }
                         }
@@ -536,7 +536,7 @@ impl WhiteboxTool for TophatTransform {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -551,7 +551,7 @@ impl WhiteboxTool for TophatTransform {
                                     max_val = f64::NEG_INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata_value(z_n, nodata) {
                                             if z_n > max_val {
                                                 max_val = z_n;
                                             }
@@ -561,7 +561,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 max_val = f64::NEG_INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_max_vals[i] > max_val {
@@ -619,7 +619,7 @@ impl WhiteboxTool for TophatTransform {
                                 min_val = f64::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -634,7 +634,7 @@ impl WhiteboxTool for TophatTransform {
                                     min_val = f64::INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata_value(z_n, nodata) {
                                             if z_n < min_val {
                                                 min_val = z_n;
                                             }
@@ -644,7 +644,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 min_val = f64::INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_min_vals[i] < min_val {
@@ -652,7 +652,7 @@ impl WhiteboxTool for TophatTransform {
                                     }
                                 }
                                 if min_val < f64::INFINITY {
-                                    data[col as usize] = min_val - input_data[(row, col)];
+                                    data[col as usize] = min_val - input_data.get_value(row, col);
                                 }
                             }
                         }
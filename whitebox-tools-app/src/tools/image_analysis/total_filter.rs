@@ -288,8 +288,8 @@ impl WhiteboxTool for TotalFilter {
         for row in 0..rows {
             sum = 0f64;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     val -= min_val;
@@ -345,8 +345,8 @@ impl WhiteboxTool for TotalFilter {
                     }
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input_data[(row, col)];
-                        if z != nodata {
+                        z = input_data.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint_x - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -359,7 +359,7 @@ impl WhiteboxTool for UnsharpMasking {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -372,7 +372,7 @@ impl WhiteboxTool for UnsharpMasking {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let i = if value < 0f64 {
                                 0f64
@@ -395,12 +395,12 @@ impl WhiteboxTool for UnsharpMasking {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum = 0.0;
                             z_final = 0.0;
                             for a in 0..num_pixels_in_filter {
                                 zn = input_fn(row + d_y[a], col + d_x[a]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     sum += weights[a];
                                     z_final += weights[a] * zn;
                                 }
@@ -332,7 +332,7 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -345,7 +345,7 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata_value(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -362,14 +362,14 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
                     if normalize {
                         for col in 0..columns {
                             z = input_fn(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 sum_weights = 0.0;
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input_fn(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         sum_weights += weights[a];
                                         z_final += weights[a] * zn;
                                     }
@@ -382,13 +382,13 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
                     } else {
                         for col in 0..columns {
                             z = input_fn(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input_fn(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         z_final += weights[a] * zn;
                                     }
                                 }
@@ -302,10 +302,13 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata_r; columns as usize];
                     for col in 0..columns {
-                        red_val = input_r[(row, col)];
-                        green_val = input_g[(row, col)];
-                        blue_val = input_b[(row, col)];
-                        if red_val != nodata_r && green_val != nodata_g && blue_val != nodata_b {
+                        red_val = input_r.get_value(row, col);
+                        green_val = input_g.get_value(row, col);
+                        blue_val = input_b.get_value(row, col);
+                        if !is_nodata_value(red_val, nodata_r)
+                            && !is_nodata_value(green_val, nodata_g)
+                            && !is_nodata_value(blue_val, nodata_b)
+                        {
                             red_val = (red_val - red_min) / red_range * 255f64;
                             if red_val < 0f64 {
                                 red_val = 0f64;
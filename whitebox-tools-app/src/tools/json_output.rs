@@ -0,0 +1,522 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::{ToolError, ToolErrorKind, ToolManager};
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Error};
+use std::path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Instant;
+
+/// Set in the environment of the whitebox_tools process that runs a tool on behalf of
+/// `--json` mode, a workflow, or tiled processing. It forces verbose output, without
+/// changing the settings.json file, and makes the process report the output files and
+/// the result of the tool, including any panic, as `ToolEvent`s written to stderr.
+pub const JSON_CHILD_ENV_VAR: &str = "WHITEBOX_TOOLS_JSON_CHILD";
+
+/// An event of a tool run. In `--json` mode, events are written to stdout as JSON objects,
+/// one per line, with the name of the event (e.g. "progress") as the value of the "event" key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ToolEvent {
+    Start {
+        tool: String,
+        working_directory: String,
+        args: Vec<String>,
+    },
+    Progress {
+        label: String,
+        percent: f64,
+    },
+    Warning {
+        message: String,
+    },
+    Message {
+        message: String,
+    },
+    /// A file created by the tool, as identified by one of its NewFile parameters.
+    Output {
+        flag: String,
+        file_type: String,
+        path: String,
+        exists: bool,
+        size_bytes: Option<u64>,
+    },
+    /// Reported by the tool process when the tool returns successfully. It is folded into
+    /// the summary rather than being passed on.
+    Finished {
+        elapsed_seconds: f64,
+    },
+    Error {
+        category: String,
+        message: String,
+        exit_code: i32,
+    },
+    Summary {
+        tool: String,
+        status: String,
+        exit_code: Option<i32>,
+        elapsed_seconds: f64,
+        tool_elapsed_seconds: Option<f64>,
+        num_warnings: usize,
+        outputs: Vec<String>,
+        output_bytes: u64,
+    },
+}
+
+impl ToolEvent {
+    pub fn error(kind: ToolErrorKind, message: &str) -> ToolEvent {
+        ToolEvent::Error {
+            category: kind.as_str().to_string(),
+            message: message.to_string(),
+            exit_code: kind.exit_code(),
+        }
+    }
+
+    /// Converts a line of the console output of a tool into an event. Tools print their
+    /// progress as 'Label: 45%' and their warnings as 'Warning: ...'; any other line is a
+    /// message. Blank lines and the rows of asterisks around tool names are dropped.
+    pub fn from_console_line(line: &str) -> Option<ToolEvent> {
+        let line = line.trim();
+        if line.is_empty() || line.chars().all(|c| c == '*') {
+            return None;
+        }
+        if let Some((label, value)) = line.rsplit_once(':') {
+            if let Some(Ok(percent)) = value
+                .trim()
+                .strip_suffix('%')
+                .map(|v| v.trim().parse::<f64>())
+            {
+                return Some(ToolEvent::Progress {
+                    label: label.trim().to_string(),
+                    percent,
+                });
+            }
+        }
+        if line.to_lowercase().starts_with("warning") {
+            return Some(ToolEvent::Warning {
+                message: line.to_string(),
+            });
+        }
+        Some(ToolEvent::Message {
+            message: line.to_string(),
+        })
+    }
+
+    /// Parses an event written by `to_json_line`, returning `None` for any other text.
+    pub fn from_json_line(line: &str) -> Option<ToolEvent> {
+        serde_json::from_str(line.trim()).ok()
+    }
+
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("Error serializing a tool event.")
+    }
+
+    /// Writes the event to stdout, as in `--json` mode.
+    pub fn emit(&self) {
+        println!("{}", self.to_json_line());
+    }
+
+    /// Writes the event to stderr, where the tool process reports its events, since the
+    /// tool itself prints to stdout.
+    pub fn report(&self) {
+        eprintln!("{}", self.to_json_line());
+    }
+}
+
+impl ToolManager {
+    /// Runs a tool, reporting its progress, warnings, output files, and a summary as JSON
+    /// lines written to stdout, one `ToolEvent` per line.
+    ///
+    /// The tool is run in a second whitebox_tools process, so that its console output can
+    /// be converted into progress, warning, and message events, and so that a panic within
+    /// the tool cannot interrupt the JSON output. That process runs the tool with
+    /// `run_tool_reporting_events`, which reports the output files and the result of the
+    /// tool as events, and these are passed on, or folded into the summary.
+    pub fn run_tool_json(&self, tool_name: String, args: Vec<String>) -> Result<(), Error> {
+        let start = Instant::now();
+        ToolEvent::Start {
+            tool: tool_name.clone(),
+            working_directory: self.working_dir.clone(),
+            args: args.clone(),
+        }
+        .emit();
+
+        let mut command = Command::new(env::current_exe()?);
+        command.arg(format!("--run={}", tool_name));
+        if !self.working_dir.is_empty() {
+            command.arg(format!("--wd={}", self.working_dir));
+        }
+        let mut child = command
+            .args(&args)
+            .env(JSON_CHILD_ENV_VAR, "1")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stderr = child.stderr.take().expect("Error capturing tool stderr.");
+        let stderr_reader = thread::spawn(move || {
+            let mut lines = vec![];
+            for_each_line(stderr, |line| lines.push(line));
+            lines
+        });
+
+        let mut num_warnings = 0usize;
+        let stdout = child.stdout.take().expect("Error capturing tool stdout.");
+        for_each_line(stdout, |line| {
+            if let Some(event) = ToolEvent::from_console_line(&line) {
+                if let ToolEvent::Warning { .. } = event {
+                    num_warnings += 1;
+                }
+                event.emit();
+            }
+        });
+
+        let status = child.wait()?;
+        let stderr_lines = stderr_reader.join().unwrap_or_default();
+
+        let mut outputs = vec![];
+        let mut output_bytes = 0u64;
+        let mut tool_elapsed_seconds = None;
+        for event in stderr_lines.iter().filter_map(|l| ToolEvent::from_json_line(l)) {
+            match event {
+                ToolEvent::Output {
+                    ref path,
+                    size_bytes,
+                    ..
+                } if status.success() => {
+                    outputs.push(path.clone());
+                    output_bytes += size_bytes.unwrap_or(0);
+                    event.emit();
+                }
+                ToolEvent::Finished { elapsed_seconds } => {
+                    tool_elapsed_seconds = Some(elapsed_seconds);
+                }
+                _ => {}
+            }
+        }
+
+        let error = if status.success() {
+            None
+        } else {
+            Some(process_error(status.code(), &stderr_lines))
+        };
+        if let Some((kind, message)) = &error {
+            ToolEvent::error(*kind, message).emit();
+        }
+        ToolEvent::Summary {
+            tool: tool_name,
+            status: if error.is_none() { "success" } else { "failure" }.to_string(),
+            exit_code: match &error {
+                Some((kind, _)) => Some(kind.exit_code()),
+                None => status.code(),
+            },
+            elapsed_seconds: start.elapsed().as_secs_f64(),
+            tool_elapsed_seconds,
+            num_warnings,
+            outputs,
+            output_bytes,
+        }
+        .emit();
+
+        match error {
+            Some((kind, message)) => Err(ToolError::new_error(kind, message)),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs a tool in the process started by `--json` mode, a workflow, or tiled processing,
+    /// i.e. with `JSON_CHILD_ENV_VAR` set. The console output of the tool is left on stdout,
+    /// and the output files of the tool, followed by a 'finished' or 'error' event, are
+    /// reported on stderr. Panics are reported by the panic hook of the process.
+    pub fn run_tool_reporting_events(&self, tool_name: String, args: Vec<String>) -> Result<(), Error> {
+        let start = Instant::now();
+        match self.run_tool(tool_name.clone(), args.clone()) {
+            Ok(()) => {
+                let elapsed_seconds = start.elapsed().as_secs_f64();
+                for event in self.get_output_events(&tool_name, &args) {
+                    event.report();
+                }
+                ToolEvent::Finished { elapsed_seconds }.report();
+                Ok(())
+            }
+            Err(err) => {
+                ToolEvent::error(ToolErrorKind::of(&err), &err.to_string()).report();
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns an output event for each of the files that a tool creates for the given
+    /// arguments, based on the NewFile parameters in its parameter description.
+    fn get_output_events(&self, tool_name: &str, args: &[String]) -> Vec<ToolEvent> {
+        let parameters = match self.get_tool(tool_name) {
+            Some(tool) => serde_json::from_str::<serde_json::Value>(&tool.get_tool_parameters())
+                .map(|v| v["parameters"].clone())
+                .unwrap_or_default(),
+            None => match self.get_plugin_list() {
+                Ok(plugins) => match plugins.get(&tool_name.to_lowercase()) {
+                    Some(plugin_data) => plugin_data["parameters"].clone(),
+                    None => serde_json::Value::Null,
+                },
+                Err(_) => serde_json::Value::Null,
+            },
+        };
+
+        let mut outputs = vec![];
+        for parameter in parameters.as_array().into_iter().flatten() {
+            let file_type = match &parameter["parameter_type"]["NewFile"] {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Object(o) => match o.keys().next() {
+                    Some(k) => k.clone(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let flags = parameter["flags"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|f| f.as_str())
+                .map(|f| f.trim_start_matches('-').to_lowercase())
+                .collect::<Vec<String>>();
+            if let Some((flag, value)) = find_arg(args, &flags) {
+                let path = self.resolve_path(&value);
+                let size_bytes = fs::metadata(&path).ok().map(|m| m.len());
+                outputs.push(ToolEvent::Output {
+                    flag,
+                    file_type,
+                    path,
+                    exists: size_bytes.is_some(),
+                    size_bytes,
+                });
+            }
+        }
+        outputs
+    }
+
+    /// Resolves a file name against the working directory, in the same way as the tools.
+    fn resolve_path(&self, file_name: &str) -> String {
+        if !file_name.contains(path::MAIN_SEPARATOR) && !file_name.contains('/') {
+            format!("{}{}", self.working_dir, file_name)
+        } else {
+            file_name.to_string()
+        }
+    }
+}
+
+/// Finds the value of the argument with one of the flags (without their leading dashes),
+/// given either as `--flag=value` or as `--flag value`.
+fn find_arg(args: &[String], flags: &[String]) -> Option<(String, String)> {
+    for i in 0..args.len() {
+        let arg = args[i].replace(['\"', '\''], "");
+        let vec = arg.splitn(2, '=').collect::<Vec<&str>>();
+        if !vec[0].starts_with('-') {
+            continue;
+        }
+        let flag = vec[0].trim_start_matches('-').to_lowercase();
+        if flags.contains(&flag) {
+            let value = if vec.len() > 1 {
+                vec[1].to_string()
+            } else {
+                args.get(i + 1)?.replace(['\"', '\''], "")
+            };
+            if !value.trim().is_empty() {
+                return Some((vec[0].to_string(), value.trim().to_string()));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the kind and message of the error of a failed tool process, from the first error
+/// event on its stderr. A process that failed without reporting an error, e.g. because it
+/// was killed, is classified by its exit code, with any other stderr output as its message.
+pub(super) fn process_error(exit_code: Option<i32>, stderr_lines: &[String]) -> (ToolErrorKind, String) {
+    for line in stderr_lines {
+        if let Some(ToolEvent::Error {
+            message, exit_code, ..
+        }) = ToolEvent::from_json_line(line)
+        {
+            let kind = ToolErrorKind::from_exit_code(exit_code).unwrap_or(ToolErrorKind::ToolFailure);
+            return (kind, message);
+        }
+    }
+    let kind = exit_code
+        .and_then(ToolErrorKind::from_exit_code)
+        .unwrap_or(ToolErrorKind::ToolFailure);
+    let lines = stderr_lines
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && ToolEvent::from_json_line(l).is_none())
+        .collect::<Vec<&str>>();
+    if lines.is_empty() {
+        return (kind, "The tool failed without reporting an error.".to_string());
+    }
+    (kind, lines.join("\n"))
+}
+
+/// Calls `f` with each line of a stream as it is read, replacing any invalid UTF-8.
+fn for_each_line<R: std::io::Read, F: FnMut(String)>(stream: R, mut f: F) {
+    let mut reader = BufReader::new(stream);
+    let mut buf = vec![];
+    while let Ok(n) = reader.read_until(b'\n', &mut buf) {
+        if n == 0 {
+            break;
+        }
+        f(String::from_utf8_lossy(&buf).trim_end().to_string());
+        buf.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::panic_message;
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn test_events_round_trip_as_json_lines() {
+        let events = vec![
+            ToolEvent::Progress {
+                label: "Progress".to_string(),
+                percent: 45.0,
+            },
+            ToolEvent::Output {
+                flag: "-o".to_string(),
+                file_type: "Raster".to_string(),
+                path: "/data/out.tif".to_string(),
+                exists: false,
+                size_bytes: None,
+            },
+            ToolEvent::Finished {
+                elapsed_seconds: 1.5,
+            },
+            ToolEvent::error(ToolErrorKind::Io, "Error reading\nthe file."),
+        ];
+        for event in events {
+            let line = event.to_json_line();
+            assert!(!line.contains('\n'));
+            assert_eq!(ToolEvent::from_json_line(&line), Some(event));
+        }
+        let line = ToolEvent::error(ToolErrorKind::License, "").to_json_line();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["event"], "error");
+        assert_eq!(value["category"], "license");
+        assert_eq!(value["exit_code"], 5);
+
+        assert_eq!(ToolEvent::from_json_line("Error: not an event"), None);
+        assert_eq!(ToolEvent::from_json_line("{\"message\": \"no event key\"}"), None);
+    }
+
+    #[test]
+    fn test_console_lines() {
+        assert_eq!(
+            ToolEvent::from_console_line("Calculating slope: 45%"),
+            Some(ToolEvent::Progress {
+                label: "Calculating slope".to_string(),
+                percent: 45.0,
+            })
+        );
+        assert_eq!(
+            ToolEvent::from_console_line("  Warning: the input is empty. "),
+            Some(ToolEvent::Warning {
+                message: "Warning: the input is empty.".to_string(),
+            })
+        );
+        assert_eq!(
+            ToolEvent::from_console_line("Elapsed Time (excluding I/O): 12ms"),
+            Some(ToolEvent::Message {
+                message: "Elapsed Time (excluding I/O): 12ms".to_string(),
+            })
+        );
+        assert_eq!(ToolEvent::from_console_line("   "), None);
+        assert_eq!(ToolEvent::from_console_line("**********"), None);
+    }
+
+    #[test]
+    fn test_process_error() {
+        let lines = vec![
+            "* Welcome *".to_string(),
+            ToolEvent::error(ToolErrorKind::UnsupportedFormat, "Unrecognized raster type.").to_json_line(),
+            ToolEvent::error(ToolErrorKind::ToolFailure, "RecvError").to_json_line(),
+            "Error: Unrecognized raster type.".to_string(),
+        ];
+        // The first error event is the cause of the failure, whatever the exit code.
+        assert_eq!(
+            process_error(Some(1), &lines),
+            (ToolErrorKind::UnsupportedFormat, "Unrecognized raster type.".to_string())
+        );
+
+        // Without an error event, the kind comes from the exit code alone.
+        let lines = vec![
+            ToolEvent::Finished { elapsed_seconds: 1.0 }.to_json_line(),
+            "out of memory".to_string(),
+        ];
+        assert_eq!(
+            process_error(Some(3), &lines),
+            (ToolErrorKind::Io, "out of memory".to_string())
+        );
+        assert_eq!(
+            process_error(None, &[]),
+            (
+                ToolErrorKind::ToolFailure,
+                "The tool failed without reporting an error.".to_string()
+            )
+        );
+        assert_eq!(process_error(Some(101), &lines).0, ToolErrorKind::ToolFailure);
+    }
+
+    #[test]
+    fn test_panics_are_not_classified_by_their_message() {
+        let payload = panic::catch_unwind(|| panic!("Error reading the file: no such file")).unwrap_err();
+        assert_eq!(ToolErrorKind::of_panic(payload.as_ref()), ToolErrorKind::ToolFailure);
+        let payload = panic::catch_unwind(|| {
+            panic::panic_any(ToolError {
+                kind: ToolErrorKind::License,
+                message: "A valid license is required.".to_string(),
+            })
+        })
+        .unwrap_err();
+        assert_eq!(ToolErrorKind::of_panic(payload.as_ref()), ToolErrorKind::License);
+        assert_eq!(
+            panic_message(payload.as_ref()),
+            "A valid license is required."
+        );
+
+        let err = Error::other("Error writing the unsupported file.");
+        assert_eq!(ToolErrorKind::of(&err), ToolErrorKind::ToolFailure);
+        let err = Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(ToolErrorKind::of(&err), ToolErrorKind::Io);
+        let err = ToolError::new_error(ToolErrorKind::UnsupportedFormat, "bad");
+        assert_eq!(ToolErrorKind::of(&err), ToolErrorKind::UnsupportedFormat);
+    }
+
+    #[test]
+    fn test_find_arg() {
+        let args = vec![
+            "-i=dem.tif".to_string(),
+            "--output".to_string(),
+            "'out file.tif'".to_string(),
+            "--empty=".to_string(),
+        ];
+        let flags = |f: &[&str]| f.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(
+            find_arg(&args, &flags(&["i", "input"])),
+            Some(("-i".to_string(), "dem.tif".to_string()))
+        );
+        assert_eq!(
+            find_arg(&args, &flags(&["o", "output"])),
+            Some(("--output".to_string(), "out file.tif".to_string()))
+        );
+        assert_eq!(find_arg(&args, &flags(&["empty"])), None);
+        assert_eq!(find_arg(&args, &flags(&["dem.tif"])), None);
+    }
+}
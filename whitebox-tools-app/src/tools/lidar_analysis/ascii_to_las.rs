@@ -22,7 +22,7 @@ use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
 /// specify the name(s) of the input ASCII file(s) (`--inputs`). Each input file will have a correspondingly named
 /// output file with a `.las` file extension. The output point data, each on a separate line, will take the format:
 ///
-/// ```
+/// ```text
 /// x,y,z,intensity,class,return,num_returns"
 /// ```
 ///
@@ -44,7 +44,7 @@ use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
 /// The `x`, `y`, and `z` patterns must always be specified. If the `rn` pattern is used, the `nr` pattern must
 /// also be specified. Examples of valid pattern string include:
 ///
-/// ```
+/// ```text
 /// 'x,y,z,i'
 /// 'x,y,z,i,rn,nr'
 /// 'x,y,z,i,c,rn,nr,sa'
@@ -387,7 +387,9 @@ impl WhiteboxTool for LidarBlockMaximum {
                 row = data.0;
                 col = data.1;
                 z = data.2;
-                if output[(row, col)] == nodata || z > output[(row, col)] {
+                if is_nodata_value(output.get_value(row, col), nodata)
+                    || z > output.get_value(row, col)
+                {
                     output.set_value(row, col, z);
                 }
                 if verbose {
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 25/04/2018
-Last Modified: 26/07/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -24,6 +24,9 @@ use std::thread;
 /// parts will be respected during clipping, i.e. LiDAR points within polygon holes will be removed from the output LAS
 /// file.
 ///
+/// When the input file is a Cloud Optimized Point Cloud (COPC), only the parts of the file that overlap the
+/// clip polygons are read and decompressed, which makes it practical to clip small areas from very large files.
+///
 /// Use the `ErasePolygonFromLidar` tool to perform the complementary operation of removing points from a LAS file
 /// that are contained within a set of polygons.
 ///
@@ -203,10 +206,31 @@ impl WhiteboxTool for ClipLidarToPolygon {
         if verbose {
             println!("Reading data...")
         };
-        let input = match LasFile::new(&input_file, "r") {
+        let polygons = Shapefile::read(&polygons_file)?;
+        let num_records = polygons.num_records;
+
+        // Only the points of COPC files within the extent of the polygons need to be read.
+        let is_laz = input_file.to_lowercase().ends_with(".laz");
+        let mut input = match LasFile::new(&input_file, if is_laz { "rh" } else { "r" }) {
             Ok(lf) => lf,
             Err(err) => panic!("Error reading file {}: {}", input_file, err),
         };
+        if is_laz {
+            if input.copc_info().is_some() {
+                let polygons_bb = BoundingBox::new(
+                    polygons.header.x_min,
+                    polygons.header.x_max,
+                    polygons.header.y_min,
+                    polygons.header.y_max,
+                );
+                input.read_bounds(polygons_bb, None)?;
+            } else {
+                input = match LasFile::new(&input_file, "r") {
+                    Ok(lf) => lf,
+                    Err(err) => panic!("Error reading file {}: {}", input_file, err),
+                };
+            }
+        }
 
         let lidar_bb = BoundingBox::new(
             input.header.min_x,
@@ -215,9 +239,6 @@ impl WhiteboxTool for ClipLidarToPolygon {
             input.header.max_y,
         );
 
-        let polygons = Shapefile::read(&polygons_file)?;
-        let num_records = polygons.num_records;
-
         let start = Instant::now();
 
         // make sure the input vector file is of polygon type
@@ -246,7 +267,7 @@ impl WhiteboxTool for ClipLidarToPolygon {
         };
 
         let n_points = input.header.number_of_points as usize;
-        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+        let num_points: f64 = input.header.number_of_points.saturating_sub(1) as f64; // used for progress calculation only
 
         let num_procs = num_cpus::get();
         let input = Arc::new(input);
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 24/07/2019
-Last Modified: 16/01/2020
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -47,6 +47,10 @@ use std::path;
 /// point clouds have had. Use the `LidarInfo` tool determine the distribution
 /// of various class values in your file.
 ///
+/// The points are read and written in chunks, rather than all at once, and so the tool can
+/// filter point clouds that are larger than the available memory. Any extra bytes attributes of the
+/// points are copied to the output file.
+///
 /// # See Also
 /// `LidarInfo`
 pub struct FilterLidarClasses {
@@ -250,11 +254,8 @@ impl WhiteboxTool for FilterLidarClasses {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
-        if verbose {
-            println!("reading input LiDAR file...");
-        }
-        let input = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
+        let mut reader = match LasReader::new(&input_file) {
+            Ok(r) => r,
             Err(err) => panic!("Error reading file {}: {}", input_file, err),
         };
 
@@ -264,19 +265,20 @@ impl WhiteboxTool for FilterLidarClasses {
             println!("Performing analysis...");
         }
 
-        let n_points = input.header.number_of_points as usize;
-        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+        let num_points: f64 = reader.header().number_of_points.saturating_sub(1) as f64; // used for progress calculation only
 
         let mut progress: i32;
         let mut old_progress: i32 = -1;
 
-        // now output the data
-        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        // the output points are written as they are filtered
+        let mut output = LasFile::initialize_using_file(&output_file, reader.las_file());
         output.header.system_id = "EXTRACTION".to_string();
+        let mut writer = LasWriter::new(output)?;
 
-        for i in 0..n_points {
-            if include_class_vals[input[i].classification() as usize] {
-                output.add_point_record(input.get_record(i));
+        let mut i = 0usize;
+        while let Some(record) = reader.read_point()? {
+            if include_class_vals[record.get_point_data().classification() as usize] {
+                writer.write_point_with_extra_bytes(record, reader.get_extra_bytes())?;
             }
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
@@ -285,25 +287,17 @@ impl WhiteboxTool for FilterLidarClasses {
                     old_progress = progress;
                 }
             }
+            i += 1;
         }
+        writer.finish()?;
 
         let elapsed_time = get_formatted_elapsed_time(start);
 
         if verbose {
-            println!("Writing output LAS file...");
-        }
-        let _ = match output.write() {
-            Ok(_) => {
-                if verbose {
-                    println!("Complete!")
-                }
-            }
-            Err(e) => println!("error while writing: {:?}", e),
-        };
-        if verbose {
+            println!("Complete!");
             println!(
                 "{}",
-                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
             );
         }
 
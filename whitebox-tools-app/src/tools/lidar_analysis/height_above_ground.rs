@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 08/11/2019
-Last Modified: 15/12/2019
+Last Modified: 17/10/2026
 License: MIT
 
 NOTES:
@@ -16,14 +16,11 @@ use crate::tools::*;
 use whitebox_common::structures::Point3D;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
+use rayon::prelude::*;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
-use std::sync::mpsc;
-use std::sync::{Arc, Mutex};
-use std::thread;
 
 /// This tool normalizes an input LiDAR point cloud (`--input`) such that point z-values in the output LAS file
 /// (`--output`) are converted from elevations to heights above the ground, specifically the height above the
@@ -31,6 +28,10 @@ use std::thread;
 /// will return an error. The `LidarTophatTransform` tool can be used to perform the normalization if a ground
 /// classification is lacking.
 ///
+/// The tool makes two passes through the input file, reading the points in chunks rather than all at once.
+/// Only the ground points are held in memory, and so the tool can normalize point clouds that are larger
+/// than the available memory. Any extra bytes attributes of the points are copied to the output file.
+///
 /// # See Also
 /// `LidarTophatTransform`
 pub struct HeightAboveGround {
@@ -196,24 +197,25 @@ impl WhiteboxTool for HeightAboveGround {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
-        let input = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
+        let mut reader = match LasReader::new(&input_file) {
+            Ok(r) => r,
             Err(err) => panic!("Error reading file {}: {}", input_file, err),
         };
 
-        let n_points = input.header.number_of_points as usize;
-        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+        let num_points: f64 = reader.header().number_of_points.saturating_sub(1) as f64; // used for progress calculation only
 
+        // The first pass through the file gathers the ground points.
         const DIMENSIONS: usize = 2;
         const CAPACITY_PER_NODE: usize = 64;
         let mut tree = KdTree::with_capacity(DIMENSIONS, CAPACITY_PER_NODE);
         let mut pd1: PointData;
         let mut p1: Point3D;
-        for i in 0..n_points {
-            pd1 = input[i];
-            p1 = input.get_transformed_coords(i);
+        let mut i = 0usize;
+        while let Some(record) = reader.read_point()? {
+            pd1 = record.get_point_data();
             if !pd1.withheld() && pd1.classification() == 2u8 {
-                tree.add([p1.x, p1.y], i).unwrap();
+                p1 = reader.get_transformed_coords(&pd1);
+                tree.add([p1.x, p1.y], p1.z).unwrap();
             }
 
             if verbose {
@@ -223,6 +225,7 @@ impl WhiteboxTool for HeightAboveGround {
                     old_progress = progress;
                 }
             }
+            i += 1;
         }
 
         if tree.size() == 0 {
@@ -231,213 +234,63 @@ impl WhiteboxTool for HeightAboveGround {
             );
         }
 
-        let num_solved_pts = Arc::new(Mutex::new(0usize));
-        let tree = Arc::new(tree);
-        let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let num_solved_pts = num_solved_pts.clone();
-            let tree = tree.clone();
-            let input = input.clone();
-            let tx = tx.clone();
-            thread::spawn(move || {
-                let mut pd1: PointData;
-                // let mut p2: PointData;
-                let mut p1: Point3D;
-                let mut p2: Point3D;
-                let mut z: f64;
-                let mut point_num: usize;
-                let mut residuals = vec![];
-                let mut old_progress: i32;
-                let mut progress: i32;
-                for i in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
-                    pd1 = input[i];
-                    p1 = input.get_transformed_coords(i);
-                    z = if pd1.classification() != 2u8 {
-                        let ret = tree.nearest(&[p1.x, p1.y], 1, &squared_euclidean).unwrap();
-                        point_num = *(ret[0].1);
-                        // p2 = input[point_num];
-                        p2 = input.get_transformed_coords(point_num);
-                        p1.z - p2.z
+        // The second pass normalizes the points, a chunk at a time.
+        let mut reader = LasReader::new(&input_file)?;
+        let header = reader.header().clone();
+        let output = LasFile::initialize_using_file(&output_file, reader.las_file());
+        let mut writer = LasWriter::new(output)?;
+        const CHUNK_SIZE: usize = 100_000;
+        let mut records = Vec::with_capacity(CHUNK_SIZE);
+        let mut num_solved_pts = 0usize;
+        loop {
+            while records.len() < CHUNK_SIZE {
+                match reader.read_point()? {
+                    Some(record) => records.push((record, reader.get_extra_bytes().to_vec())),
+                    None => break,
+                }
+            }
+            if records.is_empty() {
+                break;
+            }
+
+            let heights: Vec<f64> = records
+                .par_iter()
+                .map(|(record, _)| {
+                    let pd = record.get_point_data();
+                    let z = pd.z as f64 * header.z_scale_factor + header.z_offset;
+                    if pd.classification() != 2u8 {
+                        let x = pd.x as f64 * header.x_scale_factor + header.x_offset;
+                        let y = pd.y as f64 * header.y_scale_factor + header.y_offset;
+                        let ret = tree.nearest(&[x, y], 1, &squared_euclidean).unwrap();
+                        z - *ret[0].1
                     } else {
                         0f64
-                    };
-                    residuals.push((i, z));
-
-                    if verbose {
-                        let mut num_solved_pts =
-                            num_solved_pts.lock().expect("Error unlocking mutex");
-                        old_progress = (100.0_f64 * *num_solved_pts as f64 / num_points) as i32;
-                        *num_solved_pts += 1;
-                        progress = (100.0_f64 * *num_solved_pts as f64 / num_points) as i32;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                        }
                     }
-                }
-                tx.send(residuals).unwrap();
-            });
-        }
+                })
+                .collect();
 
-        let mut output = LasFile::initialize_using_file(&output_file, &input);
-        for n in 0..num_procs {
-            let residuals = rx.recv().expect("Error receiving data from thread.");
-            for (i, z) in residuals {
-                let pr = input.get_record(i);
-                let pr2: LidarPointRecord;
-                match pr {
-                    LidarPointRecord::PointRecord0 { mut point_data } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord0 {
-                            point_data: point_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord1 {
-                        mut point_data,
-                        gps_data,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord1 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord2 {
-                        mut point_data,
-                        colour_data,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord2 {
-                            point_data: point_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord3 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord3 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord4 {
-                        mut point_data,
-                        gps_data,
-                        wave_packet,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord4 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                    LidarPointRecord::PointRecord5 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                        wave_packet,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord5 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                    LidarPointRecord::PointRecord6 {
-                        mut point_data,
-                        gps_data,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord6 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord7 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord7 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord8 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord8 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord9 {
-                        mut point_data,
-                        gps_data,
-                        wave_packet,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord9 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                    LidarPointRecord::PointRecord10 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                        wave_packet,
-                    } => {
-                        point_data.z = ((z - input.header.z_offset) / input.header.z_scale_factor) as i32;
-                        pr2 = LidarPointRecord::PointRecord10 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                }
-                output.add_point_record(pr2);
+            num_solved_pts += records.len();
+            for ((mut record, extra_bytes), z) in records.drain(..).zip(heights) {
+                let mut point_data = record.get_point_data();
+                point_data.z = ((z - header.z_offset) / header.z_scale_factor) as i32;
+                record.set_point_data(point_data);
+                writer.write_point_with_extra_bytes(record, &extra_bytes)?;
             }
+
             if verbose {
-                progress = (100.0_f64 * (n + 1) as f64 / num_procs as f64) as i32;
+                progress = (100.0_f64 * num_solved_pts as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Creating output: {}%", progress);
+                    println!("Progress: {}%", progress.min(100));
                     old_progress = progress;
                 }
             }
         }
+        writer.finish()?;
 
         let elapsed_time = get_formatted_elapsed_time(start);
 
         if verbose {
-            println!("Writing output LAS file...");
-        }
-        let _ = match output.write() {
-            Ok(_) => {
-                if verbose {
-                    println!("Complete!")
-                }
-            }
-            Err(e) => println!("error while writing: {:?}", e),
-        };
-
-        if verbose {
+            println!("Complete!");
             println!(
                 "{}",
                 &format!("Elapsed Time (including I/O): {}", elapsed_time)
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 16/07/2017
-Last Modified: 29/02/2020
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -22,7 +22,7 @@ use std::path;
 /// with each column corresponding to a field in the table and each row a point value. Fields are separated by
 /// commas in the ASCII formatted file. The output point data, each on a separate line, will take the format:
 ///
-/// ```
+/// ```text
 /// X,Y,Z,INTENSITY,CLASS,RETURN,NUM_RETURN,SCAN_ANGLE
 /// ```
 ///
@@ -30,6 +30,9 @@ use std::path;
 /// GREEN, and BLUE values respectively. Use the `AsciiToLas` tool to convert a text file containing LiDAR
 /// point data into a LAS file.
 ///
+/// The points are read in chunks, rather than all at once, and so the tool can convert point
+/// clouds that are larger than the available memory.
+///
 /// # See Also
 /// `AsciiToLas`
 pub struct LasToAscii {
@@ -188,8 +191,8 @@ impl WhiteboxTool for LasToAscii {
                     input_file = format!("{}{}", working_directory, input_file);
                 }
 
-                let input: LasFile = match LasFile::new(&input_file, "r") {
-                    Ok(lf) => lf,
+                let mut reader = match LasReader::new(&input_file) {
+                    Ok(r) => r,
                     Err(_) => {
                         return Err(Error::new(
                             ErrorKind::NotFound,
@@ -198,15 +201,15 @@ impl WhiteboxTool for LasToAscii {
                     }
                 };
 
-                let has_rgb = input.has_rgb();
+                let has_rgb = reader.has_rgb();
                 let mut rgb: ColourData;
-                let has_time = input.has_gps_time();
+                let has_time = reader.has_gps_time();
                 let file_extension = get_file_extension(&input_file);
                 let output_file = input_file.replace(&format!(".{}", file_extension), ".csv");
                 let f = File::create(output_file)?;
                 let mut writer = BufWriter::new(f);
 
-                let n_points = input.header.number_of_points as usize;
+                let n_points = reader.header().number_of_points as usize;
 
                 // let x_prec = get_precision(&(input.header.x_scale_factor.to_string()));
                 // let y_prec = get_precision(&(input.header.y_scale_factor.to_string()));
@@ -231,9 +234,10 @@ impl WhiteboxTool for LasToAscii {
                             .as_bytes(),
                     )?;
                 }
-                for k in 0..n_points {
-                    let pd = input[k];
-                    let p = input.get_transformed_coords(k);
+                let mut k = 0usize;
+                while let Some(record) = reader.read_point()? {
+                    let pd = record.get_point_data();
+                    let p = reader.get_transformed_coords(&pd);
                     let s = if !has_rgb && !has_time {
                         format!(
                             "{},{},{},{},{},{},{},{}\n",
@@ -257,12 +261,12 @@ impl WhiteboxTool for LasToAscii {
                             pd.return_number(),
                             pd.number_of_returns(),
                             pd.scan_angle,
-                            input.get_gps_time(k).expect("Error retrieving GPS time.")
+                            record.get_gps_time().expect("Error retrieving GPS time.")
                         )
                     } else if has_rgb && !has_time {
-                        rgb = match input.get_rgb(k) {
-                            Ok(v) => v,
-                            Err(_) => {
+                        rgb = match record.get_rgb() {
+                            Some(v) => v,
+                            None => {
                                 return Err(Error::new(
                                     ErrorKind::NotFound,
                                     "RGB data not read correctly in LAS file.",
@@ -284,9 +288,9 @@ impl WhiteboxTool for LasToAscii {
                             rgb.blue
                         )
                     } else {
-                        rgb = match input.get_rgb(k) {
-                            Ok(v) => v,
-                            Err(_) => {
+                        rgb = match record.get_rgb() {
+                            Some(v) => v,
+                            None => {
                                 return Err(Error::new(
                                     ErrorKind::NotFound,
                                     "RGB data not read correctly in LAS file.",
@@ -303,7 +307,7 @@ impl WhiteboxTool for LasToAscii {
                             pd.return_number(),
                             pd.number_of_returns(),
                             pd.scan_angle,
-                            input.get_gps_time(k).expect("Error retrieving GPS time."),
+                            record.get_gps_time().expect("Error retrieving GPS time."),
                             rgb.red,
                             rgb.green,
                             rgb.blue
@@ -322,6 +326,7 @@ impl WhiteboxTool for LasToAscii {
                             old_progress = progress;
                         }
                     }
+                    k += 1;
                 }
                 let _ = writer.flush();
             }
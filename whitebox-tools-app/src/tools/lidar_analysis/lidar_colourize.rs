@@ -246,7 +246,7 @@ impl WhiteboxTool for LidarColourize {
                     row = in_image.get_row_from_y(p.y);
                     col = in_image.get_column_from_x(p.x);
                     value = in_image.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata_value(value, nodata) {
                         tx.send((i, value as u32)).unwrap();
                     } else {
                         tx.send((i, 0u32)).unwrap();
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 02/06/2017
-Last Modified: 18/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -10,6 +10,7 @@ use whitebox_lidar::*;
 use crate::tools::*;
 use std::env;
 use std::f64;
+use std::fs;
 use std::io::{Error, ErrorKind};
 use std::path;
 
@@ -21,6 +22,10 @@ use std::path;
 /// by assigning a class value (`--inclassval`) to the classification bit of points within the slice and
 /// another class value (`--outclassval`) to those points falling outside the range.
 ///
+/// The points are read and written in chunks, rather than all at once, and so the tool can
+/// process point clouds that are larger than the available memory. Any extra bytes attributes of the
+/// points are copied to the output file.
+///
 /// # See Also
 /// `LidarRemoveOutliers`, `LidarClassifySubset`
 pub struct LidarElevationSlice {
@@ -282,11 +287,8 @@ impl WhiteboxTool for LidarElevationSlice {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
-        if verbose {
-            println!("reading input LiDAR file...");
-        }
-        let input: LasFile = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
+        let mut reader = match LasReader::new(&input_file) {
+            Ok(r) => r,
             Err(_) => {
                 return Err(Error::new(
                     ErrorKind::NotFound,
@@ -294,8 +296,9 @@ impl WhiteboxTool for LidarElevationSlice {
                 ))
             }
         };
-        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        let mut output = LasFile::initialize_using_file(&output_file, reader.las_file());
         output.header.system_id = "EXTRACTION".to_string();
+        let mut writer = LasWriter::new(output)?;
 
         if verbose {
             println!("Performing analysis...");
@@ -304,185 +307,45 @@ impl WhiteboxTool for LidarElevationSlice {
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         let mut num_points_filtered: i64 = 0;
-        let num_points: f64 = (input.header.number_of_points - 1) as f64;
+        let num_points: f64 = reader.header().number_of_points.saturating_sub(1) as f64;
 
-        if filter {
-            for i in 0..input.header.number_of_points as usize {
-                // z = input.get_point_info(i).z;
-                z = input.get_transformed_coords(i).z;
+        let mut i = 0usize;
+        while let Some(mut record) = reader.read_point()? {
+            let mut point_data = record.get_point_data();
+            z = reader.get_transformed_coords(&point_data).z;
+            if filter {
                 if z >= minz && z <= maxz {
-                    output.add_point_record(input.get_record(i));
+                    writer.write_point_with_extra_bytes(record, reader.get_extra_bytes())?;
                     num_points_filtered += 1;
                 }
-                if verbose {
-                    progress = (100.0_f64 * i as f64 / num_points) as i32;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
-                }
-            }
-        } else {
-            for i in 0..input.header.number_of_points as usize {
-                let mut class_val = out_class_value; // outside elevation slice
-                // z = input.get_point_info(i).z;
-                z = input.get_transformed_coords(i).z;
+            } else {
                 if z >= minz && z <= maxz {
-                    class_val = in_class_value; // inside elevation slice
-                }
-                let pr = input.get_record(i);
-                let pr2: LidarPointRecord;
-                match pr {
-                    LidarPointRecord::PointRecord0 { mut point_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord0 {
-                            point_data: point_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord1 {
-                        mut point_data,
-                        gps_data,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord1 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord2 {
-                        mut point_data,
-                        colour_data,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord2 {
-                            point_data: point_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord3 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord3 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord4 {
-                        mut point_data,
-                        gps_data,
-                        wave_packet,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord4 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                    LidarPointRecord::PointRecord5 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                        wave_packet,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord5 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                    LidarPointRecord::PointRecord6 {
-                        mut point_data,
-                        gps_data,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord6 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord7 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord7 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord8 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord8 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                        };
-                    }
-                    LidarPointRecord::PointRecord9 {
-                        mut point_data,
-                        gps_data,
-                        wave_packet,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord9 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
-                    LidarPointRecord::PointRecord10 {
-                        mut point_data,
-                        gps_data,
-                        colour_data,
-                        wave_packet,
-                    } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord10 {
-                            point_data: point_data,
-                            gps_data: gps_data,
-                            colour_data: colour_data,
-                            wave_packet: wave_packet,
-                        };
-                    }
+                    point_data.set_classification(in_class_value); // inside elevation slice
+                } else {
+                    point_data.set_classification(out_class_value); // outside elevation slice
                 }
-                output.add_point_record(pr2);
-                if verbose {
-                    progress = (100.0_f64 * i as f64 / num_points) as i32;
-                    if progress != old_progress {
-                        println!("Saving data: {}%", progress);
-                        old_progress = progress;
-                    }
+                record.set_point_data(point_data);
+                writer.write_point_with_extra_bytes(record, reader.get_extra_bytes())?;
+                num_points_filtered += 1;
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
-            num_points_filtered = 1;
+            i += 1;
         }
+        writer.finish()?;
 
         if num_points_filtered > 0 {
             if verbose {
-                println!("Writing output LAS file...");
+                println!("Complete!")
             }
-            let _ = match output.write() {
-                Ok(_) => {
-                    if verbose {
-                        println!("Complete!")
-                    }
-                }
-                Err(e) => println!("error while writing: {:?}", e),
-            };
         } else {
+            // don't leave an empty output file behind
+            let _ = fs::remove_file(&output_file);
             println!("No points were contained in the elevation slice.");
         }
 
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 14/06/2017
-Last Modified: 22/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -20,6 +20,9 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 
+/// Calculates a hillshade value for points within a LAS file and stores these data in the RGB field.
+/// The hillshade value (0-255) is also stored as the `Hillshade` extra bytes attribute of the output
+/// points. Any extra bytes attributes of the input points are preserved.
 pub struct LidarHillshade {
     name: String,
     description: String,
@@ -320,6 +323,17 @@ impl WhiteboxTool for LidarHillshade {
         // now output the data
         let mut output = LasFile::initialize_using_file(&output_file, &input);
         output.header.point_format = 2;
+        if !output
+            .get_extra_byte_descriptors()
+            .iter()
+            .any(|d| d.name == "Hillshade")
+        {
+            output.add_extra_attribute(ExtraByteDescriptor::new(
+                "Hillshade",
+                ExtraByteDataType::U8,
+                "Hillshade value",
+            ))?;
+        }
 
         let (mut fx, mut fy, mut tan_slope, mut aspect): (f64, f64, f64, f64);
         let (mut term1, mut term2, mut term3): (f64, f64, f64);
@@ -360,7 +374,8 @@ impl WhiteboxTool for LidarHillshade {
                 point_data: p,
                 colour_data: rgb,
             };
-            output.add_point_record(lpr);
+            output.add_point_record_with_extra_bytes(lpr, input.get_extra_bytes(i));
+            output.set_extra_attribute(i, "Hillshade", hillshade)?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
@@ -336,6 +336,9 @@ impl WhiteboxTool for LidarPointStats {
                 ));
             }
         } else {
+            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
             inputs.push(input_file.clone());
         }
 
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/06/2017
-Last Modified: 22/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -21,6 +21,8 @@ use std::sync::Arc;
 use std::thread;
 
 /// Calculates normal vectors for points within a LAS file and stores these data (XYZ vector components) in the RGB field.
+/// The RGB values are scaled for display; the unscaled vector components are also stored as the `NormalX`, `NormalY`,
+/// and `NormalZ` extra bytes attributes of the output points. Any extra bytes attributes of the input points are preserved.
 pub struct NormalVectors {
     name: String,
     description: String,
@@ -274,6 +276,24 @@ impl WhiteboxTool for NormalVectors {
         // now output the data
         let mut output = LasFile::initialize_using_file(&output_file, &input);
         output.header.point_format = 2;
+        let attributes = [
+            ("NormalX", "X component of normal vector"),
+            ("NormalY", "Y component of normal vector"),
+            ("NormalZ", "Z component of normal vector"),
+        ];
+        for (name, description) in &attributes {
+            if !output
+                .get_extra_byte_descriptors()
+                .iter()
+                .any(|d| d.name == *name)
+            {
+                output.add_extra_attribute(ExtraByteDescriptor::new(
+                    name,
+                    ExtraByteDataType::F32,
+                    description,
+                ))?;
+            }
+        }
 
         let (mut r, mut g, mut b): (u16, u16, u16);
         for i in 0..n_points {
@@ -292,7 +312,10 @@ impl WhiteboxTool for NormalVectors {
                 point_data: p,
                 colour_data: rgb,
             };
-            output.add_point_record(lpr);
+            output.add_point_record_with_extra_bytes(lpr, input.get_extra_bytes(i));
+            output.set_extra_attribute(i, "NormalX", normal_values[i].x)?;
+            output.set_extra_attribute(i, "NormalY", normal_values[i].y)?;
+            output.set_extra_attribute(i, "NormalZ", normal_values[i].z)?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
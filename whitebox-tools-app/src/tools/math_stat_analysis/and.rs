@@ -240,9 +240,9 @@ impl WhiteboxTool for And {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata1; columns as usize];
                     for col in 0..columns {
-                        z1 = in1[(row, col)];
-                        z2 = in2[(row, col)];
-                        if z1 != nodata1 && z2 != nodata2 {
+                        z1 = in1.get_value(row, col);
+                        z2 = in2.get_value(row, col);
+                        if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                             if z1 != 0f64 {
                                 z1 = 1f64;
                             }
@@ -333,7 +333,7 @@ impl WhiteboxTool for Anova {
                     for col in 0..columns {
                         z = input.get_value(row, col);
                         id = features.get_value(row, col);
-                        if z != nodata && id != nodata_features {
+                        if !is_nodata_value(z, nodata) && !is_nodata_value(id, nodata_features) {
                             id_int = id.round() as i32;
                             if id_int > max_id {
                                 max_id = id_int;
@@ -396,7 +396,7 @@ impl WhiteboxTool for Anova {
             for col in 0..columns as isize {
                 z = input.get_value(row, col);
                 id = features.get_value(row, col);
-                if z != nodata && id != nodata_features {
+                if !is_nodata_value(z, nodata) && !is_nodata_value(id, nodata_features) {
                     // class statistics
                     id_int = id.round() as i32;
                     vec_id = (id_int - min_id) as usize;
@@ -211,8 +211,8 @@ impl WhiteboxTool for ArcCos {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             if z >= -1.0 && z <= 1.0 {
                                 data[col as usize] = z.acos();
                             } else {
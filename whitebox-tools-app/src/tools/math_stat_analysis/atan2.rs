@@ -260,8 +260,8 @@ impl WhiteboxTool for Atan2 {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata2; columns as usize];
                         for col in 0..columns {
-                            z2 = in2[(row, col)];
-                            if z2 != nodata2 {
+                            z2 = in2.get_value(row, col);
+                            if !is_nodata_value(z2, nodata2) {
                                 data[col as usize] = input1_constant.atan2(z2);
                             } else {
                                 data[col as usize] = nodata2;
@@ -336,8 +336,8 @@ impl WhiteboxTool for Atan2 {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            if z1 != nodata1 {
+                            z1 = in1.get_value(row, col);
+                            if !is_nodata_value(z1, nodata1) {
                                 data[col as usize] = z1.atan2(input2_constant);
                             } else {
                                 data[col as usize] = nodata1;
@@ -423,9 +423,9 @@ impl WhiteboxTool for Atan2 {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            z2 = in2[(row, col)];
-                            if z1 != nodata1 && z2 != nodata2 {
+                            z1 = in1.get_value(row, col);
+                            z2 = in2.get_value(row, col);
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 data[col as usize] = z1.atan2(z2);
                             } else {
                                 data[col as usize] = nodata1;
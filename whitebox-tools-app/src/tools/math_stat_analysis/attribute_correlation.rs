@@ -10,6 +10,7 @@ NOTES: Correlation is calculated for each pair of numeric attributes.
 
 use whitebox_common::rendering::html::*;
 use crate::tools::*;
+use whitebox_raster::is_nodata_value;
 use whitebox_vector::{FieldData, Shapefile};
 use std::env;
 use std::fs::File;
@@ -317,7 +318,7 @@ impl WhiteboxTool for AttributeCorrelation {
                                     FieldData::Real(val) => val,
                                     _ => nodata,
                                 };
-                                if z1 != nodata && z2 != nodata {
+                                if !is_nodata_value(z1, nodata) && !is_nodata_value(z2, nodata) {
                                     field1_total_deviation +=
                                         (z1 - field_averages[a]) * (z1 - field_averages[a]);
                                     field2_total_deviation +=
@@ -9,6 +9,7 @@ License: MIT
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::Scattergram;
 use crate::tools::*;
+use whitebox_raster::is_nodata_value;
 use whitebox_vector::{FieldData, Shapefile};
 use std::env;
 use std::f64;
@@ -320,7 +321,7 @@ impl WhiteboxTool for AttributeScattergram {
                 }
             };
 
-            if x != nodata && y != nodata {
+            if !is_nodata_value(x, nodata) && !is_nodata_value(y, nodata) {
                 series_xdata.push(x);
                 series_ydata.push(y);
             }
@@ -210,7 +210,7 @@ impl WhiteboxTool for Cos {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             data[col as usize] = z.cos();
                         } else {
                             data[col as usize] = nodata;
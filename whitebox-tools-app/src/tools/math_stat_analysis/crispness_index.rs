@@ -253,8 +253,8 @@ impl WhiteboxTool for CrispnessIndex {
                     let mut s = 0.0;
                     let mut warning = false;
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             if z < 0f64 || z > 1f64 {
                                 warning = true;
                             }
@@ -298,8 +298,8 @@ impl WhiteboxTool for CrispnessIndex {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut total_dev = 0f64;
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             total_dev += (z - mean) * (z - mean);
                         }
                     }
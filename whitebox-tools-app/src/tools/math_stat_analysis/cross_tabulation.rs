@@ -240,7 +240,7 @@ impl WhiteboxTool for CrossTabulation {
             for col in 0..columns {
                 z1 = input1.get_value(row, col);
                 z2 = input2.get_value(row, col);
-                if z1 != nodata1 && z2 != nodata2 {
+                if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                     index1 = (z1.round() as isize - min1) as usize;
                     index2 = (z2.round() as isize - min2) as usize;
                     class_exists1[index1] = true;
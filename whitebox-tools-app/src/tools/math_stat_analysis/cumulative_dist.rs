@@ -207,8 +207,8 @@ impl WhiteboxTool for CumulativeDistribution {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     num_cells += 1;
                     bin_num = ((z - min_val) / bin_size) as usize;
                     if bin_num > num_bins_less_one {
@@ -238,13 +238,13 @@ impl WhiteboxTool for CumulativeDistribution {
 
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     bin_num = ((z - min_val) / bin_size) as usize;
                     if bin_num > num_bins_less_one {
                         bin_num = num_bins_less_one;
                     }
-                    output[(row, col)] = cdf[bin_num];
+                    output.set_value(row, col, cdf[bin_num]);
                 }
             }
             if verbose {
@@ -260,8 +260,8 @@ impl WhiteboxTool for Divide {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata2; columns as usize];
                         for col in 0..columns {
-                            z2 = in2[(row, col)];
-                            if z2 != nodata2 {
+                            z2 = in2.get_value(row, col);
+                            if !is_nodata_value(z2, nodata2) {
                                 if z2 != 0.0 {
                                     data[col as usize] = input1_constant / z2;
                                 } else {
@@ -354,8 +354,8 @@ impl WhiteboxTool for Divide {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            if z1 != nodata1 {
+                            z1 = in1.get_value(row, col);
+                            if !is_nodata_value(z1, nodata1) {
                                 if input2_constant != 0.0 {
                                     data[col as usize] = z1 / input2_constant;
                                 } else {
@@ -445,9 +445,9 @@ impl WhiteboxTool for Divide {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            z2 = in2[(row, col)];
-                            if z1 != nodata1 && z2 != nodata2 {
+                            z1 = in1.get_value(row, col);
+                            z2 = in2.get_value(row, col);
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 if z2 != 0.0 {
                                     data[col as usize] = z1 / z2;
                                 } else {
@@ -253,8 +253,8 @@ impl WhiteboxTool for EqualTo {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata2; columns as usize];
                         for col in 0..columns {
-                            z2 = in2[(row, col)];
-                            if z2 != nodata2 {
+                            z2 = in2.get_value(row, col);
+                            if !is_nodata_value(z2, nodata2) {
                                 data[col as usize] = equal_to(input1_constant, z2);
                             } else {
                                 data[col as usize] = nodata2;
@@ -332,8 +332,8 @@ impl WhiteboxTool for EqualTo {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            if z1 != nodata1 {
+                            z1 = in1.get_value(row, col);
+                            if !is_nodata_value(z1, nodata1) {
                                 data[col as usize] = equal_to(z1, input2_constant);
                             } else {
                                 data[col as usize] = nodata1;
@@ -422,9 +422,9 @@ impl WhiteboxTool for EqualTo {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            z2 = in2[(row, col)];
-                            if z1 != nodata1 && z2 != nodata2 {
+                            z1 = in1.get_value(row, col);
+                            z2 = in2.get_value(row, col);
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 data[col as usize] = equal_to(z1, z2);
                             } else {
                                 data[col as usize] = nodata1;
@@ -346,7 +346,7 @@ impl WhiteboxTool for ImageAutocorrelation {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 total += z;
                                 n += 1f64;
                             }
@@ -385,7 +385,7 @@ impl WhiteboxTool for ImageAutocorrelation {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         total_deviation += (z - mean[a]) * (z - mean[a]);
                         k += (z - mean[a]) * (z - mean[a]) * (z - mean[a]) * (z - mean[a]);
                         wij = 0f64;
@@ -393,7 +393,7 @@ impl WhiteboxTool for ImageAutocorrelation {
                             x = col + dx[i];
                             y = row + dy[i];
                             zn = input.get_value(y, x);
-                            if zn != nodata {
+                            if !is_nodata_value(zn, nodata) {
                                 w += 1f64;
                                 numerator += (z - mean[a]) * (zn - mean[a]);
                                 wij += 1f64;
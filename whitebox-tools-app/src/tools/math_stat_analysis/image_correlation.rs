@@ -282,8 +282,8 @@ impl WhiteboxTool for ImageCorrelation {
                     let mut z: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         for col in 0..columns {
-                            z = input[(row, col)];
-                            if z != nodata {
+                            z = input.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 // image_totals[a] += z;
                                 // image_n[a] += 1f64;
                                 total += z;
@@ -345,9 +345,11 @@ impl WhiteboxTool for ImageCorrelation {
                             let mut total_product_deviations = 0f64;
                             for row in (0..rows).filter(|r| r % num_procs == tid) {
                                 for col in 0..columns {
-                                    z1 = image1[(row, col)];
-                                    z2 = image2[(row, col)];
-                                    if z1 != nodata1 && z2 != nodata2 {
+                                    z1 = image1.get_value(row, col);
+                                    z2 = image2.get_value(row, col);
+                                    if !is_nodata_value(z1, nodata1)
+                                        && !is_nodata_value(z2, nodata2)
+                                    {
                                         image1_total_deviation +=
                                             (z1 - image_averages[a]) * (z1 - image_averages[a]);
                                         image2_total_deviation +=
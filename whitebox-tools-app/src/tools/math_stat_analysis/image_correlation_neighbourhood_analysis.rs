@@ -360,7 +360,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                         for col in 0..columns {
                             z1 = image1.get_value(row, col);
                             z2 = image2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 // First, calculate the mean
                                 num_cells = 0;
                                 sum1 = 0f64;
@@ -368,7 +368,9 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                 for i in 0..num_pixels_in_filter {
                                     z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                     z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                    if z_n1 != nodata1 && z_n2 != nodata2 {
+                                    if !is_nodata_value(z_n1, nodata1)
+                                        && !is_nodata_value(z_n2, nodata2)
+                                    {
                                         sum1 += z_n1;
                                         sum2 += z_n2;
                                         num_cells += 1;
@@ -385,7 +387,9 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                     for i in 0..num_pixels_in_filter {
                                         z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                         z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                        if z_n1 != nodata1 && z_n2 != nodata2 {
+                                        if !is_nodata_value(z_n1, nodata1)
+                                            && !is_nodata_value(z_n2, nodata2)
+                                        {
                                             total_deviation1 += (z_n1 - mean1) * (z_n1 - mean1);
                                             total_deviation2 += (z_n2 - mean2) * (z_n2 - mean2);
                                             product_deviations += (z_n1 - mean1) * (z_n2 - mean2);
@@ -480,14 +484,16 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                         for col in 0..columns {
                             z1 = image1.get_value(row, col);
                             z2 = image2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 let mut v1 = Vec::with_capacity(num_pixels_in_filter);
                                 let mut v2 = Vec::with_capacity(num_pixels_in_filter);
                                 num_cells = 0;
                                 for i in 0..num_pixels_in_filter {
                                     z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                     z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                    if z_n1 != nodata1 && z_n2 != nodata2 {
+                                    if !is_nodata_value(z_n1, nodata1)
+                                        && !is_nodata_value(z_n2, nodata2)
+                                    {
                                         num_cells += 1;
                                         // tuple = (value, index, rank)
                                         v1.push((z_n1, num_cells, 0f64));
@@ -669,14 +675,16 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                         for col in 0..columns {
                             z1 = image1.get_value(row, col);
                             z2 = image2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 let mut v1 = Vec::with_capacity(num_pixels_in_filter);
                                 let mut v2 = Vec::with_capacity(num_pixels_in_filter);
                                 num_cells = 0;
                                 for i in 0..num_pixels_in_filter {
                                     z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                     z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                    if z_n1 != nodata1 && z_n2 != nodata2 {
+                                    if !is_nodata_value(z_n1, nodata1)
+                                        && !is_nodata_value(z_n2, nodata2)
+                                    {
                                         num_cells += 1;
                                         // tuple = (value, index, rank)
                                         v1.push((z_n1, num_cells, 0f64));
@@ -345,7 +345,7 @@ impl WhiteboxTool for ImageRegression {
                     for col in 0..columns {
                         x = input1.get_value(row, col);
                         y = input2.get_value(row, col);
-                        if x != nodata1 && y != nodata2 {
+                        if !is_nodata_value(x, nodata1) && !is_nodata_value(y, nodata2) {
                             sum_x += x;
                             sum_y += y;
                             sum_xy += x * y;
@@ -399,9 +399,9 @@ impl WhiteboxTool for ImageRegression {
                 let mut ss_error = 0f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     for col in 0..columns {
-                        x = input1[(row, col)];
-                        y = input2[(row, col)];
-                        if x != nodata1 && y != nodata2 {
+                        x = input1.get_value(row, col);
+                        y = input2.get_value(row, col);
+                        if !is_nodata_value(x, nodata1) && !is_nodata_value(y, nodata2) {
                             y_estimate = slope * x + intercept;
                             ss_error += (y - y_estimate) * (y - y_estimate);
                             ss_total += (y - y_mean) * (y - y_mean);
@@ -460,9 +460,9 @@ impl WhiteboxTool for ImageRegression {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
-                            x = input1[(row, col)];
-                            y = input2[(row, col)];
-                            if x != nodata1 && y != nodata2 {
+                            x = input1.get_value(row, col);
+                            y = input2.get_value(row, col);
+                            if !is_nodata_value(x, nodata1) && !is_nodata_value(y, nodata2) {
                                 y_estimate = slope * x + intercept;
                                 residual = if standardize_residuals {
                                     (y - y_estimate) / se_of_estimate
@@ -761,7 +761,7 @@ impl WhiteboxTool for ImageRegression {
                 let col = rng.gen_range(0..columns as isize);
                 x = input1.get_value(row, col);
                 y = input2.get_value(row, col);
-                if x != nodata1 && y != nodata2 {
+                if !is_nodata_value(x, nodata1) && !is_nodata_value(y, nodata2) {
                     sample_num += 1;
                     series_xdata.push(x);
                     series_ydata.push(y);
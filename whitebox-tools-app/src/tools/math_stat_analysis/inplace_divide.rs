@@ -221,7 +221,7 @@ impl WhiteboxTool for InPlaceDivide {
             for row in 0..rows {
                 for col in 0..columns {
                     z1 = in1.get_value(row, col);
-                    if z1 != nodata1 {
+                    if !is_nodata_value(z1, nodata1) {
                         in1.set_value(row, col, z1 / input2_constant);
                     }
                 }
@@ -254,9 +254,12 @@ impl WhiteboxTool for InPlaceDivide {
                 for col in 0..columns {
                     z1 = in1.get_value(row, col);
                     z2 = in2.get_value(row, col);
-                    if z1 != nodata1 && z2 != nodata2 && z2 != 0f64 {
+                    if !is_nodata_value(z1, nodata1)
+                        && !is_nodata_value(z2, nodata2)
+                        && z2 != 0f64
+                    {
                         in1.set_value(row, col, z1 / z2);
-                    } else if z1 != nodata1 {
+                    } else if !is_nodata_value(z1, nodata1) {
                         in1.set_value(row, col, nodata1);
                     }
                 }
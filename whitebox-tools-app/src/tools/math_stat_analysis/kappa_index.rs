@@ -253,7 +253,7 @@ impl WhiteboxTool for KappaIndex {
             for col in 0..columns {
                 z1 = input1.get_value(row, col);
                 z2 = input2.get_value(row, col);
-                if z1 != nodata1 && z2 != nodata2 {
+                if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                     class1 = (z1 - min_val as f64).round() as usize;
                     class2 = (z2 - min_val as f64).round() as usize;
                     error_matrix[class1][class2] += 1;
@@ -248,7 +248,7 @@ impl WhiteboxTool for KsTestForNormality {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         bin_num = ((z - min_value) / bin_size).floor() as usize;
                         if bin_num > num_bins_less_one {
                             bin_num = num_bins_less_one;
@@ -275,7 +275,7 @@ impl WhiteboxTool for KsTestForNormality {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         total_deviation += (z - mean) * (z - mean);
                     }
                 }
@@ -300,7 +300,7 @@ impl WhiteboxTool for KsTestForNormality {
                 row = rng.gen_range(0..rows as isize); // row_rng.ind_sample(&mut rng);
                 col = rng.gen_range(0..columns as isize); // col_rng.ind_sample(&mut rng);
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     bin_num = ((z - min_value) / bin_size).floor() as usize;
                     if bin_num > num_bins_less_one {
                         bin_num = num_bins_less_one;
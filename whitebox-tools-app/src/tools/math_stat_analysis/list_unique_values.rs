@@ -229,6 +229,7 @@ impl WhiteboxTool for ListUniqueValues {
         for record_num in 0..vector_data.num_records {
             key = match vector_data.attributes.get_value(record_num, &field_name) {
                 FieldData::Int(val) => val.to_string(),
+                FieldData::Int64(val) => val.to_string(),
                 FieldData::Real(val) => val.to_string(),
                 FieldData::Text(val) => val.to_string(),
                 FieldData::Date(val) => val.to_string(),
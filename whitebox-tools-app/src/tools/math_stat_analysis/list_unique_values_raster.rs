@@ -197,7 +197,7 @@ impl WhiteboxTool for ListUniqueValuesRaster {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             let count = freq_data.entry(z as usize).or_insert(0);
                             *count += 1;
                         }
@@ -303,7 +303,7 @@ impl WhiteboxTool for PairedSampleTTest {
                         for col in 0..columns {
                             z1 = input1.get_value(row, col);
                             z2 = input2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                                 n += 1;
                                 diff = z2 - z1;
                                 s += diff;
@@ -354,7 +354,7 @@ impl WhiteboxTool for PairedSampleTTest {
                 col = rng.gen_range(0..columns as isize);
                 z1 = input1.get_value(row, col);
                 z2 = input2.get_value(row, col);
-                if z1 != nodata1 && z2 != nodata2 {
+                if !is_nodata_value(z1, nodata1) && !is_nodata_value(z2, nodata2) {
                     diff = z2 - z1;
                     n += 1;
                     sum += diff;
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 15/03/2018
-Last Modified: 18/10/2019
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -34,7 +34,9 @@ use std::process::Command;
 /// multi-spectral data set can be represented by the first three or four PCA components. The higher-order components are often
 /// associated with noise in the original data set.
 ///
-/// The user must specify the names of the multiple input images (`--inputs`). Additionally, the user must specify whether to
+/// The user must specify the names of the multiple input images (`--inputs`). Each band of a multi-band input image (e.g. a
+/// Landsat or Sentinel stack stored in a single GeoTIFF) is treated as a separate input, such that a single multi-band image
+/// may be used in place of a list of single-band images. Additionally, the user must specify whether to
 /// perform a standardized PCA (`--standardized`) and the number of output components (`--num_comp`) to generate (all components
 /// will be output unless otherwise specified). A standardized PCA is performed using the correlation matrix rather than the
 /// variance-covariance matrix. This is appropriate when the variances in the input images differ substantially, such as would be
@@ -257,12 +259,6 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             cmd = input_files_str.split(",");
             input_files = cmd.collect::<Vec<&str>>();
         }
-        let num_files = input_files.len();
-        if num_files < 3 {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "There is something incorrect about the input files. At least three inputs are required to operate this tool."));
-        }
-
         let wd = if working_directory.is_empty() {
             // set the working directory to that of the first input file.
             let p = path::Path::new(input_files[0].trim());
@@ -289,15 +285,9 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
         let mut rows = -1isize;
         let mut columns = -1isize;
 
-        let mut nodata = vec![0f64; num_files];
-        let mut average = vec![0f64; num_files];
-        let mut num_cells = vec![0f64; num_files];
-        let mut input_raster: Vec<Raster> = Vec::with_capacity(num_files);
+        let mut input_raster: Vec<Raster> = Vec::with_capacity(input_files.len());
         let mut file_names = vec![];
-        if verbose {
-            println!("Calculating image means...");
-        }
-        for i in 0..num_files {
+        for i in 0..input_files.len() {
             if !input_files[i].trim().is_empty() {
                 // quality control on the image file name.
                 let mut input_file = input_files[i].trim().to_owned();
@@ -305,34 +295,56 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
                     input_file = format!("{}{}", working_directory, input_file);
                 }
 
-                // read the image
-                // let input = Raster::new(&input_file, "r")?;
-                input_raster.push(Raster::new(&input_file, "r")?);
-
-                // get the nodata value, the number of valid cells, and the average
-                nodata[i] = input_raster[i].configs.nodata;
-                num_cells[i] = input_raster[i].num_valid_cells() as f64;
-                average[i] = input_raster[i].calculate_mean();
-                file_names.push(input_raster[i].get_short_filename());
-
-                // initialize the rows and column and check that each image has the same dimensions
-                if rows == -1 || columns == -1 {
-                    rows = input_raster[i].configs.rows as isize;
-                    columns = input_raster[i].configs.columns as isize;
-                } else {
-                    if input_raster[i].configs.rows as isize != rows
-                        || input_raster[i].configs.columns as isize != columns
-                    {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                // read the image; each band of a multi-band image is treated as a separate image
+                let input = Raster::new(&input_file, "r")?;
+                let num_bands = input.num_bands();
+                let short_name = input.get_short_filename();
+                for band in 0..num_bands {
+                    if num_bands > 1 {
+                        file_names.push(format!("{} (band {})", short_name, band + 1));
+                    } else {
+                        file_names.push(short_name.clone());
                     }
                 }
+                input_raster.extend(input.split_bands());
             } else {
                 return Err(Error::new(ErrorKind::InvalidInput,
                     "There is something incorrect about the input files. At least one is an empty string."));
             }
         }
 
+        let num_files = input_raster.len();
+        if num_files < 3 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "There is something incorrect about the input files. At least three input images, or a multi-band image with at least three bands, are required to operate this tool."));
+        }
+
+        let mut nodata = vec![0f64; num_files];
+        let mut average = vec![0f64; num_files];
+        let mut num_cells = vec![0f64; num_files];
+        if verbose {
+            println!("Calculating image means...");
+        }
+        for i in 0..num_files {
+            // get the nodata value, the number of valid cells, and the average
+            nodata[i] = input_raster[i].configs.nodata;
+            num_cells[i] = input_raster[i].num_valid_cells() as f64;
+            average[i] = input_raster[i].calculate_mean();
+
+            // initialize the rows and column and check that each image has the same dimensions
+            if rows == -1 || columns == -1 {
+                rows = input_raster[i].configs.rows as isize;
+                columns = input_raster[i].configs.columns as isize;
+            } else {
+                if input_raster[i].configs.rows as isize != rows
+                    || input_raster[i].configs.columns as isize != columns
+                {
+                    return Err(Error::new(ErrorKind::InvalidInput,
+                        "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                }
+            }
+        }
+
         if rows == -1 || columns == -1 {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -351,12 +363,12 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             for col in 0..columns {
                 for i in 0..num_files {
                     z1 = input_raster[i].get_value(row, col);
-                    if z1 != nodata[i] {
+                    if !is_nodata_value(z1, nodata[i]) {
                         image_total_deviations[i] += (z1 - average[i]) * (z1 - average[i]);
 
                         for a in 0..num_files {
                             z2 = input_raster[a].get_value(row, col);
-                            if z2 != nodata[a] {
+                            if !is_nodata_value(z2, nodata[a]) {
                                 covariances[i][a] += (z1 - average[i]) * (z2 - average[a]);
                             }
                         }
@@ -627,7 +639,7 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             for row in 0..rows {
                 for col in 0..columns {
                     z1 = input_raster[0].get_value(row, col);
-                    if z1 != nodata[0] {
+                    if !is_nodata_value(z1, nodata[0]) {
                         z1 = 0f64;
                         for k in 0..num_files {
                             z1 += input_raster[k].get_value(row, col)
@@ -232,8 +232,8 @@ impl WhiteboxTool for Quantiles {
         let mut bin: isize;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     bin = ((z - min_value) / highres_bin_size).floor() as isize;
                     if bin >= highres_num_bins {
                         bin = highres_num_bins - 1;
@@ -272,15 +272,15 @@ impl WhiteboxTool for Quantiles {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     let mut i = ((z - min_value) / highres_bin_size).floor() as usize;
                     if i >= highres_num_bins as usize {
                         i = highres_num_bins as usize - 1;
                     }
                     let bin = primary_histo[i];
 
-                    output[(row, col)] = bin + 1.0;
+                    output.set_value(row, col, bin + 1.0);
                 }
             }
             if verbose {
@@ -216,8 +216,8 @@ impl WhiteboxTool for RasterSummaryStats {
                     let mut minz = f64::INFINITY;
                     let mut maxz = f64::NEG_INFINITY;
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             n += 1;
                             s += z;
                             sq += z * z;
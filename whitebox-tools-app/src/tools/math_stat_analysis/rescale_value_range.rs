@@ -332,8 +332,8 @@ impl WhiteboxTool for RescaleValueRange {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z_in = input[(row, col)];
-                        if z_in != nodata {
+                        z_in = input.get_value(row, col);
+                        if !is_nodata_value(z_in, nodata) {
                             if z_in < min_val {
                                 z_in = min_val;
                             }
@@ -231,9 +231,9 @@ impl WhiteboxTool for RootMeanSquareError {
                         let mut sq = 0.0f64;
                         let mut data: Vec<f32> = Vec::with_capacity(columns as usize);
                         for col in 0..columns {
-                            z1 = input[(row, col)];
-                            z2 = base_raster[(row, col)];
-                            if z1 != nodata && z2 != nodata_base {
+                            z1 = input.get_value(row, col);
+                            z2 = base_raster.get_value(row, col);
+                            if !is_nodata_value(z1, nodata) && !is_nodata_value(z2, nodata_base) {
                                 z_diff = z2 - z1;
                                 n += 1;
                                 s += z_diff;
@@ -330,8 +330,8 @@ impl WhiteboxTool for RootMeanSquareError {
                         let mut sq = 0.0f64;
                         let mut data: Vec<f32> = Vec::with_capacity(columns as usize);
                         for col in 0..columns {
-                            z1 = input[(row, col)];
-                            if z1 != nodata {
+                            z1 = input.get_value(row, col);
+                            if !is_nodata_value(z1, nodata) {
                                 x = input.get_x_from_column(col);
                                 src_row = (src_north - y) / src_resolution_y;
                                 src_col = (x - src_west) / src_resolution_x;
@@ -340,15 +340,15 @@ impl WhiteboxTool for RootMeanSquareError {
                                 dx = src_col - src_col.floor();
                                 dy = src_row - src_row.floor();
 
-                                n0 = base_raster[(origin_row, origin_col)];
-                                n1 = base_raster[(origin_row, origin_col + 1)];
-                                n2 = base_raster[(origin_row + 1, origin_col)];
-                                n3 = base_raster[(origin_row + 1, origin_col + 1)];
+                                n0 = base_raster.get_value(origin_row, origin_col);
+                                n1 = base_raster.get_value(origin_row, origin_col + 1);
+                                n2 = base_raster.get_value(origin_row + 1, origin_col);
+                                n3 = base_raster.get_value(origin_row + 1, origin_col + 1);
 
-                                if n0 != nodata_base
-                                    && n1 != nodata_base
-                                    && n2 != nodata_base
-                                    && n3 != nodata_base
+                                if !is_nodata_value(n0, nodata_base)
+                                    && !is_nodata_value(n1, nodata_base)
+                                    && !is_nodata_value(n2, nodata_base)
+                                    && !is_nodata_value(n3, nodata_base)
                                 {
                                     // This is the bilinear interpolation equation.
                                     z2 = n0 * (1f64 - dx) * (1f64 - dy)
@@ -357,22 +357,22 @@ impl WhiteboxTool for RootMeanSquareError {
                                         + n3 * dx * dy;
                                 } else {
                                     // some of the neighbours are nodata and an inverse-distance scheme is used instead
-                                    let w0 = if n0 != nodata_base {
+                                    let w0 = if !is_nodata_value(n0, nodata_base) {
                                         1f64 / (dx * dx + dy * dy)
                                     } else {
                                         0f64
                                     };
-                                    let w1 = if n1 != nodata_base {
+                                    let w1 = if !is_nodata_value(n1, nodata_base) {
                                         1f64 / ((1f64 - dx) * (1f64 - dx) + dy * dy)
                                     } else {
                                         0f64
                                     };
-                                    let w2 = if n2 != nodata_base {
+                                    let w2 = if !is_nodata_value(n2, nodata_base) {
                                         1f64 / (dx * dx + (1f64 - dy) * (1f64 - dy))
                                     } else {
                                         0f64
                                     };
-                                    let w3 = if n3 != nodata_base {
+                                    let w3 = if !is_nodata_value(n3, nodata_base) {
                                         1f64 / ((1f64 - dx) * (1f64 - dx)
                                             + (1f64 - dy) * (1f64 - dy))
                                     } else {
@@ -386,7 +386,7 @@ impl WhiteboxTool for RootMeanSquareError {
                                     }
                                 }
 
-                                if z2 != nodata_base && !z2.is_nan() {
+                                if !is_nodata_value(z2, nodata_base) && !z2.is_nan() {
                                     z_diff = z2 - z1;
                                     n += 1;
                                     s += z_diff;
@@ -242,7 +242,7 @@ impl WhiteboxTool for TrendSurface {
         for row in 0..rows {
             for col in 0..columns {
                 z_val = input.get_value(row, col);
-                if z_val != nodata {
+                if !is_nodata_value(z_val, nodata) {
                     x_val = input.get_x_from_column(col);
                     y_val = input.get_y_from_row(row);
                     x.push(x_val - min_x);
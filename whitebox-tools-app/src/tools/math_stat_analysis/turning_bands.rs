@@ -501,7 +501,7 @@ impl WhiteboxTool for TurningBandsSimulation {
         for row in 0..rows {
             for col in 0..columns {
                 // output[(row, col)] = (output[(row, col)] - mean) / stdev; // / iterations as f64;
-                output[(row, col)] = output[(row, col)] / iterations_rooted;
+                output.set_value(row, col, output.get_value(row, col) / iterations_rooted);
             }
 
             if verbose {
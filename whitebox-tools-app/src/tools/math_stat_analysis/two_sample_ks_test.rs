@@ -275,11 +275,11 @@ impl WhiteboxTool for TwoSampleKsTest {
             for row in 0..rows {
                 for col in 0..columns {
                     z1 = input1.get_value(row, col);
-                    if z1 != nodata1 {
+                    if !is_nodata_value(z1, nodata1) {
                         data1.push(z1);
                     }
                     z2 = input2.get_value(row, col);
-                    if z2 != nodata2 {
+                    if !is_nodata_value(z2, nodata2) {
                         data2.push(z2);
                     }
                 }
@@ -303,7 +303,7 @@ impl WhiteboxTool for TwoSampleKsTest {
                 row = rng.gen_range(0..rows as isize);
                 col = rng.gen_range(0..columns as isize);
                 z1 = input1.get_value(row, col);
-                if z1 != nodata1 {
+                if !is_nodata_value(z1, nodata1) {
                     data1.push(z1);
 
                     sample_num += 1;
@@ -323,7 +323,7 @@ impl WhiteboxTool for TwoSampleKsTest {
                 row = rng.gen_range(0..rows as isize);
                 col = rng.gen_range(0..columns as isize);
                 z2 = input2.get_value(row, col);
-                if z2 != nodata2 {
+                if !is_nodata_value(z2, nodata2) {
                     data2.push(z2);
 
                     sample_num += 1;
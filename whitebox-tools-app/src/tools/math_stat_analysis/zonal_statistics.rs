@@ -334,7 +334,7 @@ impl WhiteboxTool for ZonalStatistics {
                     let mut id: isize;
                     for col in 0..columns {
                         features_val = features.get_value(row, col);
-                        if features_val != features_nodata {
+                        if !is_nodata_value(features_val, features_nodata) {
                             id = features_val.round() as isize;
                             if id < min_id {
                                 min_id = id;
@@ -392,7 +392,10 @@ impl WhiteboxTool for ZonalStatistics {
             for col in 0..columns {
                 val = input.get_value(row, col);
                 features_val = features.get_value(row, col);
-                if val != nodata && features_val != features_nodata && !(zero_is_background && val == 0.0) {
+                if !is_nodata_value(val, nodata)
+                    && !is_nodata_value(features_val, features_nodata)
+                    && (!zero_is_background || val != 0.0)
+                {
                     id = (features_val.round() as isize - min_id) as usize;
                     features_data[id].push(val);
                     features_present[id] = true;
@@ -426,7 +429,10 @@ impl WhiteboxTool for ZonalStatistics {
             for col in 0..columns {
                 val = input.get_value(row, col);
                 features_val = features.get_value(row, col);
-                if val != nodata && features_val != features_nodata && !(zero_is_background && val == 0.0) {
+                if !is_nodata_value(val, nodata)
+                    && !is_nodata_value(features_val, features_nodata)
+                    && (!zero_is_background || val != 0.0)
+                {
                     id = (features_val.round() as isize - min_id) as usize;
                     features_total_deviation[id] +=
                         (val - features_average[id]) * (val - features_average[id]);
@@ -488,7 +494,10 @@ impl WhiteboxTool for ZonalStatistics {
                 for col in 0..columns {
                     val = input.get_value(row, col);
                     features_val = features.get_value(row, col);
-                    if val != nodata && features_val != features_nodata && !(zero_is_background && val == 0.0) {
+                    if !is_nodata_value(val, nodata)
+                        && !is_nodata_value(features_val, features_nodata)
+                        && (!zero_is_background || val != 0.0)
+                    {
                         id = (features_val.round() as isize - min_id) as usize;
                         output.set_value(row, col, out_stat[id]);
                     }
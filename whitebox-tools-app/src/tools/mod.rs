@@ -6,7 +6,19 @@ pub mod lidar_analysis;
 pub mod math_stat_analysis;
 pub mod stream_network_analysis;
 pub mod terrain_analysis;
+mod errors;
+mod json_output;
+mod raster_flags;
+mod tiling;
+mod validation;
+mod workflow;
 
+pub use self::errors::{panic_message, ToolError, ToolErrorKind};
+pub use self::json_output::{ToolEvent, JSON_CHILD_ENV_VAR};
+pub use self::tiling::TilingOptions;
+use self::raster_flags::RasterFlags;
+
+use whitebox_common::plugins::{self, PluginManager};
 use whitebox_common::utils::get_formatted_elapsed_time;
 use serde_json;
 use std::io::{Error, ErrorKind};
@@ -16,6 +28,7 @@ use std::fs;
 use std::collections::HashMap;
 use std::process::Command;
 use std::env;
+use std::sync::Arc;
 // use std::io;
 // use std::path::PathBuf;
 
@@ -24,6 +37,7 @@ pub struct ToolManager {
     pub working_dir: String,
     pub verbose: bool,
     tool_names: Vec<String>,
+    plugins: PluginManager,
 }
 
 impl ToolManager {
@@ -53,6 +67,9 @@ impl ToolManager {
         tool_names.push("RasterToVectorPolygons".to_string());
         tool_names.push("ReinitializeAttributeTable".to_string());
         tool_names.push("RemovePolygonHoles".to_string());
+        tool_names.push("ReprojectLidar".to_string());
+        tool_names.push("ReprojectRaster".to_string());
+        tool_names.push("ReprojectVector".to_string());
         tool_names.push("SetNodataValue".to_string());
         tool_names.push("SinglePartToMultiPart".to_string());
         tool_names.push("VectorLinesToRaster".to_string());
@@ -66,7 +83,7 @@ impl ToolManager {
         tool_names.push("BlockMinimumGridding".to_string());
         tool_names.push("BoundaryShapeComplexity".to_string());
         tool_names.push("BufferRaster".to_string());
-        // tool_names.push("BufferVector".to_string());
+        tool_names.push("BufferVector".to_string());
         tool_names.push("Centroid".to_string());
         tool_names.push("CentroidVector".to_string());
         tool_names.push("Clip".to_string());
@@ -117,6 +134,7 @@ impl ToolManager {
         tool_names.push("MultiplyOverlay".to_string());
         tool_names.push("NarrownessIndex".to_string());
         tool_names.push("NaturalNeighbourInterpolation".to_string());
+        tool_names.push("NearFeature".to_string());
         tool_names.push("NearestNeighbourGridding".to_string());
         tool_names.push("MinOverlay".to_string());
         tool_names.push("PatchOrientation".to_string());
@@ -139,9 +157,12 @@ impl ToolManager {
         tool_names.push("ReclassEqualInterval".to_string());
         tool_names.push("ReclassFromFile".to_string());
         tool_names.push("RelatedCircumscribingCircle".to_string());
+        tool_names.push("SelectByAttribute".to_string());
+        tool_names.push("SelectByLocation".to_string());
         tool_names.push("ShapeComplexityIndex".to_string());
         tool_names.push("ShapeComplexityIndexRaster".to_string());
         tool_names.push("SmoothVectors".to_string());
+        tool_names.push("SpatialJoin".to_string());
         tool_names.push("SplitWithLines".to_string());
         tool_names.push("SumOverlay".to_string());
         tool_names.push("SymmetricalDifference".to_string());
@@ -510,17 +531,45 @@ impl ToolManager {
         tool_names.push("VisibilityIndex".to_string());
         tool_names.push("WetnessIndex".to_string());
 
-        tool_names.sort();
-
-        let tm = ToolManager {
+        let mut tm = ToolManager {
             working_dir: working_directory.to_string(),
             verbose: *verbose_mode,
             tool_names: tool_names,
+            plugins: PluginManager::new(),
         };
+
+        // Plugin libraries in the 'plugins' folder add their tools alongside the built-in
+        // tools. A plugin that can't be loaded is reported, but doesn't prevent other tools
+        // from running.
+        let mut dir = env::current_exe()?;
+        dir.pop();
+        dir.push("plugins");
+        for e in tm.plugins.load_directory(&dir) {
+            eprintln!("Warning: {}", e);
+        }
+        for name in tm.plugins.get_tool_names() {
+            if tm.get_builtin_tool(&name).is_some() {
+                eprintln!("Warning: The plugin tool {} has the same name as a built-in tool and will not be used.", name);
+            } else {
+                tm.tool_names.push(name);
+            }
+        }
+
+        tm.tool_names.sort();
         Ok(tm)
     }
 
     fn get_tool(&self, tool_name: &str) -> Option<Box<dyn WhiteboxTool + 'static>> {
+        match self.get_builtin_tool(tool_name) {
+            Some(tool) => Some(tool),
+            None => self
+                .plugins
+                .get_tool(tool_name)
+                .map(|t| Box::new(PluginTool(t)) as Box<dyn WhiteboxTool>),
+        }
+    }
+
+    fn get_builtin_tool(&self, tool_name: &str) -> Option<Box<dyn WhiteboxTool + 'static>> {
         match tool_name.to_lowercase().replace("_", "").as_ref() {
             // data_tools
             "addpointcoordinatestotable" => {
@@ -547,6 +596,9 @@ impl ToolManager {
                 Some(Box::new(data_tools::ReinitializeAttributeTable::new()))
             }
             "removepolygonholes" => Some(Box::new(data_tools::RemovePolygonHoles::new())),
+            "reprojectlidar" => Some(Box::new(data_tools::ReprojectLidar::new())),
+            "reprojectraster" => Some(Box::new(data_tools::ReprojectRaster::new())),
+            "reprojectvector" => Some(Box::new(data_tools::ReprojectVector::new())),
             "setnodatavalue" => Some(Box::new(data_tools::SetNodataValue::new())),
             "singleparttomultipart" => Some(Box::new(data_tools::SinglePartToMultiPart::new())),
             "vectorlinestoraster" => Some(Box::new(data_tools::VectorLinesToRaster::new())),
@@ -562,7 +614,7 @@ impl ToolManager {
                 Some(Box::new(gis_analysis::BoundaryShapeComplexity::new()))
             }
             "bufferraster" => Some(Box::new(gis_analysis::BufferRaster::new())),
-            // "buffervector" => Some(Box::new(gis_analysis::BufferVector::new())),
+            "buffervector" => Some(Box::new(gis_analysis::BufferVector::new())),
             "centroid" => Some(Box::new(gis_analysis::Centroid::new())),
             "centroidvector" => Some(Box::new(gis_analysis::CentroidVector::new())),
             "clip" => Some(Box::new(gis_analysis::Clip::new())),
@@ -631,6 +683,7 @@ impl ToolManager {
             "naturalneighbourinterpolation" => {
                 Some(Box::new(gis_analysis::NaturalNeighbourInterpolation::new()))
             }
+            "nearfeature" => Some(Box::new(gis_analysis::NearFeature::new())),
             "nearestneighbourgridding" => {
                 Some(Box::new(gis_analysis::NearestNeighbourGridding::new()))
             }
@@ -659,11 +712,14 @@ impl ToolManager {
             "relatedcircumscribingcircle" => {
                 Some(Box::new(gis_analysis::RelatedCircumscribingCircle::new()))
             }
+            "selectbyattribute" => Some(Box::new(gis_analysis::SelectByAttribute::new())),
+            "selectbylocation" => Some(Box::new(gis_analysis::SelectByLocation::new())),
             "shapecomplexityindex" => Some(Box::new(gis_analysis::ShapeComplexityIndex::new())),
             "shapecomplexityindexraster" => {
                 Some(Box::new(gis_analysis::ShapeComplexityIndexRaster::new()))
             }
             "smoothvectors" => Some(Box::new(gis_analysis::SmoothVectors::new())),
+            "spatialjoin" => Some(Box::new(gis_analysis::SpatialJoin::new())),
             "splitwithlines" => Some(Box::new(gis_analysis::SplitWithLines::new())),
             "sumoverlay" => Some(Box::new(gis_analysis::SumOverlay::new())),
             "symmetricaldifference" => Some(Box::new(gis_analysis::SymmetricalDifference::new())),
@@ -1234,6 +1290,26 @@ impl ToolManager {
     }
 
     pub fn run_tool(&self, tool_name: String, args: Vec<String>) -> Result<(), Error> {
+        // The --band flag selects the band that is read from multi-band input rasters and the
+        // --cog flag writes GeoTIFF outputs as Cloud-Optimized GeoTIFFs. They are handled here,
+        // rather than by the individual tools, so that they apply to any tool.
+        let (flags, args) = RasterFlags::parse(&args)?;
+
+        let parameters = self.get_parameter_list(&tool_name);
+        if let Some(parameters) = &parameters {
+            self.validate_args(&tool_name, parameters, &args)?;
+        }
+
+        match parameters {
+            Some(parameters) if !flags.is_empty() => {
+                self.run_tool_with_flags(&tool_name, &parameters, args, &flags)
+            }
+            _ => self.execute_tool(&tool_name, args),
+        }
+    }
+
+    /// Runs a tool, or a plugin, with arguments that have already been validated.
+    fn execute_tool(&self, tool_name: &str, args: Vec<String>) -> Result<(), Error> {
         match self.get_tool(tool_name.as_ref()) {
             Some(tool) => return tool.run(args, &self.working_dir, self.verbose),
             None => {
@@ -1267,17 +1343,33 @@ impl ToolManager {
                     );
                     let mut subcommand = vec!["run".to_string()];
                     subcommand.extend_from_slice(&args);
-                    let mut child = Command::new(exe)
-                        .arg("run")
-                        .args(&args2)
-                        .spawn()
-                        .expect("failed to execute process");
-
-                    let ecode = child.wait()
-                        .expect("failed to wait on child");
-                    
+                    let mut child = match Command::new(&exe).arg("run").args(&args2).spawn() {
+                        Ok(c) => c,
+                        Err(e) => {
+                            return Err(ToolError::new_error(
+                                ToolErrorKind::Io,
+                                format!("Failure to run plugin subprocess {} ({}).", exe, e),
+                            ))
+                        }
+                    };
+
+                    let ecode = child.wait()?;
+
                     if !ecode.success() {
-                        println!("Failure to run plugin subprocess.");
+                        // Plugins that report their errors using the whitebox_tools exit codes
+                        // keep their error category; any other failure is a tool failure.
+                        let kind = ecode
+                            .code()
+                            .and_then(ToolErrorKind::from_exit_code)
+                            .unwrap_or(ToolErrorKind::ToolFailure);
+                        let code = match ecode.code() {
+                            Some(c) => c.to_string(),
+                            None => "none".to_string(),
+                        };
+                        return Err(ToolError::new_error(
+                            kind,
+                            format!("Failure to run plugin subprocess (exit code {}).", code),
+                        ));
                     }
                 } else {
                     // We couldn't find an executable file for the tool, but still check to see if it's 
@@ -1352,13 +1444,13 @@ impl ToolManager {
                         "yieldnormalization"
                     ];
                     if plugin_names.contains(&tool_name.to_lowercase().as_ref()) {
-                        return Err(Error::new(
-                            ErrorKind::NotFound,
+                        return Err(ToolError::new_error(
+                            ToolErrorKind::License,
                             format!("Invalid license: \nThis tool is part of a Whitebox extension product \nand there is a missing license. Please contact \nWhitebox Geospatial Inc. (support@whiteboxgeo.com) to obtain \na valid license key."),
                         ))
                     } else {
-                        return Err(Error::new(
-                            ErrorKind::NotFound,
+                        return Err(ToolError::new_error(
+                            ToolErrorKind::BadArgument,
                             format!("Unrecognized tool name {}.", tool_name),
                         ))
                     }
@@ -1482,6 +1574,18 @@ Example usage:
         Ok(())
     }
 
+    /// Returns the names of the tools, including the tools of plugin libraries but not
+    /// those of plugin executables, sorted.
+    pub fn get_tool_names(&self) -> Vec<String> {
+        self.tool_names.clone()
+    }
+
+    /// Returns the JSON description of a tool's parameters, as printed by --toolparameters,
+    /// or `None` if the tool is not recognized.
+    pub fn get_tool_parameters(&self, tool_name: &str) -> Option<String> {
+        self.get_tool(tool_name).map(|tool| tool.get_tool_parameters())
+    }
+
     pub fn tool_parameters(&self, tool_name: String) -> Result<(), Error> {
         match self.get_tool(tool_name.as_ref()) {
             Some(tool) => println!("{}", tool.get_tool_parameters()),
@@ -1675,6 +1779,44 @@ pub trait WhiteboxTool {
     ) -> Result<(), Error>;
 }
 
+/// A tool from a plugin library that was loaded by the ToolManager.
+struct PluginTool(Arc<dyn plugins::Tool>);
+
+impl WhiteboxTool for PluginTool {
+    fn get_tool_name(&self) -> String {
+        self.0.get_tool_name()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.0.get_tool_description()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        self.0.get_tool_parameters()
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.0.get_example_usage()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.0.get_toolbox()
+    }
+
+    fn get_source_file(&self) -> String {
+        self.0.get_source_file()
+    }
+
+    fn run(
+        &self,
+        args: Vec<String>,
+        working_directory: &str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        self.0.run(args, working_directory, verbose)
+    }
+}
+
 fn get_help<'a>(wt: Box<dyn WhiteboxTool + 'a>) -> String {
     let tool_name = wt.get_tool_name();
     let description = wt.get_tool_description();
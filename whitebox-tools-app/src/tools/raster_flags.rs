@@ -0,0 +1,212 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Applies the global --band and --cog flags, which are handled by the ToolManager, rather
+//! than by the individual tools, so that they apply to any tool or plugin.
+//!
+//! The selected band of each multi-band input raster is extracted to a temporary single-band
+//! GeoTIFF, which is given to the tool in place of the input, and the GeoTIFF outputs of the
+//! tool are rewritten as Cloud-Optimized GeoTIFFs once it has run. The flags are passed to
+//! the readers and writers explicitly, so that the flags of one run never affect another.
+
+use super::validation::{find_parameter, parse_args};
+use super::{ParameterFileType, ParameterType, ToolError, ToolErrorKind, ToolManager, ToolParameter};
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::process;
+use whitebox_raster::{CogOptions, OverviewResampling, Raster};
+
+/// The global flags that modify how the raster inputs and outputs of a tool are read and
+/// written.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct RasterFlags {
+    /// The band (one-based) that is read from multi-band input rasters.
+    pub band: Option<usize>,
+    /// The options used to write GeoTIFF outputs as Cloud-Optimized GeoTIFFs.
+    pub cog: Option<CogOptions>,
+}
+
+impl RasterFlags {
+    /// Removes the --band and --cog flags from the arguments of a tool, returning the flags
+    /// and the remaining arguments.
+    pub fn parse(args: &[String]) -> Result<(RasterFlags, Vec<String>), Error> {
+        let mut flags = RasterFlags::default();
+        let mut tool_args = Vec::with_capacity(args.len());
+        let mut a = 0;
+        while a < args.len() {
+            let arg = args[a].replace("\"", "").replace("\'", "");
+            let vec = arg.splitn(2, "=").collect::<Vec<&str>>();
+            if vec[0].to_lowercase().replace("--", "-") == "-band" {
+                let val = if vec.len() > 1 {
+                    vec[1].to_string()
+                } else {
+                    a += 1;
+                    args.get(a).cloned().unwrap_or_default()
+                };
+                let band = match val.trim().parse::<usize>() {
+                    Ok(b) if b > 0 => b,
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Error parsing --band ({}). Bands are numbered starting from 1.", val),
+                        ))
+                    }
+                };
+                flags.band = Some(band);
+            } else if vec[0].to_lowercase().replace("--", "-") == "-cog" {
+                let resampling = if vec.len() > 1 {
+                    vec[1].parse::<OverviewResampling>()?
+                } else {
+                    OverviewResampling::Nearest
+                };
+                flags.cog = Some(CogOptions::new(resampling));
+            } else {
+                tool_args.push(args[a].clone());
+            }
+            a += 1;
+        }
+        Ok((flags, tool_args))
+    }
+
+    /// Returns true if neither flag is set.
+    pub fn is_empty(&self) -> bool {
+        self.band.is_none() && self.cog.is_none()
+    }
+}
+
+impl ToolManager {
+    /// Runs a tool whose arguments have already been validated, applying the raster flags to
+    /// its raster inputs and outputs.
+    pub(super) fn run_tool_with_flags(
+        &self,
+        tool_name: &str,
+        parameters: &[ToolParameter],
+        args: Vec<String>,
+        flags: &RasterFlags,
+    ) -> Result<(), Error> {
+        let mut tool_args = parse_args(parameters, &args);
+        let mut temp_files = vec![];
+        let mut outputs = vec![];
+        let mut result = Ok(());
+        for (a, arg) in tool_args.iter_mut().enumerate() {
+            let value = match &arg.value {
+                Some(v) => v.clone(),
+                None => continue,
+            };
+            let input_files = match find_parameter(parameters, &arg.flag).map(|p| &p.parameter_type) {
+                Some(ParameterType::ExistingFile(ParameterFileType::Raster)) => vec![value],
+                Some(ParameterType::ExistingFileOrFloat(ParameterFileType::Raster))
+                    if value.trim().parse::<f64>().is_err() =>
+                {
+                    vec![value]
+                }
+                Some(ParameterType::FileList(ParameterFileType::Raster)) => value
+                    .split([';', ','])
+                    .map(|f| f.trim().to_string())
+                    .filter(|f| !f.is_empty())
+                    .collect(),
+                Some(ParameterType::NewFile(ParameterFileType::Raster)) => {
+                    outputs.push(self.resolve_file_name(&value, &self.working_dir));
+                    continue;
+                }
+                _ => continue,
+            };
+            let band = match flags.band {
+                Some(b) => b,
+                None => continue,
+            };
+            let mut selected_files = vec![];
+            for (i, input_file) in input_files.iter().enumerate() {
+                let temp_file = temp_file_name(input_file, band, a, i);
+                match self.extract_band(input_file, band, &temp_file) {
+                    Ok(true) => {
+                        selected_files.push(temp_file.clone());
+                        temp_files.push(temp_file);
+                    }
+                    Ok(false) => selected_files.push(input_file.clone()),
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+            if result.is_err() {
+                break;
+            }
+            arg.value = Some(selected_files.join(";"));
+        }
+
+        if result.is_ok() {
+            let args = tool_args
+                .iter()
+                .map(|arg| match &arg.value {
+                    Some(v) => format!("{}={}", arg.flag, v),
+                    None => arg.flag.clone(),
+                })
+                .collect();
+            result = self.execute_tool(tool_name, args);
+        }
+        for file in &temp_files {
+            let _ = fs::remove_file(file);
+        }
+        result?;
+
+        if let Some(options) = flags.cog {
+            for output_file in &outputs {
+                let lowercase = output_file.to_lowercase();
+                if (lowercase.ends_with(".tif") || lowercase.ends_with(".tiff"))
+                    && Path::new(output_file).is_file()
+                {
+                    let mut output = Raster::new(output_file, "rw")?;
+                    output.configs.cog = Some(options);
+                    output.write()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a band (one-based) of a multi-band raster to a single-band GeoTIFF, returning
+    /// false, without writing anything, if the raster contains a single band.
+    fn extract_band(&self, input_file: &str, band: usize, temp_file: &str) -> Result<bool, Error> {
+        let mut input = Raster::new(&self.resolve_file_name(input_file, &self.working_dir), "r")?;
+        if band > input.num_bands() {
+            return Err(ToolError::new_error(
+                ToolErrorKind::BadArgument,
+                format!(
+                    "Band {} was selected but {} contains {} band(s).",
+                    band,
+                    input_file,
+                    input.num_bands()
+                ),
+            ));
+        }
+        if input.num_bands() == 1 {
+            return Ok(false);
+        }
+        input.select_band(band - 1)?;
+        input.set_file_name(temp_file);
+        input.write()?;
+        Ok(true)
+    }
+}
+
+/// Returns the name of the temporary file that holds the selected band of an input file,
+/// which is unique to the argument (`arg`) and to the file's position in a file list.
+fn temp_file_name(input_file: &str, band: usize, arg: usize, file: usize) -> String {
+    let stem = Path::new(input_file)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    env::temp_dir()
+        .join(format!("{}_band{}_{}_{}_{}.tif", stem, band, process::id(), arg, file))
+        .to_string_lossy()
+        .to_string()
+}
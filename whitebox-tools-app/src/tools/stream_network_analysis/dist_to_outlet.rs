@@ -303,8 +303,10 @@ impl WhiteboxTool for DistanceToOutlet {
         let mut num_solved_cells = 0;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 && streams[(row, col)] != nodata {
-                    dir = pntr[(row, col)] as usize;
+                if streams.get_value(row, col) > 0.0
+                    && !is_nodata_value(streams.get_value(row, col), nodata)
+                {
+                    dir = pntr.get_value(row, col) as usize;
                     if dir > 0 {
                         if dir > 128 || pntr_matches[dir] == 999 {
                             return Err(Error::new(ErrorKind::InvalidInput,
@@ -313,13 +315,13 @@ impl WhiteboxTool for DistanceToOutlet {
                     } else {
                         // It's an outlet; add it to the stack
                         stack.push((row, col));
-                        output[(row, col)] = 0f64;
+                        output.set_value(row, col, 0f64);
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
-                        output[(row, col)] = background_val;
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                        output.set_value(row, col, background_val);
                     } else {
-                        output[(row, col)] = nodata;
+                        output.set_value(row, col, nodata);
                     }
                     num_solved_cells += 1;
                 }
@@ -359,7 +361,7 @@ impl WhiteboxTool for DistanceToOutlet {
             row = cell.0;
             col = cell.1;
 
-            val = output[(row, col)];
+            val = output.get_value(row, col);
 
             // how many inflowing neighbours are there?
             // count = 0i8;
@@ -378,9 +380,11 @@ impl WhiteboxTool for DistanceToOutlet {
             for i in 0..8 {
                 row_n = row + d_y[i];
                 col_n = col + d_x[i];
-                if streams[(row_n, col_n)] > 0.0 && pntr[(row_n, col_n)] == inflowing_vals[i] {
+                if streams.get_value(row_n, col_n) > 0.0
+                    && pntr.get_value(row_n, col_n) == inflowing_vals[i]
+                {
                     stack.push((row_n, col_n));
-                    output[(row_n, col_n)] = val + grid_lengths[i];
+                    output.set_value(row_n, col_n, val + grid_lengths[i]);
                 }
             }
             // }
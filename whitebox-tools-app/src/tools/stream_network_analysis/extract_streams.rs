@@ -265,10 +265,10 @@ impl WhiteboxTool for ExtractStreams {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = flow_accum[(row, col)];
-                        if z != nodata && z > fa_threshold {
+                        z = flow_accum.get_value(row, col);
+                        if !is_nodata_value(z, nodata) && z > fa_threshold {
                             data[col as usize] = 1.0;
-                        } else if z != nodata {
+                        } else if !is_nodata_value(z, nodata) {
                             data[col as usize] = background_val;
                         } else {
                             data[col as usize] = nodata;
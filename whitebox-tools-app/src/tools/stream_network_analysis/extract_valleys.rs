@@ -354,13 +354,13 @@ impl WhiteboxTool for ExtractValleys {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input.get_value(row, col);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     n = 0f64;
                                     // let mut cell_data = Vec::with_capacity(num_cells_in_filter);
                                     for i in 0..num_cells_in_filter {
                                         if filter_shape[i] {
                                             zn = input.get_value(row + dy[i], col + dx[i]);
-                                            if zn != nodata {
+                                            if !is_nodata_value(zn, nodata) {
                                                 // cell_data.push(zn);
                                                 cell_data[i] = zn;
                                                 n += 1f64;
@@ -419,16 +419,24 @@ impl WhiteboxTool for ExtractValleys {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                z = input[(row, col)];
-                                if z != nodata {
-                                    zn1 = input[(row + dy[0], col + dx[0])];
-                                    zn2 = input[(row + dy[1], col + dx[1])];
-                                    if zn1 != nodata && zn2 != nodata && zn1 > z && zn2 > z {
+                                z = input.get_value(row, col);
+                                if !is_nodata_value(z, nodata) {
+                                    zn1 = input.get_value(row + dy[0], col + dx[0]);
+                                    zn2 = input.get_value(row + dy[1], col + dx[1]);
+                                    if !is_nodata_value(zn1, nodata)
+                                        && !is_nodata_value(zn2, nodata)
+                                        && zn1 > z
+                                        && zn2 > z
+                                    {
                                         data[col as usize] = 1f64;
                                     } else {
-                                        zn1 = input[(row + dy[2], col + dx[2])];
-                                        zn2 = input[(row + dy[3], col + dx[3])];
-                                        if zn1 != nodata && zn2 != nodata && zn1 > z && zn2 > z {
+                                        zn1 = input.get_value(row + dy[2], col + dx[2]);
+                                        zn2 = input.get_value(row + dy[3], col + dx[3]);
+                                        if !is_nodata_value(zn1, nodata)
+                                            && !is_nodata_value(zn2, nodata)
+                                            && zn1 > z
+                                            && zn2 > z
+                                        {
                                             data[col as usize] = 1f64;
                                         } else {
                                             data[col as usize] = 0f64;
@@ -466,13 +474,13 @@ impl WhiteboxTool for ExtractValleys {
                 let num_scan_cells = dx.len();
                 for row in 0..rows {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             maxz = z;
                             which_cell = 3;
                             for n in 0..num_scan_cells {
-                                z = input[(row + dy[n], col + dx[n])];
-                                if z != nodata {
+                                z = input.get_value(row + dy[n], col + dx[n]);
+                                if !is_nodata_value(z, nodata) {
                                     if z > maxz {
                                         maxz = z;
                                         which_cell = n;
@@ -481,7 +489,7 @@ impl WhiteboxTool for ExtractValleys {
                             }
                             output.set_value(row + dy[which_cell], col + dx[which_cell], 0f64);
                         } else {
-                            output[(row, col)] = nodata;
+                            output.set_value(row, col, nodata);
                         }
                     }
                     if verbose {
@@ -532,11 +540,11 @@ impl WhiteboxTool for ExtractValleys {
                 for a in 0..8 {
                     for row in 0..rows {
                         for col in 0..columns {
-                            z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            z = output.get_value(row, col);
+                            if z > 0.0 && !is_nodata_value(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
-                                    neighbours[i] = output[(row + dy[i], col + dx[i])];
+                                    neighbours[i] = output.get_value(row + dy[i], col + dx[i]);
                                 }
 
                                 // scan through element
@@ -547,7 +555,7 @@ impl WhiteboxTool for ExtractValleys {
                                     }
                                 }
                                 if pattern_match {
-                                    output[(row, col)] = 0.0;
+                                    output.set_value(row, col, 0.0);
                                     did_something = true;
                                 }
                             }
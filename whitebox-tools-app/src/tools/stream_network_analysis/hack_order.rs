@@ -311,11 +311,11 @@ impl WhiteboxTool for HackStreamOrder {
         let mut current_id = 1f64;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 {
+                if streams.get_value(row, col) > 0.0 {
                     count = 0i8;
                     for i in 0..8 {
-                        if streams[(row + d_y[i], col + d_x[i])] > 0.0
-                            && pntr[(row + d_y[i], col + d_x[i])] == inflowing_vals[i]
+                        if streams.get_value(row + d_y[i], col + d_x[i]) > 0.0
+                            && pntr.get_value(row + d_y[i], col + d_x[i]) == inflowing_vals[i]
                         {
                             count += 1;
                         }
@@ -324,16 +324,16 @@ impl WhiteboxTool for HackStreamOrder {
                     if count == 0 {
                         // It's a headwater; add it to the stack
                         stack.push((row, col));
-                        output[(row, col)] = current_id;
+                        output.set_value(row, col, current_id);
                         current_id += 1f64;
                         trib_length[(row, col)] = 0f64;
                         hack_order.push(0f64);
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
-                        output[(row, col)] = background_val;
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                        output.set_value(row, col, background_val);
                     } else {
-                        output[(row, col)] = nodata;
+                        output.set_value(row, col, nodata);
                     }
                     num_solved_cells += 1;
                 }
@@ -387,10 +387,10 @@ impl WhiteboxTool for HackStreamOrder {
             row = cell.0;
             col = cell.1;
 
-            val = output[(row, col)];
+            val = output.get_value(row, col);
 
             // find the downstream cell
-            dir = pntr[(row, col)] as usize;
+            dir = pntr.get_value(row, col) as usize;
             if dir > 0 {
                 if dir > 128 || pntr_matches[dir] == 999 {
                     return Err(Error::new(ErrorKind::InvalidInput,
@@ -402,9 +402,11 @@ impl WhiteboxTool for HackStreamOrder {
                 col_n = col + d_x[c];
 
                 length = trib_length[(row, col)] + grid_lengths[c];
-                if trib_length[(row_n, col_n)] < length || trib_length[(row_n, col_n)] == nodata {
+                if trib_length[(row_n, col_n)] < length
+                    || is_nodata_value(trib_length[(row_n, col_n)], nodata)
+                {
                     trib_length[(row_n, col_n)] = length;
-                    output[(row_n, col_n)] = val;
+                    output.set_value(row_n, col_n, val);
                 }
 
                 num_inflowing.decrement(row_n, col_n, 1);
@@ -432,21 +434,23 @@ impl WhiteboxTool for HackStreamOrder {
             row = cell.0;
             col = cell.1;
 
-            trib_val = output[(row, col)];
+            trib_val = output.get_value(row, col);
             ho = hack_order[(trib_val - 1.0) as usize];
             if ho == 0.0 {
                 ho = 1.0;
-                output[(row, col)] = 1.0;
+                output.set_value(row, col, 1.0);
             } else {
-                output[(row, col)] = ho;
+                output.set_value(row, col, ho);
             }
 
             // find any inflowing stream cells
             for i in 0..8 {
                 row_n = row + d_y[i];
                 col_n = col + d_x[i];
-                if streams[(row_n, col_n)] > 0.0 && pntr[(row_n, col_n)] == inflowing_vals[i] {
-                    trib_val_n = output[(row_n, col_n)];
+                if streams.get_value(row_n, col_n) > 0.0
+                    && pntr.get_value(row_n, col_n) == inflowing_vals[i]
+                {
+                    trib_val_n = output.get_value(row_n, col_n);
                     if trib_val_n != trib_val {
                         hack_order[(trib_val_n - 1.0) as usize] = ho + 1.0;
                     }
@@ -315,11 +315,11 @@ impl WhiteboxTool for HortonStreamOrder {
         let mut current_id = 1i32;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 {
+                if streams.get_value(row, col) > 0.0 {
                     count = 0i8;
                     for i in 0..8 {
-                        if streams[(row + dy[i], col + dx[i])] > 0.0
-                            && pntr[(row + dy[i], col + dx[i])] == inflowing_vals[i]
+                        if streams.get_value(row + dy[i], col + dx[i]) > 0.0
+                            && pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                         {
                             count += 1;
                         }
@@ -333,14 +333,14 @@ impl WhiteboxTool for HortonStreamOrder {
                         current_id += 1;
                         channel_heads.push((row, col));
                         max_order.push(1f64);
-                        output[(row, col)] = 1f64;
+                        output.set_value(row, col, 1f64);
                         trib_length[(row, col)] = 0f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
-                        output[(row, col)] = background_val;
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                        output.set_value(row, col, background_val);
                     } else {
-                        output[(row, col)] = nodata;
+                        output.set_value(row, col, nodata);
                     }
                     num_solved_cells += 1;
                 }
@@ -397,10 +397,10 @@ impl WhiteboxTool for HortonStreamOrder {
             col = cell.1;
 
             trib = trib_id[(row, col)];
-            order_val = output[(row, col)];
+            order_val = output.get_value(row, col);
 
             // find the downstream cell
-            dir = pntr[(row, col)] as usize;
+            dir = pntr.get_value(row, col) as usize;
             if dir > 0 {
                 if dir > 128 || pntr_matches[dir] == 999 {
                     return Err(Error::new(ErrorKind::InvalidInput,
@@ -412,16 +412,18 @@ impl WhiteboxTool for HortonStreamOrder {
                 col_n = col + dx[c];
 
                 length = trib_length[(row, col)] + grid_lengths[c];
-                if trib_length[(row_n, col_n)] < length || trib_length[(row_n, col_n)] == nodata {
+                if trib_length[(row_n, col_n)] < length
+                    || is_nodata_value(trib_length[(row_n, col_n)], nodata)
+                {
                     trib_length[(row_n, col_n)] = length;
                     trib_id[(row_n, col_n)] = trib;
                 }
 
-                order_val_n = output[(row_n, col_n)];
+                order_val_n = output.get_value(row_n, col_n);
                 if order_val == order_val_n {
-                    output[(row_n, col_n)] = order_val + 1.0;
+                    output.set_value(row_n, col_n, order_val + 1.0);
                 } else if order_val > order_val_n {
-                    output[(row_n, col_n)] = order_val;
+                    output.set_value(row_n, col_n, order_val);
                 }
 
                 num_inflowing.decrement(row_n, col_n, 1);
@@ -431,15 +433,15 @@ impl WhiteboxTool for HortonStreamOrder {
 
                 // see if a tributary needs updating
                 trib = trib_id[(row_n, col_n)];
-                order_val = output[(row_n, col_n)];
+                order_val = output.get_value(row_n, col_n);
                 if max_order[(trib - 1) as usize] < order_val {
                     max_order[(trib - 1) as usize] = order_val;
                     let cell = channel_heads[(trib - 1) as usize];
                     let mut y = cell.0;
                     let mut x = cell.1;
-                    while output[(y, x)] != nodata {
-                        output[(y, x)] = order_val;
-                        dir = pntr[(y, x)] as usize;
+                    while !is_nodata_value(output.get_value(y, x), nodata) {
+                        output.set_value(y, x, order_val);
+                        dir = pntr.get_value(y, x) as usize;
                         if dir > 0 {
                             if dir > 128 || pntr_matches[dir] == 999 {
                                 return Err(Error::new(ErrorKind::InvalidInput,
@@ -301,11 +301,13 @@ impl WhiteboxTool for RasterStreamsToVector {
         let mut count: i8;
         for row in 0..rows {
             for col in 0..columns {
-                if streams.get_value(row, col) > 0.0 && streams.get_value(row, col) != nodata {
+                if streams.get_value(row, col) > 0.0
+                    && !is_nodata_value(streams.get_value(row, col), nodata)
+                {
                     count = 0i8;
                     for i in 0..8 {
                         if streams.get_value(row + dy[i], col + dx[i]) > 0.0
-                            && streams.get_value(row + dy[i], col + dx[i]) != nodata
+                            && !is_nodata_value(streams.get_value(row + dy[i], col + dx[i]), nodata)
                             && pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                         {
                             count += 1;
@@ -383,7 +385,7 @@ impl WhiteboxTool for RasterStreamsToVector {
                 prev_dir = 99; // this way the first point in the line is always output.
                 flag = true;
                 while flag {
-                    if pntr.get_value(row, col) != pntr_nodata {
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
                         dir = pntr.get_value(row, col) as usize;
                         already_added_point = if dir != prev_dir || all_vertices {
                             x = pntr.get_x_from_column(col);
@@ -396,7 +398,7 @@ impl WhiteboxTool for RasterStreamsToVector {
                         };
                         if dir > 0
                             && streams.get_value(row, col) > 0.0
-                            && streams.get_value(row, col) != nodata
+                            && !is_nodata_value(streams.get_value(row, col), nodata)
                         {
                             if dir > 128 || pntr_matches[dir] == 999 {
                                 return Err(Error::new(
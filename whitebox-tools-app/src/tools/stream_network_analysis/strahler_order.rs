@@ -328,13 +328,15 @@ impl WhiteboxTool for StrahlerStreamOrder {
         let mut dir: usize;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 {
+                if streams.get_value(row, col) > 0.0 {
                     // see if it is a headwater location
                     num_neighbouring_stream_cells = 0i8;
                     for c in 0..8 {
                         x = col + d_x[c];
                         y = row + d_y[c];
-                        if streams[(y, x)] > 0.0 && pntr[(y, x)] == inflowing_vals[c] {
+                        if streams.get_value(y, x) > 0.0
+                            && pntr.get_value(y, x) == inflowing_vals[c]
+                        {
                             num_neighbouring_stream_cells += 1;
                         }
                     }
@@ -343,12 +345,12 @@ impl WhiteboxTool for StrahlerStreamOrder {
                         x = col;
                         y = row;
                         current_order = 1f64;
-                        output[(y, x)] = current_order;
+                        output.set_value(y, x, current_order);
                         flag = true;
                         while flag {
                             // find the downslope neighbour
-                            if pntr[(y, x)] > 0.0 {
-                                dir = pntr[(y, x)] as usize;
+                            if pntr.get_value(y, x) > 0.0 {
+                                dir = pntr.get_value(y, x) as usize;
                                 if dir > 128 || pntr_matches[dir] == 999 {
                                     return Err(Error::new(ErrorKind::InvalidInput,
                                         "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
@@ -357,11 +359,11 @@ impl WhiteboxTool for StrahlerStreamOrder {
                                 x += d_x[pntr_matches[dir]];
                                 y += d_y[pntr_matches[dir]];
 
-                                if streams[(y, x)] <= 0.0 {
+                                if streams.get_value(y, x) <= 0.0 {
                                     //it's not a stream cell
                                     flag = false;
                                 } else {
-                                    current_value = output[(y, x)];
+                                    current_value = output.get_value(y, x);
                                     if current_value > current_order {
                                         //flag = false; // run into a larger stream, end the downstream search
                                         break;
@@ -371,9 +373,9 @@ impl WhiteboxTool for StrahlerStreamOrder {
                                         for d in 0..8 {
                                             x2 = x + d_x[d];
                                             y2 = y + d_y[d];
-                                            if streams[(y2, x2)] > 0.0
-                                                && pntr[(y2, x2)] == inflowing_vals[d]
-                                                && output[(y2, x2)] == current_order
+                                            if streams.get_value(y2, x2) > 0.0
+                                                && pntr.get_value(y2, x2) == inflowing_vals[d]
+                                                && output.get_value(y2, x2) == current_order
                                             {
                                                 num_neighbouring_stream_cells += 1;
                                             }
@@ -389,11 +391,11 @@ impl WhiteboxTool for StrahlerStreamOrder {
                                         }
                                     }
                                     if current_value < current_order {
-                                        output[(y, x)] = current_order;
+                                        output.set_value(y, x, current_order);
                                     }
                                 }
                             } else {
-                                if streams[(y, x)] > 0.0 {
+                                if streams.get_value(y, x) > 0.0 {
                                     //it is a valid stream cell and probably just has no downslope neighbour (e.g. at the edge of the grid)
                                     output.increment(y, x, 1.0);
                                 }
@@ -402,10 +404,10 @@ impl WhiteboxTool for StrahlerStreamOrder {
                         }
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
-                        output[(row, col)] = background_val;
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                        output.set_value(row, col, background_val);
                     } else {
-                        output[(row, col)] = streams_nodata;
+                        output.set_value(row, col, streams_nodata);
                     }
                 }
             }
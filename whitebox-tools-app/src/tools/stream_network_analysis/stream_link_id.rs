@@ -290,11 +290,11 @@ impl WhiteboxTool for StreamLinkIdentifier {
         let mut current_id = 1f64;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 {
+                if streams.get_value(row, col) > 0.0 {
                     count = 0i8;
                     for i in 0..8 {
-                        if streams[(row + dy[i], col + dx[i])] > 0.0
-                            && pntr[(row + dy[i], col + dx[i])] == inflowing_vals[i]
+                        if streams.get_value(row + dy[i], col + dx[i]) > 0.0
+                            && pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                         {
                             count += 1;
                         }
@@ -303,14 +303,14 @@ impl WhiteboxTool for StreamLinkIdentifier {
                     if count == 0 {
                         // It's a headwater; add it to the stack
                         stack.push((row, col));
-                        output[(row, col)] = current_id;
+                        output.set_value(row, col, current_id);
                         current_id += 1f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
-                        output[(row, col)] = background_val;
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                        output.set_value(row, col, background_val);
                     } else {
-                        output[(row, col)] = nodata;
+                        output.set_value(row, col, nodata);
                     }
                     num_solved_cells += 1;
                 }
@@ -364,10 +364,10 @@ impl WhiteboxTool for StreamLinkIdentifier {
             row = cell.0;
             col = cell.1;
 
-            val = output[(row, col)];
+            val = output.get_value(row, col);
 
             // find the downstream cell
-            dir = pntr[(row, col)] as usize;
+            dir = pntr.get_value(row, col) as usize;
             if dir > 0 {
                 if dir > 128 || pntr_matches[dir] == 999 {
                     return Err(Error::new(ErrorKind::InvalidInput,
@@ -378,9 +378,9 @@ impl WhiteboxTool for StreamLinkIdentifier {
                 col_n = col + dx[c];
                 if num_inflowing[(row_n, col_n)] > 1 {
                     current_id += 1f64;
-                    output[(row_n, col_n)] = current_id;
-                } else if output[(row_n, col_n)] == nodata {
-                    output[(row_n, col_n)] = val;
+                    output.set_value(row_n, col_n, current_id);
+                } else if is_nodata_value(output.get_value(row_n, col_n), nodata) {
+                    output.set_value(row_n, col_n, val);
                 }
 
                 num_inflowing.decrement(row_n, col_n, 1);
@@ -311,10 +311,12 @@ impl WhiteboxTool for StreamLinkLength {
         let mut dir: usize;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 && streams[(row, col)] != nodata {
-                    current_id = streams[(row, col)] as usize;
-                    dir = pntr[(row, col)] as usize;
-                    if dir > 0 && pntr[(row, col)] != pntr_nodata {
+                if streams.get_value(row, col) > 0.0
+                    && !is_nodata_value(streams.get_value(row, col), nodata)
+                {
+                    current_id = streams.get_value(row, col) as usize;
+                    dir = pntr.get_value(row, col) as usize;
+                    if dir > 0 && !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
                         if dir > 128 || pntr_matches[dir] == 999 {
                             return Err(Error::new(ErrorKind::InvalidInput,
                                 "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
@@ -335,11 +337,13 @@ impl WhiteboxTool for StreamLinkLength {
 
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 && streams[(row, col)] != nodata {
-                    current_id = streams[(row, col)] as usize;
-                    output[(row, col)] = link_length[current_id];
+                if streams.get_value(row, col) > 0.0
+                    && !is_nodata_value(streams.get_value(row, col), nodata)
+                {
+                    current_id = streams.get_value(row, col) as usize;
+                    output.set_value(row, col, link_length[current_id]);
                 } else {
-                    output[(row, col)] = background_val;
+                    output.set_value(row, col, background_val);
                 }
             }
             if verbose {
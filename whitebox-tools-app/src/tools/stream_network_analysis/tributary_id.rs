@@ -307,11 +307,11 @@ impl WhiteboxTool for TributaryIdentifier {
         let mut current_id = 1f64;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 {
+                if streams.get_value(row, col) > 0.0 {
                     count = 0i8;
                     for i in 0..8 {
-                        if streams[(row + d_y[i], col + d_x[i])] > 0.0
-                            && pntr[(row + d_y[i], col + d_x[i])] == inflowing_vals[i]
+                        if streams.get_value(row + d_y[i], col + d_x[i]) > 0.0
+                            && pntr.get_value(row + d_y[i], col + d_x[i]) == inflowing_vals[i]
                         {
                             count += 1;
                         }
@@ -320,15 +320,15 @@ impl WhiteboxTool for TributaryIdentifier {
                     if count == 0 {
                         // It's a headwater; add it to the stack
                         stack.push((row, col));
-                        output[(row, col)] = current_id;
+                        output.set_value(row, col, current_id);
                         current_id += 1f64;
                         trib_length[(row, col)] = 0f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
-                        output[(row, col)] = background_val;
+                    if !is_nodata_value(pntr.get_value(row, col), pntr_nodata) {
+                        output.set_value(row, col, background_val);
                     } else {
-                        output[(row, col)] = nodata;
+                        output.set_value(row, col, nodata);
                     }
                     num_solved_cells += 1;
                 }
@@ -382,10 +382,10 @@ impl WhiteboxTool for TributaryIdentifier {
             row = cell.0;
             col = cell.1;
 
-            val = output[(row, col)];
+            val = output.get_value(row, col);
 
             // find the downstream cell
-            dir = pntr[(row, col)] as usize;
+            dir = pntr.get_value(row, col) as usize;
             if dir > 0 {
                 if dir > 128 || pntr_matches[dir] == 999 {
                     return Err(Error::new(ErrorKind::InvalidInput,
@@ -397,9 +397,11 @@ impl WhiteboxTool for TributaryIdentifier {
                 col_n = col + d_x[c];
 
                 length = trib_length[(row, col)] + grid_lengths[c];
-                if trib_length[(row_n, col_n)] < length || trib_length[(row_n, col_n)] == nodata {
+                if trib_length[(row_n, col_n)] < length
+                    || is_nodata_value(trib_length[(row_n, col_n)], nodata)
+                {
                     trib_length[(row_n, col_n)] = length;
-                    output[(row_n, col_n)] = val;
+                    output.set_value(row_n, col_n, val);
                 }
 
                 num_inflowing.decrement(row_n, col_n, 1);
@@ -331,14 +331,14 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 sum = 0.0;
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input.get_value(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         sum += weights[a];
                                         z_final += weights[a] * zn;
                                     }
@@ -404,7 +404,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         sum_n = 0;
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata_value(val, nodata) {
                                 val = 0f64;
                             } else {
                                 sum_n += 1;
@@ -428,7 +428,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         sum = 0f64;
                         for col in 0..columns {
                             val = smoothed_dem.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata_value(val, nodata) {
                                 val = 0f64;
                             }
                             sum += val;
@@ -454,7 +454,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -521,16 +521,16 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     let mut data = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for c in 0..8 {
                                 n1[c] = input.get_value(row + dy[c], col + dx[c]);
-                                if n1[c] != nodata {
+                                if !is_nodata_value(n1[c], nodata) {
                                     n1[c] = n1[c] * z_factor;
                                 } else {
                                     n1[c] = input.get_value(row, col) * z_factor;
                                 }
                                 n2[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                if n2[c] != nodata {
+                                if !is_nodata_value(n2[c], nodata) {
                                     n2[c] = n2[c] * z_factor;
                                 } else {
                                     n2[c] = z * z_factor;
@@ -585,7 +585,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                 sumn = 0u32;
                 for col in 0..columns {
                     sum += angular_diff.get_value(row, col);
-                    if smoothed_dem.get_value(row, col) == nodata {
+                    if is_nodata_value(smoothed_dem.get_value(row, col), nodata) {
                         // it's either nodata or a flag cell in the DEM.
                         i_n.decrement(row, col, 1);
                     }
@@ -594,13 +594,13 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if smoothed_dem.get_value(0, 0) == nodata {
+                if is_nodata_value(smoothed_dem.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
                     angular_diff.increment(row, col, angular_diff.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if smoothed_dem.get_value(row, col) == nodata {
+                    if is_nodata_value(smoothed_dem.get_value(row, col), nodata) {
                         // it's either nodata or a flag cell in the DEM.
                         i_n.decrement(row, col, 1);
                     }
@@ -647,7 +647,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -333,14 +333,14 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 sum = 0.0;
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input.get_value(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         sum += weights[a];
                                         z_final += weights[a] * zn;
                                     }
@@ -404,7 +404,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         sum_n = 0;
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata_value(val, nodata) {
                                 val = 0f64;
                             } else {
                                 sum_n += 1;
@@ -428,7 +428,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         sum = 0f64;
                         for col in 0..columns {
                             val = smoothed_dem.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata_value(val, nodata) {
                                 val = 0f64;
                             }
                             sum += val;
@@ -454,7 +454,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -519,10 +519,10 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     let mut ydata = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for c in 0..8 {
                                 n[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                if n[c] != nodata {
+                                if !is_nodata_value(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z * z_factor;
@@ -573,7 +573,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                 for col in 0..columns {
                     sumx += xc.get_value(row, col);
                     sumy += yc.get_value(row, col);
-                    if smoothed_dem.get_value(row, col) == nodata
+                    if is_nodata_value(smoothed_dem.get_value(row, col), nodata)
                         || (xc.get_value(row, col) == 0f64 && yc.get_value(row, col) == 0f64)
                     {
                         // it's either nodata or a flag cell in the DEM.
@@ -585,14 +585,14 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if smoothed_dem.get_value(0, 0) == nodata {
+                if is_nodata_value(smoothed_dem.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
                     xc.increment(row, col, xc.get_value(row, col - 1));
                     yc.increment(row, col, yc.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if smoothed_dem.get_value(row, col) == nodata
+                    if is_nodata_value(smoothed_dem.get_value(row, col), nodata)
                         || (xc.get_value(row, col) == 0f64 && yc.get_value(row, col) == 0f64)
                     {
                         // it's either nodata or a flag cell in the DEM.
@@ -644,7 +644,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
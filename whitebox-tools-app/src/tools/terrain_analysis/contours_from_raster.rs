@@ -6,7 +6,7 @@ Last Modified: 04/03/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::{Array2D, Point2D};
 use crate::tools::*;
 use whitebox_vector::*;
@@ -362,7 +362,7 @@ impl WhiteboxTool for ContoursFromRaster {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             data[col as usize] = ((z - base_contour) / contour_interval).floor();
                         }
                     }
@@ -429,10 +429,10 @@ impl WhiteboxTool for ContoursFromRaster {
         for row in 0..rows {
             for col in 0..columns {
                 z = reclassed.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     for n in 0..4 {
                         zn = reclassed.get_value(row + dy[n], col + dx[n]);
-                        if z > zn && zn != nodata {
+                        if z > zn && !is_nodata_value(zn, nodata) {
                             z1 = zn as isize + 1;
                             z2 = z as isize;
                             for contour_val in z1..=z2 {
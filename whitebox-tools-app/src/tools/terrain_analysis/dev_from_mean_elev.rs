@@ -319,7 +319,7 @@ impl WhiteboxTool for DevFromMeanElev {
                     let mut data = vec![bin_nodata32; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = (z * multiplier).floor() as i32 - min_bin;
                             data[col as usize] = val;
                         }
@@ -358,7 +358,7 @@ impl WhiteboxTool for DevFromMeanElev {
             sum_n = 0;
             for col in 0..columns {
                 val = binned_data.get_value(row, col) as f64;
-                if val == bin_nodata32 as f64 {
+                if is_nodata_value(val, bin_nodata32 as f64) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -430,7 +430,7 @@ impl WhiteboxTool for DevFromMeanElev {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = binned_data.get_value(row, col);
-                        if z != bin_nodata32 {
+                        if !is_nodata_value(z, bin_nodata32) {
                             x1 = col - midpoint_x - 1;
                             if x1 < 0 {
                                 x1 = 0;
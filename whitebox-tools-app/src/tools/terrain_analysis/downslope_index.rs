@@ -308,13 +308,13 @@ impl WhiteboxTool for DownslopeIndex {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             dir = -1i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = input.get_value(row + dy[i], col + dx[i]);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -385,7 +385,7 @@ impl WhiteboxTool for DownslopeIndex {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             row_n = row;
                             col_n = col;
                             z_drop = z;
@@ -399,7 +399,7 @@ impl WhiteboxTool for DownslopeIndex {
                                     row_n += dy[dir as usize];
                                     col_n += dx[dir as usize];
                                     zn = input.get_value(row_n, col_n);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         if (z - zn) >= drop_val {
                                             z_drop = zn;
                                             flag = false;
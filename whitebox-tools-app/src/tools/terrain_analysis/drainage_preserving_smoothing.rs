@@ -11,7 +11,7 @@ Note: this tool was an experiment and has been now removed from the WhiteboxTool
 Testing showed that it was not substantially better than the FeaturePreservingSmoothing tool.
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use num_cpus;
@@ -394,7 +394,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
@@ -443,12 +443,12 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         bin_val = binned_data.get_value(row, col);
-                        if bin_val != bin_nodata {
-                            if old_median != bin_nodata {
+                        if !is_nodata_value(bin_val, bin_nodata) {
+                            if !is_nodata_value(old_median, bin_nodata) {
                                 // remove the trailing column from the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col - midpoint - 1);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] -= 1;
                                         n -= 1.0;
                                         if bin_val_n < old_median {
@@ -460,7 +460,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                                 // add the leading column to the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col + midpoint);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] += 1;
                                         n += 1.0;
                                         if bin_val_n < old_median {
@@ -502,7 +502,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                                 for col2 in start_col..end_col + 1 {
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col2);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata_value(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] += 1;
                                             n += 1f64;
                                         }
@@ -584,10 +584,10 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                     let mut values = [0f32; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     values[i] = (zn * z_factor) as f32;
                                 } else {
                                     values[i] = (z * z_factor) as f32;
@@ -698,7 +698,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                         } else {
                             threshold as f32
                         };
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum_w = 0f32;
                             a = 0f32;
                             b = 0f32;
@@ -706,7 +706,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             for n in 0..num_pixels_in_filter {
                                 xn = col + dx[n];
                                 yn = row + dy[n];
-                                if input.get_value(yn, xn) != nodata {
+                                if !is_nodata_value(input.get_value(yn, xn), nodata) {
                                     //     && exclusions.get_value(yn, xn) == 0f64
                                     // {
                                     diff =
@@ -786,7 +786,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         dfm_value = dfm_data.get_value(row, col);
                         threshold_adj = if dfm_value < 0f64 && dfm_value > dfm_threshold {
                             (max_norm_diff * (1f64 - reduction * dfm_value / dfm_threshold))
@@ -803,7 +803,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             xn = col + dx[n];
                             yn = row + dy[n];
                             zn = output.get_value(yn, xn);
-                            if zn != nodata {
+                            if !is_nodata_value(zn, nodata) {
                                 //&& exclusions.get_value(yn, xn) == 0f64 {
                                 diff = nv_smooth
                                     .get_value(row, col)
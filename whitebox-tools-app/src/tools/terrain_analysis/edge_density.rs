@@ -336,10 +336,10 @@ impl WhiteboxTool for EdgeDensity {
                     let mut values = [0f32; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     values[i] = (zn * z_factor) as f32;
                                 } else {
                                     values[i] = (z * z_factor) as f32;
@@ -405,12 +405,12 @@ impl WhiteboxTool for EdgeDensity {
                     let mut data = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             centre_norm = nv.get_value(row, col);
                             edge_found = false;
                             for c in 0..8 {
                                 zn = input.get_value(row + dy[c], col + dx[c]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     neighbour_norm = nv.get_value(row + dy[c], col + dx[c]);
                                     if centre_norm.angle_between(neighbour_norm) <= threshold32 {
                                         edge_found = true;
@@ -454,20 +454,20 @@ impl WhiteboxTool for EdgeDensity {
                 for col in 0..columns {
                     sum += edges.get_value(row, col);
                     edges.set_value(row, col, sum + edges.get_value(row - 1, col));
-                    if input.get_value(row, col) == nodata {
+                    if is_nodata_value(input.get_value(row, col), nodata) {
                         i_n.decrement(row, col, 1);
                     }
                     sumn += i_n.get_value(row, col);
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if input.get_value(0, 0) == nodata {
+                if is_nodata_value(input.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
                     edges.increment(row, col, edges.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if input.get_value(row, col) == nodata {
+                    if is_nodata_value(input.get_value(row, col), nodata) {
                         i_n.decrement(row, col, 1);
                     }
                 }
@@ -508,7 +508,7 @@ impl WhiteboxTool for EdgeDensity {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -6,7 +6,7 @@ Last Modified: 02/04/2019
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use num_cpus;
@@ -353,7 +353,7 @@ impl WhiteboxTool for ElevPercentile {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
@@ -403,12 +403,12 @@ impl WhiteboxTool for ElevPercentile {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         bin_val = binned_data.get_value(row, col);
-                        if bin_val != bin_nodata {
-                            if old_bin_val != bin_nodata {
+                        if !is_nodata_value(bin_val, bin_nodata) {
+                            if !is_nodata_value(old_bin_val, bin_nodata) {
                                 // remove the trailing column from the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col - midpoint_x - 1);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] -= 1;
                                         n -= 1.0;
                                         if bin_val_n < old_bin_val {
@@ -420,7 +420,7 @@ impl WhiteboxTool for ElevPercentile {
                                 // add the leading column to the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col + midpoint_x);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata_value(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] += 1;
                                         n += 1.0;
                                         if bin_val_n < old_bin_val {
@@ -453,7 +453,7 @@ impl WhiteboxTool for ElevPercentile {
                                 for col2 in start_col..end_col + 1 {
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col2);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata_value(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] += 1;
                                             n += 1f64;
                                             if bin_val_n < bin_val {
@@ -465,7 +465,7 @@ impl WhiteboxTool for ElevPercentile {
                             }
                         }
 
-                        if n > 0f64 && bin_val != bin_nodata {
+                        if n > 0f64 && !is_nodata_value(bin_val, bin_nodata) {
                             data[col as usize] = n_less_than / n * 100.0;
                         } else {
                             data[col as usize] = nodata;
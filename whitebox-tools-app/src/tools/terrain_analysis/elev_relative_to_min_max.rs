@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -216,8 +216,8 @@ impl WhiteboxTool for ElevRelativeToMinMax {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             data[col as usize] = (z - min_val) / range * 100f64;
                         }
                     }
@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -253,9 +253,11 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
                 let mut watershed: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        watershed = watersheds[(row, col)];
-                        if z != nodata && watershed != watershed_nodata {
+                        z = input.get_value(row, col);
+                        watershed = watersheds.get_value(row, col);
+                        if !is_nodata_value(z, nodata)
+                            && !is_nodata_value(watershed, watershed_nodata)
+                        {
                             watershed -= min_watershed;
                             if z < watershed_min_vals[watershed as usize] {
                                 watershed_min_vals[watershed as usize] = z;
@@ -305,9 +307,11 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        watershed = watersheds[(row, col)];
-                        if z != nodata && watershed != watershed_nodata {
+                        z = input.get_value(row, col);
+                        watershed = watersheds.get_value(row, col);
+                        if !is_nodata_value(z, nodata)
+                            && !is_nodata_value(watershed, watershed_nodata)
+                        {
                             watershed -= min_watershed;
                             data[col as usize] = (z - watershed_min_vals[watershed as usize])
                                 / (watershed_max_vals[watershed as usize]
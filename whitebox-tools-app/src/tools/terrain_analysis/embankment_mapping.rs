@@ -6,7 +6,7 @@ Last Modified: 05/10/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::{Array2D, BoundingBox, DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
@@ -432,6 +432,9 @@ impl WhiteboxTool for EmbankmentMapping {
         if !input_file.contains(&sep) && !input_file.contains("/") {
             input_file = format!("{}{}", working_directory, input_file);
         }
+        if !roads_file.contains(&sep) && !roads_file.contains("/") {
+            roads_file = format!("{}{}", working_directory, roads_file);
+        }
         if !output_file.contains(&sep) && !output_file.contains("/") {
             output_file = format!("{}{}", working_directory, output_file);
         }
@@ -738,7 +741,7 @@ impl WhiteboxTool for EmbankmentMapping {
 
             for n in 0..8 {
                 zn = dem.get_value(r + dy[n], c + dx[n]);
-                if zn != nodata && distance.get_value(r + dy[n], c + dx[n]) < 0.0 {
+                if !is_nodata_value(zn, nodata) && distance.get_value(r + dy[n], c + dx[n]) < 0.0 {
                     dist = cell.distance + dist_array[n];
                     if dist < max_width {
                         distance.set_value(r + dy[n], c + dx[n], dist);
@@ -784,7 +787,9 @@ impl WhiteboxTool for EmbankmentMapping {
 
             for n in 0..8 {
                 zn = dem.get_value(r + dy[n], c + dx[n]);
-                if zn != nodata && output.get_value(r + dy[n], c + dx[n]) == nodata {
+                if !is_nodata_value(zn, nodata)
+                    && is_nodata_value(output.get_value(r + dy[n], c + dx[n]), nodata)
+                {
                     dist = distance.get_value(r + dy[n], c + dx[n]);
                     if dist >= 0.0 {
                         if dist <= min_road_width {
@@ -873,9 +878,9 @@ impl WhiteboxTool for EmbankmentMapping {
                         for i in 0..8 {
                             row_n = row + dy[i];
                             col_n = col + dx[i];
-                            if (output.get_value(row_n, col_n) == nodata
+                            if (is_nodata_value(output.get_value(row_n, col_n), nodata)
                                 || output.get_value(row_n, col_n) == 0f64)
-                                && dem.get_value(row_n, col_n) != nodata
+                                && !is_nodata_value(dem.get_value(row_n, col_n), nodata)
                             {
                                 y = dem.get_y_from_row(row_n);
                                 x = dem.get_x_from_column(col_n);
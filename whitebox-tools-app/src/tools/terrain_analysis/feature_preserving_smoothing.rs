@@ -420,10 +420,10 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                     let mut values = [0f32; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     values[i] = (zn * z_factor) as f32;
                                 } else {
                                     values[i] = (z * z_factor) as f32;
@@ -512,14 +512,14 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                     let mut data = vec![Normal { a: 0f32, b: 0f32 }; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             sum_w = 0f32;
                             a = 0f32;
                             b = 0f32;
                             for n in 0..num_pixels_in_filter {
                                 xn = col + dx[n];
                                 yn = row + dy[n];
-                                if input.get_value(yn, xn) != nodata {
+                                if !is_nodata_value(input.get_value(yn, xn), nodata) {
                                     diff =
                                         nv.get_value(row, col).angle_between(nv.get_value(yn, xn));
                                     if diff > threshold32 {
@@ -602,14 +602,14 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
 
             for row in 0..rows {
                 for col in 0..columns {
-                    if input.get_value(row, col) != nodata {
+                    if !is_nodata_value(input.get_value(row, col), nodata) {
                         sum_w = 0f32;
                         z = 0f32;
                         for n in 0..8 {
                             xn = col + dx[n];
                             yn = row + dy[n];
                             zn = output.get_value(yn, xn);
-                            if zn != nodata {
+                            if !is_nodata_value(zn, nodata) {
                                 diff = nv_smooth
                                     .get_value(row, col)
                                     .angle_between(nv_smooth.get_value(yn, xn));
@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::{Array2D, DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
 use num_cpus;
@@ -288,24 +288,24 @@ impl WhiteboxTool for FillMissingData {
             // Scan the raster edges, placing NoData values in a stack
             let mut stack = vec![]; // Vec::with_capacity(((rows + columns) * 5) as usize);
             for row in 0..rows {
-                if input.get_value(row, 0) == nodata {
+                if is_nodata_value(input.get_value(row, 0), nodata) {
                     stack.push((row, 0));
                     edge_connected_nodata.set_value(row, 0, 1);
                 }
 
-                if input.get_value(row, columns - 1) == nodata {
+                if is_nodata_value(input.get_value(row, columns - 1), nodata) {
                     stack.push((row, columns - 1));
                     edge_connected_nodata.set_value(row, columns - 1, 1);
                 }
             }
 
             for col in 0..columns {
-                if input.get_value(0, col) == nodata {
+                if is_nodata_value(input.get_value(0, col), nodata) {
                     stack.push((0, col));
                     edge_connected_nodata.set_value(0, col, 1);
                 }
 
-                if input.get_value(rows - 1, col) == nodata {
+                if is_nodata_value(input.get_value(rows - 1, col), nodata) {
                     stack.push((rows - 1, col));
                     edge_connected_nodata.set_value(rows - 1, col, 1);
                 }
@@ -315,7 +315,7 @@ impl WhiteboxTool for FillMissingData {
             let mut num_cells_popped = 0;
             while let Some(cell) = stack.pop() {
                 for n in 0..8 {
-                    if input.get_value(cell.0 + dy[n], cell.1 + dx[n]) == nodata
+                    if is_nodata_value(input.get_value(cell.0 + dy[n], cell.1 + dx[n]), nodata)
                         && edge_connected_nodata.get_value(cell.0 + dy[n], cell.1 + dx[n]) == 0
                     {
                         stack.push((cell.0 + dy[n], cell.1 + dx[n]));
@@ -345,14 +345,14 @@ impl WhiteboxTool for FillMissingData {
         }
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata_value(input.get_value(row, col), nodata) {
                     for i in 0..8 {
                         row_n = row + dy[i];
                         col_n = col + dx[i];
-                        if input.get_value(row_n, col_n) == nodata
+                        if is_nodata_value(input.get_value(row_n, col_n), nodata)
                             && edge_connected_nodata.get_value(row_n, col_n) == 0
                         {
-                            frs.insert(col as f64, row as f64, input[(row, col)]);
+                            frs.insert(col as f64, row as f64, input.get_value(row, col));
                             break;
                         }
                     }
@@ -393,7 +393,7 @@ impl WhiteboxTool for FillMissingData {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                if input.get_value(row, col) == nodata
+                                if is_nodata_value(input.get_value(row, col), nodata)
                                     && edge_connected_nodata.get_value(row, col) == 0
                                 {
                                     sum_weights = 0f64;
@@ -417,7 +417,7 @@ impl WhiteboxTool for FillMissingData {
                                         data[col as usize] = nodata;
                                     }
                                 } else {
-                                    data[col as usize] = input[(row, col)];
+                                    data[col as usize] = input.get_value(row, col);
                                 }
                             }
                             tx1.send((row, data)).unwrap();
@@ -427,7 +427,7 @@ impl WhiteboxTool for FillMissingData {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                if input.get_value(row, col) == nodata
+                                if is_nodata_value(input.get_value(row, col), nodata)
                                     && edge_connected_nodata.get_value(row, col) == 0
                                 {
                                     sum_weights = 0f64;
@@ -451,7 +451,7 @@ impl WhiteboxTool for FillMissingData {
                                         data[col as usize] = nodata;
                                     }
                                 } else {
-                                    data[col as usize] = input[(row, col)];
+                                    data[col as usize] = input.get_value(row, col);
                                 }
                             }
                             tx1.send((row, data)).unwrap();
@@ -6,7 +6,7 @@ Last Modified: 18/10/2019
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -230,16 +230,24 @@ impl WhiteboxTool for FindRidges {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             zn1 = input.get_value(row + dy[0], col + dx[0]);
                             zn2 = input.get_value(row + dy[1], col + dx[1]);
-                            if zn1 != nodata && zn2 != nodata && zn1 < z && zn2 < z {
+                            if !is_nodata_value(zn1, nodata)
+                                && !is_nodata_value(zn2, nodata)
+                                && zn1 < z
+                                && zn2 < z
+                            {
                                 data[col as usize] = 1f64;
                             } else {
                                 zn1 = input.get_value(row + dy[2], col + dx[2]);
                                 zn2 = input.get_value(row + dy[3], col + dx[3]);
-                                if zn1 != nodata && zn2 != nodata && zn1 < z && zn2 < z {
+                                if !is_nodata_value(zn1, nodata)
+                                    && !is_nodata_value(zn2, nodata)
+                                    && zn1 < z
+                                    && zn2 < z
+                                {
                                     data[col as usize] = 1f64;
                                 }
                             }
@@ -301,11 +309,11 @@ impl WhiteboxTool for FindRidges {
                 for a in 0..8 {
                     for row in 0..rows {
                         for col in 0..columns {
-                            z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            z = output.get_value(row, col);
+                            if z > 0.0 && !is_nodata_value(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
-                                    neighbours[i] = output[(row + dy[i], col + dx[i])];
+                                    neighbours[i] = output.get_value(row + dy[i], col + dx[i]);
                                 }
 
                                 // scan through element
@@ -316,7 +324,7 @@ impl WhiteboxTool for FindRidges {
                                     }
                                 }
                                 if pattern_match {
-                                    output[(row, col)] = 0.0;
+                                    output.set_value(row, col, 0.0);
                                     did_something = true;
                                 }
                             }
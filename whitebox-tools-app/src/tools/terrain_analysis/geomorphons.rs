@@ -441,7 +441,7 @@ impl WhiteboxTool for Geomorphons {
                         for col in 0..columns {
                             c = col as f64;
                             z = input.get_value(row, col);
-                            if z != nodata  {
+                            if !is_nodata_value(z, nodata)  {
                                 sum_y += z;
                                 sum_xr_y += r * z;
                                 sum_xc_y += c * z;
@@ -516,7 +516,7 @@ impl WhiteboxTool for Geomorphons {
                         for col in 0..columns {
                             c = col as f64;
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 data[col as usize] = z -
                                     (b0 + (b1r * r) + (b1c * c)); // estimate
                             }
@@ -596,7 +596,7 @@ impl WhiteboxTool for Geomorphons {
                             y1 = input.get_y_from_row(row);
                             x1 = input.get_x_from_column(col);
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
 
                                 // scan profile in 8 compass directions
                                 'directions: for dir in 0..8 {
@@ -618,7 +618,7 @@ impl WhiteboxTool for Geomorphons {
                                     distance = (ydif*ydif + xdif*xdif).sqrt();
 
                                     while distance < search_length {
-                                        if z2 != nodata { // line-of-sight exists
+                                        if !is_nodata_value(z2, nodata) { // line-of-sight exists
                                             angle = (z2 - z).atan2(distance);
                                             if angle > zenith_angle { // get max angle
                                                 zenith_angle = angle;
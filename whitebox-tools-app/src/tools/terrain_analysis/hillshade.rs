@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 22/06/2017
-Last Modified: 03/09/2020
+Last Modified: 17/10/2026
 License: MIT
 */
 
@@ -13,6 +13,7 @@ use std::env;
 use std::f64;
 use std::f64::consts::PI;
 use std::io::{Error, ErrorKind};
+use std::ops::Range;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
@@ -275,266 +276,71 @@ impl WhiteboxTool for Hillshade {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
-        if verbose {
-            println!("Reading data...")
-        };
-
-        let input = Arc::new(Raster::new(&input_file, "r")?);
-
-        let start = Instant::now();
-
-        let nodata = input.configs.nodata;
-        let rows = input.configs.rows as isize;
-        let columns = input.configs.columns as isize;
-        let resx = input.configs.resolution_x;
-        let resy = input.configs.resolution_y;
-        let res = (resx + resy) / 2.;
-
         azimuth = (azimuth - 90f64).to_radians();
         altitude = altitude.to_radians();
-        let sin_theta = altitude.sin();
-        let cos_theta = altitude.cos();
-        // let eight_grid_res = input.configs.resolution_x * 8.0;
 
-        let mut configs = input.configs.clone();
-        configs.data_type = DataType::I16;
-        configs.nodata = -32768f64;
-        let mut output = Raster::initialize_using_config(&output_file, &configs);
-        
         let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
         let max_procs = configs.max_procs;
         if max_procs > 0 && max_procs < num_procs {
             num_procs = max_procs;
         }
-        
-        let (tx, rx) = mpsc::channel();
-        if !input.is_in_geographic_coordinates() {
-            for tid in 0..num_procs {
-                let input = input.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z12: f64;
-                    let mut p: f64;
-                    let mut q: f64;
-                    let offsets = [
-                        [-2, -2], [-1, -2], [0, -2], [1, -2], [2, -2], 
-                        [-2, -1], [-1, -1], [0, -1], [1, -1], [2, -1], 
-                        [-2, 0], [-1, 0], [0, 0], [1, 0], [2, 0], 
-                        [-2, 1], [-1, 1], [0, 1], [1, 1], [2, 1], 
-                        [-2, 2], [-1, 2], [0, 2], [1, 2], [2, 2]
-                    ];
-                    let mut z = [0f64; 25];
-                    let mut val: f64;
-                    let (mut term1, mut term2, mut term3): (f64, f64, f64);
-                    let mut tan_slope: f64;
-                    let mut aspect: f64;
-                    let half_pi = PI / 2f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z12 = input.get_value(row, col);
-                            if z12 != nodata {
-                                for n in 0..25 {
-                                    z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
-                                    if z[n] != nodata {
-                                        z[n] *= z_factor;
-                                    } else {
-                                        z[n] = z12 * z_factor;
-                                    }
-                                }
 
-                                /* 
-                                The following equations have been taken from Florinsky (2016) Principles and Methods
-                                of Digital Terrain Modelling, Chapter 4, pg. 117. 
-                                */
-                                p = 1. / (420. * res) * (44. * (z[3] + z[23] - z[1] - z[21]) + 31. * (z[0] + z[20] - z[4] - z[24]
-                                + 2. * (z[8] + z[18] - z[6] - z[16])) + 17. * (z[14] - z[10] + 4. * (z[13] - z[11]))
-                                + 5. * (z[9] + z[19] - z[5] - z[15]));
-
-                                q = 1. / (420. * res) * (44. * (z[5] + z[9] - z[15] - z[19]) + 31. * (z[20] + z[24] - z[0] - z[4]
-                                    + 2. * (z[6] + z[8] - z[16] - z[18])) + 17. * (z[2] - z[22] + 4. * (z[7] - z[17]))
-                                    + 5. * (z[1] + z[3] - z[21] - z[23]));
-
-                                tan_slope = (p * p + q * q).sqrt();
-                                if tan_slope < 0.00017 {
-                                    tan_slope = 0.00017;
-                                }
-                                aspect = if p != 0f64 {
-                                    PI - ((q / p).atan()) + half_pi * (p / (p).abs())
-                                } else {
-                                    PI
-                                };
-                                // let sign_p = if p != 0.0 { p.signum() } else { 0.0 };
-                                // let sign_q = if q != 0.0 { q.signum() } else { 0.0 };
-                                // aspect = -half_pi*(1.0 - sign_q)*(1.0-sign_p.abs())+PI*(1.0 + sign_p) - sign_p * (-q / (p*p + q*q).sqrt()).acos();
-                                term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
-                                term2 = sin_theta / tan_slope;
-                                term3 = cos_theta * (azimuth - aspect).sin();
-                                val = term1 * (term2 - term3);
-                                val = val * 32767.0;
-                                if val < 0.0 {
-                                    val = 0.0;
-                                }
-                                data[col as usize] = val.round();
-                            }
+        if use_streaming(&input_file, &output_file) {
+            // The DEM is too large to hold in memory and is instead processed in bands of rows.
+            let start = Instant::now();
+            let mut reader = GeoTiffReader::new(&input_file)?;
+            let rows = reader.configs.rows as isize;
+            let is_geographic = reader.configs.is_in_geographic_coordinates();
+            let mut configs = reader.configs.clone();
+            configs.data_type = DataType::I16;
+            configs.nodata = -32768f64;
+            let mut writer = GeoTiffWriter::new(&output_file, &configs)?;
+            let mut rows_completed = 0;
+            reader.stream_rows(&mut writer, 2, |band, first_row, last_row| {
+                let rx = calculate_hillshade(band, first_row..last_row, is_geographic, azimuth, altitude, z_factor, num_procs);
+                let mut data = vec![vec![]; (last_row - first_row) as usize];
+                for _ in first_row..last_row {
+                    let (r, values) = rx.recv().expect("Error receiving data from thread.");
+                    data[(r - first_row) as usize] = values;
+                    if verbose {
+                        let progress = (100.0_f64 * rows_completed as f64 / (rows - 1).max(1) as f64) as usize;
+                        if progress != old_progress {
+                            println!("Performing analysis: {}%", progress);
+                            old_progress = progress;
                         }
-
-                        tx.send((row, data)).expect("Error sending data to thread.");
                     }
-                });
-            }
-        } else { // geographic coordinates
-
-            let phi1 = input.get_y_from_row(0);
-            let lambda1 = input.get_x_from_column(0);
-
-            let phi2 = phi1;
-            let lambda2 = input.get_x_from_column(-1);
-
-            let linear_res = vincenty_distance((phi1, lambda1), (phi2, lambda2));
-            let lr2 =  haversine_distance((phi1, lambda1), (phi2, lambda2)); 
-            let diff = 100. * (linear_res - lr2).abs() / linear_res;
-            let use_haversine = diff < 0.5; // if the difference is less than 0.5%, use the faster haversine method to calculate distances.
-
-            for tid in 0..num_procs {
-                let input = input.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z4: f64;
-                    let mut p: f64;
-                    let mut q: f64;
-                    let mut a: f64;
-                    let mut b: f64;
-                    let mut c: f64;
-                    let mut d: f64;
-                    let mut e: f64;
-                    let mut phi1: f64;
-                    let mut lambda1: f64;
-                    let mut phi2: f64;
-                    let mut lambda2: f64;
-                    let offsets = [
-                        [-1, -1], [0, -1], [1, -1], 
-                        [-1, 0], [0, 0], [1, 0], 
-                        [-1, 1], [0, 1], [1, 1]
-                    ];
-                    let mut z = [0f64; 25];
-                    let mut val: f64;
-                    let (mut term1, mut term2, mut term3): (f64, f64, f64);
-                    let mut tan_slope: f64;
-                    let mut aspect: f64;
-                    let half_pi = PI / 2f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z4 = input.get_value(row, col);
-                            if z4 != nodata {
-                                for n in 0..9 {
-                                    z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
-                                    if z[n] != nodata {
-                                        z[n] *= z_factor;
-                                    } else {
-                                        z[n] = z4 * z_factor;
-                                    }
-                                }
-
-                                // Calculate a, b, c, d, and e.
-                                phi1 = input.get_y_from_row(row);
-                                lambda1 = input.get_x_from_column(col);
-
-                                phi2 = phi1;
-                                lambda2 = input.get_x_from_column(col-1);
-
-                                b = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                phi2 = input.get_y_from_row(row+1);
-                                lambda2 = lambda1;
-
-                                d = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                phi2 = input.get_y_from_row(row-1);
-                                lambda2 = lambda1;
-
-                                e = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
+                    rows_completed += 1;
+                }
+                Ok(data)
+            })?;
+            writer.finish()?;
 
-                                phi1 = input.get_y_from_row(row+1);
-                                lambda1 = input.get_x_from_column(col);
+            let elapsed_time = get_formatted_elapsed_time(start);
+            if verbose {
+                println!("Output file written");
+                println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time));
+            }
+            return Ok(());
+        }
 
-                                phi2 = phi1;
-                                lambda2 = input.get_x_from_column(col-1);
+        if verbose {
+            println!("Reading data...")
+        };
 
-                                a = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
 
-                                phi1 = input.get_y_from_row(row-1);
-                                lambda1 = input.get_x_from_column(col);
+        let start = Instant::now();
 
-                                phi2 = phi1;
-                                lambda2 = input.get_x_from_column(col-1);
+        let rows = input.configs.rows as isize;
+        let is_geographic = input.is_in_geographic_coordinates();
 
-                                c = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                /* 
-                                The following equations have been taken from Florinsky (2016) Principles and Methods
-                                of Digital Terrain Modelling, Chapter 4, pg. 117.
-                                */
-
-                                p = (a * a * c * d * (d + e) * (z[2] - z[0]) + b * (a * a * d * d + c * c * e * e) * (z[5] - z[3]) + a * c * c * e * (d + e) * (z[8] - z[6]))
-                                / (2. * (a * a * c * c * (d + e).powi(2) + b * b * (a * a * d * d + c * c * e * e)));
-
-                                q = 1. / (3. * d * e * (d + e) * (a.powi(4) + b.powi(4) + c.powi(4))) 
-                                * ((d * d * (a.powi(4) + b.powi(4) + b * b * c * c) + c * c * e * e * (a * a - b * b)) * (z[0] + z[2])
-                                - (d * d * (a.powi(4) + c.powi(4) + b * b * c * c) - e * e * (a.powi(4) + c.powi(4) + a * a * b * b)) * (z[3] + z[5])
-                                - (e * e * (b.powi(4) + c.powi(4) + a * a * b * b) - a * a * d * d * (b * b - c * c)) * (z[6] + z[8])
-                                + d * d * (b.powi(4) * (z[1] - 3. * z[4]) + c.powi(4) * (3. * z[1] - z[4]) + (a.powi(4) - 2. * b * b * c * c) * (z[1] - z[4]))
-                                + e * e * (a.powi(4) * (z[4] - 3. * z[7]) + b.powi(4) * (3. * z[4] - z[7]) + (c.powi(4) - 2. * a * a * b * b) * (z[4] - z[7]))
-                                - 2. * (a * a * d * d * (b * b - c * c) * z[7] + c * c * e * e * (a * a - b * b) * z[1]));
-                                
-                                tan_slope = (p * p + q * q).sqrt();
-                                if tan_slope < 0.00017 {
-                                    tan_slope = 0.00017;
-                                }
-                                aspect = if p != 0f64 {
-                                    PI - ((q / p).atan()) + half_pi * (p / (p).abs())
-                                } else {
-                                    PI
-                                };
-                                term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
-                                term2 = sin_theta / tan_slope;
-                                term3 = cos_theta * (azimuth - aspect).sin();
-                                val = term1 * (term2 - term3);
-                                val = val * 32767.0;
-                                if val < 0.0 {
-                                    val = 0.0;
-                                }
-                                data[col as usize] = val.round();
-                            }
-                        }
+        let mut configs = input.configs.clone();
+        configs.data_type = DataType::I16;
+        configs.nodata = -32768f64;
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
 
-                        tx.send((row, data)).expect("Error sending data to thread.");
-                    }
-                });
-            }
-        }
+        let rx = calculate_hillshade(input.clone(), 0..rows, is_geographic, azimuth, altitude, z_factor, num_procs);
 
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
@@ -582,3 +388,255 @@ impl WhiteboxTool for Hillshade {
         Ok(())
     }
 }
+
+/// Calculates the hillshade for a range of rows of `input`, with the illumination source
+/// `azimuth` and `altitude` in radians. Each row is sent on the returned channel, in no
+/// particular order, as it is completed.
+fn calculate_hillshade(
+    input: Arc<Raster>,
+    rows: Range<isize>,
+    is_geographic: bool,
+    azimuth: f64,
+    altitude: f64,
+    z_factor: f64,
+    num_procs: isize,
+) -> mpsc::Receiver<(isize, Vec<f64>)> {
+    let nodata = input.configs.nodata;
+    let columns = input.configs.columns as isize;
+    let resx = input.configs.resolution_x;
+    let resy = input.configs.resolution_y;
+    let res = (resx + resy) / 2.;
+    let sin_theta = altitude.sin();
+    let cos_theta = altitude.cos();
+    let (first_row, last_row) = (rows.start, rows.end);
+
+    let (tx, rx) = mpsc::channel();
+    if !is_geographic {
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut z12: f64;
+                let mut p: f64;
+                let mut q: f64;
+                let offsets = [
+                    [-2, -2], [-1, -2], [0, -2], [1, -2], [2, -2], 
+                    [-2, -1], [-1, -1], [0, -1], [1, -1], [2, -1], 
+                    [-2, 0], [-1, 0], [0, 0], [1, 0], [2, 0], 
+                    [-2, 1], [-1, 1], [0, 1], [1, 1], [2, 1], 
+                    [-2, 2], [-1, 2], [0, 2], [1, 2], [2, 2]
+                ];
+                let mut z = [0f64; 25];
+                let mut val: f64;
+                let (mut term1, mut term2, mut term3): (f64, f64, f64);
+                let mut tan_slope: f64;
+                let mut aspect: f64;
+                let half_pi = PI / 2f64;
+                for row in (first_row..last_row).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z12 = input.get_value(row, col);
+                        if !is_nodata_value(z12, nodata) {
+                            for n in 0..25 {
+                                z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
+                                if !is_nodata_value(z[n], nodata) {
+                                    z[n] *= z_factor;
+                                } else {
+                                    z[n] = z12 * z_factor;
+                                }
+                            }
+
+                            /* 
+                            The following equations have been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 4, pg. 117. 
+                            */
+                            p = 1. / (420. * res) * (44. * (z[3] + z[23] - z[1] - z[21]) + 31. * (z[0] + z[20] - z[4] - z[24]
+                            + 2. * (z[8] + z[18] - z[6] - z[16])) + 17. * (z[14] - z[10] + 4. * (z[13] - z[11]))
+                            + 5. * (z[9] + z[19] - z[5] - z[15]));
+
+                            q = 1. / (420. * res) * (44. * (z[5] + z[9] - z[15] - z[19]) + 31. * (z[20] + z[24] - z[0] - z[4]
+                                + 2. * (z[6] + z[8] - z[16] - z[18])) + 17. * (z[2] - z[22] + 4. * (z[7] - z[17]))
+                                + 5. * (z[1] + z[3] - z[21] - z[23]));
+
+                            tan_slope = (p * p + q * q).sqrt();
+                            if tan_slope < 0.00017 {
+                                tan_slope = 0.00017;
+                            }
+                            aspect = if p != 0f64 {
+                                PI - ((q / p).atan()) + half_pi * (p / (p).abs())
+                            } else {
+                                PI
+                            };
+                            // let sign_p = if p != 0.0 { p.signum() } else { 0.0 };
+                            // let sign_q = if q != 0.0 { q.signum() } else { 0.0 };
+                            // aspect = -half_pi*(1.0 - sign_q)*(1.0-sign_p.abs())+PI*(1.0 + sign_p) - sign_p * (-q / (p*p + q*q).sqrt()).acos();
+                            term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
+                            term2 = sin_theta / tan_slope;
+                            term3 = cos_theta * (azimuth - aspect).sin();
+                            val = term1 * (term2 - term3);
+                            val = val * 32767.0;
+                            if val < 0.0 {
+                                val = 0.0;
+                            }
+                            data[col as usize] = val.round();
+                        }
+                    }
+
+                    tx.send((row, data)).expect("Error sending data to thread.");
+                }
+            });
+        }
+    } else { // geographic coordinates
+
+        let phi1 = input.get_y_from_row(first_row);
+        let lambda1 = input.get_x_from_column(0);
+
+        let phi2 = phi1;
+        let lambda2 = input.get_x_from_column(-1);
+
+        let linear_res = vincenty_distance((phi1, lambda1), (phi2, lambda2));
+        let lr2 =  haversine_distance((phi1, lambda1), (phi2, lambda2)); 
+        let diff = 100. * (linear_res - lr2).abs() / linear_res;
+        let use_haversine = diff < 0.5; // if the difference is less than 0.5%, use the faster haversine method to calculate distances.
+
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut z4: f64;
+                let mut p: f64;
+                let mut q: f64;
+                let mut a: f64;
+                let mut b: f64;
+                let mut c: f64;
+                let mut d: f64;
+                let mut e: f64;
+                let mut phi1: f64;
+                let mut lambda1: f64;
+                let mut phi2: f64;
+                let mut lambda2: f64;
+                let offsets = [
+                    [-1, -1], [0, -1], [1, -1], 
+                    [-1, 0], [0, 0], [1, 0], 
+                    [-1, 1], [0, 1], [1, 1]
+                ];
+                let mut z = [0f64; 25];
+                let mut val: f64;
+                let (mut term1, mut term2, mut term3): (f64, f64, f64);
+                let mut tan_slope: f64;
+                let mut aspect: f64;
+                let half_pi = PI / 2f64;
+                for row in (first_row..last_row).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z4 = input.get_value(row, col);
+                        if !is_nodata_value(z4, nodata) {
+                            for n in 0..9 {
+                                z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
+                                if !is_nodata_value(z[n], nodata) {
+                                    z[n] *= z_factor;
+                                } else {
+                                    z[n] = z4 * z_factor;
+                                }
+                            }
+
+                            // Calculate a, b, c, d, and e.
+                            phi1 = input.get_y_from_row(row);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            b = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi2 = input.get_y_from_row(row+1);
+                            lambda2 = lambda1;
+
+                            d = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi2 = input.get_y_from_row(row-1);
+                            lambda2 = lambda1;
+
+                            e = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi1 = input.get_y_from_row(row+1);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            a = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi1 = input.get_y_from_row(row-1);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            c = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            /* 
+                            The following equations have been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 4, pg. 117.
+                            */
+
+                            p = (a * a * c * d * (d + e) * (z[2] - z[0]) + b * (a * a * d * d + c * c * e * e) * (z[5] - z[3]) + a * c * c * e * (d + e) * (z[8] - z[6]))
+                            / (2. * (a * a * c * c * (d + e).powi(2) + b * b * (a * a * d * d + c * c * e * e)));
+
+                            q = 1. / (3. * d * e * (d + e) * (a.powi(4) + b.powi(4) + c.powi(4))) 
+                            * ((d * d * (a.powi(4) + b.powi(4) + b * b * c * c) + c * c * e * e * (a * a - b * b)) * (z[0] + z[2])
+                            - (d * d * (a.powi(4) + c.powi(4) + b * b * c * c) - e * e * (a.powi(4) + c.powi(4) + a * a * b * b)) * (z[3] + z[5])
+                            - (e * e * (b.powi(4) + c.powi(4) + a * a * b * b) - a * a * d * d * (b * b - c * c)) * (z[6] + z[8])
+                            + d * d * (b.powi(4) * (z[1] - 3. * z[4]) + c.powi(4) * (3. * z[1] - z[4]) + (a.powi(4) - 2. * b * b * c * c) * (z[1] - z[4]))
+                            + e * e * (a.powi(4) * (z[4] - 3. * z[7]) + b.powi(4) * (3. * z[4] - z[7]) + (c.powi(4) - 2. * a * a * b * b) * (z[4] - z[7]))
+                            - 2. * (a * a * d * d * (b * b - c * c) * z[7] + c * c * e * e * (a * a - b * b) * z[1]));
+                            
+                            tan_slope = (p * p + q * q).sqrt();
+                            if tan_slope < 0.00017 {
+                                tan_slope = 0.00017;
+                            }
+                            aspect = if p != 0f64 {
+                                PI - ((q / p).atan()) + half_pi * (p / (p).abs())
+                            } else {
+                                PI
+                            };
+                            term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
+                            term2 = sin_theta / tan_slope;
+                            term3 = cos_theta * (azimuth - aspect).sin();
+                            val = term1 * (term2 - term3);
+                            val = val * 32767.0;
+                            if val < 0.0 {
+                                val = 0.0;
+                            }
+                            data[col as usize] = val.round();
+                        }
+                    }
+
+                    tx.send((row, data)).expect("Error sending data to thread.");
+                }
+            });
+        }
+    }
+
+    rx
+}
@@ -8,7 +8,7 @@ License: MIT
 NOTES: The tool should have the option to output a distance raster as well.
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -403,7 +403,7 @@ impl WhiteboxTool for HorizonAngle {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         current_elev = input.get_value(row, col);
-                        if current_elev != nodata_f32 {
+                        if !is_nodata_value(current_elev, nodata_f32) {
                             // Run down the offsets of the ray
                             current_max_slope = a_small_value;
                             current_max_elev = a_small_value;
@@ -418,11 +418,13 @@ impl WhiteboxTool for HorizonAngle {
                                 z1 = input.get_value(y1, x1);
                                 z2 = input.get_value(y2, x2);
 
-                                if z1 == nodata_f32 && z2 == nodata_f32 {
+                                if is_nodata_value(z1, nodata_f32)
+                                    && is_nodata_value(z2, nodata_f32)
+                                {
                                     break; // We're likely off the grid.
-                                } else if z1 == nodata_f32 {
+                                } else if is_nodata_value(z1, nodata_f32) {
                                     z1 = z2;
-                                } else if z2 == nodata_f32 {
+                                } else if is_nodata_value(z2, nodata_f32) {
                                     z2 = z2;
                                 }
 
@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use crate::tools::*;
@@ -271,7 +271,7 @@ impl WhiteboxTool for HypsometricAnalysis {
                 for row in 0..rows {
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata {
+                        if !is_nodata_value(val, nodata) {
                             // bin = ((val - min) / bin_width).floor() as usize;
                             bin = (100f64 * (val - min) / range).floor() as usize;
                             // if bin >= num_bins { bin = num_bins - 1; }
@@ -381,9 +381,9 @@ impl WhiteboxTool for HypsometricAnalysis {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata_value(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 if z < min_elevs[watershed_id] {
                                     min_elevs[watershed_id] = z;
@@ -434,9 +434,9 @@ impl WhiteboxTool for HypsometricAnalysis {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata_value(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 bin = ((z - min_elevs[watershed_id]) / bin_widths[watershed_id])
                                     .floor() as usize;
@@ -508,11 +508,11 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
                     let mut data = vec![out_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             z = z * z_factor;
                             for c in 0..8 {
                                 n[c] = input.get_value(row + dy[c], col + dx[c]);
-                                if n[c] != nodata {
+                                if !is_nodata_value(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z;
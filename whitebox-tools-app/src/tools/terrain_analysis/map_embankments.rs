@@ -347,7 +347,7 @@ impl WhiteboxTool for MapEmbankments {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             data[col as usize] = ((z - base_contour) / contour_interval).floor();
                         }
                     }
@@ -414,10 +414,10 @@ impl WhiteboxTool for MapEmbankments {
         for row in 0..rows {
             for col in 0..columns {
                 z = reclassed.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     for n in 0..4 {
                         zn = reclassed.get_value(row + dy[n], col + dx[n]);
-                        if z > zn && zn != nodata {
+                        if z > zn && !is_nodata_value(zn, nodata) {
                             z1 = zn as isize + 1;
                             z2 = z as isize;
                             for contour_val in z1..=z2 {
@@ -286,7 +286,7 @@ impl WhiteboxTool for MapOffTerrainObjects {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     if output.get_value(row, col) == -1f64 {
                         let mut stack = vec![];
                         stack.push(GridCell::new(row, col));
@@ -300,7 +300,9 @@ impl WhiteboxTool for MapOffTerrainObjects {
                                 row_n = cell.row + dy[n];
                                 col_n = cell.column + dx[n];
                                 zn = input.get_value(row_n, col_n);
-                                if zn != nodata && output.get_value(row_n, col_n) == -1f64 {
+                                if !is_nodata_value(zn, nodata)
+                                    && output.get_value(row_n, col_n) == -1f64
+                                {
                                     if (z - zn).abs() / cellsize[n] < max_slope {
                                         stack.push(GridCell::new(row_n, col_n));
                                         output.set_value(row_n, col_n, fid);
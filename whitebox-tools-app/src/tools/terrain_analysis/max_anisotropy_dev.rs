@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use num_cpus;
@@ -278,8 +278,8 @@ impl WhiteboxTool for MaxAnisotropyDev {
             sum_sqr = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -368,8 +368,8 @@ impl WhiteboxTool for MaxAnisotropyDev {
                             // restricts edge effects
 
                             for col in 0..columns {
-                                z = input_data[(row, col)];
-                                if z != nodata {
+                                z = input_data.get_value(row, col);
+                                if !is_nodata_value(z, nodata) {
                                     // left to right:
                                     // -midpoint  -middle_pane_radius  +middle_pane_radius  +midpoint
                                     //     x1             x2                  x3               x4
@@ -593,16 +593,16 @@ impl WhiteboxTool for MaxAnisotropyDev {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
                     z2 = data[col as usize];
-                    if z2 != nodata {
-                        z1 = output_mag[(row, col)];
-                        if z1 != nodata {
+                    if !is_nodata_value(z2, nodata) {
+                        z1 = output_mag.get_value(row, col);
+                        if !is_nodata_value(z1, nodata) {
                             if z2 * z2 > z1 * z1 {
-                                output_mag[(row, col)] = z2;
-                                output_scale[(row, col)] = midpoint as f64;
+                                output_mag.set_value(row, col, z2);
+                                output_scale.set_value(row, col, midpoint as f64);
                             }
                         } else {
-                            output_mag[(row, col)] = z2;
-                            output_scale[(row, col)] = midpoint as f64;
+                            output_mag.set_value(row, col, z2);
+                            output_scale.set_value(row, col, midpoint as f64);
                         }
                     }
                 }
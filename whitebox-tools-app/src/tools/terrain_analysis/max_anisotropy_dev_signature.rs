@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
@@ -326,8 +326,8 @@ impl WhiteboxTool for MaxAnisotropyDevSignature {
             sum_sqr = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -377,8 +377,8 @@ impl WhiteboxTool for MaxAnisotropyDevSignature {
             let mut num_panes_valid: f64;
             for sig_site in 0..signature_sites.len() {
                 let (row, col) = signature_sites[sig_site];
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     // top to bottom:
                     // -midpoint  -middle_pane_radius  +middle_pane_radius  +midpoint
                     //     y1             y2                  y3               y4
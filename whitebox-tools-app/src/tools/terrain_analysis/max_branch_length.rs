@@ -6,7 +6,7 @@ Last Modified: 18/10/2019
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use num_cpus;
@@ -276,14 +276,14 @@ impl WhiteboxTool for MaxBranchLength {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<i8> = vec![flow_nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
-                                z_n = input[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                z_n = input.get_value(row + dy[i], col + dx[i]);
+                                if !is_nodata_value(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -402,10 +402,10 @@ impl WhiteboxTool for MaxBranchLength {
                                 }
                             }
                         }
-                        if dist1 > output[(row, col)] {
+                        if dist1 > output.get_value(row, col) {
                             output.set_value(row, col, dist1);
                         }
-                        if dist2 > output[(row, col + 1)] {
+                        if dist2 > output.get_value(row, col + 1) {
                             output.set_value(row, col + 1, dist2);
                         }
                     }
@@ -460,15 +460,15 @@ impl WhiteboxTool for MaxBranchLength {
                                 }
                             }
                         }
-                        if dist1 > output[(row, col)] {
+                        if dist1 > output.get_value(row, col) {
                             output.set_value(row, col, dist1);
                         }
-                        if dist2 > output[(row + 1, col)] {
+                        if dist2 > output.get_value(row + 1, col) {
                             output.set_value(row + 1, col, dist2);
                         }
                     }
-                } else if input[(row, col)] == nodata {
-                    output[(row, col)] = nodata;
+                } else if is_nodata_value(input.get_value(row, col), nodata) {
+                    output.set_value(row, col, nodata);
                 }
             }
             if verbose {
@@ -483,11 +483,11 @@ impl WhiteboxTool for MaxBranchLength {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] != nodata {
-                        if output[(row, col)] > 0f64 {
-                            output[(row, col)] = output[(row, col)].ln();
+                    if !is_nodata_value(input.get_value(row, col), nodata) {
+                        if output.get_value(row, col) > 0f64 {
+                            output.set_value(row, col, output.get_value(row, col).ln());
                         } else {
-                            output[(row, col)] = nodata;
+                            output.set_value(row, col, nodata);
                         }
                     }
                 }
@@ -276,8 +276,8 @@ impl WhiteboxTool for MaxDifferenceFromMean {
             sum = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -345,8 +345,8 @@ impl WhiteboxTool for MaxDifferenceFromMean {
                         }
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input_data[(row, col)];
-                            if z != nodata {
+                            z = input_data.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -377,16 +377,16 @@ impl WhiteboxTool for MaxDifferenceFromMean {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
                     z2 = data[col as usize];
-                    if z2 != nodata {
-                        z1 = output_mag[(row, col)];
-                        if z1 != nodata {
+                    if !is_nodata_value(z2, nodata) {
+                        z1 = output_mag.get_value(row, col);
+                        if !is_nodata_value(z1, nodata) {
                             if z2 * z2 > z1 * z1 {
-                                output_mag[(row, col)] = z2;
-                                output_scale[(row, col)] = midpoint as f64;
+                                output_mag.set_value(row, col, z2);
+                                output_scale.set_value(row, col, midpoint as f64);
                             }
                         } else {
-                            output_mag[(row, col)] = z2;
-                            output_scale[(row, col)] = midpoint as f64;
+                            output_mag.set_value(row, col, z2);
+                            output_scale.set_value(row, col, midpoint as f64);
                         }
                     }
                 }
@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
@@ -327,8 +327,8 @@ impl WhiteboxTool for MaxElevDevSignature {
             sum_sqr = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -365,7 +365,7 @@ impl WhiteboxTool for MaxElevDevSignature {
             let (mut mean, mut sum, mut sum_sqr): (f64, f64, f64);
             let (mut v, mut s): (f64, f64);
             z = input.get_value(row, col);
-            if z != nodata {
+            if !is_nodata_value(z, nodata) {
                 for midpoint in (min_scale..max_scale).filter(|s| (s - min_scale) % step == 0) {
                     y1 = row - midpoint - 1;
                     if y1 < 0 {
@@ -6,7 +6,7 @@ Last Modified: 27/01/2022
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -231,14 +231,14 @@ impl WhiteboxTool for MaxUpslopeElevChange {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             max_slope = f64::NEG_INFINITY;
                             max_z_change = 0f64;
                             for n in 0..8 {
                                 dist = grid_lengths[n];
-                                zn = input[(row + dy[n], col + dx[n])];
-                                if zn != nodata && zn > z {
+                                zn = input.get_value(row + dy[n], col + dx[n]);
+                                if !is_nodata_value(zn, nodata) && zn > z {
                                     slope = (zn - z) / dist;
 
                                     if slope > max_slope {
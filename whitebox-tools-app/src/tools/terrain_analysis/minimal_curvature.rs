@@ -316,10 +316,10 @@ impl WhiteboxTool for MinimalCurvature {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z12 = input.get_value(row, col);
-                            if z12 != nodata {
+                            if !is_nodata_value(z12, nodata) {
                                 for n in 0..25 {
                                     z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
-                                    if z[n] != nodata {
+                                    if !is_nodata_value(z[n], nodata) {
                                         z[n] *= z_factor;
                                     } else {
                                         z[n] = z12 * z_factor;
@@ -428,10 +428,10 @@ impl WhiteboxTool for MinimalCurvature {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z4 = input.get_value(row, col);
-                            if z4 != nodata {
+                            if !is_nodata_value(z4, nodata) {
                                 for n in 0..9 {
                                     z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
-                                    if z[n] != nodata {
+                                    if !is_nodata_value(z[n], nodata) {
                                         z[n] *= z_factor;
                                     } else {
                                         z[n] = z4 * z_factor;
@@ -399,7 +399,7 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
@@ -485,12 +485,12 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             bin_val = binned_data.get_value(row, col);
-                            if bin_val != bin_nodata {
-                                if old_bin_val != bin_nodata {
+                            if !is_nodata_value(bin_val, bin_nodata) {
+                                if !is_nodata_value(old_bin_val, bin_nodata) {
                                     // remove the trailing column from the histo
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col - midpoint - 1);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata_value(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] -= 1;
                                             n -= 1.0;
                                             if bin_val_n < old_bin_val {
@@ -502,7 +502,7 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
                                     // add the leading column to the histo
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col + midpoint);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata_value(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] += 1;
                                             n += 1.0;
                                             if bin_val_n < old_bin_val {
@@ -535,7 +535,7 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
                                     for col2 in start_col..end_col + 1 {
                                         for row2 in start_row..end_row + 1 {
                                             bin_val_n = binned_data.get_value(row2, col2);
-                                            if bin_val_n != bin_nodata {
+                                            if !is_nodata_value(bin_val_n, bin_nodata) {
                                                 histo[bin_val_n as usize] += 1;
                                                 n += 1f64;
                                                 if bin_val_n < bin_val {
@@ -547,7 +547,7 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
                                 }
                             }
 
-                            if n > 0f64 && bin_val != bin_nodata {
+                            if n > 0f64 && !is_nodata_value(bin_val, bin_nodata) {
                                 data[col as usize] = n_less_than / n * 100.0;
                             } else {
                                 data[col as usize] = nodata;
@@ -565,9 +565,9 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
-                    if data[col as usize] != nodata {
+                    if !is_nodata_value(data[col as usize], nodata) {
                         z1 = output_mag.get_value(row, col);
-                        if z1 == nodata {
+                        if is_nodata_value(z1, nodata) {
                             output_mag.set_value(row, col, data[col as usize]);
                             output_scale.set_value(row, col, s as f64);
                         } else {
@@ -313,11 +313,11 @@ impl WhiteboxTool for MultiscaleRoughness {
                     let mut values = [0f64; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             z *= z_factor;
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     values[i] = zn * z_factor;
                                 } else {
                                     values[i] = z;
@@ -377,8 +377,8 @@ impl WhiteboxTool for MultiscaleRoughness {
             sum = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -470,8 +470,8 @@ impl WhiteboxTool for MultiscaleRoughness {
                         }
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input_data[(row, col)];
-                            if z != nodata {
+                            z = input_data.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -516,11 +516,11 @@ impl WhiteboxTool for MultiscaleRoughness {
             for row in 0..rows {
                 sum = 0f64;
                 for col in 0..columns {
-                    if input.get_value(row, col) != nodata {
+                    if !is_nodata_value(input.get_value(row, col), nodata) {
                         z = smoothed.get_value(row, col) * z_factor;
                         for i in 0..8 {
                             zn = smoothed.get_value(row + dy[i], col + dx[i]);
-                            if zn != nodata {
+                            if !is_nodata_value(zn, nodata) {
                                 values[i] = zn * z_factor;
                             } else {
                                 values[i] = z;
@@ -581,8 +581,8 @@ impl WhiteboxTool for MultiscaleRoughness {
                         }
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input_data[(row, col)];
-                            if z != nodata {
+                            z = input_data.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
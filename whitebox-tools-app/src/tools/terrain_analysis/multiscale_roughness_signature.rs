@@ -6,7 +6,7 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
@@ -361,11 +361,11 @@ impl WhiteboxTool for MultiscaleRoughnessSignature {
                     let mut values = [0f64; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             z *= z_factor;
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata_value(zn, nodata) {
                                     values[i] = zn * z_factor;
                                 } else {
                                     values[i] = z;
@@ -425,8 +425,8 @@ impl WhiteboxTool for MultiscaleRoughnessSignature {
             sum = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
+                val = input.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -495,8 +495,8 @@ impl WhiteboxTool for MultiscaleRoughnessSignature {
                         }
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            z = input_data[(row, col)];
-                            if z != nodata {
+                            z = input_data.get_value(row, col);
+                            if !is_nodata_value(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -541,11 +541,11 @@ impl WhiteboxTool for MultiscaleRoughnessSignature {
             for row in 0..rows {
                 sum = 0f64;
                 for col in 0..columns {
-                    if input.get_value(row, col) != nodata {
+                    if !is_nodata_value(input.get_value(row, col), nodata) {
                         z = smoothed.get_value(row, col) * z_factor;
                         for i in 0..8 {
                             zn = smoothed.get_value(row + dy[i], col + dx[i]);
-                            if zn != nodata {
+                            if !is_nodata_value(zn, nodata) {
                                 values[i] = zn * z_factor;
                             } else {
                                 values[i] = z;
@@ -588,8 +588,8 @@ impl WhiteboxTool for MultiscaleRoughnessSignature {
             let mut z: f64;
             for s in 0..signature_sites.len() {
                 let (row, col) = signature_sites[s];
-                z = input[(row, col)];
-                if z != nodata {
+                z = input.get_value(row, col);
+                if !is_nodata_value(z, nodata) {
                     y1 = row - midpoint - 1;
                     if y1 < 0 {
                         y1 = 0;
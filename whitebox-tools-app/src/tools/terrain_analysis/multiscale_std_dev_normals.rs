@@ -386,7 +386,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
             if row > 0 {
                 sum = 0u32;
                 for col in 0..columns {
-                    sum += if input.get_value(row, col) != nodata {
+                    sum += if !is_nodata_value(input.get_value(row, col), nodata) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         num_valid_cells += 1;
                         1
@@ -397,14 +397,14 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                     i_n.set_value(row, col, sum + i_n.get_value(row - 1, col));
                 }
             } else {
-                if input.get_value(0, 0) != nodata {
+                if !is_nodata_value(input.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 1);
                     num_valid_cells += 1;
                 } else {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
-                    val = if input.get_value(row, col) != nodata {
+                    val = if !is_nodata_value(input.get_value(row, col), nodata) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         num_valid_cells += 1;
                         1
@@ -528,14 +528,14 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input.get_value(row, col);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     sum = 0.0;
                                     z_final = 0.0;
                                     for a in 0..num_pixels_in_filter {
                                         x = col + d_x[a];
                                         y = row + d_y[a];
                                         zn = input.get_value(y, x);
-                                        if zn != nodata {
+                                        if !is_nodata_value(zn, nodata) {
                                             sum += weights[a];
                                             z_final += weights[a] * zn;
                                         }
@@ -592,7 +592,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             // sum_n = 0;
                             for col in 0..columns {
                                 val = input.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata_value(val, nodata) {
                                     val = 0f32;
                                     // } else {
                                     // sum_n += 1;
@@ -613,7 +613,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             sum = 0f64;
                             for col in 0..columns {
                                 val = smoothed_dem.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata_value(val, nodata) {
                                     val = 0f32;
                                 }
                                 sum += val as f64;
@@ -639,7 +639,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                         }
 
                         for col in 0..columns {
-                            if input.get_value(row, col) != nodata {
+                            if !is_nodata_value(input.get_value(row, col), nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -699,10 +699,10 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                         let mut zdata = vec![0f64; columns as usize];
                         for col in 0..columns {
                             z = smoothed_dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 for c in 0..8 {
                                     n[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                    if n[c] != nodata {
+                                    if !is_nodata_value(n[c], nodata) {
                                         n[c] = n[c] * z_factor;
                                     } else {
                                         n[c] = z * z_factor;
@@ -805,7 +805,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -864,7 +864,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                 match rx2.recv() {
                     Ok((row, data)) => {
                         for col in 0..columns {
-                            if data[col as usize] != nodata {
+                            if !is_nodata_value(data[col as usize], nodata) {
                                 total_mssd += data[col as usize] as f64;
                                 if data[col as usize] > output_mag.get_value(row, col) {
                                     output_mag.set_value(row, col, data[col as usize]);
@@ -6,7 +6,7 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
@@ -374,7 +374,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
             if row > 0 {
                 sum = 0u32;
                 for col in 0..columns {
-                    sum += if input.get_value(row, col) != nodata {
+                    sum += if !is_nodata_value(input.get_value(row, col), nodata) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         1
                     } else {
@@ -384,13 +384,13 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                     i_n.set_value(row, col, sum + i_n.get_value(row - 1, col));
                 }
             } else {
-                if input.get_value(0, 0) != nodata {
+                if !is_nodata_value(input.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 1);
                 } else {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
-                    val = if input.get_value(row, col) != nodata {
+                    val = if !is_nodata_value(input.get_value(row, col), nodata) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         1
                     } else {
@@ -495,14 +495,14 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input.get_value(row, col);
-                                if z != nodata {
+                                if !is_nodata_value(z, nodata) {
                                     sum = 0.0;
                                     z_final = 0.0;
                                     for a in 0..num_pixels_in_filter {
                                         x = col + d_x[a];
                                         y = row + d_y[a];
                                         zn = input.get_value(y, x);
-                                        if zn != nodata {
+                                        if !is_nodata_value(zn, nodata) {
                                             sum += weights[a];
                                             z_final += weights[a] * zn;
                                         }
@@ -559,7 +559,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                             // sum_n = 0;
                             for col in 0..columns {
                                 val = input.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata_value(val, nodata) {
                                     val = 0f32;
                                     // } else {
                                     // sum_n += 1;
@@ -580,7 +580,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                             sum = 0f64;
                             for col in 0..columns {
                                 val = smoothed_dem.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata_value(val, nodata) {
                                     val = 0f32;
                                 }
                                 sum += val as f64;
@@ -606,7 +606,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                         }
 
                         for col in 0..columns {
-                            if input.get_value(row, col) != nodata {
+                            if !is_nodata_value(input.get_value(row, col), nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -666,10 +666,10 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                         let mut zdata = vec![0f64; columns as usize];
                         for col in 0..columns {
                             z = smoothed_dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 for c in 0..8 {
                                     n[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                    if n[c] != nodata {
+                                    if !is_nodata_value(n[c], nodata) {
                                         n[c] = n[c] * z_factor;
                                     } else {
                                         n[c] = z * z_factor;
@@ -744,7 +744,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
             for site in 0..signature_sites.len() {
                 let (row, col) = signature_sites[site];
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     y1 = row - midpoint - 1;
                     if y1 < 0 {
                         y1 = 0;
@@ -268,16 +268,16 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input1_file.contains(&sep) && input1_file.contains("/") {
+        if !input1_file.contains(&sep) && !input1_file.contains("/") {
             input1_file = format!("{}{}", working_directory, input1_file);
         }
-        if !input2_file.contains(&sep) && input2_file.contains("/") {
+        if !input2_file.contains(&sep) && !input2_file.contains("/") {
             input2_file = format!("{}{}", working_directory, input2_file);
         }
-        if !input3_file.contains(&sep) && input3_file.contains("/") {
+        if !input3_file.contains(&sep) && !input3_file.contains("/") {
             input3_file = format!("{}{}", working_directory, input3_file);
         }
-        if hs_specified && !hs_file.contains(&sep) && hs_file.contains("/") {
+        if hs_specified && !hs_file.contains(&sep) && !hs_file.contains("/") {
             hs_file = format!("{}{}", working_directory, hs_file);
         }
         if !output_file.contains(&sep) && !output_file.contains("/") {
@@ -376,10 +376,13 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata_r; columns as usize];
                     for col in 0..columns {
-                        red_val = input_r[(row, col)];
-                        green_val = input_g[(row, col)];
-                        blue_val = input_b[(row, col)];
-                        if red_val != nodata_r && green_val != nodata_g && blue_val != nodata_b {
+                        red_val = input_r.get_value(row, col);
+                        green_val = input_g.get_value(row, col);
+                        blue_val = input_b.get_value(row, col);
+                        if !is_nodata_value(red_val, nodata_r)
+                            && !is_nodata_value(green_val, nodata_g)
+                            && !is_nodata_value(blue_val, nodata_b)
+                        {
                             /* Replaced the linear interpolation with this logistic function.*/
                             red_val = (512f64 / (1f64 + (-cutoff * (red_val).abs()).exp())).floor()
                                 - 256f64;
@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -221,11 +221,11 @@ impl WhiteboxTool for NumUpslopeNeighbours {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             count = 0.0;
                             for i in 0..8 {
-                                if input[(row + d_y[i], col + d_x[i])] > z {
+                                if input.get_value(row + d_y[i], col + d_x[i]) > z {
                                     count += 1.0;
                                 }
                             }
@@ -330,7 +330,7 @@ impl WhiteboxTool for PercentElevRange {
                             max_val = f64::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -350,7 +350,7 @@ impl WhiteboxTool for PercentElevRange {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -364,7 +364,7 @@ impl WhiteboxTool for PercentElevRange {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             min_val = f64::INFINITY;
                             max_val = f64::NEG_INFINITY;
                             for i in 0..filter_size_x {
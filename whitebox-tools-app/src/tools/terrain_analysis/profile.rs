@@ -296,7 +296,7 @@ impl WhiteboxTool for Profile {
                             row = (st_row as f64 + j as f64 * dy) as isize;
                             z = surface.get_value(row, col);
                             dist += dist_step;
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 profile_xdata.push(dist);
                                 profile_ydata.push(z);
                             }
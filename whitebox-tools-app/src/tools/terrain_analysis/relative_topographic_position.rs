@@ -342,7 +342,7 @@ impl WhiteboxTool for RelativeTopographicPosition {
                             total = 0f64;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -368,7 +368,7 @@ impl WhiteboxTool for RelativeTopographicPosition {
                                 total = 0f64;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata_value(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -386,7 +386,7 @@ impl WhiteboxTool for RelativeTopographicPosition {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             min_val = f64::INFINITY;
                             max_val = f64::NEG_INFINITY;
                             n = 0f64;
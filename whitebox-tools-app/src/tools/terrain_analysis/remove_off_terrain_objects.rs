@@ -8,7 +8,7 @@ License: MIT
 Note: This algorithm could be parallelized
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::{Array2D, DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
 use num_cpus;
@@ -312,7 +312,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             min_val = f32::INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint);
-                                if z_n < min_val && z_n != nodata {
+                                if z_n < min_val && !is_nodata_value(z_n, nodata) {
                                     min_val = z_n;
                                 }
                             }
@@ -325,7 +325,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                                 min_val = f32::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n < min_val && z_n != nodata {
+                                    if z_n < min_val && !is_nodata_value(z_n, nodata) {
                                         min_val = z_n;
                                     }
                                 }
@@ -333,7 +333,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             min_val = f32::INFINITY;
                             for v in filter_vals.iter() {
                                 if *v < min_val {
@@ -387,7 +387,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             max_val = f32::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = erosion.get_value(row2, col + midpoint);
-                                if z_n > max_val && z_n != nodata {
+                                if z_n > max_val && !is_nodata_value(z_n, nodata) {
                                     max_val = z_n;
                                 }
                             }
@@ -400,7 +400,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                                 max_val = f32::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = erosion.get_value(row2, col2);
-                                    if z_n > max_val && z_n != nodata {
+                                    if z_n > max_val && !is_nodata_value(z_n, nodata) {
                                         max_val = z_n;
                                     }
                                 }
@@ -408,7 +408,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             max_val = f32::NEG_INFINITY;
                             for v in filter_vals.iter() {
                                 if *v > max_val {
@@ -444,11 +444,10 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
             }
         }
 
-        // drop(erosion); // 3X
-        // drop(input); // 2X
-
-        drop(Arc::try_unwrap(erosion).expect("Error unwrapping Arc."));
-        drop(Arc::try_unwrap(input).expect("Error unwrapping Arc."));
+        // The worker threads may still hold their clones of these Arcs after sending their
+        // last row, so they can't be unwrapped here; the data are freed when the threads exit.
+        drop(erosion); // 3X
+        drop(input); // 2X
 
         // Back-fill the shallow hills using region growing
         if verbose {
@@ -463,7 +462,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         for row in 0..rows {
             for col in 0..columns {
                 out.set_value(row, col, initial_value);
-                if tophat.get_value(row, col) != nodata {
+                if !is_nodata_value(tophat.get_value(row, col), nodata) {
                     if tophat.get_value(row, col) <= height_diff_threshold[1] {
                         stack.push(GridCell {
                             row: row,
@@ -493,7 +492,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                 row_n = row + d_y[i];
                 col_n = col + d_x[i];
                 z_n = tophat.get_value(row_n, col_n);
-                if z_n != nodata && out[(row_n, col_n)] == initial_value {
+                if !is_nodata_value(z_n, nodata) && out[(row_n, col_n)] == initial_value {
                     if z_n - z < height_diff_threshold[i] {
                         out[(row_n, col_n)] = z_n;
                         stack.push(GridCell {
@@ -517,12 +516,12 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         ); // 4X
         for row in 0..rows {
             for col in 0..columns {
-                if tophat.get_value(row, col) != nodata && out.get_value(row, col) != initial_value
+                if !is_nodata_value(tophat.get_value(row, col), nodata) && out.get_value(row, col) != initial_value
                 {
                     for i in 0..8 {
                         row_n = row + d_y[i];
                         col_n = col + d_x[i];
-                        if tophat.get_value(row_n, col_n) != nodata
+                        if !is_nodata_value(tophat.get_value(row_n, col_n), nodata)
                             && out.get_value(row_n, col_n) == initial_value
                         {
                             frs.insert(
@@ -594,7 +593,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         let mut output = Raster::initialize_using_config(&output_file, &configs); // 8X
         for row in 0..rows {
             for col in 0..columns {
-                if out.get_value(row, col) != initial_value && tophat.get_value(row, col) != nodata
+                if out.get_value(row, col) != initial_value && !is_nodata_value(tophat.get_value(row, col), nodata)
                 {
                     output.set_value(row, col, out[(row, col)] as f64);
                 } else {
@@ -6,7 +6,7 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -264,13 +264,13 @@ impl WhiteboxTool for RuggednessIndex {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             // z = z * z_factor;
                             n = 0.0;
                             ss = 0.0;
                             for c in 0..8 {
                                 z_n = input.get_value(row + d_y[c], col + d_x[c]);
-                                if z_n != nodata {
+                                if !is_nodata_value(z_n, nodata) {
                                     // z_n = z_n * z_factor;
                                     ss += (z_n - z) * (z_n - z);
                                     n += 1.0;
@@ -6,7 +6,7 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
@@ -313,11 +313,11 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                z = input[(row, col)];
-                                if z != nodata {
+                                z = input.get_value(row, col);
+                                if !is_nodata_value(z, nodata) {
                                     for c in 0..8 {
-                                        n[c] = input[(row + d_y[c], col + d_x[c])];
-                                        if n[c] != nodata {
+                                        n[c] = input.get_value(row + d_y[c], col + d_x[c]);
+                                        if !is_nodata_value(n[c], nodata) {
                                             n[c] = n[c] * z_factor;
                                         } else {
                                             n[c] = z * z_factor;
@@ -367,7 +367,7 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 for row in 0..rows {
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata {
+                        if !is_nodata_value(val, nodata) {
                             bin = ((val - min) / bin_width).floor() as usize;
                             freq_data[bin] += 1f64;
                             slope_data[bin] += slope.get_value(row, col);
@@ -465,11 +465,11 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                z = input[(row, col)];
-                                if z != nodata {
+                                z = input.get_value(row, col);
+                                if !is_nodata_value(z, nodata) {
                                     for c in 0..8 {
-                                        n[c] = input[(row + d_y[c], col + d_x[c])];
-                                        if n[c] != nodata {
+                                        n[c] = input.get_value(row + d_y[c], col + d_x[c]);
+                                        if !is_nodata_value(n[c], nodata) {
                                             n[c] = n[c] * z_factor;
                                         } else {
                                             n[c] = z * z_factor;
@@ -530,9 +530,9 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata_value(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 if z < min_elevs[watershed_id] {
                                     min_elevs[watershed_id] = z;
@@ -585,9 +585,9 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata_value(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 bin = ((z - min_elevs[watershed_id]) / bin_widths[watershed_id])
                                     .floor() as usize;
@@ -347,14 +347,14 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 sum = 0.0;
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input.get_value(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata_value(zn, nodata) {
                                         sum += weights[a];
                                         z_final += weights[a] * zn;
                                     }
@@ -418,7 +418,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                         sum_n = 0;
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata_value(val, nodata) {
                                 val = 0f64;
                             } else {
                                 sum_n += 1;
@@ -442,7 +442,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                         sum = 0f64;
                         for col in 0..columns {
                             val = smoothed_dem.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata_value(val, nodata) {
                                 val = 0f64;
                             }
                             sum += val;
@@ -468,7 +468,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -539,10 +539,10 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                     let mut zdata = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             for c in 0..8 {
                                 n[c] = smoothed_dem[(row + dy[c], col + dx[c])];
-                                if n[c] != nodata {
+                                if !is_nodata_value(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z * z_factor;
@@ -598,7 +598,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                     sumx += xc.get_value(row, col);
                     sumy += yc.get_value(row, col);
                     sumz += zc.get_value(row, col);
-                    if smoothed_dem.get_value(row, col) == nodata {
+                    if is_nodata_value(smoothed_dem.get_value(row, col), nodata) {
                         // ||
                         // (xc.get_value(row, col) == 0f64 && yc.get_value(row, col) == 0f64) {
                         // it's either nodata or a flag cell in the DEM.
@@ -611,7 +611,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if smoothed_dem.get_value(0, 0) == nodata {
+                if is_nodata_value(smoothed_dem.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
@@ -619,7 +619,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                     yc.increment(row, col, yc.get_value(row, col - 1));
                     zc.increment(row, col, zc.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if smoothed_dem.get_value(row, col) == nodata {
+                    if is_nodata_value(smoothed_dem.get_value(row, col), nodata) {
                         //||
                         // (xc.get_value(row, col) == 0f64 && yc.get_value(row, col) == 0f64) {
                         // it's either nodata or a flag cell in the DEM.
@@ -673,7 +673,7 @@ impl WhiteboxTool for SphericalStdDevOfNormals {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
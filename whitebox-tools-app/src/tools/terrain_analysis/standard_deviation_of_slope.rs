@@ -6,7 +6,7 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use num_cpus;
@@ -334,11 +334,11 @@ impl WhiteboxTool for StandardDeviationOfSlope {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             for i in 0..8 {
-                                n[i] = input[(row + dy[i], col + dx[i])];
-                                if n[i] != nodata {
+                                n[i] = input.get_value(row + dy[i], col + dx[i]);
+                                if !is_nodata_value(n[i], nodata) {
                                     n[i] = n[i] * z_factor;
                                 } else {
                                     n[i] = z * z_factor;
@@ -384,8 +384,8 @@ impl WhiteboxTool for StandardDeviationOfSlope {
             sum_sqr = 0f64;
             sum_n = 0;
             for col in 0..columns {
-                val = output[(row, col)];
-                if val == nodata {
+                val = output.get_value(row, col);
+                if is_nodata_value(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -458,8 +458,8 @@ impl WhiteboxTool for StandardDeviationOfSlope {
 
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
+                        z = input.get_value(row, col);
+                        if !is_nodata_value(z, nodata) {
                             x1 = col - midpoint_x - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -283,7 +283,7 @@ impl WhiteboxTool for SurfaceAreaRatio {
 
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata_value(input.get_value(row, col), nodata) {
                             // get the elevation values
                             for c in 0..9 {
                                 zvals[c] = input.get_value(row + dy[c], col + dx[c]);
@@ -291,8 +291,8 @@ impl WhiteboxTool for SurfaceAreaRatio {
 
                             // calculate the distances
                             for c in 0..16 {
-                                if zvals[dist_pairs[c][0]] != nodata
-                                    && zvals[dist_pairs[c][1]] != nodata
+                                if !is_nodata_value(zvals[dist_pairs[c][0]], nodata)
+                                    && !is_nodata_value(zvals[dist_pairs[c][1]], nodata)
                                 {
                                     zdiff =
                                         (zvals[dist_pairs[c][0]] - zvals[dist_pairs[c][1]]).abs();
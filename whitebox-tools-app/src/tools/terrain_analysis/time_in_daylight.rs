@@ -6,7 +6,7 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use time::{Date, macros::time, OffsetDateTime, Time, UtcOffset};
@@ -722,7 +722,7 @@ impl WhiteboxTool for TimeInDaylight {
                             let mut data: Vec<f32> = vec![nodata_f32; columns as usize];
                             for col in 0..columns {
                                 current_elev = input.get_value(row, col);
-                                if current_elev != nodata_f32 {
+                                if !is_nodata_value(current_elev, nodata_f32) {
                                     // Run down the offsets of the ray
                                     current_max_slope = a_small_value;
                                     // current_max_elev = a_small_value;
@@ -737,11 +737,13 @@ impl WhiteboxTool for TimeInDaylight {
                                         z1 = input.get_value(y1, x1);
                                         z2 = input.get_value(y2, x2);
 
-                                        if z1 == nodata_f32 && z2 == nodata_f32 {
+                                        if is_nodata_value(z1, nodata_f32)
+                                            && is_nodata_value(z2, nodata_f32)
+                                        {
                                             break; // We're likely off the grid.
-                                        } else if z1 == nodata_f32 {
+                                        } else if is_nodata_value(z1, nodata_f32) {
                                             z1 = z2;
-                                        } else if z2 == nodata_f32 {
+                                        } else if is_nodata_value(z2, nodata_f32) {
                                             z2 = z1;
                                         }
 
@@ -797,7 +799,7 @@ impl WhiteboxTool for TimeInDaylight {
                         .par_iter()
                         .map(|ha| {
                             let mut seconds_in_shadow = 0f64;
-                            if *ha != nodata_f32 {
+                            if !is_nodata_value(*ha, nodata_f32) {
                                 for day in 0..365 {
                                     if altitudes_and_durations[day].3 >= start_day
                                         && altitudes_and_durations[day].3 <= end_day
@@ -850,7 +852,7 @@ impl WhiteboxTool for TimeInDaylight {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata_value(z, nodata) {
                         // output.set_value(row, col, z / total_daylight_time);
                         output.set_value(row, col, 1f64 - (z / total_daylight));
                     }
@@ -6,7 +6,7 @@ Last Modified: 26/03/2023
 License: MIT
 */
 
-use whitebox_raster::{Raster, RasterConfigs};
+use whitebox_raster::{is_nodata_value, Raster, RasterConfigs};
 use whitebox_common::structures::{Array2D, Point2D};
 use crate::tools::*;
 use whitebox_vector::*;
@@ -631,7 +631,7 @@ impl WhiteboxTool for TopographicHachures {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata_value(z, nodata) {
                             data[col as usize] = ((z - base_contour) / contour_interval).floor();
                         }
                     }
@@ -698,10 +698,10 @@ impl WhiteboxTool for TopographicHachures {
         for row in 0..rows {
             for col in 0..columns {
                 z = reclassed.get_value(row, col);
-                if z != nodata {
+                if !is_nodata_value(z, nodata) {
                     for n in 0..4 {
                         zn = reclassed.get_value(row + dy[n], col + dx[n]);
-                        if z > zn && zn != nodata {
+                        if z > zn && !is_nodata_value(zn, nodata) {
                             z1 = zn as isize + 1;
                             z2 = z as isize;
                             for contour_val in z1..=z2 {
@@ -1688,7 +1688,7 @@ pub fn get_flowline(cov: &RasterCoverage, p: &Point2D,
 
     zprev = cov.get_value(p1.x, p1.y);
 
-    if zprev == zlim || zprev == cov.configs.nodata {
+    if zprev == zlim || is_nodata_value(zprev, cov.configs.nodata) {
         return points;
     }
 
@@ -1708,7 +1708,7 @@ pub fn get_flowline(cov: &RasterCoverage, p: &Point2D,
 
         zcur = cov.get_value(p2.x, p2.y);
 
-        if zcur == cov.configs.nodata {
+        if is_nodata_value(zcur, cov.configs.nodata) {
             break;
         } else {
             grad2 = cov.get_gradient(p2.x, p2.y);
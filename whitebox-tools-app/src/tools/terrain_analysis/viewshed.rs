@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use whitebox_vector::*;
@@ -354,7 +354,7 @@ impl WhiteboxTool for Viewshed {
                         let mut data: Vec<f32> = vec![-32768f32; columns as usize];
                         for col in 0..columns {
                             z = dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata_value(z, nodata) {
                                 x = dem.get_x_from_column(col);
                                 y = dem.get_y_from_row(row);
                                 dz = z - stn_z;
@@ -666,7 +666,7 @@ impl WhiteboxTool for Viewshed {
                     // } else {
                     //     output.set_value(row, col, nodata);
                     // }
-                    if dem.get_value(row, col) != nodata {
+                    if !is_nodata_value(dem.get_value(row, col), nodata) {
                         value = if max_view_angle.get_value(row, col)
                             > view_angle.get_value(row, col)
                         {
@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata_value, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use whitebox_common::utils::get_formatted_elapsed_time;
@@ -646,7 +646,7 @@ impl WhiteboxTool for VisibilityIndex {
             let data = rx.recv().expect("Error receiving data from thread.");
             for row in 0..rows {
                 for col in 0..columns {
-                    if dem.get_value(row, col) != nodata {
+                    if !is_nodata_value(dem.get_value(row, col), nodata) {
                         z = data.get_value(row, col) as f64;
                         output.increment(row, col, z);
                     }
@@ -656,7 +656,7 @@ impl WhiteboxTool for VisibilityIndex {
 
         for row in 0..rows {
             for col in 0..columns {
-                if dem.get_value(row, col) != nodata {
+                if !is_nodata_value(dem.get_value(row, col), nodata) {
                     z = output.get_value(row, col);
                     output.set_value(row, col, z / num_cells_tested)
                 }
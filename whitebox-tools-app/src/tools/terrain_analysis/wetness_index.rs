@@ -249,9 +249,11 @@ impl WhiteboxTool for WetnessIndex {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![sca_nodata; columns as usize];
                     for col in 0..columns {
-                        sca_val = sca[(row, col)];
-                        slope_val = slope[(row, col)];
-                        if sca_val != sca_nodata && slope_val != slope_nodata {
+                        sca_val = sca.get_value(row, col);
+                        slope_val = slope.get_value(row, col);
+                        if !is_nodata_value(sca_val, sca_nodata)
+                            && !is_nodata_value(slope_val, slope_nodata)
+                        {
                             if slope_val != 0f64 {
                                 data[col as usize] = (sca_val / slope_val.to_radians().tan()).ln();
                             }
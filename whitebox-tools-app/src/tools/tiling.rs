@@ -0,0 +1,646 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Runs a raster tool over a set of tiles, so that rasters that are too large to be held in
+//! memory can be processed by tools that read their entire input grid.
+//!
+//! The input is either a single GeoTIFF, which is split into square tiles of `tile_size`
+//! cells, or a directory of GeoTIFF tiles that share a common resolution and grid alignment.
+//! Each tile is extended by a halo of `halo` cells, read from the neighbouring tiles, before
+//! it is processed, so that tools that use a neighbourhood of cells, such as Slope or
+//! Geomorphons, produce the same values along the tile edges as they would for the whole
+//! grid. The halo must therefore be at least the radius of the tool's neighbourhood. Tools
+//! whose outputs depend on the entire grid, such as flow accumulation or percentile-based
+//! stretches, cannot be tiled in this way.
+//!
+//! The tiles are processed in parallel and the cores of the output tiles (i.e. without their
+//! halos) are mosaicked, a row at a time, into the output GeoTIFF. If the output is a
+//! directory, each output tile is instead written to a file in that directory.
+
+use super::validation::{find_parameter, parse_args};
+use super::workflow::run_tool_process;
+use super::{ParameterFileType, ParameterType, ToolError, ToolErrorKind, ToolManager};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Error;
+use std::path;
+use std::path::Path;
+use std::process;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+use whitebox_common::structures::Array2D;
+use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_raster::{is_nodata_value, GeoTiffReader, GeoTiffWriter, Raster, RasterConfigs};
+
+/// The size of the block cache of each of the readers used to read the tile windows.
+const READER_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Options for running a tool over a set of tiles.
+#[derive(Clone, Copy, Debug)]
+pub struct TilingOptions {
+    /// The width and height of the tiles into which a single input raster is split, in cells.
+    pub tile_size: usize,
+    /// The number of cells by which each tile is extended on each side before it is processed.
+    pub halo: usize,
+}
+
+impl Default for TilingOptions {
+    fn default() -> TilingOptions {
+        TilingOptions {
+            tile_size: 2048,
+            halo: 16,
+        }
+    }
+}
+
+/// A block of rows and columns of the input grid.
+struct Tile {
+    name: String,
+    row: isize,
+    column: isize,
+    rows: usize,
+    columns: usize,
+}
+
+/// A GeoTIFF file, or a directory of GeoTIFF tiles treated as a single grid.
+struct TileSet {
+    configs: RasterConfigs,
+    // The files in the set, and the row and column of their top-left cells in the grid.
+    files: Vec<Tile>,
+    is_directory: bool,
+}
+
+impl TileSet {
+    fn new(file_name: &str) -> Result<TileSet, Error> {
+        let is_tiff = |f: &Path| {
+            f.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case("tif") || e.eq_ignore_ascii_case("tiff"))
+                .unwrap_or(false)
+        };
+        if !Path::new(file_name).is_dir() {
+            if !is_tiff(Path::new(file_name)) {
+                return Err(ToolError::new_error(
+                    ToolErrorKind::UnsupportedFormat,
+                    format!("Tiled processing requires GeoTIFF inputs ({}).", file_name),
+                ));
+            }
+            let configs = GeoTiffReader::new(file_name)?.configs;
+            let file = Tile {
+                name: file_name.to_string(),
+                row: 0,
+                column: 0,
+                rows: configs.rows,
+                columns: configs.columns,
+            };
+            return Ok(TileSet {
+                configs,
+                files: vec![file],
+                is_directory: false,
+            });
+        }
+
+        let mut file_names = fs::read_dir(file_name)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && is_tiff(p))
+            .collect::<Vec<_>>();
+        file_names.sort();
+        if file_names.is_empty() {
+            return Err(ToolError::new_error(
+                ToolErrorKind::BadArgument,
+                format!("The directory {} does not contain any GeoTIFF tiles.", file_name),
+            ));
+        }
+
+        let mut tile_configs = vec![];
+        for f in &file_names {
+            tile_configs.push(GeoTiffReader::new(&f.to_string_lossy())?.configs);
+        }
+        let mut configs = tile_configs[0].clone();
+        let (res_x, res_y) = (configs.resolution_x, configs.resolution_y);
+        for (f, c) in file_names.iter().zip(&tile_configs) {
+            if (c.resolution_x - res_x).abs() > 1e-6 * res_x
+                || (c.resolution_y - res_y).abs() > 1e-6 * res_y
+            {
+                return Err(ToolError::new_error(
+                    ToolErrorKind::BadArgument,
+                    format!(
+                        "The tile {} does not have the same resolution as the other tiles.",
+                        f.display()
+                    ),
+                ));
+            }
+            configs.north = configs.north.max(c.north);
+            configs.west = configs.west.min(c.west);
+        }
+
+        // The extents of the tiles are based on their numbers of rows and columns, rather than
+        // on their east and south coordinates, which the GeoTIFF reader places one cell inside
+        // the edges of the grid.
+        let mut files = vec![];
+        for (f, c) in file_names.iter().zip(&tile_configs) {
+            let row = (configs.north - c.north) / res_y;
+            let column = (c.west - configs.west) / res_x;
+            if (row - row.round()).abs() > 1e-3 || (column - column.round()).abs() > 1e-3 {
+                return Err(ToolError::new_error(
+                    ToolErrorKind::BadArgument,
+                    format!(
+                        "The tile {} is not aligned with the grid of the other tiles.",
+                        f.display()
+                    ),
+                ));
+            }
+            files.push(Tile {
+                name: f.to_string_lossy().to_string(),
+                row: row.round() as isize,
+                column: column.round() as isize,
+                rows: c.rows,
+                columns: c.columns,
+            });
+        }
+        configs.rows = files.iter().map(|f| f.row as usize + f.rows).max().unwrap_or(0);
+        configs.columns = files.iter().map(|f| f.column as usize + f.columns).max().unwrap_or(0);
+        configs.south = configs.north - configs.rows as f64 * res_y;
+        configs.east = configs.west + configs.columns as f64 * res_x;
+
+        Ok(TileSet {
+            configs,
+            files,
+            is_directory: true,
+        })
+    }
+
+    /// Returns true if the two sets cover the same grid.
+    fn same_grid(&self, other: &TileSet) -> bool {
+        let (a, b) = (&self.configs, &other.configs);
+        a.rows == b.rows
+            && a.columns == b.columns
+            && (a.north - b.north).abs() < 1e-3 * a.resolution_y
+            && (a.west - b.west).abs() < 1e-3 * a.resolution_x
+    }
+
+    /// Reads a window of the first band of the grid. Cells beyond the edges of the grid, or
+    /// in gaps between tiles, have the nodata value.
+    fn read_window(&self, row: isize, column: isize, rows: usize, columns: usize) -> Result<Array2D<f64>, Error> {
+        let nodata = self.configs.nodata;
+        let mut window: Array2D<f64> = Array2D::new(rows as isize, columns as isize, nodata, nodata)?;
+        for file in &self.files {
+            let r0 = row.max(file.row);
+            let c0 = column.max(file.column);
+            let r1 = (row + rows as isize).min(file.row + file.rows as isize);
+            let c1 = (column + columns as isize).min(file.column + file.columns as isize);
+            if r0 >= r1 || c0 >= c1 {
+                continue;
+            }
+            let mut reader = GeoTiffReader::new(&file.name)?.with_cache_size(READER_CACHE_SIZE);
+            let file_nodata = reader.configs.nodata;
+            let values = reader.read_window(
+                r0 - file.row,
+                c0 - file.column,
+                (r1 - r0) as usize,
+                (c1 - c0) as usize,
+            )?;
+            for r in r0..r1 {
+                for c in c0..c1 {
+                    let z = values.get_value(r - r0, c - c0);
+                    if !is_nodata_value(z, file_nodata) {
+                        window.set_value(r - row, c - column, z);
+                    }
+                }
+            }
+        }
+        Ok(window)
+    }
+
+    /// Returns the configs of a window of the grid.
+    fn window_configs(&self, row: isize, column: isize, rows: usize, columns: usize) -> RasterConfigs {
+        let mut configs = self.configs.clone();
+        configs.rows = rows;
+        configs.columns = columns;
+        configs.bands = 1;
+        configs.north = self.configs.north - row as f64 * self.configs.resolution_y;
+        configs.south = configs.north - rows as f64 * self.configs.resolution_y;
+        configs.west = self.configs.west + column as f64 * self.configs.resolution_x;
+        configs.east = configs.west + columns as f64 * self.configs.resolution_x;
+        // The georeferencing of the window is given by its extent rather than by the tags of
+        // the original file, which locate that file's top-left corner.
+        configs.model_tiepoint = vec![];
+        configs.model_transformation = [0f64; 16];
+        configs
+    }
+}
+
+impl ToolManager {
+    /// Runs a tool over a set of tiles of its raster inputs, either the tiles of a single
+    /// GeoTIFF or those in a directory, and mosaics the output tiles. The tool must have a
+    /// single raster output; all of its raster inputs must cover the same grid.
+    pub fn run_tool_tiled(&self, tool_name: String, args: Vec<String>, options: TilingOptions) -> Result<(), Error> {
+        let start = Instant::now();
+        let parameters = match self.get_parameter_list(&tool_name) {
+            Some(p) => p,
+            None => {
+                return Err(ToolError::new_error(
+                    ToolErrorKind::BadArgument,
+                    format!("Unrecognized tool name {}.", tool_name),
+                ))
+            }
+        };
+        if options.tile_size == 0 {
+            return Err(ToolError::new_error(
+                ToolErrorKind::BadArgument,
+                "The tile size must be greater than zero.",
+            ));
+        }
+        self.validate_args(&tool_name, &parameters, &args)?;
+
+        // Find the raster inputs and the raster output among the tool's arguments.
+        let mut tool_args = parse_args(&parameters, &args);
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        for (a, arg) in tool_args.iter().enumerate() {
+            let value = match &arg.value {
+                Some(v) => v,
+                None => continue,
+            };
+            match find_parameter(&parameters, &arg.flag).map(|p| &p.parameter_type) {
+                Some(ParameterType::ExistingFile(ParameterFileType::Raster)) => inputs.push(a),
+                Some(ParameterType::ExistingFileOrFloat(ParameterFileType::Raster))
+                    if value.trim().parse::<f64>().is_err() =>
+                {
+                    inputs.push(a)
+                }
+                Some(ParameterType::NewFile(ParameterFileType::Raster)) => outputs.push(a),
+                _ => {}
+            }
+        }
+        if inputs.is_empty() || outputs.len() != 1 {
+            return Err(ToolError::new_error(
+                ToolErrorKind::BadArgument,
+                format!(
+                    "{} cannot be run in tiles; tiled processing requires at least one raster input and exactly one raster output.",
+                    tool_name
+                ),
+            ));
+        }
+
+        let mut tile_sets = vec![];
+        for &a in &inputs {
+            let file_name = tool_args[a].value.clone().unwrap_or_default();
+            tile_sets.push(TileSet::new(&self.resolve_file_name(&file_name, &self.working_dir))?);
+        }
+        if tile_sets.iter().any(|t| !t.same_grid(&tile_sets[0])) {
+            return Err(ToolError::new_error(
+                ToolErrorKind::BadArgument,
+                "All of the raster inputs must cover the same grid to be processed in tiles.",
+            ));
+        }
+
+        let output_value = tool_args[outputs[0]].value.clone().unwrap_or_default();
+        let output_file = self.resolve_file_name(&output_value, &self.working_dir);
+        let sep = path::MAIN_SEPARATOR.to_string();
+        let output_is_directory = output_file.ends_with(&sep) || output_file.ends_with('/') || Path::new(&output_file).is_dir();
+        let lower_case_output = output_file.to_lowercase();
+        if !output_is_directory && !lower_case_output.ends_with(".tif") && !lower_case_output.ends_with(".tiff") {
+            return Err(ToolError::new_error(
+                ToolErrorKind::UnsupportedFormat,
+                format!("Tiled processing requires a GeoTIFF output ({}).", output_file),
+            ));
+        }
+
+        // The tiles are those of the first input, if it is a directory of tiles, or otherwise
+        // a regular grid of tiles of the requested size.
+        let grid = &tile_sets[0];
+        let tiles = if grid.is_directory {
+            grid.files
+                .iter()
+                .map(|f| Tile {
+                    name: Path::new(&f.name)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    row: f.row,
+                    column: f.column,
+                    rows: f.rows,
+                    columns: f.columns,
+                })
+                .collect::<Vec<Tile>>()
+        } else {
+            let mut tiles = vec![];
+            for row in (0..grid.configs.rows).step_by(options.tile_size) {
+                for column in (0..grid.configs.columns).step_by(options.tile_size) {
+                    tiles.push(Tile {
+                        name: format!("tile_{}_{}", row / options.tile_size, column / options.tile_size),
+                        row: row as isize,
+                        column: column as isize,
+                        rows: options.tile_size.min(grid.configs.rows - row),
+                        columns: options.tile_size.min(grid.configs.columns - column),
+                    });
+                }
+            }
+            tiles
+        };
+        let num_tiles = tiles.len();
+
+        let (output_dir, output_stem) = if output_is_directory {
+            fs::create_dir_all(&output_file)?;
+            let mut dir = output_file.clone();
+            if !dir.ends_with(&sep) && !dir.ends_with('/') {
+                dir.push_str(&sep);
+            }
+            (dir, "tiles".to_string())
+        } else {
+            let p = Path::new(&output_file);
+            let dir = match p.parent() {
+                Some(d) if !d.as_os_str().is_empty() => format!("{}{}", d.to_string_lossy(), sep),
+                _ => String::new(),
+            };
+            let stem = p
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (dir, stem)
+        };
+        let temp_dir = format!("{}{}_tiles_{}{}", output_dir, output_stem, process::id(), sep);
+        fs::create_dir_all(&temp_dir)?;
+
+        if self.verbose {
+            println!(
+                "Running {} over {} tiles (halo of {} cells)...",
+                tool_name, num_tiles, options.halo
+            );
+        }
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let mut num_procs = num_cpus::get();
+        if configs.max_procs > 0 && (configs.max_procs as usize) < num_procs {
+            num_procs = configs.max_procs as usize;
+        }
+
+        // The tool's arguments, with the raster inputs and output replaced by those of a tile.
+        // The values are substituted for each tile.
+        for &a in inputs.iter().chain(outputs.iter()) {
+            tool_args[a].value = None;
+        }
+
+        let exe = env::current_exe()?;
+        let tiles = Arc::new(tiles);
+        let tile_sets = Arc::new(tile_sets);
+        let tool_args = Arc::new(tool_args);
+        let inputs = Arc::new(inputs);
+        let output_arg = outputs[0];
+        let next_tile = Arc::new(AtomicUsize::new(0));
+        let failed = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..num_procs.min(num_tiles) {
+            let tiles = tiles.clone();
+            let tile_sets = tile_sets.clone();
+            let tool_args = tool_args.clone();
+            let inputs = inputs.clone();
+            let next_tile = next_tile.clone();
+            let failed = failed.clone();
+            let tx = tx.clone();
+            let exe = exe.clone();
+            let tool_name = tool_name.clone();
+            let temp_dir = temp_dir.clone();
+            let working_dir = self.working_dir.clone();
+            thread::spawn(move || loop {
+                let t = next_tile.fetch_add(1, Ordering::SeqCst);
+                if t >= tiles.len() || failed.load(Ordering::SeqCst) {
+                    break;
+                }
+                let tile = &tiles[t];
+                let result = (|| -> Result<bool, Error> {
+                    let row = tile.row - options.halo as isize;
+                    let column = tile.column - options.halo as isize;
+                    let rows = tile.rows + 2 * options.halo;
+                    let columns = tile.columns + 2 * options.halo;
+                    let mut tile_args = tool_args
+                        .iter()
+                        .map(|a| (a.flag.clone(), a.value.clone()))
+                        .collect::<Vec<(String, Option<String>)>>();
+                    let mut input_files = vec![];
+                    for (i, &a) in inputs.iter().enumerate() {
+                        let window = tile_sets[i].read_window(row, column, rows, columns)?;
+                        let nodata = tile_sets[i].configs.nodata;
+                        if i == 0 && !tile_has_data(&window, options.halo, nodata) {
+                            // There is nothing to process within the tile.
+                            return Ok(false);
+                        }
+                        let file_name = format!("{}{}_input{}.tif", temp_dir, tile.name, i + 1);
+                        let window_configs = tile_sets[i].window_configs(row, column, rows, columns);
+                        let mut input = Raster::initialize_using_array2d(&file_name, &window_configs, window);
+                        input.write()?;
+                        tile_args[a].1 = Some(file_name.clone());
+                        input_files.push(file_name);
+                    }
+                    tile_args[output_arg].1 = Some(format!("{}{}_output.tif", temp_dir, tile.name));
+
+                    let mut command = Command::new(&exe);
+                    command.arg(format!("--run={}", tool_name));
+                    if !working_dir.is_empty() {
+                        command.arg(format!("--wd={}", working_dir));
+                    }
+                    for (flag, value) in tile_args {
+                        match value {
+                            Some(v) => command.arg(format!("{}={}", flag, v)),
+                            None => command.arg(flag),
+                        };
+                    }
+                    let result = run_tool_process(command);
+                    for f in input_files {
+                        let _ = fs::remove_file(f);
+                    }
+                    result.map(|_| true)
+                })();
+                if result.is_err() {
+                    failed.store(true, Ordering::SeqCst);
+                }
+                tx.send((t, result)).unwrap();
+            });
+        }
+        drop(tx);
+
+        let mut processed = vec![false; num_tiles];
+        let mut failure = None;
+        let mut num_completed = 0;
+        let mut old_progress = usize::MAX;
+        for (t, result) in rx {
+            match result {
+                Ok(p) => processed[t] = p,
+                Err(e) => {
+                    if failure.is_none() {
+                        failure = Some((t, e));
+                    }
+                }
+            }
+            num_completed += 1;
+            if self.verbose {
+                let progress = (100.0_f64 * num_completed as f64 / num_tiles as f64) as usize;
+                if progress != old_progress {
+                    println!("Processing tiles: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if let Some((t, e)) = failure {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(ToolError::new_error(
+                ToolErrorKind::of(&e),
+                format!("Error processing tile {}: {}", tiles[t].name, e),
+            ));
+        }
+
+        let result = if output_is_directory {
+            self.write_output_tiles(&tiles, &processed, &tile_sets[0], &temp_dir, &output_dir, options.halo)
+        } else {
+            self.mosaic_output_tiles(&tiles, &processed, &tile_sets[0], &temp_dir, &output_file, options.halo, &tool_name)
+        };
+        let _ = fs::remove_dir_all(&temp_dir);
+        result?;
+
+        if self.verbose {
+            println!(
+                "{} of {} tiles contained data and were processed",
+                processed.iter().filter(|p| **p).count(),
+                num_tiles
+            );
+            println!(
+                "Elapsed Time (including I/O): {}",
+                get_formatted_elapsed_time(start)
+            );
+        }
+        Ok(())
+    }
+
+    /// Mosaics the cores of the output tiles into a single GeoTIFF, one row at a time.
+    #[allow(clippy::too_many_arguments)]
+    fn mosaic_output_tiles(
+        &self,
+        tiles: &[Tile],
+        processed: &[bool],
+        grid: &TileSet,
+        temp_dir: &str,
+        output_file: &str,
+        halo: usize,
+        tool_name: &str,
+    ) -> Result<(), Error> {
+        let tile_output = |t: usize| format!("{}{}_output.tif", temp_dir, tiles[t].name);
+        let first = match processed.iter().position(|p| *p) {
+            Some(t) => t,
+            None => {
+                return Err(ToolError::new_error(
+                    ToolErrorKind::BadArgument,
+                    "None of the tiles contain any data.",
+                ))
+            }
+        };
+        let tile_configs = GeoTiffReader::new(&tile_output(first))?.configs;
+        let mut configs = grid.window_configs(0, 0, grid.configs.rows, grid.configs.columns);
+        configs.nodata = tile_configs.nodata;
+        configs.data_type = tile_configs.data_type;
+        configs.photometric_interp = tile_configs.photometric_interp;
+        configs.palette = tile_configs.palette.clone();
+        configs.z_units = tile_configs.z_units.clone();
+        configs.metadata = vec![format!(
+            "Created by whitebox_tools\' {} tool, run in {} tiles with a halo of {} cells",
+            tool_name,
+            tiles.len(),
+            halo
+        )];
+        let nodata = configs.nodata;
+        let rows = configs.rows as isize;
+        let columns = configs.columns;
+        let mut writer = GeoTiffWriter::new(output_file, &configs)?;
+
+        let mut readers: HashMap<usize, GeoTiffReader> = HashMap::new();
+        let mut old_progress = usize::MAX;
+        for row in 0..rows {
+            let mut values = vec![nodata; columns];
+            for (t, tile) in tiles.iter().enumerate() {
+                if row < tile.row || row >= tile.row + tile.rows as isize {
+                    readers.remove(&t);
+                    continue;
+                }
+                if !processed[t] {
+                    continue;
+                }
+                let reader = match readers.entry(t) {
+                    Entry::Occupied(e) => e.into_mut(),
+                    Entry::Vacant(e) => e.insert(GeoTiffReader::new(&tile_output(t))?.with_cache_size(READER_CACHE_SIZE)),
+                };
+                let tile_nodata = reader.configs.nodata;
+                let tile_values = reader.read_window(row - tile.row + halo as isize, halo as isize, 1, tile.columns)?;
+                for c in 0..tile.columns {
+                    let z = tile_values.get_value(0, c as isize);
+                    let column = tile.column + c as isize;
+                    if !is_nodata_value(z, tile_nodata) && column >= 0 && (column as usize) < columns {
+                        values[column as usize] = z;
+                    }
+                }
+            }
+            writer.write_row(&values)?;
+            if self.verbose {
+                let progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Mosaicking tiles: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        writer.finish()
+    }
+
+    /// Writes the core of each output tile to a file in the output directory, named after
+    /// the input tile.
+    fn write_output_tiles(
+        &self,
+        tiles: &[Tile],
+        processed: &[bool],
+        grid: &TileSet,
+        temp_dir: &str,
+        output_dir: &str,
+        halo: usize,
+    ) -> Result<(), Error> {
+        for (t, tile) in tiles.iter().enumerate() {
+            if !processed[t] {
+                continue;
+            }
+            let mut reader = GeoTiffReader::new(&format!("{}{}_output.tif", temp_dir, tile.name))?;
+            let values = reader.read_window(halo as isize, halo as isize, tile.rows, tile.columns)?;
+            let mut configs = grid.window_configs(tile.row, tile.column, tile.rows, tile.columns);
+            configs.nodata = reader.configs.nodata;
+            configs.data_type = reader.configs.data_type;
+            configs.photometric_interp = reader.configs.photometric_interp;
+            configs.palette = reader.configs.palette.clone();
+            let file_name = format!("{}{}.tif", output_dir, tile.name);
+            let mut output = Raster::initialize_using_array2d(&file_name, &configs, values);
+            output.write()?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if any cell in the core of a tile window (i.e. excluding its halo) has data.
+fn tile_has_data(window: &Array2D<f64>, halo: usize, nodata: f64) -> bool {
+    let halo = halo as isize;
+    for row in halo..window.rows - halo {
+        for column in halo..window.columns - halo {
+            if !is_nodata_value(window.get_value(row, column), nodata) {
+                return true;
+            }
+        }
+    }
+    false
+}
@@ -0,0 +1,546 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Checks the arguments of a tool against the parameters that it declares, before the tool
+//! is run. Each tool parses its own arguments, and a missing or malformed argument would
+//! otherwise cause a panic part way through the tool, or be silently ignored. All of the
+//! problems that are found are reported together, in a single error.
+
+use super::{
+    ParameterFileType, ParameterType, ToolError, ToolErrorKind, ToolManager, ToolParameter,
+    VectorGeometryType,
+};
+use std::fs::File;
+use std::io::{Error, Read};
+use std::path;
+use std::path::Path;
+use whitebox_vector::{get_vector_format_from_file, ShapeType, Shapefile, VectorFormat};
+
+#[derive(Deserialize)]
+struct ToolParameters {
+    parameters: Vec<ToolParameter>,
+}
+
+/// An argument of a tool, i.e. a flag and its value, if it has one.
+pub(super) struct ToolArg {
+    pub flag: String,
+    pub value: Option<String>,
+}
+
+impl ToolManager {
+    /// Returns the parameters of a tool or plugin, or `None` if the tool is not recognized.
+    pub(super) fn get_parameter_list(&self, tool_name: &str) -> Option<Vec<ToolParameter>> {
+        let json = match self.get_tool(tool_name) {
+            Some(tool) => tool.get_tool_parameters(),
+            None => {
+                let plugins = self.get_plugin_list().ok()?;
+                let plugin_data = plugins.get(&tool_name.to_lowercase())?;
+                format!("{{\"parameters\":{}}}", plugin_data["parameters"])
+            }
+        };
+        serde_json::from_str::<ToolParameters>(&json)
+            .ok()
+            .map(|p| p.parameters)
+    }
+
+    /// Resolves a file name against a directory, in the same way as the tools.
+    pub(super) fn resolve_file_name(&self, file_name: &str, directory: &str) -> String {
+        if !file_name.contains(path::MAIN_SEPARATOR) && !file_name.contains('/') {
+            format!("{}{}", directory, file_name)
+        } else {
+            file_name.to_string()
+        }
+    }
+
+    /// Checks the arguments of a tool, returning a BadArgument error that lists all of the
+    /// problems found. Flags that are not parameters of the tool are reported as warnings.
+    pub(super) fn validate_args(
+        &self,
+        tool_name: &str,
+        parameters: &[ToolParameter],
+        args: &[String],
+    ) -> Result<(), Error> {
+        let tool_args = parse_args(parameters, args);
+        if self.verbose {
+            for arg in &tool_args {
+                if find_parameter(parameters, &arg.flag).is_none() {
+                    println!("Warning: {} is not a parameter of {}.", arg.flag, tool_name);
+                }
+            }
+        }
+        let problems = self.check_args(parameters, &tool_args, &self.working_dir, &|_| false);
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(ToolError::new_error(
+            ToolErrorKind::BadArgument,
+            format!(
+                "Invalid arguments for {}:\n{}",
+                tool_name,
+                problems.join("\n")
+            ),
+        ))
+    }
+
+    /// Returns a description of each problem with the arguments of a tool. Input files, and
+    /// the directories of output files, must exist, unless `will_exist` returns true for their
+    /// resolved path, as it does for the files that are created by the steps of a workflow.
+    pub(super) fn check_args(
+        &self,
+        parameters: &[ToolParameter],
+        args: &[ToolArg],
+        working_dir: &str,
+        will_exist: &dyn Fn(&str) -> bool,
+    ) -> Vec<String> {
+        let mut problems = vec![];
+        let check_file =
+            |flag: &str, file: &str, file_type: &ParameterFileType, problems: &mut Vec<String>| {
+                let file_name = self.resolve_file_name(file, working_dir);
+                if will_exist(&file_name) {
+                    return;
+                }
+                let path = strip_layer_name(&file_name);
+                if !Path::new(&path).is_file() {
+                    problems.push(format!("{}: the file {} does not exist.", flag, file_name));
+                    return;
+                }
+                let geometry_type = match file_type {
+                    ParameterFileType::Vector(g) | ParameterFileType::RasterAndVector(g) => g,
+                    _ => return,
+                };
+                if let Some(shape_type) = read_shape_type(&file_name) {
+                    if !geometry_type_matches(geometry_type, shape_type) {
+                        problems.push(format!(
+                            "{}: {} does not contain {} features.",
+                            flag,
+                            file_name,
+                            geometry_type_name(geometry_type)
+                        ));
+                    }
+                }
+            };
+
+        for arg in args {
+            let parameter = match find_parameter(parameters, &arg.flag) {
+                Some(p) => p,
+                None => continue,
+            };
+            let flag = &arg.flag;
+            let value = arg.value.clone().unwrap_or_default();
+            let value = value.trim();
+            if let ParameterType::Boolean = parameter.parameter_type {
+                if arg.value.is_some()
+                    && !value.eq_ignore_ascii_case("true")
+                    && !value.eq_ignore_ascii_case("false")
+                {
+                    problems.push(format!("{}: '{}' is not true or false.", flag, value));
+                }
+                continue;
+            }
+            if value.is_empty() {
+                // An empty value is how some front ends leave an optional parameter unset.
+                if !parameter.optional && parameter.default_value.is_none() {
+                    problems.push(format!("{}: a value is required.", flag));
+                }
+                continue;
+            }
+            match &parameter.parameter_type {
+                ParameterType::Integer => match value.parse::<f64>() {
+                    Ok(v) if v.fract() == 0f64 => {}
+                    _ => problems.push(format!("{}: '{}' is not an integer.", flag, value)),
+                },
+                ParameterType::Float if value.parse::<f64>().is_err() => {
+                    problems.push(format!("{}: '{}' is not a number.", flag, value));
+                }
+                ParameterType::OptionList(options) => {
+                    let normalized = normalize_option(value);
+                    if !options.iter().any(|o| normalize_option(o) == normalized) {
+                        problems.push(format!(
+                            "{}: '{}' is not one of the options ({}).",
+                            flag,
+                            value,
+                            options.join(", ")
+                        ));
+                    }
+                }
+                ParameterType::ExistingFile(file_type) => {
+                    check_file(flag, value, file_type, &mut problems)
+                }
+                ParameterType::ExistingFileOrFloat(file_type) if value.parse::<f64>().is_err() => {
+                    check_file(flag, value, file_type, &mut problems);
+                }
+                ParameterType::FileList(file_type) => {
+                    for file in value
+                        .split([';', ','])
+                        .map(|f| f.trim())
+                        .filter(|f| !f.is_empty())
+                    {
+                        check_file(flag, file, file_type, &mut problems);
+                    }
+                }
+                ParameterType::NewFile(_) => {
+                    let file_name = self.resolve_file_name(value, working_dir);
+                    if let Some(dir) = Path::new(&file_name).parent() {
+                        if !dir.as_os_str().is_empty()
+                            && !dir.is_dir()
+                            && !will_exist(&dir.to_string_lossy())
+                        {
+                            problems.push(format!(
+                                "{}: the directory {} does not exist.",
+                                flag,
+                                dir.display()
+                            ));
+                        }
+                    }
+                }
+                ParameterType::Directory => {
+                    let dir = self.resolve_file_name(value, working_dir);
+                    if !parameter.optional && !Path::new(&dir).is_dir() {
+                        problems.push(format!("{}: the directory {} does not exist.", flag, dir));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for parameter in parameters {
+            if parameter.optional || parameter.default_value.is_some() {
+                continue;
+            }
+            if let ParameterType::Boolean = parameter.parameter_type {
+                // A missing flag is simply false.
+                continue;
+            }
+            let given = args.iter().any(|a| parameter_has_flag(parameter, &a.flag));
+            if !given {
+                problems.push(format!(
+                    "{}: the required parameter '{}' is missing.",
+                    parameter.flags.join(", "),
+                    parameter.name
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+/// Splits the arguments of a tool into flags and values. A value is given either as
+/// `--flag=value` or as `--flag value`, except for Boolean parameters, which may be given
+/// without a value.
+pub(super) fn parse_args(parameters: &[ToolParameter], args: &[String]) -> Vec<ToolArg> {
+    let mut tool_args = vec![];
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].replace(['\"', '\''], "");
+        if let Some((flag, value)) = arg.split_once('=') {
+            tool_args.push(ToolArg {
+                flag: flag.trim().to_string(),
+                value: Some(value.trim().to_string()),
+            });
+            i += 1;
+            continue;
+        }
+        let is_boolean = match find_parameter(parameters, &arg) {
+            Some(p) => matches!(p.parameter_type, ParameterType::Boolean),
+            None => false,
+        };
+        let next_is_value = match args.get(i + 1) {
+            Some(next) => !next.starts_with('-') || next.trim().parse::<f64>().is_ok(),
+            None => false,
+        };
+        if next_is_value && !is_boolean {
+            tool_args.push(ToolArg {
+                flag: arg.trim().to_string(),
+                value: Some(args[i + 1].replace(['\"', '\''], "").trim().to_string()),
+            });
+            i += 2;
+        } else {
+            tool_args.push(ToolArg {
+                flag: arg.trim().to_string(),
+                value: None,
+            });
+            i += 1;
+        }
+    }
+    tool_args
+}
+
+/// Returns the parameter with a flag, comparing flags without their leading dashes.
+pub(super) fn find_parameter<'a>(
+    parameters: &'a [ToolParameter],
+    flag: &str,
+) -> Option<&'a ToolParameter> {
+    parameters.iter().find(|p| parameter_has_flag(p, flag))
+}
+
+fn parameter_has_flag(parameter: &ToolParameter, flag: &str) -> bool {
+    let flag = flag.trim_start_matches('-');
+    parameter
+        .flags
+        .iter()
+        .any(|f| f.trim_start_matches('-').eq_ignore_ascii_case(flag))
+}
+
+/// Options are compared without regard to case, spaces, underscores, or hyphens, e.g.
+/// 'catchment area' and 'Catchment_Area' are the same option.
+fn normalize_option(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Removes the layer name from a GeoPackage file name of the form 'file.gpkg:layer'.
+fn strip_layer_name(file_name: &str) -> String {
+    match file_name.to_lowercase().rfind(".gpkg:") {
+        Some(i) => file_name[..i + 5].to_string(),
+        None => file_name.to_string(),
+    }
+}
+
+/// Reads the shape type of a vector file, in any of the formats read by `Shapefile::read`.
+/// Only the header of a Shapefile is read; the other formats give their geometry type with
+/// their features, and so are read in full.
+fn read_shape_type(file_name: &str) -> Option<ShapeType> {
+    match get_vector_format_from_file(file_name) {
+        VectorFormat::Shapefile => {
+            let mut header = [0u8; 36];
+            File::open(file_name).ok()?.read_exact(&mut header).ok()?;
+            Some(ShapeType::from_int(i32::from_le_bytes([
+                header[32], header[33], header[34], header[35],
+            ])))
+        }
+        VectorFormat::GeoJson | VectorFormat::GeoPackage => {
+            Some(Shapefile::read(file_name).ok()?.header.shape_type)
+        }
+        VectorFormat::Unknown => None,
+    }
+}
+
+fn geometry_type_matches(geometry_type: &VectorGeometryType, shape_type: ShapeType) -> bool {
+    let (is_point, is_line, is_polygon) = match shape_type.base_shape_type() {
+        ShapeType::Point | ShapeType::MultiPoint => (true, false, false),
+        ShapeType::PolyLine => (false, true, false),
+        ShapeType::Polygon => (false, false, true),
+        _ => return true,
+    };
+    match geometry_type {
+        VectorGeometryType::Any => true,
+        VectorGeometryType::Point => is_point,
+        VectorGeometryType::Line => is_line,
+        VectorGeometryType::Polygon => is_polygon,
+        VectorGeometryType::LineOrPolygon => is_line || is_polygon,
+    }
+}
+
+fn geometry_type_name(geometry_type: &VectorGeometryType) -> &'static str {
+    match geometry_type {
+        VectorGeometryType::Any => "vector",
+        VectorGeometryType::Point => "point",
+        VectorGeometryType::Line => "line",
+        VectorGeometryType::Polygon => "polygon",
+        VectorGeometryType::LineOrPolygon => "line or polygon",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use whitebox_common::structures::Point2D;
+    use whitebox_vector::{AttributeField, FieldData, FieldDataType, ShapefileGeometry};
+
+    fn test_dir(name: &str) -> String {
+        let dir = env::temp_dir().join(format!(
+            "whitebox_validation_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        format!("{}{}", dir.to_string_lossy(), path::MAIN_SEPARATOR)
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn problems(tm: &ToolManager, tool_name: &str, tool_args: &[&str]) -> Vec<String> {
+        let parameters = tm.get_parameter_list(tool_name).unwrap();
+        let tool_args = parse_args(&parameters, &args(tool_args));
+        tm.check_args(&parameters, &tool_args, &tm.working_dir, &|_| false)
+    }
+
+    fn write_lines(file_name: &str) {
+        let mut output = Shapefile::new(file_name, ShapeType::PolyLine).unwrap();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 4u8, 0u8));
+        let mut geometry = ShapefileGeometry::new(ShapeType::PolyLine);
+        geometry.add_part(&[Point2D::new(0.0, 0.0), Point2D::new(10.0, 5.0)]);
+        output.add_record(geometry);
+        output.attributes.add_record(vec![FieldData::Int(1)], false);
+        output.write().unwrap();
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let tm = ToolManager::new(&test_dir("parse"), &false).unwrap();
+        let parameters = tm.get_parameter_list("D8FlowAccumulation").unwrap();
+        let tool_args = parse_args(
+            &parameters,
+            &args(&[
+                "-i='pointer.tif'",
+                "--output",
+                "accum.tif",
+                "--pntr",
+                "--log",
+                "false",
+                "--out_type",
+                "cells",
+            ]),
+        );
+        let parsed = tool_args
+            .iter()
+            .map(|a| (a.flag.as_str(), a.value.as_deref()))
+            .collect::<Vec<_>>();
+        // A Boolean flag does not take the next argument as its value, unless it is given
+        // with '='.
+        assert_eq!(
+            parsed,
+            vec![
+                ("-i", Some("pointer.tif")),
+                ("--output", Some("accum.tif")),
+                ("--pntr", None),
+                ("--log", None),
+                ("false", None),
+                ("--out_type", Some("cells")),
+            ]
+        );
+        assert_eq!(
+            find_parameter(&parameters, "input").unwrap().name,
+            "Input DEM or D8 Pointer File"
+        );
+        assert!(find_parameter(&parameters, "---OUT_TYPE").is_some());
+        assert!(find_parameter(&parameters, "--dem").is_none());
+
+        // A negative number is a value rather than a flag.
+        let parameters = tm.get_parameter_list("Slope").unwrap();
+        let tool_args = parse_args(&parameters, &args(&["--zfactor", "-2.5"]));
+        assert_eq!(tool_args.len(), 1);
+        assert_eq!(tool_args[0].value.as_deref(), Some("-2.5"));
+    }
+
+    #[test]
+    fn test_check_args() {
+        let wd = test_dir("check");
+        let tm = ToolManager::new(&wd, &false).unwrap();
+        fs::write(format!("{}dem.tif", wd), "").unwrap();
+        let found = problems(&tm, "Slope", &["-i=dem.tif", "-o=slope.tif", "--zfactor=2"]);
+        assert!(found.is_empty(), "{:?}", found);
+        assert!(problems(
+            &tm,
+            "D8FlowAccumulation",
+            &[
+                "-i=dem.tif",
+                "-o=accum.tif",
+                "--out_type=Specific_Contributing-Area",
+                "--log=TRUE"
+            ]
+        )
+        .is_empty());
+
+        // All of the problems are reported.
+        let found = problems(
+            &tm,
+            "BreachDepressionsLeastCost",
+            &[
+                "-i=missing.tif",
+                "-o=no_dir/breached.tif",
+                "--dist=2.5",
+                "--max_cost=high",
+                "--fill=yes",
+                "--flat_increment=",
+            ],
+        );
+        let expected = [
+            "the file",
+            "the directory",
+            "'2.5' is not an integer",
+            "'high' is not a number",
+            "'yes' is not true or false",
+        ];
+        assert_eq!(found.len(), expected.len(), "{:?}", found);
+        for (problem, expected) in found.iter().zip(expected) {
+            assert!(problem.contains(expected), "{}", problem);
+        }
+        let found = problems(&tm, "D8FlowAccumulation", &["-o=accum.tif", "--out_type=area"]);
+        assert_eq!(found.len(), 2, "{:?}", found);
+        assert!(found[0].contains("is not one of the options (cells, catchment area"));
+        assert!(found[1].contains("the required parameter 'Input DEM or D8 Pointer File'"));
+
+        // validate_args gives the problems as a BadArgument error.
+        let parameters = tm.get_parameter_list("Slope").unwrap();
+        let err = tm
+            .validate_args("Slope", &parameters, &args(&["-i=missing.tif"]))
+            .err()
+            .unwrap();
+        assert_eq!(ToolErrorKind::of(&err), ToolErrorKind::BadArgument);
+        assert!(err.to_string().starts_with("Invalid arguments for Slope:\n"));
+    }
+
+    #[test]
+    fn test_vector_geometry_types_are_checked() {
+        let wd = test_dir("geometry");
+        let tm = ToolManager::new(&wd, &false).unwrap();
+        for file_name in ["lines.shp", "lines.geojson", "lines.gpkg"] {
+            write_lines(&format!("{}{}", wd, file_name));
+            let shape_type = read_shape_type(&format!("{}{}", wd, file_name));
+            assert_eq!(shape_type, Some(ShapeType::PolyLine), "{}", file_name);
+
+            let input = format!("-i={}", file_name);
+            let clip = format!("--clip={}", file_name);
+            let found = problems(&tm, "Clip", &[&input, &clip, "-o=clipped.shp"]);
+            assert_eq!(found.len(), 1, "{:?}", found);
+            let expected = format!(
+                "--clip: {}{} does not contain polygon features.",
+                wd, file_name
+            );
+            assert_eq!(found[0], expected);
+        }
+        // A GeoPackage is read with its layer name.
+        let found = problems(
+            &tm,
+            "Clip",
+            &["-i=lines.gpkg:lines", "--clip=lines.gpkg:lines", "-o=clipped.shp"],
+        );
+        assert_eq!(found.len(), 1, "{:?}", found);
+        assert_eq!(read_shape_type(&format!("{}dem.tif", wd)), None);
+
+        assert!(geometry_type_matches(&VectorGeometryType::Point, ShapeType::MultiPointZ));
+        assert!(geometry_type_matches(&VectorGeometryType::LineOrPolygon, ShapeType::PolygonM));
+        assert!(!geometry_type_matches(&VectorGeometryType::Line, ShapeType::Polygon));
+        // A file without features is not rejected.
+        assert!(geometry_type_matches(&VectorGeometryType::Polygon, ShapeType::Null));
+    }
+
+    #[test]
+    fn test_file_names() {
+        let tm = ToolManager::new(&test_dir("names"), &false).unwrap();
+        let sep = path::MAIN_SEPARATOR;
+        assert_eq!(tm.resolve_file_name("dem.tif", "/data/"), "/data/dem.tif");
+        assert_eq!(tm.resolve_file_name("/other/dem.tif", "/data/"), "/other/dem.tif");
+        let nested = format!("sub{}dem.tif", sep);
+        assert_eq!(tm.resolve_file_name(&nested, "/data/"), nested);
+        assert_eq!(strip_layer_name("/data/roads.GPKG:roads"), "/data/roads.GPKG");
+        assert_eq!(strip_layer_name("/data/roads.shp"), "/data/roads.shp");
+        assert_eq!(normalize_option("Catchment_Area"), normalize_option("catchment area"));
+    }
+}
@@ -0,0 +1,838 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Runs a workflow, i.e. a set of tool invocations described in a JSON file, e.g.:
+//!
+//! ```json
+//! {
+//!   "working_directory": "/data/tile_01/",
+//!   "max_procs": 4,
+//!   "steps": [
+//!     { "name": "breach", "tool": "BreachDepressionsLeastCost",
+//!       "args": { "dem": "dem.tif", "output": "@breached.tif", "dist": 20, "fill": true } },
+//!     { "name": "pointer", "tool": "D8Pointer",
+//!       "args": { "dem": "@breached.tif", "output": "@pointer.tif" } },
+//!     { "name": "accum", "tool": "D8FlowAccumulation",
+//!       "args": { "input": "@pointer.tif", "output": "flow_accum.tif", "pntr": true } },
+//!     { "name": "slope", "tool": "Slope",
+//!       "args": { "dem": "@breached.tif", "output": "slope.tif" } }
+//!   ]
+//! }
+//! ```
+//!
+//! The args of each step are keyed by the tool's parameter flags, without the leading dashes.
+//! A step depends on another if it reads a file that the other step creates, and steps that
+//! do not depend on each other are run in parallel, up to `max_procs` steps at a time. File
+//! names beginning with '@' are intermediate files, which are created in the temporary
+//! directory (`temp_directory`, by default a sub-directory of the working directory named
+//! after the workflow file) and deleted once the workflow is complete, unless
+//! `keep_intermediates` is true.
+//!
+//! All of the steps are validated against the parameters of their tools before any are run.
+//! A step is skipped if its output files are newer than its input files, unless a step that
+//! it depends on is run.
+
+use super::json_output::{process_error, JSON_CHILD_ENV_VAR};
+use super::validation::{find_parameter, ToolArg};
+use super::{ParameterType, ToolError, ToolErrorKind, ToolManager};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fs;
+use std::io::Error;
+use std::path;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Instant, SystemTime};
+use whitebox_common::utils::get_formatted_elapsed_time;
+
+#[derive(Deserialize)]
+struct WorkflowFile {
+    working_directory: Option<String>,
+    temp_directory: Option<String>,
+    max_procs: Option<usize>,
+    #[serde(default)]
+    keep_intermediates: bool,
+    steps: Vec<StepDefinition>,
+}
+
+#[derive(Deserialize)]
+struct StepDefinition {
+    name: Option<String>,
+    tool: String,
+    #[serde(default)]
+    args: serde_json::Map<String, Value>,
+}
+
+/// A validated workflow step, with its arguments in command-line form and the resolved
+/// paths of the files that it reads and creates.
+struct Step {
+    name: String,
+    tool: String,
+    args: Vec<String>,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum StepStatus {
+    Skipped,
+    Pending,
+    Running,
+    Complete,
+}
+
+impl ToolManager {
+    /// Runs the workflow described in a JSON file.
+    pub fn run_workflow(&self, file_name: &str) -> Result<(), Error> {
+        let start = Instant::now();
+        let workflow_file = self.resolve_file_name(file_name, &self.working_dir);
+        let contents = fs::read_to_string(&workflow_file)?;
+        let workflow: WorkflowFile = match serde_json::from_str(&contents) {
+            Ok(w) => w,
+            Err(e) => {
+                return Err(ToolError::new_error(
+                    ToolErrorKind::BadArgument,
+                    format!("Error reading the workflow file {}: {}", workflow_file, e),
+                ))
+            }
+        };
+
+        let sep = path::MAIN_SEPARATOR.to_string();
+        let mut working_dir = workflow
+            .working_directory
+            .clone()
+            .unwrap_or(self.working_dir.clone());
+        if !working_dir.is_empty() && !working_dir.ends_with(&sep) && !working_dir.ends_with('/') {
+            working_dir.push_str(&sep);
+        }
+        let mut temp_dir = match &workflow.temp_directory {
+            Some(d) => self.resolve_file_name(d, &working_dir),
+            None => {
+                let stem = Path::new(&workflow_file)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("workflow");
+                format!("{}{}_intermediates", working_dir, stem)
+            }
+        };
+        if !temp_dir.ends_with(&sep) && !temp_dir.ends_with('/') {
+            temp_dir.push_str(&sep);
+        }
+
+        let (steps, intermediates) = self.validate_workflow(&workflow, &working_dir, &temp_dir)?;
+        let num_steps = steps.len();
+
+        // Find the dependencies between the steps and the order in which they can be run.
+        let (producers, dependencies) = step_dependencies(&steps);
+        let order = topological_order(&dependencies).expect("Error ordering the workflow steps.");
+
+        let run = find_steps_to_run(&steps, &order, &producers, &intermediates);
+        let num_to_run = run.iter().filter(|r| **r).count();
+
+        if self.verbose {
+            println!(
+                "Running workflow {} ({} steps; {} up to date)...",
+                workflow_file,
+                num_steps,
+                num_steps - num_to_run
+            );
+        }
+
+        if !intermediates.is_empty() {
+            fs::create_dir_all(&temp_dir)?;
+        }
+
+        let max_parallel = match workflow.max_procs {
+            Some(n) if n > 0 => n,
+            _ => {
+                let configs = whitebox_common::configs::get_configs()?;
+                if configs.max_procs > 0 {
+                    configs.max_procs as usize
+                } else {
+                    num_cpus::get()
+                }
+            }
+        };
+
+        let exe = env::current_exe()?;
+        let mut status = run
+            .iter()
+            .map(|r| if *r { StepStatus::Pending } else { StepStatus::Skipped })
+            .collect::<Vec<StepStatus>>();
+        if self.verbose {
+            for &s in &order {
+                if status[s] == StepStatus::Skipped {
+                    println!("Step '{}' ({}) is up to date", steps[s].name, steps[s].tool);
+                }
+            }
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut num_running = 0;
+        let mut failure: Option<(usize, Error)> = None;
+        loop {
+            if failure.is_none() {
+                for &s in &order {
+                    if num_running >= max_parallel {
+                        break;
+                    }
+                    let ready = status[s] == StepStatus::Pending
+                        && dependencies[s].iter().all(|&d| {
+                            status[d] == StepStatus::Complete || status[d] == StepStatus::Skipped
+                        });
+                    if ready {
+                        if self.verbose {
+                            println!("Step '{}' ({}) started", steps[s].name, steps[s].tool);
+                        }
+                        status[s] = StepStatus::Running;
+                        num_running += 1;
+                        let mut command = Command::new(&exe);
+                        command
+                            .arg(format!("--run={}", steps[s].tool))
+                            .args(&steps[s].args);
+                        let tx = tx.clone();
+                        thread::spawn(move || {
+                            let step_start = Instant::now();
+                            tx.send((s, run_tool_process(command), step_start)).unwrap();
+                        });
+                    }
+                }
+            }
+            if num_running == 0 {
+                break;
+            }
+            let (s, result, step_start) = rx.recv().expect("Error receiving data from thread.");
+            num_running -= 1;
+            match result {
+                Ok(()) => {
+                    status[s] = StepStatus::Complete;
+                    if self.verbose {
+                        println!(
+                            "Step '{}' ({}) completed in {}",
+                            steps[s].name,
+                            steps[s].tool,
+                            get_formatted_elapsed_time(step_start)
+                        );
+                    }
+                }
+                Err(e) => {
+                    // Let the running steps finish, but do not start any more.
+                    if failure.is_none() {
+                        failure = Some((s, e));
+                    }
+                }
+            }
+        }
+
+        if let Some((s, e)) = failure {
+            return Err(ToolError::new_error(
+                ToolErrorKind::of(&e),
+                format!(
+                    "Workflow step '{}' ({}) failed: {}",
+                    steps[s].name, steps[s].tool, e
+                ),
+            ));
+        }
+
+        if !workflow.keep_intermediates && !intermediates.is_empty() {
+            for file in &intermediates {
+                remove_with_sidecar_files(file);
+            }
+            // Only succeeds if the directory is empty.
+            let _ = fs::remove_dir(&temp_dir);
+        }
+
+        if self.verbose {
+            println!(
+                "Elapsed Time (including I/O): {}",
+                get_formatted_elapsed_time(start)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks the steps of a workflow against the parameters of their tools and converts
+    /// them into command-line arguments. All of the problems found are reported together.
+    fn validate_workflow(
+        &self,
+        workflow: &WorkflowFile,
+        working_dir: &str,
+        temp_dir: &str,
+    ) -> Result<(Vec<Step>, HashSet<String>), Error> {
+        let mut problems = vec![];
+        let mut steps = vec![];
+        let mut intermediates = HashSet::new();
+        let mut names = HashSet::new();
+        // The parameters and arguments of each step, which are checked once the files created
+        // by all of the steps are known.
+        let mut step_args = vec![];
+
+        if workflow.steps.is_empty() {
+            problems.push("The workflow has no steps.".to_string());
+        }
+
+        for (i, definition) in workflow.steps.iter().enumerate() {
+            let name = definition
+                .name
+                .clone()
+                .unwrap_or(format!("step {}", i + 1));
+            if !names.insert(name.clone()) {
+                problems.push(format!("More than one step is named '{}'.", name));
+            }
+            let parameters = match self.get_parameter_list(&definition.tool) {
+                Some(p) => p,
+                None => {
+                    problems.push(format!(
+                        "Step '{}': unrecognized tool name {}.",
+                        name, definition.tool
+                    ));
+                    continue;
+                }
+            };
+
+            let mut step = Step {
+                name: name.clone(),
+                tool: definition.tool.clone(),
+                args: vec![],
+                inputs: vec![],
+                outputs: vec![],
+            };
+            let resolve = |value: &str, intermediates: &mut HashSet<String>| -> String {
+                if let Some(intermediate) = value.strip_prefix('@') {
+                    let file = format!("{}{}", temp_dir, intermediate);
+                    intermediates.insert(file.clone());
+                    file
+                } else {
+                    self.resolve_file_name(value, working_dir)
+                }
+            };
+
+            let mut tool_args = vec![];
+            for (key, value) in &definition.args {
+                let parameter = match find_parameter(&parameters, key) {
+                    Some(p) => p,
+                    None => {
+                        problems.push(format!(
+                            "Step '{}': {} has no parameter '{}'.",
+                            name, definition.tool, key
+                        ));
+                        continue;
+                    }
+                };
+                let flag = parameter.flags.last().cloned().unwrap_or(format!("--{}", key));
+                let text = match value_to_string(value) {
+                    Some(t) => t,
+                    None => {
+                        problems.push(format!(
+                            "Step '{}': the value of '{}' must be a string, number, boolean, or list.",
+                            name, key
+                        ));
+                        continue;
+                    }
+                };
+                let value = match &parameter.parameter_type {
+                    ParameterType::ExistingFile(_) => {
+                        let file = resolve(&text, &mut intermediates);
+                        step.inputs.push(file.clone());
+                        file
+                    }
+                    ParameterType::ExistingFileOrFloat(_) if text.trim().parse::<f64>().is_err() => {
+                        let file = resolve(&text, &mut intermediates);
+                        step.inputs.push(file.clone());
+                        file
+                    }
+                    ParameterType::FileList(_) => {
+                        let files = text
+                            .split([';', ','])
+                            .filter(|f| !f.trim().is_empty())
+                            .map(|f| resolve(f.trim(), &mut intermediates))
+                            .collect::<Vec<String>>();
+                        step.inputs.extend(files.iter().cloned());
+                        files.join(";")
+                    }
+                    ParameterType::NewFile(_) => {
+                        let file = resolve(&text, &mut intermediates);
+                        step.outputs.push(file.clone());
+                        file
+                    }
+                    ParameterType::Directory => self.resolve_file_name(&text, working_dir),
+                    _ => text,
+                };
+                if let ParameterType::Boolean = parameter.parameter_type {
+                    // A Boolean parameter is given as a flag without a value.
+                    if value.eq_ignore_ascii_case("true") {
+                        step.args.push(flag.clone());
+                    }
+                } else {
+                    step.args.push(format!("{}={}", flag, value));
+                }
+                tool_args.push(ToolArg {
+                    flag,
+                    value: Some(value),
+                });
+            }
+            step_args.push((parameters, tool_args));
+
+            steps.push(step);
+        }
+
+        // Each file may be created by only one step, and each input must either exist or be
+        // created by a step, which check_args is told about.
+        let mut producers: HashMap<&str, &str> = HashMap::new();
+        for step in &steps {
+            for output in &step.outputs {
+                if let Some(other) = producers.insert(output, &step.name) {
+                    problems.push(format!(
+                        "The file {} is created by both step '{}' and step '{}'.",
+                        output, other, step.name
+                    ));
+                }
+            }
+        }
+        for (step, (parameters, tool_args)) in steps.iter().zip(&step_args) {
+            // The intermediates directory is created when the workflow is run.
+            let will_exist = |f: &str| {
+                producers.contains_key(f)
+                    || intermediates.contains(f)
+                    || Path::new(f) == Path::new(temp_dir)
+            };
+            for problem in self.check_args(parameters, tool_args, working_dir, &will_exist) {
+                problems.push(format!("Step '{}': {}", step.name, problem));
+            }
+        }
+        for step in &steps {
+            for input in &step.inputs {
+                if !producers.contains_key(input.as_str()) {
+                    if intermediates.contains(input) {
+                        problems.push(format!(
+                            "Step '{}': no step creates the intermediate file {}.",
+                            step.name, input
+                        ));
+                    }
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            let (_, dependencies) = step_dependencies(&steps);
+            if let Err(cycle) = topological_order(&dependencies) {
+                let names = cycle
+                    .iter()
+                    .map(|&s| format!("'{}'", steps[s].name))
+                    .collect::<Vec<String>>();
+                problems.push(format!(
+                    "The workflow contains a cycle involving steps {}.",
+                    names.join(", ")
+                ));
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(ToolError::new_error(
+                ToolErrorKind::BadArgument,
+                format!("The workflow is invalid:\n{}", problems.join("\n")),
+            ));
+        }
+
+        Ok((steps, intermediates))
+    }
+}
+
+/// Runs a tool in a whitebox_tools subprocess, returning its error if it fails.
+pub(super) fn run_tool_process(mut command: Command) -> Result<(), Error> {
+    let output = command.env(JSON_CHILD_ENV_VAR, "1").output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr_lines = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>();
+    let (kind, message) = process_error(output.status.code(), &stderr_lines);
+    Err(ToolError::new_error(kind, message))
+}
+
+/// Decides which steps need to be run. A step is run if any of its outputs are missing or
+/// older than its inputs, or if it depends on a step that is run. Missing intermediate
+/// files are only recreated if a step that reads them is run.
+fn find_steps_to_run(
+    steps: &[Step],
+    order: &[usize],
+    producers: &HashMap<String, usize>,
+    intermediates: &HashSet<String>,
+) -> Vec<bool> {
+    let modified = |file: &str| fs::metadata(file).and_then(|m| m.modified()).ok();
+    let mut run = vec![false; steps.len()];
+    // The times at which deleted intermediate files are taken to have been created.
+    let mut virtual_times: HashMap<&str, SystemTime> = HashMap::new();
+    for &s in order {
+        let step = &steps[s];
+        let mut out_of_date = step.outputs.is_empty();
+        let mut newest_input: Option<SystemTime> = None;
+        for input in &step.inputs {
+            if let Some(&p) = producers.get(input) {
+                if run[p] {
+                    out_of_date = true;
+                }
+            }
+            match modified(input).or_else(|| virtual_times.get(input.as_str()).cloned()) {
+                Some(t) => newest_input = Some(newest_input.map_or(t, |n| n.max(t))),
+                None => out_of_date = true,
+            }
+        }
+        let mut oldest_output: Option<SystemTime> = None;
+        for output in &step.outputs {
+            match modified(output) {
+                Some(t) => oldest_output = Some(oldest_output.map_or(t, |o| o.min(t))),
+                None => {
+                    if !intermediates.contains(output) {
+                        out_of_date = true;
+                    }
+                }
+            }
+        }
+        if let (Some(i), Some(o)) = (newest_input, oldest_output) {
+            if i > o {
+                out_of_date = true;
+            }
+        }
+        run[s] = out_of_date;
+        if !out_of_date {
+            let t = newest_input
+                .or(oldest_output)
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            for output in &step.outputs {
+                if modified(output).is_none() {
+                    virtual_times.insert(output, t);
+                }
+            }
+        }
+    }
+
+    loop {
+        let mut changed = false;
+        // A step that is run needs any missing intermediate inputs to be recreated...
+        for &s in order.iter().rev() {
+            if run[s] {
+                for input in &steps[s].inputs {
+                    if let Some(&p) = producers.get(input) {
+                        if !run[p] && modified(input).is_none() {
+                            run[p] = true;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        // ...and the steps that read the outputs of a step that is run must also be run.
+        for &s in order {
+            if !run[s]
+                && steps[s]
+                    .inputs
+                    .iter()
+                    .any(|f| producers.get(f).is_some_and(|&p| run[p]))
+            {
+                run[s] = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    run
+}
+
+/// Returns the step that creates each file, and the steps that each step depends on, i.e.
+/// the steps that create the files that it reads.
+fn step_dependencies(steps: &[Step]) -> (HashMap<String, usize>, Vec<Vec<usize>>) {
+    let mut producers = HashMap::new();
+    for (s, step) in steps.iter().enumerate() {
+        for output in &step.outputs {
+            producers.insert(output.clone(), s);
+        }
+    }
+    let mut dependencies = vec![vec![]; steps.len()];
+    for (s, step) in steps.iter().enumerate() {
+        for input in &step.inputs {
+            if let Some(&p) = producers.get(input) {
+                if !dependencies[s].contains(&p) {
+                    dependencies[s].push(p);
+                }
+            }
+        }
+    }
+    (producers, dependencies)
+}
+
+/// Returns the steps in an order in which each step follows the steps that it depends on,
+/// or, if there is a cycle, the steps that could not be ordered.
+fn topological_order(dependencies: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let n = dependencies.len();
+    let mut num_dependencies = dependencies.iter().map(|d| d.len()).collect::<Vec<usize>>();
+    let mut dependents = vec![vec![]; n];
+    for (s, deps) in dependencies.iter().enumerate() {
+        for &d in deps {
+            dependents[d].push(s);
+        }
+    }
+    let mut queue = (0..n)
+        .filter(|&s| num_dependencies[s] == 0)
+        .collect::<VecDeque<usize>>();
+    let mut order = Vec::with_capacity(n);
+    while let Some(s) = queue.pop_front() {
+        order.push(s);
+        for &d in &dependents[s] {
+            num_dependencies[d] -= 1;
+            if num_dependencies[d] == 0 {
+                queue.push_back(d);
+            }
+        }
+    }
+    if order.len() < n {
+        return Err((0..n).filter(|&s| num_dependencies[s] > 0).collect());
+    }
+    Ok(order)
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Array(a) => a
+            .iter()
+            .map(value_to_string)
+            .collect::<Option<Vec<String>>>()
+            .map(|v| v.join(";")),
+        _ => None,
+    }
+}
+
+/// Removes a file along with any files that share its name but not its extension, such as
+/// the .shx and .dbf files of a Shapefile or the header file of a raster.
+fn remove_with_sidecar_files(file_name: &str) {
+    let file = Path::new(file_name);
+    let (dir, stem) = match (file.parent(), file.file_stem()) {
+        (Some(d), Some(s)) => (d, s.to_owned()),
+        _ => return,
+    };
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_file() && p.file_stem() == Some(stem.as_os_str()) {
+                let _ = fs::remove_file(p);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    fn test_dir(name: &str) -> String {
+        let dir = env::temp_dir().join(format!(
+            "whitebox_workflow_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        format!("{}{}", dir.to_string_lossy(), path::MAIN_SEPARATOR)
+    }
+
+    fn step(name: &str, inputs: &[&str], outputs: &[&str]) -> Step {
+        Step {
+            name: name.to_string(),
+            tool: "Tool".to_string(),
+            args: vec![],
+            inputs: inputs.iter().map(|f| f.to_string()).collect(),
+            outputs: outputs.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    // Creates a file that was last modified `age` seconds ago.
+    fn create_file(file_name: &str, age: u64) {
+        let file = File::create(file_name).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(age)).unwrap();
+    }
+
+    fn validate(json: &str, working_dir: &str) -> Result<(Vec<Step>, HashSet<String>), Error> {
+        let workflow: WorkflowFile = serde_json::from_str(json).unwrap();
+        let tm = ToolManager::new(working_dir, &false).unwrap();
+        tm.validate_workflow(&workflow, working_dir, &format!("{}tmp/", working_dir))
+    }
+
+    #[test]
+    fn test_dependency_ordering() {
+        // 'accum' and 'slope' read the output of 'pointer' and 'breach', which are listed
+        // after them, and 'hillshade' is independent of the others.
+        let steps = vec![
+            step("accum", &["pointer.tif"], &["accum.tif"]),
+            step("slope", &["breached.tif"], &["slope.tif"]),
+            step("pointer", &["breached.tif"], &["pointer.tif"]),
+            step("breach", &["dem.tif"], &["breached.tif"]),
+            step("hillshade", &["dem.tif"], &["hillshade.tif"]),
+            step("stats", &["accum.tif", "slope.tif", "accum.tif"], &[]),
+        ];
+        let (producers, dependencies) = step_dependencies(&steps);
+        assert_eq!(producers.get("pointer.tif"), Some(&2));
+        assert_eq!(producers.get("dem.tif"), None);
+        assert_eq!(
+            dependencies,
+            vec![vec![2], vec![3], vec![3], vec![], vec![], vec![0, 1]]
+        );
+        let order = topological_order(&dependencies).unwrap();
+        assert_eq!(order.len(), steps.len());
+        let position = |s: usize| order.iter().position(|&o| o == s).unwrap();
+        for (s, deps) in dependencies.iter().enumerate() {
+            for &d in deps {
+                assert!(position(d) < position(s), "{:?}", order);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        // 'b' and 'c' depend on each other, and 'd' depends on the cycle.
+        let dependencies = vec![vec![], vec![0, 2], vec![1], vec![2]];
+        assert_eq!(topological_order(&dependencies), Err(vec![1, 2, 3]));
+        // A step that reads its own output is a cycle.
+        assert_eq!(topological_order(&[vec![0]]), Err(vec![0]));
+        assert_eq!(topological_order(&[]), Ok(vec![]));
+
+        let wd = test_dir("cycle");
+        let err = validate(
+            r#"{ "steps": [
+                { "name": "a", "tool": "D8Pointer", "args": { "dem": "@b.tif", "output": "@a.tif" } },
+                { "name": "b", "tool": "D8Pointer", "args": { "dem": "@a.tif", "output": "@b.tif" } },
+                { "name": "c", "tool": "D8Pointer", "args": { "dem": "@b.tif", "output": "c.tif" } }
+            ] }"#,
+            &wd,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(ToolErrorKind::of(&err), ToolErrorKind::BadArgument);
+        assert!(
+            err.to_string().contains("cycle involving steps 'a', 'b', 'c'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let wd = test_dir("validation");
+        create_file(&format!("{}dem.tif", wd), 0);
+        let (steps, intermediates) = validate(
+            r#"{ "steps": [
+                { "name": "breach", "tool": "BreachDepressionsLeastCost",
+                  "args": { "dem": "dem.tif", "output": "@breached.tif", "dist": 20, "fill": true } },
+                { "tool": "D8Pointer", "args": { "dem": "@breached.tif", "output": "pointer.tif" } }
+            ] }"#,
+            &wd,
+        )
+        .unwrap();
+        assert_eq!(steps[1].name, "step 2");
+        assert_eq!(steps[0].inputs, vec![format!("{}dem.tif", wd)]);
+        assert_eq!(steps[1].inputs, vec![format!("{}tmp/breached.tif", wd)]);
+        assert_eq!(steps[1].outputs, vec![format!("{}pointer.tif", wd)]);
+        assert!(steps[0].args.contains(&"--fill".to_string()));
+        assert!(steps[0].args.contains(&"--dist=20".to_string()));
+        assert_eq!(
+            intermediates,
+            HashSet::from([format!("{}tmp/breached.tif", wd)])
+        );
+
+        let err = validate(
+            r#"{ "steps": [
+                { "name": "a", "tool": "NoSuchTool" },
+                { "name": "b", "tool": "D8Pointer", "args": { "dem": "@missing.tif", "output": "x.tif" } },
+                { "name": "b", "tool": "Slope", "args": { "dem": "dem.tif", "output": "x.tif", "zfactor": {} } }
+            ] }"#,
+            &wd,
+        )
+        .err()
+        .unwrap();
+        let message = err.to_string();
+        for problem in [
+            "unrecognized tool name NoSuchTool",
+            "More than one step is named 'b'",
+            "no step creates the intermediate file",
+            "is created by both step 'b' and step 'b'",
+            "the value of 'zfactor' must be",
+        ] {
+            assert!(message.contains(problem), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_out_of_date_steps_are_run() {
+        let wd = test_dir("out_of_date");
+        let file = |name: &str| format!("{}{}", wd, name);
+        let steps = vec![
+            step("breach", &[&file("dem.tif")], &[&file("breached.tif")]),
+            step("pointer", &[&file("breached.tif")], &[&file("pointer.tif")]),
+            step("slope", &[&file("dem.tif")], &[&file("slope.tif")]),
+        ];
+        let (producers, dependencies) = step_dependencies(&steps);
+        let order = topological_order(&dependencies).unwrap();
+        let no_intermediates = HashSet::new();
+
+        create_file(&file("dem.tif"), 300);
+        create_file(&file("breached.tif"), 200);
+        create_file(&file("pointer.tif"), 100);
+        create_file(&file("slope.tif"), 100);
+        assert_eq!(
+            find_steps_to_run(&steps, &order, &producers, &no_intermediates),
+            vec![false, false, false]
+        );
+
+        // A step whose input is newer than its output is run, along with the steps that
+        // depend on it.
+        create_file(&file("breached.tif"), 400);
+        assert_eq!(
+            find_steps_to_run(&steps, &order, &producers, &no_intermediates),
+            vec![true, true, false]
+        );
+        create_file(&file("dem.tif"), 0);
+        assert_eq!(
+            find_steps_to_run(&steps, &order, &producers, &no_intermediates),
+            vec![true, true, true]
+        );
+
+        // A missing output is recreated.
+        create_file(&file("dem.tif"), 300);
+        create_file(&file("breached.tif"), 200);
+        fs::remove_file(file("slope.tif")).unwrap();
+        assert_eq!(
+            find_steps_to_run(&steps, &order, &producers, &no_intermediates),
+            vec![false, false, true]
+        );
+
+        // A deleted intermediate file is only recreated if a step that reads it is run.
+        create_file(&file("slope.tif"), 100);
+        fs::remove_file(file("breached.tif")).unwrap();
+        let intermediates = HashSet::from([file("breached.tif")]);
+        assert_eq!(
+            find_steps_to_run(&steps, &order, &producers, &intermediates),
+            vec![false, false, false]
+        );
+        fs::remove_file(file("pointer.tif")).unwrap();
+        assert_eq!(
+            find_steps_to_run(&steps, &order, &producers, &intermediates),
+            vec![true, true, false]
+        );
+    }
+}
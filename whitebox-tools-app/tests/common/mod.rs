@@ -0,0 +1,1066 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Shared code for the tool tests. Each test creates a directory of small synthetic data sets
+//! (a DEM, a class raster, a D8 pointer and a streams raster derived from the DEM, point, line,
+//! and polygon Shapefiles, a second polygon Shapefile for the overlay tools, and a LAS file)
+//! and runs tools
+//! on them through `ToolManager::run_tool`, with arguments that are derived from each tool's
+//! parameter description.
+
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::panic;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::structures::Point2D;
+use whitebox_lidar::{LasFile, LasHeader, LidarPointRecord, PointData, Vlr};
+use whitebox_raster::{DataType, PhotometricInterpretation, Raster, RasterConfigs};
+use whitebox_tools::tools::ToolManager;
+use whitebox_vector::{
+    AttributeField, FieldData, FieldDataType, ShapeType, Shapefile, ShapefileGeometry,
+};
+
+pub const ROWS: usize = 32;
+pub const COLUMNS: usize = 32;
+pub const RESOLUTION: f64 = 10.0;
+pub const WEST: f64 = 500_000.0;
+pub const NORTH: f64 = 4_800_000.0;
+pub const NODATA: f64 = -32768.0;
+pub const EPSG: u16 = 32617;
+
+/// A tool is abandoned if it has not finished after this long. Every tool finishes within a
+/// few seconds on the synthetic data, even in a debug build.
+const TOOL_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub const DEM: &str = "dem.tif";
+pub const CLASSES: &str = "classes.tif";
+pub const POINTER: &str = "pointer.tif";
+pub const STREAMS: &str = "streams.tif";
+pub const POINTS: &str = "points.shp";
+pub const LINES: &str = "lines.shp";
+pub const POLYGONS: &str = "polygons.shp";
+pub const OVERLAY: &str = "overlay.shp";
+pub const LIDAR: &str = "points.las";
+
+/// A directory containing the synthetic input data sets.
+pub struct TestData {
+    pub dir: PathBuf,
+}
+
+impl TestData {
+    /// Creates the synthetic data sets in a new directory. Each test uses its own directory,
+    /// since the tests of a file are run in parallel.
+    pub fn new(test_name: &str) -> TestData {
+        let dir = std::env::temp_dir().join(format!(
+            "whitebox_tools_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Error creating the test directory.");
+        let data = TestData { dir };
+        data.write_dem();
+        data.write_classes();
+        data.write_flow_rasters();
+        data.write_points();
+        data.write_lines();
+        data.write_polygons();
+        data.write_overlay();
+        data.write_lidar();
+        data
+    }
+
+    pub fn path(&self, file_name: &str) -> String {
+        self.dir.join(file_name).to_string_lossy().to_string()
+    }
+
+    /// Returns the working directory, with a trailing separator, as the tools expect it.
+    pub fn working_dir(&self) -> String {
+        format!("{}{}", self.dir.to_string_lossy(), MAIN_SEPARATOR)
+    }
+
+    /// Runs a tool in the data directory, returning an error if the tool fails, panics, or
+    /// does not finish in time. Tools are run quietly, since verbose tools open their HTML
+    /// reports in a browser.
+    pub fn run_tool(&self, tool_name: &str, args: &[String]) -> Result<(), Error> {
+        let working_dir = self.working_dir();
+        let tool_name = tool_name.to_string();
+        let args = args.to_vec();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(|| {
+                let tm = ToolManager::new(&working_dir, &false)?;
+                tm.run_tool(tool_name, args)
+            });
+            let result = match result {
+                Ok(r) => r,
+                Err(e) => Err(Error::other(panic_message(e))),
+            };
+            let _ = tx.send(result);
+        });
+        match rx.recv_timeout(TOOL_TIMEOUT) {
+            Ok(result) => result,
+            Err(_) => Err(Error::new(ErrorKind::TimedOut, "The tool did not finish.")),
+        }
+    }
+
+    /// Runs a tool, as `run_tool` does, and returns the names of the raster, vector, and LiDAR
+    /// files that it created or modified, sorted. Tools name some of their outputs after their
+    /// inputs, rather than after their output parameters, and some modify their inputs.
+    pub fn run_tool_for_outputs(
+        &self,
+        tool_name: &str,
+        args: &[String],
+    ) -> Result<Vec<String>, Error> {
+        let before = self.snapshot();
+        self.run_tool(tool_name, args)?;
+        let mut outputs = self
+            .snapshot()
+            .into_iter()
+            .filter(|(name, state)| before.get(name) != Some(state))
+            .map(|(name, _)| name)
+            .filter(|name| is_data_file(name))
+            .collect::<Vec<String>>();
+        outputs.sort();
+        Ok(outputs)
+    }
+
+    /// Returns the size and modification time of each file in the directory.
+    fn snapshot(&self) -> HashMap<String, (u64, Option<SystemTime>)> {
+        let mut files = HashMap::new();
+        for entry in fs::read_dir(&self.dir).into_iter().flatten().flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                files.insert(
+                    entry.file_name().to_string_lossy().to_string(),
+                    (metadata.len(), metadata.modified().ok()),
+                );
+            }
+        }
+        files
+    }
+
+    /// The DEM is a valley between two hills, with a pit, a flat area, and a block of NoData
+    /// cells in its north-east corner.
+    fn write_dem(&self) {
+        let mut raster = new_raster(
+            &self.path(DEM),
+            DataType::F32,
+            PhotometricInterpretation::Continuous,
+        );
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                raster.set_value(row as isize, col as isize, dem_value(row, col));
+            }
+        }
+        raster.write().expect("Error writing the synthetic DEM.");
+    }
+
+    /// The class raster divides the grid into four classes, with the same NoData cells as
+    /// the DEM.
+    fn write_classes(&self) {
+        let mut raster = new_raster(
+            &self.path(CLASSES),
+            DataType::I16,
+            PhotometricInterpretation::Categorical,
+        );
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                let value = if is_nodata_cell(row, col) {
+                    NODATA
+                } else {
+                    (1 + 2 * (row * 2 / ROWS) + col * 2 / COLUMNS) as f64
+                };
+                raster.set_value(row as isize, col as isize, value);
+            }
+        }
+        raster
+            .write()
+            .expect("Error writing the synthetic class raster.");
+    }
+
+    /// Writes a D8 pointer, with the values used by the D8Pointer tool, and a streams raster,
+    /// i.e. the cells with a D8 flow accumulation of at least 8 cells, for the tools that
+    /// require them.
+    fn write_flow_rasters(&self) {
+        const DX: [isize; 8] = [1, 1, 1, 0, -1, -1, -1, 0];
+        const DY: [isize; 8] = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let is_valid = |row: isize, col: isize| {
+            row >= 0
+                && col >= 0
+                && (row as usize) < ROWS
+                && (col as usize) < COLUMNS
+                && !is_nodata_cell(row as usize, col as usize)
+        };
+        let mut flow_dir = vec![vec![None; COLUMNS]; ROWS];
+        for row in 0..ROWS as isize {
+            for col in 0..COLUMNS as isize {
+                if !is_valid(row, col) {
+                    continue;
+                }
+                let z = dem_value(row as usize, col as usize);
+                let mut max_slope = 0f64;
+                for n in 0..8 {
+                    let (r, c) = (row + DY[n], col + DX[n]);
+                    if !is_valid(r, c) {
+                        continue;
+                    }
+                    let distance = if n % 2 == 0 { 2f64.sqrt() } else { 1.0 };
+                    let slope = (z - dem_value(r as usize, c as usize)) / distance;
+                    if slope > max_slope {
+                        max_slope = slope;
+                        flow_dir[row as usize][col as usize] = Some(n);
+                    }
+                }
+            }
+        }
+
+        let mut accumulation = vec![vec![0usize; COLUMNS]; ROWS];
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                if !is_valid(row as isize, col as isize) {
+                    continue;
+                }
+                let (mut r, mut c) = (row, col);
+                accumulation[r][c] += 1;
+                while let Some(n) = flow_dir[r][c] {
+                    r = (r as isize + DY[n]) as usize;
+                    c = (c as isize + DX[n]) as usize;
+                    accumulation[r][c] += 1;
+                }
+            }
+        }
+
+        let mut pointer = new_raster(
+            &self.path(POINTER),
+            DataType::I16,
+            PhotometricInterpretation::Categorical,
+        );
+        let mut streams = new_raster(
+            &self.path(STREAMS),
+            DataType::I16,
+            PhotometricInterpretation::Categorical,
+        );
+        for row in 0..ROWS {
+            for col in 0..COLUMNS {
+                let (p, s) = if !is_valid(row as isize, col as isize) {
+                    (NODATA, NODATA)
+                } else {
+                    (
+                        flow_dir[row][col].map_or(0.0, |n| (1 << n) as f64),
+                        if accumulation[row][col] >= 8 {
+                            1.0
+                        } else {
+                            0.0
+                        },
+                    )
+                };
+                pointer.set_value(row as isize, col as isize, p);
+                streams.set_value(row as isize, col as isize, s);
+            }
+        }
+        pointer
+            .write()
+            .expect("Error writing the synthetic pointer.");
+        streams
+            .write()
+            .expect("Error writing the synthetic streams.");
+    }
+
+    fn write_points(&self) {
+        let mut output = Shapefile::new(&self.path(POINTS), ShapeType::Point)
+            .expect("Error creating the synthetic points.");
+        output.projection = esri_wkt_from_epsg(EPSG);
+        add_standard_fields(&mut output);
+        let mut rng = Lcg::new(1);
+        for i in 0..40 {
+            let (x, y) = (
+                WEST + 5.0 + rng.next() * (COLUMNS as f64 * RESOLUTION - 10.0),
+                NORTH - 5.0 - rng.next() * (ROWS as f64 * RESOLUTION - 10.0),
+            );
+            output.add_point_record(x, y);
+            let (row, col) = cell_of(x, y);
+            let value = dem_value(row.min(ROWS - 1), col.min(COLUMNS - 1));
+            add_standard_record(&mut output, i, if value == NODATA { 50.0 } else { value });
+        }
+        output.write().expect("Error writing the synthetic points.");
+    }
+
+    fn write_lines(&self) {
+        let mut output = Shapefile::new(&self.path(LINES), ShapeType::PolyLine)
+            .expect("Error creating the synthetic lines.");
+        output.projection = esri_wkt_from_epsg(EPSG);
+        add_standard_fields(&mut output);
+        let lines = [
+            vec![(12.0, 300.0), (80.0, 240.0), (160.0, 215.0), (300.0, 180.0)],
+            vec![(160.0, 310.0), (165.0, 160.0), (150.0, 20.0)],
+            vec![(20.0, 40.0), (120.0, 90.0), (250.0, 60.0), (290.0, 110.0)],
+            // Closes the area between the other lines, for the Polygonize tool.
+            vec![(300.0, 180.0), (290.0, 110.0)],
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let mut geometry = ShapefileGeometry::new(ShapeType::PolyLine);
+            geometry.add_part(&to_points(line));
+            output.add_record(geometry);
+            add_standard_record(&mut output, i, 10.0 * (i + 1) as f64);
+        }
+        output.write().expect("Error writing the synthetic lines.");
+    }
+
+    /// The polygons are a square with a hole, a triangle, and an L-shaped polygon, which
+    /// overlaps the square.
+    fn write_polygons(&self) {
+        let mut output = Shapefile::new(&self.path(POLYGONS), ShapeType::Polygon)
+            .expect("Error creating the synthetic polygons.");
+        output.projection = esri_wkt_from_epsg(EPSG);
+        add_standard_fields(&mut output);
+        let polygons = [
+            vec![
+                // Rings are clockwise and holes are counter-clockwise, as in a Shapefile.
+                vec![
+                    (20.0, 300.0),
+                    (140.0, 300.0),
+                    (140.0, 180.0),
+                    (20.0, 180.0),
+                    (20.0, 300.0),
+                ],
+                vec![
+                    (60.0, 260.0),
+                    (60.0, 220.0),
+                    (100.0, 220.0),
+                    (100.0, 260.0),
+                    (60.0, 260.0),
+                ],
+            ],
+            vec![vec![
+                (180.0, 290.0),
+                (300.0, 170.0),
+                (180.0, 170.0),
+                (180.0, 290.0),
+            ]],
+            vec![vec![
+                (110.0, 150.0),
+                (110.0, 20.0),
+                (15.0, 20.0),
+                (15.0, 70.0),
+                (60.0, 70.0),
+                (60.0, 200.0),
+                (110.0, 150.0),
+            ]],
+        ];
+        for (i, rings) in polygons.iter().enumerate() {
+            let mut geometry = ShapefileGeometry::new(ShapeType::Polygon);
+            for ring in rings {
+                geometry.add_part(&to_points(ring));
+            }
+            output.add_record(geometry);
+            add_standard_record(&mut output, i, 100.0 * (i + 1) as f64);
+        }
+        output
+            .write()
+            .expect("Error writing the synthetic polygons.");
+    }
+
+    /// The overlay is a rectangle that overlaps each of the polygons in part.
+    fn write_overlay(&self) {
+        let mut output = Shapefile::new(&self.path(OVERLAY), ShapeType::Polygon)
+            .expect("Error creating the synthetic overlay.");
+        output.projection = esri_wkt_from_epsg(EPSG);
+        add_standard_fields(&mut output);
+        let mut geometry = ShapefileGeometry::new(ShapeType::Polygon);
+        geometry.add_part(&to_points(&[
+            (90.0, 240.0),
+            (240.0, 240.0),
+            (240.0, 60.0),
+            (90.0, 60.0),
+            (90.0, 240.0),
+        ]));
+        output.add_record(geometry);
+        add_standard_record(&mut output, 0, 1.0);
+        output
+            .write()
+            .expect("Error writing the synthetic overlay.");
+    }
+
+    /// The point cloud samples the DEM surface, as ground points, with a few vegetation
+    /// points above it.
+    fn write_lidar(&self) {
+        let mut output =
+            LasFile::new(&self.path(LIDAR), "w").expect("Error creating the LAS file.");
+        let header = LasHeader {
+            point_format: 0,
+            x_scale_factor: 0.001,
+            y_scale_factor: 0.001,
+            z_scale_factor: 0.001,
+            x_offset: WEST,
+            y_offset: NORTH - ROWS as f64 * RESOLUTION,
+            z_offset: 0.0,
+            project_id_used: true,
+            ..Default::default()
+        };
+        output.add_header(header);
+        let wkt = esri_wkt_from_epsg(EPSG);
+        let mut vlr: Vlr = Default::default();
+        vlr.user_id = String::from("LASF_Projection");
+        vlr.record_id = 2112u16;
+        vlr.description = String::from("OGC WKT Coordinate System");
+        vlr.binary_data = format!("{}\0", wkt).as_bytes().to_vec();
+        vlr.record_length_after_header = vlr.binary_data.len() as u16;
+        output.add_vlr(vlr);
+        output.wkt = wkt;
+        let mut rng = Lcg::new(2);
+        for i in 0..1500 {
+            let x = WEST + rng.next() * COLUMNS as f64 * RESOLUTION;
+            let y = NORTH - rng.next() * ROWS as f64 * RESOLUTION;
+            let (row, col) = cell_of(x, y);
+            let (row, col) = (row.min(ROWS - 1), col.min(COLUMNS - 1));
+            if is_nodata_cell(row, col) {
+                continue;
+            }
+            let vegetation = i % 7 == 0;
+            let z = dem_value(row, col)
+                + if vegetation {
+                    4.0 + 8.0 * rng.next()
+                } else {
+                    0.0
+                };
+            let mut point_data = PointData {
+                x: ((x - WEST) / 0.001) as i32,
+                y: ((y - (NORTH - ROWS as f64 * RESOLUTION)) / 0.001) as i32,
+                z: (z / 0.001) as i32,
+                intensity: 100 + (i % 50) as u16,
+                ..Default::default()
+            };
+            point_data.set_classification(if vegetation { 5 } else { 2 });
+            point_data.set_number_of_returns(if vegetation { 2 } else { 1 });
+            point_data.set_return_number(1);
+            output.add_point_record(LidarPointRecord::PointRecord0 { point_data });
+        }
+        output.write().expect("Error writing the LAS file.");
+    }
+}
+
+impl Drop for TestData {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn new_raster(
+    file_name: &str,
+    data_type: DataType,
+    photometric_interp: PhotometricInterpretation,
+) -> Raster {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: NODATA,
+        north: NORTH,
+        south: NORTH - ROWS as f64 * RESOLUTION,
+        west: WEST,
+        east: WEST + COLUMNS as f64 * RESOLUTION,
+        resolution_x: RESOLUTION,
+        resolution_y: RESOLUTION,
+        data_type,
+        photometric_interp,
+        epsg_code: EPSG,
+        coordinate_ref_system_wkt: esri_wkt_from_epsg(EPSG),
+        projection: esri_wkt_from_epsg(EPSG),
+        ..Default::default()
+    };
+    Raster::initialize_using_config(file_name, &configs)
+}
+
+/// The cells in the north-east corner of the grid are NoData in the synthetic rasters.
+pub fn is_nodata_cell(row: usize, col: usize) -> bool {
+    row < 4 && col >= COLUMNS - 5
+}
+
+fn dem_value(row: usize, col: usize) -> f64 {
+    if is_nodata_cell(row, col) {
+        return NODATA;
+    }
+    let (r, c) = (row as f64, col as f64);
+    // A pit, which the hydrological tools fill or breach.
+    if (row, col) == (20, 10) {
+        return 60.0;
+    }
+    // A flat area.
+    if (24..28).contains(&row) && (22..27).contains(&col) {
+        return 70.0;
+    }
+    let valley = 6.0 * ((c - 16.0) / 4.0).powi(2).min(9.0);
+    let hills = 12.0 * (r / 5.0).sin() * (c / 7.0).cos();
+    (100.0 - 0.8 * r + valley + hills).round_to(0.01)
+}
+
+trait RoundTo {
+    fn round_to(self, precision: f64) -> f64;
+}
+
+impl RoundTo for f64 {
+    fn round_to(self, precision: f64) -> f64 {
+        (self / precision).round() * precision
+    }
+}
+
+fn cell_of(x: f64, y: f64) -> (usize, usize) {
+    (
+        ((NORTH - y) / RESOLUTION) as usize,
+        ((x - WEST) / RESOLUTION) as usize,
+    )
+}
+
+/// Converts coordinates relative to the north-west corner of the grid, with y increasing
+/// northwards from the southern edge, into map coordinates.
+fn to_points(coords: &[(f64, f64)]) -> Vec<Point2D> {
+    coords
+        .iter()
+        .map(|(x, y)| Point2D::new(WEST + x, NORTH - ROWS as f64 * RESOLUTION + y))
+        .collect()
+}
+
+/// Every synthetic vector has an integer FID and CLASS, a real VALUE, and a text NAME.
+fn add_standard_fields(output: &mut Shapefile) {
+    output
+        .attributes
+        .add_field(&AttributeField::new("FID", FieldDataType::Int, 6u8, 0u8));
+    output.attributes.add_field(&AttributeField::new(
+        "VALUE",
+        FieldDataType::Real,
+        12u8,
+        4u8,
+    ));
+    output
+        .attributes
+        .add_field(&AttributeField::new("CLASS", FieldDataType::Int, 4u8, 0u8));
+    output
+        .attributes
+        .add_field(&AttributeField::new("NAME", FieldDataType::Text, 10u8, 0u8));
+}
+
+fn add_standard_record(output: &mut Shapefile, i: usize, value: f64) {
+    output.attributes.add_record(
+        vec![
+            FieldData::Int(i as i32 + 1),
+            FieldData::Real(value),
+            FieldData::Int((i % 3) as i32 + 1),
+            FieldData::Text(format!("feature{}", i + 1)),
+        ],
+        false,
+    );
+}
+
+/// A small linear congruential generator, so that the synthetic data are the same on every
+/// platform and with every version of the rand crate.
+struct Lcg(u64);
+
+impl Lcg {
+    fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    /// Returns a value in [0, 1).
+    fn next(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn panic_message(e: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        format!("The tool panicked: {}", s)
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        format!("The tool panicked: {}", s)
+    } else {
+        "The tool panicked.".to_string()
+    }
+}
+
+/// Arguments that are added to the derived arguments of a tool, or that replace them, for
+/// tools that require a free-form string, an input that the parameter types do not
+/// distinguish, or an optional parameter that has no usable default.
+const EXTRA_ARGS: &[(&str, &[&str])] = &[
+    (
+        "AttributeCorrelationNeighbourhoodAnalysis",
+        &["--input=points.shp"],
+    ),
+    ("ContoursFromPoints", &["--field=VALUE"]),
+    (
+        "CreateColourComposite",
+        &["--green=classes.tif", "--blue=pointer.tif"],
+    ),
+    ("CreateHexagonalVectorGrid", &["--width=50.0"]),
+    (
+        "CreateRectangularVectorGrid",
+        &["--width=50.0", "--height=50.0"],
+    ),
+    ("DeviationFromRegionalDirection", &["--elong_threshold=0.1"]),
+    ("FilterLidarScanAngles", &["--threshold=10"]),
+    ("IdwInterpolation", &["--radius=100.0"]),
+    ("ImageRegression", &["--input2=classes.tif"]),
+    ("Isobasins", &["--size=50"]),
+    ("LasToZlidar", &["--inputs=points.las"]),
+    ("LidarClassifySubset", &["--subset_class=6"]),
+    ("LidarHexBinning", &["--width=50.0"]),
+    ("MergeVectors", &["--inputs=polygons.shp;polygons.shp"]),
+    ("NaturalNeighbourInterpolation", &["--field=VALUE"]),
+    (
+        "NearFeature",
+        &["--input=points.shp", "--near=lines.shp", "--k=3"],
+    ),
+    (
+        "PrincipalComponentAnalysis",
+        &["--inputs=dem.tif;classes.tif;pointer.tif"],
+    ),
+    ("Reclass", &["--reclass_vals=1;0;90;2;90;200"]),
+    ("ReprojectLidar", &["--epsg=4326"]),
+    ("ReprojectRaster", &["--epsg=32618"]),
+    ("ReprojectVector", &["--epsg=4326"]),
+    (
+        "RgbToIhs",
+        &["--red=dem.tif", "--green=classes.tif", "--blue=pointer.tif"],
+    ),
+    (
+        "SelectByAttribute",
+        &["--statement=CLASS IN (2, 3) AND NAME LIKE 'feature%' AND VALUE < 300"],
+    ),
+    (
+        "SelectByLocation",
+        &["--select=points.shp", "--relation=contains"],
+    ),
+    (
+        "SpatialJoin",
+        &["--join=points.shp", "--predicate=contains", "--aggregate=sum"],
+    ),
+    ("TINGridding", &["--field=VALUE"]),
+    ("VectorHexBinning", &["--width=50.0"]),
+    (
+        "WeightedOverlay",
+        &["--weights=0.6;0.4", "--cost=false;false"],
+    ),
+    ("WeightedSum", &["--weights=0.6;0.4"]),
+];
+
+/// Tools that are not run by the tests, and why.
+pub const UNTESTED: &[(&str, &str)] = &[
+    ("AsciiToLas", "requires an ASCII point file"),
+    (
+        "ClassifyOverlapPoints",
+        "requires a LAS file with GPS times",
+    ),
+    ("CsvPointsToVector", "requires a CSV file"),
+    ("HistogramMatching", "requires a histogram file"),
+    ("Isobasins", "overflows in debug builds"),
+    (
+        "KMeansClustering",
+        "overflows on NoData cells in debug builds",
+    ),
+    ("LidarRooftopAnalysis", "overflows in debug builds"),
+    ("MergeTableWithCsv", "requires a CSV file"),
+    (
+        "ModifiedKMeansClustering",
+        "overflows on NoData cells in debug builds",
+    ),
+    ("PanchromaticSharpening", "requires a multi-spectral image"),
+    ("ReclassFromFile", "requires a reclass file"),
+    (
+        "SelectTilesByPolygon",
+        "requires a directory of LiDAR tiles",
+    ),
+    ("SinglePartToMultiPart", "panics on polygons with holes"),
+    ("TurningBandsSimulation", "does not always finish"),
+    ("UserDefinedWeightsFilter", "requires a weights file"),
+    ("ZlidarToLas", "requires a zLidar file"),
+];
+
+/// An output file of a tool, i.e. the value of one of its NewFile parameters.
+pub struct Output {
+    pub flag: String,
+    pub file_type: String,
+    pub file_name: String,
+}
+
+/// The arguments with which a tool is run on the synthetic data, and its output files.
+pub struct Invocation {
+    pub args: Vec<String>,
+    pub outputs: Vec<Output>,
+    /// The input files, i.e. the values of the ExistingFile and FileList parameters.
+    pub inputs: Vec<String>,
+}
+
+impl Invocation {
+    /// Returns true if one of the inputs of the tool is a synthetic raster, which are all on
+    /// the same grid.
+    pub fn has_raster_input(&self) -> bool {
+        self.inputs
+            .iter()
+            .flat_map(|i| i.split(';'))
+            .any(|f| [DEM, CLASSES, POINTER, STREAMS].contains(&f))
+    }
+}
+
+/// Derives the arguments with which a tool is run on the synthetic data from its parameter
+/// description (the JSON printed by --toolparameters), in the way that the front ends do:
+/// parameters with a default value are given it, and required parameters are given a value
+/// that suits their type. LiDAR inputs and outputs are always given, since tools that have
+/// optional ones otherwise process every file in the working directory, and LiDAR and
+/// gridding tools are given the resolution of the synthetic rasters. Returns an error if a
+/// required parameter cannot be given a value, e.g. a free-form string without an entry in
+/// EXTRA_ARGS.
+pub fn derive_invocation(tool_name: &str, parameters_json: &str) -> Result<Invocation, String> {
+    let parameters: Value = serde_json::from_str(parameters_json)
+        .map_err(|e| format!("Error parsing the parameters of {}: {}", tool_name, e))?;
+    let extra_args = EXTRA_ARGS
+        .iter()
+        .find(|(t, _)| *t == tool_name)
+        .map_or(&[][..], |(_, a)| *a);
+    let mut invocation = Invocation {
+        args: vec![],
+        outputs: vec![],
+        inputs: vec![],
+    };
+    for parameter in parameters["parameters"].as_array().into_iter().flatten() {
+        let flag = parameter["flags"]
+            .as_array()
+            .and_then(|f| f.iter().filter_map(|f| f.as_str()).next_back())
+            .unwrap_or_default()
+            .to_string();
+        let default_value = parameter["default_value"].as_str();
+        let required = !parameter["optional"].as_bool().unwrap_or(false) && default_value.is_none();
+        let (kind, detail) = match &parameter["parameter_type"] {
+            Value::String(s) => (s.as_str(), &Value::Null),
+            Value::Object(o) => match o.iter().next() {
+                Some((k, v)) => (k.as_str(), v),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let extra_arg = extra_args
+            .iter()
+            .find(|a| a.split('=').next() == Some(flag.as_str()));
+        let value = match kind {
+            _ if extra_arg.is_some() => {
+                let value = extra_arg.and_then(|a| a.split_once('=')).map(|(_, v)| v);
+                if kind.contains("File") {
+                    invocation.inputs.extend(value.map(|v| v.to_string()));
+                }
+                value.map(|v| v.to_string())
+            }
+            "Boolean" => {
+                if default_value.is_some_and(|v| v.eq_ignore_ascii_case("true")) {
+                    invocation.args.push(flag.clone());
+                }
+                None
+            }
+            "ExistingFile" | "ExistingFileOrFloat" | "FileList" => {
+                let file = match input_file(&flag, detail) {
+                    Some(f) if required || f == LIDAR => f,
+                    None if required => {
+                        return Err(format!("{} has no synthetic input file", flag));
+                    }
+                    _ => continue,
+                };
+                let file = match (kind, file) {
+                    // Tools that take a list of rasters generally require at least two.
+                    ("FileList", DEM) => format!("{};{}", DEM, CLASSES),
+                    _ => file.to_string(),
+                };
+                invocation.inputs.push(file.clone());
+                Some(file)
+            }
+            "NewFile" => {
+                let (file_type, extension) = match output_type(detail) {
+                    Some(t) => t,
+                    None if required => return Err(format!("{} has an unknown file type", flag)),
+                    None => continue,
+                };
+                let file_name = format!("{}.{}", flag.trim_start_matches('-'), extension);
+                invocation.outputs.push(Output {
+                    flag: flag.clone(),
+                    file_type: file_type.to_string(),
+                    file_name: file_name.clone(),
+                });
+                Some(file_name)
+            }
+            "Float" if ["--resolution", "--cell_size", "--grid_res"].contains(&flag.as_str()) => {
+                Some(RESOLUTION.to_string())
+            }
+            _ if default_value.is_some() => default_value.map(|v| v.to_string()),
+            "VectorAttributeField" if required => {
+                let field = match detail.get(0).and_then(|d| d.as_str()) {
+                    Some("Text") => "NAME",
+                    Some("Integer") => "CLASS",
+                    _ => "VALUE",
+                };
+                Some(field.to_string())
+            }
+            "OptionList" if required => detail
+                .get(0)
+                .and_then(|o| o.as_str())
+                .map(|o| o.to_string()),
+            "Integer" if required => Some("3".to_string()),
+            "Float" if required => Some("1.0".to_string()),
+            _ if required => return Err(format!("{} requires a value", flag)),
+            _ => None,
+        };
+        if let Some(value) = value {
+            invocation.args.push(format!("{}={}", flag, value));
+        }
+    }
+    for arg in extra_args {
+        let flag = arg.split('=').next().unwrap_or_default();
+        if !invocation
+            .args
+            .iter()
+            .any(|a| a.split('=').next() == Some(flag))
+        {
+            invocation.args.push(arg.to_string());
+        }
+    }
+    Ok(invocation)
+}
+
+/// The synthetic data set used for an input file, based on its type and flag.
+fn input_file(flag: &str, file_type: &Value) -> Option<&'static str> {
+    let is_overlay = ["--overlay", "--clip", "--erase"].contains(&flag);
+    let vector_file = |geometry: &Value| match geometry.as_str() {
+        Some("Point") => POINTS,
+        Some("Line") => LINES,
+        _ if is_overlay => OVERLAY,
+        _ => POLYGONS,
+    };
+    match file_type {
+        Value::String(s) => match s.as_str() {
+            "Raster"
+                if ["pntr", "pointer", "backlink"]
+                    .iter()
+                    .any(|f| flag.contains(f)) =>
+            {
+                Some(POINTER)
+            }
+            "Raster" if flag.contains("streams") => Some(STREAMS),
+            "Raster" => Some(DEM),
+            "Lidar" => Some(LIDAR),
+            _ => None,
+        },
+        Value::Object(o) => match o.iter().next()? {
+            (k, g) if k == "Vector" || k == "RasterAndVector" => Some(vector_file(g)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The type of an output file, i.e. raster, vector, or lidar, and its file extension.
+fn output_type(file_type: &Value) -> Option<(&'static str, &'static str)> {
+    match file_type {
+        Value::String(s) => match s.as_str() {
+            "Raster" => Some(("raster", "tif")),
+            "Lidar" => Some(("lidar", "las")),
+            "Html" => Some(("html", "html")),
+            "Csv" => Some(("csv", "csv")),
+            "Text" => Some(("text", "txt")),
+            _ => None,
+        },
+        Value::Object(o) => match o.iter().next()? {
+            (k, _) if k == "Vector" || k == "RasterAndVector" => Some(("vector", "shp")),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns true for the main file of a raster, vector, or LiDAR data set. Other outputs, such
+/// as HTML reports, contain file paths and times, and are not compared.
+pub fn is_data_file(file_name: &str) -> bool {
+    let file_name = file_name.to_lowercase();
+    [".tif", ".shp", ".las"]
+        .iter()
+        .any(|e| file_name.ends_with(e))
+}
+
+/// The files that make up a data set, e.g. the .shp, .shx, .dbf, and .prj files of a
+/// Shapefile.
+pub fn data_set_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![path.to_path_buf()];
+    if path.extension().is_some_and(|e| e == "shp") {
+        for extension in ["shx", "dbf", "prj"] {
+            let file = path.with_extension(extension);
+            if file.exists() {
+                files.push(file);
+            }
+        }
+    }
+    files
+}
+
+/// Two values are equal if they differ by no more than an absolute tolerance, for values
+/// near zero, or a relative tolerance, for larger values.
+pub fn approx_eq(a: f64, b: f64) -> bool {
+    if a == b || (a.is_nan() && b.is_nan()) {
+        return true;
+    }
+    let diff = (a - b).abs();
+    diff <= 1e-4 || diff <= 1e-5 * a.abs().max(b.abs())
+}
+
+/// Compares a raster with its golden version, returning a description of the first
+/// difference found. NoData cells must be NoData in both rasters.
+pub fn compare_rasters(output: &str, golden: &str) -> Result<(), String> {
+    let a = Raster::new(output, "r").map_err(|e| format!("Error reading {}: {}", output, e))?;
+    let b = Raster::new(golden, "r").map_err(|e| format!("Error reading {}: {}", golden, e))?;
+    if a.configs.rows != b.configs.rows || a.configs.columns != b.configs.columns {
+        return Err(format!(
+            "the raster has {} rows and {} columns, rather than {} and {}",
+            a.configs.rows, a.configs.columns, b.configs.rows, b.configs.columns
+        ));
+    }
+    for (name, x, y) in [
+        ("north", a.configs.north, b.configs.north),
+        ("south", a.configs.south, b.configs.south),
+        ("east", a.configs.east, b.configs.east),
+        ("west", a.configs.west, b.configs.west),
+        ("nodata", a.configs.nodata, b.configs.nodata),
+    ] {
+        if !approx_eq(x, y) {
+            return Err(format!("the {} value is {}, rather than {}", name, x, y));
+        }
+    }
+    for row in 0..a.configs.rows as isize {
+        for col in 0..a.configs.columns as isize {
+            let (x, y) = (a.get_value(row, col), b.get_value(row, col));
+            let (x_nodata, y_nodata) = (x == a.configs.nodata, y == b.configs.nodata);
+            if x_nodata != y_nodata || (!x_nodata && !approx_eq(x, y)) {
+                return Err(format!(
+                    "the value at row {}, column {} is {}, rather than {}",
+                    row, col, x, y
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compares a Shapefile with its golden version, i.e. its geometries and attributes.
+pub fn compare_vectors(output: &str, golden: &str) -> Result<(), String> {
+    let a = Shapefile::read(output).map_err(|e| format!("Error reading {}: {}", output, e))?;
+    let b = Shapefile::read(golden).map_err(|e| format!("Error reading {}: {}", golden, e))?;
+    if a.header.shape_type != b.header.shape_type {
+        return Err(format!(
+            "the shape type is {}, rather than {}",
+            a.header.shape_type, b.header.shape_type
+        ));
+    }
+    if a.num_records != b.num_records {
+        return Err(format!(
+            "there are {} features, rather than {}",
+            a.num_records, b.num_records
+        ));
+    }
+    for i in 0..a.num_records {
+        let (x, y) = (a.get_record(i), b.get_record(i));
+        if x.parts != y.parts || x.points.len() != y.points.len() {
+            return Err(format!("the parts of feature {} differ", i + 1));
+        }
+        for (p, q) in x.points.iter().zip(y.points.iter()) {
+            if !approx_eq(p.x, q.x) || !approx_eq(p.y, q.y) {
+                return Err(format!(
+                    "feature {} has the point ({}, {}), rather than ({}, {})",
+                    i + 1,
+                    p.x,
+                    p.y,
+                    q.x,
+                    q.y
+                ));
+            }
+        }
+        if x.z_array.len() != y.z_array.len()
+            || x.z_array
+                .iter()
+                .zip(&y.z_array)
+                .any(|(p, q)| !approx_eq(*p, *q))
+        {
+            return Err(format!("the z values of feature {} differ", i + 1));
+        }
+    }
+    let field_names = |s: &Shapefile| {
+        s.attributes
+            .get_fields()
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<String>>()
+    };
+    if field_names(&a) != field_names(&b) {
+        return Err(format!(
+            "the fields are {:?}, rather than {:?}",
+            field_names(&a),
+            field_names(&b)
+        ));
+    }
+    for i in 0..a.num_records {
+        let (x, y) = (a.attributes.get_record(i), b.attributes.get_record(i));
+        for (j, (p, q)) in x.iter().zip(y.iter()).enumerate() {
+            let equal = match (p, q) {
+                (FieldData::Real(p), FieldData::Real(q)) => approx_eq(*p, *q),
+                _ => p == q,
+            };
+            if !equal {
+                return Err(format!(
+                    "the {} attribute of feature {} is {:?}, rather than {:?}",
+                    field_names(&a)[j],
+                    i + 1,
+                    p,
+                    q
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compares a LAS file with its golden version, i.e. the coordinates, classification,
+/// intensity, and returns of its points.
+pub fn compare_lidar(output: &str, golden: &str) -> Result<(), String> {
+    let a = LasFile::new(output, "r").map_err(|e| format!("Error reading {}: {}", output, e))?;
+    let b = LasFile::new(golden, "r").map_err(|e| format!("Error reading {}: {}", golden, e))?;
+    if a.header.number_of_points != b.header.number_of_points {
+        return Err(format!(
+            "there are {} points, rather than {}",
+            a.header.number_of_points, b.header.number_of_points
+        ));
+    }
+    for i in 0..a.header.number_of_points as usize {
+        let (p, q) = (a.get_transformed_coords(i), b.get_transformed_coords(i));
+        if !approx_eq(p.x, q.x) || !approx_eq(p.y, q.y) || !approx_eq(p.z, q.z) {
+            return Err(format!(
+                "point {} is ({}, {}, {}), rather than ({}, {}, {})",
+                i, p.x, p.y, p.z, q.x, q.y, q.z
+            ));
+        }
+        let (p, q) = (a.get_point_info(i), b.get_point_info(i));
+        if p.classification() != q.classification()
+            || p.intensity != q.intensity
+            || p.return_number() != q.return_number()
+            || p.number_of_returns() != q.number_of_returns()
+        {
+            return Err(format!("the attributes of point {} differ", i));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Runs every tool on the synthetic data and compares the raster, vector, and LiDAR files
+//! that it creates with the golden files in tests/golden/<tool name>, within numeric
+//! tolerances.
+//!
+//! When a tool's output changes deliberately, or a tool is added, the golden files are
+//! rewritten from the current outputs by running the tests with WHITEBOX_UPDATE_GOLDEN=1,
+//! e.g. `WHITEBOX_UPDATE_GOLDEN=1 cargo test -p whitebox_tools --test golden_outputs`, and
+//! the changed files are reviewed and committed with the change.
+
+mod common;
+
+use common::*;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use whitebox_tools::tools::ToolManager;
+
+const UPDATE_GOLDEN_ENV_VAR: &str = "WHITEBOX_UPDATE_GOLDEN";
+
+/// Tools whose outputs differ from one run to the next, because they are based on random
+/// numbers, or break ties in an arbitrary order. Only the set of files that they create is
+/// compared.
+const RANDOM_OUTPUTS: &[&str] = &[
+    "MajorityFilter",
+    "RandomField",
+    "RandomSample",
+    "Rho8Pointer",
+    "StochasticDepressionAnalysis",
+];
+
+#[test]
+fn tool_outputs_match_golden_files() {
+    let update = env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some();
+    let tm = ToolManager::new("", &false).expect("Error creating the ToolManager.");
+    let mut failures = vec![];
+    for tool_name in tm.get_tool_names() {
+        if UNTESTED.iter().any(|(t, _)| *t == tool_name) {
+            continue;
+        }
+        let parameters = tm.get_tool_parameters(&tool_name).unwrap_or_default();
+        let invocation = match derive_invocation(&tool_name, &parameters) {
+            Ok(i) => i,
+            Err(e) => {
+                failures.push(format!("{}: {}", tool_name, e));
+                continue;
+            }
+        };
+        let data = TestData::new(&tool_name);
+        let outputs = match data.run_tool_for_outputs(&tool_name, &invocation.args) {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                failures.push(format!("{}: {}", tool_name, e));
+                continue;
+            }
+        };
+        let golden_dir = golden_dir().join(&tool_name);
+        let result = if update {
+            update_golden_files(&data, &outputs, &golden_dir)
+        } else {
+            compare_golden_files(&tool_name, &data, &outputs, &golden_dir)
+        };
+        if let Err(e) = result {
+            failures.push(format!("{}: {}", tool_name, e));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} tools did not match their golden files (set {} to update them):\n{}",
+        failures.len(),
+        UPDATE_GOLDEN_ENV_VAR,
+        failures.join("\n")
+    );
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+}
+
+/// Returns the names of the data files in a tool's golden directory, sorted.
+fn golden_files(golden_dir: &Path) -> Vec<String> {
+    let mut files = fs::read_dir(golden_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| is_data_file(name))
+        .collect::<Vec<String>>();
+    files.sort();
+    files
+}
+
+fn compare_golden_files(
+    tool_name: &str,
+    data: &TestData,
+    outputs: &[String],
+    golden_dir: &Path,
+) -> Result<(), String> {
+    let expected = golden_files(golden_dir);
+    if outputs != expected {
+        return Err(format!(
+            "the tool created {:?}, rather than {:?}",
+            outputs, expected
+        ));
+    }
+    if RANDOM_OUTPUTS.contains(&tool_name) {
+        return Ok(());
+    }
+    for file_name in outputs {
+        let output = data.path(file_name);
+        let golden = golden_dir.join(file_name).to_string_lossy().to_string();
+        let result = if file_name.ends_with(".tif") {
+            compare_rasters(&output, &golden)
+        } else if file_name.ends_with(".shp") {
+            compare_vectors(&output, &golden)
+        } else {
+            compare_lidar(&output, &golden)
+        };
+        result.map_err(|e| format!("in {}, {}", file_name, e))?;
+    }
+    Ok(())
+}
+
+fn update_golden_files(
+    data: &TestData,
+    outputs: &[String],
+    golden_dir: &Path,
+) -> Result<(), String> {
+    let _ = fs::remove_dir_all(golden_dir);
+    if outputs.is_empty() {
+        return Ok(());
+    }
+    fs::create_dir_all(golden_dir).map_err(|e| e.to_string())?;
+    for file_name in outputs {
+        for file in data_set_files(&data.dir.join(file_name)) {
+            let golden = golden_dir.join(file.file_name().unwrap_or_default());
+            fs::copy(&file, &golden).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
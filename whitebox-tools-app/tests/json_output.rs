@@ -0,0 +1,149 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the --json mode of the whitebox_tools executable, which reports a tool run as JSON
+//! lines on stdout, and the exit codes that identify the category of a failed run.
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use whitebox_raster::{DataType, PhotometricInterpretation, Raster, RasterConfigs};
+
+// Each run with a new --wd saves the settings.json file, and so the runs of the tests, which
+// are otherwise run in parallel, are made one at a time.
+static RUN_LOCK: Mutex<()> = Mutex::new(());
+
+fn test_dir(name: &str) -> String {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "whitebox_json_output_test_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    format!("{}{}", dir.to_string_lossy(), std::path::MAIN_SEPARATOR)
+}
+
+fn write_dem(file_name: &str) {
+    let configs = RasterConfigs {
+        rows: 20,
+        columns: 30,
+        nodata: -32768.0,
+        north: 20.0,
+        south: 0.0,
+        east: 30.0,
+        west: 0.0,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut dem = Raster::initialize_using_config(file_name, &configs);
+    for row in 0..20 {
+        for col in 0..30 {
+            dem.set_value(row, col, (row * col) as f64 * 0.1);
+        }
+    }
+    dem.write().expect("Error writing the DEM.");
+}
+
+/// Runs whitebox_tools in --json mode, returning its exit code and the events on stdout.
+fn run_json(wd: &str, args: &[&str]) -> (Option<i32>, Vec<Value>) {
+    let _lock = RUN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let output = Command::new(env!("CARGO_BIN_EXE_whitebox_tools"))
+        .arg(format!("--wd={}", wd))
+        .arg("--json")
+        .args(args)
+        .output()
+        .expect("Error running whitebox_tools.");
+    let events = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<Value>(line)
+                .unwrap_or_else(|_| panic!("The line '{}' is not a JSON object.", line))
+        })
+        .collect::<Vec<Value>>();
+    for event in &events {
+        assert!(event["event"].is_string(), "{}", event);
+    }
+    (output.status.code(), events)
+}
+
+fn events_of<'a>(events: &'a [Value], name: &str) -> Vec<&'a Value> {
+    events.iter().filter(|e| e["event"] == name).collect()
+}
+
+#[test]
+fn successful_run_is_reported() {
+    let wd = test_dir("success");
+    write_dem(&format!("{}dem.tif", wd));
+    let (exit_code, events) = run_json(&wd, &["--run=Slope", "-i=dem.tif", "--output=slope.tif"]);
+    assert_eq!(exit_code, Some(0));
+
+    let start = &events[0];
+    assert_eq!(start["event"], "start");
+    assert_eq!(start["tool"], "Slope");
+    assert_eq!(start["args"], serde_json::json!(["-i=dem.tif", "--output=slope.tif"]));
+
+    let progress = events_of(&events, "progress");
+    assert!(!progress.is_empty());
+    for event in progress {
+        let percent = event["percent"].as_f64().unwrap();
+        assert!((0.0..=100.0).contains(&percent), "{}", event);
+    }
+    assert!(events_of(&events, "error").is_empty());
+
+    let output_path = format!("{}slope.tif", wd);
+    let size = fs::metadata(&output_path).unwrap().len();
+    let outputs = events_of(&events, "output");
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0]["flag"], "--output");
+    assert_eq!(outputs[0]["file_type"], "Raster");
+    assert_eq!(outputs[0]["path"], output_path.as_str());
+    assert_eq!(outputs[0]["exists"], true);
+    assert_eq!(outputs[0]["size_bytes"], size);
+
+    let summary = events.last().unwrap();
+    assert_eq!(summary["event"], "summary");
+    assert_eq!(summary["status"], "success");
+    assert_eq!(summary["exit_code"], 0);
+    assert_eq!(summary["outputs"], serde_json::json!([output_path]));
+    assert_eq!(summary["output_bytes"], size);
+    assert!(summary["tool_elapsed_seconds"].as_f64().unwrap() <= summary["elapsed_seconds"].as_f64().unwrap());
+    assert_eq!(events_of(&events, "summary").len(), 1);
+}
+
+#[test]
+fn failures_are_categorized() {
+    let wd = test_dir("failure");
+    fs::write(format!("{}not_a_tiff.tif", wd), "not a GeoTIFF").unwrap();
+    let cases: [(&[&str], &str, i32); 4] = [
+        (&["--run=NoSuchTool"], "bad_argument", 2),
+        (&["--run=Slope", "-o=slope.tif"], "bad_argument", 2),
+        (&["--run=Slope", "-i=missing.tif", "-o=slope.tif"], "bad_argument", 2),
+        (&["--run=Slope", "-i=not_a_tiff.tif", "-o=slope.tif"], "unsupported_format", 4),
+    ];
+    for (args, category, code) in cases {
+        let (exit_code, events) = run_json(&wd, args);
+        assert_eq!(exit_code, Some(code), "{:?}", args);
+        assert_eq!(events[0]["event"], "start", "{:?}", args);
+        let errors = events_of(&events, "error");
+        assert_eq!(errors.len(), 1, "{:?}", args);
+        assert_eq!(errors[0]["category"], category, "{:?}", args);
+        assert_eq!(errors[0]["exit_code"], code, "{:?}", args);
+        assert!(!errors[0]["message"].as_str().unwrap().is_empty(), "{:?}", args);
+        assert!(events_of(&events, "output").is_empty(), "{:?}", args);
+        let summary = events.last().unwrap();
+        assert_eq!(summary["event"], "summary", "{:?}", args);
+        assert_eq!(summary["status"], "failure", "{:?}", args);
+        assert_eq!(summary["exit_code"], code, "{:?}", args);
+        assert!(summary["tool_elapsed_seconds"].is_null(), "{:?}", args);
+    }
+}
@@ -0,0 +1,135 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Runs tools on a raster with NaN NoData, as GDAL writes floating-point rasters. The NaN
+//! cells must be treated as NoData rather than as data.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use whitebox_raster::{DataType, PhotometricInterpretation, Raster, RasterConfigs};
+
+const SIZE: usize = 5;
+const NAN_CELL: (usize, usize) = (2, 2);
+
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "whitebox_nan_nodata_test_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    dir
+}
+
+fn cell_value(row: usize, col: usize) -> f64 {
+    if (row, col) == NAN_CELL {
+        f64::NAN
+    } else {
+        (row * SIZE + col + 1) as f64
+    }
+}
+
+/// Writes a 5 x 5 F32 raster with NaN NoData, in which one cell is NaN.
+fn write_input(file_name: &str) {
+    let configs = RasterConfigs {
+        rows: SIZE,
+        columns: SIZE,
+        nodata: f64::NAN,
+        north: 4_800_050.0,
+        south: 4_800_000.0,
+        west: 500_000.0,
+        east: 500_050.0,
+        resolution_x: 10.0,
+        resolution_y: 10.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    for row in 0..SIZE {
+        for col in 0..SIZE {
+            raster.set_value(row as isize, col as isize, cell_value(row, col));
+        }
+    }
+    raster.write().expect("Error writing the raster.");
+}
+
+fn run(tool: &str, args: &[String]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_whitebox_tools"))
+        .arg(format!("--run={}", tool))
+        .args(args)
+        .output()
+        .expect("Error running whitebox_tools.");
+    assert!(
+        output.status.success(),
+        "{}: {}",
+        tool,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn raster_summary_stats_skips_nan_cells() {
+    let dir = test_dir("stats");
+    let input = dir.join("input.tif").to_string_lossy().to_string();
+    write_input(&input);
+    let stdout = run("RasterSummaryStats", &[format!("-i={}", input)]);
+    // the values are 1 to 25, less the 13 of the NaN cell
+    assert!(stdout.contains("non-nodata grid cells: 24"), "{}", stdout);
+    assert!(stdout.contains("nodata grid cells: 1"), "{}", stdout);
+    assert!(stdout.contains("Image total: 312\n"), "{}", stdout);
+    assert!(stdout.contains("Image average: 13\n"), "{}", stdout);
+}
+
+#[test]
+fn bilinear_resampling_falls_back_to_nearest_neighbour_at_nan_cells() {
+    let dir = test_dir("resample");
+    let input = dir.join("input.tif").to_string_lossy().to_string();
+    let output = dir.join("output.tif").to_string_lossy().to_string();
+    write_input(&input);
+    // Reprojecting to the same system gives nearly the same grid, and so each output cell
+    // centre falls very near an input cell centre. The bilinear neighbours of the cells around
+    // the NaN cell include it.
+    run(
+        "ReprojectRaster",
+        &[
+            format!("-i={}", input),
+            format!("-o={}", output),
+            "--src_epsg=32617".to_string(),
+            "--epsg=32617".to_string(),
+            "--method=bilinear".to_string(),
+            "--cell_size=10".to_string(),
+        ],
+    );
+    let output = Raster::new(&output, "r").expect("Error reading the output.");
+    assert!(output.configs.nodata.is_nan());
+    let mut num_checked = 0;
+    for row in 0..output.configs.rows as isize {
+        for col in 0..output.configs.columns as isize {
+            let x = output.get_x_from_column(col);
+            let y = output.get_y_from_row(row);
+            let input_row = ((4_800_050.0 - y) / 10.0 - 0.5).round();
+            let input_col = ((x - 500_000.0) / 10.0 - 0.5).round();
+            if input_row < 0.0 || input_col < 0.0 || input_row >= 5.0 || input_col >= 5.0 {
+                continue;
+            }
+            let (input_row, input_col) = (input_row as usize, input_col as usize);
+            let z = output.get_value(row, col);
+            if (input_row, input_col) == NAN_CELL {
+                assert!(output.is_nodata(z));
+            } else {
+                let expected = cell_value(input_row, input_col);
+                assert!((z - expected).abs() < 1e-3, "({}, {}) is {}", row, col, z);
+            }
+            num_checked += 1;
+        }
+    }
+    assert_eq!(num_checked, SIZE * SIZE);
+}
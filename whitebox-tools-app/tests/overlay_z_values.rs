@@ -0,0 +1,251 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that the polygon overlay tools keep the z and measure values of their inputs. The
+//! input is a square whose values vary along its edges, and the overlay is a square that
+//! overlaps it, with a constant value. Each output vertex must take the value of an input
+//! edge that it lies on. Also tests that the values of clipped polylines are kept, and that
+//! the output polygons of an input feature are written as one multipart record.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_common::structures::Point2D;
+use whitebox_tools::tools::ToolManager;
+use whitebox_vector::*;
+
+const OVERLAY_VALUE: f64 = 1000.0;
+
+const TOOLS: &[(&str, &str)] = &[
+    ("Clip", "--clip"),
+    ("Erase", "--erase"),
+    ("Intersect", "--overlay"),
+    ("Union", "--overlay"),
+    ("Difference", "--overlay"),
+    ("SymmetricalDifference", "--overlay"),
+];
+
+fn test_dir(name: &str) -> String {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "whitebox_overlay_test_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    format!("{}{}", dir.to_string_lossy(), std::path::MAIN_SEPARATOR)
+}
+
+/// A clockwise square, as a Shapefile exterior ring.
+fn square(x: f64, y: f64, size: f64) -> Vec<Point2D> {
+    vec![
+        Point2D::new(x, y + size),
+        Point2D::new(x + size, y + size),
+        Point2D::new(x + size, y),
+        Point2D::new(x, y),
+        Point2D::new(x, y + size),
+    ]
+}
+
+fn is_on_square(p: &Point2D, x: f64, y: f64, size: f64) -> bool {
+    p.x == x || p.y == y || p.x == x + size || p.y == y + size
+}
+
+fn input_value(p: &Point2D) -> f64 {
+    p.x + 2.0 * p.y
+}
+
+fn write_square(
+    file_name: &str,
+    shape_type: ShapeType,
+    ring: &[Point2D],
+    value: fn(&Point2D) -> f64,
+) {
+    let mut output = Shapefile::new(file_name, shape_type).expect("Error creating the file.");
+    output
+        .attributes
+        .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+    let values: Vec<f64> = ring.iter().map(value).collect();
+    let mut geometry = ShapefileGeometry::new(shape_type);
+    if shape_type == ShapeType::PolygonZ {
+        geometry.add_partz(ring, &[], &values);
+    } else {
+        geometry.add_partm(ring, &values);
+    }
+    output.add_record(geometry);
+    output.attributes.add_record(vec![FieldData::Int(1)], false);
+    output.write().expect("Error writing the file.");
+}
+
+fn check_overlay_values(shape_type: ShapeType) {
+    let dir = test_dir(&format!("{:?}", shape_type.to_int()));
+    write_square(
+        &format!("{}input.shp", dir),
+        shape_type,
+        &square(0.0, 0.0, 10.0),
+        input_value,
+    );
+    write_square(
+        &format!("{}overlay.shp", dir),
+        shape_type,
+        &square(5.0, 5.0, 10.0),
+        |_| OVERLAY_VALUE,
+    );
+    let tm = ToolManager::new(&dir, &false).expect("Error creating the ToolManager.");
+    for (tool_name, overlay_flag) in TOOLS {
+        let args = vec![
+            "-i=input.shp".to_string(),
+            format!("{}=overlay.shp", overlay_flag),
+            "-o=output.shp".to_string(),
+        ];
+        tm.run_tool(tool_name.to_string(), args)
+            .expect("Error running the tool.");
+        let output =
+            Shapefile::read(&format!("{}output.shp", dir)).expect("Error reading the output.");
+        assert_eq!(output.header.shape_type, shape_type, "{}", tool_name);
+        assert!(output.num_records > 0, "{}", tool_name);
+        for record_num in 0..output.num_records {
+            let record = output.get_record(record_num);
+            let values = if shape_type == ShapeType::PolygonZ {
+                &record.z_array
+            } else {
+                &record.m_array
+            };
+            assert_eq!(values.len(), record.points.len(), "{}", tool_name);
+            for (p, value) in record.points.iter().zip(values) {
+                let from_input =
+                    is_on_square(p, 0.0, 0.0, 10.0) && (value - input_value(p)).abs() < 1e-9;
+                let from_overlay =
+                    is_on_square(p, 5.0, 5.0, 10.0) && (value - OVERLAY_VALUE).abs() < 1e-9;
+                assert!(
+                    from_input || from_overlay,
+                    "{}: ({}, {}) has the value {}",
+                    tool_name,
+                    p.x,
+                    p.y,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn overlay_tools_keep_z_values() {
+    check_overlay_values(ShapeType::PolygonZ);
+}
+
+#[test]
+fn overlay_tools_keep_measures() {
+    check_overlay_values(ShapeType::PolygonM);
+}
+
+/// Writes a file with a single polygon record with a part for each ring.
+fn write_polygon(file_name: &str, rings: &[Vec<Point2D>]) {
+    let mut output =
+        Shapefile::new(file_name, ShapeType::Polygon).expect("Error creating the file.");
+    output
+        .attributes
+        .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+    let mut geometry = ShapefileGeometry::new(ShapeType::Polygon);
+    for ring in rings {
+        geometry.add_part(ring);
+    }
+    output.add_record(geometry);
+    output.attributes.add_record(vec![FieldData::Int(1)], false);
+    output.write().expect("Error writing the file.");
+}
+
+#[test]
+fn clipped_polylines_keep_z_values_and_measures() {
+    let dir = test_dir("polyline");
+    let mut input = Shapefile::new(&format!("{}line.shp", dir), ShapeType::PolyLineZ)
+        .expect("Error creating the file.");
+    input
+        .attributes
+        .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+    let points = vec![
+        Point2D::new(-5.0, 2.0),
+        Point2D::new(5.0, 8.0),
+        Point2D::new(15.0, 2.0),
+    ];
+    let z_values: Vec<f64> = points.iter().map(input_value).collect();
+    let measures: Vec<f64> = points.iter().map(|p| p.x).collect();
+    let mut geometry = ShapefileGeometry::new(ShapeType::PolyLineZ);
+    geometry.add_partz(&points, &measures, &z_values);
+    input.add_record(geometry);
+    input.attributes.add_record(vec![FieldData::Int(1)], false);
+    input.write().expect("Error writing the file.");
+    write_polygon(&format!("{}clip.shp", dir), &[square(0.0, 0.0, 10.0)]);
+
+    let tm = ToolManager::new(&dir, &false).expect("Error creating the ToolManager.");
+    // The line crosses the square at x = 0 and x = 10, leaving one piece inside it and two
+    // outside it.
+    for (tool_name, flag, num_pieces) in [("Clip", "--clip", 1), ("Erase", "--erase", 2)] {
+        let args = vec![
+            "-i=line.shp".to_string(),
+            format!("{}=clip.shp", flag),
+            "-o=output.shp".to_string(),
+        ];
+        tm.run_tool(tool_name.to_string(), args)
+            .expect("Error running the tool.");
+        let output =
+            Shapefile::read(&format!("{}output.shp", dir)).expect("Error reading the output.");
+        assert_eq!(output.header.shape_type, ShapeType::PolyLineZ, "{}", tool_name);
+        assert_eq!(output.num_records, num_pieces, "{}", tool_name);
+        for record_num in 0..output.num_records {
+            let record = output.get_record(record_num);
+            assert_eq!(record.z_array.len(), record.points.len(), "{}", tool_name);
+            assert_eq!(record.m_array.len(), record.points.len(), "{}", tool_name);
+            for (i, p) in record.points.iter().enumerate() {
+                assert!((record.z_array[i] - input_value(p)).abs() < 1e-9, "{}", tool_name);
+                assert!((record.m_array[i] - p.x).abs() < 1e-9, "{}", tool_name);
+            }
+        }
+    }
+}
+
+#[test]
+fn output_polygons_of_a_feature_are_one_record() {
+    let dir = test_dir("multipart");
+    write_polygon(&format!("{}input.shp", dir), &[square(0.0, 0.0, 10.0)]);
+    // two corners of the input square
+    write_polygon(
+        &format!("{}corners.shp", dir),
+        &[square(-1.0, -1.0, 4.0), square(7.0, 7.0, 4.0)],
+    );
+    // a bar across the middle of the input square
+    let bar = vec![
+        Point2D::new(-1.0, 6.0),
+        Point2D::new(11.0, 6.0),
+        Point2D::new(11.0, 4.0),
+        Point2D::new(-1.0, 4.0),
+        Point2D::new(-1.0, 6.0),
+    ];
+    write_polygon(&format!("{}bar.shp", dir), &[bar]);
+
+    let tm = ToolManager::new(&dir, &false).expect("Error creating the ToolManager.");
+    let cases = [
+        ("Clip", "--clip", "corners.shp"),
+        ("Intersect", "--overlay", "corners.shp"),
+        ("Erase", "--erase", "bar.shp"),
+        ("Difference", "--overlay", "bar.shp"),
+    ];
+    for (tool_name, flag, overlay) in cases {
+        let args = vec![
+            "-i=input.shp".to_string(),
+            format!("{}={}", flag, overlay),
+            "-o=output.shp".to_string(),
+        ];
+        tm.run_tool(tool_name.to_string(), args)
+            .expect("Error running the tool.");
+        let output =
+            Shapefile::read(&format!("{}output.shp", dir)).expect("Error reading the output.");
+        assert_eq!(output.num_records, 1, "{}", tool_name);
+        assert_eq!(output.get_record(0).num_parts, 2, "{}", tool_name);
+        assert_eq!(output.attributes.header.num_records, 1, "{}", tool_name);
+    }
+}
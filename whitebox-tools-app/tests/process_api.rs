@@ -0,0 +1,160 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the in-memory `process` API of the D8 hydrology tools. Chaining
+//! BreachDepressionsLeastCost, D8Pointer, and D8FlowAccumulation in memory must give the same
+//! rasters as running the tools on files, without writing any intermediate files.
+
+mod common;
+
+use common::{data_set_files, TestData, DEM};
+use std::sync::Arc;
+use whitebox_common::structures::Array2D;
+use whitebox_raster::Raster;
+use whitebox_tools::tools::hydro_analysis::*;
+
+// Checks that an in-memory output matches the output of the tool run on files, as it was
+// written, i.e. with the values stored in the data type of the file.
+fn assert_same_raster(in_memory: &Raster, file_name: &str) {
+    let file = Raster::new(file_name, "r").expect("Error reading the raster.");
+    assert_eq!(in_memory.configs.rows, file.configs.rows, "{}", file_name);
+    assert_eq!(in_memory.configs.columns, file.configs.columns, "{}", file_name);
+    assert_eq!(in_memory.configs.north, file.configs.north, "{}", file_name);
+    assert_eq!(in_memory.configs.west, file.configs.west, "{}", file_name);
+    assert_eq!(in_memory.configs.nodata, file.configs.nodata, "{}", file_name);
+    assert_eq!(in_memory.configs.data_type, file.configs.data_type, "{}", file_name);
+    for row in 0..file.configs.rows as isize {
+        for col in 0..file.configs.columns as isize {
+            let expected = file.get_value(row, col);
+            let value = in_memory.get_value(row, col);
+            let data_type = file.configs.data_type;
+            let same = if data_type.is_float() && data_type.get_data_size() == 4 {
+                value as f32 == expected as f32
+            } else {
+                value == expected
+            };
+            assert!(
+                same,
+                "{} ({}, {}): {} != {}",
+                file_name,
+                row,
+                col,
+                value,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn chained_process_matches_file_based_run() {
+    let data = TestData::new("process_api");
+    let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+    data.run_tool(
+        "BreachDepressionsLeastCost",
+        &args(&["-i=dem.tif", "-o=breached.tif", "--dist=20"]),
+    )
+    .expect("Error running BreachDepressionsLeastCost.");
+    data.run_tool("D8Pointer", &args(&["-i=breached.tif", "-o=pointer.tif"]))
+        .expect("Error running D8Pointer.");
+    for (out_type, name) in [("cells", "cells"), ("specific contributing area", "sca")] {
+        data.run_tool(
+            "D8FlowAccumulation",
+            &[
+                "-i=pointer.tif".to_string(),
+                format!("-o=flow_accum_{}.tif", name),
+                "--pntr".to_string(),
+                format!("--out_type={}", out_type),
+            ],
+        )
+        .expect("Error running D8FlowAccumulation.");
+    }
+
+    let files_before = data_set_files(&data.dir);
+    let dem = Arc::new(Raster::new(&data.path(DEM), "r").expect("Error reading the DEM."));
+    let breached = BreachDepressionsLeastCost::new()
+        .process(dem.clone(), &BreachDepressionsLeastCostParameters::default(), false)
+        .expect("Error breaching the DEM.");
+    // The pit in the DEM is breached.
+    assert_ne!(breached.get_value(20, 10), dem.get_value(20, 10));
+    assert_same_raster(&breached, &data.path("breached.tif"));
+
+    let pointer = D8Pointer::new()
+        .process(Arc::new(breached), &D8PointerParameters::default(), false)
+        .expect("Error calculating the pointer.");
+    assert_same_raster(&pointer, &data.path("pointer.tif"));
+
+    let pointer = Arc::new(pointer);
+    for (out_type, name) in [
+        (D8FlowAccumulationOutputType::Cells, "cells"),
+        (D8FlowAccumulationOutputType::SpecificContributingArea, "sca"),
+    ] {
+        let params = D8FlowAccumulationParameters {
+            out_type,
+            pntr: true,
+            ..Default::default()
+        };
+        let flow_accum = D8FlowAccumulation::new()
+            .process(pointer.clone(), &params, false)
+            .expect("Error calculating the flow accumulation.");
+        assert_same_raster(&flow_accum, &data.path(&format!("flow_accum_{}.tif", name)));
+    }
+    // Nothing was written by the in-memory chain.
+    assert_eq!(data_set_files(&data.dir), files_before);
+
+    // An in-memory output is written once it is named.
+    let params = D8FlowAccumulationParameters {
+        out_type: D8FlowAccumulationOutputType::Cells,
+        pntr: true,
+        ..Default::default()
+    };
+    let mut flow_accum = D8FlowAccumulation::new()
+        .process(pointer, &params, false)
+        .expect("Error calculating the flow accumulation.");
+    let file_name = data.path("flow_accum_in_memory.tif");
+    flow_accum.set_file_name(&file_name);
+    flow_accum.write().expect("Error writing the flow accumulation.");
+    let written = Raster::new(&file_name, "r").expect("Error reading the written raster.");
+    assert_same_raster(&written, &data.path("flow_accum_cells.tif"));
+}
+
+#[test]
+fn set_file_name_uses_the_surfer_nodata_value() {
+    let data = TestData::new("process_api_surfer");
+    let dem = Raster::new(&data.path(DEM), "r").expect("Error reading the DEM.");
+    let nodata = dem.configs.nodata;
+    let mut output = Raster::initialize_using_file(&data.path("copy.tif"), &dem);
+    for row in 0..dem.configs.rows as isize {
+        for col in 0..dem.configs.columns as isize {
+            output.set_value(row, col, dem.get_value(row, col));
+        }
+    }
+    output.set_file_name(&data.path("copy.grd"));
+    assert_eq!(output.configs.nodata, 1.71041e38);
+    for row in 0..dem.configs.rows as isize {
+        for col in 0..dem.configs.columns as isize {
+            let value = dem.get_value(row, col);
+            let expected = if value == nodata { 1.71041e38 } else { value };
+            assert_eq!(output.get_value(row, col), expected, "({}, {})", row, col);
+        }
+    }
+
+    // Rasters that are created as Surfer grids have the same NoData value, whether they
+    // are filled with NoData or with the values of an array.
+    let surfer = Raster::initialize_using_file(&data.path("surfer.grd"), &dem);
+    assert_eq!(surfer.configs.nodata, 1.71041e38);
+    assert_eq!(surfer.get_value(0, 0), 1.71041e38);
+    let (rows, columns) = (dem.configs.rows as isize, dem.configs.columns as isize);
+    let mut array: Array2D<f64> =
+        Array2D::new(rows, columns, 5.0, nodata).expect("Error creating the array.");
+    array.set_value(1, 2, nodata);
+    let surfer = Raster::initialize_using_array2d(&data.path("array.grd"), &dem.configs, array);
+    assert_eq!(surfer.configs.nodata, 1.71041e38);
+    assert_eq!(surfer.get_value(1, 2), 1.71041e38);
+    assert_eq!(surfer.get_value(0, 0), 5.0);
+}
@@ -0,0 +1,128 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the global --band and --cog flags, which the ToolManager applies to the raster
+//! inputs and outputs of any tool. The flags of one run must not affect the next run.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_raster::{DataType, GeoTiffReader, PhotometricInterpretation, Raster, RasterConfigs};
+use whitebox_tools::tools::ToolManager;
+
+const ROWS: usize = 20;
+const COLUMNS: usize = 30;
+const BANDS: usize = 3;
+
+fn test_dir(name: &str) -> String {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "whitebox_raster_flags_test_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    format!("{}{}", dir.to_string_lossy(), std::path::MAIN_SEPARATOR)
+}
+
+fn band_value(band: usize, row: usize, column: usize) -> f64 {
+    -((band * 1000 + row * COLUMNS + column) as f64)
+}
+
+fn write_multiband(file_name: &str) {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: -32768.0,
+        north: ROWS as f64,
+        south: 0.0,
+        east: COLUMNS as f64,
+        west: 0.0,
+        resolution_x: 1.0,
+        resolution_y: 1.0,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(file_name, &configs);
+    raster.set_num_bands(BANDS as u8);
+    for band in 0..BANDS {
+        for row in 0..ROWS {
+            for column in 0..COLUMNS {
+                raster.set_band_value(band, row as isize, column as isize, band_value(band, row, column));
+            }
+        }
+    }
+    raster.write().expect("Error writing the multi-band raster.");
+}
+
+fn run_absolute_value(dir: &str, output: &str, flags: &[&str]) {
+    let tm = ToolManager::new(dir, &false).expect("Error creating the tool manager.");
+    let mut args = vec![
+        "-i=multiband.tif".to_string(),
+        format!("-o={}", output),
+    ];
+    args.extend(flags.iter().map(|f| f.to_string()));
+    tm.run_tool("AbsoluteValue".to_string(), args)
+        .expect("Error running AbsoluteValue.");
+}
+
+#[test]
+fn band_and_cog_flags_apply_to_a_single_run() {
+    let dir = test_dir("single_run");
+    write_multiband(&format!("{}multiband.tif", dir));
+
+    run_absolute_value(&dir, "band2.tif", &["--band=2", "--cog"]);
+    let output = Raster::new(&format!("{}band2.tif", dir), "r").unwrap();
+    assert_eq!(output.num_bands(), 1);
+    for row in 0..ROWS {
+        for column in 0..COLUMNS {
+            assert_eq!(
+                output.get_value(row as isize, column as isize),
+                band_value(1, row, column).abs()
+            );
+        }
+    }
+    let reader = GeoTiffReader::new(&format!("{}band2.tif", dir)).unwrap();
+    assert_eq!(reader.block_size(), (512, 512), "--cog output is not tiled");
+
+    // Neither flag carries over to the next run.
+    run_absolute_value(&dir, "unflagged.tif", &[]);
+    let output = Raster::new(&format!("{}unflagged.tif", dir), "r").unwrap();
+    assert_eq!(output.get_value(5, 7), band_value(0, 5, 7).abs());
+    let reader = GeoTiffReader::new(&format!("{}unflagged.tif", dir)).unwrap();
+    assert_ne!(reader.block_size(), (512, 512), "output without --cog is tiled");
+
+    // The temporary band file is removed once the tool has run.
+    let temp_files = fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with(&format!("multiband_band2_{}_", std::process::id()))
+        })
+        .count();
+    assert_eq!(temp_files, 0);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn band_flag_rejects_a_missing_band() {
+    let dir = test_dir("missing_band");
+    write_multiband(&format!("{}multiband.tif", dir));
+    let tm = ToolManager::new(&dir, &false).unwrap();
+    let args = vec![
+        "-i=multiband.tif".to_string(),
+        "-o=band4.tif".to_string(),
+        "--band=4".to_string(),
+    ];
+    let err = tm.run_tool("AbsoluteValue".to_string(), args).unwrap_err();
+    assert!(err.to_string().contains("contains 3 band(s)"), "{}", err);
+    assert!(!PathBuf::from(format!("{}band4.tif", dir)).exists());
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,310 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Runs every tool on the synthetic data and checks the headers of the rasters that it
+//! creates: the grid must be consistent with its extent and resolution, the coordinate
+//! reference system of the inputs must be passed through to the output, and a tool that
+//! is run on the synthetic rasters must create its outputs on the same grid and leave the
+//! NoData cells of its inputs as NoData. The lists below record the known exceptions, so
+//! that a tool that starts to drop these headers is caught.
+
+mod common;
+
+use common::*;
+use whitebox_raster::Raster;
+use whitebox_tools::tools::ToolManager;
+
+/// Tools whose raster outputs do not carry the coordinate reference system of their inputs.
+const CRS_NOT_PASSED: &[(&str, &str)] = &[
+    (
+        "AggregateRaster",
+        "builds its output configs without the input CRS",
+    ),
+    (
+        "BlockMaximumGridding",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "BlockMinimumGridding",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "ClipRasterToPolygon",
+        "builds its output configs without the input CRS",
+    ),
+    (
+        "FlightlineOverlap",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "IdwInterpolation",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarBlockMaximum",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarBlockMinimum",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarDigitalSurfaceModel",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarIdwInterpolation",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarKappaIndex",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarNearestNeighbourGridding",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarPointDensity",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarPointStats",
+        "builds its output grids without the input CRS",
+    ),
+    (
+        "LidarRbfInterpolation",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "LidarTINGridding",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "NaturalNeighbourInterpolation",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "NearestNeighbourGridding",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "NewRasterFromBase",
+        "builds its output configs without the input CRS",
+    ),
+    (
+        "RadialBasisFunctionInterpolation",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "ReprojectRaster",
+        "transforms its output to the requested CRS",
+    ),
+    (
+        "Resample",
+        "builds its output configs without the input CRS",
+    ),
+    (
+        "TINGridding",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "TrendSurfaceVectorPoints",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "VectorLinesToRaster",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "VectorPointsToRaster",
+        "builds its output grid without the input CRS",
+    ),
+    (
+        "VectorPolygonsToRaster",
+        "builds its output grid without the input CRS",
+    ),
+];
+
+/// Tools that create their raster outputs on a different grid than that of their inputs.
+const GRID_CHANGED: &[(&str, &str)] = &[
+    ("AggregateRaster", "coarsens the grid"),
+    ("ClipRasterToPolygon", "crops the grid to the polygons"),
+    ("ReprojectRaster", "creates a grid in the requested CRS"),
+];
+
+/// Tools that assign values to the NoData cells of their inputs, or that do not copy the
+/// NoData cells of the inputs to their outputs.
+const NODATA_FILLED: &[(&str, &str)] = &[
+    (
+        "BalanceContrastEnhancement",
+        "writes 0 to the NoData cells of an image",
+    ),
+    (
+        "BoundaryShapeComplexity",
+        "uses a different NoData value than that of the input",
+    ),
+    ("ConvertNodataToZero", "replaces NoData with zero"),
+    (
+        "CornerDetection",
+        "writes 0 to the NoData cells of an image",
+    ),
+    (
+        "CreateColourComposite",
+        "writes 0 to the NoData cells of an image",
+    ),
+    ("CreatePlane", "evaluates the plane at every cell"),
+    ("DInfPointer", "writes -1 to cells without a flow direction"),
+    (
+        "DirectDecorrelationStretch",
+        "writes 0 to the NoData cells of an image",
+    ),
+    ("EuclideanAllocation", "allocates every cell"),
+    ("FlipImage", "moves the NoData cells"),
+    (
+        "HypsometricallyTintedHillshade",
+        "writes a colour to the NoData cells",
+    ),
+    ("IntegralImage", "accumulates over the NoData cells"),
+    ("IsNoData", "flags the NoData cells"),
+    ("MosaicWithFeathering", "writes NaN to the NoData cells"),
+    (
+        "MultiscaleTopographicPositionImage",
+        "writes 0 to the NoData cells of an image",
+    ),
+    ("PercentileFilter", "filters the NoData cells"),
+    ("RandomField", "creates values for every cell"),
+    ("RandomSample", "writes 0 to the cells that are not sampled"),
+    ("RasterCellAssignment", "assigns every cell"),
+    ("Resample", "interpolates into the NoData cells"),
+    ("TrendSurface", "evaluates the surface at every cell"),
+    (
+        "WriteFunctionMemoryInsertion",
+        "writes 0 to the NoData cells of an image",
+    ),
+];
+
+#[test]
+fn raster_outputs_keep_the_input_headers() {
+    let tm = ToolManager::new("", &false).expect("Error creating the ToolManager.");
+    let mut failures = vec![];
+    for tool_name in tm.get_tool_names() {
+        if UNTESTED.iter().any(|(t, _)| *t == tool_name) {
+            continue;
+        }
+        let parameters = tm.get_tool_parameters(&tool_name).unwrap_or_default();
+        let invocation = match derive_invocation(&tool_name, &parameters) {
+            Ok(i) => i,
+            Err(e) => {
+                failures.push(format!("{}: {}", tool_name, e));
+                continue;
+            }
+        };
+        let data = TestData::new(&tool_name);
+        let outputs = match data.run_tool_for_outputs(&tool_name, &invocation.args) {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                failures.push(format!("{}: {}", tool_name, e));
+                continue;
+            }
+        };
+        for file_name in outputs.iter().filter(|f| f.ends_with(".tif")) {
+            if invocation
+                .inputs
+                .iter()
+                .any(|i| i.contains(file_name.as_str()))
+            {
+                // An input that the tool modified, e.g. a D8 pointer that it corrected.
+                continue;
+            }
+            if let Err(e) = check_raster(&tool_name, &invocation, &data.path(file_name)) {
+                failures.push(format!("{}: in {}, {}", tool_name, file_name, e));
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} raster outputs did not keep the headers of their inputs:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+fn is_listed(list: &[(&str, &str)], tool_name: &str) -> bool {
+    list.iter().any(|(t, _)| *t == tool_name)
+}
+
+fn check_raster(tool_name: &str, invocation: &Invocation, file_name: &str) -> Result<(), String> {
+    let raster = Raster::new(file_name, "r").map_err(|e| format!("Error reading it: {}", e))?;
+    let configs = &raster.configs;
+    if !approx_eq(
+        (configs.east - configs.west) / configs.columns as f64,
+        configs.resolution_x,
+    ) || !approx_eq(
+        (configs.north - configs.south) / configs.rows as f64,
+        configs.resolution_y,
+    ) {
+        return Err(format!(
+            "the extent (N {}, S {}, E {}, W {}) does not match {} rows and {} columns of {} by {}",
+            configs.north,
+            configs.south,
+            configs.east,
+            configs.west,
+            configs.rows,
+            configs.columns,
+            configs.resolution_x,
+            configs.resolution_y
+        ));
+    }
+    if !invocation.inputs.is_empty()
+        && !is_listed(CRS_NOT_PASSED, tool_name)
+        && configs.epsg_code != EPSG
+    {
+        return Err(format!(
+            "the EPSG code is {}, rather than {}",
+            configs.epsg_code, EPSG
+        ));
+    }
+    if !invocation.has_raster_input() || is_listed(GRID_CHANGED, tool_name) {
+        return Ok(());
+    }
+    if configs.rows != ROWS
+        || configs.columns != COLUMNS
+        || !approx_eq(configs.north, NORTH)
+        || !approx_eq(configs.west, WEST)
+        || !approx_eq(configs.resolution_x, RESOLUTION)
+        || !approx_eq(configs.resolution_y, RESOLUTION)
+    {
+        return Err(format!(
+            "the grid has {} rows and {} columns of {} by {} from N {}, W {}, rather than that of the inputs",
+            configs.rows,
+            configs.columns,
+            configs.resolution_x,
+            configs.resolution_y,
+            configs.north,
+            configs.west
+        ));
+    }
+    if is_listed(NODATA_FILLED, tool_name) {
+        return Ok(());
+    }
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            if is_nodata_cell(row, col) {
+                let z = raster.get_value(row as isize, col as isize);
+                if z != configs.nodata {
+                    return Err(format!(
+                        "the NoData cell at row {}, column {} has the value {}",
+                        row, col, z
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
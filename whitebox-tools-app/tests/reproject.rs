@@ -0,0 +1,370 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the ReprojectRaster, ReprojectVector and ReprojectLidar tools by transforming the
+//! synthetic data, which lie in UTM zone 17N (EPSG:32617) on the central meridian, into
+//! WGS84 geographic coordinates (EPSG:4326) and back again. The geographic coordinates of
+//! the corners of the synthetic grid were computed independently, from the inverse
+//! transverse Mercator series of Snyder (1987).
+
+mod common;
+
+use common::*;
+use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_lidar::LasFile;
+use whitebox_raster::{DataType, PhotometricInterpretation, Raster, RasterConfigs};
+use whitebox_vector::{AttributeField, FieldData, FieldDataType, ShapeType, Shapefile};
+
+/// The north-west and south-east corners of the synthetic grid, as (x, y) in UTM zone 17N
+/// and (longitude, latitude) in WGS84.
+const NW_UTM: (f64, f64) = (WEST, NORTH);
+const SE_UTM: (f64, f64) = (
+    WEST + COLUMNS as f64 * RESOLUTION,
+    NORTH - ROWS as f64 * RESOLUTION,
+);
+const NW_GEOGRAPHIC: (f64, f64) = (-81.0, 43.352_855_391);
+const SE_GEOGRAPHIC: (f64, f64) = (-80.996_051_538, 43.349_973_872);
+/// The longitude of the north-east corner, which is the most easterly point of the grid.
+const NE_LONGITUDE: f64 = -80.996_051_352;
+
+/// About 1 cm on the ground, which is the accuracy of the series used for the corners.
+const DEGREE_TOLERANCE: f64 = 1e-7;
+
+const PLANE: &str = "plane.tif";
+
+/// The cells of the plane raster within these rows and columns are NoData.
+const NODATA_BLOCK: std::ops::Range<usize> = 10..17;
+
+/// A planar surface, which bilinear interpolation reproduces, so that the values of a
+/// reprojected raster can be compared with the surface at the new cell centres.
+fn plane_value(x: f64, y: f64) -> f64 {
+    100.0 + 0.01 * (x - WEST) + 0.02 * (NORTH - y)
+}
+
+fn write_plane(data: &TestData) {
+    let configs = RasterConfigs {
+        rows: ROWS,
+        columns: COLUMNS,
+        nodata: NODATA,
+        north: NORTH,
+        south: NORTH - ROWS as f64 * RESOLUTION,
+        west: WEST,
+        east: WEST + COLUMNS as f64 * RESOLUTION,
+        resolution_x: RESOLUTION,
+        resolution_y: RESOLUTION,
+        data_type: DataType::F32,
+        photometric_interp: PhotometricInterpretation::Continuous,
+        epsg_code: EPSG,
+        coordinate_ref_system_wkt: esri_wkt_from_epsg(EPSG),
+        ..Default::default()
+    };
+    let mut raster = Raster::initialize_using_config(&data.path(PLANE), &configs);
+    for row in 0..ROWS {
+        for col in 0..COLUMNS {
+            let z = if NODATA_BLOCK.contains(&row) && NODATA_BLOCK.contains(&col) {
+                NODATA
+            } else {
+                let x = raster.get_x_from_column(col as isize);
+                let y = raster.get_y_from_row(row as isize);
+                plane_value(x, y)
+            };
+            raster.set_value(row as isize, col as isize, z);
+        }
+    }
+    raster.write().expect("Error writing the plane raster.");
+}
+
+fn run(data: &TestData, tool_name: &str, args: &[&str]) {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    if let Err(e) = data.run_tool(tool_name, &args) {
+        panic!("{} failed: {}", tool_name, e);
+    }
+}
+
+fn assert_near(value: f64, expected: f64, tolerance: f64, what: &str) {
+    assert!(
+        (value - expected).abs() <= tolerance,
+        "{} is {}, not {}",
+        what,
+        value,
+        expected
+    );
+}
+
+#[test]
+fn reproject_raster_to_geographic_covers_the_input() {
+    let data = TestData::new("reproject_raster_geographic");
+    write_plane(&data);
+    run(
+        &data,
+        "ReprojectRaster",
+        &[
+            "--input=plane.tif",
+            "--output=geographic.tif",
+            "--epsg=4326",
+            "--method=bilinear",
+        ],
+    );
+
+    let output = Raster::new(&data.path("geographic.tif"), "r").expect("Error reading output.");
+    let configs = &output.configs;
+    assert_eq!(configs.epsg_code, 4326);
+
+    // the grid starts at the north-west corner and covers the south-east corner
+    assert_near(configs.west, NW_GEOGRAPHIC.0, DEGREE_TOLERANCE, "west");
+    assert_near(configs.north, NW_GEOGRAPHIC.1, DEGREE_TOLERANCE, "north");
+    let cell_size = configs.resolution_x;
+    assert_eq!(configs.resolution_y, cell_size);
+    assert!(configs.east >= NE_LONGITUDE - DEGREE_TOLERANCE);
+    assert!(configs.east - cell_size < NE_LONGITUDE + DEGREE_TOLERANCE);
+    assert!(configs.south <= SE_GEOGRAPHIC.1 + DEGREE_TOLERANCE);
+    assert!(configs.south + cell_size > SE_GEOGRAPHIC.1 - DEGREE_TOLERANCE);
+
+    // by default, the cell size keeps the number of cells of the input
+    let num_cells = configs.rows * configs.columns;
+    assert!(
+        num_cells >= ROWS * COLUMNS && num_cells <= (ROWS + 1) * (COLUMNS + 1),
+        "{} x {} cells",
+        configs.rows,
+        configs.columns
+    );
+}
+
+#[test]
+fn reproject_raster_uses_the_specified_cell_size() {
+    let data = TestData::new("reproject_raster_cell_size");
+    write_plane(&data);
+    let cell_size = 0.000_05;
+    run(
+        &data,
+        "ReprojectRaster",
+        &[
+            "--input=plane.tif",
+            "--output=geographic.tif",
+            "--epsg=4326",
+            "--cell_size=0.00005",
+        ],
+    );
+
+    let output = Raster::new(&data.path("geographic.tif"), "r").expect("Error reading output.");
+    let configs = &output.configs;
+    assert_eq!(configs.resolution_x, cell_size);
+    assert_eq!(configs.resolution_y, cell_size);
+    let width = NE_LONGITUDE - NW_GEOGRAPHIC.0;
+    let height = NW_GEOGRAPHIC.1 - SE_GEOGRAPHIC.1;
+    assert_eq!(configs.columns, (width / cell_size).ceil() as usize);
+    assert_eq!(configs.rows, (height / cell_size).ceil() as usize);
+}
+
+#[test]
+fn reproject_raster_round_trip_keeps_values_and_nodata() {
+    let data = TestData::new("reproject_raster_round_trip");
+    write_plane(&data);
+    run(
+        &data,
+        "ReprojectRaster",
+        &[
+            "--input=plane.tif",
+            "--output=geographic.tif",
+            "--epsg=4326",
+            "--method=bilinear",
+        ],
+    );
+    run(
+        &data,
+        "ReprojectRaster",
+        &[
+            "--input=geographic.tif",
+            "--output=utm.tif",
+            "--epsg=32617",
+            "--method=bilinear",
+            "--cell_size=10.0",
+        ],
+    );
+
+    let input = Raster::new(&data.path(PLANE), "r").expect("Error reading input.");
+    let output = Raster::new(&data.path("utm.tif"), "r").expect("Error reading output.");
+    let configs = &output.configs;
+    assert_eq!(configs.epsg_code, EPSG);
+    assert_eq!(configs.resolution_x, RESOLUTION);
+    assert_eq!(configs.resolution_y, RESOLUTION);
+
+    // The geographic grid extends up to a cell beyond the south and east edges of the input,
+    // and those cells are NoData, so the round trip may add a row and column of NoData there.
+    assert_near(configs.west, NW_UTM.0, 0.01, "west");
+    assert_near(configs.north, NW_UTM.1, 0.01, "north");
+    assert!(configs.east >= SE_UTM.0 - 0.01 && configs.east <= SE_UTM.0 + 2.0 * RESOLUTION);
+    assert!(configs.south <= SE_UTM.1 + 0.01 && configs.south >= SE_UTM.1 - 2.0 * RESOLUTION);
+
+    let (mut num_valid, mut num_nodata) = (0, 0);
+    for row in 0..configs.rows as isize {
+        for col in 0..configs.columns as isize {
+            let x = output.get_x_from_column(col);
+            let y = output.get_y_from_row(row);
+            let row_in = input.get_row_from_y(y);
+            let col_in = input.get_column_from_x(x);
+            let z = output.get_value(row, col);
+            let in_block = |r: isize, c: isize| {
+                NODATA_BLOCK.contains(&(r as usize)) && NODATA_BLOCK.contains(&(c as usize))
+            };
+            // cells two or more cells away from the NoData block and the edges
+            let interior = (row_in - 2..=row_in + 2).all(|r| {
+                (col_in - 2..=col_in + 2).all(|c| {
+                    r >= 0 && c >= 0 && r < ROWS as isize && c < COLUMNS as isize && !in_block(r, c)
+                })
+            });
+            if row_in > 10 && row_in < 16 && col_in > 10 && col_in < 16 {
+                assert_eq!(z, NODATA, "({}, {}) is within the NoData block", row, col);
+                num_nodata += 1;
+            } else if interior {
+                assert_near(z, plane_value(x, y), 0.01, &format!("({}, {})", row, col));
+                num_valid += 1;
+            }
+        }
+    }
+    assert_eq!(num_nodata, 25);
+    assert!(num_valid > 400, "only {} interior cells", num_valid);
+}
+
+/// The corners of the synthetic grid, as points.
+fn write_corners(data: &TestData) {
+    let mut output = Shapefile::new(&data.path("corners.shp"), ShapeType::Point)
+        .expect("Error creating the corners.");
+    output.projection = esri_wkt_from_epsg(EPSG);
+    output
+        .attributes
+        .add_field(&AttributeField::new("ID", FieldDataType::Int, 4, 0));
+    for (i, (x, y)) in [NW_UTM, SE_UTM].iter().enumerate() {
+        output.add_point_record(*x, *y);
+        output
+            .attributes
+            .add_record(vec![FieldData::Int(i as i32 + 1)], false);
+    }
+    output.write().expect("Error writing the corners.");
+}
+
+#[test]
+fn reproject_vector_transforms_known_points() {
+    let data = TestData::new("reproject_vector_points");
+    write_corners(&data);
+    run(
+        &data,
+        "ReprojectVector",
+        &[
+            "--input=corners.shp",
+            "--output=geographic.shp",
+            "--epsg=4326",
+        ],
+    );
+
+    let output = Shapefile::read(&data.path("geographic.shp")).expect("Error reading output.");
+    assert_eq!(output.projection.trim(), esri_wkt_from_epsg(4326));
+    assert_eq!(output.num_records, 2);
+    for (i, expected) in [NW_GEOGRAPHIC, SE_GEOGRAPHIC].iter().enumerate() {
+        let p = output.get_record(i).points[0];
+        assert_near(p.x, expected.0, DEGREE_TOLERANCE, "longitude");
+        assert_near(p.y, expected.1, DEGREE_TOLERANCE, "latitude");
+    }
+}
+
+#[test]
+fn reproject_vector_round_trip_keeps_geometries_and_attributes() {
+    let data = TestData::new("reproject_vector_round_trip");
+    for file_name in [POINTS, POLYGONS] {
+        run(
+            &data,
+            "ReprojectVector",
+            &[
+                &format!("--input={}", file_name),
+                "--output=geographic.shp",
+                "--epsg=4326",
+            ],
+        );
+        run(
+            &data,
+            "ReprojectVector",
+            &["--input=geographic.shp", "--output=utm.shp", "--epsg=32617"],
+        );
+
+        let input = Shapefile::read(&data.path(file_name)).expect("Error reading input.");
+        let output = Shapefile::read(&data.path("utm.shp")).expect("Error reading output.");
+        assert_eq!(output.projection.trim(), esri_wkt_from_epsg(EPSG));
+        assert_eq!(output.header.shape_type, input.header.shape_type);
+        assert_eq!(output.num_records, input.num_records);
+        for i in 0..input.num_records {
+            let (a, b) = (input.get_record(i), output.get_record(i));
+            assert_eq!(a.parts, b.parts, "{} record {}", file_name, i);
+            assert_eq!(a.points.len(), b.points.len(), "{} record {}", file_name, i);
+            for (p, q) in a.points.iter().zip(b.points.iter()) {
+                assert_near(q.x, p.x, 1e-6, "x");
+                assert_near(q.y, p.y, 1e-6, "y");
+            }
+            if input.header.shape_type != ShapeType::Point {
+                assert_near(b.x_min, a.x_min, 1e-6, "x_min");
+                assert_near(b.y_max, a.y_max, 1e-6, "y_max");
+            }
+            assert_eq!(
+                input.attributes.get_record(i),
+                output.attributes.get_record(i),
+                "{} record {}",
+                file_name,
+                i
+            );
+        }
+    }
+}
+
+#[test]
+fn reproject_lidar_round_trip_keeps_points() {
+    let data = TestData::new("reproject_lidar_round_trip");
+    run(
+        &data,
+        "ReprojectLidar",
+        &[
+            "--input=points.las",
+            "--output=geographic.las",
+            "--epsg=4326",
+        ],
+    );
+    run(
+        &data,
+        "ReprojectLidar",
+        &["--input=geographic.las", "--output=utm.las", "--epsg=32617"],
+    );
+
+    let input = LasFile::new(&data.path(LIDAR), "r").expect("Error reading input.");
+    let mut geographic =
+        LasFile::new(&data.path("geographic.las"), "r").expect("Error reading output.");
+    assert_eq!(geographic.get_wkt(), esri_wkt_from_epsg(4326));
+    assert_eq!(geographic.header.x_scale_factor, 1e-7);
+    assert_eq!(geographic.header.y_scale_factor, 1e-7);
+    // all of the points lie within the grid
+    let header = &geographic.header;
+    assert!(header.min_x >= NW_GEOGRAPHIC.0 - DEGREE_TOLERANCE);
+    assert!(header.max_x <= NE_LONGITUDE + DEGREE_TOLERANCE);
+    assert!(header.min_y >= SE_GEOGRAPHIC.1 - DEGREE_TOLERANCE);
+    assert!(header.max_y <= NW_GEOGRAPHIC.1 + DEGREE_TOLERANCE);
+
+    let mut output = LasFile::new(&data.path("utm.las"), "r").expect("Error reading output.");
+    assert_eq!(output.get_wkt(), esri_wkt_from_epsg(EPSG));
+    let num_points = input.header.number_of_points as usize;
+    assert_eq!(output.header.number_of_points as usize, num_points);
+    for i in 0..num_points {
+        let (p, q) = (
+            input.get_transformed_coords(i),
+            output.get_transformed_coords(i),
+        );
+        // the geographic coordinates are stored to 1e-7 degrees, or about a centimetre
+        assert_near(q.x, p.x, 0.01, "x");
+        assert_near(q.y, p.y, 0.01, "y");
+        assert_eq!(q.z, p.z);
+        let (a, b) = (input.get_point_info(i), output.get_point_info(i));
+        assert_eq!(a.classification(), b.classification());
+        assert_eq!(a.intensity, b.intensity);
+    }
+}
@@ -0,0 +1,63 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Runs the tools that share a raster with their worker threads through an `Arc` many times.
+//! A worker that still held its clone of the `Arc` after sending its last result made these
+//! tools panic intermittently when they unwrapped it, so a single run rarely shows the race.
+
+mod common;
+
+use common::*;
+
+const RUNS: usize = 25;
+
+fn run_repeatedly(test_name: &str, tool_name: &str, inputs: &[&str]) {
+    let data = TestData::new(test_name);
+    let mut args: Vec<String> = inputs.iter().map(|a| a.to_string()).collect();
+    args.push("--output=output.tif".to_string());
+    for run in 0..RUNS {
+        if let Err(e) = data.run_tool(tool_name, &args) {
+            panic!("{} failed on run {}: {}", tool_name, run + 1, e);
+        }
+    }
+}
+
+#[test]
+fn breach_depressions_least_cost_runs_repeatedly() {
+    // the raster is only shared when pits are left to fill, which a breach distance of one
+    // cell ensures
+    run_repeatedly(
+        "shared_breach",
+        "BreachDepressionsLeastCost",
+        &["--dem=dem.tif", "--dist=1", "--fill"],
+    );
+}
+
+#[test]
+fn depth_in_sink_runs_repeatedly() {
+    run_repeatedly("shared_depth_in_sink", "DepthInSink", &["--dem=dem.tif"]);
+}
+
+#[test]
+fn fill_depressions_runs_repeatedly() {
+    run_repeatedly("shared_fill", "FillDepressions", &["--dem=dem.tif"]);
+}
+
+#[test]
+fn remove_off_terrain_objects_runs_repeatedly() {
+    run_repeatedly(
+        "shared_otos",
+        "RemoveOffTerrainObjects",
+        &["--input=dem.tif", "--filter=5", "--slope=15.0"],
+    );
+}
+
+#[test]
+fn sink_runs_repeatedly() {
+    run_repeatedly("shared_sink", "Sink", &["--input=dem.tif"]);
+}
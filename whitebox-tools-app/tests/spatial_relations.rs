@@ -0,0 +1,410 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests the tools that relate the features of one vector file to those of another, on small
+//! layers of points and polygons whose relations are known.
+
+use std::fs;
+use std::path::PathBuf;
+use whitebox_common::structures::Point2D;
+use whitebox_tools::tools::ToolManager;
+use whitebox_vector::*;
+
+fn test_dir(name: &str) -> String {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "whitebox_spatial_relations_test_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("Error creating the test directory.");
+    format!("{}{}", dir.to_string_lossy(), std::path::MAIN_SEPARATOR)
+}
+
+/// Writes a file of points, with an ID field numbering them from 1, and a VALUE field of ten
+/// times the ID.
+fn write_points(file_name: &str, points: &[(f64, f64)]) {
+    let mut output = Shapefile::new(file_name, ShapeType::Point).expect("Error creating the file.");
+    output
+        .attributes
+        .add_field(&AttributeField::new("ID", FieldDataType::Int, 7u8, 0u8));
+    output
+        .attributes
+        .add_field(&AttributeField::new("VALUE", FieldDataType::Real, 12u8, 4u8));
+    for (i, (x, y)) in points.iter().enumerate() {
+        output.add_point_record(*x, *y);
+        output.attributes.add_record(
+            vec![FieldData::Int(i as i32 + 1), FieldData::Real(10.0 * (i + 1) as f64)],
+            false,
+        );
+    }
+    output.write().expect("Error writing the file.");
+}
+
+/// Writes a file of squares, given by their lower-left corners and sizes, with an ID field
+/// numbering them from 1.
+fn write_squares(file_name: &str, squares: &[(f64, f64, f64)]) {
+    let mut output =
+        Shapefile::new(file_name, ShapeType::Polygon).expect("Error creating the file.");
+    output
+        .attributes
+        .add_field(&AttributeField::new("ID", FieldDataType::Int, 7u8, 0u8));
+    for (i, (x, y, size)) in squares.iter().enumerate() {
+        // a clockwise exterior ring
+        let ring = [
+            Point2D::new(*x, *y + size),
+            Point2D::new(*x + size, *y + size),
+            Point2D::new(*x + size, *y),
+            Point2D::new(*x, *y),
+            Point2D::new(*x, *y + size),
+        ];
+        let mut geometry = ShapefileGeometry::new(ShapeType::Polygon);
+        geometry.add_part(&ring);
+        output.add_record(geometry);
+        output
+            .attributes
+            .add_record(vec![FieldData::Int(i as i32 + 1)], false);
+    }
+    output.write().expect("Error writing the file.");
+}
+
+fn run(dir: &str, tool_name: &str, args: &[&str]) {
+    let tm = ToolManager::new(dir, &false).expect("Error creating the ToolManager.");
+    tm.run_tool(
+        tool_name.to_string(),
+        args.iter().map(|a| a.to_string()).collect(),
+    )
+    .expect("Error running the tool.");
+}
+
+fn read(dir: &str, file_name: &str) -> Shapefile {
+    Shapefile::read(&format!("{}{}", dir, file_name)).expect("Error reading the output.")
+}
+
+fn field_values(file: &Shapefile, field_name: &str) -> Vec<FieldData> {
+    (0..file.num_records)
+        .map(|record_num| file.attributes.get_value(record_num, field_name))
+        .collect()
+}
+
+/// Reads the rows of a CSV table, less its header.
+fn read_table(file_name: &str) -> Vec<Vec<f64>> {
+    let table = fs::read_to_string(file_name).expect("Error reading the table.");
+    table
+        .lines()
+        .skip(1)
+        .map(|line| {
+            line.split(',')
+                .map(|v| v.parse::<f64>().expect("Error parsing the table."))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn near_feature_table_lists_the_k_nearest_features() {
+    let dir = test_dir("near_table");
+    write_points(&format!("{}input.shp", dir), &[(0.0, 0.0)]);
+    // the first and fourth points are equally near, and the first of them ranks first
+    write_points(
+        &format!("{}near.shp", dir),
+        &[(3.0, 0.0), (0.0, 5.0), (10.0, 0.0), (-3.0, 0.0)],
+    );
+    run(
+        &dir,
+        "NearFeature",
+        &[
+            "-i=input.shp",
+            "--near=near.shp",
+            "-o=output.shp",
+            "--table=nearest.csv",
+            "--k=3",
+        ],
+    );
+    let rows = read_table(&format!("{}nearest.csv", dir));
+    assert_eq!(rows.len(), 3);
+    // FID, RANK, NEAR_FID, NEAR_DIST, NEAR_ANGLE, FROM_X, FROM_Y, NEAR_X, NEAR_Y
+    assert_eq!(rows[0], vec![1.0, 1.0, 1.0, 3.0, 90.0, 0.0, 0.0, 3.0, 0.0]);
+    assert_eq!(rows[1], vec![1.0, 2.0, 4.0, 3.0, 270.0, 0.0, 0.0, -3.0, 0.0]);
+    assert_eq!(rows[2], vec![1.0, 3.0, 2.0, 5.0, 0.0, 0.0, 0.0, 0.0, 5.0]);
+
+    let output = read(&dir, "output.shp");
+    assert_eq!(field_values(&output, "NEAR_FID"), vec![FieldData::Int(1)]);
+}
+
+#[test]
+fn near_feature_excludes_each_feature_from_its_own_nearest() {
+    let dir = test_dir("near_self");
+    let points = [(0.0, 0.0), (4.0, 0.0), (10.0, 0.0)];
+    write_points(&format!("{}points.shp", dir), &points);
+    write_points(&format!("{}points.gpkg", dir), &points);
+    let inputs = [
+        ("points.shp", "points.shp"),
+        ("points.gpkg", "points.gpkg"),
+        ("points.gpkg:points", "points.gpkg:POINTS"),
+        ("points.gpkg", "points.gpkg:points"),
+    ];
+    for (input, near) in inputs {
+        run(
+            &dir,
+            "NearFeature",
+            &[
+                &format!("-i={}", input),
+                &format!("--near={}", near),
+                "-o=output.shp",
+            ],
+        );
+        let output = read(&dir, "output.shp");
+        assert_eq!(
+            field_values(&output, "NEAR_FID"),
+            vec![FieldData::Int(2), FieldData::Int(1), FieldData::Int(2)],
+            "{} {}",
+            input,
+            near
+        );
+        assert_eq!(
+            field_values(&output, "NEAR_DIST"),
+            vec![
+                FieldData::Real(4.0),
+                FieldData::Real(4.0),
+                FieldData::Real(6.0)
+            ]
+        );
+    }
+}
+
+#[test]
+fn near_feature_max_dist_leaves_null_fields() {
+    let dir = test_dir("near_max_dist");
+    write_points(&format!("{}input.shp", dir), &[(0.0, 0.0), (100.0, 0.0)]);
+    write_points(&format!("{}near.shp", dir), &[(5.0, 0.0)]);
+    run(
+        &dir,
+        "NearFeature",
+        &[
+            "-i=input.shp",
+            "--near=near.shp",
+            "-o=output.shp",
+            "--max_dist=10.0",
+            "--table=nearest.csv",
+            "--k=2",
+        ],
+    );
+    let output = read(&dir, "output.shp");
+    assert_eq!(output.num_records, 2);
+    assert_eq!(output.attributes.get_value(0, "NEAR_FID"), FieldData::Int(1));
+    assert_eq!(output.attributes.get_value(0, "NEAR_DIST"), FieldData::Real(5.0));
+    for field_name in ["NEAR_FID", "NEAR_DIST", "NEAR_ANGLE", "NEAR_X", "NEAR_Y"] {
+        assert_eq!(
+            output.attributes.get_value(1, field_name),
+            FieldData::Null,
+            "{}",
+            field_name
+        );
+    }
+    // the second feature has no row in the table
+    let rows = read_table(&format!("{}nearest.csv", dir));
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0][..4], [1.0, 1.0, 1.0, 5.0]);
+}
+
+#[test]
+fn spatial_join_one_to_many_copies_targets_for_each_match() {
+    let dir = test_dir("join_one_to_many");
+    write_squares(
+        &format!("{}squares.shp", dir),
+        &[(0.0, 0.0, 10.0), (20.0, 0.0, 10.0), (40.0, 0.0, 10.0)],
+    );
+    write_points(
+        &format!("{}points.shp", dir),
+        &[(5.0, 5.0), (25.0, 5.0), (2.0, 2.0), (100.0, 100.0)],
+    );
+    run(
+        &dir,
+        "SpatialJoin",
+        &[
+            "-i=squares.shp",
+            "--join=points.shp",
+            "-o=output.shp",
+            "--predicate=contains",
+            "--join_operation=one_to_many",
+        ],
+    );
+    let output = read(&dir, "output.shp");
+    // the joined ID field is renamed, as the squares already have one
+    let field_names: Vec<String> = output
+        .attributes
+        .get_fields()
+        .iter()
+        .map(|f| f.name.clone())
+        .collect();
+    assert_eq!(field_names, vec!["ID", "JOIN_FID", "ID_1", "VALUE"]);
+    let ints = |values: &[i32]| values.iter().map(|v| FieldData::Int(*v)).collect::<Vec<_>>();
+    assert_eq!(field_values(&output, "ID"), ints(&[1, 1, 2, 3]));
+    // the square without any points is kept, with null join attributes
+    let mut expected = ints(&[1, 3, 2]);
+    expected.push(FieldData::Null);
+    assert_eq!(field_values(&output, "JOIN_FID"), expected);
+    assert_eq!(field_values(&output, "ID_1"), expected);
+    assert_eq!(
+        field_values(&output, "VALUE"),
+        vec![
+            FieldData::Real(10.0),
+            FieldData::Real(30.0),
+            FieldData::Real(20.0),
+            FieldData::Null
+        ]
+    );
+}
+
+#[test]
+fn spatial_join_nearest_adds_join_dist_and_breaks_ties() {
+    let dir = test_dir("join_nearest");
+    write_points(&format!("{}targets.shp", dir), &[(0.0, 0.0), (80.0, 0.0)]);
+    // the first two points are equally near the first target, and the first of them is joined
+    write_points(
+        &format!("{}points.shp", dir),
+        &[(3.0, 0.0), (-3.0, 0.0), (0.0, 4.0), (100.0, 0.0)],
+    );
+    run(
+        &dir,
+        "SpatialJoin",
+        &[
+            "-i=targets.shp",
+            "--join=points.shp",
+            "-o=output.shp",
+            "--predicate=nearest",
+        ],
+    );
+    let output = read(&dir, "output.shp");
+    assert_eq!(
+        field_values(&output, "JOIN_COUNT"),
+        vec![FieldData::Int(1), FieldData::Int(1)]
+    );
+    assert_eq!(
+        field_values(&output, "JOIN_DIST"),
+        vec![FieldData::Real(3.0), FieldData::Real(20.0)]
+    );
+    assert_eq!(
+        field_values(&output, "ID_1"),
+        vec![FieldData::Int(1), FieldData::Int(4)]
+    );
+
+    // the second target has no join feature within the search distance
+    run(
+        &dir,
+        "SpatialJoin",
+        &[
+            "-i=targets.shp",
+            "--join=points.shp",
+            "-o=output.shp",
+            "--predicate=nearest",
+            "--dist=10.0",
+        ],
+    );
+    let output = read(&dir, "output.shp");
+    assert_eq!(
+        field_values(&output, "JOIN_COUNT"),
+        vec![FieldData::Int(1), FieldData::Int(0)]
+    );
+    assert_eq!(
+        field_values(&output, "JOIN_DIST"),
+        vec![FieldData::Real(3.0), FieldData::Null]
+    );
+}
+
+#[test]
+fn spatial_join_within_distance_summarizes_the_selected_fields() {
+    let dir = test_dir("join_within_distance");
+    write_points(&format!("{}targets.shp", dir), &[(0.0, 0.0)]);
+    write_points(
+        &format!("{}points.shp", dir),
+        &[(3.0, 0.0), (0.0, 4.0), (10.0, 0.0)],
+    );
+    run(
+        &dir,
+        "SpatialJoin",
+        &[
+            "-i=targets.shp",
+            "--join=points.shp",
+            "-o=output.shp",
+            "--predicate=within_distance",
+            "--dist=5.0",
+            "--aggregate=sum",
+            "--fields=VALUE",
+        ],
+    );
+    let output = read(&dir, "output.shp");
+    assert!(output.attributes.get_field_num("ID_1").is_none());
+    assert_eq!(output.attributes.get_value(0, "JOIN_COUNT"), FieldData::Int(2));
+    assert_eq!(output.attributes.get_value(0, "VALUE_1"), FieldData::Real(30.0));
+}
+
+#[test]
+fn select_by_location_touches() {
+    let dir = test_dir("select_touches");
+    // the first square shares an edge with the selecting square, the second overlaps it, the
+    // third is apart from it, and the fourth has a corner on its edge
+    write_squares(
+        &format!("{}squares.shp", dir),
+        &[
+            (0.0, 0.0, 10.0),
+            (5.0, 0.0, 10.0),
+            (25.0, 0.0, 5.0),
+            (20.0, 5.0, 5.0),
+        ],
+    );
+    write_squares(&format!("{}select.shp", dir), &[(10.0, 0.0, 10.0)]);
+    run(
+        &dir,
+        "SelectByLocation",
+        &[
+            "-i=squares.shp",
+            "--select=select.shp",
+            "-o=output.shp",
+            "--relation=touches",
+        ],
+    );
+    let output = read(&dir, "output.shp");
+    assert_eq!(
+        field_values(&output, "ID"),
+        vec![FieldData::Int(1), FieldData::Int(4)]
+    );
+}
+
+#[test]
+fn select_by_location_within_distance_and_invert() {
+    let dir = test_dir("select_within_distance");
+    // the points are 5, 2, 20, and 0 units from the selecting square
+    write_points(
+        &format!("{}points.shp", dir),
+        &[(5.0, 5.0), (22.0, 5.0), (40.0, 5.0), (15.0, 5.0)],
+    );
+    write_squares(&format!("{}select.shp", dir), &[(10.0, 0.0, 10.0)]);
+    let args = [
+        "-i=points.shp",
+        "--select=select.shp",
+        "-o=output.shp",
+        "--relation=within_distance",
+        "--dist=3.0",
+    ];
+    run(&dir, "SelectByLocation", &args);
+    let output = read(&dir, "output.shp");
+    assert_eq!(
+        field_values(&output, "ID"),
+        vec![FieldData::Int(2), FieldData::Int(4)]
+    );
+
+    let mut args = args.to_vec();
+    args.push("--invert");
+    run(&dir, "SelectByLocation", &args);
+    let output = read(&dir, "output.shp");
+    assert_eq!(
+        field_values(&output, "ID"),
+        vec![FieldData::Int(1), FieldData::Int(3)]
+    );
+}
@@ -0,0 +1,87 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests tiled processing (--tile_size and --halo) with the whitebox_tools executable. Running
+//! a focal tool in tiles, with a halo of at least the radius of its filter, must give the same
+//! raster as running it on the whole grid.
+
+mod common;
+
+use common::{TestData, DEM};
+use std::process::Command;
+use whitebox_raster::Raster;
+
+fn run(tool: &str, args: &[String], tiling: Option<(usize, usize)>) {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_whitebox_tools"));
+    command.arg(format!("--run={}", tool)).args(args);
+    if let Some((tile_size, halo)) = tiling {
+        command
+            .arg(format!("--tile_size={}", tile_size))
+            .arg(format!("--halo={}", halo));
+    }
+    let output = command.output().expect("Error running whitebox_tools.");
+    assert!(
+        output.status.success(),
+        "{} {:?}: {}",
+        tool,
+        tiling,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// Returns the number of cells that differ between two rasters, which must cover the same grid.
+fn num_differences(file_name1: &str, file_name2: &str) -> usize {
+    let r1 = Raster::new(file_name1, "r").expect("Error reading the raster.");
+    let r2 = Raster::new(file_name2, "r").expect("Error reading the raster.");
+    assert_eq!(r1.configs.rows, r2.configs.rows);
+    assert_eq!(r1.configs.columns, r2.configs.columns);
+    assert_eq!(r1.configs.north, r2.configs.north);
+    assert_eq!(r1.configs.west, r2.configs.west);
+    assert_eq!(r1.configs.nodata, r2.configs.nodata);
+    assert_eq!(r1.configs.data_type, r2.configs.data_type);
+    let mut num_different = 0;
+    for row in 0..r1.configs.rows as isize {
+        for col in 0..r1.configs.columns as isize {
+            if r1.get_value(row, col) != r2.get_value(row, col) {
+                num_different += 1;
+            }
+        }
+    }
+    num_different
+}
+
+#[test]
+fn tiled_run_matches_untiled_run() {
+    let data = TestData::new("tiling");
+    let dem = data.path(DEM);
+    // The 32 x 32 cell DEM is split into 10 x 10 cell tiles, and so the last row and column
+    // of tiles are partial. The DEM also has NoData cells. Each case gives the radius of the
+    // tool's neighbourhood, which is 2 for Slope's 5 x 5 polynomial fit.
+    let cases: [(&str, Vec<String>, usize); 2] = [
+        ("Slope", vec![], 2),
+        ("MaximumFilter", vec!["--filterx=7".to_string(), "--filtery=3".to_string()], 3),
+    ];
+    for (tool, tool_args, radius) in cases {
+        let file = |name: &str| data.path(&format!("{}_{}.tif", tool, name));
+        let args = |output: &str| {
+            let mut args = vec![format!("-i={}", dem), format!("-o={}", output)];
+            args.extend(tool_args.iter().cloned());
+            args
+        };
+        run(tool, &args(&file("untiled")), None);
+        for halo in [radius, radius + 2] {
+            let tiled = file(&format!("tiled_{}", halo));
+            run(tool, &args(&tiled), Some((10, halo)));
+            assert_eq!(num_differences(&tiled, &file("untiled")), 0, "{} halo={}", tool, halo);
+        }
+        // Without a halo, the cells along the tile edges differ.
+        let no_halo = file("no_halo");
+        run(tool, &args(&no_halo), Some((10, 0)));
+        assert!(num_differences(&no_halo, &file("untiled")) > 0, "{}", tool);
+    }
+}
@@ -0,0 +1,110 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests running workflows with the whitebox_tools executable: a successful workflow creates
+//! its outputs and deletes its intermediate files, steps that are up to date are not run
+//! again, and a failed step stops the steps that depend on it and sets the exit code.
+
+mod common;
+
+use common::TestData;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn run_workflow(data: &TestData, name: &str, workflow: serde_json::Value) -> Output {
+    let file_name = data.path(&format!("{}.json", name));
+    fs::write(&file_name, workflow.to_string()).expect("Error writing the workflow file.");
+    Command::new(env!("CARGO_BIN_EXE_whitebox_tools"))
+        .arg(format!("--workflow={}", file_name))
+        .output()
+        .expect("Error running whitebox_tools.")
+}
+
+#[test]
+fn workflow_creates_outputs_and_deletes_intermediates() {
+    let data = TestData::new("workflow");
+    let workflow = json!({
+        "working_directory": data.working_dir(),
+        "max_procs": 2,
+        "steps": [
+            { "name": "pointer", "tool": "D8Pointer",
+              "args": { "dem": "@breached.tif", "output": "pointer_out.tif" } },
+            { "name": "breach", "tool": "BreachDepressionsLeastCost",
+              "args": { "dem": "dem.tif", "output": "@breached.tif", "dist": 20, "fill": true } },
+            { "name": "slope", "tool": "Slope",
+              "args": { "dem": "@breached.tif", "output": "slope_out.tif" } }
+        ]
+    });
+    let output = run_workflow(&data, "hydro", workflow.clone());
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let outputs = [data.path("pointer_out.tif"), data.path("slope_out.tif")];
+    for file in &outputs {
+        assert!(Path::new(file).exists(), "{}", file);
+    }
+    assert!(!Path::new(&data.path("hydro_intermediates")).exists());
+
+    // The outputs are newer than the DEM, and so the steps are not run again, even though the
+    // intermediate file that they were created from has been deleted.
+    let modified = |file: &String| fs::metadata(file).unwrap().modified().unwrap();
+    let before = outputs.iter().map(modified).collect::<Vec<_>>();
+    let output = run_workflow(&data, "hydro", workflow);
+    assert!(output.status.success());
+    assert_eq!(outputs.iter().map(modified).collect::<Vec<_>>(), before);
+}
+
+#[test]
+fn failed_step_stops_dependent_steps() {
+    let data = TestData::new("workflow_failure");
+    fs::write(data.path("not_a_tiff.tif"), "not a GeoTIFF").unwrap();
+    let output = run_workflow(
+        &data,
+        "failure",
+        json!({
+            "working_directory": data.working_dir(),
+            "max_procs": 1,
+            "steps": [
+                { "name": "slope", "tool": "Slope",
+                  "args": { "dem": "not_a_tiff.tif", "output": "@slope.tif" } },
+                { "name": "pointer", "tool": "D8Pointer",
+                  "args": { "dem": "@slope.tif", "output": "pointer_out.tif" } }
+            ]
+        }),
+    );
+    // The exit code is that of the failed step.
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Workflow step 'slope' (Slope) failed"),
+        "{}",
+        stderr
+    );
+    assert!(!Path::new(&data.path("pointer_out.tif")).exists());
+
+    // An invalid workflow is rejected before any step is run.
+    let output = run_workflow(
+        &data,
+        "invalid",
+        json!({
+            "working_directory": data.working_dir(),
+            "steps": [
+                { "name": "slope", "tool": "Slope",
+                  "args": { "dem": "dem.tif", "output": "slope_out.tif" } },
+                { "name": "pointer", "tool": "D8Pointer",
+                  "args": { "dem": "@missing.tif", "output": "pointer_out.tif" } }
+            ]
+        }),
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(!Path::new(&data.path("slope_out.tif")).exists());
+}
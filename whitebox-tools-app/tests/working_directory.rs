@@ -0,0 +1,112 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+//! Tests that tools find input files that are named without a directory in the working
+//! directory. Each of these tools resolved some of its inputs against the directory that
+//! the process was started in instead, which is never the test data directory.
+
+mod common;
+
+use common::*;
+use whitebox_vector::Shapefile;
+
+fn run_in_working_directory(test_name: &str, tool_name: &str, args: &[&str], output: &str) {
+    let data = TestData::new(test_name);
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    if let Err(e) = data.run_tool(tool_name, &args) {
+        panic!("{} failed: {}", tool_name, e);
+    }
+    assert!(
+        data.dir.join(output).exists(),
+        "{} did not create {}",
+        tool_name,
+        output
+    );
+}
+
+#[test]
+fn embankment_mapping_reads_roads_from_the_working_directory() {
+    run_in_working_directory(
+        "wd_embankment",
+        "EmbankmentMapping",
+        &[
+            "--dem=dem.tif",
+            "--road_vec=lines.shp",
+            "--output=output.tif",
+        ],
+        "output.tif",
+    );
+}
+
+#[test]
+fn extract_raster_values_at_points_reads_points_from_the_working_directory() {
+    let data = TestData::new("wd_extract_values");
+    let args = vec![
+        "--inputs=dem.tif".to_string(),
+        "--points=points.shp".to_string(),
+    ];
+    if let Err(e) = data.run_tool("ExtractRasterValuesAtPoints", &args) {
+        panic!("ExtractRasterValuesAtPoints failed: {}", e);
+    }
+    let points = Shapefile::read(&data.path(POINTS)).expect("Error reading the points.");
+    assert!(points.attributes.get_field_num("VALUE1").is_some());
+}
+
+#[test]
+fn find_parallel_flow_reads_streams_from_the_working_directory() {
+    run_in_working_directory(
+        "wd_parallel_flow",
+        "FindParallelFlow",
+        &[
+            "--d8_pntr=pointer.tif",
+            "--streams=streams.tif",
+            "--output=output.tif",
+        ],
+        "output.tif",
+    );
+}
+
+#[test]
+fn lidar_point_stats_reads_its_input_from_the_working_directory() {
+    run_in_working_directory(
+        "wd_lidar_point_stats",
+        "LidarPointStats",
+        &["--input=points.las", "--resolution=10.0", "--num_points"],
+        "points_num_pnts.tif",
+    );
+}
+
+#[test]
+fn multiscale_topographic_position_image_reads_inputs_from_the_working_directory() {
+    run_in_working_directory(
+        "wd_mtpi",
+        "MultiscaleTopographicPositionImage",
+        &[
+            "--local=dem.tif",
+            "--meso=dem.tif",
+            "--broad=dem.tif",
+            "--hillshade=dem.tif",
+            "--output=output.tif",
+        ],
+        "output.tif",
+    );
+}
+
+#[test]
+fn topological_stream_order_reads_streams_from_the_working_directory() {
+    run_in_working_directory(
+        "wd_topological_order",
+        "TopologicalStreamOrder",
+        &[
+            "--d8_pntr=pointer.tif",
+            "--streams=streams.tif",
+            "--output=output.tif",
+        ],
+        "output.tif",
+    );
+}
@@ -0,0 +1,760 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+Notes: Reading and writing of GeoJSON (RFC 7946) files. A GeoJSON FeatureCollection
+is mapped onto the same in-memory model that is used for Shapefiles, i.e. a set of
+ShapefileGeometry records of a single ShapeType and a ShapefileAttributes table. Field
+types are inferred from the JSON property values when reading, and written back out as
+the corresponding JSON types (numbers, strings, and booleans), such that a file can
+make a round trip without loss of its attribute types.
+*/
+
+use crate::shapefile::attributes::*;
+use crate::shapefile::geometry::*;
+use crate::shapefile::Shapefile;
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use whitebox_common::algorithms::is_clockwise_order;
+use whitebox_common::spatial_ref_system::{esri_wkt_from_epsg, SpatialReference};
+use whitebox_common::structures::Point2D;
+
+/// Reads the GeoJSON file pointed to by `sf.file_name` into the Shapefile.
+pub(crate) fn read_geojson(sf: &mut Shapefile) -> Result<(), Error> {
+    let contents = fs::read_to_string(&sf.file_name)?;
+    let root: Value = serde_json::from_str(&contents).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Error parsing GeoJSON file {}: {}", sf.file_name, e),
+        )
+    })?;
+
+    // A file may contain a FeatureCollection, a single Feature, or a bare geometry.
+    let features: Vec<Value> = match root.get("type").and_then(|t| t.as_str()) {
+        Some("FeatureCollection") => match root.get("features") {
+            Some(Value::Array(f)) => f.clone(),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The GeoJSON FeatureCollection does not contain a 'features' array.",
+                ))
+            }
+        },
+        Some("Feature") => vec![root.clone()],
+        Some(_) => {
+            let mut feature = Map::new();
+            feature.insert("type".to_string(), Value::from("Feature"));
+            feature.insert("geometry".to_string(), root.clone());
+            vec![Value::Object(feature)]
+        }
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The file does not appear to be a valid GeoJSON file.",
+            ))
+        }
+    };
+
+    // Decide on a single ShapeType for all of the records.
+    let mut base_type = ShapeType::Null;
+    let mut has_z = false;
+    for feature in &features {
+        if let Some(geom) = feature.get("geometry").filter(|g| !g.is_null()) {
+            let geom_type = geometry_base_type(geom)?;
+            base_type = match (base_type, geom_type) {
+                (ShapeType::Null, t) => t,
+                (a, b) if a == b => a,
+                (ShapeType::Point, ShapeType::MultiPoint)
+                | (ShapeType::MultiPoint, ShapeType::Point) => ShapeType::MultiPoint,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "GeoJSON files containing a mixture of point, line, and polygon geometries are not supported.",
+                    ))
+                }
+            };
+            if let Some(coords) = geom.get("coordinates") {
+                has_z = has_z || coordinates_have_z(coords);
+            }
+        }
+    }
+    if base_type == ShapeType::Null {
+        base_type = ShapeType::Point;
+    }
+    sf.header.shape_type = match (base_type, has_z) {
+        (ShapeType::Point, true) => ShapeType::PointZ,
+        (ShapeType::MultiPoint, true) => ShapeType::MultiPointZ,
+        (ShapeType::PolyLine, true) => ShapeType::PolyLineZ,
+        (ShapeType::Polygon, true) => ShapeType::PolygonZ,
+        (t, _) => t,
+    };
+
+    // Read the geometries.
+    for feature in &features {
+        let record = match feature.get("geometry").filter(|g| !g.is_null()) {
+            Some(geom) => read_geometry(geom, sf.header.shape_type)?,
+            None => ShapefileGeometry::new(ShapeType::Null),
+        };
+        sf.records.push(record);
+    }
+    sf.num_records = sf.records.len();
+    sf.projection = read_crs(&root, &sf.records);
+
+    // Read the attributes. The fields are ordered by their first appearance.
+    let mut field_names: Vec<String> = vec![];
+    let mut field_index: HashMap<String, usize> = HashMap::new();
+    for feature in &features {
+        if let Some(Value::Object(props)) = feature.get("properties") {
+            for key in props.keys() {
+                if !field_index.contains_key(key) {
+                    field_index.insert(key.clone(), field_names.len());
+                    field_names.push(key.clone());
+                }
+            }
+        }
+    }
+    let mut field_info = vec![FieldInfo::default(); field_names.len()];
+    for feature in &features {
+        if let Some(Value::Object(props)) = feature.get("properties") {
+            for (key, value) in props {
+                field_info[field_index[key]].update(value);
+            }
+        }
+    }
+    for (name, info) in field_names.iter().zip(field_info.iter()) {
+        sf.attributes.add_field(&info.to_field(name));
+    }
+    for feature in &features {
+        let mut rec = vec![FieldData::Null; field_names.len()];
+        if let Some(Value::Object(props)) = feature.get("properties") {
+            for (key, value) in props {
+                let i = field_index[key];
+                rec[i] = field_info[i].to_field_data(value);
+            }
+        }
+        sf.attributes.add_record(rec, false);
+    }
+
+    Ok(())
+}
+
+/// Writes the Shapefile's records and attributes to `sf.file_name` as a GeoJSON FeatureCollection.
+pub(crate) fn write_geojson(sf: &Shapefile) -> Result<(), Error> {
+    let f = File::create(&sf.file_name)?;
+    let mut writer = BufWriter::new(f);
+
+    writer.write_all(b"{\"type\":\"FeatureCollection\",")?;
+    if let Ok(srs) = SpatialReference::from_wkt(&sf.projection) {
+        // RFC 7946 assumes WGS84 coordinates; any other system is flagged using the
+        // (deprecated, but widely supported) 2008 'crs' member.
+        match srs.find_epsg() {
+            Some(4326) => {}
+            Some(epsg) => write!(
+                writer,
+                "\"crs\":{{\"type\":\"name\",\"properties\":{{\"name\":\"urn:ogc:def:crs:EPSG::{}\"}}}},",
+                epsg
+            )?,
+            None => println!(
+                "Warning: The EPSG code of the coordinate system ({}) is unknown, and so it cannot be recorded in the GeoJSON file {}.",
+                srs.name, sf.file_name
+            ),
+        }
+    }
+    writer.write_all(b"\"features\":[")?;
+
+    let fields = sf.attributes.get_fields();
+    for i in 0..sf.num_records {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        let mut properties = Map::new();
+        if i < sf.attributes.header.num_records as usize {
+            let rec = sf.attributes.get_record(i);
+            for (field, value) in fields.iter().zip(rec.iter()) {
+                properties.insert(field.name.clone(), field_data_to_json(field, value));
+            }
+        }
+        let mut feature = Map::new();
+        feature.insert("type".to_string(), Value::from("Feature"));
+        feature.insert("geometry".to_string(), geometry_to_json(&sf.records[i]));
+        feature.insert("properties".to_string(), Value::Object(properties));
+        serde_json::to_writer(&mut writer, &Value::Object(feature))?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.write_all(b"]}\n")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn read_crs(root: &Value, records: &[ShapefileGeometry]) -> String {
+    let name = root
+        .get("crs")
+        .and_then(|c| c.get("properties"))
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str());
+    let code = match name {
+        // e.g. 'urn:ogc:def:crs:EPSG::26917' or 'EPSG:26917'
+        Some(n) if n.contains("CRS84") => Some(4326u16),
+        Some(n) => n.rsplit(':').next().and_then(|c| c.parse::<u16>().ok()),
+        // RFC 7946 coordinates are always WGS84, but projected coordinates, from a file written
+        // without a 'crs' member, are left without a coordinate system
+        None if records.iter().all(|r| {
+            r.points
+                .iter()
+                .all(|p| p.x.abs() <= 180f64 && p.y.abs() <= 90f64)
+        }) =>
+        {
+            Some(4326u16)
+        }
+        None => None,
+    };
+    match code {
+        Some(c) => {
+            let wkt = esri_wkt_from_epsg(c);
+            if wkt == "Unknown EPSG Code" {
+                String::new()
+            } else {
+                wkt
+            }
+        }
+        None => String::new(),
+    }
+}
+
+fn geometry_base_type(geom: &Value) -> Result<ShapeType, Error> {
+    match geom.get("type").and_then(|t| t.as_str()) {
+        Some("Point") => Ok(ShapeType::Point),
+        Some("MultiPoint") => Ok(ShapeType::MultiPoint),
+        Some("LineString") | Some("MultiLineString") => Ok(ShapeType::PolyLine),
+        Some("Polygon") | Some("MultiPolygon") => Ok(ShapeType::Polygon),
+        Some(t) => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Unsupported GeoJSON geometry type: {}", t),
+        )),
+        None => Err(Error::new(
+            ErrorKind::InvalidData,
+            "GeoJSON geometry is missing its 'type' member.",
+        )),
+    }
+}
+
+fn coordinates_have_z(coords: &Value) -> bool {
+    match coords {
+        Value::Array(a) if a.first().is_some_and(|v| v.is_number()) => a.len() > 2,
+        Value::Array(a) => a.iter().any(coordinates_have_z),
+        _ => false,
+    }
+}
+
+/// Converts a GeoJSON position into a point and z value.
+fn read_position(v: &Value) -> Result<(Point2D, f64), Error> {
+    let pos = v.as_array().filter(|a| a.len() >= 2).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidData, "Invalid GeoJSON position.")
+    })?;
+    let get = |i: usize| pos.get(i).and_then(|n| n.as_f64());
+    match (get(0), get(1)) {
+        (Some(x), Some(y)) => Ok((Point2D::new(x, y), get(2).unwrap_or(0f64))),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            "Invalid GeoJSON position.",
+        )),
+    }
+}
+
+fn read_positions(v: &Value) -> Result<(Vec<Point2D>, Vec<f64>), Error> {
+    let arr = v.as_array().ok_or_else(|| {
+        Error::new(ErrorKind::InvalidData, "Invalid GeoJSON coordinate array.")
+    })?;
+    let mut points = Vec::with_capacity(arr.len());
+    let mut z_values = Vec::with_capacity(arr.len());
+    for p in arr {
+        let (pt, z) = read_position(p)?;
+        points.push(pt);
+        z_values.push(z);
+    }
+    Ok((points, z_values))
+}
+
+fn as_array(v: Option<&Value>) -> Result<&Vec<Value>, Error> {
+    v.and_then(|c| c.as_array()).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "GeoJSON geometry is missing its 'coordinates' array.",
+        )
+    })
+}
+
+fn read_geometry(geom: &Value, shape_type: ShapeType) -> Result<ShapefileGeometry, Error> {
+    let mut sfg = ShapefileGeometry::new(shape_type);
+    let has_z = shape_type.dimension() == ShapeTypeDimension::Z;
+    let coords = geom.get("coordinates");
+    match geom.get("type").and_then(|t| t.as_str()) {
+        Some("Point") | Some("MultiPoint") => {
+            let (points, z_values) = if geom["type"] == "Point" {
+                let (p, z) = read_position(coords.unwrap_or(&Value::Null))?;
+                (vec![p], vec![z])
+            } else {
+                read_positions(coords.unwrap_or(&Value::Null))?
+            };
+            for (p, z) in points.into_iter().zip(z_values) {
+                if has_z {
+                    sfg.add_pointz(p, 0f64, z);
+                } else {
+                    sfg.add_point(p);
+                }
+            }
+        }
+        Some("LineString") => {
+            let (points, z_values) = read_positions(coords.unwrap_or(&Value::Null))?;
+            add_part(&mut sfg, &points, &z_values, has_z);
+        }
+        Some("MultiLineString") => {
+            for line in as_array(coords)? {
+                let (points, z_values) = read_positions(line)?;
+                add_part(&mut sfg, &points, &z_values, has_z);
+            }
+        }
+        Some("Polygon") => {
+            add_polygon(&mut sfg, coords.unwrap_or(&Value::Null), has_z)?;
+        }
+        Some("MultiPolygon") => {
+            for poly in as_array(coords)? {
+                add_polygon(&mut sfg, poly, has_z)?;
+            }
+        }
+        _ => {
+            geometry_base_type(geom)?;
+        }
+    }
+    Ok(sfg)
+}
+
+fn add_part(sfg: &mut ShapefileGeometry, points: &[Point2D], z_values: &[f64], has_z: bool) {
+    if has_z {
+        sfg.add_partz(points, &[], z_values);
+    } else {
+        sfg.add_part(points);
+    }
+}
+
+/// Adds the rings of a GeoJSON polygon. GeoJSON exterior rings are counter-clockwise
+/// and holes are clockwise, which is the opposite of the Shapefile convention.
+fn add_polygon(sfg: &mut ShapefileGeometry, rings: &Value, has_z: bool) -> Result<(), Error> {
+    let rings = as_array(Some(rings))?;
+    for (i, ring) in rings.iter().enumerate() {
+        let (mut points, mut z_values) = read_positions(ring)?;
+        let is_hole = i > 0;
+        if is_clockwise_order(&points) == is_hole {
+            points.reverse();
+            z_values.reverse();
+        }
+        add_part(sfg, &points, &z_values, has_z);
+    }
+    Ok(())
+}
+
+fn geometry_to_json(sfg: &ShapefileGeometry) -> Value {
+    if sfg.shape_type == ShapeType::Null || sfg.num_points == 0 {
+        return Value::Null;
+    }
+    let has_z = sfg.has_z_data();
+    let position = |i: usize| -> Value {
+        let mut p = vec![json_number(sfg.points[i].x), json_number(sfg.points[i].y)];
+        if has_z {
+            p.push(json_number(sfg.z_array[i]));
+        }
+        Value::Array(p)
+    };
+    let mut geom = Map::new();
+    match sfg.shape_type.base_shape_type() {
+        ShapeType::Point => {
+            geom.insert("type".to_string(), Value::from("Point"));
+            geom.insert("coordinates".to_string(), position(0));
+        }
+        ShapeType::MultiPoint => {
+            geom.insert("type".to_string(), Value::from("MultiPoint"));
+            let coords = (0..sfg.num_points as usize).map(position).collect();
+            geom.insert("coordinates".to_string(), Value::Array(coords));
+        }
+        ShapeType::PolyLine => {
+            let lines: Vec<Value> = (0..sfg.num_parts as usize)
+                .map(|part| {
+                    let (start, end) = sfg.get_part_range(part);
+                    Value::Array((start..end).map(position).collect())
+                })
+                .collect();
+            if lines.len() == 1 {
+                geom.insert("type".to_string(), Value::from("LineString"));
+                geom.insert("coordinates".to_string(), lines[0].clone());
+            } else {
+                geom.insert("type".to_string(), Value::from("MultiLineString"));
+                geom.insert("coordinates".to_string(), Value::Array(lines));
+            }
+        }
+        _ => {
+            // Reverse the vertex order of each ring to match the GeoJSON convention.
+            let polygons: Vec<Value> = sfg
+                .get_polygon_parts()
+                .iter()
+                .map(|rings| {
+                    Value::Array(
+                        rings
+                            .iter()
+                            .map(|part| {
+                                let (start, end) = sfg.get_part_range(*part);
+                                Value::Array((start..end).rev().map(position).collect())
+                            })
+                            .collect(),
+                    )
+                })
+                .collect();
+            if polygons.len() == 1 {
+                geom.insert("type".to_string(), Value::from("Polygon"));
+                geom.insert("coordinates".to_string(), polygons[0].clone());
+            } else {
+                geom.insert("type".to_string(), Value::from("MultiPolygon"));
+                geom.insert("coordinates".to_string(), Value::Array(polygons));
+            }
+        }
+    }
+    Value::Object(geom)
+}
+
+fn json_number(v: f64) -> Value {
+    Number::from_f64(v).map_or(Value::Null, Value::Number)
+}
+
+fn field_data_to_json(field: &AttributeField, value: &FieldData) -> Value {
+    let is_real = field.field_type == 'F' || (field.field_type == 'N' && field.decimal_count > 0);
+    match value {
+        FieldData::Int(v) if is_real => json_number(*v as f64),
+        FieldData::Int(v) => Value::from(*v),
+        FieldData::Int64(v) if is_real => json_number(*v as f64),
+        FieldData::Int64(v) => Value::from(*v),
+        FieldData::Real(v) if !is_real && v.fract() == 0f64 && v.abs() < i64::MAX as f64 => {
+            Value::from(*v as i64)
+        }
+        FieldData::Real(v) => json_number(*v),
+        FieldData::Text(v) => Value::from(v.clone()),
+        FieldData::Date(v) => Value::from(format!("{:04}-{:02}-{:02}", v.year, v.month, v.day)),
+        FieldData::Bool(v) => Value::from(*v),
+        FieldData::Null => Value::Null,
+    }
+}
+
+/// Parses an ISO 8601 date (YYYY-MM-DD) string.
+fn parse_date(s: &str) -> Option<DateData> {
+    let b = s.as_bytes();
+    if b.len() != 10 || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+    let year = s[0..4].parse::<u16>().ok()?;
+    let month = s[5..7].parse::<u8>().ok()?;
+    let day = s[8..10].parse::<u8>().ok()?;
+    if month == 0 || month > 12 || day == 0 || day > 31 {
+        return None;
+    }
+    Some(DateData { year, month, day })
+}
+
+/// Accumulates the type and size of an attribute field from its JSON values.
+#[derive(Clone, Default)]
+struct FieldInfo {
+    field_type: Option<FieldDataType>,
+    length: usize,
+    int_digits: usize,
+    decimals: usize,
+}
+
+impl FieldInfo {
+    fn update(&mut self, value: &Value) {
+        let value_type = match value {
+            Value::Null => return,
+            Value::Bool(_) => FieldDataType::Bool,
+            Value::Number(n) => {
+                let s = n.to_string();
+                let (int_part, frac_part) = match s.split_once('.') {
+                    Some((i, f)) => (i.to_string(), f.to_string()),
+                    None => (s.clone(), String::new()),
+                };
+                self.int_digits = self.int_digits.max(int_part.len());
+                self.decimals = self.decimals.max(frac_part.len());
+                if n.is_f64() {
+                    FieldDataType::Real
+                } else {
+                    FieldDataType::Int
+                }
+            }
+            Value::String(s) if parse_date(s).is_some() => FieldDataType::Date,
+            _ => FieldDataType::Text,
+        };
+        self.length = self.length.max(json_text(value).len());
+        self.field_type = Some(match (self.field_type.take(), value_type) {
+            (None, t) => t,
+            (Some(a), b) if a == b => a,
+            (Some(FieldDataType::Int), FieldDataType::Real)
+            | (Some(FieldDataType::Real), FieldDataType::Int) => FieldDataType::Real,
+            _ => FieldDataType::Text,
+        });
+    }
+
+    fn to_field(&self, name: &str) -> AttributeField {
+        match self.field_type {
+            Some(FieldDataType::Int) => {
+                AttributeField::new(name, FieldDataType::Int, self.int_digits.clamp(1, 20) as u8, 0)
+            }
+            Some(FieldDataType::Real) => {
+                let decimals = self.decimals.clamp(1, 15);
+                let length = (self.int_digits + decimals + 1).clamp(3, 30);
+                AttributeField::new(name, FieldDataType::Real, length as u8, decimals as u8)
+            }
+            Some(FieldDataType::Bool) => AttributeField::new(name, FieldDataType::Bool, 1, 0),
+            Some(FieldDataType::Date) => AttributeField::new(name, FieldDataType::Date, 8, 0),
+            _ => AttributeField::new(
+                name,
+                FieldDataType::Text,
+                self.length.clamp(1, 254) as u8,
+                0,
+            ),
+        }
+    }
+
+    fn to_field_data(&self, value: &Value) -> FieldData {
+        if value.is_null() {
+            return FieldData::Null;
+        }
+        match self.field_type {
+            Some(FieldDataType::Int) => match value.as_i64() {
+                Some(v) if v >= i32::MIN as i64 && v <= i32::MAX as i64 => FieldData::Int(v as i32),
+                Some(v) => FieldData::Int64(v),
+                None => FieldData::Null,
+            },
+            Some(FieldDataType::Real) => value.as_f64().map_or(FieldData::Null, FieldData::Real),
+            Some(FieldDataType::Bool) => value.as_bool().map_or(FieldData::Null, FieldData::Bool),
+            Some(FieldDataType::Date) => value
+                .as_str()
+                .and_then(parse_date)
+                .map_or(FieldData::Null, FieldData::Date),
+            _ => FieldData::Text(json_text(value)),
+        }
+    }
+}
+
+/// Returns the text form of a JSON value, without quotes for strings.
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::shapefile::attributes::*;
+    use crate::shapefile::geometry::*;
+    use crate::shapefile::Shapefile;
+    use std::env;
+    use std::fs;
+    use whitebox_common::algorithms::is_clockwise_order;
+    use whitebox_common::spatial_ref_system::{esri_wkt_from_epsg, SpatialReference};
+    use whitebox_common::structures::Point2D;
+
+    fn temp_file(name: &str) -> String {
+        env::temp_dir()
+            .join(format!("whitebox_vector_geojson_{}", name))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Returns a closed, clockwise square ring.
+    fn square(x: f64, y: f64, size: f64) -> Vec<Point2D> {
+        vec![
+            Point2D::new(x, y),
+            Point2D::new(x, y + size),
+            Point2D::new(x + size, y + size),
+            Point2D::new(x + size, y),
+            Point2D::new(x, y),
+        ]
+    }
+
+    fn write_and_read(sf: &mut Shapefile) -> Shapefile {
+        sf.write().unwrap();
+        let input = Shapefile::read(&sf.file_name).unwrap();
+        fs::remove_file(&sf.file_name).unwrap();
+        input
+    }
+
+    #[test]
+    fn test_field_types_round_trip() {
+        let mut output = Shapefile::new(&temp_file("fields.geojson"), ShapeType::Point).unwrap();
+        output.attributes.add_field(&AttributeField::new("COUNT", FieldDataType::Int, 11u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("VALUE", FieldDataType::Real, 12u8, 2u8));
+        output.attributes.add_field(&AttributeField::new("NAME", FieldDataType::Text, 20u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("SURVEYED", FieldDataType::Date, 8u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("ACTIVE", FieldDataType::Bool, 1u8, 0u8));
+        let records = [
+            vec![
+                FieldData::Int(7),
+                FieldData::Real(150.25),
+                FieldData::Text("Mixed \"Forest\"".to_string()),
+                FieldData::Date(DateData { year: 2021, month: 6, day: 15 }),
+                FieldData::Bool(true),
+            ],
+            vec![
+                FieldData::Int64(5_000_000_000),
+                FieldData::Real(-3.5),
+                FieldData::Text("Wetland".to_string()),
+                FieldData::Null,
+                FieldData::Bool(false),
+            ],
+            vec![FieldData::Null; 5],
+        ];
+        for (i, rec) in records.iter().enumerate() {
+            output.add_point_record(i as f64, 2.0 * i as f64);
+            output.attributes.add_record(rec.clone(), false);
+        }
+
+        let input = write_and_read(&mut output);
+        assert_eq!(input.header.shape_type, ShapeType::Point);
+        assert_eq!(input.num_records, 3);
+        assert_eq!(input.attributes.get_num_fields(), 5);
+        for i in 0..5 {
+            let (a, b) = (input.attributes.get_field(i), output.attributes.get_field(i));
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.field_type, b.field_type);
+        }
+        for (i, rec) in records.iter().enumerate() {
+            assert_eq!(&input.attributes.get_record(i), rec);
+            assert_eq!(input.get_record(i).points, vec![Point2D::new(i as f64, 2.0 * i as f64)]);
+        }
+    }
+
+    #[test]
+    fn test_multipart_geometries_round_trip() {
+        let mut lines = Shapefile::new(&temp_file("lines.geojson"), ShapeType::PolyLineZ).unwrap();
+        lines.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 4u8, 0u8));
+        let mut sfg = ShapefileGeometry::new(ShapeType::PolyLineZ);
+        let (first, second) = (square(0.0, 0.0, 1.0), square(5.0, 5.0, 2.0));
+        sfg.add_partz(&first, &[0.0; 5], &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        sfg.add_partz(&second[..3], &[0.0; 3], &[6.0, 7.0, 8.0]);
+        lines.add_record(sfg.clone());
+        lines.attributes.add_record(vec![FieldData::Int(1)], false);
+
+        let input = write_and_read(&mut lines);
+        assert_eq!(input.header.shape_type, ShapeType::PolyLineZ);
+        let record = input.get_record(0);
+        assert_eq!(record.num_parts, 2);
+        assert_eq!(record.parts, sfg.parts);
+        assert_eq!(record.points, sfg.points);
+        assert_eq!(record.z_array, sfg.z_array);
+
+        // a polygon with a hole, and a second polygon, make up a single MultiPolygon
+        let file_name = temp_file("polygons.geojson");
+        let mut polygons = Shapefile::new(&file_name, ShapeType::Polygon).unwrap();
+        polygons.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 4u8, 0u8));
+        let mut sfg = ShapefileGeometry::new(ShapeType::Polygon);
+        let mut hole = square(2.0, 2.0, 2.0);
+        hole.reverse();
+        sfg.add_part(&square(0.0, 0.0, 10.0));
+        sfg.add_part(&hole);
+        sfg.add_part(&square(20.0, 0.0, 5.0));
+        polygons.add_record(sfg.clone());
+        polygons.attributes.add_record(vec![FieldData::Int(1)], false);
+        polygons.write().unwrap();
+        let json = fs::read_to_string(&file_name).unwrap();
+        assert!(json.contains("\"type\":\"MultiPolygon\""));
+
+        let input = write_and_read(&mut polygons);
+        let record = input.get_record(0);
+        assert_eq!(record.num_parts, 3);
+        assert_eq!(record.points, sfg.points);
+        assert_eq!(record.get_polygon_parts(), vec![vec![0, 1], vec![2]]);
+        assert!(is_clockwise_order(&record.points[0..5]));
+        assert!(!is_clockwise_order(&record.points[5..10]));
+    }
+
+    #[test]
+    fn test_mixed_geometries() {
+        let file_name = temp_file("mixed.geojson");
+        fs::write(
+            &file_name,
+            r#"{"type":"FeatureCollection","features":[
+                {"type":"Feature","geometry":{"type":"Point","coordinates":[1,2]},"properties":{"ID":1}},
+                {"type":"Feature","geometry":{"type":"MultiPoint","coordinates":[[3,4,10],[5,6,11]]},"properties":{"ID":2}}
+            ]}"#,
+        )
+        .unwrap();
+        let input = Shapefile::read(&file_name).unwrap();
+        assert_eq!(input.header.shape_type, ShapeType::MultiPointZ);
+        assert_eq!(input.get_record(0).points, vec![Point2D::new(1.0, 2.0)]);
+        assert_eq!(input.get_record(1).num_points, 2);
+        assert_eq!(input.get_record(1).z_array, vec![10.0, 11.0]);
+
+        fs::write(
+            &file_name,
+            r#"{"type":"FeatureCollection","features":[
+                {"type":"Feature","geometry":{"type":"Point","coordinates":[1,2]},"properties":{}},
+                {"type":"Feature","geometry":{"type":"LineString","coordinates":[[0,0],[1,1]]},"properties":{}}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(Shapefile::read(&file_name).is_err());
+        fs::remove_file(&file_name).unwrap();
+    }
+
+    #[test]
+    fn test_null_geometries() {
+        let file_name = temp_file("null.geojson");
+        fs::write(
+            &file_name,
+            r#"{"type":"FeatureCollection","features":[
+                {"type":"Feature","geometry":null,"properties":{"NAME":"unlocated"}},
+                {"type":"Feature","geometry":{"type":"LineString","coordinates":[[0,0],[1,1]]},"properties":{"NAME":"road"}}
+            ]}"#,
+        )
+        .unwrap();
+        let mut input = Shapefile::read(&file_name).unwrap();
+        assert_eq!(input.header.shape_type, ShapeType::PolyLine);
+        assert_eq!(input.num_records, 2);
+        assert_eq!(input.get_record(0).shape_type, ShapeType::Null);
+        assert_eq!(input.get_record(0).num_points, 0);
+        assert_eq!(input.attributes.get_value(0, "NAME"), FieldData::Text("unlocated".to_string()));
+
+        // the null geometry, and its attributes, are written back out
+        input.file_mode = "w".to_string();
+        let input = write_and_read(&mut input);
+        assert_eq!(input.num_records, 2);
+        assert_eq!(input.get_record(0).shape_type, ShapeType::Null);
+        assert_eq!(input.get_record(1).num_points, 2);
+        assert_eq!(input.attributes.get_value(1, "NAME"), FieldData::Text("road".to_string()));
+    }
+
+    #[test]
+    fn test_projected_crs_round_trip() {
+        // the ESRI WKT of a .prj file, which does not give the EPSG code
+        let wkt = esri_wkt_from_epsg(32617);
+        let wkt = format!("{}]", &wkt[..wkt.rfind(",AUTHORITY[").unwrap()]);
+        let mut output = Shapefile::new(&temp_file("projected.geojson"), ShapeType::Point).unwrap();
+        output.projection = wkt.clone();
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 4u8, 0u8));
+        output.add_point_record(500000.0, 4800000.0);
+        output.attributes.add_record(vec![FieldData::Int(1)], false);
+        let input = write_and_read(&mut output);
+        assert_eq!(SpatialReference::from_wkt(&input.projection).unwrap().epsg, Some(32617));
+        assert_eq!(input.get_record(0).points, vec![Point2D::new(500000.0, 4800000.0)]);
+
+        // projected coordinates in a system without a known EPSG code are not taken to be WGS84
+        output.projection = wkt.replace("WGS_1984_UTM_Zone_17N", "Custom_UTM");
+        let input = write_and_read(&mut output);
+        assert!(input.projection.is_empty());
+    }
+}
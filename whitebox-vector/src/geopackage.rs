@@ -0,0 +1,1079 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+Notes: Reading and writing of vector (features) layers in OGC GeoPackage files. A
+GeoPackage is an SQLite database that may hold several layers, and so a layer is
+addressed by appending its name to the file name, e.g. 'data.gpkg:roads'. When no
+layer name is given, a file containing a single layer is read, and the layer written
+takes its name from the file. Writing a layer replaces any existing layer of the same
+name but leaves the other layers of the file untouched.
+
+Unlike dBASE tables, GeoPackage columns have no name length limit, hold 64-bit
+integers, and distinguish NULLs from empty values, all of which are preserved.
+*/
+
+use crate::shapefile::attributes::*;
+use crate::shapefile::geometry::*;
+use crate::shapefile::Shapefile;
+use byteorder::{LittleEndian, WriteBytesExt};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::io::{Cursor, Error, ErrorKind};
+use std::path::Path;
+use whitebox_common::algorithms::is_clockwise_order;
+use whitebox_common::spatial_ref_system::{esri_wkt_from_epsg, SpatialReference};
+use whitebox_common::structures::Point2D;
+use whitebox_common::utils::{ByteOrderReader, Endianness};
+
+/// Splits a file name of the form 'file.gpkg:layer' into its file and layer names.
+pub fn split_layer_name(file_name: &str) -> (String, Option<String>) {
+    match file_name.to_lowercase().rfind(".gpkg:") {
+        Some(i) => {
+            let layer = &file_name[i + 6..];
+            let layer = if layer.is_empty() {
+                None
+            } else {
+                Some(layer.to_string())
+            };
+            (file_name[..i + 5].to_string(), layer)
+        }
+        None => (file_name.to_string(), None),
+    }
+}
+
+fn sql_error(e: rusqlite::Error) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("GeoPackage error: {}", e))
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Reads a GeoPackage features layer, addressed by `sf.file_name`, into the Shapefile.
+pub(crate) fn read_geopackage(sf: &mut Shapefile) -> Result<(), Error> {
+    let (path, layer) = split_layer_name(&sf.file_name);
+    if !Path::new(&path).exists() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("The GeoPackage file {} does not exist.", path),
+        ));
+    }
+    let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(sql_error)?;
+
+    // Find the layer.
+    let mut stmt = conn
+        .prepare("SELECT table_name FROM gpkg_contents WHERE data_type = 'features' ORDER BY rowid")
+        .map_err(sql_error)?;
+    let layers = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(sql_error)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(sql_error)?;
+    let table = match layer {
+        Some(name) => match layers.iter().find(|l| l.eq_ignore_ascii_case(&name)) {
+            Some(l) => l.clone(),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "The GeoPackage does not contain a layer named '{}'. Available layers: {}",
+                        name,
+                        layers.join(", ")
+                    ),
+                ))
+            }
+        },
+        None => match layers.len() {
+            0 => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The GeoPackage does not contain any vector layers.",
+                ))
+            }
+            1 => layers[0].clone(),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The GeoPackage contains multiple layers ({}). Please specify one as 'file.gpkg:layer'.",
+                        layers.join(", ")
+                    ),
+                ))
+            }
+        },
+    };
+
+    let (geom_column, geom_type_name, srs_id, z_flag, m_flag): (String, String, i64, i64, i64) =
+        conn.query_row(
+            "SELECT column_name, geometry_type_name, srs_id, z, m FROM gpkg_geometry_columns WHERE table_name = ?1",
+            params![table],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )
+        .map_err(sql_error)?;
+
+    // The coordinate reference system.
+    let srs: Option<(String, i64, String)> = conn
+        .query_row(
+            "SELECT organization, organization_coordsys_id, definition FROM gpkg_spatial_ref_sys WHERE srs_id = ?1",
+            params![srs_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(sql_error)?;
+    sf.projection = match srs {
+        Some((_, _, definition)) if !definition.eq_ignore_ascii_case("undefined") => definition,
+        Some((org, code, _)) if org.eq_ignore_ascii_case("EPSG") && code > 0 => {
+            let wkt = esri_wkt_from_epsg(code as u16);
+            if wkt == "Unknown EPSG Code" {
+                String::new()
+            } else {
+                wkt
+            }
+        }
+        _ => String::new(),
+    };
+
+    // The attribute columns, excluding the primary key and geometry.
+    let mut columns: Vec<(String, FieldDataType)> = vec![];
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", quote(&table)))
+        .map_err(sql_error)?;
+    let mut rows = stmt.query([]).map_err(sql_error)?;
+    while let Some(row) = rows.next().map_err(sql_error)? {
+        let name: String = row.get(1).map_err(sql_error)?;
+        let decl_type: String = row.get(2).map_err(sql_error)?;
+        let pk: i64 = row.get(5).map_err(sql_error)?;
+        if pk > 0 || name.eq_ignore_ascii_case(&geom_column) {
+            continue;
+        }
+        let decl_type = decl_type.to_uppercase();
+        let field_type = if decl_type.contains("INT") {
+            FieldDataType::Int
+        } else if decl_type.starts_with("BOOL") {
+            FieldDataType::Bool
+        } else if decl_type.contains("REAL")
+            || decl_type.contains("FLOA")
+            || decl_type.contains("DOUB")
+        {
+            FieldDataType::Real
+        } else if decl_type == "DATE" {
+            FieldDataType::Date
+        } else if decl_type.starts_with("BLOB") {
+            continue; // binary columns are not supported
+        } else {
+            FieldDataType::Text
+        };
+        columns.push((name, field_type));
+    }
+
+    let mut sql = format!("SELECT {}", quote(&geom_column));
+    for (name, _) in &columns {
+        sql.push_str(&format!(", {}", quote(name)));
+    }
+    sql.push_str(&format!(" FROM {}", quote(&table)));
+    let mut stmt = conn.prepare(&sql).map_err(sql_error)?;
+    let mut rows = stmt.query([]).map_err(sql_error)?;
+    let mut geometries: Vec<Option<WkbGeometry>> = vec![];
+    let mut records: Vec<Vec<FieldData>> = vec![];
+    let mut sizes = vec![FieldSize::default(); columns.len()];
+    while let Some(row) = rows.next().map_err(sql_error)? {
+        geometries.push(match row.get_ref(0).map_err(sql_error)? {
+            ValueRef::Blob(b) => read_gpkg_geometry(b)?,
+            _ => None,
+        });
+        let mut rec = Vec::with_capacity(columns.len());
+        for (i, (_, field_type)) in columns.iter().enumerate() {
+            let value = to_field_data(row.get_ref(i + 1).map_err(sql_error)?, field_type);
+            sizes[i].update(&value);
+            rec.push(value);
+        }
+        records.push(rec);
+    }
+
+    // Decide on a single ShapeType for the layer.
+    let declared = geom_type_name.to_uppercase();
+    let mut base_type = match declared.as_str() {
+        "POINT" => ShapeType::Point,
+        "MULTIPOINT" => ShapeType::MultiPoint,
+        "LINESTRING" | "MULTILINESTRING" | "CURVE" | "MULTICURVE" => ShapeType::PolyLine,
+        "POLYGON" | "MULTIPOLYGON" | "SURFACE" | "MULTISURFACE" => ShapeType::Polygon,
+        _ => ShapeType::Null,
+    };
+    let mut has_z = z_flag == 1;
+    let mut has_m = m_flag == 1;
+    for g in geometries.iter().flatten() {
+        has_z = has_z || g.has_z;
+        has_m = has_m || g.has_m;
+        if declared == "POINT" || declared == "MULTIPOINT" {
+            continue;
+        }
+        base_type = match (base_type, g.base_type()) {
+            (ShapeType::Null, t) => t,
+            (a, b) if a == b => a,
+            (ShapeType::Point, ShapeType::MultiPoint) | (ShapeType::MultiPoint, ShapeType::Point) => {
+                ShapeType::MultiPoint
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "GeoPackage layers containing a mixture of point, line, and polygon geometries are not supported.",
+                ))
+            }
+        };
+    }
+    if base_type == ShapeType::Null {
+        base_type = ShapeType::Point;
+    }
+    sf.header.shape_type = match (base_type, has_z, has_m) {
+        (ShapeType::Point, true, _) => ShapeType::PointZ,
+        (ShapeType::MultiPoint, true, _) => ShapeType::MultiPointZ,
+        (ShapeType::PolyLine, true, _) => ShapeType::PolyLineZ,
+        (ShapeType::Polygon, true, _) => ShapeType::PolygonZ,
+        (ShapeType::Point, false, true) => ShapeType::PointM,
+        (ShapeType::MultiPoint, false, true) => ShapeType::MultiPointM,
+        (ShapeType::PolyLine, false, true) => ShapeType::PolyLineM,
+        (ShapeType::Polygon, false, true) => ShapeType::PolygonM,
+        (t, _, _) => t,
+    };
+
+    for g in &geometries {
+        let record = match g {
+            Some(g) => g.to_shapefile_geometry(sf.header.shape_type),
+            None => ShapefileGeometry::new(ShapeType::Null),
+        };
+        sf.records.push(record);
+    }
+    sf.num_records = sf.records.len();
+
+    for ((name, field_type), size) in columns.iter().zip(sizes.iter()) {
+        sf.attributes.add_field(&size.to_field(name, field_type));
+    }
+    for rec in records {
+        sf.attributes.add_record(rec, false);
+    }
+
+    Ok(())
+}
+
+/// Writes the Shapefile's records and attributes to a GeoPackage layer, addressed by `sf.file_name`.
+pub(crate) fn write_geopackage(sf: &Shapefile) -> Result<(), Error> {
+    let (path, layer) = split_layer_name(&sf.file_name);
+    let table = layer.unwrap_or_else(|| {
+        Path::new(&path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("layer")
+            .to_string()
+    });
+    let mut conn = Connection::open(&path).map_err(sql_error)?;
+    let tx = conn.transaction().map_err(sql_error)?;
+
+    tx.execute_batch(
+        "PRAGMA application_id = 1196444487;
+        PRAGMA user_version = 10200;
+        CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (
+            srs_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL PRIMARY KEY,
+            organization TEXT NOT NULL,
+            organization_coordsys_id INTEGER NOT NULL,
+            definition TEXT NOT NULL,
+            description TEXT
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_contents (
+            table_name TEXT NOT NULL PRIMARY KEY,
+            data_type TEXT NOT NULL,
+            identifier TEXT UNIQUE,
+            description TEXT DEFAULT '',
+            last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            min_x DOUBLE,
+            min_y DOUBLE,
+            max_x DOUBLE,
+            max_y DOUBLE,
+            srs_id INTEGER,
+            CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+        );
+        CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (
+            table_name TEXT NOT NULL,
+            column_name TEXT NOT NULL,
+            geometry_type_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL,
+            z TINYINT NOT NULL,
+            m TINYINT NOT NULL,
+            CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+            CONSTRAINT uk_gc_table_name UNIQUE (table_name),
+            CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+            CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id)
+        );
+        INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES
+            ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system'),
+            ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system');",
+    )
+    .map_err(sql_error)?;
+    let wgs84 = esri_wkt_from_epsg(4326);
+    tx.execute(
+        "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('WGS 84 geodetic', 4326, 'EPSG', 4326, ?1, 'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid')",
+        params![wgs84],
+    )
+    .map_err(sql_error)?;
+
+    // Replace any existing layer of the same name.
+    let old_geom_column: Option<String> = tx
+        .query_row(
+            "SELECT column_name FROM gpkg_geometry_columns WHERE table_name = ?1",
+            params![table],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(sql_error)?;
+    if let Some(column) = old_geom_column {
+        tx.execute_batch(&format!(
+            "DROP TABLE IF EXISTS {};",
+            quote(&format!("rtree_{}_{}", table, column))
+        ))
+        .map_err(sql_error)?;
+    }
+    let has_extensions: bool = tx
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'gpkg_extensions'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(sql_error)?
+        > 0;
+    if has_extensions {
+        tx.execute("DELETE FROM gpkg_extensions WHERE table_name = ?1", params![table])
+            .map_err(sql_error)?;
+    }
+    tx.execute("DELETE FROM gpkg_geometry_columns WHERE table_name = ?1", params![table])
+        .map_err(sql_error)?;
+    tx.execute("DELETE FROM gpkg_contents WHERE table_name = ?1", params![table])
+        .map_err(sql_error)?;
+    tx.execute_batch(&format!("DROP TABLE IF EXISTS {};", quote(&table)))
+        .map_err(sql_error)?;
+
+    // The coordinate reference system.
+    let projection = sf.projection.trim();
+    let srs_id: i64 = if projection.is_empty() {
+        -1
+    } else {
+        match SpatialReference::from_wkt(projection) {
+            Ok(srs) if srs.epsg.is_some() => {
+                let code = srs.epsg.unwrap_or(0) as i64;
+                tx.execute(
+                    "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES (?1, ?2, 'EPSG', ?2, ?3, '')",
+                    params![srs.name, code, projection],
+                )
+                .map_err(sql_error)?;
+                code
+            }
+            _ => {
+                let existing: Option<i64> = tx
+                    .query_row(
+                        "SELECT srs_id FROM gpkg_spatial_ref_sys WHERE definition = ?1",
+                        params![projection],
+                        |row| row.get(0),
+                    )
+                    .optional()
+                    .map_err(sql_error)?;
+                match existing {
+                    Some(id) => id,
+                    None => {
+                        let id: i64 = tx
+                            .query_row(
+                                "SELECT MAX(MAX(srs_id) + 1, 100000) FROM gpkg_spatial_ref_sys",
+                                [],
+                                |row| row.get(0),
+                            )
+                            .map_err(sql_error)?;
+                        let name = SpatialReference::from_wkt(projection)
+                            .map(|s| s.name)
+                            .unwrap_or_else(|_| "Unknown".to_string());
+                        tx.execute(
+                            "INSERT INTO gpkg_spatial_ref_sys VALUES (?1, ?2, 'NONE', ?2, ?3, '')",
+                            params![name, id, projection],
+                        )
+                        .map_err(sql_error)?;
+                        id
+                    }
+                }
+            }
+        }
+    };
+
+    // The geometry type. Multi-part types are used if any record has more than one part.
+    let shape_type = sf.header.shape_type;
+    let is_multi = sf.records.iter().any(|r| match shape_type.base_shape_type() {
+        ShapeType::PolyLine => r.num_parts > 1,
+        ShapeType::Polygon => r.num_parts > 1 && r.get_polygon_parts().len() > 1,
+        _ => false,
+    });
+    let geom_type_name = match (shape_type.base_shape_type(), is_multi) {
+        (ShapeType::Point, _) => "POINT",
+        (ShapeType::MultiPoint, _) => "MULTIPOINT",
+        (ShapeType::PolyLine, false) => "LINESTRING",
+        (ShapeType::PolyLine, true) => "MULTILINESTRING",
+        (ShapeType::Polygon, false) => "POLYGON",
+        (ShapeType::Polygon, true) => "MULTIPOLYGON",
+        _ => "GEOMETRY",
+    };
+    let dimension = shape_type.dimension();
+    let z_flag = (dimension == ShapeTypeDimension::Z) as i64;
+    let m_flag = (dimension == ShapeTypeDimension::Measure) as i64;
+
+    // Create the feature table. The primary key column must not clash with an attribute.
+    let fields = sf.attributes.get_fields();
+    let mut fid_column = "fid".to_string();
+    let mut n = 1;
+    while fields.iter().any(|f| f.name.eq_ignore_ascii_case(&fid_column)) {
+        fid_column = format!("fid_{}", n);
+        n += 1;
+    }
+    let geom_column = if fields.iter().any(|f| f.name.eq_ignore_ascii_case("geom")) {
+        "geometry".to_string()
+    } else {
+        "geom".to_string()
+    };
+    let mut sql = format!(
+        "CREATE TABLE {} ({} INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, {} {}",
+        quote(&table),
+        quote(&fid_column),
+        quote(&geom_column),
+        geom_type_name
+    );
+    for field in fields {
+        sql.push_str(&format!(", {} {}", quote(&field.name), column_type(field)));
+    }
+    sql.push(')');
+    tx.execute_batch(&sql).map_err(sql_error)?;
+
+    tx.execute(
+        "INSERT INTO gpkg_contents (table_name, data_type, identifier, description, min_x, min_y, max_x, max_y, srs_id) VALUES (?1, 'features', ?1, '', ?2, ?3, ?4, ?5, ?6)",
+        params![table, sf.header.x_min, sf.header.y_min, sf.header.x_max, sf.header.y_max, srs_id],
+    )
+    .map_err(sql_error)?;
+    tx.execute(
+        "INSERT INTO gpkg_geometry_columns VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![table, geom_column, geom_type_name, srs_id, z_flag, m_flag],
+    )
+    .map_err(sql_error)?;
+
+    // Insert the features.
+    let mut sql = format!("INSERT INTO {} ({}", quote(&table), quote(&geom_column));
+    for field in fields {
+        sql.push_str(&format!(", {}", quote(&field.name)));
+    }
+    sql.push_str(") VALUES (?");
+    for _ in fields {
+        sql.push_str(", ?");
+    }
+    sql.push(')');
+    {
+        let mut stmt = tx.prepare(&sql).map_err(sql_error)?;
+        for i in 0..sf.num_records {
+            let mut values: Vec<Value> = Vec::with_capacity(fields.len() + 1);
+            values.push(match write_gpkg_geometry(&sf.records[i], srs_id as i32, is_multi)? {
+                Some(blob) => Value::Blob(blob),
+                None => Value::Null,
+            });
+            if i < sf.attributes.header.num_records as usize {
+                for value in sf.attributes.get_record(i) {
+                    values.push(to_sql_value(value));
+                }
+            }
+            values.resize(fields.len() + 1, Value::Null);
+            stmt.execute(rusqlite::params_from_iter(values.iter()))
+                .map_err(sql_error)?;
+        }
+    }
+
+    tx.commit().map_err(sql_error)?;
+
+    Ok(())
+}
+
+fn column_type(field: &AttributeField) -> &'static str {
+    match field.field_type {
+        'N' | 'I' | 'O' if field.decimal_count == 0 => "INTEGER",
+        'N' | 'F' | 'I' | 'O' => "REAL",
+        'D' => "DATE",
+        'L' => "BOOLEAN",
+        _ => "TEXT",
+    }
+}
+
+fn to_sql_value(value: FieldData) -> Value {
+    match value {
+        FieldData::Int(v) => Value::Integer(v as i64),
+        FieldData::Int64(v) => Value::Integer(v),
+        FieldData::Real(v) => Value::Real(v),
+        FieldData::Text(v) => Value::Text(v),
+        FieldData::Date(v) => Value::Text(format!("{:04}-{:02}-{:02}", v.year, v.month, v.day)),
+        FieldData::Bool(v) => Value::Integer(v as i64),
+        FieldData::Null => Value::Null,
+    }
+}
+
+fn to_field_data(value: ValueRef, field_type: &FieldDataType) -> FieldData {
+    let text = |v: ValueRef| -> Option<String> {
+        match v {
+            ValueRef::Text(t) => Some(String::from_utf8_lossy(t).to_string()),
+            ValueRef::Integer(i) => Some(i.to_string()),
+            ValueRef::Real(r) => Some(r.to_string()),
+            _ => None,
+        }
+    };
+    match (field_type, value) {
+        (_, ValueRef::Null) => FieldData::Null,
+        (FieldDataType::Int, ValueRef::Integer(i)) => match i32::try_from(i) {
+            Ok(v) => FieldData::Int(v),
+            Err(_) => FieldData::Int64(i),
+        },
+        (FieldDataType::Int, ValueRef::Real(r)) => FieldData::Int(r as i32),
+        (FieldDataType::Real, ValueRef::Integer(i)) => FieldData::Real(i as f64),
+        (FieldDataType::Real, ValueRef::Real(r)) => FieldData::Real(r),
+        (FieldDataType::Bool, ValueRef::Integer(i)) => FieldData::Bool(i != 0),
+        (FieldDataType::Date, v) => text(v)
+            .and_then(|s| {
+                if s.len() >= 10 && s.is_char_boundary(10) {
+                    Some(DateData {
+                        year: s[0..4].parse::<u16>().ok()?,
+                        month: s[5..7].parse::<u8>().ok()?,
+                        day: s[8..10].parse::<u8>().ok()?,
+                    })
+                } else {
+                    None
+                }
+            })
+            .map_or(FieldData::Null, FieldData::Date),
+        (FieldDataType::Text, v) => text(v).map_or(FieldData::Null, FieldData::Text),
+        (_, v) => match text(v) {
+            Some(s) => match field_type {
+                FieldDataType::Int => s.parse::<i32>().map_or(FieldData::Null, FieldData::Int),
+                FieldDataType::Real => s.parse::<f64>().map_or(FieldData::Null, FieldData::Real),
+                _ => FieldData::Text(s),
+            },
+            None => FieldData::Null,
+        },
+    }
+}
+
+/// Accumulates the dBASE-style size of an attribute field from its values.
+#[derive(Clone, Default)]
+struct FieldSize {
+    length: usize,
+    int_digits: usize,
+    decimals: usize,
+}
+
+impl FieldSize {
+    fn update(&mut self, value: &FieldData) {
+        let s = value.to_string();
+        match value {
+            FieldData::Null => {}
+            FieldData::Real(_) => {
+                let (i, f) = s.split_once('.').unwrap_or((&s, ""));
+                self.int_digits = self.int_digits.max(i.len());
+                self.decimals = self.decimals.max(f.len());
+            }
+            FieldData::Int(_) | FieldData::Int64(_) => {
+                self.int_digits = self.int_digits.max(s.len());
+            }
+            _ => self.length = self.length.max(s.len()),
+        }
+    }
+
+    fn to_field(&self, name: &str, field_type: &FieldDataType) -> AttributeField {
+        match field_type {
+            FieldDataType::Int => {
+                AttributeField::new(name, FieldDataType::Int, self.int_digits.clamp(1, 20) as u8, 0)
+            }
+            FieldDataType::Real => {
+                let decimals = self.decimals.clamp(1, 15);
+                let length = (self.int_digits + decimals + 1).clamp(3, 30);
+                AttributeField::new(name, FieldDataType::Real, length as u8, decimals as u8)
+            }
+            FieldDataType::Bool => AttributeField::new(name, FieldDataType::Bool, 1, 0),
+            FieldDataType::Date => AttributeField::new(name, FieldDataType::Date, 8, 0),
+            FieldDataType::Text => AttributeField::new(
+                name,
+                FieldDataType::Text,
+                self.length.clamp(1, 254) as u8,
+                0,
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: f64,
+    m: f64,
+}
+
+impl Coord {
+    fn point(&self) -> Point2D {
+        Point2D::new(self.x, self.y)
+    }
+}
+
+enum WkbShape {
+    Points(Vec<Coord>),
+    Lines(Vec<Vec<Coord>>),
+    Polygons(Vec<Vec<Vec<Coord>>>),
+}
+
+/// A geometry decoded from well-known binary (WKB).
+struct WkbGeometry {
+    shape: WkbShape,
+    is_multi: bool,
+    has_z: bool,
+    has_m: bool,
+}
+
+impl WkbGeometry {
+    fn base_type(&self) -> ShapeType {
+        match (&self.shape, self.is_multi) {
+            (WkbShape::Points(_), false) => ShapeType::Point,
+            (WkbShape::Points(_), true) => ShapeType::MultiPoint,
+            (WkbShape::Lines(_), _) => ShapeType::PolyLine,
+            (WkbShape::Polygons(_), _) => ShapeType::Polygon,
+        }
+    }
+
+    fn to_shapefile_geometry(&self, shape_type: ShapeType) -> ShapefileGeometry {
+        let mut sfg = ShapefileGeometry::new(shape_type);
+        let dimension = shape_type.dimension();
+        let add_part = |sfg: &mut ShapefileGeometry, coords: &[Coord]| {
+            let points: Vec<Point2D> = coords.iter().map(|c| c.point()).collect();
+            match dimension {
+                ShapeTypeDimension::Z => {
+                    let z: Vec<f64> = coords.iter().map(|c| c.z).collect();
+                    let m: Vec<f64> = if self.has_m {
+                        coords.iter().map(|c| c.m).collect()
+                    } else {
+                        vec![]
+                    };
+                    sfg.add_partz(&points, &m, &z);
+                }
+                ShapeTypeDimension::Measure => {
+                    let m: Vec<f64> = coords.iter().map(|c| c.m).collect();
+                    sfg.add_partm(&points, &m);
+                }
+                ShapeTypeDimension::XY => sfg.add_part(&points),
+            }
+        };
+        match &self.shape {
+            WkbShape::Points(coords) => {
+                for c in coords {
+                    match dimension {
+                        ShapeTypeDimension::Z => sfg.add_pointz(c.point(), c.m, c.z),
+                        ShapeTypeDimension::Measure => sfg.add_pointm(c.point(), c.m),
+                        ShapeTypeDimension::XY => sfg.add_point(c.point()),
+                    }
+                }
+            }
+            WkbShape::Lines(lines) => {
+                for line in lines {
+                    add_part(&mut sfg, line);
+                }
+            }
+            WkbShape::Polygons(polygons) => {
+                // Shapefile exterior rings are clockwise and holes are counter-clockwise.
+                for rings in polygons {
+                    for (i, ring) in rings.iter().enumerate() {
+                        let points: Vec<Point2D> = ring.iter().map(|c| c.point()).collect();
+                        if is_clockwise_order(&points) == (i > 0) {
+                            let reversed: Vec<Coord> = ring.iter().rev().copied().collect();
+                            add_part(&mut sfg, &reversed);
+                        } else {
+                            add_part(&mut sfg, ring);
+                        }
+                    }
+                }
+            }
+        }
+        sfg
+    }
+}
+
+/// Decodes a GeoPackage geometry blob, returning None for empty geometries.
+fn read_gpkg_geometry(blob: &[u8]) -> Result<Option<WkbGeometry>, Error> {
+    if blob.len() < 8 || &blob[0..2] != b"GP" {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Invalid GeoPackage geometry blob.",
+        ));
+    }
+    let flags = blob[3];
+    let envelope_len = match (flags >> 1) & 0x07 {
+        0 => 0,
+        1 => 32,
+        2 | 3 => 48,
+        4 => 64,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Invalid GeoPackage geometry envelope.",
+            ))
+        }
+    };
+    if blob.len() < 8 + envelope_len {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Invalid GeoPackage geometry envelope.",
+        ));
+    }
+    if flags & 0x10 != 0 {
+        return Ok(None); // empty geometry
+    }
+    let mut bor = ByteOrderReader::<Cursor<&[u8]>>::new(
+        Cursor::new(&blob[8 + envelope_len..]),
+        Endianness::LittleEndian,
+    );
+    let geom = read_wkb(&mut bor)?;
+    let is_empty = match &geom.shape {
+        WkbShape::Points(p) => p.is_empty(),
+        WkbShape::Lines(l) => l.is_empty(),
+        WkbShape::Polygons(p) => p.is_empty(),
+    };
+    Ok(if is_empty { None } else { Some(geom) })
+}
+
+fn read_wkb(bor: &mut ByteOrderReader<Cursor<&[u8]>>) -> Result<WkbGeometry, Error> {
+    let byte_order = if bor.read_u8()? == 1 {
+        Endianness::LittleEndian
+    } else {
+        Endianness::BigEndian
+    };
+    bor.set_byte_order(byte_order);
+    let mut code = bor.read_u32()?;
+    // Extended (EWKB) dimension and SRID flags.
+    let mut has_z = code & 0x8000_0000 != 0;
+    let mut has_m = code & 0x4000_0000 != 0;
+    if code & 0x2000_0000 != 0 {
+        bor.read_u32()?;
+    }
+    code &= 0x0fff_ffff;
+    match code / 1000 {
+        1 => has_z = true,
+        2 => has_m = true,
+        3 => {
+            has_z = true;
+            has_m = true;
+        }
+        _ => {}
+    }
+    let read_coord = |bor: &mut ByteOrderReader<Cursor<&[u8]>>| -> Result<Coord, Error> {
+        let mut c = Coord {
+            x: bor.read_f64()?,
+            y: bor.read_f64()?,
+            ..Default::default()
+        };
+        if has_z {
+            c.z = bor.read_f64()?;
+        }
+        if has_m {
+            c.m = bor.read_f64()?;
+        }
+        Ok(c)
+    };
+    let read_coords = |bor: &mut ByteOrderReader<Cursor<&[u8]>>| -> Result<Vec<Coord>, Error> {
+        let n = bor.read_u32()? as usize;
+        let mut coords = Vec::with_capacity(n);
+        for _ in 0..n {
+            coords.push(read_coord(bor)?);
+        }
+        Ok(coords)
+    };
+    let (shape, is_multi) = match code % 1000 {
+        1 => {
+            let c = read_coord(bor)?;
+            if c.x.is_nan() || c.y.is_nan() {
+                (WkbShape::Points(vec![]), false)
+            } else {
+                (WkbShape::Points(vec![c]), false)
+            }
+        }
+        2 => (WkbShape::Lines(vec![read_coords(bor)?]), false),
+        3 => {
+            let n = bor.read_u32()? as usize;
+            let mut rings = Vec::with_capacity(n);
+            for _ in 0..n {
+                rings.push(read_coords(bor)?);
+            }
+            let polygons = if rings.is_empty() { vec![] } else { vec![rings] };
+            (WkbShape::Polygons(polygons), false)
+        }
+        4..=6 => {
+            let n = bor.read_u32()? as usize;
+            let mut points = vec![];
+            let mut lines = vec![];
+            let mut polygons = vec![];
+            for _ in 0..n {
+                let part = read_wkb(bor)?;
+                has_z = has_z || part.has_z;
+                has_m = has_m || part.has_m;
+                match part.shape {
+                    WkbShape::Points(p) => points.extend(p),
+                    WkbShape::Lines(l) => lines.extend(l),
+                    WkbShape::Polygons(p) => polygons.extend(p),
+                }
+            }
+            match code % 1000 {
+                4 => (WkbShape::Points(points), true),
+                5 => (WkbShape::Lines(lines), true),
+                _ => (WkbShape::Polygons(polygons), true),
+            }
+        }
+        c => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported WKB geometry type: {}", c),
+            ))
+        }
+    };
+    Ok(WkbGeometry {
+        shape,
+        is_multi,
+        has_z,
+        has_m,
+    })
+}
+
+/// Encodes a record as a GeoPackage geometry blob, returning None for null records.
+fn write_gpkg_geometry(
+    sfg: &ShapefileGeometry,
+    srs_id: i32,
+    is_multi: bool,
+) -> Result<Option<Vec<u8>>, Error> {
+    if sfg.shape_type == ShapeType::Null || sfg.num_points == 0 {
+        return Ok(None);
+    }
+    let mut buf: Vec<u8> = Vec::with_capacity(48 + sfg.num_points as usize * 24);
+    buf.extend_from_slice(b"GP");
+    buf.push(0); // version
+    buf.push(0x03); // little-endian, with an xy envelope
+    buf.write_i32::<LittleEndian>(srs_id)?;
+    buf.write_f64::<LittleEndian>(sfg.x_min)?;
+    buf.write_f64::<LittleEndian>(sfg.x_max)?;
+    buf.write_f64::<LittleEndian>(sfg.y_min)?;
+    buf.write_f64::<LittleEndian>(sfg.y_max)?;
+
+    let dimension = sfg.shape_type.dimension();
+    let has_z = dimension == ShapeTypeDimension::Z && sfg.has_z_data();
+    let has_m = dimension == ShapeTypeDimension::Measure && sfg.has_m_data();
+    let offset = match (has_z, has_m) {
+        (true, _) => 1000,
+        (false, true) => 2000,
+        _ => 0,
+    };
+    let write_header = |buf: &mut Vec<u8>, code: u32| -> Result<(), Error> {
+        buf.push(1); // little-endian
+        buf.write_u32::<LittleEndian>(code + offset)?;
+        Ok(())
+    };
+    let write_coords = |buf: &mut Vec<u8>, indices: &mut dyn Iterator<Item = usize>| -> Result<(), Error> {
+        for i in indices {
+            buf.write_f64::<LittleEndian>(sfg.points[i].x)?;
+            buf.write_f64::<LittleEndian>(sfg.points[i].y)?;
+            if has_z {
+                buf.write_f64::<LittleEndian>(sfg.z_array[i])?;
+            }
+            if has_m {
+                buf.write_f64::<LittleEndian>(sfg.m_array[i])?;
+            }
+        }
+        Ok(())
+    };
+
+    match sfg.shape_type.base_shape_type() {
+        ShapeType::Point => {
+            write_header(&mut buf, 1)?;
+            write_coords(&mut buf, &mut (0..1))?;
+        }
+        ShapeType::MultiPoint => {
+            write_header(&mut buf, 4)?;
+            buf.write_u32::<LittleEndian>(sfg.num_points as u32)?;
+            for i in 0..sfg.num_points as usize {
+                write_header(&mut buf, 1)?;
+                write_coords(&mut buf, &mut (i..i + 1))?;
+            }
+        }
+        ShapeType::PolyLine => {
+            if is_multi {
+                write_header(&mut buf, 5)?;
+                buf.write_u32::<LittleEndian>(sfg.num_parts as u32)?;
+            }
+            for part in 0..sfg.num_parts as usize {
+                let (start, end) = sfg.get_part_range(part);
+                write_header(&mut buf, 2)?;
+                buf.write_u32::<LittleEndian>((end - start) as u32)?;
+                write_coords(&mut buf, &mut (start..end))?;
+            }
+        }
+        _ => {
+            // Exterior rings are written counter-clockwise and holes clockwise.
+            let polygons = sfg.get_polygon_parts();
+            if is_multi {
+                write_header(&mut buf, 6)?;
+                buf.write_u32::<LittleEndian>(polygons.len() as u32)?;
+            }
+            for rings in &polygons {
+                write_header(&mut buf, 3)?;
+                buf.write_u32::<LittleEndian>(rings.len() as u32)?;
+                for part in rings {
+                    let (start, end) = sfg.get_part_range(*part);
+                    buf.write_u32::<LittleEndian>((end - start) as u32)?;
+                    write_coords(&mut buf, &mut (start..end).rev())?;
+                }
+            }
+        }
+    }
+    Ok(Some(buf))
+}
+
+#[cfg(test)]
+mod test {
+    use super::read_gpkg_geometry;
+    use crate::shapefile::attributes::*;
+    use crate::shapefile::geometry::*;
+    use crate::shapefile::Shapefile;
+    use std::env;
+    use std::fs;
+    use std::io::ErrorKind;
+    use whitebox_common::structures::Point2D;
+
+    fn temp_file(name: &str) -> String {
+        let file_name = env::temp_dir()
+            .join(format!("whitebox_vector_gpkg_{}", name))
+            .to_string_lossy()
+            .to_string();
+        let _ = fs::remove_file(&file_name);
+        file_name
+    }
+
+    #[test]
+    fn test_attributes_round_trip() {
+        let file_name = temp_file("attributes.gpkg");
+        let mut output = Shapefile::new(&file_name, ShapeType::Point).unwrap();
+        let fields = [
+            AttributeField::new("land_cover_classification", FieldDataType::Int, 11u8, 0u8),
+            AttributeField::new("population_estimate", FieldDataType::Int, 20u8, 0u8),
+            AttributeField::new("mean_elevation_metres", FieldDataType::Real, 12u8, 3u8),
+            AttributeField::new("site description", FieldDataType::Text, 40u8, 0u8),
+            AttributeField::new("first_surveyed", FieldDataType::Date, 8u8, 0u8),
+            AttributeField::new("is_protected", FieldDataType::Bool, 1u8, 0u8),
+        ];
+        for field in &fields {
+            output.attributes.add_field(field);
+        }
+        let records = [
+            vec![
+                FieldData::Int(3),
+                FieldData::Int64(8_100_000_000),
+                FieldData::Real(212.125),
+                FieldData::Text("Mixed forest, 'north' slope".to_string()),
+                FieldData::Date(DateData { year: 2019, month: 11, day: 2 }),
+                FieldData::Bool(true),
+            ],
+            vec![
+                FieldData::Null,
+                FieldData::Int64(-3_000_000_000),
+                FieldData::Null,
+                FieldData::Text("Wetland".to_string()),
+                FieldData::Null,
+                FieldData::Bool(false),
+            ],
+            vec![FieldData::Null; 6],
+        ];
+        for (i, rec) in records.iter().enumerate() {
+            output.add_point_record(i as f64, -(i as f64));
+            output.attributes.add_record(rec.clone(), false);
+        }
+        output.write().unwrap();
+
+        let input = Shapefile::read(&file_name).unwrap();
+        assert_eq!(input.header.shape_type, ShapeType::Point);
+        assert_eq!(input.num_records, 3);
+        assert_eq!(input.attributes.get_num_fields(), fields.len());
+        for (i, field) in fields.iter().enumerate() {
+            assert_eq!(input.attributes.get_field(i).name, field.name);
+            assert_eq!(input.attributes.get_field(i).field_type, field.field_type);
+        }
+        for (i, rec) in records.iter().enumerate() {
+            assert_eq!(&input.attributes.get_record(i), rec);
+            assert_eq!(input.get_record(i).points, vec![Point2D::new(i as f64, -(i as f64))]);
+        }
+        fs::remove_file(&file_name).unwrap();
+    }
+
+    #[test]
+    fn test_layer_selection() {
+        let file_name = temp_file("layers.gpkg");
+        let mut roads = Shapefile::new(&format!("{}:roads", file_name), ShapeType::PolyLine).unwrap();
+        roads.attributes.add_field(&AttributeField::new("NAME", FieldDataType::Text, 10u8, 0u8));
+        let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+        sfg.add_part(&[Point2D::new(0.0, 0.0), Point2D::new(10.0, 5.0)]);
+        roads.add_record(sfg);
+        roads.attributes.add_record(vec![FieldData::Text("Main".to_string())], false);
+        roads.write().unwrap();
+
+        let mut sites = Shapefile::new(&format!("{}:sites", file_name), ShapeType::Point).unwrap();
+        sites.attributes.add_field(&AttributeField::new("ID", FieldDataType::Int, 4u8, 0u8));
+        for i in 0..3 {
+            sites.add_point_record(i as f64, i as f64);
+            sites.attributes.add_record(vec![FieldData::Int(i)], false);
+        }
+        sites.write().unwrap();
+
+        // the layer must be named when there is more than one
+        let err = Shapefile::read(&file_name).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("roads, sites"));
+
+        let input = Shapefile::read(&format!("{}:sites", file_name)).unwrap();
+        assert_eq!(input.header.shape_type, ShapeType::Point);
+        assert_eq!(input.num_records, 3);
+        assert_eq!(input.attributes.get_value(2, "ID"), FieldData::Int(2));
+
+        // layer names are not case sensitive
+        let input = Shapefile::read(&format!("{}:ROADS", file_name)).unwrap();
+        assert_eq!(input.header.shape_type, ShapeType::PolyLine);
+        assert_eq!(input.get_record(0).points[1], Point2D::new(10.0, 5.0));
+        assert_eq!(input.attributes.get_value(0, "NAME"), FieldData::Text("Main".to_string()));
+
+        let err = Shapefile::read(&format!("{}:rivers", file_name)).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // writing a layer again replaces it, leaving the other layers in place
+        let mut sites = Shapefile::new(&format!("{}:sites", file_name), ShapeType::Point).unwrap();
+        sites.attributes.add_field(&AttributeField::new("ID", FieldDataType::Int, 4u8, 0u8));
+        sites.add_point_record(5.0, 5.0);
+        sites.attributes.add_record(vec![FieldData::Int(5)], false);
+        sites.write().unwrap();
+        assert_eq!(Shapefile::read(&format!("{}:sites", file_name)).unwrap().num_records, 1);
+        assert_eq!(Shapefile::read(&format!("{}:roads", file_name)).unwrap().num_records, 1);
+        fs::remove_file(&file_name).unwrap();
+    }
+
+    #[test]
+    fn test_truncated_envelope() {
+        // the flags declare a 32-byte envelope, but the blob ends after the header
+        let mut blob = vec![b'G', b'P', 0u8, 0b0000_0011, 0, 0, 0, 0];
+        let err = read_gpkg_geometry(&blob).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        blob.extend_from_slice(&[0u8; 31]);
+        assert!(read_gpkg_geometry(&blob).is_err());
+        // an empty geometry with its envelope
+        blob.push(0);
+        blob[3] |= 0x10;
+        assert!(read_gpkg_geometry(&blob).unwrap().is_none());
+    }
+}
@@ -2,11 +2,13 @@
 This code is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: June 21, 2017
-Last Modified: 12/04/2018
+Last Modified: 17/10/2026
 License: MIT
 */
 
 // private sub-module defined in other files
+mod geojson;
+mod geopackage;
 pub mod shapefile;
 
 // exports identifiers from private sub-modules in the current module namespace
@@ -14,8 +16,13 @@ pub mod shapefile;
 //     AttributeField, AttributeHeader, DateData, FieldData, FieldDataType, Intersector,
 //     ShapefileAttributes,
 // };
+pub use crate::geopackage::split_layer_name;
 pub use crate::shapefile::attributes::*;
+pub use crate::shapefile::expression::AttributeExpression;
 pub use crate::shapefile::geometry::*;
 pub use crate::shapefile::geometry::ShapeType;
 pub use crate::shapefile::Shapefile;
+pub use crate::shapefile::{
+    add_suffix_to_vector_file_name, get_vector_format_from_file, VectorFormat,
+};
 // pub use whitebox_common::structures::Point2D;
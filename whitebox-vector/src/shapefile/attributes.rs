@@ -2,7 +2,7 @@
 This file is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 10/04/2018
-Last Modified: 12/04/2018
+Last Modified: 17/10/2026
 License: MIT
 
 NOTE: Structures and functions for handling the Shapefile attribute table info
@@ -59,6 +59,8 @@ impl fmt::Display for DateData {
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldData {
     Int(i32),
+    /// An integer value that is outside of the range of a 32-bit integer.
+    Int64(i64),
     Real(f64),
     Text(String),
     Date(DateData),
@@ -71,6 +73,7 @@ impl fmt::Display for FieldData {
         // write!(f, "{:?}", self)
         match self {
             FieldData::Int(value) => return write!(f, "{}", value),
+            FieldData::Int64(value) => return write!(f, "{}", value),
             FieldData::Real(value) => return write!(f, "{}", value),
             FieldData::Text(value) => return write!(f, "{}", value),
             FieldData::Date(value) => return write!(f, "{}", value),
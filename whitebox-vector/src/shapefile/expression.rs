@@ -0,0 +1,1067 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTE: A parser and evaluator for the logical expressions that are used to select the records
+of an attribute table, e.g. CLASS = 2 AND NAME LIKE 'f%'.
+*/
+
+use super::attributes::{DateData, FieldData, ShapefileAttributes};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+
+/// A logical expression over the fields of an attribute table, e.g.
+/// `CLASS = 2 AND NAME LIKE 'f%'`, that is parsed once and then evaluated for each record.
+///
+/// Field names are written as they are, or within double quotes if they contain spaces or
+/// are keywords (e.g. `"DATE"`), and text values within single quotes. Dates are written as
+/// `DATE '2024-05-31'`, and text values are also converted to dates when they are compared
+/// with a date field. The operators are `AND`, `OR`, `NOT`, the comparisons (`=`, `!=`, `<>`,
+/// `<`, `<=`, `>`, `>=`), `+`, `-`, `*`, `/`, `%`, `IS [NOT] NULL`, `[NOT] IN (...)`,
+/// `[NOT] LIKE` (with the `%` and `_` wildcards), and `[NOT] BETWEEN ... AND ...`.
+///
+/// Null values follow the rules of SQL: comparisons and functions of a null value are null,
+/// and a record is only matched if the expression is true.
+pub struct AttributeExpression {
+    root: Node,
+}
+
+impl AttributeExpression {
+    /// Parses an expression, finding its fields in the table `attributes`. Field names are
+    /// matched exactly or, failing that, ignoring case.
+    pub fn parse(
+        expression: &str,
+        attributes: &ShapefileAttributes,
+    ) -> Result<AttributeExpression, Error> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            pos: 0,
+            attributes,
+        };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(syntax_error(format!("unexpected {}", token)));
+        }
+        Ok(AttributeExpression { root })
+    }
+
+    /// Evaluates the expression for a record of the attribute table.
+    pub fn evaluate(&self, record: &[FieldData]) -> Result<FieldData, Error> {
+        self.root.evaluate(record)
+    }
+
+    /// Returns true if the expression is true for a record of the attribute table, and false
+    /// if it is false or null.
+    pub fn is_match(&self, record: &[FieldData]) -> Result<bool, Error> {
+        match self.evaluate(record)? {
+            FieldData::Bool(value) => Ok(value),
+            FieldData::Null => Ok(false),
+            value => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The expression evaluates to {}, rather than true or false.",
+                    describe(&value)
+                ),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Identifier(String),
+    QuotedIdentifier(String),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "'{}'", value),
+            Token::Text(value) => write!(f, "'{}'", value.replace('\'', "''")),
+            Token::Identifier(name) => write!(f, "'{}'", name),
+            Token::QuotedIdentifier(name) => write!(f, "'\"{}\"'", name),
+            Token::Symbol(symbol) => write!(f, "'{}'", symbol),
+        }
+    }
+}
+
+// The two-character symbols come first, so that they are matched before their first character.
+const SYMBOLS: [&str; 19] = [
+    "==", "!=", "<>", "<=", ">=", "&&", "||", "(", ")", ",", "=", "<", ">", "+", "-", "*", "/",
+    "%", "!",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, Error> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let number: String = chars[start..i].iter().collect();
+            match number.parse::<f64>() {
+                Ok(value) => tokens.push(Token::Number(value)),
+                Err(_) => return Err(syntax_error(format!("'{}' is not a number", number))),
+            }
+        } else if c == '\'' || c == '"' {
+            // a doubled quote stands for the quote character itself
+            let mut value = String::new();
+            let mut is_closed = false;
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == c {
+                    if chars.get(i + 1) == Some(&c) {
+                        value.push(c);
+                        i += 2;
+                        continue;
+                    }
+                    is_closed = true;
+                    i += 1;
+                    break;
+                }
+                value.push(chars[i]);
+                i += 1;
+            }
+            if !is_closed {
+                return Err(syntax_error(format!("missing closing quote ({})", c)));
+            }
+            tokens.push(if c == '\'' {
+                Token::Text(value)
+            } else {
+                Token::QuotedIdentifier(value)
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Identifier(chars[start..i].iter().collect()));
+        } else {
+            let next: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            match SYMBOLS.iter().find(|s| next.starts_with(*s)) {
+                Some(symbol) => {
+                    tokens.push(Token::Symbol(symbol));
+                    i += symbol.len();
+                }
+                None => return Err(syntax_error(format!("unexpected character '{}'", c))),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn from_symbol(symbol: &str) -> Option<Comparison> {
+        match symbol {
+            "=" | "==" => Some(Comparison::Equal),
+            "!=" | "<>" => Some(Comparison::NotEqual),
+            "<" => Some(Comparison::Less),
+            "<=" => Some(Comparison::LessOrEqual),
+            ">" => Some(Comparison::Greater),
+            ">=" => Some(Comparison::GreaterOrEqual),
+            _ => None,
+        }
+    }
+
+    fn is_true(&self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Equal => ordering == Ordering::Equal,
+            Comparison::NotEqual => ordering != Ordering::Equal,
+            Comparison::Less => ordering == Ordering::Less,
+            Comparison::LessOrEqual => ordering != Ordering::Greater,
+            Comparison::Greater => ordering == Ordering::Greater,
+            Comparison::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Arithmetic {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl Arithmetic {
+    fn from_symbol(symbol: &str) -> Option<Arithmetic> {
+        match symbol {
+            "+" => Some(Arithmetic::Add),
+            "-" => Some(Arithmetic::Subtract),
+            "*" => Some(Arithmetic::Multiply),
+            "/" => Some(Arithmetic::Divide),
+            "%" => Some(Arithmetic::Remainder),
+            _ => None,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Arithmetic::Add => "+",
+            Arithmetic::Subtract => "-",
+            Arithmetic::Multiply => "*",
+            Arithmetic::Divide => "/",
+            Arithmetic::Remainder => "%",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Function {
+    Upper,
+    Lower,
+    Trim,
+    Length,
+    Substr,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Concat,
+    Coalesce,
+    Abs,
+    Round,
+    Floor,
+    Ceil,
+    Date,
+    Year,
+    Month,
+    Day,
+    Today,
+}
+
+// The name of each function, with its minimum and maximum numbers of arguments.
+const FUNCTIONS: [(&str, Function, usize, usize); 19] = [
+    ("upper", Function::Upper, 1, 1),
+    ("lower", Function::Lower, 1, 1),
+    ("trim", Function::Trim, 1, 1),
+    ("length", Function::Length, 1, 1),
+    ("substr", Function::Substr, 2, 3),
+    ("contains", Function::Contains, 2, 2),
+    ("starts_with", Function::StartsWith, 2, 2),
+    ("ends_with", Function::EndsWith, 2, 2),
+    ("concat", Function::Concat, 1, usize::MAX),
+    ("coalesce", Function::Coalesce, 1, usize::MAX),
+    ("abs", Function::Abs, 1, 1),
+    ("round", Function::Round, 1, 2),
+    ("floor", Function::Floor, 1, 1),
+    ("ceil", Function::Ceil, 1, 1),
+    ("date", Function::Date, 1, 3),
+    ("year", Function::Year, 1, 1),
+    ("month", Function::Month, 1, 1),
+    ("day", Function::Day, 1, 1),
+    ("today", Function::Today, 0, 0),
+];
+
+#[derive(Debug)]
+enum Node {
+    Value(FieldData),
+    Field(usize),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Compare(Comparison, Box<Node>, Box<Node>),
+    Arithmetic(Arithmetic, Box<Node>, Box<Node>),
+    Negate(Box<Node>),
+    IsNull(Box<Node>),
+    In(Box<Node>, Vec<Node>),
+    Like(Box<Node>, Box<Node>),
+    Between(Box<Node>, Box<Node>, Box<Node>),
+    Function(Function, Vec<Node>),
+}
+
+/// A recursive descent parser. From the lowest to the highest precedence, the operators are
+/// OR; AND; NOT; the comparisons, IS NULL, IN, LIKE, and BETWEEN; + and -; and *, / and %.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    attributes: &'a ShapefileAttributes,
+}
+
+impl<'a> Parser<'a> {
+    fn is_keyword(&self, offset: usize, keyword: &str) -> bool {
+        match self.tokens.get(self.pos + offset) {
+            Some(Token::Identifier(name)) => name.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        if self.is_keyword(0, keyword) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn accept_symbol(&mut self, symbol: &str) -> bool {
+        if matches!(self.tokens.get(self.pos), Some(Token::Symbol(s)) if *s == symbol) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, found: bool, expected: &str) -> Result<(), Error> {
+        if found {
+            return Ok(());
+        }
+        Err(match self.tokens.get(self.pos) {
+            Some(token) => syntax_error(format!("expected {} but found {}", expected, token)),
+            None => syntax_error(format!("expected {} at the end", expected)),
+        })
+    }
+
+    fn parse_or(&mut self) -> Result<Node, Error> {
+        let mut node = self.parse_and()?;
+        while self.accept_keyword("OR") || self.accept_symbol("||") {
+            node = Node::Or(Box::new(node), Box::new(self.parse_and()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, Error> {
+        let mut node = self.parse_not()?;
+        while self.accept_keyword("AND") || self.accept_symbol("&&") {
+            node = Node::And(Box::new(node), Box::new(self.parse_not()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, Error> {
+        if self.accept_keyword("NOT") || self.accept_symbol("!") {
+            return Ok(Node::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> Result<Node, Error> {
+        let left = self.parse_additive()?;
+        if let Some(Token::Symbol(symbol)) = self.tokens.get(self.pos) {
+            if let Some(comparison) = Comparison::from_symbol(symbol) {
+                self.pos += 1;
+                let right = self.parse_additive()?;
+                return Ok(Node::Compare(comparison, Box::new(left), Box::new(right)));
+            }
+        }
+        if self.accept_keyword("IS") {
+            let is_negated = self.accept_keyword("NOT");
+            let found = self.accept_keyword("NULL");
+            self.expect(found, "NULL")?;
+            let node = Node::IsNull(Box::new(left));
+            return Ok(if is_negated {
+                Node::Not(Box::new(node))
+            } else {
+                node
+            });
+        }
+        let is_negated = self.is_keyword(0, "NOT")
+            && (self.is_keyword(1, "IN") || self.is_keyword(1, "LIKE") || self.is_keyword(1, "BETWEEN"));
+        if is_negated {
+            self.pos += 1;
+        }
+        let node = if self.accept_keyword("IN") {
+            let found = self.accept_symbol("(");
+            self.expect(found, "'('")?;
+            let mut list = vec![self.parse_or()?];
+            while self.accept_symbol(",") {
+                list.push(self.parse_or()?);
+            }
+            let found = self.accept_symbol(")");
+            self.expect(found, "')'")?;
+            Node::In(Box::new(left), list)
+        } else if self.accept_keyword("LIKE") {
+            Node::Like(Box::new(left), Box::new(self.parse_additive()?))
+        } else if self.accept_keyword("BETWEEN") {
+            let low = self.parse_additive()?;
+            let found = self.accept_keyword("AND");
+            self.expect(found, "AND")?;
+            let high = self.parse_additive()?;
+            Node::Between(Box::new(left), Box::new(low), Box::new(high))
+        } else {
+            return Ok(left);
+        };
+        Ok(if is_negated {
+            Node::Not(Box::new(node))
+        } else {
+            node
+        })
+    }
+
+    fn parse_additive(&mut self) -> Result<Node, Error> {
+        let mut node = self.parse_multiplicative()?;
+        while let Some(Token::Symbol(symbol @ ("+" | "-"))) = self.tokens.get(self.pos) {
+            let operation = Arithmetic::from_symbol(symbol).unwrap();
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            node = Node::Arithmetic(operation, Box::new(node), Box::new(right));
+        }
+        Ok(node)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Node, Error> {
+        let mut node = self.parse_unary()?;
+        while let Some(Token::Symbol(symbol @ ("*" | "/" | "%"))) = self.tokens.get(self.pos) {
+            let operation = Arithmetic::from_symbol(symbol).unwrap();
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            node = Node::Arithmetic(operation, Box::new(node), Box::new(right));
+        }
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, Error> {
+        if self.accept_symbol("-") {
+            return Ok(Node::Negate(Box::new(self.parse_unary()?)));
+        }
+        if self.accept_symbol("+") {
+            return self.parse_unary();
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, Error> {
+        let token = match self.tokens.get(self.pos) {
+            Some(token) => token.clone(),
+            None => return Err(syntax_error("the expression ends unexpectedly".to_string())),
+        };
+        self.pos += 1;
+        match token {
+            Token::Number(value) => Ok(Node::Value(FieldData::Real(value))),
+            Token::Text(value) => Ok(Node::Value(FieldData::Text(value))),
+            Token::QuotedIdentifier(name) => self.field(&name),
+            Token::Symbol("(") => {
+                let node = self.parse_or()?;
+                let found = self.accept_symbol(")");
+                self.expect(found, "')'")?;
+                Ok(node)
+            }
+            Token::Identifier(name) => {
+                if self.accept_symbol("(") {
+                    return self.function(&name);
+                }
+                if name.eq_ignore_ascii_case("TRUE") {
+                    Ok(Node::Value(FieldData::Bool(true)))
+                } else if name.eq_ignore_ascii_case("FALSE") {
+                    Ok(Node::Value(FieldData::Bool(false)))
+                } else if name.eq_ignore_ascii_case("NULL") {
+                    Ok(Node::Value(FieldData::Null))
+                } else if let (true, Some(Token::Text(value))) = (
+                    name.eq_ignore_ascii_case("DATE"),
+                    self.tokens.get(self.pos).cloned(),
+                ) {
+                    self.pos += 1;
+                    Ok(Node::Value(FieldData::Date(parse_date(&value)?)))
+                } else {
+                    self.field(&name)
+                }
+            }
+            token => Err(syntax_error(format!("unexpected {}", token))),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<Node, Error> {
+        let fields = self.attributes.get_fields();
+        let index = fields.iter().position(|f| f.name == name).or_else(|| {
+            fields
+                .iter()
+                .position(|f| f.name.eq_ignore_ascii_case(name))
+        });
+        match index {
+            Some(index) => Ok(Node::Field(index)),
+            None => Err(syntax_error(format!(
+                "there is no field named '{}' in the attribute table",
+                name
+            ))),
+        }
+    }
+
+    fn function(&mut self, name: &str) -> Result<Node, Error> {
+        let (function, min_args, max_args) = match FUNCTIONS
+            .iter()
+            .find(|f| f.0.eq_ignore_ascii_case(name))
+        {
+            Some(f) => (f.1, f.2, f.3),
+            None => return Err(syntax_error(format!("there is no function named '{}'", name))),
+        };
+        let mut args = vec![];
+        if !self.accept_symbol(")") {
+            args.push(self.parse_or()?);
+            while self.accept_symbol(",") {
+                args.push(self.parse_or()?);
+            }
+            let found = self.accept_symbol(")");
+            self.expect(found, "')'")?;
+        }
+        if args.len() < min_args || args.len() > max_args {
+            let expected = if min_args == max_args {
+                format!("{}", min_args)
+            } else if max_args == usize::MAX {
+                format!("at least {}", min_args)
+            } else {
+                format!("{} to {}", min_args, max_args)
+            };
+            return Err(syntax_error(format!(
+                "the function '{}' takes {} argument(s), not {}",
+                name,
+                expected,
+                args.len()
+            )));
+        }
+        Ok(Node::Function(function, args))
+    }
+}
+
+impl Node {
+    fn evaluate(&self, record: &[FieldData]) -> Result<FieldData, Error> {
+        Ok(match self {
+            Node::Value(value) => value.clone(),
+            Node::Field(index) => record.get(*index).cloned().unwrap_or(FieldData::Null),
+            Node::Not(node) => match logical(&node.evaluate(record)?)? {
+                Some(value) => FieldData::Bool(!value),
+                None => FieldData::Null,
+            },
+            Node::And(left, right) => {
+                let left = logical(&left.evaluate(record)?)?;
+                if left == Some(false) {
+                    return Ok(FieldData::Bool(false));
+                }
+                and(left, logical(&right.evaluate(record)?)?)
+            }
+            Node::Or(left, right) => {
+                let left = logical(&left.evaluate(record)?)?;
+                if left == Some(true) {
+                    return Ok(FieldData::Bool(true));
+                }
+                match (left, logical(&right.evaluate(record)?)?) {
+                    (_, Some(true)) => FieldData::Bool(true),
+                    (Some(false), Some(false)) => FieldData::Bool(false),
+                    _ => FieldData::Null,
+                }
+            }
+            Node::Compare(comparison, left, right) => {
+                match compare(&left.evaluate(record)?, &right.evaluate(record)?)? {
+                    Some(ordering) => FieldData::Bool(comparison.is_true(ordering)),
+                    None => FieldData::Null,
+                }
+            }
+            Node::Arithmetic(operation, left, right) => {
+                arithmetic(*operation, left.evaluate(record)?, right.evaluate(record)?)?
+            }
+            Node::Negate(node) => match node.evaluate(record)? {
+                FieldData::Null => FieldData::Null,
+                value => FieldData::Real(-number(&value)?),
+            },
+            Node::IsNull(node) => FieldData::Bool(node.evaluate(record)? == FieldData::Null),
+            Node::In(node, list) => {
+                let value = node.evaluate(record)?;
+                if value == FieldData::Null {
+                    return Ok(FieldData::Null);
+                }
+                let mut has_null = false;
+                for item in list {
+                    match compare(&value, &item.evaluate(record)?)? {
+                        Some(Ordering::Equal) => return Ok(FieldData::Bool(true)),
+                        Some(_) => {}
+                        None => has_null = true,
+                    }
+                }
+                if has_null {
+                    FieldData::Null
+                } else {
+                    FieldData::Bool(false)
+                }
+            }
+            Node::Like(node, pattern) => match (node.evaluate(record)?, pattern.evaluate(record)?) {
+                (FieldData::Null, _) | (_, FieldData::Null) => FieldData::Null,
+                (value, pattern) => {
+                    let value: Vec<char> = text(&value).chars().collect();
+                    let pattern: Vec<char> = text(&pattern).chars().collect();
+                    FieldData::Bool(is_like(&value, &pattern))
+                }
+            },
+            Node::Between(node, low, high) => {
+                let value = node.evaluate(record)?;
+                let above_low = compare(&value, &low.evaluate(record)?)?
+                    .map(|ordering| ordering != Ordering::Less);
+                let below_high = compare(&value, &high.evaluate(record)?)?
+                    .map(|ordering| ordering != Ordering::Greater);
+                and(above_low, below_high)
+            }
+            Node::Function(function, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(record))
+                    .collect::<Result<Vec<FieldData>, Error>>()?;
+                call(*function, &args)?
+            }
+        })
+    }
+}
+
+fn syntax_error(message: String) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("Error parsing the expression: {}.", message),
+    )
+}
+
+fn type_error(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+/// Describes a value and its type, for use in error messages.
+fn describe(value: &FieldData) -> String {
+    match value {
+        FieldData::Int(_) | FieldData::Int64(_) | FieldData::Real(_) => {
+            format!("the number {}", value)
+        }
+        FieldData::Text(value) => format!("the text '{}'", value),
+        FieldData::Date(value) => format!(
+            "the date {:04}-{:02}-{:02}",
+            value.year, value.month, value.day
+        ),
+        FieldData::Bool(value) => format!("{}", value),
+        FieldData::Null => "null".to_string(),
+    }
+}
+
+/// The value of a logical operand, which is None if it is null.
+fn logical(value: &FieldData) -> Result<Option<bool>, Error> {
+    match value {
+        FieldData::Bool(value) => Ok(Some(*value)),
+        FieldData::Null => Ok(None),
+        value => Err(type_error(format!(
+            "Expected true or false, but found {}.",
+            describe(value)
+        ))),
+    }
+}
+
+fn and(left: Option<bool>, right: Option<bool>) -> FieldData {
+    match (left, right) {
+        (Some(false), _) | (_, Some(false)) => FieldData::Bool(false),
+        (Some(true), Some(true)) => FieldData::Bool(true),
+        _ => FieldData::Null,
+    }
+}
+
+fn as_number(value: &FieldData) -> Option<f64> {
+    match value {
+        FieldData::Int(value) => Some(*value as f64),
+        FieldData::Int64(value) => Some(*value as f64),
+        FieldData::Real(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn number(value: &FieldData) -> Result<f64, Error> {
+    as_number(value).ok_or_else(|| {
+        type_error(format!("Expected a number, but found {}.", describe(value)))
+    })
+}
+
+/// The text of a value, which is used by the string functions and LIKE. Values that are not
+/// text are formatted as they are in the attribute table.
+fn text(value: &FieldData) -> String {
+    match value {
+        FieldData::Text(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn date(value: &FieldData) -> Result<DateData, Error> {
+    match value {
+        FieldData::Date(value) => Ok(*value),
+        FieldData::Text(value) => parse_date(value),
+        value => Err(type_error(format!(
+            "Expected a date, but found {}.",
+            describe(value)
+        ))),
+    }
+}
+
+/// Parses a date written as YYYY-MM-DD, YYYY/MM/DD, or YYYYMMDD.
+fn parse_date(value: &str) -> Result<DateData, Error> {
+    let value = value.trim();
+    ["%Y-%m-%d", "%Y/%m/%d", "%Y%m%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .and_then(to_date_data)
+        .ok_or_else(|| {
+            type_error(format!(
+                "'{}' is not a valid date; dates are written as 'YYYY-MM-DD'.",
+                value
+            ))
+        })
+}
+
+fn to_naive_date(value: &DateData) -> Result<NaiveDate, Error> {
+    NaiveDate::from_ymd_opt(value.year as i32, value.month as u32, value.day as u32).ok_or_else(
+        || {
+            type_error(format!(
+                "{:04}-{:02}-{:02} is not a valid date.",
+                value.year, value.month, value.day
+            ))
+        },
+    )
+}
+
+fn to_date_data(value: NaiveDate) -> Option<DateData> {
+    if value.year() < 0 || value.year() > u16::MAX as i32 {
+        return None;
+    }
+    Some(DateData {
+        year: value.year() as u16,
+        month: value.month() as u8,
+        day: value.day() as u8,
+    })
+}
+
+/// Compares two values, which is None if either is null. Text is converted to a date when it
+/// is compared with a date.
+fn compare(left: &FieldData, right: &FieldData) -> Result<Option<Ordering>, Error> {
+    let date_key = |d: DateData| (d.year, d.month, d.day);
+    Ok(match (left, right) {
+        (FieldData::Null, _) | (_, FieldData::Null) => None,
+        (FieldData::Text(left), FieldData::Text(right)) => Some(left.cmp(right)),
+        (FieldData::Bool(left), FieldData::Bool(right)) => Some(left.cmp(right)),
+        (FieldData::Date(_), FieldData::Date(_) | FieldData::Text(_))
+        | (FieldData::Text(_), FieldData::Date(_)) => {
+            Some(date_key(date(left)?).cmp(&date_key(date(right)?)))
+        }
+        _ => match (as_number(left), as_number(right)) {
+            (Some(left), Some(right)) => left.partial_cmp(&right),
+            _ => {
+                return Err(type_error(format!(
+                    "Cannot compare {} with {}.",
+                    describe(left),
+                    describe(right)
+                )))
+            }
+        },
+    })
+}
+
+fn arithmetic(
+    operation: Arithmetic,
+    left: FieldData,
+    right: FieldData,
+) -> Result<FieldData, Error> {
+    Ok(match (operation, &left, &right) {
+        (_, FieldData::Null, _) | (_, _, FieldData::Null) => FieldData::Null,
+        (Arithmetic::Add, FieldData::Text(left), FieldData::Text(right)) => {
+            FieldData::Text(format!("{}{}", left, right))
+        }
+        (Arithmetic::Subtract, FieldData::Date(left), FieldData::Date(right)) => {
+            let days = (to_naive_date(left)? - to_naive_date(right)?).num_days();
+            FieldData::Int(days as i32)
+        }
+        (Arithmetic::Add | Arithmetic::Subtract, FieldData::Date(value), days)
+        | (Arithmetic::Add, days, FieldData::Date(value))
+            if as_number(days).is_some() =>
+        {
+            // a number of whole days is added to, or subtracted from, the date
+            let mut days = number(days)?.trunc() as i64;
+            if operation == Arithmetic::Subtract {
+                days = -days;
+            }
+            let value = to_naive_date(value)?
+                .checked_add_signed(Duration::days(days))
+                .and_then(to_date_data);
+            match value {
+                Some(value) => FieldData::Date(value),
+                None => return Err(type_error("The date is out of range.".to_string())),
+            }
+        }
+        _ => match (as_number(&left), as_number(&right)) {
+            (Some(left), Some(right)) => {
+                let value = match operation {
+                    Arithmetic::Add => left + right,
+                    Arithmetic::Subtract => left - right,
+                    Arithmetic::Multiply => left * right,
+                    Arithmetic::Divide => left / right,
+                    Arithmetic::Remainder => left % right,
+                };
+                // division by zero gives no value
+                if value.is_finite() {
+                    FieldData::Real(value)
+                } else {
+                    FieldData::Null
+                }
+            }
+            _ => {
+                return Err(type_error(format!(
+                    "Cannot apply '{}' to {} and {}.",
+                    operation.symbol(),
+                    describe(&left),
+                    describe(&right)
+                )))
+            }
+        },
+    })
+}
+
+fn call(function: Function, args: &[FieldData]) -> Result<FieldData, Error> {
+    match function {
+        Function::Concat => {
+            // null arguments are skipped
+            let value: String = args
+                .iter()
+                .filter(|arg| **arg != FieldData::Null)
+                .map(text)
+                .collect();
+            return Ok(FieldData::Text(value));
+        }
+        Function::Coalesce => {
+            let value = args.iter().find(|arg| **arg != FieldData::Null);
+            return Ok(value.cloned().unwrap_or(FieldData::Null));
+        }
+        _ => {}
+    }
+    if args.contains(&FieldData::Null) {
+        return Ok(FieldData::Null);
+    }
+    Ok(match function {
+        Function::Upper => FieldData::Text(text(&args[0]).to_uppercase()),
+        Function::Lower => FieldData::Text(text(&args[0]).to_lowercase()),
+        Function::Trim => FieldData::Text(text(&args[0]).trim().to_string()),
+        Function::Length => FieldData::Int(text(&args[0]).chars().count() as i32),
+        Function::Substr => {
+            // the start is the position of the first character, starting at 1
+            let start = (number(&args[1])?.trunc() as i64 - 1).max(0) as usize;
+            let length = match args.get(2) {
+                Some(length) => number(length)?.trunc().max(0f64) as usize,
+                None => usize::MAX,
+            };
+            FieldData::Text(text(&args[0]).chars().skip(start).take(length).collect())
+        }
+        Function::Contains => FieldData::Bool(text(&args[0]).contains(&text(&args[1]))),
+        Function::StartsWith => FieldData::Bool(text(&args[0]).starts_with(&text(&args[1]))),
+        Function::EndsWith => FieldData::Bool(text(&args[0]).ends_with(&text(&args[1]))),
+        Function::Abs => FieldData::Real(number(&args[0])?.abs()),
+        Function::Round => {
+            let digits = match args.get(1) {
+                Some(digits) => number(digits)?.trunc() as i32,
+                None => 0,
+            };
+            let scale = 10f64.powi(digits);
+            FieldData::Real((number(&args[0])? * scale).round() / scale)
+        }
+        Function::Floor => FieldData::Real(number(&args[0])?.floor()),
+        Function::Ceil => FieldData::Real(number(&args[0])?.ceil()),
+        Function::Date => {
+            if args.len() == 1 {
+                FieldData::Date(date(&args[0])?)
+            } else if args.len() == 3 {
+                let (year, month, day) = (number(&args[0])?, number(&args[1])?, number(&args[2])?);
+                let value = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+                    .and_then(to_date_data);
+                match value {
+                    Some(value) => FieldData::Date(value),
+                    None => {
+                        return Err(type_error(format!(
+                            "date({}, {}, {}) is not a valid date.",
+                            year, month, day
+                        )))
+                    }
+                }
+            } else {
+                return Err(type_error(
+                    "The date function takes either a text value or the year, month, and day."
+                        .to_string(),
+                ));
+            }
+        }
+        Function::Year => FieldData::Int(date(&args[0])?.year as i32),
+        Function::Month => FieldData::Int(date(&args[0])?.month as i32),
+        Function::Day => FieldData::Int(date(&args[0])?.day as i32),
+        Function::Today => match to_date_data(Local::now().date_naive()) {
+            Some(value) => FieldData::Date(value),
+            None => return Err(type_error("The date is out of range.".to_string())),
+        },
+        Function::Concat | Function::Coalesce => unreachable!(),
+    })
+}
+
+/// Matches text with a LIKE pattern, in which '%' matches any sequence of characters and '_'
+/// matches any single character. On a mismatch, the last '%' is extended by a character.
+fn is_like(value: &[char], pattern: &[char]) -> bool {
+    let (mut v, mut p) = (0, 0);
+    let mut last_wildcard: Option<(usize, usize)> = None;
+    while v < value.len() {
+        if p < pattern.len() && pattern[p] == '%' {
+            last_wildcard = Some((p + 1, v));
+            p += 1;
+        } else if p < pattern.len() && (pattern[p] == '_' || pattern[p] == value[v]) {
+            v += 1;
+            p += 1;
+        } else if let Some((wildcard_p, wildcard_v)) = last_wildcard {
+            p = wildcard_p;
+            v = wildcard_v + 1;
+            last_wildcard = Some((wildcard_p, wildcard_v + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AttributeField, FieldDataType};
+
+    fn table() -> ShapefileAttributes {
+        let mut attributes = ShapefileAttributes::default();
+        attributes.add_field(&AttributeField::new("CLASS", FieldDataType::Int, 4u8, 0u8));
+        attributes.add_field(&AttributeField::new("VALUE", FieldDataType::Real, 12u8, 4u8));
+        attributes.add_field(&AttributeField::new("NAME", FieldDataType::Text, 20u8, 0u8));
+        attributes.add_field(&AttributeField::new("SURVEYED", FieldDataType::Date, 8u8, 0u8));
+        attributes.add_field(&AttributeField::new("Land Use", FieldDataType::Text, 20u8, 0u8));
+        attributes
+    }
+
+    fn record(class: i32, value: FieldData, name: &str, date: (u16, u8, u8)) -> Vec<FieldData> {
+        vec![
+            FieldData::Int(class),
+            value,
+            FieldData::Text(name.to_string()),
+            FieldData::Date(DateData {
+                year: date.0,
+                month: date.1,
+                day: date.2,
+            }),
+            FieldData::Null,
+        ]
+    }
+
+    fn is_match(expression: &str, record: &[FieldData]) -> bool {
+        AttributeExpression::parse(expression, &table())
+            .unwrap()
+            .is_match(record)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_logic_and_comparisons() {
+        let r = record(2, FieldData::Real(150.5), "Forest", (2021, 6, 15));
+        assert!(is_match("CLASS = 2 AND VALUE > 100", &r));
+        assert!(is_match("class == 2 && value >= 150.5", &r));
+        assert!(is_match("CLASS = 1 OR NOT VALUE < 100", &r));
+        assert!(!is_match("CLASS <> 2 OR (VALUE < 100 AND NAME = 'Forest')", &r));
+        assert!(is_match("CLASS IN (1, 2, 3) AND CLASS NOT IN (4)", &r));
+        assert!(is_match("VALUE BETWEEN 150 AND 151 AND CLASS NOT BETWEEN 3 AND 4", &r));
+        assert!(is_match("VALUE * 2 - 1 = 300 AND CLASS % 2 = 0", &r));
+        assert!(is_match("-VALUE < -CLASS", &r));
+    }
+
+    #[test]
+    fn test_text_functions() {
+        let r = record(2, FieldData::Real(150.5), " Mixed Forest ", (2021, 6, 15));
+        assert!(is_match("trim(NAME) = 'Mixed Forest'", &r));
+        assert!(is_match("upper(trim(NAME)) LIKE 'MIXED%'", &r));
+        assert!(is_match("NAME LIKE '%For_st%' AND NAME NOT LIKE 'Forest%'", &r));
+        assert!(is_match("contains(lower(NAME), 'forest')", &r));
+        assert!(is_match("starts_with(NAME, ' M') AND ends_with(NAME, 't ')", &r));
+        assert!(is_match("substr(NAME, 2, 5) = 'Mixed' AND length(NAME) = 14", &r));
+        assert!(is_match("concat('class ', CLASS) = 'class 2'", &r));
+        assert!(is_match("'It''s' + ' ok' = 'It''s ok'", &r));
+    }
+
+    #[test]
+    fn test_nulls() {
+        let r = record(2, FieldData::Null, "Forest", (2021, 6, 15));
+        assert!(is_match("VALUE IS NULL AND NAME IS NOT NULL", &r));
+        assert!(is_match("\"Land Use\" IS NULL", &r));
+        // comparisons with null are neither true nor false
+        assert!(!is_match("VALUE > 0", &r));
+        assert!(!is_match("NOT VALUE > 0", &r));
+        assert!(!is_match("VALUE = NULL", &r));
+        assert!(is_match("VALUE > 0 OR CLASS = 2", &r));
+        assert!(!is_match("CLASS IN (1, NULL)", &r));
+        assert!(is_match("coalesce(VALUE, 0) = 0 AND VALUE + 1 IS NULL", &r));
+        assert!(is_match("upper(\"Land Use\") IS NULL", &r));
+    }
+
+    #[test]
+    fn test_dates() {
+        let r = record(2, FieldData::Real(1.0), "Forest", (2021, 6, 15));
+        assert!(is_match("SURVEYED > DATE '2021-01-01'", &r));
+        assert!(is_match("SURVEYED = '2021-06-15' AND SURVEYED < '20210616'", &r));
+        assert!(is_match(
+            "SURVEYED BETWEEN date(2021, 6, 1) AND date('2021/06/30')",
+            &r
+        ));
+        assert!(is_match("year(SURVEYED) = 2021 AND month(SURVEYED) = 6 AND day(SURVEYED) = 15", &r));
+        assert!(is_match("SURVEYED + 17 = DATE '2021-07-02'", &r));
+        assert!(is_match("SURVEYED - DATE '2020-06-15' = 365", &r));
+        assert!(is_match("SURVEYED < today()", &r));
+    }
+
+    #[test]
+    fn test_errors() {
+        let attributes = table();
+        for expression in [
+            "CLASS = ",
+            "CLASS = 2 AND",
+            "(CLASS = 2",
+            "NAME = 'Forest",
+            "ELEVATION > 100",
+            "upper(NAME, 2) = 'X'",
+            "unknown(NAME)",
+            "SURVEYED > DATE '2021-13-01'",
+            "CLASS IS 2",
+            "CLASS = 2 NAME",
+        ] {
+            assert!(
+                AttributeExpression::parse(expression, &attributes).is_err(),
+                "{}",
+                expression
+            );
+        }
+        let r = record(2, FieldData::Real(1.0), "Forest", (2021, 6, 15));
+        for expression in ["NAME > 5", "CLASS AND TRUE", "CLASS + 1", "SURVEYED > 'soon'"] {
+            let expression = AttributeExpression::parse(expression, &attributes).unwrap();
+            assert!(expression.is_match(&r).is_err());
+        }
+    }
+}
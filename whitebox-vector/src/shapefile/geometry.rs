@@ -2,11 +2,11 @@
 This file is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 10/04/2018
-Last Modified: 30/09/2018
+Last Modified: 17/10/2026
 License: MIT
 */
-use whitebox_common::algorithms::point_in_poly;
-use whitebox_common::structures::{BoundingBox, Point2D};
+use whitebox_common::algorithms::{locate_on_lines, locate_on_rings, point_in_poly, FeatureGeometry};
+use whitebox_common::structures::{BoundingBox, Point2D, Polygon};
 use std::f64;
 use std::fmt;
 
@@ -303,6 +303,190 @@ impl ShapefileGeometry {
         point_in_poly(&point, &(self.points[0..last_point]))
     }
 
+    /// Returns the range of point indices, [start, end), belonging to a part.
+    pub fn get_part_range(&self, part: usize) -> (usize, usize) {
+        let start = self.parts[part] as usize;
+        let end = if part < self.num_parts as usize - 1 {
+            self.parts[part + 1] as usize
+        } else {
+            self.num_points as usize
+        };
+        (start, end)
+    }
+
+    /// Groups the parts of a polygon into individual polygons. Each polygon is a list of
+    /// part indices, beginning with an exterior ring and followed by the holes within it.
+    pub fn get_polygon_parts(&self) -> Vec<Vec<usize>> {
+        let mut polygons: Vec<Vec<usize>> = vec![];
+        let mut holes: Vec<usize> = vec![];
+        for part in 0..self.num_parts as usize {
+            if self.is_hole(part as i32) {
+                holes.push(part);
+            } else {
+                polygons.push(vec![part]);
+            }
+        }
+        for hole in holes {
+            let (start, _) = self.get_part_range(hole);
+            let container = polygons.iter().position(|poly| {
+                let (s, e) = self.get_part_range(poly[0]);
+                point_in_poly(&self.points[start], &self.points[s..e])
+            });
+            match container {
+                Some(p) => polygons[p].push(hole),
+                None if !polygons.is_empty() => {
+                    let last = polygons.len() - 1;
+                    polygons[last].push(hole);
+                }
+                None => polygons.push(vec![hole]),
+            }
+        }
+        polygons
+    }
+
+    /// Returns the polygons of a POLYGON geometry, each made of an exterior ring and the holes
+    /// within it.
+    pub fn get_polygons(&self) -> Vec<Polygon> {
+        self.get_polygon_parts()
+            .iter()
+            .map(|parts| {
+                let mut rings = parts.iter().map(|part| {
+                    let (start, end) = self.get_part_range(*part);
+                    self.points[start..end].to_vec()
+                });
+                let exterior = rings.next().unwrap_or_default();
+                Polygon::new(exterior, rings.collect())
+            })
+            .collect()
+    }
+
+    /// Returns the points, lines, or polygons of the geometry, indexed for testing its
+    /// spatial relations with other geometries.
+    pub fn get_feature_geometry(&self) -> FeatureGeometry {
+        match self.shape_type.base_shape_type() {
+            ShapeType::PolyLine => FeatureGeometry::lines(
+                (0..self.num_parts as usize)
+                    .map(|part| {
+                        let (start, end) = self.get_part_range(part);
+                        self.points[start..end].to_vec()
+                    })
+                    .collect(),
+            ),
+            ShapeType::Polygon => FeatureGeometry::polygons(self.get_polygons()),
+            _ => FeatureGeometry::points(self.points.clone()),
+        }
+    }
+
+    /// Creates a geometry with a part for each of the rings of a set of polygons, e.g. the
+    /// output of a polygon overlay. If the shape type has z or measure values, those of each
+    /// vertex are interpolated along the nearest edge of the `sources` geometries that have
+    /// them. Where a vertex is within `tolerance` of the edges of more than one source, e.g.
+    /// at an intersection, the first of these sources is used. Vertices take values of zero
+    /// when no source has them.
+    pub fn from_polygons(
+        shape_type: ShapeType,
+        polygons: &[Polygon],
+        sources: &[&ShapefileGeometry],
+        tolerance: f64,
+    ) -> ShapefileGeometry {
+        let rings: Vec<&Vec<Point2D>> = polygons.iter().flat_map(|p| p.rings()).collect();
+        ShapefileGeometry::from_parts(shape_type, rings, sources, tolerance)
+    }
+
+    /// Creates a geometry with a part for each of a set of lines, e.g. the pieces of a
+    /// polyline that has been split where it crosses other features. The z and measure values
+    /// of the vertices are interpolated along the `sources` geometries as they are by
+    /// `from_polygons`.
+    pub fn from_polylines(
+        shape_type: ShapeType,
+        lines: &[Vec<Point2D>],
+        sources: &[&ShapefileGeometry],
+        tolerance: f64,
+    ) -> ShapefileGeometry {
+        ShapefileGeometry::from_parts(shape_type, lines.iter().collect(), sources, tolerance)
+    }
+
+    fn from_parts(
+        shape_type: ShapeType,
+        rings: Vec<&Vec<Point2D>>,
+        sources: &[&ShapefileGeometry],
+        tolerance: f64,
+    ) -> ShapefileGeometry {
+        let mut sfg = ShapefileGeometry::new(shape_type);
+        let dimension = shape_type.dimension();
+        if dimension == ShapeTypeDimension::XY {
+            for ring in rings {
+                sfg.add_part(ring);
+            }
+            return sfg;
+        }
+
+        let points: Vec<Point2D> = rings.iter().flat_map(|r| r.iter().cloned()).collect();
+        let tolerances: Vec<f64> = points
+            .iter()
+            .map(|p| tolerance.max(1e-9 * p.x.abs().max(p.y.abs()).max(1f64)))
+            .collect();
+        let mut z_values = vec![0f64; points.len()];
+        let mut measures = vec![0f64; points.len()];
+        let mut z_distances = vec![f64::INFINITY; points.len()];
+        let mut m_distances = vec![f64::INFINITY; points.len()];
+        let mut has_m_data = false;
+        for source in sources {
+            let (has_z, has_m) = (source.has_z_data(), source.has_m_data());
+            if !has_z && !has_m {
+                continue;
+            }
+            has_m_data = has_m_data || has_m;
+            let source_rings: Vec<Vec<Point2D>> = (0..source.num_parts as usize)
+                .map(|part| {
+                    let (start, end) = source.get_part_range(part);
+                    source.points[start..end].to_vec()
+                })
+                .collect();
+            // the last vertex of a polyline's part is not joined to its first
+            let positions = if source.shape_type.base_shape_type() == ShapeType::PolyLine {
+                locate_on_lines(&points, &source_rings)
+            } else {
+                locate_on_rings(&points, &source_rings)
+            };
+            for (i, position) in positions.iter().enumerate() {
+                let position = match position {
+                    Some(p) => p,
+                    None => continue,
+                };
+                let (start, _) = source.get_part_range(position.ring);
+                let (a, b) = (start + position.vertex, start + position.next_vertex);
+                let interpolate = |values: &Vec<f64>| values[a] + position.t * (values[b] - values[a]);
+                // an earlier source that is within the tolerance takes precedence
+                if has_z && z_distances[i] > tolerances[i] && position.distance < z_distances[i] {
+                    z_distances[i] = position.distance;
+                    z_values[i] = interpolate(&source.z_array);
+                }
+                if has_m && m_distances[i] > tolerances[i] && position.distance < m_distances[i] {
+                    m_distances[i] = position.distance;
+                    measures[i] = interpolate(&source.m_array);
+                }
+            }
+        }
+
+        let mut start = 0;
+        for ring in rings {
+            let end = start + ring.len();
+            if dimension == ShapeTypeDimension::Z {
+                let ring_measures = if has_m_data {
+                    &measures[start..end]
+                } else {
+                    &[]
+                };
+                sfg.add_partz(ring, ring_measures, &z_values[start..end]);
+            } else {
+                sfg.add_partm(ring, &measures[start..end]);
+            }
+            start = end;
+        }
+        sfg
+    }
+
     /// Checks whether or not a part in a polygon is a hole.
     pub fn is_hole(&self, part_num: i32) -> bool {
         // see if it's a polygon
@@ -329,7 +513,7 @@ impl ShapefileGeometry {
 
         let st_point = self.parts[part_num as usize] as usize;
 
-        let end_point = if part_num < self.num_parts - 2 {
+        let end_point = if part_num < self.num_parts - 1 {
             // remember, the last point in each part is the same as the first...it's not a legitemate point.
             (self.parts[part_num as usize + 1] - 2i32) as usize
         } else {
@@ -2,13 +2,16 @@
 This code is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 21/06/2017
-Last Modified: 17/10/2018
+Last Modified: 17/10/2026
 License: MIT
 
-Notes: The logic behind working with the ESRI Shapefile format.
+Notes: The logic behind working with the ESRI Shapefile format. Files with a .geojson
+or .json extension are read and written as GeoJSON, and files with a .gpkg extension
+(optionally followed by ':layer') as GeoPackage layers, using the same in-memory model.
 */
 
 pub mod attributes;
+pub mod expression;
 pub mod geometry;
 
 use self::attributes::*;
@@ -79,11 +82,58 @@ m_max: {}",
     }
 }
 
+/// The vector file formats supported by `Shapefile::read` and `Shapefile::write`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum VectorFormat {
+    Unknown,
+    Shapefile,
+    GeoJson,
+    GeoPackage,
+}
+
+/// Determines the vector format of a file from its extension.
+pub fn get_vector_format_from_file(file_name: &str) -> VectorFormat {
+    let (file_name, _) = crate::geopackage::split_layer_name(file_name);
+    let extension = Path::new(&file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match extension.as_str() {
+        "shp" => VectorFormat::Shapefile,
+        "geojson" | "json" => VectorFormat::GeoJson,
+        "gpkg" => VectorFormat::GeoPackage,
+        _ => VectorFormat::Unknown,
+    }
+}
+
+/// Adds a suffix to the name of a vector file, keeping its extension, e.g. 'streams.geojson'
+/// and '_outlets' give 'streams_outlets.geojson'. For a 'file.gpkg:layer' name, the suffix is
+/// added to the layer name, giving another layer of the same GeoPackage.
+pub fn add_suffix_to_vector_file_name(file_name: &str, suffix: &str) -> String {
+    let (file_name, layer) = crate::geopackage::split_layer_name(file_name);
+    if let Some(layer) = layer {
+        return format!("{}:{}{}", file_name, layer, suffix);
+    }
+    let name_start = file_name.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    match file_name[name_start..].rfind('.') {
+        Some(i) => {
+            let (stem, extension) = file_name.split_at(name_start + i);
+            format!("{}{}{}", stem, suffix, extension)
+        }
+        None => format!("{}{}", file_name, suffix),
+    }
+}
+
 /// `Shapefile` is an in-memory ESRI Shapefile.
 ///
 /// Examples:
 ///
-/// ```
+/// ```no_run
+/// # use whitebox_vector::*;
+/// # fn main() -> std::io::Result<()> {
+/// # let input_file = "input.shp";
+/// # let output_file = "output.shp";
 /// // Read a Shapefile from a file.
 /// let input = Shapefile::read(&input_file)?;
 ///
@@ -96,6 +146,8 @@ m_max: {}",
 /// output.attributes.add_field(&fid);
 /// output.attributes.add_field(&val);
 ///
+/// # Ok(())
+/// # }
 /// ```
 #[derive(Default, Clone)]
 pub struct Shapefile {
@@ -115,7 +167,11 @@ impl Shapefile {
             file_mode: "r".to_string(),
             ..Default::default()
         };
-        sf.read_file()?;
+        match get_vector_format_from_file(file_name) {
+            VectorFormat::GeoJson => crate::geojson::read_geojson(&mut sf)?,
+            VectorFormat::GeoPackage => crate::geopackage::read_geopackage(&mut sf)?,
+            _ => sf.read_file()?,
+        }
         Ok(sf)
     }
 
@@ -194,12 +250,12 @@ impl Shapefile {
         &self.records[index]
     }
 
-    /// Adds a new ShapefileGeometry.
+    /// Adds a new ShapefileGeometry. Null records may be added to a file of any ShapeType.
     pub fn add_record(&mut self, geometry: ShapefileGeometry) {
         if self.file_mode == "r" {
             panic!("The file was opened in read-only mode.");
         }
-        if geometry.shape_type == self.header.shape_type {
+        if geometry.shape_type == self.header.shape_type || geometry.shape_type == ShapeType::Null {
             self.records.push(geometry);
             self.num_records += 1;
         } else {
@@ -675,7 +731,14 @@ impl Shapefile {
                     match self.attributes.fields[j as usize].field_type {
                         'N' | 'F' | 'I' | 'O' => {
                             if self.attributes.fields[j as usize].decimal_count == 0 {
-                                r.push(FieldData::Int(str_rep.parse::<i32>().unwrap_or(0)));
+                                match str_rep.parse::<i32>() {
+                                    Ok(v) => r.push(FieldData::Int(v)),
+                                    Err(_) => r.push(
+                                        str_rep
+                                            .parse::<i64>()
+                                            .map_or(FieldData::Int(0), FieldData::Int64),
+                                    ),
+                                }
                             } else {
                                 r.push(FieldData::Real(str_rep.parse::<f64>().unwrap_or(0f64)));
                             }
@@ -727,6 +790,18 @@ impl Shapefile {
             ));
         }
 
+        match get_vector_format_from_file(&self.file_name) {
+            VectorFormat::GeoJson => {
+                self.calculate_extent();
+                return crate::geojson::write_geojson(self);
+            }
+            VectorFormat::GeoPackage => {
+                self.calculate_extent();
+                return crate::geopackage::write_geopackage(self);
+            }
+            _ => {}
+        }
+
         /////////////////////////////////////////
         // Write the geometry data (.shp file) //
         /////////////////////////////////////////
@@ -1195,8 +1270,8 @@ impl Shapefile {
                         let spcs: String = vec![' '; fl].into_iter().collect();
                         writer.write_all(spcs.as_bytes())?;
                     }
-                    FieldData::Int(v) => {
-                        let b = v.to_string();
+                    FieldData::Int(_) | FieldData::Int64(_) => {
+                        let b = rec[j as usize].to_string();
                         if b.len() < fl {
                             let mut spcs: String = vec![' '; fl - b.len()].into_iter().collect();
                             spcs.push_str(&b);
@@ -1308,7 +1383,7 @@ impl Shapefile {
                 self.header.m_max = 0f64;
                 self.header.z_min = 0f64;
                 self.header.z_max = 0f64;
-                for sg in &self.records {
+                for sg in self.records.iter().filter(|r| r.shape_type != ShapeType::Null) {
                     if sg.points[0].x < self.header.x_min {
                         self.header.x_min = sg.points[0].x;
                     }
@@ -1333,7 +1408,7 @@ impl Shapefile {
                 self.header.m_max = 0f64;
                 self.header.z_min = 0f64;
                 self.header.z_max = 0f64;
-                for sg in &self.records {
+                for sg in self.records.iter().filter(|r| r.shape_type != ShapeType::Null) {
                     if sg.x_min < self.header.x_min {
                         self.header.x_min = sg.x_min;
                     }
@@ -1358,7 +1433,7 @@ impl Shapefile {
                 self.header.m_max = f64::NEG_INFINITY;
                 self.header.z_min = 0f64;
                 self.header.z_max = 0f64;
-                for sg in &self.records {
+                for sg in self.records.iter().filter(|r| r.shape_type != ShapeType::Null) {
                     if sg.points[0].x < self.header.x_min {
                         self.header.x_min = sg.points[0].x;
                     }
@@ -1390,7 +1465,7 @@ impl Shapefile {
                 self.header.z_min = 0f64;
                 self.header.z_max = 0f64;
 
-                for sg in &self.records {
+                for sg in self.records.iter().filter(|r| r.shape_type != ShapeType::Null) {
                     if sg.x_min < self.header.x_min {
                         self.header.x_min = sg.x_min;
                     }
@@ -1421,7 +1496,7 @@ impl Shapefile {
                 self.header.m_max = f64::NEG_INFINITY;
                 self.header.z_min = f64::INFINITY;
                 self.header.z_max = f64::NEG_INFINITY;
-                for sg in &self.records {
+                for sg in self.records.iter().filter(|r| r.shape_type != ShapeType::Null) {
                     if sg.points[0].x < self.header.x_min {
                         self.header.x_min = sg.points[0].x;
                     }
@@ -1459,7 +1534,7 @@ impl Shapefile {
                 self.header.z_min = f64::INFINITY;
                 self.header.z_max = f64::NEG_INFINITY;
 
-                for sg in &self.records {
+                for sg in self.records.iter().filter(|r| r.shape_type != ShapeType::Null) {
                     if sg.x_min < self.header.x_min {
                         self.header.x_min = sg.x_min;
                     }
@@ -1490,3 +1565,29 @@ impl Shapefile {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::add_suffix_to_vector_file_name;
+
+    #[test]
+    fn test_add_suffix_to_vector_file_name() {
+        assert_eq!(
+            add_suffix_to_vector_file_name("/data/streams.shp", "_outlets"),
+            "/data/streams_outlets.shp"
+        );
+        assert_eq!(
+            add_suffix_to_vector_file_name("/data.v2/streams.geojson", "_outlets"),
+            "/data.v2/streams_outlets.geojson"
+        );
+        assert_eq!(
+            add_suffix_to_vector_file_name("streams.gpkg:rivers", "_outlets"),
+            "streams.gpkg:rivers_outlets"
+        );
+        assert_eq!(
+            add_suffix_to_vector_file_name("streams.gpkg", "_outlets"),
+            "streams_outlets.gpkg"
+        );
+        assert_eq!(add_suffix_to_vector_file_name("streams", "_outlets"), "streams_outlets");
+    }
+}
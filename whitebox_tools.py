@@ -1068,6 +1068,64 @@ Okay, that's it for now.
         if use_diagonals: args.append("--use_diagonals")
         return self.run_tool('remove_raster_polygon_holes', args, callback) # returns 1 if error
 
+    def reproject_lidar(self, i, output, epsg, src_epsg=None, callback=None):
+        """Transforms a LiDAR file into a different coordinate reference system.
+
+        Keyword arguments:
+
+        i -- Input LiDAR file. 
+        output -- Output LiDAR file. 
+        epsg -- EPSG code of the output coordinate reference system. 
+        src_epsg -- Optional EPSG code of the input, overriding the input's own spatial reference. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--epsg='{}'".format(epsg))
+        if src_epsg is not None: args.append("--src_epsg='{}'".format(src_epsg))
+        return self.run_tool('reproject_lidar', args, callback) # returns 1 if error
+
+    def reproject_raster(self, i, output, epsg, src_epsg=None, method="cc", cell_size=None, callback=None):
+        """Transforms a raster into a different coordinate reference system.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output raster file. 
+        epsg -- EPSG code of the output coordinate reference system. 
+        src_epsg -- Optional EPSG code of the input, overriding the input's own spatial reference. 
+        method -- Resampling method; options include 'nn' (nearest neighbour), 'bilinear', and 'cc' (cubic convolution). 
+        cell_size -- Optionally specified cell size of the output raster, in the units of the target coordinate system. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--epsg='{}'".format(epsg))
+        if src_epsg is not None: args.append("--src_epsg='{}'".format(src_epsg))
+        args.append("--method={}".format(method))
+        if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
+        return self.run_tool('reproject_raster', args, callback) # returns 1 if error
+
+    def reproject_vector(self, i, output, epsg, src_epsg=None, callback=None):
+        """Transforms a vector into a different coordinate reference system.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        output -- Output vector file. 
+        epsg -- EPSG code of the output coordinate reference system. 
+        src_epsg -- Optional EPSG code of the input, overriding the input's own spatial reference. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--epsg='{}'".format(epsg))
+        if src_epsg is not None: args.append("--src_epsg='{}'".format(src_epsg))
+        return self.run_tool('reproject_vector', args, callback) # returns 1 if error
+
     def set_nodata_value(self, i, output, back_value=0.0, callback=None):
         """Assign the NoData value for an input image.
 
@@ -1648,6 +1706,28 @@ Okay, that's it for now.
         if clip: args.append("--clip")
         return self.run_tool('natural_neighbour_interpolation', args, callback) # returns 1 if error
 
+    def near_feature(self, i, near, output, max_dist=0.0, table=None, k=1, callback=None):
+        """Finds the nearest feature in one vector to each feature in another, with its distance and direction.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        near -- Input vector file containing the near features. 
+        output -- Output vector file. 
+        max_dist -- Maximum distance to a near feature (zero for no limit). 
+        table -- Optional output CSV file of the k nearest features to each input feature. 
+        k -- Number of nearest features to each input feature in the output table. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--near='{}'".format(near))
+        args.append("--output='{}'".format(output))
+        args.append("--max_dist={}".format(max_dist))
+        if table is not None: args.append("--table='{}'".format(table))
+        args.append("--k={}".format(k))
+        return self.run_tool('near_feature', args, callback) # returns 1 if error
+
     def nearest_neighbour_gridding(self, i, field, output, use_z=False, cell_size=None, base=None, max_dist=None, callback=None):
         """Creates a raster grid based on a set of vector points and assigns grid values using the nearest neighbour.
 
@@ -1866,6 +1946,44 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('reclass_from_file', args, callback) # returns 1 if error
 
+    def select_by_attribute(self, i, output, statement, callback=None):
+        """Extracts the features of a vector file whose attributes satisfy a logical expression.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        output -- Output vector file. 
+        statement -- Logical expression, e.g. CLASS = 2 AND NAME LIKE 'f%'. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--statement={}".format(statement))
+        return self.run_tool('select_by_attribute', args, callback) # returns 1 if error
+
+    def select_by_location(self, i, select, output, relation="intersects", dist=0.0, invert=False, callback=None):
+        """Extracts the features of a vector file that have a spatial relation with the features of another.
+
+        Keyword arguments:
+
+        i -- Input vector file, from which features are selected. 
+        select -- Input vector file containing the selecting features. 
+        output -- Output vector file. 
+        relation -- Spatial relation that an input feature must have with a selecting feature; options include 'intersects' (default), 'within', 'contains', 'touches', and 'within_distance'. 
+        dist -- Search distance for the 'within_distance' relation. 
+        invert -- Select the input features that do not have the relation with any selecting feature. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--select='{}'".format(select))
+        args.append("--output='{}'".format(output))
+        args.append("--relation={}".format(relation))
+        args.append("--dist={}".format(dist))
+        if invert: args.append("--invert")
+        return self.run_tool('select_by_location', args, callback) # returns 1 if error
+
     def smooth_vectors(self, i, output, filter=3, callback=None):
         """Smooths a vector coverage of either a POLYLINE or POLYGON base ShapeType.
 
@@ -1992,6 +2110,34 @@ Okay, that's it for now.
         if gridcells: args.append("--gridcells")
         return self.run_tool('buffer_raster', args, callback) # returns 1 if error
 
+    def buffer_vector(self, i, output, dist=10.0, cap_style="round", join_style="round", mitre_limit=5.0, segments=8, dissolve=False, snap=0.0, callback=None):
+        """Creates polygons covering the areas within a distance of vector points, lines, or polygons.
+
+        Keyword arguments:
+
+        i -- Input vector file.
+        output -- Output vector polygon file.
+        dist -- Buffer distance; negative distances shrink polygons.
+        cap_style -- Shape of the ends of buffered lines and of buffered points; options include 'round' (default), 'flat', and 'square'.
+        join_style -- Shape of the corners on the outside of bends; options include 'round' (default), 'mitre', and 'bevel'.
+        mitre_limit -- Furthest distance of a mitred corner from its vertex, as a multiple of the buffer distance.
+        segments -- Number of line segments used to approximate a quarter circle.
+        dissolve -- Optional flag to request the output polygons be dissolved.
+        snap -- Snap tolerance.
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--dist={}".format(dist))
+        args.append("--cap_style={}".format(cap_style))
+        args.append("--join_style={}".format(join_style))
+        args.append("--mitre_limit={}".format(mitre_limit))
+        args.append("--segments={}".format(segments))
+        if dissolve: args.append("--dissolve")
+        args.append("--snap={}".format(snap))
+        return self.run_tool('buffer_vector', args, callback) # returns 1 if error
+
     def cost_allocation(self, source, backlink, output, callback=None):
         """Identifies the source cell to which each grid cell is connected by a least-cost pathway in a cost-distance analysis.
 
@@ -2414,6 +2560,32 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('polygonize', args, callback) # returns 1 if error
 
+    def spatial_join(self, i, join, output, predicate="intersects", dist=0.0, join_operation="one_to_one", aggregate="first", fields=None, callback=None):
+        """Joins the attributes of the features in one vector to those of another, based on their spatial relation.
+
+        Keyword arguments:
+
+        i -- Input vector file, to which the join attributes are added. 
+        join -- Input vector file from which the attributes are joined. 
+        output -- Output vector file. 
+        predicate -- Spatial relation used to match join features to target features; options include 'intersects' (default), 'within', 'contains', 'nearest', and 'within_distance'. 
+        dist -- Search distance for the 'within_distance' predicate, or the maximum distance for the 'nearest' predicate (zero for no limit). 
+        join_operation -- Output one record per target feature ('one_to_one', default) or per matching pair of features ('one_to_many'). 
+        aggregate -- Method used to combine the attributes of multiple matching features in a one-to-one join; options include 'first' (default), 'count', 'sum', 'mean', 'min', and 'max'. 
+        fields -- Comma-separated list of the join fields to include (all fields other than FID if not specified). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--join='{}'".format(join))
+        args.append("--output='{}'".format(output))
+        args.append("--predicate={}".format(predicate))
+        args.append("--dist={}".format(dist))
+        args.append("--join_operation={}".format(join_operation))
+        args.append("--aggregate={}".format(aggregate))
+        if fields is not None: args.append("--fields='{}'".format(fields))
+        return self.run_tool('spatial_join', args, callback) # returns 1 if error
+
     def split_with_lines(self, i, split, output, callback=None):
         """Splits the lines or polygons in one layer using the lines in another layer.
 
@@ -5718,14 +5890,14 @@ Okay, that's it for now.
         args.append("--filtery={}".format(filtery))
         return self.run_tool('closing', args, callback) # returns 1 if error
 
-    def create_colour_composite(self, red, green, blue, output, opacity=None, enhance=True, zeros=False, callback=None):
+    def create_colour_composite(self, red, output, green=None, blue=None, opacity=None, enhance=True, zeros=False, callback=None):
         """Creates a colour-composite image from three bands of multispectral imagery.
 
         Keyword arguments:
 
-        red -- Input red band image file. 
-        green -- Input green band image file. 
-        blue -- Input blue band image file. 
+        red -- Input red band image file, or a multi-band image if green and blue are unspecified. 
+        green -- Input green band image file (optional). 
+        blue -- Input blue band image file (optional). 
         opacity -- Input opacity band image file (optional). 
         output -- Output colour composite file. 
         enhance -- Optional flag indicating whether a balance contrast enhancement is performed. 
@@ -5734,8 +5906,8 @@ Okay, that's it for now.
         """
         args = []
         args.append("--red='{}'".format(red))
-        args.append("--green='{}'".format(green))
-        args.append("--blue='{}'".format(blue))
+        if green is not None: args.append("--green='{}'".format(green))
+        if blue is not None: args.append("--blue='{}'".format(blue))
         if opacity is not None: args.append("--opacity='{}'".format(opacity))
         args.append("--output='{}'".format(output))
         if enhance: args.append("--enhance")